@@ -1,6 +1,7 @@
 use async_trait::async_trait;
+use serde::Serialize;
 
-use crate::domain::{DomainError, SymbolReference};
+use crate::domain::{DomainError, ReferenceKind, SymbolReference};
 
 /// Query options for call graph lookups.
 #[derive(Debug, Clone, Default)]
@@ -11,6 +12,13 @@ pub struct CallGraphQuery {
     pub language: Option<String>,
     /// Filter by reference kind
     pub reference_kind: Option<String>,
+    /// Only match references of these kinds; empty matches every kind.
+    /// Applies on top of `reference_kind`. Use `with_kinds()` to set it.
+    pub kinds: Vec<ReferenceKind>,
+    /// When true, skip references made from test files (test directories and
+    /// `*_test.*`, `test_*`, `*.test.*` or `*.spec.*` files). Use
+    /// `with_exclude_tests()` to set this flag.
+    pub exclude_tests: bool,
     /// Maximum number of results to return
     pub limit: Option<u32>,
     /// When true, `resolve_symbols` treats the short name as a regexp pattern
@@ -39,6 +47,17 @@ impl CallGraphQuery {
         self
     }
 
+    pub fn with_kinds(mut self, kinds: impl IntoIterator<Item = ReferenceKind>) -> Self {
+        self.kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Skip references whose file looks like a test file.
+    pub fn with_exclude_tests(mut self) -> Self {
+        self.exclude_tests = true;
+        self
+    }
+
     pub fn with_limit(mut self, limit: u32) -> Self {
         self.limit = Some(limit);
         self
@@ -64,6 +83,14 @@ pub trait CallGraphRepository: Send + Sync {
         query: &CallGraphQuery,
     ) -> Result<Vec<SymbolReference>, DomainError>;
 
+    /// Number of references [`Self::find_callers`] would return, ignoring
+    /// `query.limit`.
+    async fn count_callers(
+        &self,
+        callee_symbol: &str,
+        query: &CallGraphQuery,
+    ) -> Result<u64, DomainError>;
+
     /// Find all references where the given symbol is the caller (what does this symbol call?).
     async fn find_callees(
         &self,
@@ -96,6 +123,9 @@ pub trait CallGraphRepository: Send + Sync {
         repository_ids: &[String],
     ) -> Result<Vec<SymbolReference>, DomainError>;
 
+    /// Find every reference in the namespace, across all repositories.
+    async fn find_all(&self) -> Result<Vec<SymbolReference>, DomainError>;
+
     /// Delete all references for a specific file within a repository.
     async fn delete_by_file_path(
         &self,
@@ -103,12 +133,41 @@ pub trait CallGraphRepository: Send + Sync {
         file_path: &str,
     ) -> Result<u64, DomainError>;
 
+    /// Rewrite `old` to `new` wherever it appears as the caller or callee of a
+    /// reference matching `query`'s filters (`limit` is ignored), in one
+    /// transaction. Returns the number of references changed.
+    async fn rename_symbol(
+        &self,
+        old: &str,
+        new: &str,
+        query: &CallGraphQuery,
+    ) -> Result<u64, DomainError>;
+
     /// Delete all references for a repository.
     async fn delete_by_repository(&self, repository_id: &str) -> Result<(), DomainError>;
 
     /// Get statistics about the call graph for a repository.
     async fn get_stats(&self, repository_id: &str) -> Result<CallGraphStats, DomainError>;
 
+    /// The `limit` callee symbols with the most references matching `query`'s
+    /// filters (fan-in), most referenced first and ties by name. Symbols with
+    /// fewer than `min_count` references are skipped; `query.limit` is ignored.
+    async fn top_by_fanin(
+        &self,
+        query: &CallGraphQuery,
+        limit: u32,
+        min_count: u64,
+    ) -> Result<Vec<SymbolFanCount>, DomainError>;
+
+    /// Like [`Self::top_by_fanin`], for the caller symbols making the most
+    /// references (fan-out). Anonymous callers are not counted.
+    async fn top_by_fanout(
+        &self,
+        query: &CallGraphQuery,
+        limit: u32,
+        min_count: u64,
+    ) -> Result<Vec<SymbolFanCount>, DomainError>;
+
     /// Find symbols that reference a given symbol across all repositories.
     /// Useful for cross-project analysis.
     async fn find_cross_repo_references(
@@ -132,8 +191,16 @@ pub trait CallGraphRepository: Send + Sync {
     ) -> Result<Vec<String>, DomainError>;
 }
 
+/// A symbol and its number of references in one direction, from
+/// [`CallGraphRepository::top_by_fanin`] or [`CallGraphRepository::top_by_fanout`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolFanCount {
+    pub symbol: String,
+    pub count: u64,
+}
+
 /// Statistics about the call graph for a repository.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CallGraphStats {
     /// Total number of symbol references
     pub total_references: u64,
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::domain::DomainError;
+
+/// Vectors computed for chunk content, keyed by [`CodeChunk::embedding_key`]
+/// and model name, so re-indexing unchanged chunks skips the embedder.
+///
+/// [`CodeChunk::embedding_key`]: crate::domain::CodeChunk::embedding_key
+#[async_trait]
+pub trait EmbeddingCache: Send + Sync {
+    /// Cached vectors for those of `keys` stored under `model`, by key.
+    async fn get_many(
+        &self,
+        model: &str,
+        keys: &[&str],
+    ) -> Result<HashMap<String, Vec<f32>>, DomainError>;
+
+    /// Store `(key, vector)` pairs under `model`, replacing existing entries.
+    async fn put_many(
+        &self,
+        model: &str,
+        entries: &[(String, Vec<f32>)],
+    ) -> Result<(), DomainError>;
+
+    /// Remove every cached vector. Returns how many were removed.
+    async fn clear(&self) -> Result<u64, DomainError>;
+}
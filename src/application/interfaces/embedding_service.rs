@@ -20,4 +20,10 @@ pub trait EmbeddingService: Send + Sync {
     }
 
     fn config(&self) -> &EmbeddingConfig;
+
+    /// Width of the vectors this service produces.  The vector repository
+    /// sizes a namespace's `FLOAT[N]` column from this before the first save.
+    fn dimensions(&self) -> usize {
+        self.config().dimensions()
+    }
 }
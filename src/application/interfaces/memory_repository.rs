@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use serde::Serialize;
 
 use crate::domain::{
     DomainError, DreamRun, ImportedSession, MemoryItem, MemoryKind, MemoryNode, NodeKind,
@@ -140,7 +141,7 @@ pub trait MemoryRepository: Send + Sync {
 }
 
 /// Statistics about the memory store.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct MemoryStats {
     /// Total memory items across all kinds.
     pub total_items: u64,
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 
-use crate::domain::{DomainError, LanguageStats, Repository};
+use crate::domain::{DomainError, IndexFilters, LanguageStats, Repository};
 
 /// Persistence for repository metadata.
 #[async_trait]
@@ -36,4 +36,22 @@ pub trait MetadataRepository: Send + Sync {
         id: &str,
         git_remote: Option<&str>,
     ) -> Result<(), DomainError>;
+
+    /// Record the git commit a repository's index was last synced to.
+    async fn update_indexed_commit(
+        &self,
+        id: &str,
+        indexed_commit: Option<&str>,
+    ) -> Result<(), DomainError>;
+
+    /// Replace the stored root directories (relative to the repository path)
+    /// of a multi-root repository; empty for a single root.
+    async fn update_roots(&self, id: &str, roots: &[String]) -> Result<(), DomainError>;
+
+    /// Replace the stored include/exclude globs for a repository.
+    async fn update_index_filters(
+        &self,
+        id: &str,
+        index_filters: &IndexFilters,
+    ) -> Result<(), DomainError>;
 }
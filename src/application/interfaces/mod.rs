@@ -4,6 +4,7 @@ mod channel_endpoint_repository;
 mod channel_extractor;
 mod channel_resolver;
 mod chat_client;
+mod embedding_cache;
 mod embedding_service;
 mod file_hash_repository;
 mod memory_repository;
@@ -20,6 +21,7 @@ pub use channel_endpoint_repository::*;
 pub use channel_extractor::*;
 pub use channel_resolver::*;
 pub use chat_client::*;
+pub use embedding_cache::*;
 pub use embedding_service::*;
 pub use file_hash_repository::*;
 pub use memory_repository::*;
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::domain::{CodeChunk, DomainError, Language};
+use crate::domain::{CodeChunk, DomainError, Language, SymbolReference};
 
 /// Parses source code into semantic chunks.
 #[async_trait]
@@ -14,6 +14,20 @@ pub trait ParserService: Send + Sync {
         repository_id: &str,
     ) -> Result<Vec<CodeChunk>, DomainError>;
 
+    /// Extract call-graph references directly from the source.  These are a
+    /// fallback: SCIP references take precedence whenever both exist for a
+    /// file.  The default returns none.
+    async fn extract_references(
+        &self,
+        content: &str,
+        file_path: &str,
+        language: Language,
+        repository_id: &str,
+    ) -> Result<Vec<SymbolReference>, DomainError> {
+        let _ = (content, file_path, language, repository_id);
+        Ok(vec![])
+    }
+
     fn supported_languages(&self) -> Vec<Language>;
 
     fn supports_language(&self, language: Language) -> bool {
@@ -1,6 +1,54 @@
+use std::collections::{BTreeMap, HashMap};
+
 use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::domain::{CodeChunk, DomainError, Embedding, Language, SearchQuery, SearchResult};
+
+/// State of an auxiliary search index (full-text or vector).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexState {
+    Ok,
+    Missing,
+    /// Present but out of step with the chunks it indexes.
+    Stale,
+}
 
-use crate::domain::{CodeChunk, DomainError, Embedding, SearchQuery, SearchResult};
+/// Consistency facts about a vector store, gathered by
+/// [`VectorRepository::integrity`] for `codesearch health`.
+#[derive(Debug, Clone)]
+pub struct StorageIntegrity {
+    pub chunk_count: u64,
+    pub embedding_count: u64,
+    /// Chunks with no embedding row.
+    pub chunks_without_embedding: u64,
+    /// Embedding rows whose `chunk_id` matches no chunk.
+    pub orphan_embeddings: u64,
+    /// The BM25 full-text index over chunk content.
+    pub text_index: IndexState,
+    /// The approximate-nearest-neighbour index over embeddings.
+    pub vector_index: IndexState,
+    /// Stored chunks per repository id.
+    pub chunks_per_repository: HashMap<String, u64>,
+}
+
+/// Chunk counts of one repository, as aggregated by
+/// [`VectorRepository::stats_by_repository`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RepositoryChunkStats {
+    /// Chunks per node type (`function`, `class`, ...).
+    pub by_node_type: BTreeMap<String, u64>,
+    /// Chunks per language.
+    pub by_language: BTreeMap<String, u64>,
+}
+
+/// One indexed file, as listed by [`VectorRepository::list_files`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileEntry {
+    pub file_path: String,
+    pub chunk_count: u64,
+    pub language: Language,
+}
 
 /// Vector storage and similarity search operations.
 #[async_trait]
@@ -18,6 +66,20 @@ pub trait VectorRepository: Send + Sync {
 
     async fn delete(&self, chunk_id: &str) -> Result<(), DomainError>;
 
+    /// Delete a batch of chunks (and their embeddings) by id in a single
+    /// operation. Returns the number of chunks deleted; unknown ids are
+    /// ignored.
+    ///
+    /// The default implementation calls [`delete`] for each id and counts
+    /// every id as deleted. Adapters should override this with a
+    /// single-transaction batch delete.
+    async fn bulk_delete(&self, chunk_ids: &[&str]) -> Result<u64, DomainError> {
+        for chunk_id in chunk_ids {
+            self.delete(chunk_id).await?;
+        }
+        Ok(chunk_ids.len() as u64)
+    }
+
     async fn delete_by_repository(&self, repository_id: &str) -> Result<(), DomainError>;
 
     /// Delete all chunks for a specific file path within a repository.
@@ -53,6 +115,10 @@ pub trait VectorRepository: Send + Sync {
     /// `query_embedding: None` requests a text-only search: the semantic leg
     /// is skipped regardless of `is_text_search()`.  Used when the store
     /// carries no embeddings (see [`has_embeddings`]).
+    ///
+    /// For a paginated query (`query.is_paginated()`), semantic results are
+    /// ordered by descending score then ascending chunk id, and only those
+    /// after `query.cursor()` are returned (keyset pagination).
     async fn search(
         &self,
         query_embedding: Option<&[f32]>,
@@ -67,6 +133,18 @@ pub trait VectorRepository: Send + Sync {
         Ok(true)
     }
 
+    /// Return the stored embedding of every chunk in `ids` that has one, in
+    /// no particular order.  Ids without a vector (unknown chunks, or a
+    /// no-embeddings store) are simply absent from the result.
+    ///
+    /// Used by diversity re-ranking to compare results with each other after
+    /// the initial search.  The default returns nothing, which makes every
+    /// pair of results look unrelated.
+    async fn fetch_embeddings(&self, ids: &[&str]) -> Result<Vec<Embedding>, DomainError> {
+        let _ = ids;
+        Ok(vec![])
+    }
+
     async fn count(&self) -> Result<u64, DomainError>;
 
     /// Return all chunks stored for a given file path within a repository.
@@ -124,6 +202,16 @@ pub trait VectorRepository: Send + Sync {
         Ok(chunks)
     }
 
+    /// Definition lookup without embeddings: chunks whose `symbol_name` is
+    /// exactly `query.query()` (score 1.0) or starts with it (score 0.5),
+    /// exact matches first, honouring the query's filters and limit.
+    ///
+    /// The default returns nothing, for adapters without symbol lookup.
+    async fn find_by_symbol(&self, query: &SearchQuery) -> Result<Vec<SearchResult>, DomainError> {
+        let _ = query;
+        Ok(vec![])
+    }
+
     /// Return `(symbol_name, file_path)` pairs for every chunk in `repository_id`
     /// that has a non-null `symbol_name`.
     ///
@@ -139,10 +227,55 @@ pub trait VectorRepository: Send + Sync {
         Ok(vec![])
     }
 
+    /// Every file stored for `repository_id` with its chunk count, sorted by
+    /// path. A file whose chunks carry two languages is listed once per
+    /// language.
+    ///
+    /// The default returns nothing, for adapters without file listing.
+    async fn list_files(&self, repository_id: &str) -> Result<Vec<FileEntry>, DomainError> {
+        let _ = repository_id;
+        Ok(vec![])
+    }
+
+    /// Chunk counts per node type and per language for every repository in
+    /// the store, keyed by repository id.
+    ///
+    /// The default returns nothing, for adapters without aggregation.
+    async fn stats_by_repository(
+        &self,
+    ) -> Result<HashMap<String, RepositoryChunkStats>, DomainError> {
+        Ok(HashMap::new())
+    }
+
+    /// Chunk counts per language, for one repository or (`None`) every
+    /// repository in the store.
+    ///
+    /// The default returns nothing, for adapters without aggregation.
+    async fn count_by_language(
+        &self,
+        repository_id: Option<&str>,
+    ) -> Result<HashMap<Language, u64>, DomainError> {
+        let _ = repository_id;
+        Ok(HashMap::new())
+    }
+
+    /// Check the store for missing or dangling rows and broken auxiliary
+    /// indexes.  The default returns `None`: there is nothing persistent to
+    /// check (e.g. the in-memory adapter).
+    async fn integrity(&self) -> Result<Option<StorageIntegrity>, DomainError> {
+        Ok(None)
+    }
+
     /// Called once after a batch of writes to finalise any deferred work
     /// (e.g. rebuilding a full-text search index). The default implementation
     /// is a no-op; backends that maintain auxiliary indexes should override it.
     async fn flush(&self) -> Result<(), DomainError> {
         Ok(())
     }
+
+    /// Reclaim storage left behind by deleted rows. The default is a no-op,
+    /// for backends that reclaim space themselves.
+    async fn compact(&self) -> Result<(), DomainError> {
+        Ok(())
+    }
 }
@@ -0,0 +1,26 @@
+//! Names of the Prometheus metrics recorded through the `metrics` facade.
+//!
+//! Recording is a no-op until a recorder is installed; the MCP HTTP server
+//! installs one and serves it at `/metrics` (see
+//! `connector::api::metrics`).
+
+/// Counter: hybrid/semantic searches run.
+pub const SEARCH_REQUESTS_TOTAL: &str = "codesearch_search_requests_total";
+
+/// Histogram: end-to-end search latency, including embedding and reranking.
+pub const SEARCH_DURATION_SECONDS: &str = "codesearch_search_duration_seconds";
+
+/// Counter: indexing runs started (full or incremental).
+pub const INDEX_REQUESTS_TOTAL: &str = "codesearch_index_requests_total";
+
+/// Counter: chunks written to the vector store by indexing.
+pub const CHUNKS_INDEXED_TOTAL: &str = "codesearch_chunks_indexed_total";
+
+/// Histogram: embedding latency, per query or per indexing batch.
+pub const EMBEDDING_DURATION_SECONDS: &str = "codesearch_embedding_duration_seconds";
+
+/// Histogram: cross-encoder reranking latency.
+pub const RERANK_DURATION_SECONDS: &str = "codesearch_rerank_duration_seconds";
+
+/// Gauge: chunks stored per namespace (label `namespace`), refreshed on scrape.
+pub const DB_CHUNK_COUNT: &str = "codesearch_db_chunk_count";
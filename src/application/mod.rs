@@ -1,5 +1,6 @@
 pub mod git_remote;
 pub mod interfaces;
+pub mod metrics;
 pub mod use_cases;
 
 pub use interfaces::*;
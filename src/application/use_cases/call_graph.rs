@@ -1,21 +1,158 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
 use anyhow::Context;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 
-use crate::application::{CallGraphQuery, CallGraphRepository, CallGraphStats};
+use crate::application::use_cases::pattern_utils::{class_hint_from_symbol, short_symbol_name};
+use crate::application::{
+    CallGraphQuery, CallGraphRepository, CallGraphStats, GraphExport, ImpactEdge, ImpactNode,
+    VectorRepository, ANONYMOUS_SYMBOL,
+};
 use crate::domain::{DomainError, SymbolReference};
 
+/// Default hop limit for [`CallGraphUseCase::find_paths`].
+pub const DEFAULT_PATH_MAX_DEPTH: usize = 6;
+
+/// Default cap on the number of shortest paths returned by
+/// [`CallGraphUseCase::find_paths`].
+pub const DEFAULT_PATH_MAX_PATHS: usize = 10;
+
+/// Entry points [`CallGraphUseCase::find_unreachable_symbols`] callers pass
+/// as roots by default: they run without a caller in the indexed code.
+pub const DEFAULT_ENTRY_POINTS: &[&str] = &["main"];
+
+/// Maximum number of fully-qualified symbols a path endpoint may resolve to.
+/// Keeps an ambiguous short name from seeding the BFS with half the graph.
+const PATH_RESOLVE_LIMIT: u32 = 10;
+
+/// One caller → callee edge along a call path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallPathHop {
+    /// Symbol making the call.
+    pub caller: String,
+    /// Symbol being called.
+    pub callee: String,
+    /// File where the reference occurs.
+    pub file_path: String,
+    /// Line number of the reference in `file_path`.
+    pub line: u32,
+    /// Kind of reference relationship (e.g. "call", "type_reference").
+    pub reference_kind: String,
+    /// Repository that contains the reference.
+    pub repository_id: String,
+}
+
+impl CallPathHop {
+    fn from_reference(reference: &SymbolReference) -> Self {
+        Self {
+            caller: reference.caller_symbol().unwrap_or_default().to_string(),
+            callee: reference.callee_symbol().to_string(),
+            file_path: reference.reference_file_path().to_string(),
+            line: reference.reference_line(),
+            reference_kind: reference.reference_kind().to_string(),
+            repository_id: reference.repository_id().to_string(),
+        }
+    }
+}
+
+/// A single call chain from the source symbol to the target symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallPath {
+    /// Edges in call order: `hops[0].caller` is the source symbol and
+    /// `hops.last().callee` is the target.
+    pub hops: Vec<CallPathHop>,
+}
+
+impl CallPath {
+    /// Symbols along the chain, source first and target last.
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self
+            .hops
+            .first()
+            .map(|h| h.caller.clone())
+            .into_iter()
+            .collect();
+        symbols.extend(self.hops.iter().map(|h| h.callee.clone()));
+        symbols
+    }
+}
+
+/// Result of a shortest-path search between two symbols.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallPathReport {
+    /// The source symbol as requested.
+    pub from: String,
+    /// The target symbol as requested.
+    pub to: String,
+    /// Hop limit the search ran with.
+    pub max_depth: usize,
+    /// Every shortest path found, capped at the requested count. Empty when
+    /// the target is unreachable within `max_depth` hops.
+    pub paths: Vec<CallPath>,
+    /// `true` when more shortest paths exist than were returned.
+    pub truncated: bool,
+}
+
+/// A strongly-connected component of the call graph, reported as a cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallCycle {
+    /// Member symbols, starting from the alphabetically first and following
+    /// call edges, so a simple loop (`A → B → A`) reads in call order.
+    pub cycle: Vec<String>,
+    /// Files holding the calls between members, sorted.
+    pub files: Vec<String>,
+}
+
+/// A symbol ranked by `hotspots`, with both of its reference counts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hotspot {
+    pub symbol: String,
+    /// References to the symbol.
+    pub fan_in: u64,
+    /// References made from the symbol.
+    pub fan_out: u64,
+    /// Defining chunk's file and start line, when the chunk store knows it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+}
+
+/// The most referenced and most referencing symbols of the call graph.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HotspotReport {
+    /// Highest fan-in first.
+    pub by_fan_in: Vec<Hotspot>,
+    /// Highest fan-out first.
+    pub by_fan_out: Vec<Hotspot>,
+}
+
 /// Use case for managing call graph (symbol references).
 /// Provides a decoupled interface for saving, querying, and deleting
 /// symbol references populated by the SCIP indexing phase.
 pub struct CallGraphUseCase {
     repository: Arc<dyn CallGraphRepository>,
+    /// Chunk store listing the symbols a repository defines; needed by
+    /// [`find_unreachable_symbols`](Self::find_unreachable_symbols) and used
+    /// by [`find_hotspots`](Self::find_hotspots) to locate definitions.
+    vector_repo: Option<Arc<dyn VectorRepository>>,
 }
 
 impl CallGraphUseCase {
     pub fn new(repository: Arc<dyn CallGraphRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            vector_repo: None,
+        }
+    }
+
+    /// Attach the chunk store so definitions can be enumerated for dead-code
+    /// detection.
+    pub fn with_vector_repo(mut self, vector_repo: Arc<dyn VectorRepository>) -> Self {
+        self.vector_repo = Some(vector_repo);
+        self
     }
 
     /// Persist a slice of pre-extracted [`SymbolReference`]s produced by the
@@ -73,6 +210,50 @@ impl CallGraphUseCase {
         self.repository.delete_by_repository(repository_id).await
     }
 
+    /// Point every reference to or from `old` at `new` instead, so the graph
+    /// follows a rename without re-indexing. Stored chunks keep the old name
+    /// until the next index run. Returns the number of references changed.
+    pub async fn rename_symbol(
+        &self,
+        old: &str,
+        new: &str,
+        query: &CallGraphQuery,
+    ) -> Result<u64, DomainError> {
+        if new.trim().is_empty() {
+            return Err(DomainError::invalid_input("New symbol name is empty"));
+        }
+        if old == new {
+            return Ok(0);
+        }
+        self.repository.rename_symbol(old, new, query).await
+    }
+
+    /// References [`Self::rename_symbol`] would change: those with `symbol` as
+    /// their exact caller or callee (import aliases are left alone).
+    pub async fn find_symbol_references(
+        &self,
+        symbol: &str,
+        query: &CallGraphQuery,
+    ) -> Result<Vec<SymbolReference>, DomainError> {
+        let mut references: Vec<SymbolReference> = self
+            .repository
+            .find_callers(symbol, query)
+            .await?
+            .into_iter()
+            .filter(|r| r.callee_symbol() == symbol)
+            .collect();
+        for reference in self.repository.find_callees(symbol, query).await? {
+            if !references.iter().any(|r| r.id() == reference.id()) {
+                references.push(reference);
+            }
+        }
+        references.sort_by(|a, b| {
+            (a.reference_file_path(), a.reference_line())
+                .cmp(&(b.reference_file_path(), b.reference_line()))
+        });
+        Ok(references)
+    }
+
     /// Find all references where the given symbol is the callee (what calls this symbol?).
     pub async fn find_callers(
         &self,
@@ -82,6 +263,15 @@ impl CallGraphUseCase {
         self.repository.find_callers(callee_symbol, query).await
     }
 
+    /// Number of references to the given symbol (how often is it called?).
+    pub async fn count_callers(
+        &self,
+        callee_symbol: &str,
+        query: &CallGraphQuery,
+    ) -> Result<u64, DomainError> {
+        self.repository.count_callers(callee_symbol, query).await
+    }
+
     /// Find all references where the given symbol is the caller (what does this symbol call?).
     pub async fn find_callees(
         &self,
@@ -150,4 +340,732 @@ impl CallGraphUseCase {
             .resolve_symbols(short_name, query, resolve_limit)
             .await
     }
+
+    /// Find the shortest call chains from `from` to `to`.
+    ///
+    /// Runs a layered BFS over callee edges starting at every symbol `from`
+    /// resolves to and stops at the first layer that reaches a symbol `to`
+    /// resolves to, or after `max_depth` hops. A symbol is expanded only at the
+    /// depth it was first reached, so cycles in the call graph cannot loop the
+    /// search. All shortest paths are enumerated, capped at `max_paths`.
+    pub async fn find_paths(
+        &self,
+        from: &str,
+        to: &str,
+        max_depth: usize,
+        max_paths: usize,
+        query: &CallGraphQuery,
+    ) -> Result<CallPathReport, DomainError> {
+        let seeds = self.resolve_path_endpoint(from, query).await?;
+        let targets: HashSet<String> = self
+            .resolve_path_endpoint(to, query)
+            .await?
+            .into_iter()
+            .collect();
+
+        // depth_of: first BFS layer each symbol was reached at (seeds = 0).
+        // parents: incoming edges from the layer directly above, one per caller.
+        let mut depth_of: HashMap<String, usize> = seeds.iter().map(|s| (s.clone(), 0)).collect();
+        let mut parents: HashMap<String, Vec<SymbolReference>> = HashMap::new();
+        let mut frontier: Vec<String> = seeds;
+        let mut reached: Vec<String> = Vec::new();
+
+        for depth in 1..=max_depth {
+            if frontier.is_empty() || !reached.is_empty() {
+                break;
+            }
+            let mut next = Vec::new();
+            for symbol in &frontier {
+                for reference in self.repository.find_callees(symbol, query).await? {
+                    let callee = reference.callee_symbol().to_string();
+                    match depth_of.get(&callee) {
+                        // Reached on an earlier layer: a back edge or a longer route.
+                        Some(&d) if d < depth => continue,
+                        Some(_) => {}
+                        None => {
+                            depth_of.insert(callee.clone(), depth);
+                            if targets.contains(&callee) {
+                                reached.push(callee.clone());
+                            }
+                            next.push(callee.clone());
+                        }
+                    }
+                    let incoming = parents.entry(callee).or_default();
+                    if !incoming
+                        .iter()
+                        .any(|r| r.caller_symbol() == reference.caller_symbol())
+                    {
+                        incoming.push(reference);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        // Collect one more than the cap so truncation can be reported.
+        reached.sort();
+        let mut paths: Vec<CallPath> = Vec::new();
+        for target in &reached {
+            let mut suffix: Vec<&SymbolReference> = Vec::new();
+            collect_paths(
+                target,
+                &parents,
+                &mut suffix,
+                &mut paths,
+                max_paths.saturating_add(1),
+            );
+        }
+        let truncated = paths.len() > max_paths;
+        paths.truncate(max_paths);
+
+        debug!(
+            from,
+            to,
+            found = paths.len(),
+            truncated,
+            "find_paths: search complete"
+        );
+
+        Ok(CallPathReport {
+            from: from.to_string(),
+            to: to.to_string(),
+            max_depth,
+            paths,
+            truncated,
+        })
+    }
+
+    /// One shortest call chain from `from` to `to` as symbol names (both
+    /// ends included), searching until the graph is exhausted rather than up
+    /// to a hop limit. `None` when `to` is unreachable.
+    pub async fn shortest_path(
+        &self,
+        from: &str,
+        to: &str,
+        query: &CallGraphQuery,
+    ) -> Result<Option<Vec<String>>, DomainError> {
+        let report = self.find_paths(from, to, usize::MAX, 1, query).await?;
+        Ok(report.paths.first().map(CallPath::symbols))
+    }
+
+    /// Collect the callers of `root`, up to `depth` hops, for rendering as a
+    /// graph (see [`GraphExport::dot`] and [`GraphExport::mermaid`]).
+    ///
+    /// `root` resolves like a [`find_paths`](Self::find_paths) endpoint. Each
+    /// caller appears once, at the depth it was first reached; every distinct
+    /// edge walked is kept, so cycles show up as back edges. Anonymous
+    /// (module-level) callers are kept per file but not expanded further.
+    pub async fn export_graph(
+        &self,
+        root: &str,
+        depth: usize,
+        query: &CallGraphQuery,
+    ) -> Result<GraphExport, DomainError> {
+        let root_symbols = self.resolve_path_endpoint(root, query).await?;
+        let mut visited: HashSet<String> = root_symbols.iter().cloned().collect();
+        let mut seen_edges: HashSet<(String, String, String)> = HashSet::new();
+        let mut nodes: Vec<ImpactNode> = Vec::new();
+        let mut edges: Vec<ImpactEdge> = Vec::new();
+        let mut frontier = root_symbols.clone();
+
+        for level in 1..=depth {
+            if frontier.is_empty() {
+                break;
+            }
+            let mut next = Vec::new();
+            for callee in &frontier {
+                for reference in self.repository.find_callers(callee, query).await? {
+                    let caller = reference.caller_symbol().unwrap_or(ANONYMOUS_SYMBOL);
+                    let file_path = reference.reference_file_path();
+                    let kind = reference.reference_kind().to_string();
+                    let key = match reference.caller_symbol() {
+                        Some(symbol) => symbol.to_string(),
+                        None => format!("{}@{}", ANONYMOUS_SYMBOL, file_path),
+                    };
+
+                    if seen_edges.insert((key.clone(), callee.clone(), kind.clone())) {
+                        edges.push(ImpactEdge {
+                            caller: caller.to_string(),
+                            callee: callee.clone(),
+                            reference_kind: kind.clone(),
+                            file_path: file_path.to_string(),
+                            line: reference.reference_line(),
+                        });
+                    }
+                    if !visited.insert(key) {
+                        continue;
+                    }
+                    nodes.push(ImpactNode {
+                        symbol: caller.to_string(),
+                        depth: level,
+                        file_path: file_path.to_string(),
+                        line: reference.reference_line(),
+                        reference_kind: kind,
+                        repository_id: reference.repository_id().to_string(),
+                        import_alias: reference.import_alias().map(str::to_string),
+                        via_symbol: Some(callee.clone()),
+                        call_count: 0,
+                        path: Vec::new(),
+                    });
+                    if reference.caller_symbol().is_some() {
+                        next.push(caller.to_string());
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        debug!(
+            root,
+            nodes = nodes.len(),
+            edges = edges.len(),
+            "export_graph: traversal complete"
+        );
+
+        Ok(GraphExport {
+            root: root.to_string(),
+            root_symbols,
+            nodes,
+            edges,
+        })
+    }
+
+    /// Find circular call chains (`A → B → C → A`) within a repository.
+    ///
+    /// Returns every strongly-connected component of the call graph with more
+    /// than one symbol, each sorted by name; components are ordered largest
+    /// first. Direct self-recursion is not reported.
+    pub async fn find_cycles(&self, repository_id: &str) -> Result<Vec<Vec<String>>, DomainError> {
+        let references = self.repository.find_by_repository(repository_id).await?;
+        Ok(strongly_connected_cycles(&references))
+    }
+
+    /// [`find_cycles`](Self::find_cycles) over several repositories at once,
+    /// so cycles that cross a repository boundary are found too.
+    pub async fn find_cycles_in_repositories(
+        &self,
+        repository_ids: &[String],
+    ) -> Result<Vec<Vec<String>>, DomainError> {
+        let references = self.repository.find_by_repositories(repository_ids).await?;
+        Ok(strongly_connected_cycles(&references))
+    }
+
+    /// Strongly-connected components of the call graph in
+    /// `query.repository_id`, or of the whole namespace when no repository is
+    /// given, restricted to references matching `query.language` and
+    /// `query.reference_kind`.
+    ///
+    /// Unlike [`find_cycles`](Self::find_cycles), a single symbol that calls
+    /// itself is reported as a component of its own. Members are sorted by
+    /// name and components ordered largest first.
+    pub async fn find_strongly_connected_components(
+        &self,
+        query: &CallGraphQuery,
+    ) -> Result<Vec<Vec<String>>, DomainError> {
+        let mut references = match &query.repository_id {
+            Some(repository_id) => self.repository.find_by_repository(repository_id).await?,
+            None => self.repository.find_all().await?,
+        };
+        references.retain(|r| matches_query(r, query));
+        let graph = CallAdjacency::new(&references);
+        let mut components: Vec<Vec<String>> = graph
+            .components()
+            .iter()
+            .filter(|c| graph.is_cycle(c))
+            .map(|c| graph.sorted_names(c))
+            .collect();
+        components.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        Ok(components)
+    }
+
+    /// Cycles across `repository_ids`, loaded together so loops that cross a
+    /// repository boundary are found, with at least `min_size` symbols (a
+    /// self-recursive symbol has size 1). Each cycle lists its members in call
+    /// order and the files holding the calls between them.
+    pub async fn find_call_cycles(
+        &self,
+        repository_ids: &[String],
+        query: &CallGraphQuery,
+        min_size: usize,
+    ) -> Result<Vec<CallCycle>, DomainError> {
+        let references = self.filtered_references(repository_ids, query).await?;
+        Ok(call_cycles(&references, min_size))
+    }
+
+    /// The `top` symbols with the most references to them (fan-in) and from
+    /// them (fan-out) under `query`'s filters. The fan-in ranking skips
+    /// symbols referenced fewer than `min_fan_in` times. Each symbol is placed
+    /// at its definition when a chunk store is attached and knows it.
+    pub async fn find_hotspots(
+        &self,
+        query: &CallGraphQuery,
+        top: u32,
+        min_fan_in: u64,
+    ) -> Result<HotspotReport, DomainError> {
+        let mut report = HotspotReport::default();
+        for ranked in self.repository.top_by_fanin(query, top, min_fan_in).await? {
+            let fan_out = self
+                .repository
+                .find_callees(&ranked.symbol, query)
+                .await?
+                .len() as u64;
+            report.by_fan_in.push(
+                self.hotspot(ranked.symbol, ranked.count, fan_out, query)
+                    .await?,
+            );
+        }
+        for ranked in self.repository.top_by_fanout(query, top, 1).await? {
+            let fan_in = self.repository.count_callers(&ranked.symbol, query).await?;
+            report.by_fan_out.push(
+                self.hotspot(ranked.symbol, fan_in, ranked.count, query)
+                    .await?,
+            );
+        }
+        Ok(report)
+    }
+
+    async fn hotspot(
+        &self,
+        symbol: String,
+        fan_in: u64,
+        fan_out: u64,
+        query: &CallGraphQuery,
+    ) -> Result<Hotspot, DomainError> {
+        let chunk = match &self.vector_repo {
+            Some(vector_repo) => {
+                vector_repo
+                    .find_chunk_by_symbol(
+                        query.repository_id.as_deref().unwrap_or_default(),
+                        short_symbol_name(&symbol),
+                        class_hint_from_symbol(&symbol),
+                    )
+                    .await?
+            }
+            None => None,
+        };
+        Ok(Hotspot {
+            file_path: chunk.as_ref().map(|c| c.file_path().to_string()),
+            line: chunk.as_ref().map(|c| c.start_line()),
+            symbol,
+            fan_in,
+            fan_out,
+        })
+    }
+
+    /// Functions and methods in `query.repository_id` that nothing calls.
+    ///
+    /// Every callable chunk with a symbol name is a definition; it is reported
+    /// when neither its name nor any fully-qualified symbol it resolves to has
+    /// a caller other than itself (so purely self-recursive functions count
+    /// as unreachable). Symbols named in `roots` are entry points and never
+    /// reported. `query.language` restricts the definitions considered.
+    ///
+    /// Each node carries the definition's file and start line, `depth` 0 and
+    /// its node type as `reference_kind`; nodes are sorted by file and line.
+    /// Dynamic dispatch, reflection and callers outside the indexed code are
+    /// invisible to the call graph, so results are candidates, not proof.
+    pub async fn find_unreachable_symbols(
+        &self,
+        roots: &[&str],
+        query: &CallGraphQuery,
+    ) -> Result<Vec<ImpactNode>, DomainError> {
+        self.find_rarely_called_symbols(roots, query, 1).await
+    }
+
+    /// Like [`find_unreachable_symbols`](Self::find_unreachable_symbols), but
+    /// reports definitions with fewer than `min_callers` references from
+    /// other symbols, so 1 means uncalled; 0 is rejected. `call_count` holds
+    /// each node's count.
+    pub async fn find_rarely_called_symbols(
+        &self,
+        roots: &[&str],
+        query: &CallGraphQuery,
+        min_callers: u64,
+    ) -> Result<Vec<ImpactNode>, DomainError> {
+        if min_callers == 0 {
+            return Err(DomainError::invalid_input(
+                "min_callers must be at least 1 (1 reports uncalled definitions)",
+            ));
+        }
+        let repository_id = query
+            .repository_id
+            .as_deref()
+            .ok_or_else(|| DomainError::invalid_input("Dead-code detection needs a repository"))?;
+        let vector_repo = self.vector_repo.as_ref().ok_or_else(|| {
+            DomainError::invalid_input("Dead-code detection needs the chunk store")
+        })?;
+
+        let mut unreachable = Vec::new();
+        for file in vector_repo.list_files(repository_id).await? {
+            if query
+                .language
+                .as_deref()
+                .is_some_and(|l| file.language.as_str() != l)
+            {
+                continue;
+            }
+            for chunk in vector_repo
+                .find_chunks_by_file(repository_id, &file.file_path)
+                .await?
+            {
+                // A long definition is split into parts; the first stands for it.
+                if !chunk.is_callable() || chunk.part().is_some_and(|p| p > 1) {
+                    continue;
+                }
+                let (Some(name), Some(symbol)) = (chunk.symbol_name(), chunk.call_graph_name())
+                else {
+                    continue;
+                };
+                if roots.contains(&name) || roots.contains(&symbol.as_str()) {
+                    continue;
+                }
+                let call_count = self.external_caller_count(name, &symbol, query).await?;
+                if call_count >= min_callers {
+                    continue;
+                }
+                unreachable.push(ImpactNode {
+                    symbol,
+                    depth: 0,
+                    file_path: chunk.file_path().to_string(),
+                    line: chunk.start_line(),
+                    reference_kind: chunk.node_type().as_str().to_string(),
+                    repository_id: repository_id.to_string(),
+                    import_alias: None,
+                    via_symbol: None,
+                    call_count,
+                    path: Vec::new(),
+                });
+            }
+        }
+        unreachable.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then(a.line.cmp(&b.line))
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        Ok(unreachable)
+    }
+
+    /// How many references from anything other than the symbol itself reach
+    /// `name`/`symbol` or one of the fully-qualified symbols they resolve to.
+    /// Callers are counted in the store; references the symbol makes to
+    /// itself are then taken off, each exactly once even when it matches
+    /// several of the names.
+    async fn external_caller_count(
+        &self,
+        name: &str,
+        symbol: &str,
+        query: &CallGraphQuery,
+    ) -> Result<u64, DomainError> {
+        let mut candidates: BTreeSet<String> = [name.to_string(), symbol.to_string()].into();
+        candidates.extend(
+            self.repository
+                .resolve_symbols(symbol, query, PATH_RESOLVE_LIMIT)
+                .await?,
+        );
+        let mut count = 0;
+        for candidate in &candidates {
+            count += self.repository.count_callers(candidate, query).await?;
+        }
+
+        let unlimited = CallGraphQuery {
+            limit: None,
+            ..query.clone()
+        };
+        let mut seen = HashSet::new();
+        let mut self_references = 0;
+        for candidate in &candidates {
+            for reference in self.repository.find_callees(candidate, &unlimited).await? {
+                let is_self_reference = candidates.iter().any(|c| {
+                    reference.callee_symbol() == c.as_str()
+                        || reference.import_alias() == Some(c.as_str())
+                });
+                if is_self_reference && seen.insert(reference.id().to_string()) {
+                    self_references += 1;
+                }
+            }
+        }
+        Ok(count.saturating_sub(self_references))
+    }
+
+    async fn filtered_references(
+        &self,
+        repository_ids: &[String],
+        query: &CallGraphQuery,
+    ) -> Result<Vec<SymbolReference>, DomainError> {
+        let mut references = self.repository.find_by_repositories(repository_ids).await?;
+        references.retain(|r| matches_query(r, query));
+        Ok(references)
+    }
+
+    /// Resolve a path endpoint to its fully-qualified symbols, falling back to
+    /// the name as given when nothing in the graph matches.
+    async fn resolve_path_endpoint(
+        &self,
+        name: &str,
+        query: &CallGraphQuery,
+    ) -> Result<Vec<String>, DomainError> {
+        let resolved = self
+            .repository
+            .resolve_symbols(name, query, PATH_RESOLVE_LIMIT)
+            .await?;
+        Ok(if resolved.is_empty() {
+            vec![name.to_string()]
+        } else {
+            resolved
+        })
+    }
+}
+
+/// Walk `parents` back from `node` to a BFS seed, emitting every path found
+/// until `cap` paths have been collected. `suffix` holds the edges from `node`
+/// to the target in reverse order. Parents always sit on a strictly shallower
+/// layer, so the walk terminates even when the underlying graph has cycles.
+fn collect_paths<'a>(
+    node: &str,
+    parents: &'a HashMap<String, Vec<SymbolReference>>,
+    suffix: &mut Vec<&'a SymbolReference>,
+    out: &mut Vec<CallPath>,
+    cap: usize,
+) {
+    if out.len() >= cap {
+        return;
+    }
+    match parents.get(node) {
+        None => out.push(CallPath {
+            hops: suffix
+                .iter()
+                .rev()
+                .map(|r| CallPathHop::from_reference(r))
+                .collect(),
+        }),
+        Some(edges) => {
+            for edge in edges {
+                let Some(caller) = edge.caller_symbol() else {
+                    continue;
+                };
+                suffix.push(edge);
+                collect_paths(caller, parents, suffix, out, cap);
+                suffix.pop();
+                if out.len() >= cap {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Caller → callee edges over interned symbol names, the input to Tarjan's
+/// algorithm.
+struct CallAdjacency<'a> {
+    names: Vec<&'a str>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<'a> CallAdjacency<'a> {
+    fn new(references: &'a [SymbolReference]) -> Self {
+        let mut id_of: HashMap<&str, usize> = HashMap::new();
+        let mut names: Vec<&str> = Vec::new();
+        let mut edges: Vec<Vec<usize>> = Vec::new();
+        for reference in references {
+            let Some(caller) = reference.caller_symbol() else {
+                continue;
+            };
+            let mut id = |name| {
+                *id_of.entry(name).or_insert_with(|| {
+                    names.push(name);
+                    edges.push(Vec::new());
+                    names.len() - 1
+                })
+            };
+            let from = id(caller);
+            let to = id(reference.callee_symbol());
+            edges[from].push(to);
+        }
+        Self { names, edges }
+    }
+
+    /// Tarjan's strongly-connected-components algorithm. Every symbol lands in
+    /// exactly one component, including the single-symbol ones that are not
+    /// cycles. Iterative, so deep call chains cannot overflow the stack.
+    fn components(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let n = self.names.len();
+        let mut index = vec![UNVISITED; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack: Vec<usize> = Vec::new();
+        let mut next_index = 0;
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for root in 0..n {
+            if index[root] != UNVISITED {
+                continue;
+            }
+            // Explicit DFS stack of (node, position of the next edge to follow).
+            let mut work = vec![(root, 0usize)];
+            index[root] = next_index;
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&(v, edge)) = work.last() {
+                if let Some(&w) = self.edges[v].get(edge) {
+                    if let Some(top) = work.last_mut() {
+                        top.1 += 1;
+                    }
+                    if index[w] == UNVISITED {
+                        index[w] = next_index;
+                        lowlink[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        work.push((w, 0));
+                    } else if on_stack[w] {
+                        lowlink[v] = lowlink[v].min(index[w]);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some(&(parent, _)) = work.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[v]);
+                }
+                if lowlink[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Whether a component is a cycle: two or more symbols, or one that calls
+    /// itself.
+    fn is_cycle(&self, component: &[usize]) -> bool {
+        match component {
+            [single] => self.edges[*single].contains(single),
+            _ => component.len() > 1,
+        }
+    }
+
+    fn sorted_names(&self, component: &[usize]) -> Vec<String> {
+        let mut names: Vec<String> = component
+            .iter()
+            .map(|&v| self.names[v].to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Members of a component starting from the alphabetically first symbol
+    /// and following its call edges depth-first (callees in name order), so
+    /// a simple loop comes out in call order.
+    fn call_order(&self, component: &[usize]) -> Vec<String> {
+        let members: HashSet<usize> = component.iter().copied().collect();
+        let Some(&start) = component.iter().min_by_key(|&&v| self.names[v]) else {
+            return Vec::new();
+        };
+        let mut seen: HashSet<usize> = HashSet::new();
+        let mut order = Vec::new();
+        let mut work = vec![start];
+        while let Some(v) = work.pop() {
+            if !seen.insert(v) {
+                continue;
+            }
+            order.push(self.names[v].to_string());
+            let mut next: Vec<usize> = self.edges[v]
+                .iter()
+                .copied()
+                .filter(|w| members.contains(w) && !seen.contains(w))
+                .collect();
+            // Reverse name order on the stack pops the first name first.
+            next.sort_by(|a, b| self.names[*b].cmp(self.names[*a]));
+            work.extend(next);
+        }
+        order
+    }
+}
+
+/// Components of two or more symbols, each sorted by name, largest first.
+fn strongly_connected_cycles(references: &[SymbolReference]) -> Vec<Vec<String>> {
+    let graph = CallAdjacency::new(references);
+    let mut cycles: Vec<Vec<String>> = graph
+        .components()
+        .iter()
+        .filter(|c| c.len() > 1)
+        .map(|c| graph.sorted_names(c))
+        .collect();
+    cycles.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    cycles
+}
+
+/// Every cycle (self-recursion included) with at least `min_size` symbols, in
+/// call order and with the files of the calls between its members, largest
+/// first.
+fn call_cycles(references: &[SymbolReference], min_size: usize) -> Vec<CallCycle> {
+    let graph = CallAdjacency::new(references);
+    let components: Vec<Vec<usize>> = graph
+        .components()
+        .into_iter()
+        .filter(|c| c.len() >= min_size && graph.is_cycle(c))
+        .collect();
+
+    let mut component_of: HashMap<&str, usize> = HashMap::new();
+    for (i, component) in components.iter().enumerate() {
+        for &v in component {
+            component_of.insert(graph.names[v], i);
+        }
+    }
+    let mut files: Vec<BTreeSet<&str>> = vec![BTreeSet::new(); components.len()];
+    for reference in references {
+        let Some(caller) = reference.caller_symbol() else {
+            continue;
+        };
+        if let (Some(&from), Some(&to)) = (
+            component_of.get(caller),
+            component_of.get(reference.callee_symbol()),
+        ) {
+            if from == to {
+                files[from].insert(reference.reference_file_path());
+            }
+        }
+    }
+
+    let mut cycles: Vec<CallCycle> = components
+        .iter()
+        .zip(files)
+        .map(|(component, files)| CallCycle {
+            cycle: graph.call_order(component),
+            files: files.into_iter().map(str::to_string).collect(),
+        })
+        .collect();
+    cycles.sort_by(|a, b| {
+        b.cycle
+            .len()
+            .cmp(&a.cycle.len())
+            .then_with(|| a.cycle.cmp(&b.cycle))
+    });
+    cycles
+}
+
+/// Whether a reference passes the query's language and reference-kind filters.
+fn matches_query(reference: &SymbolReference, query: &CallGraphQuery) -> bool {
+    query
+        .language
+        .as_deref()
+        .is_none_or(|l| reference.language().as_str() == l)
+        && query
+            .reference_kind
+            .as_deref()
+            .is_none_or(|k| reference.reference_kind().as_str() == k)
 }
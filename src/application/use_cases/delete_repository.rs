@@ -87,19 +87,25 @@ impl DeleteRepositoryUseCase {
     }
 
     pub async fn delete_by_path(&self, path: &str) -> Result<(), DomainError> {
-        let canonical_path = Path::new(path)
+        let canonical = Path::new(path)
             .canonicalize()
-            .map_err(|e| DomainError::InvalidInput(format!("Invalid path '{}': {}", path, e)))?
-            .to_string_lossy()
-            .to_string();
+            .map_err(|e| DomainError::InvalidInput(format!("Invalid path '{}': {}", path, e)))?;
+        let canonical_path = canonical.to_string_lossy().to_string();
 
-        let repo = self
-            .repository_repo
-            .find_by_path(&canonical_path)
-            .await?
-            .ok_or_else(|| {
-                DomainError::not_found(format!("Repository not found at path: {}", path))
-            })?;
+        let repo = match self.repository_repo.find_by_path(&canonical_path).await? {
+            Some(repo) => repo,
+            // Not a repository path itself; it may be one root of a
+            // multi-root repository.
+            None => self
+                .repository_repo
+                .list()
+                .await?
+                .into_iter()
+                .find(|repo| repo.root_paths().contains(&canonical))
+                .ok_or_else(|| {
+                    DomainError::not_found(format!("Repository not found at path: {}", path))
+                })?,
+        };
 
         self.execute(repo.id()).await
     }
@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::application::{GitDiff, ImpactAnalysis, ImpactAnalysisUseCase, VectorRepository};
+use crate::domain::{DomainError, ReferenceKind};
+
+/// Blast radius of one symbol whose chunk a diff touches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedSymbolImpact {
+    /// Symbol name of the changed chunk.
+    pub symbol: String,
+    pub file_path: String,
+    /// Line span of the changed chunk in `file_path`.
+    pub start_line: u32,
+    pub end_line: u32,
+    /// Impact analysis run from the symbol.
+    pub impact: ImpactAnalysis,
+}
+
+/// Impact of every symbol changed since a revision, for `impact --diff`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffImpactReport {
+    /// Revision the working tree was diffed against.
+    pub base: String,
+    /// Files with changed lines.
+    pub changed_files: usize,
+    /// Changed symbols in diff order, each with its own blast radius.
+    pub changed_symbols: Vec<ChangedSymbolImpact>,
+    /// Distinct symbols affected by any change; a symbol reached from two
+    /// changes counts once.
+    pub total_affected: usize,
+}
+
+/// Use case: map the lines changed since a revision onto indexed chunks and
+/// run [`ImpactAnalysisUseCase`] from every symbol they define.
+pub struct DiffImpactUseCase {
+    impact: ImpactAnalysisUseCase,
+    vector_repo: Arc<dyn VectorRepository>,
+    git: Arc<dyn GitDiff>,
+}
+
+impl DiffImpactUseCase {
+    pub fn new(
+        impact: ImpactAnalysisUseCase,
+        vector_repo: Arc<dyn VectorRepository>,
+        git: Arc<dyn GitDiff>,
+    ) -> Self {
+        Self {
+            impact,
+            vector_repo,
+            git,
+        }
+    }
+
+    /// See [`ImpactAnalysisUseCase::with_follow_impls`].
+    pub fn with_follow_impls(mut self, follow_impls: bool) -> Self {
+        self.impact = self.impact.with_follow_impls(follow_impls);
+        self
+    }
+
+    /// See [`ImpactAnalysisUseCase::with_kinds`].
+    pub fn with_kinds(mut self, kinds: Vec<ReferenceKind>) -> Self {
+        self.impact = self.impact.with_kinds(kinds);
+        self
+    }
+
+    /// See [`ImpactAnalysisUseCase::with_exclude_tests`].
+    pub fn with_exclude_tests(mut self, exclude_tests: bool) -> Self {
+        self.impact = self.impact.with_exclude_tests(exclude_tests);
+        self
+    }
+
+    /// Diff `repo_path` against `base` and analyse every changed symbol of
+    /// `repository_id`. A chunk is changed when a changed line falls inside
+    /// its line span; chunks without a symbol name (module-level code) are
+    /// skipped, and a symbol split over several chunks is analysed once.
+    pub async fn analyze(
+        &self,
+        repo_path: &Path,
+        repository_id: &str,
+        base: &str,
+    ) -> Result<DiffImpactReport, DomainError> {
+        let changed = self.git.changed_lines(repo_path, base).await?;
+
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut changed_symbols = Vec::new();
+        for file in &changed {
+            let chunks = self
+                .vector_repo
+                .find_chunks_by_file(repository_id, &file.file_path)
+                .await?;
+            for chunk in &chunks {
+                let Some(symbol) = chunk.symbol_name() else {
+                    continue;
+                };
+                let touched = file
+                    .ranges
+                    .iter()
+                    .any(|&(start, end)| start <= chunk.end_line() && chunk.start_line() <= end);
+                if !touched || !seen.insert(symbol.to_string()) {
+                    continue;
+                }
+                debug!(
+                    symbol,
+                    file = %file.file_path,
+                    "impact --diff: chunk touched by the diff"
+                );
+                let impact = self
+                    .impact
+                    .analyze(symbol, Some(repository_id), false)
+                    .await?;
+                changed_symbols.push(ChangedSymbolImpact {
+                    symbol: symbol.to_string(),
+                    file_path: chunk.file_path().to_string(),
+                    start_line: chunk.start_line(),
+                    end_line: chunk.end_line(),
+                    impact,
+                });
+            }
+        }
+
+        let total_affected = changed_symbols
+            .iter()
+            .flat_map(|c| c.impact.by_depth.iter().flatten())
+            .map(|node| node.symbol.as_str())
+            .collect::<HashSet<_>>()
+            .len();
+
+        Ok(DiffImpactReport {
+            base: base.to_string(),
+            changed_files: changed.len(),
+            changed_symbols,
+            total_affected,
+        })
+    }
+}
@@ -0,0 +1,231 @@
+//! Render the caller graph around a symbol as Graphviz DOT or Mermaid.
+//!
+//! [`CallGraphUseCase::export_graph`](super::CallGraphUseCase::export_graph)
+//! builds a [`GraphExport`]; [`GraphExport::dot`] and [`GraphExport::mermaid`]
+//! wrap it in a [`Display`] implementation for the chosen format. Edges are
+//! colour-coded by reference kind and caller nodes are labelled with the file
+//! and line of the reference.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use super::{ImpactEdge, ImpactNode, ANONYMOUS_SYMBOL};
+
+/// Default hop limit for [`CallGraphUseCase::export_graph`](super::CallGraphUseCase::export_graph).
+pub const DEFAULT_GRAPH_MAX_DEPTH: usize = 3;
+
+/// Fallback edge colour for reference kinds without an entry in
+/// [`kind_color`].
+const OTHER_KIND_COLOR: &str = "#7f8c8d";
+
+/// Callers of a symbol, up to a hop limit, ready to render.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphExport {
+    /// The symbol as requested.
+    pub root: String,
+    /// Fully-qualified symbols the root resolved to; the graph's sinks.
+    pub root_symbols: Vec<String>,
+    /// Every caller reached, at the hop depth it was first reached.
+    pub nodes: Vec<ImpactNode>,
+    /// Every distinct caller → callee edge walked, with its reference kind.
+    pub edges: Vec<ImpactEdge>,
+}
+
+impl GraphExport {
+    /// Graphviz rendering, e.g. `codesearch graph foo | dot -Tsvg`.
+    pub fn dot(&self) -> DotGraph<'_> {
+        DotGraph(self)
+    }
+
+    /// Mermaid `graph TD` rendering, for Markdown that renders diagrams.
+    pub fn mermaid(&self) -> MermaidGraph<'_> {
+        MermaidGraph(self)
+    }
+}
+
+/// [`GraphExport`] formatted as a Graphviz digraph.
+pub struct DotGraph<'a>(&'a GraphExport);
+
+impl Display for DotGraph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let graph = self.0;
+        writeln!(f, "digraph {{")?;
+        writeln!(f, "    rankdir=BT;")?;
+        writeln!(
+            f,
+            "    node [shape=box, style=\"rounded,filled\", fillcolor=\"#f4f6f7\", fontname=\"Helvetica\"];"
+        )?;
+        writeln!(f, "    edge [fontname=\"Helvetica\", fontsize=10];")?;
+
+        for root in &graph.root_symbols {
+            let id = dot_escape(root);
+            writeln!(
+                f,
+                "    \"{id}\" [label=\"{id}\", fillcolor=\"#e74c3c\", penwidth=2];"
+            )?;
+        }
+        for node in &graph.nodes {
+            writeln!(
+                f,
+                "    \"{}\" [label=\"{}\\n{}:{}\"];",
+                dot_escape(&node_id(&node.symbol, &node.file_path)),
+                dot_escape(&node.symbol),
+                dot_escape(&node.file_path),
+                node.line
+            )?;
+        }
+        for edge in &graph.edges {
+            writeln!(
+                f,
+                "    \"{}\" -> \"{}\" [label=\"{}\", color=\"{}\", fontcolor=\"{}\"];",
+                dot_escape(&node_id(&edge.caller, &edge.file_path)),
+                dot_escape(&edge.callee),
+                dot_escape(&edge.reference_kind),
+                kind_color(&edge.reference_kind),
+                kind_color(&edge.reference_kind)
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// [`GraphExport`] formatted as a Mermaid flowchart. Node ids are generated,
+/// so symbol names only appear inside quoted, entity-escaped labels.
+pub struct MermaidGraph<'a>(&'a GraphExport);
+
+impl Display for MermaidGraph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let graph = self.0;
+        writeln!(f, "graph TD")?;
+
+        let mut ids: HashMap<String, String> = HashMap::new();
+        for root in &graph.root_symbols {
+            let id = format!("n{}", ids.len());
+            writeln!(f, "    {id}[\"{}\"]", mermaid_escape(root))?;
+            ids.insert(root.clone(), id);
+        }
+        for node in &graph.nodes {
+            let key = node_id(&node.symbol, &node.file_path);
+            if ids.contains_key(&key) {
+                continue;
+            }
+            let id = format!("n{}", ids.len());
+            writeln!(
+                f,
+                "    {id}[\"{}<br/>{}:{}\"]",
+                mermaid_escape(&node.symbol),
+                mermaid_escape(&node.file_path),
+                node.line
+            )?;
+            ids.insert(key, id);
+        }
+
+        // `linkStyle` addresses edges by their declaration order.
+        let mut styles = Vec::new();
+        for edge in &graph.edges {
+            let (Some(from), Some(to)) = (
+                ids.get(&node_id(&edge.caller, &edge.file_path)),
+                ids.get(&edge.callee),
+            ) else {
+                continue;
+            };
+            writeln!(
+                f,
+                "    {from} -->|{}| {to}",
+                mermaid_escape(&edge.reference_kind)
+            )?;
+            styles.push(kind_color(&edge.reference_kind));
+        }
+        for (index, color) in styles.iter().enumerate() {
+            writeln!(f, "    linkStyle {index} stroke:{color},color:{color}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Edge colour for a reference kind (see `ReferenceKind::as_str`).
+fn kind_color(kind: &str) -> &'static str {
+    match kind {
+        "call" => "#2980b9",
+        "method_call" => "#8e44ad",
+        "import" => "#27ae60",
+        "type_reference" | "generic_argument" => "#e67e22",
+        "instantiation" => "#c0392b",
+        "implementation" | "inheritance" | "override" => "#16a085",
+        _ => OTHER_KIND_COLOR,
+    }
+}
+
+/// Graph node identifier for a symbol. Anonymous callers are qualified by
+/// file so that module-level code in different files stays distinct.
+fn node_id(symbol: &str, file_path: &str) -> String {
+    if symbol == ANONYMOUS_SYMBOL {
+        format!("{}@{}", symbol, file_path)
+    } else {
+        symbol.to_string()
+    }
+}
+
+/// Escape a string for use inside a double-quoted DOT identifier.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escape a string for use inside a double-quoted Mermaid label.
+fn mermaid_escape(s: &str) -> String {
+    s.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> GraphExport {
+        GraphExport {
+            root: "validate".to_string(),
+            root_symbols: vec!["validate".to_string()],
+            nodes: vec![ImpactNode {
+                symbol: "auth".to_string(),
+                depth: 1,
+                file_path: "src/auth.rs".to_string(),
+                line: 20,
+                reference_kind: "call".to_string(),
+                repository_id: "repo1".to_string(),
+                import_alias: None,
+                via_symbol: Some("validate".to_string()),
+                call_count: 0,
+                path: Vec::new(),
+            }],
+            edges: vec![ImpactEdge {
+                caller: "auth".to_string(),
+                callee: "validate".to_string(),
+                reference_kind: "call".to_string(),
+                file_path: "src/auth.rs".to_string(),
+                line: 20,
+            }],
+        }
+    }
+
+    #[test]
+    fn dot_labels_nodes_with_location_and_colours_edges_by_kind() {
+        let dot = sample().dot().to_string();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("\"auth\" [label=\"auth\\nsrc/auth.rs:20\"]"));
+        assert!(dot.contains("\"auth\" -> \"validate\" [label=\"call\", color=\"#2980b9\""));
+    }
+
+    #[test]
+    fn mermaid_uses_generated_ids_and_styles_each_edge() {
+        let mermaid = sample().mermaid().to_string();
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("n0[\"validate\"]"));
+        assert!(mermaid.contains("n1[\"auth<br/>src/auth.rs:20\"]"));
+        assert!(mermaid.contains("n1 -->|call| n0"));
+        assert!(mermaid.contains("linkStyle 0 stroke:#2980b9"));
+    }
+}
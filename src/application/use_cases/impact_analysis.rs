@@ -1,4 +1,4 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -7,10 +7,20 @@ use tracing::{debug, warn};
 
 use crate::application::use_cases::pattern_utils::build_fuzzy_pattern;
 use crate::application::{CallGraphQuery, CallGraphUseCase};
-use crate::domain::DomainError;
+use crate::domain::{DomainError, ReferenceKind, SymbolReference};
 
 pub const ANONYMOUS_SYMBOL: &str = "<anonymous>";
 
+/// Reference kind reported for a trait or interface method reached from one
+/// of its implementations, or a base class method reached from an override:
+/// calls made through the trait or base class dispatch to the changed method.
+pub const DISPATCH_REFERENCE_KIND: &str = "dispatch";
+
+/// Edges from a method to the trait, interface or base class method it
+/// stands in for, followed in both directions unless `follow_impls` is off.
+const DISPATCH_EDGE_KINDS: [ReferenceKind; 2] =
+    [ReferenceKind::Implementation, ReferenceKind::Override];
+
 /// Maximum number of fully-qualified symbols to resolve from a short name before
 /// seeding the blast-radius BFS. Caps the ambiguity fan-out; when a name resolves
 /// to more than this many FQNs the extras are dropped and the result is flagged
@@ -57,6 +67,43 @@ pub struct ImpactNode {
     /// The immediate parent symbol in the BFS traversal (i.e. the symbol that led to this one).
     /// `None` only for the root symbol itself; always `Some` for every other node.
     pub via_symbol: Option<String>,
+    /// How many references call this symbol. Only impact analysis fills it
+    /// in; it is 0 for anonymous callers and in other reports.
+    #[serde(default)]
+    pub call_count: u64,
+    /// Symbols from the root down to this node (both included), following
+    /// `via_symbol`. Only impact analysis fills it in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<String>,
+}
+
+impl ImpactNode {
+    /// Ranking weight for text output: `depth * ln(call_count + 1)`, so
+    /// heavily-called symbols rise above rarely-called ones.
+    pub fn impact_score(&self) -> f64 {
+        self.depth as f64 * (self.call_count as f64 + 1.0).ln()
+    }
+}
+
+/// A single caller → callee edge discovered during the blast-radius BFS.
+///
+/// Unlike [`ImpactNode`], which records only the first route by which each
+/// symbol was reached, edges cover every reference walked — including ones
+/// into already-visited symbols — so the report can be rendered as a graph.
+/// Each `(caller, callee, reference_kind)` triple appears once, so cycles in
+/// the call graph yield a single back edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactEdge {
+    /// The calling symbol ([`ANONYMOUS_SYMBOL`] for module-level code).
+    pub caller: String,
+    /// The symbol being called.
+    pub callee: String,
+    /// Kind of reference relationship (e.g. "call", "type_reference").
+    pub reference_kind: String,
+    /// File where the reference occurs.
+    pub file_path: String,
+    /// Line number where the reference occurs in `file_path`.
+    pub line: u32,
 }
 
 /// Full blast-radius report for a symbol.
@@ -75,6 +122,9 @@ pub struct ImpactAnalysis {
     pub max_depth_reached: usize,
     /// Affected symbols grouped by hop depth (index 0 = depth 1 = direct callers).
     pub by_depth: Vec<Vec<ImpactNode>>,
+    /// Every distinct caller → callee edge walked by the BFS.
+    #[serde(default)]
+    pub edges: Vec<ImpactEdge>,
 }
 
 impl ImpactAnalysis {
@@ -123,15 +173,55 @@ impl ImpactAnalysis {
     }
 }
 
+/// `path` with `symbol` appended.
+fn extend_path(path: &[String], symbol: &str) -> Vec<String> {
+    let mut extended = path.to_vec();
+    extended.push(symbol.to_string());
+    extended
+}
+
 /// Use case: BFS outward from a symbol through the call graph to identify
 /// every symbol that would be affected if the root symbol changes.
 pub struct ImpactAnalysisUseCase {
     call_graph: Arc<CallGraphUseCase>,
+    follow_impls: bool,
+    kinds: Vec<ReferenceKind>,
+    exclude_tests: bool,
 }
 
 impl ImpactAnalysisUseCase {
     pub fn new(call_graph: Arc<CallGraphUseCase>) -> Self {
-        Self { call_graph }
+        Self {
+            call_graph,
+            follow_impls: true,
+            kinds: Vec::new(),
+            exclude_tests: false,
+        }
+    }
+
+    /// Follow [`ReferenceKind::Implementation`] and [`ReferenceKind::Override`]
+    /// edges (on by default): a changed trait or base class method affects
+    /// its implementations and overrides, and a changed implementation or
+    /// override affects the method it stands in for and so everything calling
+    /// through the trait or base class ([`DISPATCH_REFERENCE_KIND`]).
+    pub fn with_follow_impls(mut self, follow_impls: bool) -> Self {
+        self.follow_impls = follow_impls;
+        self
+    }
+
+    /// Only traverse references of these kinds (every kind when empty, the
+    /// default). Implementation and override edges are still followed unless
+    /// [`Self::with_follow_impls`] turns them off. The root symbol itself is
+    /// resolved against every kind.
+    pub fn with_kinds(mut self, kinds: Vec<ReferenceKind>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    /// Skip references made from test files while traversing.
+    pub fn with_exclude_tests(mut self, exclude_tests: bool) -> Self {
+        self.exclude_tests = exclude_tests;
+        self
     }
 
     /// Resolve `pattern` to root symbols, capped at [`RESOLVE_SYMBOLS_LIMIT`].
@@ -247,33 +337,106 @@ impl ImpactAnalysisUseCase {
         };
 
         let mut visited: HashSet<String> = HashSet::new();
+        // Route from the root to every visited named symbol, for `ImpactNode::path`.
+        let mut paths: HashMap<String, Vec<String>> = HashMap::new();
 
-        // Seed the BFS with every root symbol.
-        let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+        // Seed the BFS with every root symbol. The flag marks trait methods
+        // reached through dispatch, whose other implementations are not
+        // affected by the change.
+        let mut queue: VecDeque<(String, usize, bool)> = VecDeque::new();
         for sym in &root_symbols {
             if visited.insert(sym.clone()) {
-                queue.push_back((sym.clone(), 0));
+                paths.insert(sym.clone(), vec![sym.clone()]);
+                queue.push_back((sym.clone(), 0, false));
             }
         }
 
         // by_depth[i] holds nodes at depth i+1
         let mut by_depth: Vec<Vec<ImpactNode>> = Vec::new();
+        let mut edges: Vec<ImpactEdge> = Vec::new();
+        let mut seen_edges: HashSet<(String, String, String)> = HashSet::new();
+
+        // Roots are resolved above against every reference; the kind and
+        // test-file filters only narrow the traversal.
+        let mut traversal = query;
+        if self.exclude_tests {
+            traversal = traversal.with_exclude_tests();
+        }
+        let dispatch_queries: Vec<CallGraphQuery> = DISPATCH_EDGE_KINDS
+            .iter()
+            .map(|kind| traversal.clone().with_reference_kind(kind.as_str()))
+            .collect();
+        if !self.kinds.is_empty() {
+            let mut kinds = self.kinds.clone();
+            if self.follow_impls {
+                kinds.extend(
+                    DISPATCH_EDGE_KINDS
+                        .iter()
+                        .filter(|k| !self.kinds.contains(k)),
+                );
+            }
+            traversal = traversal.with_kinds(kinds);
+        }
+        while let Some((current, depth, dispatched)) = queue.pop_front() {
+            let mut callers = self.call_graph.find_callers(&current, &traversal).await?;
+            if !self.follow_impls || dispatched {
+                callers.retain(|r| !DISPATCH_EDGE_KINDS.contains(&r.reference_kind()));
+            }
+            let mut implemented = Vec::new();
+            if self.follow_impls {
+                for dispatch_query in &dispatch_queries {
+                    implemented.extend(
+                        self.call_graph
+                            .find_callees(&current, dispatch_query)
+                            .await?,
+                    );
+                }
+            }
 
-        while let Some((current, depth)) = queue.pop_front() {
-            let callers = self.call_graph.find_callers(&current, &query).await?;
-            if callers.is_empty() {
+            // Each hop: the reference, the symbol it leads to and its label.
+            let hops: Vec<(&SymbolReference, Option<&str>, String)> = callers
+                .iter()
+                .map(|r| (r, r.caller_symbol(), r.reference_kind().to_string()))
+                .chain(implemented.iter().map(|r| {
+                    (
+                        r,
+                        Some(r.callee_symbol()),
+                        DISPATCH_REFERENCE_KIND.to_string(),
+                    )
+                }))
+                .collect();
+            if hops.is_empty() {
                 continue;
             }
 
             let next_depth = depth + 1;
+            let current_path = paths.get(&current).cloned().unwrap_or_default();
 
             // Ensure the depth level exists.
             while by_depth.len() < next_depth {
                 by_depth.push(Vec::new());
             }
 
-            for reference in &callers {
-                match reference.caller_symbol() {
+            for (reference, next, kind) in hops {
+                // Anonymous callers are distinct per file, so key them by file
+                // to avoid collapsing unrelated module-level call sites.
+                let caller_label = next.unwrap_or(ANONYMOUS_SYMBOL);
+                let caller_key = match next {
+                    Some(sym) => sym.to_string(),
+                    None => format!("{}:{}", ANONYMOUS_SYMBOL, reference.reference_file_path()),
+                };
+                let dispatch = kind == DISPATCH_REFERENCE_KIND;
+                if seen_edges.insert((caller_key, current.clone(), kind.clone())) {
+                    edges.push(ImpactEdge {
+                        caller: caller_label.to_string(),
+                        callee: current.clone(),
+                        reference_kind: kind.clone(),
+                        file_path: reference.reference_file_path().to_string(),
+                        line: reference.reference_line(),
+                    });
+                }
+
+                match next {
                     None => {
                         // Anonymous caller (top-level / module-level code with no enclosing
                         // function).  Include it in the impact report so the user can see it,
@@ -297,6 +460,8 @@ impl ImpactAnalysisUseCase {
                             repository_id: reference.repository_id().to_string(),
                             import_alias: reference.import_alias().map(str::to_string),
                             via_symbol: Some(current.clone()),
+                            call_count: 0,
+                            path: extend_path(&current_path, ANONYMOUS_SYMBOL),
                         });
                     }
                     Some(caller_sym) => {
@@ -306,19 +471,27 @@ impl ImpactAnalysisUseCase {
                             continue;
                         }
                         visited.insert(caller_sym.clone());
+                        let call_count = self
+                            .call_graph
+                            .count_callers(&caller_sym, &traversal)
+                            .await?;
+                        let path = extend_path(&current_path, &caller_sym);
+                        paths.insert(caller_sym.clone(), path.clone());
 
                         by_depth[next_depth - 1].push(ImpactNode {
                             symbol: caller_sym.clone(),
                             depth: next_depth,
                             file_path: reference.reference_file_path().to_string(),
                             line: reference.reference_line(),
-                            reference_kind: reference.reference_kind().to_string(),
+                            reference_kind: kind,
                             repository_id: reference.repository_id().to_string(),
                             import_alias: reference.import_alias().map(str::to_string),
                             via_symbol: Some(current.clone()),
+                            call_count,
+                            path,
                         });
 
-                        queue.push_back((caller_sym, next_depth));
+                        queue.push_back((caller_sym, next_depth, dispatch));
                     }
                 }
             }
@@ -337,6 +510,7 @@ impl ImpactAnalysisUseCase {
             total_affected,
             max_depth_reached,
             by_depth,
+            edges,
         })
     }
 }
@@ -0,0 +1,296 @@
+//! Move a repository's index between machines.
+//!
+//! [`IndexArchiveUseCase::export`] gathers everything indexing produced for a
+//! repository — chunks, embeddings, file hashes, call-graph references and the
+//! repository row — into an [`IndexArchive`]; [`IndexArchiveUseCase::import`]
+//! writes one back into the local stores under a new checkout path. The
+//! on-disk encoding lives in the adapter layer (`write_index_archive` /
+//! `read_index_archive`).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::memory_support::unix_now;
+use crate::application::{
+    CallGraphUseCase, FileHashRepository, MetadataRepository, VectorRepository,
+};
+use crate::domain::{
+    CodeChunk, DomainError, Embedding, FileHash, Repository, SymbolReference, VectorStore,
+};
+
+/// Version of the archive layout written by [`IndexArchiveUseCase::export`].
+/// Bumped whenever an entry changes shape; import refuses newer versions.
+///
+/// 2: entries live under a `<repository id>/` directory, so one archive can
+/// hold a whole namespace. Version 1 archives (entries at the top level) are
+/// still imported.
+pub const INDEX_ARCHIVE_FORMAT_VERSION: u32 = 2;
+
+/// Oldest archive version [`IndexArchiveUseCase::import`] still accepts.
+const MIN_INDEX_ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Model name recorded for namespaces indexed without embeddings. Mirrors the
+/// adapter's `NO_EMBEDDINGS_MODEL` sentinel.
+const NO_EMBEDDINGS_MODEL: &str = "none";
+
+/// Chunks and embeddings are read and written this many at a time, so a large
+/// repository never needs one giant storage call.
+const ARCHIVE_BATCH: usize = 512;
+
+/// Describes an archive: what produced it and which embedding space its
+/// vectors live in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub repository_id: String,
+    /// Model that produced the archived embeddings (`"none"` when the
+    /// namespace was indexed with `--no-embeddings`).
+    pub embedding_model: String,
+    pub dimensions: usize,
+    pub chunk_count: usize,
+    pub embedding_count: usize,
+    /// Unix timestamp of the export.
+    pub exported_at: i64,
+}
+
+/// A repository's complete index, ready to be written to or read from disk.
+#[derive(Debug, Clone)]
+pub struct IndexArchive {
+    pub manifest: ArchiveManifest,
+    pub repository: Repository,
+    pub chunks: Vec<CodeChunk>,
+    pub embeddings: Vec<Embedding>,
+    pub file_hashes: Vec<FileHash>,
+    pub references: Vec<SymbolReference>,
+}
+
+pub struct IndexArchiveUseCase {
+    repository_repo: Arc<dyn MetadataRepository>,
+    vector_repo: Arc<dyn VectorRepository>,
+    file_hash_repo: Arc<dyn FileHashRepository>,
+    call_graph_use_case: Arc<CallGraphUseCase>,
+    /// Embedding model and dimensions of the local namespace.
+    embedding_model: String,
+    dimensions: usize,
+}
+
+impl IndexArchiveUseCase {
+    pub fn new(
+        repository_repo: Arc<dyn MetadataRepository>,
+        vector_repo: Arc<dyn VectorRepository>,
+        file_hash_repo: Arc<dyn FileHashRepository>,
+        call_graph_use_case: Arc<CallGraphUseCase>,
+        embedding_model: impl Into<String>,
+        dimensions: usize,
+    ) -> Self {
+        Self {
+            repository_repo,
+            vector_repo,
+            file_hash_repo,
+            call_graph_use_case,
+            embedding_model: embedding_model.into(),
+            dimensions,
+        }
+    }
+
+    /// Collect the index of every repository in `namespace`, one archive per
+    /// repository.
+    pub async fn export_namespace(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<IndexArchive>, DomainError> {
+        let mut archives = Vec::new();
+        for repository in self.repository_repo.list().await? {
+            if repository.namespace() == Some(namespace) {
+                archives.push(self.export(repository.id()).await?);
+            }
+        }
+        Ok(archives)
+    }
+
+    /// Collect the index of repository `id` into an archive.
+    pub async fn export(&self, id: &str) -> Result<IndexArchive, DomainError> {
+        let repository = self
+            .repository_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Repository not found: {}", id)))?;
+
+        let mut chunks = Vec::new();
+        for file in self.vector_repo.list_files(id).await? {
+            chunks.extend(
+                self.vector_repo
+                    .find_chunks_by_file(id, &file.file_path)
+                    .await?,
+            );
+        }
+
+        let mut embeddings = Vec::new();
+        for batch in chunks.chunks(ARCHIVE_BATCH) {
+            let ids: Vec<&str> = batch.iter().map(|c| c.id()).collect();
+            embeddings.extend(self.vector_repo.fetch_embeddings(&ids).await?);
+        }
+
+        let file_hashes = self.file_hash_repo.find_by_repository(id).await?;
+        let references = self.call_graph_use_case.find_by_repository(id).await?;
+
+        let embedding_model = if embeddings.is_empty() {
+            NO_EMBEDDINGS_MODEL.to_string()
+        } else {
+            self.embedding_model.clone()
+        };
+
+        info!(
+            "Exporting repository {} ({} chunks, {} embeddings, {} references)",
+            repository.name(),
+            chunks.len(),
+            embeddings.len(),
+            references.len()
+        );
+
+        Ok(IndexArchive {
+            manifest: ArchiveManifest {
+                format_version: INDEX_ARCHIVE_FORMAT_VERSION,
+                repository_id: repository.id().to_string(),
+                embedding_model,
+                dimensions: self.dimensions,
+                chunk_count: chunks.len(),
+                embedding_count: embeddings.len(),
+                exported_at: unix_now(),
+            },
+            repository,
+            chunks,
+            embeddings,
+            file_hashes,
+            references,
+        })
+    }
+
+    /// Load `archive` into the local stores, registering the repository at
+    /// `path` (an absolute path to the local checkout, or the archived path
+    /// when the sources are not available locally) under the local
+    /// namespace. Any data already stored under the archived repository id is
+    /// replaced. Refuses archives whose embeddings come from a different model
+    /// or dimensionality than the local namespace.
+    pub async fn import(
+        &self,
+        archive: IndexArchive,
+        path: &str,
+        store: VectorStore,
+        namespace: &str,
+    ) -> Result<Repository, DomainError> {
+        self.check_compatible(&archive.manifest)?;
+
+        let id = archive.repository.id().to_string();
+        if let Some(existing) = self.repository_repo.find_by_path(path).await? {
+            if existing.id() != id {
+                return Err(DomainError::invalid_input(format!(
+                    "'{}' is already indexed as repository {}; delete it first",
+                    path,
+                    existing.id()
+                )));
+            }
+        }
+
+        self.vector_repo.delete_by_repository(&id).await?;
+        self.file_hash_repo.delete_by_repository(&id).await?;
+        self.call_graph_use_case.delete_by_repository(&id).await?;
+
+        let mut by_chunk: HashMap<String, Embedding> = archive
+            .embeddings
+            .into_iter()
+            .map(|e| (e.chunk_id().to_string(), e))
+            .collect();
+        for batch in archive.chunks.chunks(ARCHIVE_BATCH) {
+            // save_batch takes either one embedding per chunk or none at all,
+            // so chunks the exporter had no vector for are saved on their own.
+            let (embedded, bare): (Vec<&CodeChunk>, Vec<&CodeChunk>) =
+                batch.iter().partition(|c| by_chunk.contains_key(c.id()));
+            let embeddings: Vec<Embedding> = embedded
+                .iter()
+                .filter_map(|c| by_chunk.remove(c.id()))
+                .collect();
+            let embedded: Vec<CodeChunk> = embedded.into_iter().cloned().collect();
+            let bare: Vec<CodeChunk> = bare.into_iter().cloned().collect();
+            if !embedded.is_empty() {
+                self.vector_repo.save_batch(&embedded, &embeddings).await?;
+            }
+            if !bare.is_empty() {
+                self.vector_repo.save_batch(&bare, &[]).await?;
+            }
+        }
+
+        self.file_hash_repo.save_batch(&archive.file_hashes).await?;
+        self.call_graph_use_case
+            .save_references(&archive.references)
+            .await
+            .map_err(|e| DomainError::storage(e.to_string()))?;
+
+        let source = archive.repository;
+        let mut repository = Repository::reconstitute(
+            id,
+            source.name().to_string(),
+            path.to_string(),
+            source.created_at(),
+            unix_now(),
+            source.chunk_count(),
+            source.file_count(),
+            store,
+            Some(namespace.to_string()),
+            source.git_remote().map(str::to_string),
+            source.languages().clone(),
+            source.index_filters().clone(),
+        );
+        repository.set_indexed_commit(source.indexed_commit().map(str::to_string));
+        repository.set_roots(source.roots().to_vec());
+        self.repository_repo.save(&repository).await?;
+
+        // Rebuilds the full-text index; the vector index is maintained as
+        // rows are inserted.
+        self.vector_repo.flush().await?;
+
+        info!(
+            "Imported repository {} at {} ({} chunks)",
+            repository.name(),
+            path,
+            archive.chunks.len()
+        );
+        Ok(repository)
+    }
+
+    fn check_compatible(&self, manifest: &ArchiveManifest) -> Result<(), DomainError> {
+        if !(MIN_INDEX_ARCHIVE_FORMAT_VERSION..=INDEX_ARCHIVE_FORMAT_VERSION)
+            .contains(&manifest.format_version)
+        {
+            return Err(DomainError::invalid_input(format!(
+                "Unsupported archive format version {} (expected {} to {})",
+                manifest.format_version,
+                MIN_INDEX_ARCHIVE_FORMAT_VERSION,
+                INDEX_ARCHIVE_FORMAT_VERSION
+            )));
+        }
+        // An archive without vectors fits any namespace.
+        if manifest.embedding_count == 0 {
+            return Ok(());
+        }
+        if manifest.embedding_model != self.embedding_model
+            || manifest.dimensions != self.dimensions
+        {
+            return Err(DomainError::invalid_input(format!(
+                "Archive embeddings come from '{}' ({} dimensions) but this namespace uses \
+                 '{}' ({} dimensions). Import into a namespace created with \
+                 --embedding-model {} --embedding-dimensions {}.",
+                manifest.embedding_model,
+                manifest.dimensions,
+                self.embedding_model,
+                self.dimensions,
+                manifest.embedding_model,
+                manifest.dimensions
+            )));
+        }
+        Ok(())
+    }
+}
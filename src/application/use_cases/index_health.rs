@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use crate::application::{FileHashRepository, IndexState, MetadataRepository, VectorRepository};
+use crate::domain::DomainError;
+
+/// One inconsistency found by [`IndexHealthUseCase`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthIssue {
+    /// Chunks stored without an embedding; vector search can't find them.
+    MissingEmbeddings { count: u64 },
+    /// Embeddings whose chunk no longer exists.
+    OrphanEmbeddings { count: u64 },
+    /// The BM25 full-text index is missing or out of step with the chunks.
+    TextIndex { state: IndexState },
+    /// The HNSW index on the embeddings table is missing.
+    VectorIndexMissing,
+    /// Repository metadata disagrees with the rows actually stored.
+    CountMismatch {
+        repository: String,
+        path: String,
+        recorded_chunks: u64,
+        actual_chunks: u64,
+        recorded_files: u64,
+        actual_files: u64,
+    },
+}
+
+/// Consistency checks over one namespace's index, behind `codesearch health`.
+pub struct IndexHealthUseCase {
+    vector_repo: Arc<dyn VectorRepository>,
+    repository_repo: Arc<dyn MetadataRepository>,
+    file_hash_repo: Arc<dyn FileHashRepository>,
+}
+
+impl IndexHealthUseCase {
+    pub fn new(
+        vector_repo: Arc<dyn VectorRepository>,
+        repository_repo: Arc<dyn MetadataRepository>,
+        file_hash_repo: Arc<dyn FileHashRepository>,
+    ) -> Self {
+        Self {
+            vector_repo,
+            repository_repo,
+            file_hash_repo,
+        }
+    }
+
+    /// Check the vector store and the metadata of every repository indexed in
+    /// `namespace`.  Returns `None` when the store has nothing persistent to
+    /// check, and an empty list when the index is healthy.
+    pub async fn execute(&self, namespace: &str) -> Result<Option<Vec<HealthIssue>>, DomainError> {
+        let Some(integrity) = self.vector_repo.integrity().await? else {
+            return Ok(None);
+        };
+        let mut issues = Vec::new();
+
+        // A store indexed with `--no-embeddings` (text search only) has no
+        // embeddings at all by design; only a partial set is a problem.
+        if integrity.embedding_count > 0 && integrity.chunks_without_embedding > 0 {
+            issues.push(HealthIssue::MissingEmbeddings {
+                count: integrity.chunks_without_embedding,
+            });
+        }
+        if integrity.orphan_embeddings > 0 {
+            issues.push(HealthIssue::OrphanEmbeddings {
+                count: integrity.orphan_embeddings,
+            });
+        }
+        if integrity.chunk_count > 0 && integrity.text_index != IndexState::Ok {
+            issues.push(HealthIssue::TextIndex {
+                state: integrity.text_index,
+            });
+        }
+        if integrity.vector_index == IndexState::Missing {
+            issues.push(HealthIssue::VectorIndexMissing);
+        }
+
+        for repo in self.repository_repo.list().await? {
+            if repo.namespace() != Some(namespace) {
+                continue;
+            }
+            let actual_chunks = integrity
+                .chunks_per_repository
+                .get(repo.id())
+                .copied()
+                .unwrap_or(0);
+            // Every indexed file gets a content hash, including files that
+            // produced no chunks, so hashes are the ground truth for files.
+            let actual_files = self
+                .file_hash_repo
+                .find_by_repository(repo.id())
+                .await?
+                .len() as u64;
+            if repo.chunk_count() != actual_chunks || repo.file_count() != actual_files {
+                issues.push(HealthIssue::CountMismatch {
+                    repository: repo.name().to_string(),
+                    path: repo.path().to_string(),
+                    recorded_chunks: repo.chunk_count(),
+                    actual_chunks,
+                    recorded_files: repo.file_count(),
+                    actual_files,
+                });
+            }
+        }
+
+        Ok(Some(issues))
+    }
+}
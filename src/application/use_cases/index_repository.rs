@@ -1,28 +1,38 @@
 use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
 use futures_util::StreamExt;
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
-use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
-use tracing::{debug, info, warn};
+use tracing::{debug, info, warn, Instrument};
 
 use crate::application::git_remote::detect_remote;
+use crate::application::metrics::{
+    CHUNKS_INDEXED_TOTAL, EMBEDDING_DURATION_SECONDS, INDEX_REQUESTS_TOTAL,
+};
 use crate::application::{
-    is_messaging_package, AnalysisRepository, CallGraphUseCase, ChannelEndpointRepository,
-    ChannelExtractor, ChannelResolver, EmbeddingService, FileHashRepository, MetadataRepository,
-    ParserService, ResolveChannelsUseCase, VectorRepository,
+    is_messaging_package, AnalysisRepository, CallGraphQuery, CallGraphUseCase,
+    ChannelEndpointRepository, ChannelExtractor, ChannelResolver, EmbeddingCache, EmbeddingService,
+    FileHashRepository, MetadataRepository, ParserService, PruneRepositoryUseCase,
+    ResolveChannelsUseCase, VectorRepository,
 };
 use crate::domain::{
     compute_file_hash, namespace_scope_id, ChannelEndpoint, DomainError, Embedding, EndpointSource,
-    FileHash, Language, LanguageStats, Repository, SymbolReference, VectorStore,
+    FileHash, IndexFilters, Language, LanguageStats, ReferenceKind, Repository, SymbolReference,
+    VectorStore,
 };
 
-/// Default number of concurrent `parse_only` calls during the parse phase.
-const DEFAULT_PARSE_CONCURRENCY: usize = 4;
+/// Number of files parsed at once when no `--jobs` is given: one per CPU.
+fn default_parse_concurrency() -> usize {
+    std::thread::available_parallelism().map_or(4, |n| n.get())
+}
 
 /// Number of chunks accumulated across files before a single `embed_chunks`
 /// call is issued.  Smaller values produce more frequent flushes and smoother
@@ -30,10 +40,212 @@ const DEFAULT_PARSE_CONCURRENCY: usize = 4;
 /// since `embed_chunks` processes all chunks in one call regardless of count.
 const CROSS_FILE_EMBED_BATCH: usize = 128;
 
+/// Files larger than this are skipped unless `with_max_file_size` raises the
+/// limit; past it a source file is almost always a generated bundle.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Leading bytes searched for a NUL to recognise binary files.
+const BINARY_SNIFF_LEN: u64 = 8 * 1024;
+
+/// Why `path` should not be indexed: over `max_file_size`, or binary (a NUL
+/// in its first [`BINARY_SNIFF_LEN`] bytes) whatever its extension says.
+/// Unreadable files return `None` and fail later with a proper warning.
+fn skip_reason(path: &Path, max_file_size: u64) -> Option<String> {
+    let len = std::fs::metadata(path).ok()?.len();
+    if len > max_file_size {
+        return Some(format!(
+            "{} bytes exceeds the {} byte limit",
+            len, max_file_size
+        ));
+    }
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .ok()?
+        .take(BINARY_SNIFF_LEN)
+        .read_to_end(&mut head)
+        .ok()?;
+    head.contains(&0).then(|| "binary content".to_string())
+}
+
+/// Canonicalise the paths given to `index` into the repository path and its
+/// roots. One path is the repository itself, and `None` keeps whatever roots
+/// were stored for it; several become roots relative to their deepest common
+/// parent, which is the repository path.
+fn resolve_roots(paths: &[&str]) -> Result<(PathBuf, Option<Vec<String>>), DomainError> {
+    let mut absolute = Vec::with_capacity(paths.len());
+    for path in paths {
+        let canonical = Path::new(path)
+            .canonicalize()
+            .map_err(|e| DomainError::InvalidInput(format!("Invalid path: {}", e)))?;
+        if !absolute.contains(&canonical) {
+            absolute.push(canonical);
+        }
+    }
+    let Some(first) = absolute.first() else {
+        return Err(DomainError::invalid_input("No path to index"));
+    };
+    if absolute.len() == 1 {
+        return Ok((first.clone(), None));
+    }
+
+    let mut base = first.clone();
+    while !absolute.iter().all(|p| p.starts_with(&base)) {
+        if !base.pop() {
+            return Err(DomainError::invalid_input("Paths share no common parent"));
+        }
+    }
+    for (i, a) in absolute.iter().enumerate() {
+        if let Some(b) = absolute
+            .iter()
+            .skip(i + 1)
+            .find(|b| a.starts_with(b) || b.starts_with(a))
+        {
+            return Err(DomainError::invalid_input(format!(
+                "Root paths must not contain one another: {} and {}",
+                a.display(),
+                b.display()
+            )));
+        }
+    }
+    let mut roots: Vec<String> = absolute
+        .iter()
+        .map(|p| {
+            p.strip_prefix(&base)
+                .unwrap_or(p)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    roots.sort();
+    Ok((base, Some(roots)))
+}
+
+/// Walk `roots` (relative to `base`; empty walks all of `base`), skipping
+/// hidden and git-ignored files.
+fn walk_roots(base: &Path, roots: &[String]) -> ignore::Walk {
+    let mut dirs = roots.iter().map(|root| base.join(root));
+    let mut builder = WalkBuilder::new(dirs.next().unwrap_or_else(|| base.to_path_buf()));
+    for dir in dirs {
+        builder.add(dir);
+    }
+    builder
+        .hidden(true)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .build()
+}
+
+/// Whether the repository-relative `path` lies under one of `roots` (always
+/// true for a single-root repository).
+fn within_roots(path: &str, roots: &[String]) -> bool {
+    roots.is_empty() || roots.iter().any(|root| Path::new(path).starts_with(root))
+}
+
+/// Compile the user's include/exclude globs into a matcher rooted at the
+/// repository. Globs use `.gitignore` syntax and are relative to `root`.
+fn build_path_filter(root: &Path, filters: &IndexFilters) -> Result<Override, DomainError> {
+    let mut builder = OverrideBuilder::new(root);
+    let globs = filters
+        .include
+        .iter()
+        .cloned()
+        .chain(filters.exclude.iter().map(|g| format!("!{}", g)));
+    for glob in globs {
+        builder
+            .add(&glob)
+            .map_err(|e| DomainError::InvalidInput(format!("Invalid glob '{}': {}", glob, e)))?;
+    }
+    builder
+        .build()
+        .map_err(|e| DomainError::InvalidInput(format!("Invalid index filters: {}", e)))
+}
+
+/// Whether `path` is dropped by the include/exclude globs. Applied to files
+/// that already survived `.gitignore` filtering, so an include glob can never
+/// pull an ignored file back in.
+fn is_filtered_out(path_filter: &Override, path: &Path) -> bool {
+    path_filter.matched(path, false).is_ignore()
+}
+
+/// Detect the language of a file on disk.  Extension-less files (e.g.
+/// `bin/deploy`) have their first line read so a shebang can identify them;
+/// everything else is decided by [`Language::from_path`] without I/O.
+fn detect_language(path: &Path, header_language: Language) -> Language {
+    let mut head = String::new();
+    if path.extension().is_none() {
+        if let Ok(file) = std::fs::File::open(path) {
+            let _ = BufReader::new(file).take(256).read_line(&mut head);
+        }
+    }
+    language_of(path, &head, header_language)
+}
+
+/// [`Language::from_path_and_content`], except that `.h` headers — valid C
+/// and C++ alike — are parsed as `header_language`.
+fn language_of(path: &Path, content: &str, header_language: Language) -> Language {
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("h"))
+    {
+        return header_language;
+    }
+    Language::from_path_and_content(path, content)
+}
+
 /// Return type of [`do_flush`]: file count, chunk count, ref count, and per-
 /// language stats accumulated for the flushed batch.
 type FlushStats = (u64, u64, u64, HashMap<String, LanguageStats>);
 
+/// How far an indexing run has got, reported after every file is parsed.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexProgress {
+    pub files_done: u64,
+    pub files_total: u64,
+    /// Repository-relative path of the file just parsed; empty when the file
+    /// could not be read or parsed.
+    pub current_file: String,
+    /// Chunks parsed so far in this run.
+    pub chunks_so_far: u64,
+    /// Chunks embedded so far in this run (cache hits included). Embedding
+    /// trails parsing, so this lags `chunks_so_far`.
+    pub embeddings_so_far: u64,
+}
+
+/// What [`IndexRepositoryUseCase::execute_incremental`] changed, judged by
+/// the stored file hashes before and after the run.
+#[derive(Debug, Clone)]
+pub struct IndexUpdate {
+    /// The repository as left by the run.
+    pub repository: Repository,
+    pub files_added: u64,
+    /// Files whose content changed (every surviving file on a forced run).
+    pub files_changed: u64,
+    pub files_removed: u64,
+    /// Net change in the repository's chunk count.
+    pub chunk_delta: i64,
+}
+
+/// How many chunks the embedding cache answered during an index run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct EmbeddingCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl EmbeddingCacheStats {
+    /// Share of looked-up chunks served from the cache, or `None` when
+    /// nothing was looked up.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// Receives [`IndexProgress`] updates. Called from the indexing task, so it
+/// should return quickly.
+pub type IndexProgressCallback = Arc<dyn Fn(IndexProgress) + Send + Sync>;
+
 /// Port trait for the SCIP indexing phase.
 ///
 /// Implementations live in the connector layer (e.g. `ScipRunner`) so
@@ -58,6 +270,57 @@ pub trait Scip: Send + Sync {
         has_js_ts: bool,
         has_php: bool,
     ) -> Result<HashMap<String, Vec<SymbolReference>>, DomainError>;
+
+    /// Standard error of each indexer the last [`Scip::run`] invoked, as
+    /// `(indexer, stderr)` pairs, for the indexing report.
+    fn indexer_stderr(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// One file changed since a revision, as reported by [`GitDiff`]. Paths are
+/// relative to the indexed directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitChange {
+    Added(String),
+    Modified(String),
+    Deleted(String),
+    Renamed { from: String, to: String },
+}
+
+/// Lines of one file changed since a revision, as reported by
+/// [`GitDiff::changed_lines`]. Ranges are 1-based and inclusive, in the
+/// working-tree version of the file (the old version for a deleted file).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedLines {
+    pub file_path: String,
+    pub ranges: Vec<(u32, u32)>,
+}
+
+/// Port trait for reading git state (e.g. `GitCli`, which shells out to the
+/// `git` binary).
+#[async_trait::async_trait]
+pub trait GitDiff: Send + Sync {
+    /// The commit `HEAD` points at, or `None` when `repo_path` is not in a
+    /// git work tree or git is unavailable.
+    async fn head(&self, repo_path: &Path) -> Option<String>;
+
+    /// Files changed between `since` and the working tree, untracked files
+    /// included. Returns `Err` when git cannot compute the diff (unknown
+    /// revision, not a work tree, no `git` binary).
+    async fn changes_since(
+        &self,
+        repo_path: &Path,
+        since: &str,
+    ) -> Result<Vec<GitChange>, DomainError>;
+
+    /// Line ranges changed between `since` and the working tree, per file.
+    /// An untracked file counts as changed throughout.
+    async fn changed_lines(
+        &self,
+        repo_path: &Path,
+        since: &str,
+    ) -> Result<Vec<ChangedLines>, DomainError>;
 }
 
 pub struct IndexRepositoryUseCase {
@@ -83,8 +346,30 @@ pub struct IndexRepositoryUseCase {
     /// execution features).  Stored analyses are invalidated whenever indexing
     /// changes the call graph they were computed from.
     analysis_repo: Option<Arc<dyn AnalysisRepository>>,
-    /// Maximum number of concurrent `parse_only` calls.
+    /// Number of files parsed at once, each on a blocking worker thread
+    /// (`index --jobs`).
     parse_concurrency: usize,
+    /// Language `.h` headers are parsed as (C unless overridden).
+    header_language: Language,
+    /// Optional per-file progress listener (CLI progress line, SSE stream).
+    progress_callback: Option<IndexProgressCallback>,
+    /// Whether Markdown and plain-text documentation is indexed alongside code.
+    index_docs: bool,
+    /// Optional git access: records the indexed commit and serves `since`.
+    git: Option<Arc<dyn GitDiff>>,
+    /// `index --since`: take the changed files of an incremental run from
+    /// `git diff` instead of hashing every file. `Some(None)` diffs against
+    /// the commit recorded by the previous run.
+    since: Option<Option<String>>,
+    /// Optional store of vectors by chunk content, consulted before the
+    /// embedder so unchanged chunks of a re-indexed file are not re-embedded.
+    embedding_cache: Option<Arc<dyn EmbeddingCache>>,
+    /// Embedding and cache counts since the current run started.
+    embed_counters: Arc<EmbedCounters>,
+    /// Files larger than this many bytes are not indexed.
+    max_file_size: u64,
+    /// Files skipped as too large or binary since the current run started.
+    skipped_files: Arc<AtomicU64>,
 }
 
 impl IndexRepositoryUseCase {
@@ -108,7 +393,16 @@ impl IndexRepositoryUseCase {
             channel_endpoint_repo: None,
             channel_resolver: None,
             analysis_repo: None,
-            parse_concurrency: DEFAULT_PARSE_CONCURRENCY,
+            parse_concurrency: default_parse_concurrency(),
+            header_language: Language::C,
+            progress_callback: None,
+            index_docs: true,
+            git: None,
+            since: None,
+            embedding_cache: None,
+            embed_counters: Arc::default(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            skipped_files: Arc::default(),
         }
     }
 
@@ -118,6 +412,70 @@ impl IndexRepositoryUseCase {
         self
     }
 
+    /// Skip files larger than `bytes` (default [`DEFAULT_MAX_FILE_SIZE`]).
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// What the SCIP indexers of the last run wrote to standard error.
+    pub fn scip_stderr(&self) -> Vec<(String, String)> {
+        self.scip
+            .as_ref()
+            .map(|scip| scip.indexer_stderr())
+            .unwrap_or_default()
+    }
+
+    /// Files the last run skipped as too large or binary.
+    pub fn skipped_file_count(&self) -> u64 {
+        self.skipped_files.load(Ordering::Relaxed)
+    }
+
+    /// Whether `path` is too large or binary to index; counted and logged.
+    fn skips_file(&self, path: &Path) -> bool {
+        let Some(reason) = skip_reason(path, self.max_file_size) else {
+            return false;
+        };
+        debug!("Skipping {}: {}", path.display(), reason);
+        self.skipped_files.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+
+    /// Reuse vectors from `cache` for chunk content embedded before with the
+    /// same model, and store fresh vectors in it.
+    pub fn with_embedding_cache(mut self, cache: Arc<dyn EmbeddingCache>) -> Self {
+        self.embedding_cache = Some(cache);
+        self
+    }
+
+    /// Embedding cache hits and misses of the last run (all zero without a
+    /// cache).
+    pub fn embedding_cache_stats(&self) -> EmbeddingCacheStats {
+        self.embed_counters.snapshot()
+    }
+
+    fn embedder(&self) -> CachedEmbedder {
+        CachedEmbedder {
+            service: Arc::clone(&self.embedding_service),
+            cache: self.embedding_cache.clone(),
+            counters: Arc::clone(&self.embed_counters),
+        }
+    }
+
+    /// Attach git access, so each run records the commit it indexed.
+    pub fn with_git(mut self, git: Arc<dyn GitDiff>) -> Self {
+        self.git = Some(git);
+        self
+    }
+
+    /// Drive incremental runs from `git diff <rev>`; `None` diffs against
+    /// the commit recorded by the previous run. Needs [`Self::with_git`];
+    /// falls back to hashing every file when git cannot answer.
+    pub fn with_since(mut self, rev: Option<String>) -> Self {
+        self.since = Some(rev);
+        self
+    }
+
     /// Attach channel-endpoint extraction (cross-service linking).
     pub fn with_channel_extraction(
         mut self,
@@ -142,6 +500,20 @@ impl IndexRepositoryUseCase {
         self
     }
 
+    /// Stale-file pruning over this use case's stores.
+    fn prune_use_case(&self) -> PruneRepositoryUseCase {
+        let prune = PruneRepositoryUseCase::new(
+            self.repository_repo.clone(),
+            self.vector_repo.clone(),
+            self.file_hash_repo.clone(),
+            self.call_graph_use_case.clone(),
+        );
+        match &self.channel_endpoint_repo {
+            Some(channel_repo) => prune.with_channel_endpoints(channel_repo.clone()),
+            None => prune,
+        }
+    }
+
     /// Drop every stored analysis for `repository_id`.  Called after indexing
     /// changes the call graph, since stored analyses derive entirely from it.
     ///
@@ -170,12 +542,50 @@ impl IndexRepositoryUseCase {
         }
     }
 
-    /// Set the maximum number of concurrent parse tasks.
+    /// Replace the parser, e.g. with one configured for a different chunk
+    /// size.
+    pub fn with_parser_service(mut self, parser_service: Arc<dyn ParserService>) -> Self {
+        self.parser_service = parser_service;
+        self
+    }
+
+    /// Include (the default) or skip Markdown and plain-text documentation.
+    pub fn with_docs(mut self, index_docs: bool) -> Self {
+        self.index_docs = index_docs;
+        self
+    }
+
+    /// `true` when files of `language` are parsed and indexed.
+    fn indexes_language(&self, language: Language) -> bool {
+        language != Language::Unknown
+            && (self.index_docs || language != Language::Markdown)
+            && self.parser_service.supports_language(language)
+    }
+
+    /// Set the number of files parsed in parallel (defaults to the CPU count).
     pub fn with_parse_concurrency(mut self, n: usize) -> Self {
         self.parse_concurrency = n.max(1);
         self
     }
 
+    /// Report progress to `callback` after every file is parsed.
+    pub fn with_progress_callback(mut self, callback: IndexProgressCallback) -> Self {
+        self.progress_callback = Some(callback);
+        self
+    }
+
+    fn report_progress(&self, progress: impl FnOnce() -> IndexProgress) {
+        if let Some(callback) = &self.progress_callback {
+            callback(progress());
+        }
+    }
+
+    /// Parse `.h` headers as `language` (C or C++) instead of C.
+    pub fn with_header_language(mut self, language: Language) -> Self {
+        self.header_language = language;
+        self
+    }
+
     /// Delegate to the injected [`Scip`] indexer, or return an empty map when
     /// none is configured (e.g. in tests).
     ///
@@ -203,7 +613,7 @@ impl IndexRepositoryUseCase {
             batch,
             repository_id,
             Arc::clone(scip_refs),
-            Arc::clone(&self.embedding_service),
+            self.embedder(),
         ))
     }
 
@@ -217,6 +627,8 @@ impl IndexRepositoryUseCase {
         ))
     }
 
+    /// Index `path`, reusing the include/exclude globs stored for the
+    /// repository (if it was indexed before).
     pub async fn execute(
         &self,
         path: &str,
@@ -225,16 +637,119 @@ impl IndexRepositoryUseCase {
         namespace: Option<String>,
         force: bool,
     ) -> Result<Repository, DomainError> {
-        let path = Path::new(path);
-        let absolute_path = path
+        self.execute_with_filters(path, name, store, namespace, force, None)
+            .await
+    }
+
+    /// Bring the index of `path` up to date and report which files were
+    /// added, changed and removed since the last run. Only changed and new
+    /// files are re-parsed (everything when `force` is set); a repository
+    /// that was never indexed is indexed in full.
+    pub async fn execute_incremental(
+        &self,
+        path: &str,
+        name: Option<&str>,
+        store: VectorStore,
+        namespace: Option<String>,
+        force: bool,
+    ) -> Result<IndexUpdate, DomainError> {
+        let absolute_path = Path::new(path)
             .canonicalize()
             .map_err(|e| DomainError::InvalidInput(format!("Invalid path: {}", e)))?;
+        let existing = self
+            .repository_repo
+            .find_by_path(&absolute_path.to_string_lossy())
+            .await?;
+        let (before, chunks_before) = match &existing {
+            Some(repository) => (
+                self.file_hashes(repository.id()).await?,
+                repository.chunk_count(),
+            ),
+            None => (HashMap::new(), 0),
+        };
+
+        let repository = self.execute(path, name, store, namespace, force).await?;
+        let after = self.file_hashes(repository.id()).await?;
+
+        let files_added = after.keys().filter(|p| !before.contains_key(*p)).count();
+        let files_removed = before.keys().filter(|p| !after.contains_key(*p)).count();
+        let files_changed = after
+            .iter()
+            .filter(|(p, hash)| before.get(*p).is_some_and(|old| force || old != *hash))
+            .count();
+
+        Ok(IndexUpdate {
+            files_added: files_added as u64,
+            files_changed: files_changed as u64,
+            files_removed: files_removed as u64,
+            chunk_delta: repository.chunk_count() as i64 - chunks_before as i64,
+            repository,
+        })
+    }
+
+    /// Stored content hash of every indexed file, keyed by relative path.
+    async fn file_hashes(
+        &self,
+        repository_id: &str,
+    ) -> Result<HashMap<String, String>, DomainError> {
+        Ok(self
+            .file_hash_repo
+            .find_by_repository(repository_id)
+            .await?
+            .into_iter()
+            .map(|h| (h.file_path().to_string(), h.content_hash().to_string()))
+            .collect())
+    }
+
+    /// Index `path` with explicit include/exclude globs.
+    ///
+    /// `filters` – when `Some`, replaces the globs stored for the repository;
+    ///             when `None`, the stored globs (if any) are reused.
+    pub async fn execute_with_filters(
+        &self,
+        path: &str,
+        name: Option<&str>,
+        store: VectorStore,
+        namespace: Option<String>,
+        force: bool,
+        filters: Option<IndexFilters>,
+    ) -> Result<Repository, DomainError> {
+        self.execute_with_roots(&[path], name, store, namespace, force, filters)
+            .await
+    }
+
+    /// Index one repository made of several directories. With more than one
+    /// path the repository lives at their common parent and only the given
+    /// directories are walked; all of them share one repository id. A single
+    /// path behaves like [`Self::execute_with_filters`], keeping any roots
+    /// stored for it.
+    pub async fn execute_with_roots(
+        &self,
+        paths: &[&str],
+        name: Option<&str>,
+        store: VectorStore,
+        namespace: Option<String>,
+        force: bool,
+        filters: Option<IndexFilters>,
+    ) -> Result<Repository, DomainError> {
+        metrics::counter!(INDEX_REQUESTS_TOTAL).increment(1);
+        self.embed_counters.reset();
+        self.skipped_files.store(0, Ordering::Relaxed);
+        let (absolute_path, roots) = resolve_roots(paths)?;
 
         let path_str = absolute_path.to_string_lossy().to_string();
 
         // Check if repository already exists
         let existing = self.repository_repo.find_by_path(&path_str).await?;
 
+        let roots = roots
+            .or_else(|| existing.as_ref().map(|r| r.roots().to_vec()))
+            .unwrap_or_default();
+        let filters = filters
+            .or_else(|| existing.as_ref().map(|r| r.index_filters().clone()))
+            .unwrap_or_default();
+        let path_filter = build_path_filter(&absolute_path, &filters)?;
+
         if force {
             // Force re-index: delete everything and start fresh
             if let Some(ref existing) = existing {
@@ -256,25 +771,63 @@ impl IndexRepositoryUseCase {
                     .await;
                 self.repository_repo.delete(existing.id()).await?;
             }
-            return self
-                .index(&absolute_path, &path_str, name, store, namespace)
-                .await;
+            let mut repository = self
+                .index(
+                    &absolute_path,
+                    &path_str,
+                    name,
+                    store,
+                    namespace,
+                    filters,
+                    roots,
+                    &path_filter,
+                )
+                .await?;
+            self.record_indexed_commit(&absolute_path, &mut repository)
+                .await?;
+            return Ok(repository);
         }
 
-        match existing {
-            Some(repository) => {
+        let mut repository = match existing {
+            Some(mut repository) => {
                 // Incremental indexing
                 info!("Incremental indexing repository: {}", path_str);
-                self.incremental_index(&absolute_path, &repository).await
+                if repository.index_filters() != &filters {
+                    self.repository_repo
+                        .update_index_filters(repository.id(), &filters)
+                        .await?;
+                    repository.set_index_filters(filters);
+                }
+                if repository.roots() != roots.as_slice() {
+                    self.repository_repo
+                        .update_roots(repository.id(), &roots)
+                        .await?;
+                    repository.set_roots(roots);
+                }
+                self.incremental_index(&absolute_path, &repository, &path_filter)
+                    .await?
             }
             None => {
                 // First-time indexing
-                self.index(&absolute_path, &path_str, name, store, namespace)
-                    .await
+                self.index(
+                    &absolute_path,
+                    &path_str,
+                    name,
+                    store,
+                    namespace,
+                    filters,
+                    roots,
+                    &path_filter,
+                )
+                .await?
             }
-        }
+        };
+        self.record_indexed_commit(&absolute_path, &mut repository)
+            .await?;
+        Ok(repository)
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn index(
         &self,
         absolute_path: &Path,
@@ -282,6 +835,9 @@ impl IndexRepositoryUseCase {
         name: Option<&str>,
         store: VectorStore,
         namespace: Option<String>,
+        filters: IndexFilters,
+        roots: Vec<String>,
+        path_filter: &Override,
     ) -> Result<Repository, DomainError> {
         let repo_name = name.map(String::from).unwrap_or_else(|| {
             absolute_path
@@ -298,13 +854,15 @@ impl IndexRepositoryUseCase {
             debug!("Detected git remote '{}' for {}", remote, repo_name);
         }
 
-        let repository = Repository::new_with_storage(
+        let mut repository = Repository::new_with_storage(
             repo_name.clone(),
             path_str.to_string(),
             store,
             namespace,
             git_remote,
         );
+        repository.set_index_filters(filters);
+        repository.set_roots(roots);
         self.repository_repo.save(&repository).await?;
 
         info!("Indexing repository: {} at {}", repo_name, path_str);
@@ -312,24 +870,22 @@ impl IndexRepositoryUseCase {
         let start_time = Instant::now();
 
         // First pass: collect all files to process
-        let files_to_process: Vec<PathBuf> = WalkBuilder::new(absolute_path)
-            .hidden(true)
-            .git_ignore(true)
-            .git_global(true)
-            .git_exclude(true)
-            .build()
+        let files_to_process: Vec<PathBuf> = walk_roots(absolute_path, repository.roots())
             .filter_map(|entry| entry.ok())
             .filter(|entry| entry.path().is_file())
+            .filter(|entry| !is_filtered_out(path_filter, entry.path()))
             .filter(|entry| {
-                let language = Language::from_path(entry.path());
-                language != Language::Unknown && self.parser_service.supports_language(language)
+                self.indexes_language(detect_language(entry.path(), self.header_language))
             })
+            .filter(|entry| !self.skips_file(entry.path()))
             .map(|entry| entry.path().to_path_buf())
             .collect();
 
         let total_files = files_to_process.len() as u64;
         info!("Found {} files to index", total_files);
 
+        // Oversized and binary files are already out of the list, so a repo
+        // whose only JavaScript is a generated bundle never starts SCIP.
         let has_js_ts = files_to_process.iter().any(|p| {
             matches!(
                 Language::from_path(p),
@@ -344,13 +900,8 @@ impl IndexRepositoryUseCase {
                 .await?,
         );
 
-        let progress_bar = ProgressBar::new(total_files);
-        progress_bar.set_style(
-            ProgressStyle::default_bar()
-                .template("  {spinner:.green} {bar:40.green/dim} {pos}/{len} {msg:.dim}")
-                .expect("Invalid progress bar template")
-                .progress_chars("━━─"),
-        );
+        let mut files_done = 0u64;
+        let mut chunks_parsed = 0u64;
 
         let mut file_count = 0u64;
         let mut chunk_count = 0u64;
@@ -360,8 +911,9 @@ impl IndexRepositoryUseCase {
         // Parsing and embedding/writing are fully pipelined:
         //
         //  • The parse stream runs in its own tokio task (spawn_parse_stream),
-        //    so its futures advance on the thread pool independently of this
-        //    task.  Results are sent through an mpsc channel.
+        //    parsing `parse_concurrency` files at once on blocking workers,
+        //    independently of this task.  Results are sent through an mpsc
+        //    channel.
         //
         //  • Each full batch is handed to a spawned flush task (do_flush).
         //    At most one flush is in-flight at a time (double-buffering).
@@ -369,15 +921,14 @@ impl IndexRepositoryUseCase {
         //  • When this task awaits a flush handle, the parse task keeps
         //    running and the channel buffers incoming results.  On a large
         //    repo the parser stays comfortably ahead of the embedder.
-        let parse_concurrency = (self.parse_concurrency * 4).max(8);
-
         let mut parse_rx = spawn_parse_stream(
             files_to_process,
             absolute_path.to_path_buf(),
             repository.id().to_string(),
             self.parser_service.clone(),
             self.channel_extractor.clone(),
-            parse_concurrency,
+            self.header_language,
+            self.parse_concurrency,
         );
 
         let mut pending: Vec<ParseOnlyResult> = Vec::new();
@@ -388,9 +939,19 @@ impl IndexRepositoryUseCase {
         let mut write_handle: Option<JoinHandle<Result<FlushStats, DomainError>>> = None;
 
         while let Some(maybe_result) = parse_rx.recv().await {
-            progress_bar.inc(1);
+            files_done += 1;
+            chunks_parsed += maybe_result.as_ref().map_or(0, |r| r.chunks.len() as u64);
+            self.report_progress(|| IndexProgress {
+                files_done,
+                files_total: total_files,
+                current_file: maybe_result
+                    .as_ref()
+                    .map(|r| r.relative_path.clone())
+                    .unwrap_or_default(),
+                chunks_so_far: chunks_parsed,
+                embeddings_so_far: self.embed_counters.embedded(),
+            });
             if let Some(result) = maybe_result {
-                progress_bar.set_message(result.relative_path.clone());
                 pending_chunk_count += result.chunks.len();
                 pending.push(result);
 
@@ -452,7 +1013,7 @@ impl IndexRepositoryUseCase {
                 std::mem::take(&mut pending),
                 repository.id().to_string(),
                 Arc::clone(&scip_refs),
-                Arc::clone(&self.embedding_service),
+                self.embedder(),
             )
             .await?;
             let stats = do_write(
@@ -472,8 +1033,6 @@ impl IndexRepositoryUseCase {
             );
         }
 
-        progress_bar.finish_and_clear();
-
         self.repository_repo
             .update_stats(repository.id(), chunk_count, file_count)
             .await?;
@@ -484,8 +1043,14 @@ impl IndexRepositoryUseCase {
 
         // Cross-file channel resolution: confirm libraries via SCIP and resolve
         // config-driven channels. Runs after all endpoints are persisted.
-        self.resolve_channels(repository.id(), absolute_path, &scip_refs)
-            .await?;
+        self.resolve_channels(
+            repository.id(),
+            absolute_path,
+            repository.roots(),
+            path_filter,
+            &scip_refs,
+        )
+        .await?;
 
         let duration = start_time.elapsed();
         info!(
@@ -510,6 +1075,8 @@ impl IndexRepositoryUseCase {
         &self,
         repository_id: &str,
         absolute_path: &Path,
+        roots: &[String],
+        path_filter: &Override,
         scip_refs: &HashMap<String, Vec<SymbolReference>>,
     ) -> Result<(), DomainError> {
         let (Some(resolver), Some(repo)) = (&self.channel_resolver, &self.channel_endpoint_repo)
@@ -550,7 +1117,7 @@ impl IndexRepositoryUseCase {
                 .flatten()
                 .any(|r| r.callee_package().is_some_and(is_messaging_package));
         let (config_candidates, sources_by_file) = if needs_resolution {
-            discover_config_candidates(absolute_path).await
+            discover_config_candidates(absolute_path, roots.to_vec(), path_filter.clone()).await
         } else {
             (Vec::new(), HashMap::new())
         };
@@ -582,10 +1149,163 @@ impl IndexRepositoryUseCase {
         Ok(())
     }
 
+    /// Relative path and content hash of every indexable file under
+    /// `absolute_path` (only under `roots` when there are any).
+    async fn walk_current_files(
+        &self,
+        absolute_path: &Path,
+        roots: &[String],
+        path_filter: &Override,
+    ) -> HashMap<String, String> {
+        let mut current_files = HashMap::new();
+        for entry in walk_roots(absolute_path, roots) {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(e) => {
+                    warn!("Error walking directory: {}", e);
+                    continue;
+                }
+            };
+            if let Some((relative_path, content_hash)) = self
+                .hash_file(absolute_path, entry.path(), path_filter)
+                .await
+            {
+                current_files.insert(relative_path, content_hash);
+            }
+        }
+        current_files
+    }
+
+    /// The stored hashes of `existing` brought up to date with `changes`:
+    /// deleted and renamed-away paths are dropped and only added, modified
+    /// and renamed-to files are read and hashed.
+    async fn current_files_from_git(
+        &self,
+        absolute_path: &Path,
+        roots: &[String],
+        path_filter: &Override,
+        existing: &HashMap<String, String>,
+        changes: Vec<GitChange>,
+    ) -> HashMap<String, String> {
+        let mut current_files = existing.clone();
+        let mut touched = Vec::new();
+        for change in changes {
+            match change {
+                GitChange::Added(path) | GitChange::Modified(path) => touched.push(path),
+                GitChange::Deleted(path) => {
+                    current_files.remove(&path);
+                }
+                GitChange::Renamed { from, to } => {
+                    current_files.remove(&from);
+                    touched.push(to);
+                }
+            }
+        }
+        for path in touched {
+            current_files.remove(&path);
+            if !within_roots(&path, roots) {
+                continue;
+            }
+            // The walk skips hidden files; so does the diff.
+            if Path::new(&path)
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+            {
+                continue;
+            }
+            if let Some((relative_path, content_hash)) = self
+                .hash_file(absolute_path, &absolute_path.join(&path), path_filter)
+                .await
+            {
+                current_files.insert(relative_path, content_hash);
+            }
+        }
+        current_files
+    }
+
+    /// Relative path and content hash of `entry_path`, or `None` when it is
+    /// not an indexable file (filtered out, unsupported language, unreadable).
+    async fn hash_file(
+        &self,
+        absolute_path: &Path,
+        entry_path: &Path,
+        path_filter: &Override,
+    ) -> Option<(String, String)> {
+        if !entry_path.is_file() || is_filtered_out(path_filter, entry_path) {
+            return None;
+        }
+
+        let language = detect_language(entry_path, self.header_language);
+        if !self.indexes_language(language) || self.skips_file(entry_path) {
+            return None;
+        }
+
+        let relative_path = entry_path
+            .strip_prefix(absolute_path)
+            .unwrap_or(entry_path)
+            .to_string_lossy()
+            .to_string();
+
+        let content = match tokio::fs::read_to_string(entry_path).await {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("Failed to read file {}: {}", relative_path, e);
+                return None;
+            }
+        };
+
+        let content_hash = compute_file_hash(&content);
+        Some((relative_path, content_hash))
+    }
+
+    /// Files changed since the `--since` revision (the recorded indexed
+    /// commit when none was given), or `None` to fall back to a full walk:
+    /// no `--since`, no git, no recorded commit, or git failed.
+    async fn git_changes(
+        &self,
+        absolute_path: &Path,
+        repository: &Repository,
+    ) -> Option<Vec<GitChange>> {
+        let since = self.since.as_ref()?;
+        let git = self.git.as_ref()?;
+        let Some(rev) = since.as_deref().or(repository.indexed_commit()) else {
+            info!("No indexed commit recorded yet; hashing every file");
+            return None;
+        };
+        match git.changes_since(absolute_path, rev).await {
+            Ok(changes) => {
+                info!("git reports {} changed files since {}", changes.len(), rev);
+                Some(changes)
+            }
+            Err(e) => {
+                warn!("git diff against {} failed, hashing every file: {}", rev, e);
+                None
+            }
+        }
+    }
+
+    /// Record the commit `HEAD` points at as the one the index is synced to.
+    async fn record_indexed_commit(
+        &self,
+        absolute_path: &Path,
+        repository: &mut Repository,
+    ) -> Result<(), DomainError> {
+        let Some(git) = &self.git else {
+            return Ok(());
+        };
+        let head = git.head(absolute_path).await;
+        self.repository_repo
+            .update_indexed_commit(repository.id(), head.as_deref())
+            .await?;
+        repository.set_indexed_commit(head);
+        Ok(())
+    }
+
     async fn incremental_index(
         &self,
         absolute_path: &Path,
         repository: &Repository,
+        path_filter: &Override,
     ) -> Result<Repository, DomainError> {
         let start_time = Instant::now();
 
@@ -600,60 +1320,26 @@ impl IndexRepositoryUseCase {
         }
 
         // Load existing file hashes
-        let existing_hashes = self
-            .file_hash_repo
-            .find_by_repository(repository.id())
-            .await?;
-        let existing_hash_map: HashMap<String, String> = existing_hashes
-            .into_iter()
-            .map(|h| (h.file_path().to_string(), h.content_hash().to_string()))
-            .collect();
-
-        // Collect current files
-        let mut current_files: HashMap<String, String> = HashMap::new();
-        let walker = WalkBuilder::new(absolute_path)
-            .hidden(true)
-            .git_ignore(true)
-            .git_global(true)
-            .git_exclude(true)
-            .build();
-
-        for entry in walker {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(e) => {
-                    warn!("Error walking directory: {}", e);
-                    continue;
-                }
-            };
-            let entry_path = entry.path();
-
-            if !entry_path.is_file() {
-                continue;
+        let existing_hash_map = self.file_hashes(repository.id()).await?;
+
+        // Collect current files: from `git diff` when `--since` was given
+        // and git can answer, otherwise by hashing every file in the tree.
+        let current_files = match self.git_changes(absolute_path, repository).await {
+            Some(changes) => {
+                self.current_files_from_git(
+                    absolute_path,
+                    repository.roots(),
+                    path_filter,
+                    &existing_hash_map,
+                    changes,
+                )
+                .await
             }
-
-            let language = Language::from_path(entry_path);
-            if language == Language::Unknown || !self.parser_service.supports_language(language) {
-                continue;
+            None => {
+                self.walk_current_files(absolute_path, repository.roots(), path_filter)
+                    .await
             }
-
-            let relative_path = entry_path
-                .strip_prefix(absolute_path)
-                .unwrap_or(entry_path)
-                .to_string_lossy()
-                .to_string();
-
-            let content = match tokio::fs::read_to_string(entry_path).await {
-                Ok(c) => c,
-                Err(e) => {
-                    warn!("Failed to read file {}: {}", relative_path, e);
-                    continue;
-                }
-            };
-
-            let content_hash = compute_file_hash(&content);
-            current_files.insert(relative_path, content_hash);
-        }
+        };
 
         // Detect changes
         let current_paths: HashSet<&String> = current_files.keys().collect();
@@ -751,13 +1437,8 @@ impl IndexRepositoryUseCase {
             .collect();
         let total_to_process = files_to_process.len() as u64;
 
-        let progress_bar = ProgressBar::new(total_to_process);
-        progress_bar.set_style(
-            ProgressStyle::default_bar()
-                .template("  {spinner:.green} {bar:40.green/dim} {pos}/{len} {msg:.dim}")
-                .expect("Invalid progress bar template")
-                .progress_chars("━━─"),
-        );
+        let mut files_done = 0u64;
+        let mut chunks_parsed = 0u64;
 
         let mut new_processed_paths: HashSet<String> = HashSet::new();
         let mut processed_count = 0u64;
@@ -769,15 +1450,14 @@ impl IndexRepositoryUseCase {
         // just for the hash in the sequential phase.
         let current_files_snapshot = current_files.clone();
 
-        let parse_concurrency = (self.parse_concurrency * 4).max(8);
-
         let mut parse_rx = spawn_parse_stream(
             files_to_process,
             absolute_path.to_path_buf(),
             repository.id().to_string(),
             self.parser_service.clone(),
             self.channel_extractor.clone(),
-            parse_concurrency,
+            self.header_language,
+            self.parse_concurrency,
         );
 
         let mut pending: Vec<ParseOnlyResult> = Vec::new();
@@ -786,10 +1466,19 @@ impl IndexRepositoryUseCase {
         let mut write_handle: Option<JoinHandle<Result<FlushStats, DomainError>>> = None;
 
         while let Some(maybe_result) = parse_rx.recv().await {
-            progress_bar.inc(1);
+            files_done += 1;
+            chunks_parsed += maybe_result.as_ref().map_or(0, |r| r.chunks.len() as u64);
+            self.report_progress(|| IndexProgress {
+                files_done,
+                files_total: total_to_process,
+                current_file: maybe_result
+                    .as_ref()
+                    .map(|r| r.relative_path.clone())
+                    .unwrap_or_default(),
+                chunks_so_far: chunks_parsed,
+                embeddings_so_far: self.embed_counters.embedded(),
+            });
             if let Some(mut result) = maybe_result {
-                progress_bar.set_message(result.relative_path.clone());
-
                 // Only fall back to the walk hash when parse_only did not
                 // produce one (empty); the parse_only hash is derived from
                 // the content that was actually read and parsed, so it is
@@ -862,7 +1551,7 @@ impl IndexRepositoryUseCase {
                 std::mem::take(&mut pending),
                 repository.id().to_string(),
                 Arc::clone(&scip_refs),
-                Arc::clone(&self.embedding_service),
+                self.embedder(),
             )
             .await?;
             let stats = do_write(
@@ -882,8 +1571,6 @@ impl IndexRepositoryUseCase {
             );
         }
 
-        progress_bar.finish_and_clear();
-
         // SCIP references for unchanged files. Rewriting these edges also makes
         // derived analyses stale, so flag the call graph as changed whenever the
         // resync touches at least one unchanged file.
@@ -892,12 +1579,25 @@ impl IndexRepositoryUseCase {
                 continue;
             }
             call_graph_changed = true;
+            let mut stored = Vec::new();
+            for kind in [ReferenceKind::Implementation, ReferenceKind::Override] {
+                stored.extend(
+                    self.call_graph_use_case
+                        .find_by_file(
+                            relative_path,
+                            &CallGraphQuery::new()
+                                .with_repository(repository.id())
+                                .with_reference_kind(kind.as_str()),
+                        )
+                        .await?,
+                );
+            }
             self.call_graph_use_case
                 .delete_by_file(repository.id(), relative_path)
                 .await?;
             new_reference_count += self
                 .call_graph_use_case
-                .save_references(file_refs)
+                .save_references(&with_implementations(file_refs, &stored))
                 .await
                 .map_err(|e| DomainError::internal(format!("{:#}", e)))?;
         }
@@ -906,7 +1606,7 @@ impl IndexRepositoryUseCase {
         for path in current_paths.intersection(&existing_paths) {
             if !modified.contains(path) {
                 let entry_path = absolute_path.join(*path);
-                let language = Language::from_path(&entry_path);
+                let language = detect_language(&entry_path, self.header_language);
                 if language != Language::Unknown {
                     let lang_key = language.as_str().to_string();
                     let stats = language_stats.entry(lang_key).or_default();
@@ -915,9 +1615,23 @@ impl IndexRepositoryUseCase {
             }
         }
 
+        // The hash diff only sees files that were hashed; chunks of files
+        // deleted without one (an interrupted run, an older index) are pruned
+        // by comparing the stored paths against the working tree.
+        let prune = self.prune_use_case();
+        let stale = prune.find_stale(repository).await?;
+        if !stale.is_empty() {
+            info!("Pruning {} stale files", stale.len());
+            deleted_chunk_count += prune.remove(repository.id(), &stale).await?;
+            call_graph_changed = true;
+        }
+
         let total_file_count = unchanged_count as u64 + processed_count;
         let previous_chunk_count = repository.chunk_count();
-        let total_chunk_count = previous_chunk_count - deleted_chunk_count + new_chunk_count;
+        // Saturate: a stale stored count (e.g. from an interrupted run) must
+        // not underflow when more chunks were deleted than were recorded.
+        let total_chunk_count =
+            previous_chunk_count.saturating_sub(deleted_chunk_count) + new_chunk_count;
 
         self.repository_repo
             .update_stats(repository.id(), total_chunk_count, total_file_count)
@@ -929,8 +1643,14 @@ impl IndexRepositoryUseCase {
 
         // Resolve across the full endpoint set (config resolution and library
         // confirmation can span changed and unchanged files).
-        self.resolve_channels(repository.id(), absolute_path, &scip_refs)
-            .await?;
+        self.resolve_channels(
+            repository.id(),
+            absolute_path,
+            repository.roots(),
+            path_filter,
+            &scip_refs,
+        )
+        .await?;
 
         if call_graph_changed {
             self.invalidate_analyses(repository.id(), repository.namespace())
@@ -953,6 +1673,147 @@ impl IndexRepositoryUseCase {
             .await?
             .ok_or_else(|| DomainError::internal("Repository not found after indexing"))
     }
+
+    /// Re-index one file of an already-indexed repository without walking
+    /// the rest of it.
+    ///
+    /// The file's chunks, call-graph references and channel endpoints are
+    /// dropped; if it still exists and passes the repository's filters it is
+    /// then re-parsed and re-embedded. SCIP only runs when the file is in a
+    /// language whose call graph comes from it. Repository chunk and file
+    /// counts are adjusted in place; per-language stats are left for the next
+    /// full incremental run.
+    pub async fn update_file(
+        &self,
+        repository_id: &str,
+        file_path: &Path,
+    ) -> Result<Repository, DomainError> {
+        let repository = self
+            .repository_repo
+            .find_by_id(repository_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Repository {repository_id}")))?;
+        let root = Path::new(repository.path());
+        let relative_path = file_path
+            .strip_prefix(root)
+            .map_err(|_| {
+                DomainError::InvalidInput(format!(
+                    "{} is not inside repository {}",
+                    file_path.display(),
+                    repository.path()
+                ))
+            })?
+            .to_string_lossy()
+            .to_string();
+
+        let was_indexed = self
+            .file_hash_repo
+            .find_by_repository(repository_id)
+            .await?
+            .iter()
+            .any(|h| h.file_path() == relative_path);
+
+        let deleted_chunk_count = self
+            .vector_repo
+            .delete_by_file_path(repository_id, &relative_path)
+            .await?;
+        self.call_graph_use_case
+            .delete_by_file(repository_id, &relative_path)
+            .await?;
+        if let Some(channel_repo) = &self.channel_endpoint_repo {
+            channel_repo
+                .delete_by_file_path(repository_id, &relative_path)
+                .await?;
+        }
+        self.file_hash_repo
+            .delete_by_paths(repository_id, std::slice::from_ref(&relative_path))
+            .await?;
+
+        let path_filter = build_path_filter(root, repository.index_filters())?;
+        let language = detect_language(file_path, self.header_language);
+        let indexable = file_path.is_file()
+            && within_roots(&relative_path, repository.roots())
+            && !is_filtered_out(&path_filter, file_path)
+            && self.indexes_language(language)
+            && !self.skips_file(file_path);
+
+        let mut stats: FlushStats = (0, 0, 0, HashMap::new());
+        if indexable {
+            let result = parse_only(
+                file_path.to_path_buf(),
+                root,
+                repository_id,
+                self.parser_service.as_ref(),
+                self.channel_extractor.as_deref(),
+                self.header_language,
+            )
+            .await;
+            if let Some(result) = result {
+                let is_js_ts = matches!(language, Language::JavaScript | Language::TypeScript);
+                let is_php = language == Language::Php;
+                let mut scip_refs = if is_js_ts || is_php {
+                    self.run_scip(root, repository_id, is_js_ts, is_php).await?
+                } else {
+                    HashMap::new()
+                };
+                scip_refs.retain(|path, _| *path == relative_path);
+                let scip_refs = Arc::new(scip_refs);
+                let has_endpoints = !result.endpoints.is_empty();
+
+                let embed = do_embed(
+                    vec![result],
+                    repository_id.to_string(),
+                    Arc::clone(&scip_refs),
+                    self.embedder(),
+                )
+                .await?;
+                stats = do_write(
+                    embed,
+                    Arc::clone(&self.vector_repo),
+                    Arc::clone(&self.file_hash_repo),
+                    Arc::clone(&self.call_graph_use_case),
+                    self.channel_endpoint_repo.clone(),
+                )
+                .await?;
+
+                if has_endpoints {
+                    self.resolve_channels(
+                        repository_id,
+                        root,
+                        repository.roots(),
+                        &path_filter,
+                        &scip_refs,
+                    )
+                    .await?;
+                }
+            }
+        }
+        let (written_file_count, new_chunk_count, _, _) = stats;
+
+        let chunk_count =
+            repository.chunk_count().saturating_sub(deleted_chunk_count) + new_chunk_count;
+        let file_count =
+            repository.file_count().saturating_sub(was_indexed as u64) + written_file_count;
+        self.repository_repo
+            .update_stats(repository_id, chunk_count, file_count)
+            .await?;
+
+        if was_indexed || written_file_count > 0 {
+            self.invalidate_analyses(repository_id, repository.namespace())
+                .await;
+        }
+        self.vector_repo.flush().await?;
+
+        debug!(
+            "Updated {}: {} chunks removed, {} written",
+            relative_path, deleted_chunk_count, new_chunk_count
+        );
+
+        self.repository_repo
+            .find_by_id(repository_id)
+            .await?
+            .ok_or_else(|| DomainError::internal("Repository not found after update"))
+    }
 }
 
 /// Result of parsing a single file, before embedding.
@@ -961,6 +1822,9 @@ struct ParseOnlyResult {
     content_hash: String,
     language: Language,
     chunks: Vec<crate::domain::CodeChunk>,
+    /// References found by the parser itself.  Used only when SCIP has
+    /// nothing for the file.
+    references: Vec<SymbolReference>,
     /// Channel endpoints extracted from the file (empty when no channel
     /// extractor is configured or the language has no detectors).
     endpoints: Vec<ChannelEndpoint>,
@@ -1009,16 +1873,19 @@ async fn join_flatten<T>(
 /// Spawn the parse stream as an independent tokio task and return the
 /// receiving end of an [`mpsc`] channel.
 ///
-/// Running the stream inside its own task means that `parse_only` futures
-/// advance on the tokio thread pool even when the calling task is blocked
-/// awaiting a flush handle.  The channel acts as a bounded buffer so the
-/// parser stays ahead of the embedder without unbounded memory growth.
+/// Running the stream inside its own task means parsing continues while the
+/// calling task is blocked awaiting a flush handle. Tree-sitter parsing is
+/// CPU-bound, so each file is parsed on a `spawn_blocking` worker, with at
+/// most `concurrency` in flight; results arrive in completion order. The
+/// channel acts as a bounded buffer so the parser stays ahead of the embedder
+/// without unbounded memory growth.
 fn spawn_parse_stream(
     files: Vec<PathBuf>,
     abs_path: PathBuf,
     repo_id: String,
     parser_service: Arc<dyn ParserService>,
     channel_extractor: Option<Arc<dyn ChannelExtractor>>,
+    header_language: Language,
     concurrency: usize,
 ) -> mpsc::Receiver<Option<ParseOnlyResult>> {
     // Buffer enough results to absorb a full flush cycle without stalling.
@@ -1031,14 +1898,24 @@ fn spawn_parse_stream(
                 let abs_path = abs_path.clone();
                 let repo_id = repo_id.clone();
                 async move {
-                    parse_only(
-                        entry_path,
-                        &abs_path,
-                        &repo_id,
-                        &*parser_service,
-                        channel_extractor.as_deref(),
-                    )
-                    .await
+                    let display_path = entry_path.display().to_string();
+                    let handle = tokio::runtime::Handle::current();
+                    let parsed = tokio::task::spawn_blocking(move || {
+                        handle.block_on(parse_only(
+                            entry_path,
+                            &abs_path,
+                            &repo_id,
+                            &*parser_service,
+                            channel_extractor.as_deref(),
+                            header_language,
+                        ))
+                    })
+                    .await;
+                    // A parser panic loses this file only, like a parse error.
+                    parsed.unwrap_or_else(|e| {
+                        warn!("Parsing {} panicked: {}", display_path, e);
+                        None
+                    })
                 }
             })
             .buffer_unordered(concurrency);
@@ -1052,6 +1929,133 @@ fn spawn_parse_stream(
     rx
 }
 
+/// Running embedding cache hit/miss counts, shared with the embed tasks.
+#[derive(Default)]
+struct EmbedCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    /// Chunks that got a vector this run, from the cache or the service.
+    embedded: AtomicU64,
+}
+
+impl EmbedCounters {
+    fn record(&self, hits: usize, misses: usize) {
+        self.hits.fetch_add(hits as u64, Ordering::Relaxed);
+        self.misses.fetch_add(misses as u64, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.embedded.store(0, Ordering::Relaxed);
+    }
+
+    fn embedded(&self) -> u64 {
+        self.embedded.load(Ordering::Relaxed)
+    }
+
+    fn snapshot(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The embedding service, fronted by the optional embedding cache.
+#[derive(Clone)]
+struct CachedEmbedder {
+    service: Arc<dyn EmbeddingService>,
+    cache: Option<Arc<dyn EmbeddingCache>>,
+    counters: Arc<EmbedCounters>,
+}
+
+impl CachedEmbedder {
+    fn embeddings_enabled(&self) -> bool {
+        self.service.embeddings_enabled()
+    }
+
+    async fn embed_chunks(
+        &self,
+        chunks: &[crate::domain::CodeChunk],
+    ) -> Result<Vec<Embedding>, DomainError> {
+        let embeddings = self.embed_with_cache(chunks).await?;
+        self.counters
+            .embedded
+            .fetch_add(embeddings.len() as u64, Ordering::Relaxed);
+        Ok(embeddings)
+    }
+
+    /// Embed `chunks`, taking vectors the cache already holds for their
+    /// content and embedding only the rest. Cache failures are logged and
+    /// treated as misses; they never fail indexing.
+    async fn embed_with_cache(
+        &self,
+        chunks: &[crate::domain::CodeChunk],
+    ) -> Result<Vec<Embedding>, DomainError> {
+        let Some(cache) = &self.cache else {
+            return self.service.embed_chunks(chunks).await;
+        };
+        let model = self.service.config().model_name().to_string();
+        let keys: Vec<String> = chunks
+            .iter()
+            .map(crate::domain::CodeChunk::embedding_key)
+            .collect();
+        let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let cached = cache.get_many(&model, &key_refs).await.unwrap_or_else(|e| {
+            warn!(
+                "Embedding cache lookup failed, embedding every chunk: {}",
+                e
+            );
+            HashMap::new()
+        });
+
+        let misses: Vec<crate::domain::CodeChunk> = chunks
+            .iter()
+            .zip(&keys)
+            .filter(|(_, key)| !cached.contains_key(*key))
+            .map(|(chunk, _)| chunk.clone())
+            .collect();
+        let fresh = if misses.is_empty() {
+            Vec::new()
+        } else {
+            self.service.embed_chunks(&misses).await?
+        };
+        if fresh.len() != misses.len() {
+            return Err(DomainError::internal(format!(
+                "Embedding count mismatch: got {} embeddings for {} chunks",
+                fresh.len(),
+                misses.len()
+            )));
+        }
+        self.counters
+            .record(chunks.len() - misses.len(), misses.len());
+
+        let entries: Vec<(String, Vec<f32>)> = misses
+            .iter()
+            .zip(&fresh)
+            .map(|(chunk, embedding)| (chunk.embedding_key(), embedding.vector().to_vec()))
+            .collect();
+        if let Err(e) = cache.put_many(&model, &entries).await {
+            warn!("Failed to store embeddings in the cache: {}", e);
+        }
+
+        let mut fresh = fresh.into_iter();
+        Ok(chunks
+            .iter()
+            .zip(&keys)
+            .filter_map(|(chunk, key)| match cached.get(key) {
+                Some(vector) => Some(Embedding::new(
+                    chunk.id().to_string(),
+                    vector.clone(),
+                    model.clone(),
+                )),
+                None => fresh.next(),
+            })
+            .collect())
+    }
+}
+
 /// Phase 1 of the two-stage flush pipeline: tokenise and embed a batch.
 ///
 /// Pure CPU work — no DB I/O.  Runs concurrently with the DB-write phase of
@@ -1060,7 +2064,7 @@ async fn do_embed(
     batch: Vec<ParseOnlyResult>,
     repository_id: String,
     scip_refs: Arc<HashMap<String, Vec<SymbolReference>>>,
-    embedding_service: Arc<dyn EmbeddingService>,
+    embedding_service: CachedEmbedder,
 ) -> Result<EmbedResult, DomainError> {
     // Flatten chunks while preserving per-file counts for later re-splitting.
     let mut flat_chunks: Vec<crate::domain::CodeChunk> = Vec::new();
@@ -1083,7 +2087,20 @@ async fn do_embed(
         if !embedding_service.embeddings_enabled() || flat_chunks.is_empty() {
             per_file_chunk_count.iter().map(|_| Some(vec![])).collect()
         } else {
-            match embedding_service.embed_chunks(&flat_chunks).await {
+            let span = tracing::info_span!(
+                "embed_chunks",
+                chunks = flat_chunks.len(),
+                latency_ms = tracing::field::Empty,
+            );
+            let embed_start = Instant::now();
+            let embedded = embedding_service
+                .embed_chunks(&flat_chunks)
+                .instrument(span.clone())
+                .await;
+            let embed_time = embed_start.elapsed();
+            span.record("latency_ms", embed_time.as_millis() as u64);
+            metrics::histogram!(EMBEDDING_DURATION_SECONDS).record(embed_time.as_secs_f64());
+            match embedded {
                 Ok(all) => {
                     if all.len() != flat_chunks.len() {
                         return Err(DomainError::internal(format!(
@@ -1141,6 +2158,26 @@ async fn do_embed(
     })
 }
 
+/// A file's SCIP references plus the [`ReferenceKind::Implementation`] and
+/// [`ReferenceKind::Override`] edges among `parsed`: SCIP indexers do not
+/// report which trait or interface a method implements, nor which base
+/// method it overrides, so those edges from the parser are kept alongside.
+fn with_implementations(
+    scip_refs: &[SymbolReference],
+    parsed: &[SymbolReference],
+) -> Vec<SymbolReference> {
+    scip_refs
+        .iter()
+        .chain(parsed.iter().filter(|r| {
+            matches!(
+                r.reference_kind(),
+                ReferenceKind::Implementation | ReferenceKind::Override
+            )
+        }))
+        .cloned()
+        .collect()
+}
+
 /// Phase 2 of the two-stage flush pipeline: persist an already-embedded batch.
 ///
 /// All DB writes — delete stale, save chunks+embeddings, save call-graph refs,
@@ -1225,7 +2262,15 @@ async fn do_write(
                 .delete_by_file(&repository_id, &result.relative_path)
                 .await?;
             call_graph_use_case
-                .save_references(scip_file_refs)
+                .save_references(&with_implementations(scip_file_refs, &result.references))
+                .await
+                .map_err(|e| DomainError::internal(format!("{:#}", e)))?
+        } else if !result.references.is_empty() {
+            call_graph_use_case
+                .delete_by_file(&repository_id, &result.relative_path)
+                .await?;
+            call_graph_use_case
+                .save_references(&result.references)
                 .await
                 .map_err(|e| DomainError::internal(format!("{:#}", e)))?
         } else {
@@ -1256,6 +2301,7 @@ async fn do_write(
         "Flushed batch: {} files, {} chunks, {} references",
         file_count, chunk_count, ref_count
     );
+    metrics::counter!(CHUNKS_INDEXED_TOTAL).increment(chunk_count);
 
     Ok((file_count, chunk_count, ref_count, language_stats))
 }
@@ -1270,8 +2316,8 @@ async fn parse_only(
     repo_id: &str,
     parser_service: &dyn ParserService,
     channel_extractor: Option<&dyn ChannelExtractor>,
+    header_language: Language,
 ) -> Option<ParseOnlyResult> {
-    let language = Language::from_path(&entry_path);
     let relative_path = entry_path
         .strip_prefix(absolute_path)
         .unwrap_or(&entry_path)
@@ -1285,6 +2331,7 @@ async fn parse_only(
             return None;
         }
     };
+    let language = language_of(&entry_path, &content, header_language);
 
     let content_hash = compute_file_hash(&content);
 
@@ -1299,6 +2346,19 @@ async fn parse_only(
         }
     };
 
+    // Parser-derived references are a fallback for files SCIP does not
+    // cover; failures are logged and the file is indexed without them.
+    let references = match parser_service
+        .extract_references(&content, &relative_path, language, repo_id)
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            warn!("Failed to extract references from {}: {}", relative_path, e);
+            Vec::new()
+        }
+    };
+
     // Channel extraction failures must not fail chunk indexing — log and
     // continue with no endpoints for the file.
     let endpoints = match channel_extractor {
@@ -1325,6 +2385,7 @@ async fn parse_only(
         content_hash,
         language,
         chunks,
+        references,
         endpoints,
     })
 }
@@ -1341,6 +2402,8 @@ async fn parse_only(
 #[allow(clippy::type_complexity)]
 async fn discover_config_candidates(
     absolute_path: &Path,
+    roots: Vec<String>,
+    path_filter: Override,
 ) -> (Vec<(String, String)>, HashMap<String, String>) {
     let root = absolute_path.to_path_buf();
     tokio::task::spawn_blocking(move || {
@@ -1352,18 +2415,14 @@ async fn discover_config_candidates(
         // exactly the files the indexer indexed — no hidden/gitignored/generated
         // files (e.g. `node_modules`, `target`) that would slow the scan and
         // match config candidates from sources the indexer never chunked.
-        let walker = WalkBuilder::new(&root)
-            .hidden(true)
-            .git_ignore(true)
-            .git_global(true)
-            .git_exclude(true)
-            .build();
-        for entry in walker.flatten() {
+        for entry in walk_roots(&root, &roots).flatten() {
             let path = entry.path();
-            if !matches!(
-                Language::from_path(path),
-                Language::JavaScript | Language::TypeScript
-            ) {
+            if is_filtered_out(&path_filter, path)
+                || !matches!(
+                    Language::from_path(path),
+                    Language::JavaScript | Language::TypeScript
+                )
+            {
                 continue;
             }
             let Ok(source) = std::fs::read_to_string(path) else {
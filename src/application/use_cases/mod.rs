@@ -4,13 +4,17 @@ mod cluster_detection;
 mod community_naming;
 mod coupling_detection;
 mod delete_repository;
+mod diff_impact;
 mod execution_features;
 mod execution_features_naming;
 mod explain;
 mod file_relationship;
 mod graph_expansion;
+mod graph_export;
 mod impact_analysis;
 mod import_session;
+mod index_archive;
+mod index_health;
 mod index_repository;
 mod list_repositories;
 mod memory_browse;
@@ -22,10 +26,12 @@ mod memory_search;
 mod memory_summary;
 pub(crate) mod memory_support;
 pub(crate) mod pattern_utils;
+mod prune_repository;
 mod repository_overview;
 mod resolve_channels;
 mod rrf_fuse;
 mod search_code;
+mod similar_code;
 mod snippet_lookup;
 mod symbol_cluster_detection;
 mod symbol_context;
@@ -37,12 +43,16 @@ pub use cluster_detection::*;
 pub use community_naming::*;
 pub use coupling_detection::*;
 pub use delete_repository::*;
+pub use diff_impact::*;
 pub use execution_features::*;
 pub use explain::*;
 pub use file_relationship::*;
 pub use graph_expansion::*;
+pub use graph_export::*;
 pub use impact_analysis::*;
 pub use import_session::*;
+pub use index_archive::*;
+pub use index_health::*;
 pub use index_repository::*;
 pub use list_repositories::*;
 pub use memory_browse::*;
@@ -50,10 +60,12 @@ pub use memory_dream::*;
 pub use memory_extraction::*;
 pub use memory_search::*;
 pub use memory_summary::*;
+pub use prune_repository::*;
 pub use repository_overview::*;
 pub use resolve_channels::*;
 pub use rrf_fuse::*;
 pub use search_code::*;
+pub use similar_code::*;
 pub use snippet_lookup::*;
 pub use symbol_cluster_detection::*;
 pub use symbol_context::*;
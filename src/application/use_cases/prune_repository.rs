@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::{debug, info};
+
+use crate::application::{
+    AnalysisRepository, CallGraphUseCase, ChannelEndpointRepository, FileHashRepository,
+    MetadataRepository, VectorRepository,
+};
+use crate::domain::{namespace_scope_id, DomainError, Repository};
+
+/// What [`PruneRepositoryUseCase::execute`] found (and, unless it was a dry
+/// run, removed) for one repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct PruneReport {
+    pub repository_id: String,
+    /// Indexed paths, relative to the repository root, that no longer exist
+    /// on disk.
+    pub stale_files: Vec<String>,
+    /// Chunks deleted (0 on a dry run).
+    pub chunks_removed: u64,
+    pub dry_run: bool,
+}
+
+/// Removes index data for files that were deleted from disk.
+///
+/// Incremental indexing only notices deletions of files it has a content
+/// hash for; chunks left behind by an interrupted run or an older index have
+/// none and would keep turning up in search. Pruning compares every path the
+/// index knows about (chunks and file hashes) against the repository's
+/// working tree instead.
+pub struct PruneRepositoryUseCase {
+    repository_repo: Arc<dyn MetadataRepository>,
+    vector_repo: Arc<dyn VectorRepository>,
+    file_hash_repo: Arc<dyn FileHashRepository>,
+    call_graph_use_case: Arc<CallGraphUseCase>,
+    channel_endpoint_repo: Option<Arc<dyn ChannelEndpointRepository>>,
+    /// Optional store of derived analyses, invalidated when pruning removes
+    /// call-graph rows.
+    analysis_repo: Option<Arc<dyn AnalysisRepository>>,
+}
+
+impl PruneRepositoryUseCase {
+    pub fn new(
+        repository_repo: Arc<dyn MetadataRepository>,
+        vector_repo: Arc<dyn VectorRepository>,
+        file_hash_repo: Arc<dyn FileHashRepository>,
+        call_graph_use_case: Arc<CallGraphUseCase>,
+    ) -> Self {
+        Self {
+            repository_repo,
+            vector_repo,
+            file_hash_repo,
+            call_graph_use_case,
+            channel_endpoint_repo: None,
+            analysis_repo: None,
+        }
+    }
+
+    /// Also prune stored channel endpoints.
+    pub fn with_channel_endpoints(
+        mut self,
+        channel_endpoint_repo: Arc<dyn ChannelEndpointRepository>,
+    ) -> Self {
+        self.channel_endpoint_repo = Some(channel_endpoint_repo);
+        self
+    }
+
+    /// Attach the analysis store so stored analyses are invalidated when
+    /// files are pruned.
+    pub fn with_analysis_repo(mut self, analysis_repo: Arc<dyn AnalysisRepository>) -> Self {
+        self.analysis_repo = Some(analysis_repo);
+        self
+    }
+
+    /// Prune the repository with id `repository_id`. With `dry_run` the
+    /// stale files are reported but nothing is deleted.
+    pub async fn execute(
+        &self,
+        repository_id: &str,
+        dry_run: bool,
+    ) -> Result<PruneReport, DomainError> {
+        let repository = self
+            .repository_repo
+            .find_by_id(repository_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::not_found(format!("Repository not found: {}", repository_id))
+            })?;
+
+        let stale_files = self.find_stale(&repository).await?;
+        let mut chunks_removed = 0;
+        if !dry_run && !stale_files.is_empty() {
+            chunks_removed = self.remove(repository_id, &stale_files).await?;
+            self.repository_repo
+                .update_stats(
+                    repository_id,
+                    repository.chunk_count().saturating_sub(chunks_removed),
+                    repository.file_count(),
+                )
+                .await?;
+            self.invalidate_analyses(&repository).await?;
+            self.vector_repo.flush().await?;
+            info!(
+                "Pruned {} stale files ({} chunks) from {}",
+                stale_files.len(),
+                chunks_removed,
+                repository.name()
+            );
+        }
+
+        Ok(PruneReport {
+            repository_id: repository_id.to_string(),
+            stale_files,
+            chunks_removed,
+            dry_run,
+        })
+    }
+
+    /// Indexed paths of `repository` (from its chunks and file hashes) that
+    /// no longer exist under its root, sorted.
+    pub async fn find_stale(&self, repository: &Repository) -> Result<Vec<String>, DomainError> {
+        let mut indexed: BTreeSet<String> = self
+            .vector_repo
+            .list_files(repository.id())
+            .await?
+            .into_iter()
+            .map(|f| f.file_path)
+            .collect();
+        indexed.extend(
+            self.file_hash_repo
+                .find_by_repository(repository.id())
+                .await?
+                .into_iter()
+                .map(|h| h.file_path().to_string()),
+        );
+
+        let root = Path::new(repository.path());
+        Ok(indexed
+            .into_iter()
+            .filter(|path| !root.join(path).exists())
+            .collect())
+    }
+
+    /// Delete chunks, embeddings, call-graph rows, channel endpoints and
+    /// file hashes for `paths`. Returns the number of chunks deleted.
+    pub async fn remove(&self, repository_id: &str, paths: &[String]) -> Result<u64, DomainError> {
+        let mut chunks_removed = 0;
+        for path in paths {
+            debug!("Pruning stale file: {}", path);
+            chunks_removed += self
+                .vector_repo
+                .delete_by_file_path(repository_id, path)
+                .await?;
+            self.call_graph_use_case
+                .delete_by_file(repository_id, path)
+                .await?;
+            if let Some(channel_repo) = &self.channel_endpoint_repo {
+                channel_repo
+                    .delete_by_file_path(repository_id, path)
+                    .await?;
+            }
+        }
+        self.file_hash_repo
+            .delete_by_paths(repository_id, paths)
+            .await?;
+        Ok(chunks_removed)
+    }
+
+    async fn invalidate_analyses(&self, repository: &Repository) -> Result<(), DomainError> {
+        if let Some(analysis_repo) = &self.analysis_repo {
+            analysis_repo.delete_by_repository(repository.id()).await?;
+            if let Some(ns) = repository.namespace() {
+                analysis_repo
+                    .delete_by_repository(&namespace_scope_id(ns))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
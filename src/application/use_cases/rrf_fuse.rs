@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::domain::SearchResult;
+use crate::domain::{LegScore, ScoreExplanation, SearchQuery, SearchResult};
 
 /// Smoothing constant for Reciprocal Rank Fusion.
 /// Higher values reduce the weight difference between high and low-ranked items.
@@ -77,16 +77,102 @@ pub fn is_test_file(path: &str) -> bool {
 /// Results from test files are penalised by [`TEST_FILE_PENALTY`] before the
 /// final sort so that production code consistently ranks above test helpers.
 pub fn rrf_fuse(lists: Vec<Vec<SearchResult>>, limit: usize) -> Vec<SearchResult> {
+    let weighted = lists.into_iter().map(|list| (list, 1.0)).collect();
+    rrf_fuse_weighted(weighted, RRF_K, limit)
+}
+
+/// Fuse the semantic and keyword legs of a hybrid search with the RRF
+/// constant and leg weights carried by `query`, capped at `query.limit()`.
+///
+/// With `query.explain()`, each fused result records its rank and raw score
+/// in both legs alongside the fused score, which fusion otherwise discards.
+pub fn rrf_fuse_hybrid(
+    semantic: Vec<SearchResult>,
+    text: Vec<SearchResult>,
+    query: &SearchQuery,
+) -> Vec<SearchResult> {
+    let provenance = query
+        .explain()
+        .then(|| (leg_scores(&semantic), leg_scores(&text)));
+    let fused = rrf_fuse_weighted(
+        vec![
+            (semantic, query.semantic_weight()),
+            (text, query.text_weight()),
+        ],
+        query.rrf_k().unwrap_or(RRF_K),
+        query.limit(),
+    );
+    let Some((semantic, text)) = provenance else {
+        return fused;
+    };
+    fused
+        .into_iter()
+        .map(|result| {
+            let id = result.chunk().id();
+            let explanation = ScoreExplanation {
+                semantic: semantic.get(id).copied(),
+                text: text.get(id).copied(),
+                rrf: Some(result.score()),
+                rerank: None,
+            };
+            result.with_explanation(explanation)
+        })
+        .collect()
+}
+
+/// Rank and raw score of every result in one leg, by chunk id.
+fn leg_scores(list: &[SearchResult]) -> HashMap<String, LegScore> {
+    list.iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let leg = LegScore {
+                rank: i + 1,
+                score: r.score(),
+            };
+            (r.chunk().id().to_string(), leg)
+        })
+        .collect()
+}
+
+/// [`rrf_fuse`] with a smoothing constant `k` and a weight per list: a result
+/// scores `weight / (k + rank)` from each list it appears in.
+///
+/// [`RRF_MIN_SCORE`] is calibrated for `k = RRF_K` and unit weights; it is
+/// rescaled here so it keeps meaning "one hit ranked within the top ~15 of
+/// the lightest list" whatever `k` and the weights are, so boosting one leg
+/// never filters out the other leg's top hits.
+pub fn rrf_fuse_weighted(
+    lists: Vec<(Vec<SearchResult>, f32)>,
+    k: f32,
+    limit: usize,
+) -> Vec<SearchResult> {
+    let min_weight = lists
+        .iter()
+        .map(|(_, w)| *w)
+        .filter(|w| *w > 0.0)
+        .reduce(f32::min)
+        .unwrap_or(0.0);
+    // The rank at which a single default hit falls below the threshold;
+    // grouped so the defaults scale by exactly 1.0 and keep it.
+    let cutoff_rank = 1.0 / RRF_MIN_SCORE - RRF_K;
+    let min_score = RRF_MIN_SCORE * (min_weight * (RRF_K + cutoff_rank) / (k + cutoff_rank));
     let mut scores: HashMap<String, (SearchResult, f32)> = HashMap::new();
 
-    for list in lists {
+    for (list, weight) in lists {
         for (rank, result) in list.into_iter().enumerate() {
-            let rrf = 1.0 / (RRF_K + (rank + 1) as f32);
+            let rrf = weight / (k + (rank + 1) as f32);
             let id = result.chunk().id().to_string();
-            scores
-                .entry(id)
-                .and_modify(|(_, s)| *s += rrf)
-                .or_insert((result, rrf));
+            match scores.remove(&id) {
+                // The same chunk found by another list: sum the scores and
+                // keep the legs both lists found it by.
+                Some((kept, s)) => {
+                    let legs = result.search_legs().to_vec();
+                    scores.insert(id, (kept.with_search_legs(legs), s + rrf));
+                }
+                None => {
+                    scores.insert(id, (result, rrf));
+                }
+            }
         }
     }
 
@@ -99,7 +185,12 @@ pub fn rrf_fuse(lists: Vec<Vec<SearchResult>>, limit: usize) -> Vec<SearchResult
         }
     }
 
-    fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    // Ties go to the lower chunk id so equal scores rank the same every run.
+    fused.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.chunk().id().cmp(b.0.chunk().id()))
+    });
 
     // Deduplicate by physical location: if two chunks share the same
     // (repository_id, file_path, start_line, end_line) they are the same code
@@ -109,7 +200,7 @@ pub fn rrf_fuse(lists: Vec<Vec<SearchResult>>, limit: usize) -> Vec<SearchResult
 
     fused
         .into_iter()
-        .filter(|(_, score)| *score >= RRF_MIN_SCORE)
+        .filter(|(_, score)| *score >= min_score)
         .filter(|(r, _)| {
             let loc = (
                 r.chunk().repository_id().to_string(),
@@ -120,14 +211,23 @@ pub fn rrf_fuse(lists: Vec<Vec<SearchResult>>, limit: usize) -> Vec<SearchResult
             seen_locations.insert(loc)
         })
         .take(limit)
-        .map(|(r, score)| SearchResult::new(r.chunk().clone(), score))
+        .map(|(r, score)| {
+            // Keep provenance from an earlier fusion (a hybrid search fused
+            // again across query variants or with the graph leg).
+            let fused = SearchResult::new(r.chunk().clone(), score)
+                .with_search_legs(r.search_legs().to_vec());
+            match r.explanation() {
+                Some(explanation) => fused.with_explanation(explanation.clone()),
+                None => fused,
+            }
+        })
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{CodeChunk, Language, NodeType, SearchResult};
+    use crate::domain::{CodeChunk, Language, NodeType, SearchLeg, SearchResult};
 
     /// Build a minimal SearchResult with a known ID; the raw score is irrelevant
     /// because rrf_fuse re-scores everything by rank position.
@@ -323,6 +423,60 @@ mod tests {
         assert_eq!(fused[0].chunk().id(), "first");
     }
 
+    #[test]
+    fn unit_weights_and_default_k_match_unweighted_fusion() {
+        let lists = || {
+            vec![
+                vec![make_result("a"), make_result("b"), make_result("c")],
+                vec![make_result("c"), make_result("d")],
+            ]
+        };
+        let plain = rrf_fuse(lists(), 10);
+        let weighted = rrf_fuse_weighted(
+            lists().into_iter().map(|list| (list, 1.0)).collect(),
+            RRF_K,
+            10,
+        );
+        let summary = |r: &[SearchResult]| -> Vec<(String, f32)> {
+            r.iter()
+                .map(|r| (r.chunk().id().to_string(), r.score()))
+                .collect()
+        };
+        assert_eq!(summary(&plain), summary(&weighted));
+    }
+
+    #[test]
+    fn heavier_text_leg_promotes_keyword_matches() {
+        // "exact" is only a BM25 hit, one rank below that leg's top;
+        // "fuzzy" is the semantic leg's top hit and absent from BM25.
+        let semantic = || vec![make_result("fuzzy")];
+        let text = || vec![make_result("keyword"), make_result("exact")];
+        let position = |fused: &[SearchResult], id: &str| {
+            fused.iter().position(|r| r.chunk().id() == id).unwrap()
+        };
+
+        let equal = rrf_fuse_weighted(vec![(semantic(), 1.0), (text(), 1.0)], RRF_K, 10);
+        assert!(position(&equal, "fuzzy") < position(&equal, "exact"));
+
+        let text_heavy = rrf_fuse_weighted(vec![(semantic(), 1.0), (text(), 2.0)], RRF_K, 10);
+        assert!(position(&text_heavy, "exact") < position(&text_heavy, "fuzzy"));
+        let exact = &text_heavy[position(&text_heavy, "exact")];
+        assert!((exact.score() - 2.0 / (RRF_K + 2.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn smaller_k_sharpens_rank_differences() {
+        let list = || vec![make_result("first"), make_result("second")];
+        let gap = |k: f32| {
+            let fused = rrf_fuse_weighted(vec![(list(), 1.0)], k, 10);
+            fused[0].score() / fused[1].score()
+        };
+        assert!(gap(1.0) > gap(RRF_K));
+        // The minimum-score cutoff follows k, so a large k doesn't drop
+        // every result.
+        assert_eq!(rrf_fuse_weighted(vec![(list(), 1.0)], 600.0, 10).len(), 2);
+    }
+
     #[test]
     fn duplicate_location_different_uuid_deduplicated() {
         // Simulate two index runs that produced chunks with different UUIDs for
@@ -352,4 +506,62 @@ mod tests {
         let fused = rrf_fuse(vec![vec![a, b]], 10);
         assert_eq!(fused.len(), 2);
     }
+
+    #[test]
+    fn hybrid_fusion_records_leg_provenance_when_explaining() {
+        let semantic = vec![
+            SearchResult::new(make_result("a").chunk().clone(), 0.9),
+            SearchResult::new(make_result("b").chunk().clone(), 0.8),
+        ];
+        let text = vec![SearchResult::new(make_result("b").chunk().clone(), 7.5)];
+
+        let plain = rrf_fuse_hybrid(semantic.clone(), text.clone(), &SearchQuery::new("q"));
+        assert!(plain.iter().all(|r| r.explanation().is_none()));
+
+        let query = SearchQuery::new("q").with_explain(true);
+        let fused = rrf_fuse_hybrid(semantic, text, &query);
+        let b = fused.iter().find(|r| r.chunk().id() == "b").unwrap();
+        let explanation = b.explanation().expect("provenance recorded");
+        assert_eq!(
+            explanation.semantic,
+            Some(LegScore {
+                rank: 2,
+                score: 0.8
+            })
+        );
+        assert_eq!(
+            explanation.text,
+            Some(LegScore {
+                rank: 1,
+                score: 7.5
+            })
+        );
+        assert_eq!(explanation.rrf, Some(b.score()));
+
+        let a = fused.iter().find(|r| r.chunk().id() == "a").unwrap();
+        assert_eq!(a.explanation().unwrap().text, None);
+    }
+
+    #[test]
+    fn fusion_keeps_the_legs_each_list_found_a_result_by() {
+        let semantic = vec![make_result("a").with_semantic_leg()];
+        let text = vec![
+            make_result("a").with_search_leg(SearchLeg::Text {
+                bm25_score: 3.0,
+                terms: vec!["foo".to_string()],
+            }),
+            make_result("b").with_search_leg(SearchLeg::Text {
+                bm25_score: 2.0,
+                terms: vec!["bar".to_string()],
+            }),
+        ];
+        let fused = rrf_fuse(vec![semantic, text], 10);
+
+        let a = fused.iter().find(|r| r.chunk().id() == "a").unwrap();
+        assert_eq!(a.search_legs().len(), 2);
+        assert_eq!(a.matched_terms(), ["foo"]);
+        let b = fused.iter().find(|r| r.chunk().id() == "b").unwrap();
+        assert_eq!(b.matched_terms(), ["bar"]);
+        assert!(matches!(b.search_legs(), [SearchLeg::Text { .. }]));
+    }
 }
@@ -1,7 +1,11 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::time::Instant;
 
-use tracing::{info, warn};
+use tracing::field::Empty;
+use tracing::{debug, info, info_span, warn, Instrument, Span};
 
 /// Global minimum score threshold applied to all search results before they are
 /// returned to the caller, regardless of the search path taken (semantic,
@@ -9,10 +13,30 @@ use tracing::{info, warn};
 /// uninformative and only add noise to the output.
 const MIN_RESULT_SCORE: f32 = 0.1;
 
+/// How many candidates diversity re-ranking draws from, as a multiple of the
+/// requested result count.  MMR needs a pool larger than its output to have
+/// near-duplicates to skip over.
+const MMR_POOL_MULTIPLIER: usize = 3;
+
+/// Extra candidates fetched when duplicates are collapsed, as a multiple of
+/// the requested result count, so the collapsed list can still fill it.
+const DEDUP_POOL_MULTIPLIER: usize = 2;
+
+/// Most expanded phrasings searched alongside the original query, so a
+/// chatty expander cannot multiply the number of searches without bound.
+const MAX_EXPANDED_VARIANTS: usize = 3;
+
+use crate::application::metrics::{
+    EMBEDDING_DURATION_SECONDS, RERANK_DURATION_SECONDS, SEARCH_DURATION_SECONDS,
+    SEARCH_REQUESTS_TOTAL,
+};
 use crate::application::use_cases::graph_expansion::GraphExpansionUseCase;
 use crate::application::use_cases::rrf_fuse::rrf_fuse;
 use crate::application::{EmbeddingService, QueryExpander, RerankingService, VectorRepository};
-use crate::domain::{DomainError, SearchQuery, SearchResult};
+use crate::domain::{
+    cosine_similarity, DomainError, LegScore, NodeType, ScoreExplanation, SearchLeg, SearchQuery,
+    SearchResult,
+};
 
 pub struct SearchCodeUseCase {
     vector_repo: Arc<dyn VectorRepository>,
@@ -20,6 +44,8 @@ pub struct SearchCodeUseCase {
     reranking_service: Option<Arc<dyn RerankingService>>,
     query_expander: Option<Arc<dyn QueryExpander>>,
     graph_expansion: Option<Arc<GraphExpansionUseCase>>,
+    diversity: Option<f32>,
+    dedup: bool,
 }
 
 impl SearchCodeUseCase {
@@ -33,6 +59,8 @@ impl SearchCodeUseCase {
             reranking_service: None,
             query_expander: None,
             graph_expansion: None,
+            diversity: None,
+            dedup: true,
         }
     }
 
@@ -54,7 +82,59 @@ impl SearchCodeUseCase {
         self
     }
 
+    /// Re-rank the final list with Maximal Marginal Relevance: each pick
+    /// maximises `λ · relevance − (1 − λ) · max_similarity_to_already_picked`.
+    /// `lambda` is clamped to `[0, 1]`; `1.0` is pure relevance and leaves
+    /// diversity re-ranking off.
+    pub fn with_diversity(mut self, lambda: f32) -> Self {
+        let lambda = lambda.clamp(0.0, 1.0);
+        self.diversity = (lambda < 1.0).then_some(lambda);
+        self
+    }
+
+    /// Collapse duplicate results (on by default): identical content in
+    /// different files (vendored copies, generated code) and overlapping
+    /// parts of the same file keep only their best-ranked instance.
+    pub fn with_dedup(mut self, enabled: bool) -> Self {
+        self.dedup = enabled;
+        self
+    }
+
+    /// Look up definitions by symbol name — exact matches, then prefix
+    /// matches — without embedding the query. Filters and limit come from
+    /// `query`.
+    pub async fn find_symbol(&self, query: &SearchQuery) -> Result<Vec<SearchResult>, DomainError> {
+        info!("Looking up symbol: {}", query.query());
+        validate_node_types(query)?;
+        self.vector_repo.find_by_symbol(query).await
+    }
+
     pub async fn execute(&self, query: SearchQuery) -> Result<Vec<SearchResult>, DomainError> {
+        Ok(self.execute_with_expansions(query).await?.0)
+    }
+
+    /// [`execute`](Self::execute), also returning the phrasings query
+    /// expansion searched besides the original query (empty when expansion
+    /// is off or the expander failed).
+    #[tracing::instrument(
+        name = "search",
+        skip_all,
+        fields(
+            query = %query.query(),
+            text_search = query.is_text_search(),
+            limit = query.limit(),
+            result_count = Empty,
+            latency_ms = Empty,
+        )
+    )]
+    pub async fn execute_with_expansions(
+        &self,
+        query: SearchQuery,
+    ) -> Result<(Vec<SearchResult>, Vec<String>), DomainError> {
+        validate_node_types(&query)?;
+        if query.is_paginated() {
+            return Ok((self.execute_page(query).await?, Vec::new()));
+        }
         info!(
             "Searching for: {} (text_search={}, expand_query={})",
             query.query(),
@@ -63,6 +143,7 @@ impl SearchCodeUseCase {
         );
 
         let start_time = Instant::now();
+        metrics::counter!(SEARCH_REQUESTS_TOTAL).increment(1);
 
         let fetch_limit = if self.reranking_service.is_some() {
             // Use an inverse-log formula so the overhead shrinks as num grows:
@@ -81,10 +162,20 @@ impl SearchCodeUseCase {
         } else {
             query.limit()
         };
+        let fetch_limit = if self.diversity.is_some() {
+            fetch_limit.max(query.limit() * MMR_POOL_MULTIPLIER)
+        } else {
+            fetch_limit
+        };
+        let fetch_limit = if self.dedup {
+            fetch_limit.max(query.limit() * DEDUP_POOL_MULTIPLIER)
+        } else {
+            fetch_limit
+        };
 
         if fetch_limit != query.limit() {
             info!(
-                "Using fetch_limit={} (target={}, +{} extra for reranking headroom)",
+                "Using fetch_limit={} (target={}, +{} extra for reranking/diversity/dedup headroom)",
                 fetch_limit,
                 query.limit(),
                 fetch_limit - query.limit()
@@ -114,27 +205,33 @@ impl SearchCodeUseCase {
 
         // The repository fuses two legs — BM25 and semantic — using RRF when
         // query.is_text_search() is true.
+        let mut expansions = Vec::new();
         let mut results = if let Some(expander) =
             self.query_expander.as_ref().filter(|_| semantic_available)
         {
             // --- Query expansion path ---
             // Expand the original query into multiple variants, embed each, search
             // for each independently, then fuse all result lists with RRF.
-            let variants = expander.expand(query.query()).await?;
+            let variants = expand_variants(expander.as_ref(), query.query()).await;
             info!("Query expanded into {} variants", variants.len());
             for (i, variant) in variants.iter().enumerate() {
-                info!("  expanded query[{}]: {}", i, variant);
+                debug!("  expanded query[{}]: {}", i, variant);
             }
+            expansions = variants[1..].to_vec();
 
             let mut set = tokio::task::JoinSet::new();
             for variant in variants {
                 let embedding_service = self.embedding_service.clone();
                 let vector_repo = self.vector_repo.clone();
                 let search_query = search_query.clone();
-                set.spawn(async move {
-                    let embedding = embedding_service.embed_query(&variant).await?;
-                    vector_repo.search(Some(&embedding), &search_query).await
-                });
+                set.spawn(
+                    async move {
+                        let embedding = embed_query(embedding_service.as_ref(), &variant).await?;
+                        let results = vector_repo.search(Some(&embedding), &search_query).await?;
+                        Ok::<_, DomainError>(explain_unfused(results, &search_query))
+                    }
+                    .in_current_span(),
+                );
             }
 
             let mut all_results: Vec<Vec<SearchResult>> = Vec::with_capacity(set.len());
@@ -158,13 +255,15 @@ impl SearchCodeUseCase {
             // `None` tells the repository to skip the semantic leg
             // (see VectorRepository::search).
             let query_embedding = if semantic_available {
-                Some(self.embedding_service.embed_query(query.query()).await?)
+                Some(embed_query(self.embedding_service.as_ref(), query.query()).await?)
             } else {
                 None
             };
-            self.vector_repo
+            let results = self
+                .vector_repo
                 .search(query_embedding.as_deref(), &search_query)
-                .await?
+                .await?;
+            explain_unfused(results, &search_query)
         };
 
         // Graph expansion leg: expand the top hits through the call graph and
@@ -214,9 +313,58 @@ impl SearchCodeUseCase {
                 reranker.model_name()
             );
 
+            // Diversity re-ranking and dedup still need the wider pool
+            // afterwards.
+            let top_n = if self.diversity.is_some() || self.dedup {
+                fetch_limit
+            } else {
+                query.limit()
+            };
+            let span = info_span!(
+                "rerank",
+                model = reranker.model_name(),
+                candidates = results.len(),
+                result_count = Empty,
+                latency_ms = Empty,
+            );
+            let rerank_start = Instant::now();
+            // Rerankers build fresh results, so carry the provenance and the
+            // legs each result was found by across.
+            let mut provenance: HashMap<String, (Option<ScoreExplanation>, Vec<SearchLeg>)> =
+                results
+                    .iter()
+                    .map(|r| {
+                        let legs = r.search_legs().to_vec();
+                        (r.chunk().id().to_string(), (r.explanation().cloned(), legs))
+                    })
+                    .collect();
             results = reranker
-                .rerank(query.query(), results, Some(query.limit()))
+                .rerank(query.query(), results, Some(top_n))
+                .instrument(span.clone())
                 .await?;
+            results = results
+                .into_iter()
+                .map(|r| match provenance.remove(r.chunk().id()) {
+                    Some((explanation, legs)) => {
+                        let r = r.with_search_legs(legs);
+                        match explanation {
+                            Some(explanation) => {
+                                let rerank = Some(r.score());
+                                r.with_explanation(ScoreExplanation {
+                                    rerank,
+                                    ..explanation
+                                })
+                            }
+                            None => r,
+                        }
+                    }
+                    None => r,
+                })
+                .collect();
+            span.record("result_count", results.len());
+            let rerank_time = rerank_start.elapsed();
+            span.record("latency_ms", rerank_time.as_millis() as u64);
+            metrics::histogram!(RERANK_DURATION_SECONDS).record(rerank_time.as_secs_f64());
             reranked = true;
         }
 
@@ -236,14 +384,51 @@ impl SearchCodeUseCase {
             }
         }
 
+        if self.dedup {
+            let before = results.len();
+            results = collapse_duplicates(results);
+            if results.len() < before {
+                info!("Collapsed {} duplicate results", before - results.len());
+            }
+        }
+
+        if let Some(lambda) = self.diversity {
+            results = self.diversify(results, lambda, query.limit()).await;
+        } else {
+            results.truncate(query.limit());
+        }
+
         let duration = start_time.elapsed();
         info!(
             "Found {} results in {:.2}s",
             results.len(),
             duration.as_secs_f64()
         );
+        let span = Span::current();
+        span.record("result_count", results.len());
+        span.record("latency_ms", duration.as_millis() as u64);
+        metrics::histogram!(SEARCH_DURATION_SECONDS).record(duration.as_secs_f64());
 
-        Ok(results)
+        Ok((results, expansions))
+    }
+
+    /// One page of a paginated search. Pages rank by semantic similarity
+    /// alone, in the repository's (score, chunk id) order, so a cursor taken
+    /// from one page continues exactly where it stopped; fusion, expansion,
+    /// reranking, dedup and diversity would reorder results between pages
+    /// and are skipped.
+    async fn execute_page(&self, query: SearchQuery) -> Result<Vec<SearchResult>, DomainError> {
+        query.cursor()?;
+        if !self.vector_repo.has_embeddings().await? {
+            return Err(DomainError::invalid_input(
+                "Paginated search needs a namespace indexed with embeddings",
+            ));
+        }
+        let query = query.with_text_search(false);
+        let embedding = embed_query(self.embedding_service.as_ref(), query.query()).await?;
+        let results = self.vector_repo.search(Some(&embedding), &query).await?;
+        info!("Found {} results for page", results.len());
+        Ok(explain_unfused(results, &query))
     }
 
     pub async fn search(
@@ -254,4 +439,374 @@ impl SearchCodeUseCase {
         let search_query = SearchQuery::new(query).with_limit(limit);
         self.execute(search_query).await
     }
+
+    /// Fetch the stored vectors for `results` and MMR-select `limit` of them.
+    /// Without vectors (fetch failure, no-embeddings store) every pair looks
+    /// unrelated and the selection degrades to plain relevance order.
+    async fn diversify(
+        &self,
+        results: Vec<SearchResult>,
+        lambda: f32,
+        limit: usize,
+    ) -> Vec<SearchResult> {
+        let ids: Vec<&str> = results.iter().map(|r| r.chunk().id()).collect();
+        let embeddings = match self.vector_repo.fetch_embeddings(&ids).await {
+            Ok(embeddings) => embeddings,
+            Err(e) => {
+                warn!("Failed to fetch embeddings for diversity re-ranking: {}", e);
+                vec![]
+            }
+        };
+        let vectors: HashMap<String, Vec<f32>> = embeddings
+            .into_iter()
+            .map(|e| (e.chunk_id().to_string(), e.vector().to_vec()))
+            .collect();
+
+        let pool = results.len();
+        let selected = mmr_select(results, &vectors, lambda, limit);
+        info!(
+            "Diversity re-ranking (lambda={:.2}): {} of {} candidates",
+            lambda,
+            selected.len(),
+            pool
+        );
+        selected
+    }
+}
+
+/// With `query.explain()`, record the leg position of results that came
+/// straight from the semantic leg: a semantic-only search, or a hybrid one
+/// whose keyword leg was unavailable. Fused results already carry theirs.
+fn explain_unfused(results: Vec<SearchResult>, query: &SearchQuery) -> Vec<SearchResult> {
+    if !query.explain() {
+        return results;
+    }
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(i, r)| {
+            if r.explanation().is_some() {
+                return r;
+            }
+            let semantic = Some(LegScore {
+                rank: i + 1,
+                score: r.score(),
+            });
+            r.with_explanation(ScoreExplanation {
+                semantic,
+                ..ScoreExplanation::default()
+            })
+        })
+        .collect()
+}
+
+/// Fold duplicates into the best-ranked instance of each result, counting
+/// them on it: results whose content is identical once whitespace is
+/// collapsed, and results from the same file whose line ranges overlap (the
+/// parts of a split symbol, or a method and its enclosing `impl`). `results`
+/// must be in rank order.
+fn collapse_duplicates(results: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut kept: Vec<SearchResult> = Vec::with_capacity(results.len());
+    let mut by_content: HashMap<u64, usize> = HashMap::new();
+
+    for result in results {
+        let chunk = result.chunk();
+        let content_hash = {
+            let mut hasher = DefaultHasher::new();
+            for word in chunk.content().split_whitespace() {
+                word.hash(&mut hasher);
+            }
+            hasher.finish()
+        };
+        let original = by_content.get(&content_hash).copied().or_else(|| {
+            kept.iter().position(|k| {
+                let other = k.chunk();
+                other.repository_id() == chunk.repository_id()
+                    && other.file_path() == chunk.file_path()
+                    && other.start_line() <= chunk.end_line()
+                    && chunk.start_line() <= other.end_line()
+            })
+        });
+        match original {
+            Some(index) => {
+                let duplicates = kept[index].duplicates() + 1 + result.duplicates();
+                kept[index] = kept[index].clone().with_duplicates(duplicates);
+            }
+            None => {
+                by_content.insert(content_hash, kept.len());
+                kept.push(result);
+            }
+        }
+    }
+    kept
+}
+
+/// Embed a search query inside an `embed_query` span, so traces show the
+/// embedding latency separately from the store lookup.
+async fn embed_query(
+    embedding_service: &dyn EmbeddingService,
+    query: &str,
+) -> Result<Vec<f32>, DomainError> {
+    let span = info_span!("embed_query", query = %query, latency_ms = Empty);
+    let started = Instant::now();
+    let embedding = embedding_service
+        .embed_query(query)
+        .instrument(span.clone())
+        .await?;
+    let elapsed = started.elapsed();
+    span.record("latency_ms", elapsed.as_millis() as u64);
+    metrics::histogram!(EMBEDDING_DURATION_SECONDS).record(elapsed.as_secs_f64());
+    Ok(embedding)
+}
+
+/// Reject node-type filters that match nothing, naming the valid values, so a
+/// typo like `--node-type fn` is an error rather than an empty result.
+pub(super) fn validate_node_types(query: &SearchQuery) -> Result<(), DomainError> {
+    let Some(types) = query.node_types() else {
+        return Ok(());
+    };
+    if let Some(unknown) = types.iter().find(|t| NodeType::try_parse(t).is_none()) {
+        let valid: Vec<&str> = NodeType::all().iter().map(|t| t.as_str()).collect();
+        return Err(DomainError::invalid_input(format!(
+            "Unknown node type '{unknown}'. Valid values: {}",
+            valid.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Greedy Maximal Marginal Relevance selection of up to `limit` results.
+///
+/// Relevance is the result score min-max normalised over the pool, so RRF,
+/// cosine, and reranker scores all trade off against cosine similarity on
+/// the same `[0, 1]` scale.  Results keep their original scores; only the
+/// order (and the cut) changes.
+fn mmr_select(
+    results: Vec<SearchResult>,
+    vectors: &HashMap<String, Vec<f32>>,
+    lambda: f32,
+    limit: usize,
+) -> Vec<SearchResult> {
+    let (min, max) = results
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), r| {
+            (lo.min(r.score()), hi.max(r.score()))
+        });
+    let range = max - min;
+    let relevance = |r: &SearchResult| {
+        if range > 0.0 {
+            (r.score() - min) / range
+        } else {
+            1.0
+        }
+    };
+    let similarity = |a: &SearchResult, b: &SearchResult| match (
+        vectors.get(a.chunk().id()),
+        vectors.get(b.chunk().id()),
+    ) {
+        (Some(va), Some(vb)) => cosine_similarity(va, vb),
+        _ => 0.0,
+    };
+
+    let mut remaining = results;
+    let mut selected: Vec<SearchResult> = Vec::with_capacity(limit.min(remaining.len()));
+    while selected.len() < limit && !remaining.is_empty() {
+        let best = remaining
+            .iter()
+            .enumerate()
+            .map(|(i, candidate)| {
+                let redundancy = selected
+                    .iter()
+                    .map(|s| similarity(candidate, s))
+                    .fold(0.0f32, f32::max);
+                (
+                    i,
+                    lambda * relevance(candidate) - (1.0 - lambda) * redundancy,
+                )
+            })
+            // Ties keep the earlier (higher-ranked) candidate.
+            .fold(None, |best: Option<(usize, f32)>, (i, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((i, score)),
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        selected.push(remaining.remove(best));
+    }
+    selected
+}
+
+/// The original query followed by at most [`MAX_EXPANDED_VARIANTS`] distinct
+/// phrasings from `expander`. An expander error (LLM unreachable) degrades to
+/// the original query alone instead of failing the search.
+async fn expand_variants(expander: &dyn QueryExpander, query: &str) -> Vec<String> {
+    let expanded = match expander.expand(query).await {
+        Ok(variants) => variants,
+        Err(e) => {
+            warn!(
+                "Query expansion failed (searching the original query only): {}",
+                e
+            );
+            Vec::new()
+        }
+    };
+    let mut variants = vec![query.to_string()];
+    for variant in expanded {
+        if variants.len() > MAX_EXPANDED_VARIANTS {
+            break;
+        }
+        let variant = variant.trim();
+        if !variant.is_empty() && !variants.iter().any(|v| v == variant) {
+            variants.push(variant.to_string());
+        }
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CodeChunk, Language, NodeType};
+
+    fn result(id: &str, score: f32) -> SearchResult {
+        let chunk = CodeChunk::reconstitute(
+            id.to_string(),
+            format!("{id}.rs"),
+            format!("fn {id}() {{}}"),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            Some(id.to_string()),
+            None,
+            "repo".to_string(),
+        );
+        SearchResult::new(chunk, score)
+    }
+
+    fn chunk_result(id: &str, file: &str, lines: (u32, u32), content: &str) -> SearchResult {
+        let chunk = CodeChunk::reconstitute(
+            id.to_string(),
+            file.to_string(),
+            content.to_string(),
+            lines.0,
+            lines.1,
+            Language::Rust,
+            NodeType::Function,
+            Some(id.to_string()),
+            None,
+            "repo".to_string(),
+        );
+        SearchResult::new(chunk, 0.5)
+    }
+
+    fn vectors(entries: &[(&str, [f32; 2])]) -> HashMap<String, Vec<f32>> {
+        entries
+            .iter()
+            .map(|(id, v)| (id.to_string(), v.to_vec()))
+            .collect()
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.chunk().id()).collect()
+    }
+
+    #[test]
+    fn mmr_skips_near_duplicates() {
+        // `a` and `a2` point the same way; `b` is slightly less relevant but
+        // orthogonal, so it should be picked before the duplicate.
+        let results = vec![result("a", 0.9), result("a2", 0.88), result("b", 0.8)];
+        let vecs = vectors(&[("a", [1.0, 0.0]), ("a2", [1.0, 0.01]), ("b", [0.0, 1.0])]);
+
+        let picked = mmr_select(results, &vecs, 0.5, 2);
+        assert_eq!(ids(&picked), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn mmr_with_lambda_one_keeps_relevance_order() {
+        let results = vec![result("a", 0.9), result("a2", 0.88), result("b", 0.8)];
+        let vecs = vectors(&[("a", [1.0, 0.0]), ("a2", [1.0, 0.01]), ("b", [0.0, 1.0])]);
+
+        let picked = mmr_select(results, &vecs, 1.0, 3);
+        assert_eq!(ids(&picked), vec!["a", "a2", "b"]);
+    }
+
+    #[test]
+    fn mmr_without_vectors_keeps_relevance_order() {
+        let results = vec![result("a", 0.9), result("b", 0.5), result("c", 0.1)];
+
+        let picked = mmr_select(results, &HashMap::new(), 0.3, 2);
+        assert_eq!(ids(&picked), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn collapse_duplicates_folds_identical_content_across_files() {
+        let results = vec![
+            chunk_result("a", "src/a.rs", (1, 3), "fn f() {\n    1\n}"),
+            chunk_result("b", "src/b.rs", (1, 1), "fn g() {}"),
+            chunk_result("c", "vendor/a.rs", (10, 12), "fn f() {\n\t1 }"),
+            chunk_result("d", "gen/a.rs", (1, 3), "fn  f() {  1  }"),
+        ];
+
+        let kept = collapse_duplicates(results);
+        assert_eq!(ids(&kept), vec!["a", "b"]);
+        assert_eq!(kept[0].duplicates(), 2);
+        assert_eq!(kept[1].duplicates(), 0);
+    }
+
+    #[test]
+    fn collapse_duplicates_folds_overlapping_ranges_in_one_file() {
+        let results = vec![
+            chunk_result("method", "src/a.rs", (5, 8), "fn m() {}"),
+            chunk_result("impl", "src/a.rs", (1, 20), "impl A { fn m() {} }"),
+            chunk_result("after", "src/a.rs", (21, 30), "fn n() {}"),
+            chunk_result("other", "src/b.rs", (5, 8), "fn o() {}"),
+        ];
+
+        let kept = collapse_duplicates(results);
+        assert_eq!(ids(&kept), vec!["method", "after", "other"]);
+        assert_eq!(kept[0].duplicates(), 1);
+    }
+
+    struct FixedExpander(Result<Vec<&'static str>, &'static str>);
+
+    #[async_trait::async_trait]
+    impl QueryExpander for FixedExpander {
+        async fn expand(&self, _query: &str) -> Result<Vec<String>, DomainError> {
+            match &self.0 {
+                Ok(variants) => Ok(variants.iter().map(|v| v.to_string()).collect()),
+                Err(e) => Err(DomainError::storage(*e)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn expand_variants_caps_and_dedups_phrasings() {
+        let expander = FixedExpander(Ok(vec![
+            "auth flow",
+            "login handler",
+            " ",
+            "login handler",
+            "verify credentials",
+            "session token",
+            "password check",
+        ]));
+        let variants = expand_variants(&expander, "auth flow").await;
+        assert_eq!(
+            variants,
+            vec![
+                "auth flow",
+                "login handler",
+                "verify credentials",
+                "session token"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn expand_variants_falls_back_to_original_on_error() {
+        let expander = FixedExpander(Err("connection refused"));
+        let variants = expand_variants(&expander, "auth flow").await;
+        assert_eq!(variants, vec!["auth flow"]);
+    }
 }
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use tracing::info;
+
+use super::search_code::validate_node_types;
+use crate::application::VectorRepository;
+use crate::domain::{CodeChunk, DomainError, SearchQuery, SearchResult};
+
+/// The chunk a similarity lookup started from, and the chunks most similar to it.
+#[derive(Debug, Clone)]
+pub struct SimilarCode {
+    pub source: CodeChunk,
+    pub results: Vec<SearchResult>,
+}
+
+/// Finds code similar to an indexed location, behind `codesearch similar`.
+///
+/// The chunk covering the location is looked up in the store and its stored
+/// embedding is used as the query vector, so nothing is embedded again.
+pub struct SimilarCodeUseCase {
+    vector_repo: Arc<dyn VectorRepository>,
+}
+
+impl SimilarCodeUseCase {
+    pub fn new(vector_repo: Arc<dyn VectorRepository>) -> Self {
+        Self { vector_repo }
+    }
+
+    /// Search for the chunks most similar to the one covering `line` of
+    /// `file_path` (relative to its repository root, in any repository).
+    /// `query` supplies the limit, `min_score` threshold and filters; its
+    /// text is ignored. The source chunk itself is never returned.
+    pub async fn execute(
+        &self,
+        file_path: &str,
+        line: u32,
+        query: &SearchQuery,
+    ) -> Result<SimilarCode, DomainError> {
+        validate_node_types(query)?;
+
+        // The tightest chunk containing the line is the function the user is
+        // pointing at, not the class or module around it.
+        let source = self
+            .vector_repo
+            .find_chunks_by_file("", file_path)
+            .await?
+            .into_iter()
+            .filter(|c| c.start_line() <= line && c.end_line() >= line)
+            .min_by_key(|c| c.line_count())
+            .ok_or_else(|| {
+                DomainError::not_found(format!("No indexed chunk covers {file_path}:{line}"))
+            })?;
+
+        let embedding = self
+            .vector_repo
+            .fetch_embeddings(&[source.id()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                DomainError::invalid_input(format!(
+                    "{} has no stored embedding; similarity search needs a namespace \
+                     indexed with embeddings",
+                    source.location()
+                ))
+            })?;
+
+        // One extra hit, since the source chunk is its own best match.
+        let search_query = query
+            .clone()
+            .with_limit(query.limit() + 1)
+            .with_text_search(false);
+        let mut results = self
+            .vector_repo
+            .search(Some(embedding.vector()), &search_query)
+            .await?;
+        results.retain(|r| r.chunk().id() != source.id());
+        results.truncate(query.limit());
+
+        info!(
+            "Found {} chunks similar to {}",
+            results.len(),
+            source.location()
+        );
+        Ok(SimilarCode { source, results })
+    }
+}
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use anyhow::Context;
 
 use crate::application::use_cases::pattern_utils::{class_hint_from_symbol, short_symbol_name};
-use crate::application::VectorRepository;
-use crate::domain::{CodeChunk, DomainError};
+use crate::application::{FileEntry, VectorRepository};
+use crate::domain::{CodeChunk, DomainError, Language};
 
 /// Retrieves an indexed [`CodeChunk`] for a reference location shown in the TUI.
 ///
@@ -52,6 +52,42 @@ impl SnippetLookupUseCase {
         Ok(best.cloned())
     }
 
+    /// Every chunk stored for `file_path`, in line order; with `line`, only
+    /// the chunks whose range contains it (nested definitions and
+    /// overlapping parts can share a line).
+    ///
+    /// `repository_id` may be an empty string to search across all repositories.
+    pub async fn chunks_for_file(
+        &self,
+        repository_id: &str,
+        file_path: &str,
+        line: Option<u32>,
+    ) -> Result<Vec<CodeChunk>, DomainError> {
+        let mut chunks = self
+            .vector_repo
+            .find_chunks_by_file(repository_id, file_path)
+            .await?;
+        if let Some(line) = line {
+            chunks.retain(|c| c.start_line() <= line && c.end_line() >= line);
+        }
+        chunks.sort_by_key(|c| (c.start_line(), c.part().unwrap_or(1)));
+        Ok(chunks)
+    }
+
+    /// Every file stored for `repository_id` with its chunk count, sorted by
+    /// path; with `language`, only the files in that language.
+    pub async fn files_for_repository(
+        &self,
+        repository_id: &str,
+        language: Option<Language>,
+    ) -> Result<Vec<FileEntry>, DomainError> {
+        let mut files = self.vector_repo.list_files(repository_id).await?;
+        if let Some(language) = language {
+            files.retain(|f| f.language == language);
+        }
+        Ok(files)
+    }
+
     /// Return the definition chunk for a callee symbol given its fully-qualified name.
     ///
     /// Used for callee nodes in the Context tree view where only the callee FQN is
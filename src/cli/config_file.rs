@@ -0,0 +1,269 @@
+//! Optional TOML file of CLI defaults, read before the command line is parsed
+//! (default `~/.codesearch/config.toml`, or the path in `CODESEARCH_CONFIG`).
+//!
+//! Top-level keys mirror the global flags and `[defaults.<subcommand>]` tables
+//! the subcommand flags, both under their snake_case argument names:
+//!
+//! ```toml
+//! data_dir = "~/work/.cs"
+//! namespace = "myproject"
+//! no_rerank = true
+//!
+//! [defaults.search]
+//! num = 20
+//! format = "json"
+//! ```
+//!
+//! Values become the arguments' default values, so anything given on the
+//! command line still wins and missing keys keep the built-in defaults.
+
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Arg, Command};
+use serde::Deserialize;
+
+/// Environment variable naming a config file to use instead of the default.
+pub const CONFIG_ENV_VAR: &str = "CODESEARCH_CONFIG";
+
+/// Config file looked up under `$HOME` when `CODESEARCH_CONFIG` is not set.
+const DEFAULT_CONFIG_FILE: &str = ".codesearch/config.toml";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfigFile {
+    /// Per-subcommand defaults, keyed by subcommand name; nested tables reach
+    /// nested subcommands (`[defaults.memory.search]`).
+    #[serde(default)]
+    defaults: toml::Table,
+
+    /// Global flags (`data_dir`, `namespace`, `no_rerank`, …).
+    #[serde(flatten)]
+    globals: toml::Table,
+}
+
+impl ConfigFile {
+    /// Read the config file. A missing default file is not an error (there is
+    /// simply nothing to apply); a missing `CODESEARCH_CONFIG` file is.
+    pub async fn load() -> Result<Self> {
+        let (path, required) = match std::env::var_os(CONFIG_ENV_VAR) {
+            Some(path) => (PathBuf::from(path), true),
+            None => match std::env::var_os("HOME") {
+                Some(home) => (PathBuf::from(home).join(DEFAULT_CONFIG_FILE), false),
+                None => return Ok(Self::default()),
+            },
+        };
+        let text = match tokio::fs::read_to_string(&path).await {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => {
+                return Ok(Self::default())
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        Self::parse(&text).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Whether the file pins the namespace, which then should not be
+    /// auto-resolved from the current repository.
+    pub fn sets_namespace(&self) -> bool {
+        self.globals.contains_key("namespace")
+    }
+
+    /// Install the file's values as default values on `cmd`'s arguments.
+    /// Unknown keys and values the flag would reject are reported here, with
+    /// the key that caused them, rather than as a confusing parse error.
+    pub fn apply(&self, mut cmd: Command) -> Result<Command> {
+        for (key, value) in &self.globals {
+            let is_global = cmd
+                .get_arguments()
+                .any(|a| a.get_id() == arg_id(key).as_str() && a.is_global_set());
+            if !is_global {
+                bail!("unknown key '{key}': top-level keys must be global flags");
+            }
+            cmd = set_default(cmd, key, value, key)?;
+        }
+        for (name, table) in &self.defaults {
+            cmd = apply_subcommand(cmd, name, table, &format!("defaults.{name}"))?;
+        }
+        Ok(cmd)
+    }
+}
+
+fn apply_subcommand(cmd: Command, name: &str, table: &toml::Value, path: &str) -> Result<Command> {
+    let toml::Value::Table(table) = table else {
+        bail!("'{path}' must be a table of flag defaults");
+    };
+    let Some(sub) = cmd.find_subcommand(name) else {
+        bail!("unknown subcommand '{name}' in '{path}'");
+    };
+    let mut sub = sub.clone();
+    for (key, value) in table {
+        let key_path = format!("{path}.{key}");
+        sub = if value.is_table() {
+            apply_subcommand(sub, key, value, &key_path)?
+        } else {
+            set_default(sub, key, value, &key_path)?
+        };
+    }
+    let name = sub.get_name().to_string();
+    Ok(cmd.mut_subcommand(name, |_| sub))
+}
+
+fn set_default(cmd: Command, key: &str, value: &toml::Value, path: &str) -> Result<Command> {
+    let id = arg_id(key);
+    let Some(arg) = cmd.get_arguments().find(|a| a.get_id() == id.as_str()) else {
+        bail!("unknown key '{path}'");
+    };
+
+    // Flags (`no_rerank`) take a boolean; everything else takes its value as
+    // it would be written on the command line, or an array for repeatable
+    // flags (`language = ["rust", "go"]`).
+    let values: Vec<String> = if !arg.get_action().takes_values() {
+        match value {
+            toml::Value::Boolean(b) => vec![b.to_string()],
+            _ => bail!("'{path}' is a flag and expects true or false"),
+        }
+    } else {
+        match value {
+            toml::Value::Array(items) => items
+                .iter()
+                .map(|v| scalar(v, path))
+                .collect::<Result<_>>()?,
+            v => vec![scalar(v, path)?],
+        }
+    };
+    // Run each value through the flag's own parser (and possible values) on
+    // a throwaway command, since clap does not check default values.
+    let probe = Command::new(cmd.get_name().to_string())
+        .no_binary_name(true)
+        .arg(
+            Arg::new("value")
+                .value_parser(arg.get_value_parser().clone())
+                .allow_hyphen_values(true),
+        );
+    for v in &values {
+        probe
+            .clone()
+            .try_get_matches_from([OsStr::new(v)])
+            .map_err(|e| {
+                let message = e.to_string();
+                let reason = message.lines().next().unwrap_or_default();
+                anyhow!(
+                    "invalid value for '{path}': {}",
+                    reason.trim_start_matches("error: ")
+                )
+            })?;
+    }
+    Ok(cmd.mut_arg(id, |a| a.default_values(values)))
+}
+
+fn scalar(value: &toml::Value, path: &str) -> Result<String> {
+    Ok(match value {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Integer(i) => i.to_string(),
+        toml::Value::Float(f) => f.to_string(),
+        toml::Value::Boolean(b) => b.to_string(),
+        toml::Value::Datetime(d) => d.to_string(),
+        _ => bail!("'{path}' must be a string, number or boolean"),
+    })
+}
+
+/// Argument id for a config key: ids are the snake_case field names, and the
+/// kebab-case spelling of the long flag is accepted too.
+fn arg_id(key: &str) -> String {
+    key.replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ArgAction;
+
+    use super::*;
+
+    fn command() -> Command {
+        Command::new("codesearch")
+            .arg(Arg::new("namespace").long("namespace").global(true))
+            .arg(
+                Arg::new("no_rerank")
+                    .long("no-rerank")
+                    .global(true)
+                    .action(ArgAction::SetTrue),
+            )
+            .subcommand(
+                Command::new("search")
+                    .arg(Arg::new("query").required(true))
+                    .arg(
+                        Arg::new("num")
+                            .long("num")
+                            .default_value("10")
+                            .value_parser(clap::value_parser!(usize)),
+                    ),
+            )
+    }
+
+    fn matches(config: &str, args: &[&str]) -> clap::ArgMatches {
+        ConfigFile::parse(config)
+            .unwrap()
+            .apply(command())
+            .unwrap()
+            .try_get_matches_from(args)
+            .unwrap()
+    }
+
+    const CONFIG: &str = r#"
+namespace = "myproject"
+no_rerank = true
+
+[defaults.search]
+num = 20
+"#;
+
+    #[test]
+    fn config_values_replace_builtin_defaults() {
+        let m = matches(CONFIG, &["codesearch", "search", "q"]);
+        let (_, search) = m.subcommand().unwrap();
+        assert_eq!(search.get_one::<String>("namespace").unwrap(), "myproject");
+        assert!(search.get_flag("no_rerank"));
+        assert_eq!(*search.get_one::<usize>("num").unwrap(), 20);
+    }
+
+    #[test]
+    fn command_line_wins_over_config() {
+        let m = matches(
+            CONFIG,
+            &[
+                "codesearch",
+                "--namespace",
+                "other",
+                "search",
+                "q",
+                "--num",
+                "5",
+            ],
+        );
+        let (_, search) = m.subcommand().unwrap();
+        assert_eq!(search.get_one::<String>("namespace").unwrap(), "other");
+        assert_eq!(*search.get_one::<usize>("num").unwrap(), 5);
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_invalid_values() {
+        let apply = |text: &str| ConfigFile::parse(text).unwrap().apply(command());
+
+        let err = apply("chroma_url = \"http://localhost:8000\"").unwrap_err();
+        assert!(err.to_string().contains("unknown key 'chroma_url'"));
+
+        let err = apply("[defaults.search]\nnum = \"many\"").unwrap_err();
+        assert!(err.to_string().contains("defaults.search.num"), "{err}");
+
+        let err = apply("[defaults.index]\nforce = true").unwrap_err();
+        assert!(err.to_string().contains("unknown subcommand 'index'"));
+
+        let err = apply("no_rerank = \"yes\"").unwrap_err();
+        assert!(err.to_string().contains("expects true or false"));
+    }
+}
@@ -1,5 +1,10 @@
 use clap::{Subcommand, ValueEnum};
 
+mod config_file;
+pub use config_file::*;
+
+use crate::application::{DEFAULT_GRAPH_MAX_DEPTH, DEFAULT_PATH_MAX_DEPTH, DEFAULT_PATH_MAX_PATHS};
+
 /// Default port for the MCP HTTP server started by `codesearch serve`.
 pub const DEFAULT_MCP_PORT: u16 = 8677;
 
@@ -30,6 +35,77 @@ pub fn validate_namespace(s: &str) -> Result<String, String> {
     Ok(s.to_string())
 }
 
+/// Parse a size such as `1048576`, `512KB` or `2MB` (binary units) into bytes.
+pub fn parse_byte_size(s: &str) -> Result<u64, String> {
+    let upper = s.trim().to_ascii_uppercase();
+    let (digits, multiplier) = [
+        ("GB", 1u64 << 30),
+        ("MB", 1 << 20),
+        ("KB", 1 << 10),
+        ("B", 1),
+    ]
+    .iter()
+    .find_map(|(suffix, m)| upper.strip_suffix(suffix).map(|d| (d, *m)))
+    .unwrap_or((upper.as_str(), 1));
+    digits
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid size '{s}' (expected e.g. 1048576, 512KB or 2MB)"))
+}
+
+/// Subcommands for the `namespace` command.
+#[derive(Subcommand)]
+pub enum NamespaceSubcommand {
+    /// List the namespaces in the index with their embedding model, size and
+    /// repositories
+    List {
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
+    /// Drop a namespace: its chunks, embeddings and full-text index, plus the
+    /// repositories stored in it along with their call graph and file hashes.
+    /// Other namespaces are left untouched.
+    Delete {
+        /// Namespace to delete
+        #[arg(value_parser = validate_namespace)]
+        name: String,
+
+        /// Skip the confirmation prompt (required when stdin is not a terminal)
+        #[arg(long)]
+        yes: bool,
+
+        /// Allow deleting the namespace selected by --namespace
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Subcommands for the `scip` command.
+#[derive(Subcommand)]
+pub enum ScipSubcommand {
+    /// Write a repository's call graph as a SCIP index, for Sourcegraph and
+    /// other SCIP-compatible tools
+    Export {
+        /// Repository ID or name to export
+        repository: String,
+
+        /// Index file to write
+        #[arg(short, long, visible_alias = "output", default_value = "index.scip")]
+        out: String,
+    },
+}
+
+/// Subcommands for the `cache` command.
+#[derive(Subcommand)]
+pub enum CacheSubcommand {
+    /// Drop every cached embedding; the next index run embeds all chunks again
+    Clear,
+}
+
 /// Subcommands for the `features` command.
 #[derive(Subcommand)]
 pub enum FeaturesSubcommand {
@@ -88,6 +164,58 @@ pub enum OutputFormat {
     Json,
     /// vimgrep-compatible format (file:line:col:text) for quickfix/Telescope
     Vimgrep,
+    /// SARIF 2.1.0 log for code-scanning dashboards (`search` only)
+    Sarif,
+}
+
+/// Output format for the `impact` command.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum ImpactOutputFormat {
+    /// Human-readable text (default)
+    #[default]
+    Text,
+    /// JSON report object
+    Json,
+    /// vimgrep-compatible format (file:line:col:text) for quickfix/Telescope
+    Vimgrep,
+    /// Graphviz digraph, e.g. `codesearch impact foo -F dot | dot -Tsvg`
+    Dot,
+    /// SARIF 2.1.0 log with one result per affected symbol
+    Sarif,
+}
+
+/// Output format for the `graph` command.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum GraphOutputFormat {
+    /// Graphviz digraph (default), e.g. `codesearch graph foo | dot -Tsvg`
+    #[default]
+    Dot,
+    /// Mermaid flowchart for Markdown
+    Mermaid,
+}
+
+/// Output format for the `context` command.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum ContextOutputFormat {
+    /// Human-readable text (default)
+    #[default]
+    Text,
+    /// JSON context object
+    Json,
+    /// vimgrep-compatible format (file:line:col:text) for quickfix/Telescope
+    Vimgrep,
+    /// Mermaid `graph TD` flowchart, for Markdown renderers that support it
+    Mermaid,
+}
+
+/// Grammar used for `.h` headers, which are valid C and C++ alike.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum HeaderLanguage {
+    /// Parse headers as C (default)
+    #[default]
+    C,
+    /// Parse headers as C++
+    Cpp,
 }
 
 /// Output format for cluster commands (text or json only).
@@ -109,6 +237,26 @@ impl From<OutputFormatTextJson> for OutputFormat {
     }
 }
 
+/// Sort order for `codesearch files`.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum FileSort {
+    /// By file path (default)
+    #[default]
+    Path,
+    /// Most chunks first
+    Chunks,
+}
+
+/// Format of the log lines written to stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable text, errors only (default)
+    #[default]
+    Text,
+    /// One JSON object per line, at the same level as the log file
+    Json,
+}
+
 /// Sections of the `overview` report that can be skipped.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum OverviewSection {
@@ -426,7 +574,10 @@ pub enum EmbeddingTarget {
     #[default]
     Onnx,
     /// OpenAI-compatible /v1/embeddings endpoint (set OPENAI_BASE_URL to override)
+    #[value(alias = "openai")]
     Api,
+    /// Local Ollama server's /api/embeddings endpoint (set OLLAMA_BASE_URL to override)
+    Ollama,
 }
 
 /// Provider to use for LLM calls (query expansion, explain, etc.).
@@ -500,17 +651,19 @@ pub enum Commands {
         #[arg(value_parser = validate_namespace)]
         name: Option<String>,
 
-        /// Embedding backend: 'onnx' (bundled, offline) or 'api' (OpenAI-compatible endpoint)
+        /// Embedding backend: 'onnx' (bundled, offline), 'api' (OpenAI-compatible
+        /// endpoint; also accepted as 'openai'), or 'ollama' (local Ollama server)
         #[arg(
             long,
+            alias = "embedding-provider",
             value_enum,
             default_value = "onnx",
             conflicts_with = "no_embeddings"
         )]
         embedding_target: EmbeddingTarget,
 
-        /// Embedding model — HuggingFace ID for 'onnx', model name for 'api'
-        /// (required for 'api'; defaults to all-MiniLM-L6-v2 for 'onnx')
+        /// Embedding model — HuggingFace ID for 'onnx', model name for 'api' and
+        /// 'ollama' (required for both; defaults to all-MiniLM-L6-v2 for 'onnx')
         #[arg(long, conflicts_with = "no_embeddings")]
         embedding_model: Option<String>,
 
@@ -526,10 +679,18 @@ pub enum Commands {
         no_embeddings: bool,
     },
 
+    /// Manage namespaces (see `create` to make one)
+    Namespace {
+        #[command(subcommand)]
+        subcommand: NamespaceSubcommand,
+    },
+
     /// Index a repository: parse, embed, and store its code for search
     Index {
-        /// Path to the repository (or file) to index
-        path: String,
+        /// Path to the repository (or file) to index. Several paths are
+        /// indexed as one repository (name it with --name)
+        #[arg(required = true)]
+        paths: Vec<String>,
 
         /// Namespace to index into (defaults to the global --namespace value)
         #[arg(short, long)]
@@ -538,6 +699,69 @@ pub enum Commands {
         /// Force full re-index, ignoring cached file hashes
         #[arg(short, long)]
         force: bool,
+
+        /// Only index files matching this glob (repeatable; .gitignore syntax).
+        /// Remembered for later re-indexes of the same repository.
+        #[arg(long = "include", value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Skip files matching this glob (repeatable; .gitignore syntax).
+        /// Remembered for later re-indexes of the same repository.
+        #[arg(long = "exclude", value_name = "GLOB")]
+        exclude: Vec<String>,
+
+        /// Keep running after the initial index and re-index files as they change
+        #[arg(short, long)]
+        watch: bool,
+
+        /// With --watch, wait this long after the last file event before
+        /// re-indexing, so bursts of saves are handled together
+        #[arg(long, default_value = "500", value_name = "MS", requires = "watch")]
+        debounce_ms: u64,
+
+        /// Parse `.h` headers as C or C++
+        #[arg(long, value_enum, default_value = "c")]
+        header_language: HeaderLanguage,
+
+        /// Split functions and classes longer than this many lines into
+        /// overlapping parts (0 keeps every node whole)
+        #[arg(long, default_value = "150", value_name = "LINES")]
+        max_chunk_lines: usize,
+
+        /// Skip Markdown and plain-text documentation (.md, .mdx, .txt) and
+        /// index code only
+        #[arg(long)]
+        no_docs: bool,
+
+        /// Take changed files from `git diff REV` instead of hashing every
+        /// file (REV defaults to the commit recorded by the last index run).
+        /// Falls back to hashing when git cannot answer
+        #[arg(long, value_name = "REV", conflicts_with = "force")]
+        since: Option<Option<String>>,
+
+        /// Number of files parsed in parallel (defaults to the number of CPUs)
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+
+        /// Skip files larger than this (e.g. 512KB, 2MB); binary files are
+        /// always skipped
+        #[arg(long, default_value = "1MB", value_name = "SIZE", value_parser = parse_byte_size)]
+        max_file_size: u64,
+    },
+
+    /// Re-index only the files that were added, changed or removed since the
+    /// last index run
+    Update {
+        /// Path to the repository to update
+        path: String,
+
+        /// Namespace to index into (defaults to the global --namespace value)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Re-index every file, ignoring cached file hashes
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Search indexed code by natural-language query (hybrid semantic + keyword)
@@ -557,13 +781,162 @@ pub enum Commands {
         #[arg(short, long)]
         repository: Option<Vec<String>>,
 
-        /// Output format: text, json, or vimgrep (for Neovim/Telescope)
+        /// Only return chunks of these node types (comma-separated), e.g. function,class
+        #[arg(short = 'T', long = "node-type", value_delimiter = ',')]
+        node_type: Option<Vec<String>>,
+
+        /// Only return coroutines: Kotlin `suspend fun`, Rust `async fn`
+        #[arg(long)]
+        async_only: bool,
+
+        /// Only return chunks whose file path matches this glob, e.g.
+        /// 'src/auth/**' (repeatable); prefix with '!' to exclude, e.g.
+        /// '!**/tests/**'
+        #[arg(short, long, value_name = "GLOB")]
+        path: Vec<String>,
+
+        /// Skip chunks whose file path matches this glob, e.g. 'vendor/**'
+        /// (repeatable)
+        #[arg(short = 'E', long, value_name = "GLOB")]
+        exclude: Vec<String>,
+
+        /// Only return chunks whose symbol name matches this regex, e.g.
+        /// '^handle_' or 'Repository$'. Inline flags like '(?i)', '\b' and
+        /// '\p{..}' are rejected since the database engines read them differently
+        #[arg(short = 'S', long = "symbol-regex", value_name = "REGEX")]
+        symbol_regex: Option<String>,
+
+        /// Trade relevance for variety (MMR lambda): 1.0 ranks purely by
+        /// relevance, lower values push near-duplicate results down
+        #[arg(long, default_value = "1.0", value_name = "LAMBDA")]
+        diversity: f32,
+
+        /// Output format: text, json, vimgrep (for Neovim/Telescope), or sarif
         #[arg(short = 'F', long, value_enum, default_value = "text")]
         format: OutputFormat,
 
         /// Disable keyword (BM25) search and use only semantic (vector) search
         #[arg(long = "no-text-search", default_value_t = true, action = clap::ArgAction::SetFalse)]
         text_search: bool,
+
+        /// RRF smoothing constant for hybrid fusion: lower values widen the
+        /// gap between top-ranked and lower-ranked hits
+        #[arg(long, default_value = "60", value_name = "K")]
+        rrf_k: f32,
+
+        /// Weight of the semantic leg in hybrid fusion
+        #[arg(long, default_value = "1.0", value_name = "WEIGHT")]
+        semantic_weight: f32,
+
+        /// Weight of the keyword (BM25) leg in hybrid fusion; raise it to favour
+        /// exact keyword matches
+        #[arg(long, default_value = "1.0", value_name = "WEIGHT")]
+        text_weight: f32,
+
+        /// Keyword (BM25) candidates fetched before fusion (default: three per
+        /// requested result, at least 30)
+        #[arg(long, value_name = "N")]
+        text_candidates: Option<usize>,
+
+        /// Keep duplicate results: identical code in several files and
+        /// overlapping chunks of one file are collapsed by default
+        #[arg(long)]
+        no_dedup: bool,
+
+        /// Cluster results under one header per file (text), or as
+        /// `{file_path, matches}` objects (json)
+        #[arg(long)]
+        group_by_file: bool,
+
+        /// Show N lines above and below each result, read from the current
+        /// source file (text and json)
+        #[arg(short = 'C', long, default_value = "0", value_name = "N")]
+        context: usize,
+
+        /// Show how each score came about: semantic and BM25 rank/score, the
+        /// fused RRF score and the reranker score
+        #[arg(long)]
+        explain: bool,
+
+        /// Treat the query as a symbol name and return its definitions (exact,
+        /// then prefix matches) instead of searching by meaning
+        #[arg(long)]
+        symbol: bool,
+
+        /// Print the query variants generated by query expansion (implies
+        /// --expand-query)
+        #[arg(long)]
+        show_expansions: bool,
+    },
+
+    /// Find code similar to the function at a location, using its stored
+    /// embedding as the query
+    Similar {
+        /// Location as <file>:<line>, the file relative to the indexed
+        /// repository root
+        location: String,
+
+        #[arg(long, default_value = "10")]
+        num: usize,
+
+        /// Minimum cosine similarity (0.0-1.0) for a result to be shown
+        #[arg(short, long)]
+        threshold: Option<f32>,
+
+        #[arg(short = 'L', long)]
+        language: Option<Vec<String>>,
+
+        #[arg(short, long)]
+        repository: Option<Vec<String>>,
+
+        /// Only return chunks of these node types (comma-separated), e.g. function,class
+        #[arg(short = 'T', long = "node-type", value_delimiter = ',')]
+        node_type: Option<Vec<String>>,
+
+        /// Only return chunks whose file path matches this glob (repeatable);
+        /// prefix with '!' to exclude
+        #[arg(short, long, value_name = "GLOB")]
+        path: Vec<String>,
+
+        /// Output format: text, json, vimgrep (for Neovim/Telescope), or sarif
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Show the chunks stored for a file, to debug why it does or doesn't
+    /// show up in search results
+    Chunk {
+        /// File path relative to the indexed repository root
+        file: String,
+
+        /// Only show chunks whose line range contains this line
+        #[arg(short, long)]
+        line: Option<u32>,
+
+        /// Restrict to a specific repository ID
+        #[arg(short, long)]
+        repository: Option<String>,
+    },
+
+    /// List the files stored for a repository with their chunk counts, to
+    /// debug files that are missing from the index or indexed twice
+    Files {
+        /// Restrict to a specific repository ID (default: every indexed
+        /// repository)
+        #[arg(short, long)]
+        repository: Option<String>,
+
+        /// Only list files in this language
+        #[arg(short, long)]
+        language: Option<String>,
+
+        /// Sort order: path or chunks
+        #[arg(long, value_enum, default_value = "path")]
+        sort: FileSort,
+
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
     },
 
     /// List the repositories indexed in the current namespace
@@ -575,25 +948,129 @@ pub enum Commands {
         id_or_path: String,
     },
 
+    /// Remove chunks, embeddings, file hashes and call-graph rows of files
+    /// that no longer exist on disk
+    Prune {
+        /// Repository ID to prune (default: every indexed repository)
+        repository: Option<String>,
+
+        /// List the stale files without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
+    /// Write the index (chunks, embeddings, file hashes, call graph) of a
+    /// repository, or of every repository in the namespace, to a portable
+    /// .tar.zst archive
+    Export {
+        /// Repository ID or name to export (defaults to the whole namespace)
+        repository: Option<String>,
+
+        /// Archive file to write
+        #[arg(short, long, visible_alias = "output", default_value = "index.tar.zst")]
+        out: String,
+    },
+
+    /// Load an archive written by `export` into the current namespace
+    Import {
+        /// Archive file to read
+        archive: String,
+
+        /// Local checkout of the archived repository (single-repository
+        /// archives only). Without it the archived paths are kept, so the
+        /// index can be searched where the sources are not checked out
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+
+    /// Reclaim space in the database file left behind by deleted
+    /// repositories (DuckDB never shrinks the file on its own)
+    Vacuum {
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
+    /// Manage the embedding cache that lets re-indexing skip chunks whose
+    /// content was already embedded with the same model
+    Cache {
+        #[command(subcommand)]
+        subcommand: CacheSubcommand,
+    },
+
+    /// Exchange call graphs with SCIP tooling
+    Scip {
+        #[command(subcommand)]
+        subcommand: ScipSubcommand,
+    },
+
     /// Show index statistics (chunks, embeddings, call-graph size) for the namespace
-    Stats,
+    Stats {
+        /// Output format
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
+    /// Check the namespace's index for missing embeddings, stale indexes and
+    /// metadata drift; exits non-zero when issues are found
+    Health,
 
     /// Show the blast radius of changing a symbol (BFS over the call graph)
     Impact {
-        /// Symbol name or regex pattern (see --regex)
-        symbol: String,
+        /// Symbol name or regex pattern (see --regex); not needed with --diff
+        #[arg(required_unless_present = "diff", conflicts_with = "diff")]
+        symbol: Option<String>,
 
         /// Restrict analysis to a specific repository ID
         #[arg(short, long)]
         repository: Option<String>,
 
-        /// Output format: text or json
+        /// Output format: text, json, vimgrep, dot (Graphviz), or sarif
         #[arg(short = 'F', long, value_enum, default_value = "text")]
-        format: OutputFormat,
+        format: ImpactOutputFormat,
 
         /// Treat SYMBOL as a literal regex; by default it is auto-wrapped as .*SYMBOL.*
         #[arg(long)]
         regex: bool,
+
+        /// Stop at trait, interface and base class boundaries: by default a
+        /// changed trait or base method reaches its implementations and
+        /// overrides, and a changed implementation or override reaches the
+        /// method it stands in for and its callers (`dispatch`)
+        #[arg(long = "no-follow-impls", default_value_t = true, action = clap::ArgAction::SetFalse)]
+        follow_impls: bool,
+
+        /// Reference kinds to traverse (comma-separated), e.g.
+        /// call,method_call,instantiation, or `all` for every kind
+        #[arg(
+            long,
+            value_delimiter = ',',
+            default_value = "call,method_call,macro_invocation"
+        )]
+        kinds: Vec<String>,
+
+        /// Ignore references made from test files (test directories and
+        /// *_test.*, test_*, *.test.* or *.spec.* files)
+        #[arg(long)]
+        exclude_tests: bool,
+
+        /// Analyse every symbol whose chunk changed since REV (`git diff -U0
+        /// REV`, working tree included) instead of SYMBOL
+        #[arg(long, value_name = "REV")]
+        diff: Option<String>,
+
+        /// Exit non-zero when more than --threshold symbols are affected,
+        /// after printing the report (for CI gates)
+        #[arg(long)]
+        exit_code: bool,
+
+        /// Largest number of affected symbols --exit-code accepts
+        #[arg(long, default_value_t = 0, requires = "exit_code")]
+        threshold: usize,
     },
 
     /// Show callers (entry points → symbol) and callees (symbol → leaves) as an indented tree
@@ -605,15 +1082,147 @@ pub enum Commands {
         #[arg(short, long)]
         repository: Option<String>,
 
-        /// Output format: text, json, or vimgrep
+        /// Maximum number of callers and of callees to show (each direction is capped separately)
+        #[arg(short, long)]
+        limit: Option<usize>,
+
+        /// Output format: text, json, vimgrep, or mermaid
         #[arg(short = 'F', long, value_enum, default_value = "text")]
-        format: OutputFormat,
+        format: ContextOutputFormat,
 
         /// Treat SYMBOL as a literal regex; by default it is auto-wrapped as .*SYMBOL.*
         #[arg(long)]
         regex: bool,
     },
 
+    /// Trace the shortest call chains from one symbol to another (BFS over the call graph)
+    Path {
+        /// Symbol the chain starts at (the caller side)
+        from: String,
+
+        /// Symbol the chain ends at (the callee side)
+        to: String,
+
+        /// Maximum number of hops to search before giving up
+        #[arg(short, long, default_value_t = DEFAULT_PATH_MAX_DEPTH)]
+        depth: usize,
+
+        /// Maximum number of shortest paths to show
+        #[arg(long, default_value_t = DEFAULT_PATH_MAX_PATHS)]
+        max_paths: usize,
+
+        /// Restrict the search to a specific repository ID
+        #[arg(short, long)]
+        repository: Option<String>,
+
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
+    /// Export the callers of a symbol as a Graphviz (DOT) or Mermaid graph
+    Graph {
+        /// Symbol whose callers to draw
+        symbol: String,
+
+        /// Number of caller hops to include
+        #[arg(short, long, default_value_t = DEFAULT_GRAPH_MAX_DEPTH)]
+        depth: usize,
+
+        /// Restrict the graph to a specific repository ID
+        #[arg(short, long)]
+        repository: Option<String>,
+
+        /// Output format: dot or mermaid
+        #[arg(short = 'F', long, value_enum, default_value = "dot")]
+        format: GraphOutputFormat,
+    },
+
+    /// Rank symbols by incoming (fan-in) and outgoing (fan-out) reference
+    /// counts, with the file and line defining each
+    Hotspots {
+        /// Restrict the ranking to a specific repository ID
+        #[arg(short, long)]
+        repository: Option<String>,
+
+        /// Symbols listed per ranking
+        #[arg(short, long, default_value = "10")]
+        top: u32,
+
+        /// Leave symbols with fewer incoming references out of the fan-in
+        /// ranking
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        min_fanin: u64,
+
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
+    /// List circular call chains (strongly-connected groups of symbols)
+    Cycles {
+        /// Restrict the search to a specific repository ID
+        #[arg(short, long)]
+        repository: Option<String>,
+
+        /// Only report cycles with at least this many symbols (1 includes
+        /// self-recursive functions)
+        #[arg(long, default_value = "2")]
+        min_size: usize,
+
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
+    /// Point the call graph at a renamed symbol: every reference to or from
+    /// OLD_SYMBOL now uses NEW_SYMBOL. Stored chunks keep the old name until
+    /// the next index run
+    Rename {
+        /// Current symbol name, exactly as stored in the call graph
+        old_symbol: String,
+
+        /// Name to replace it with
+        new_symbol: String,
+
+        /// Restrict the rename to a specific repository ID
+        #[arg(short, long)]
+        repository: Option<String>,
+
+        /// List the references that would change without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List functions and methods nothing calls, according to the call graph
+    DeadCode {
+        /// Restrict the search to a specific repository ID
+        #[arg(short, long)]
+        repository: Option<String>,
+
+        /// Only report definitions in this language (e.g. rust, python)
+        #[arg(short, long)]
+        language: Option<String>,
+
+        /// Skip definitions in test files (`*_test.*`, `tests/`, `spec/`)
+        #[arg(long)]
+        exclude_tests: bool,
+
+        /// Report definitions with fewer than N callers; 2 also lists
+        /// functions called from a single place (must be at least 1)
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 1,
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        min_callers: u64,
+
+        /// Output format: text or json
+        #[arg(short = 'F', long, value_enum, default_value = "text")]
+        format: OutputFormatTextJson,
+    },
+
     /// LLM-driven explanation of a symbol's call flow, data flow, and business purpose
     Explain {
         /// Symbol name or regex pattern (see --regex)
@@ -5,9 +5,15 @@ use duckdb::{params, Connection};
 use tokio::sync::Mutex;
 use tracing::debug;
 
-use crate::application::{CallGraphQuery, CallGraphRepository, CallGraphStats};
+use crate::application::{CallGraphQuery, CallGraphRepository, CallGraphStats, SymbolFanCount};
 use crate::domain::{DomainError, Language, ReferenceKind, SymbolReference};
 
+/// Lower-cased reference paths matching this are test files, for
+/// [`CallGraphQuery::exclude_tests`]: the same conventions as
+/// [`crate::application::is_test_file`] (test directories, `.test.`/`.spec.`
+/// components, `test_` and `_test` stems), as a DuckDB regex.
+const TEST_PATH_REGEX: &str = r"(^|/)(tests?|specs?|__tests?__|testdata)/|(^|/)[^/]+\.(test|spec)\.[^/]+$|(^|/)test_[^/]*$|(^|/)[^/.]*_test(\.[^/]*)?$";
+
 pub struct DuckdbCallGraphRepository {
     conn: Arc<Mutex<Connection>>,
 }
@@ -128,9 +134,78 @@ impl DuckdbCallGraphRepository {
         if query.reference_kind.is_some() {
             conditions.push("reference_kind = ?".to_string());
         }
+        // Kind names and the test pattern are fixed strings, so they are
+        // inlined rather than bound; callers' placeholder order is unchanged.
+        if !query.kinds.is_empty() {
+            let kinds = query
+                .kinds
+                .iter()
+                .map(|kind| format!("'{}'", kind.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            conditions.push(format!("reference_kind IN ({})", kinds));
+        }
+        if query.exclude_tests {
+            conditions.push(format!(
+                "NOT regexp_matches(lower(reference_file_path), '{}')",
+                TEST_PATH_REGEX
+            ));
+        }
 
         conditions.join(" AND ")
     }
+
+    /// Symbols of `column` (`callee_symbol` or `caller_symbol`) with the most
+    /// references matching `query`, for `top_by_fanin` / `top_by_fanout`.
+    async fn top_by_count(
+        &self,
+        column: &str,
+        query: &CallGraphQuery,
+        limit: u32,
+        min_count: u64,
+    ) -> Result<Vec<SymbolFanCount>, DomainError> {
+        let conn = self.conn.lock().await;
+
+        let where_clause = Self::build_where_clause(query, &format!("{column} IS NOT NULL"));
+        let sql = format!(
+            "SELECT {column}, COUNT(*) AS ref_count FROM symbol_references \
+             WHERE {where_clause} \
+             GROUP BY {column} HAVING COUNT(*) >= ? \
+             ORDER BY ref_count DESC, {column} LIMIT {limit}"
+        );
+
+        let mut params_vec: Vec<Box<dyn duckdb::ToSql>> = Vec::new();
+        if let Some(ref repo_id) = query.repository_id {
+            params_vec.push(Box::new(repo_id.clone()));
+        }
+        if let Some(ref lang) = query.language {
+            params_vec.push(Box::new(lang.clone()));
+        }
+        if let Some(ref kind) = query.reference_kind {
+            params_vec.push(Box::new(kind.clone()));
+        }
+        params_vec.push(Box::new(min_count as i64));
+        let params_refs: Vec<&dyn duckdb::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DomainError::storage(format!("Failed to prepare statement: {}", e)))?;
+        let rows = stmt
+            .query_map(params_refs.as_slice(), |row| {
+                Ok(SymbolFanCount {
+                    symbol: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as u64,
+                })
+            })
+            .map_err(|e| DomainError::storage(format!("Failed to rank symbols: {}", e)))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results
+                .push(row.map_err(|e| DomainError::storage(format!("Failed to read row: {}", e)))?);
+        }
+        Ok(results)
+    }
 }
 
 #[async_trait]
@@ -269,6 +344,46 @@ impl CallGraphRepository for DuckdbCallGraphRepository {
         Ok(results)
     }
 
+    async fn count_callers(
+        &self,
+        callee_symbol: &str,
+        query: &CallGraphQuery,
+    ) -> Result<u64, DomainError> {
+        let conn = self.conn.lock().await;
+
+        // Same deduplicating UNION as `find_callers`, counted.
+        let callee_where = Self::build_where_clause(query, "callee_symbol = ?");
+        let alias_where = Self::build_where_clause(query, "import_alias = ?");
+        let sql = format!(
+            "SELECT COUNT(*) FROM ( \
+             SELECT id FROM symbol_references WHERE {cw} \
+             UNION \
+             SELECT id FROM symbol_references WHERE {aw})",
+            cw = callee_where,
+            aw = alias_where,
+        );
+
+        let mut params_vec: Vec<Box<dyn duckdb::ToSql>> = Vec::new();
+        for _ in 0..2 {
+            params_vec.push(Box::new(callee_symbol.to_string()));
+            if let Some(ref repo_id) = query.repository_id {
+                params_vec.push(Box::new(repo_id.clone()));
+            }
+            if let Some(ref lang) = query.language {
+                params_vec.push(Box::new(lang.clone()));
+            }
+            if let Some(ref kind) = query.reference_kind {
+                params_vec.push(Box::new(kind.clone()));
+            }
+        }
+        let params_refs: Vec<&dyn duckdb::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+
+        let count: i64 = conn
+            .query_row(&sql, params_refs.as_slice(), |row| row.get(0))
+            .map_err(|e| DomainError::storage(format!("Failed to count callers: {}", e)))?;
+        Ok(count as u64)
+    }
+
     async fn find_callees(
         &self,
         caller_symbol: &str,
@@ -448,6 +563,32 @@ impl CallGraphRepository for DuckdbCallGraphRepository {
         Ok(results)
     }
 
+    async fn find_all(&self) -> Result<Vec<SymbolReference>, DomainError> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare(
+                r#"SELECT id, caller_symbol, callee_symbol, caller_file_path,
+                          reference_file_path, reference_line, reference_column,
+                          reference_kind, language, repository_id,
+                          caller_node_type, enclosing_scope, import_alias, callee_package
+                   FROM symbol_references
+                   ORDER BY repository_id, reference_file_path, reference_line"#,
+            )
+            .map_err(|e| DomainError::storage(format!("Failed to prepare statement: {}", e)))?;
+
+        let rows = stmt
+            .query_map([], Self::row_to_symbol_reference)
+            .map_err(|e| DomainError::storage(format!("Failed to query references: {}", e)))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results
+                .push(row.map_err(|e| DomainError::storage(format!("Failed to read row: {}", e)))?);
+        }
+
+        Ok(results)
+    }
+
     async fn delete_by_file_path(
         &self,
         repository_id: &str,
@@ -477,6 +618,65 @@ impl CallGraphRepository for DuckdbCallGraphRepository {
         Ok(count as u64)
     }
 
+    async fn rename_symbol(
+        &self,
+        old: &str,
+        new: &str,
+        query: &CallGraphQuery,
+    ) -> Result<u64, DomainError> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn
+            .transaction()
+            .map_err(|e| DomainError::storage(format!("Failed to begin transaction: {}", e)))?;
+
+        // Filter values follow the symbol placeholders, in build_where_clause order.
+        let mut filters: Vec<String> = Vec::new();
+        if let Some(ref repo_id) = query.repository_id {
+            filters.push(repo_id.clone());
+        }
+        if let Some(ref lang) = query.language {
+            filters.push(lang.clone());
+        }
+        if let Some(ref kind) = query.reference_kind {
+            filters.push(kind.clone());
+        }
+        let with_filters = |leading: [&str; 2]| {
+            let mut values: Vec<String> = leading.iter().map(|v| v.to_string()).collect();
+            values.extend(filters.iter().cloned());
+            duckdb::params_from_iter(values)
+        };
+
+        // Counted up front: a self-call matches both updates but is one row.
+        let affected: i64 = tx
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM symbol_references WHERE {}",
+                    Self::build_where_clause(query, "(callee_symbol = ? OR caller_symbol = ?)")
+                ),
+                with_filters([old, old]),
+                |row| row.get(0),
+            )
+            .map_err(|e| DomainError::storage(format!("Failed to count references: {}", e)))?;
+
+        for column in ["callee_symbol", "caller_symbol"] {
+            let sql = format!(
+                "UPDATE symbol_references SET {column} = ? WHERE {}",
+                Self::build_where_clause(query, &format!("{column} = ?"))
+            );
+            tx.execute(&sql, with_filters([new, old]))
+                .map_err(|e| DomainError::storage(format!("Failed to rename symbol: {}", e)))?;
+        }
+
+        tx.commit()
+            .map_err(|e| DomainError::storage(format!("Failed to commit: {}", e)))?;
+
+        debug!(
+            "Renamed {} to {} in {} symbol references",
+            old, new, affected
+        );
+        Ok(affected as u64)
+    }
+
     async fn delete_by_repository(&self, repository_id: &str) -> Result<(), DomainError> {
         let conn = self.conn.lock().await;
         conn.execute(
@@ -571,6 +771,26 @@ impl CallGraphRepository for DuckdbCallGraphRepository {
         })
     }
 
+    async fn top_by_fanin(
+        &self,
+        query: &CallGraphQuery,
+        limit: u32,
+        min_count: u64,
+    ) -> Result<Vec<SymbolFanCount>, DomainError> {
+        self.top_by_count("callee_symbol", query, limit, min_count)
+            .await
+    }
+
+    async fn top_by_fanout(
+        &self,
+        query: &CallGraphQuery,
+        limit: u32,
+        min_count: u64,
+    ) -> Result<Vec<SymbolFanCount>, DomainError> {
+        self.top_by_count("caller_symbol", query, limit, min_count)
+            .await
+    }
+
     async fn find_cross_repo_references(
         &self,
         symbol_name: &str,
@@ -856,6 +1076,112 @@ mod tests {
         assert_eq!(callers[0].callee_symbol(), "other_function");
     }
 
+    #[tokio::test]
+    async fn test_find_callers_filters_kinds_and_test_files() {
+        let repo = create_test_repo().await;
+
+        let reference = |caller: &str, file: &str, kind| {
+            SymbolReference::new(
+                Some(caller.to_string()),
+                "target".to_string(),
+                file.to_string(),
+                file.to_string(),
+                1,
+                1,
+                kind,
+                Language::Rust,
+                "repo-123".to_string(),
+            )
+        };
+        repo.save_batch(&[
+            reference("run", "src/lib.rs", ReferenceKind::Call),
+            reference("uses_type", "src/types.rs", ReferenceKind::TypeReference),
+            reference("test_run", "tests/run.rs", ReferenceKind::Call),
+            reference("spec_run", "web/run.spec.ts", ReferenceKind::MethodCall),
+            reference("go_run", "pkg/run_test.go", ReferenceKind::Call),
+        ])
+        .await
+        .unwrap();
+
+        let callers = |query: CallGraphQuery| {
+            let repo = &repo;
+            async move {
+                let mut names: Vec<String> = repo
+                    .find_callers("target", &query)
+                    .await
+                    .unwrap()
+                    .iter()
+                    .filter_map(|r| r.caller_symbol().map(str::to_string))
+                    .collect();
+                names.sort();
+                names
+            }
+        };
+
+        let calls =
+            CallGraphQuery::new().with_kinds([ReferenceKind::Call, ReferenceKind::MethodCall]);
+        assert_eq!(
+            callers(calls.clone()).await,
+            ["go_run", "run", "spec_run", "test_run"]
+        );
+        assert_eq!(callers(calls.clone().with_exclude_tests()).await, ["run"]);
+        assert_eq!(
+            repo.count_callers("target", &calls.with_exclude_tests())
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            callers(CallGraphQuery::new().with_exclude_tests()).await,
+            ["run", "uses_type"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_top_by_fanin_and_fanout() {
+        let repo = create_test_repo().await;
+
+        let call = |caller: Option<&str>, callee: &str, line| {
+            SymbolReference::new(
+                caller.map(str::to_string),
+                callee.to_string(),
+                "src/lib.rs".to_string(),
+                "src/lib.rs".to_string(),
+                line,
+                1,
+                ReferenceKind::Call,
+                Language::Rust,
+                "repo-123".to_string(),
+            )
+        };
+        repo.save_batch(&[
+            call(Some("a"), "log", 1),
+            call(Some("b"), "log", 2),
+            call(None, "log", 3),
+            call(Some("a"), "parse", 4),
+            call(Some("a"), "render", 5),
+        ])
+        .await
+        .unwrap();
+
+        let query = CallGraphQuery::new().with_repository("repo-123");
+        let fan_in = repo.top_by_fanin(&query, 10, 1).await.unwrap();
+        let ranked: Vec<(&str, u64)> = fan_in
+            .iter()
+            .map(|f| (f.symbol.as_str(), f.count))
+            .collect();
+        assert_eq!(ranked, [("log", 3), ("parse", 1), ("render", 1)]);
+        assert_eq!(repo.top_by_fanin(&query, 10, 2).await.unwrap().len(), 1);
+        assert_eq!(repo.top_by_fanin(&query, 1, 1).await.unwrap().len(), 1);
+
+        let fan_out = repo.top_by_fanout(&query, 10, 1).await.unwrap();
+        let ranked: Vec<(&str, u64)> = fan_out
+            .iter()
+            .map(|f| (f.symbol.as_str(), f.count))
+            .collect();
+        assert_eq!(ranked, [("a", 3), ("b", 1)]);
+    }
+
     #[tokio::test]
     async fn test_find_callees() {
         let repo = create_test_repo().await;
@@ -893,6 +1219,59 @@ mod tests {
         assert_eq!(callees.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_rename_symbol_updates_callers_and_callees() {
+        let repo = create_test_repo().await;
+        let reference = |caller: &str, callee: &str, repository: &str| {
+            SymbolReference::new(
+                Some(caller.to_string()),
+                callee.to_string(),
+                "src/lib.rs".to_string(),
+                "src/lib.rs".to_string(),
+                1,
+                1,
+                ReferenceKind::Call,
+                Language::Rust,
+                repository.to_string(),
+            )
+        };
+        repo.save_batch(&[
+            reference("main", "old_name", "repo-123"),
+            reference("old_name", "helper", "repo-123"),
+            reference("old_name", "old_name", "repo-123"),
+            reference("main", "old_name", "repo-456"),
+        ])
+        .await
+        .unwrap();
+
+        let query = CallGraphQuery::new().with_repository("repo-123");
+        let renamed = repo
+            .rename_symbol("old_name", "new_name", &query)
+            .await
+            .unwrap();
+
+        assert_eq!(renamed, 3);
+        assert_eq!(
+            repo.find_callers("new_name", &query).await.unwrap().len(),
+            2
+        );
+        assert_eq!(
+            repo.find_callees("new_name", &query).await.unwrap().len(),
+            2
+        );
+        assert!(repo
+            .find_callers("old_name", &query)
+            .await
+            .unwrap()
+            .is_empty());
+        // Other repositories are outside the query and keep the old name.
+        let other = CallGraphQuery::new().with_repository("repo-456");
+        assert_eq!(
+            repo.find_callers("old_name", &other).await.unwrap().len(),
+            1
+        );
+    }
+
     #[tokio::test]
     async fn test_get_stats() {
         let repo = create_test_repo().await;
@@ -0,0 +1,173 @@
+//! Embedding cache stored in its own DuckDB file (`embedding_cache.duckdb`)
+//! under the data directory, apart from the code index: it outlives
+//! namespaces and repositories, and `cache clear` drops it without touching
+//! any index.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use duckdb::{params, Connection};
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::application::EmbeddingCache;
+use crate::domain::DomainError;
+
+/// File name of the embedding cache database inside the data directory.
+pub const EMBEDDING_CACHE_DB_FILE: &str = "embedding_cache.duckdb";
+
+/// Keys looked up per query, keeping the `IN (...)` list bounded.
+const LOOKUP_BATCH: usize = 512;
+
+pub struct DuckdbEmbeddingCache {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DuckdbEmbeddingCache {
+    /// Open (or create) the cache database at `db_path`.
+    pub fn new(db_path: &Path) -> Result<Self, DomainError> {
+        let conn = Connection::open(db_path).map_err(|e| {
+            DomainError::storage(format!("Failed to open embedding cache database: {e}"))
+        })?;
+        Self::initialize(conn)
+    }
+
+    /// In-memory database for tests.
+    pub fn in_memory() -> Result<Self, DomainError> {
+        let conn = Connection::open_in_memory().map_err(|e| {
+            DomainError::storage(format!("Failed to open in-memory embedding cache: {e}"))
+        })?;
+        Self::initialize(conn)
+    }
+
+    fn initialize(conn: Connection) -> Result<Self, DomainError> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS embedding_cache (
+                content_key TEXT NOT NULL,
+                model TEXT NOT NULL,
+                vector BLOB NOT NULL,
+                PRIMARY KEY (content_key, model)
+            );
+            "#,
+        )
+        .map_err(|e| {
+            DomainError::storage(format!("Failed to initialize embedding_cache schema: {e}"))
+        })?;
+        debug!("DuckDB embedding_cache table initialized");
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+}
+
+/// Vectors are stored as little-endian `f32` bytes, so they round-trip
+/// exactly.
+fn encode(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[async_trait]
+impl EmbeddingCache for DuckdbEmbeddingCache {
+    async fn get_many(
+        &self,
+        model: &str,
+        keys: &[&str],
+    ) -> Result<HashMap<String, Vec<f32>>, DomainError> {
+        let mut found = HashMap::new();
+        let conn = self.conn.lock().await;
+        for batch in keys.chunks(LOOKUP_BATCH) {
+            let placeholders = vec!["?"; batch.len()].join(",");
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT content_key, vector FROM embedding_cache \
+                     WHERE model = ? AND content_key IN ({placeholders})"
+                ))
+                .map_err(|e| DomainError::storage(format!("Failed to prepare statement: {e}")))?;
+            let values =
+                duckdb::params_from_iter(std::iter::once(model).chain(batch.iter().copied()));
+            let rows = stmt
+                .query_map(values, |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+                })
+                .map_err(|e| {
+                    DomainError::storage(format!("Failed to query embedding cache: {e}"))
+                })?;
+            for row in rows {
+                let (key, bytes) = row.map_err(|e| {
+                    DomainError::storage(format!("Failed to read embedding cache row: {e}"))
+                })?;
+                found.insert(key, decode(&bytes));
+            }
+        }
+        Ok(found)
+    }
+
+    async fn put_many(
+        &self,
+        model: &str,
+        entries: &[(String, Vec<f32>)],
+    ) -> Result<(), DomainError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self.conn.lock().await;
+        let tx = conn
+            .transaction()
+            .map_err(|e| DomainError::storage(format!("Failed to begin transaction: {e}")))?;
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT OR REPLACE INTO embedding_cache (content_key, model, vector) \
+                     VALUES (?, ?, ?)",
+                )
+                .map_err(|e| DomainError::storage(format!("Failed to prepare statement: {e}")))?;
+            for (key, vector) in entries {
+                stmt.execute(params![key, model, encode(vector)])
+                    .map_err(|e| DomainError::storage(format!("Failed to cache embedding: {e}")))?;
+            }
+        }
+        tx.commit()
+            .map_err(|e| DomainError::storage(format!("Failed to commit transaction: {e}")))?;
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<u64, DomainError> {
+        let conn = self.conn.lock().await;
+        let removed = conn
+            .execute("DELETE FROM embedding_cache", [])
+            .map_err(|e| DomainError::storage(format!("Failed to clear embedding cache: {e}")))?;
+        Ok(removed as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn vectors_are_cached_per_model() {
+        let cache = DuckdbEmbeddingCache::in_memory().unwrap();
+        cache
+            .put_many("m1", &[("a".to_string(), vec![0.5, -1.25])])
+            .await
+            .unwrap();
+
+        let hits = cache.get_many("m1", &["a", "b"]).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits["a"], vec![0.5, -1.25]);
+        assert!(cache.get_many("m2", &["a"]).await.unwrap().is_empty());
+
+        assert_eq!(cache.clear().await.unwrap(), 1);
+        assert!(cache.get_many("m1", &["a"]).await.unwrap().is_empty());
+    }
+}
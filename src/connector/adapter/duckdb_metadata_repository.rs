@@ -8,7 +8,7 @@ use tokio::sync::Mutex;
 use tracing::debug;
 
 use crate::application::MetadataRepository;
-use crate::domain::{DomainError, LanguageStats, Repository, VectorStore};
+use crate::domain::{DomainError, IndexFilters, LanguageStats, Repository, VectorStore};
 
 pub struct DuckdbMetadataRepository {
     conn: Arc<Mutex<Connection>>,
@@ -27,7 +27,8 @@ impl DuckdbMetadataRepository {
 
     /// Opens the database in read-only mode.
     ///
-    /// Used for the ChromaDB backend path where DuckDB only stores metadata.
+    /// Used by read-only commands when vectors live in another store (e.g.
+    /// Postgres) and DuckDB only holds metadata.
     /// Multiple read-only connections can coexist, enabling concurrent searches.
     /// Schema initialization is skipped (tables must already exist).
     pub fn new_read_only(db_path: &Path) -> Result<Self, DomainError> {
@@ -62,6 +63,53 @@ impl DuckdbMetadataRepository {
         Arc::clone(&self.conn)
     }
 
+    /// Names of the namespaces in the database: every schema holding a
+    /// `chunks` table, named as recorded in `namespace_config` (the schema
+    /// name itself when no config row exists), sorted.
+    pub async fn list_namespaces(&self) -> Result<Vec<String>, DomainError> {
+        let conn = self.conn.lock().await;
+        let storage =
+            |e: duckdb::Error| DomainError::storage(format!("Failed to list namespaces: {}", e));
+
+        let schemas: Vec<String> = {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT table_schema FROM information_schema.tables \
+                     WHERE table_name = 'chunks' AND table_schema <> 'main'",
+                )
+                .map_err(storage)?;
+            let rows = stmt.query_map([], |row| row.get(0)).map_err(storage)?;
+            rows.collect::<Result<_, _>>().map_err(storage)?
+        };
+
+        // A metadata-only database (e.g. vectors in Postgres) has no config table.
+        let has_config: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM information_schema.tables \
+                 WHERE table_schema = 'main' AND table_name = 'namespace_config'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(storage)?;
+        let mut names_by_schema: HashMap<String, String> = HashMap::new();
+        if has_config {
+            let mut stmt = conn
+                .prepare("SELECT schema_token, namespace FROM namespace_config")
+                .map_err(storage)?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(storage)?;
+            names_by_schema = rows.collect::<Result<_, _>>().map_err(storage)?;
+        }
+
+        let mut namespaces: Vec<String> = schemas
+            .into_iter()
+            .map(|schema| names_by_schema.remove(&schema).unwrap_or(schema))
+            .collect();
+        namespaces.sort();
+        Ok(namespaces)
+    }
+
     #[allow(dead_code)]
     pub fn in_memory() -> Result<Self, DomainError> {
         let conn = Connection::open_in_memory().map_err(|e| {
@@ -88,8 +136,14 @@ impl DuckdbMetadataRepository {
                 store TEXT DEFAULT 'duckdb',
                 namespace TEXT,
                 git_remote TEXT,
-                languages TEXT
+                languages TEXT,
+                index_filters TEXT,
+                indexed_commit TEXT,
+                roots TEXT
             );
+            ALTER TABLE repositories ADD COLUMN IF NOT EXISTS index_filters TEXT;
+            ALTER TABLE repositories ADD COLUMN IF NOT EXISTS indexed_commit TEXT;
+            ALTER TABLE repositories ADD COLUMN IF NOT EXISTS roots TEXT;
             "#,
         )
         .map_err(|e| DomainError::storage(format!("Failed to initialize schema: {}", e)))?;
@@ -110,6 +164,33 @@ impl DuckdbMetadataRepository {
         json.and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default()
     }
+
+    fn serialize_index_filters(filters: &IndexFilters) -> Option<String> {
+        if filters.is_empty() {
+            None
+        } else {
+            serde_json::to_string(filters).ok()
+        }
+    }
+
+    fn deserialize_index_filters(json: Option<String>) -> IndexFilters {
+        json.and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Roots are stored as a JSON list; single-root repositories store NULL.
+    fn serialize_roots(roots: &[String]) -> Option<String> {
+        if roots.is_empty() {
+            None
+        } else {
+            serde_json::to_string(roots).ok()
+        }
+    }
+
+    fn deserialize_roots(json: Option<String>) -> Vec<String> {
+        json.and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[async_trait]
@@ -117,11 +198,13 @@ impl MetadataRepository for DuckdbMetadataRepository {
     async fn save(&self, repository: &Repository) -> Result<(), DomainError> {
         let conn = self.conn.lock().await;
         let languages_json = Self::serialize_languages(repository.languages());
+        let index_filters_json = Self::serialize_index_filters(repository.index_filters());
+        let roots_json = Self::serialize_roots(repository.roots());
 
         conn.execute(
             r#"
-            INSERT INTO repositories (id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            INSERT INTO repositories (id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages, index_filters, indexed_commit, roots)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
             ON CONFLICT (id) DO UPDATE SET
                 name = excluded.name,
                 path = excluded.path,
@@ -132,7 +215,10 @@ impl MetadataRepository for DuckdbMetadataRepository {
                 store = excluded.store,
                 namespace = excluded.namespace,
                 git_remote = excluded.git_remote,
-                languages = excluded.languages
+                languages = excluded.languages,
+                index_filters = excluded.index_filters,
+                indexed_commit = excluded.indexed_commit,
+                roots = excluded.roots
             "#,
             params![
                 repository.id(),
@@ -146,6 +232,9 @@ impl MetadataRepository for DuckdbMetadataRepository {
                 repository.namespace(),
                 repository.git_remote(),
                 languages_json,
+                index_filters_json,
+                repository.indexed_commit(),
+                roots_json,
             ],
         )
         .map_err(|e| DomainError::storage(format!("Failed to save repository: {}", e)))?;
@@ -157,7 +246,7 @@ impl MetadataRepository for DuckdbMetadataRepository {
         let conn = self.conn.lock().await;
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages FROM repositories WHERE id = ?1",
+                "SELECT id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages, index_filters, indexed_commit, roots FROM repositories WHERE id = ?1",
             )
             .map_err(|e| DomainError::storage(format!("Failed to prepare statement: {}", e)))?;
 
@@ -168,7 +257,8 @@ impl MetadataRepository for DuckdbMetadataRepository {
             let namespace: Option<String> = row.get(8)?;
             let git_remote: Option<String> = row.get(9)?;
             let languages_json: Option<String> = row.get(10)?;
-            Ok(Repository::reconstitute(
+            let index_filters_json: Option<String> = row.get(11)?;
+            let mut repository = Repository::reconstitute(
                 row.get(0)?,
                 row.get(1)?,
                 row.get(2)?,
@@ -180,7 +270,11 @@ impl MetadataRepository for DuckdbMetadataRepository {
                 namespace,
                 git_remote,
                 Self::deserialize_languages(languages_json),
-            ))
+                Self::deserialize_index_filters(index_filters_json),
+            );
+            repository.set_indexed_commit(row.get(12)?);
+            repository.set_roots(Self::deserialize_roots(row.get(13)?));
+            Ok(repository)
         }) {
             Ok(repo) => Ok(Some(repo)),
             Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
@@ -195,7 +289,7 @@ impl MetadataRepository for DuckdbMetadataRepository {
         let conn = self.conn.lock().await;
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages FROM repositories WHERE path = ?1",
+                "SELECT id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages, index_filters, indexed_commit, roots FROM repositories WHERE path = ?1",
             )
             .map_err(|e| DomainError::storage(format!("Failed to prepare statement: {}", e)))?;
 
@@ -206,7 +300,8 @@ impl MetadataRepository for DuckdbMetadataRepository {
             let namespace: Option<String> = row.get(8)?;
             let git_remote: Option<String> = row.get(9)?;
             let languages_json: Option<String> = row.get(10)?;
-            Ok(Repository::reconstitute(
+            let index_filters_json: Option<String> = row.get(11)?;
+            let mut repository = Repository::reconstitute(
                 row.get(0)?,
                 row.get(1)?,
                 row.get(2)?,
@@ -218,7 +313,11 @@ impl MetadataRepository for DuckdbMetadataRepository {
                 namespace,
                 git_remote,
                 Self::deserialize_languages(languages_json),
-            ))
+                Self::deserialize_index_filters(index_filters_json),
+            );
+            repository.set_indexed_commit(row.get(12)?);
+            repository.set_roots(Self::deserialize_roots(row.get(13)?));
+            Ok(repository)
         }) {
             Ok(repo) => Ok(Some(repo)),
             Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
@@ -233,7 +332,7 @@ impl MetadataRepository for DuckdbMetadataRepository {
         let conn = self.conn.lock().await;
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages FROM repositories ORDER BY name",
+                "SELECT id, name, path, created_at, updated_at, chunk_count, file_count, store, namespace, git_remote, languages, index_filters, indexed_commit, roots FROM repositories ORDER BY name",
             )
             .map_err(|e| DomainError::storage(format!("Failed to prepare statement: {}", e)))?;
 
@@ -245,7 +344,8 @@ impl MetadataRepository for DuckdbMetadataRepository {
                 let namespace: Option<String> = row.get(8)?;
                 let git_remote: Option<String> = row.get(9)?;
                 let languages_json: Option<String> = row.get(10)?;
-                Ok(Repository::reconstitute(
+                let index_filters_json: Option<String> = row.get(11)?;
+                let mut repository = Repository::reconstitute(
                     row.get(0)?,
                     row.get(1)?,
                     row.get(2)?,
@@ -257,7 +357,11 @@ impl MetadataRepository for DuckdbMetadataRepository {
                     namespace,
                     git_remote,
                     Self::deserialize_languages(languages_json),
-                ))
+                    Self::deserialize_index_filters(index_filters_json),
+                );
+                repository.set_indexed_commit(row.get(12)?);
+                repository.set_roots(Self::deserialize_roots(row.get(13)?));
+                Ok(repository)
             })
             .map_err(|e| DomainError::storage(format!("Failed to query repositories: {}", e)))?;
 
@@ -342,4 +446,55 @@ impl MetadataRepository for DuckdbMetadataRepository {
 
         Ok(())
     }
+
+    async fn update_indexed_commit(
+        &self,
+        id: &str,
+        indexed_commit: Option<&str>,
+    ) -> Result<(), DomainError> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "UPDATE repositories SET indexed_commit = ?1 WHERE id = ?2",
+            params![indexed_commit, id],
+        )
+        .map_err(|e| {
+            DomainError::storage(format!("Failed to update repository indexed commit: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    async fn update_roots(&self, id: &str, roots: &[String]) -> Result<(), DomainError> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "UPDATE repositories SET roots = ?1 WHERE id = ?2",
+            params![Self::serialize_roots(roots), id],
+        )
+        .map_err(|e| DomainError::storage(format!("Failed to update roots: {}", e)))?;
+        Ok(())
+    }
+
+    async fn update_index_filters(
+        &self,
+        id: &str,
+        index_filters: &IndexFilters,
+    ) -> Result<(), DomainError> {
+        let conn = self.conn.lock().await;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        let index_filters_json = Self::serialize_index_filters(index_filters);
+
+        conn.execute(
+            "UPDATE repositories SET index_filters = ?1, updated_at = ?2 WHERE id = ?3",
+            params![index_filters_json, now, id],
+        )
+        .map_err(|e| {
+            DomainError::storage(format!("Failed to update repository index filters: {}", e))
+        })?;
+
+        Ok(())
+    }
 }
@@ -2,19 +2,19 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use duckdb::{params, params_from_iter, AccessMode, Config, Connection, Row};
+use serde::Serialize;
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 
-use crate::application::{rrf_fuse, VectorRepository};
-use crate::domain::{CodeChunk, DomainError, Embedding, SearchQuery, SearchResult};
-
-/// Maximum number of BM25 candidates fetched per search leg.
-/// BM25 is exact keyword matching so a small pool is sufficient;
-/// the semantic leg handles broader recall.
-const BM25_FETCH_LIMIT: usize = 10;
+use crate::application::{
+    rrf_fuse_hybrid, FileEntry, IndexState, RepositoryChunkStats, StorageIntegrity,
+    VectorRepository,
+};
+use crate::domain::{CodeChunk, DomainError, Embedding, Language, SearchQuery, SearchResult};
 
 /// Over-fetch multiplier applied to the HNSW candidate pass when the query
 /// carries column filters (language / node_type / repository).  The index scan
@@ -31,6 +31,10 @@ const HNSW_FILTER_OVERFETCH_FLOOR: usize = 64;
 /// what used to be one prepare + execute round-trip per embedding.
 const EMBEDDING_INSERT_BATCH: usize = 128;
 
+/// Maximum number of ids bound into one `IN (…)` list by `bulk_delete`;
+/// larger batches are split over several statements in the same transaction.
+const DELETE_ID_BATCH: usize = 999;
+
 use super::NO_EMBEDDINGS_MODEL;
 
 /// Embedding configuration that must remain consistent across all operations on
@@ -49,6 +53,76 @@ pub struct NamespaceEmbeddingConfig {
     pub dimensions: usize,
 }
 
+/// One namespace stored in a DuckDB file, as listed by
+/// [`DuckdbVectorRepository::list_namespaces`].
+#[derive(Debug, Clone, Serialize)]
+pub struct NamespaceSummary {
+    pub namespace: String,
+    /// `None` for a schema whose `namespace_config` row is missing.
+    pub embedding_model: Option<String>,
+    pub dimensions: Option<usize>,
+    pub chunk_count: u64,
+    pub embedding_count: u64,
+    /// Rough footprint: chunk text plus one `f32` per embedding dimension.
+    /// Ignores indexes and DuckDB's compression.
+    pub estimated_bytes: u64,
+    /// Names of the repositories indexed into the namespace.
+    pub repositories: Vec<String>,
+}
+
+/// Translate a file-path glob into a SQL `LIKE` pattern (used with
+/// `ESCAPE '\'`): `*` becomes `%`, `?` becomes `_`, and literal `%`, `_`,
+/// and `\` are escaped.  `**` collapses naturally since `%` already spans `/`.
+pub(super) fn glob_to_like(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        match c {
+            '*' => out.push('%'),
+            '?' => out.push('_'),
+            '%' | '_' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// SQL condition on `column` for the query's path globs: any of the plain
+/// globs and none of the `!` ones.  `None` when the query has no globs.
+pub(super) fn path_glob_clause(column: &str, query: &SearchQuery) -> Option<String> {
+    let like = |glob: &str| {
+        format!(
+            "{column} LIKE '{}' ESCAPE '\\'",
+            glob_to_like(glob).replace('\'', "''")
+        )
+    };
+    let (included, excluded) = query.split_path_globs();
+    let mut clauses = Vec::new();
+    if !included.is_empty() {
+        let any: Vec<String> = included.into_iter().map(like).collect();
+        clauses.push(format!("({})", any.join(" OR ")));
+    }
+    clauses.extend(
+        excluded
+            .into_iter()
+            .map(|glob| format!("NOT {}", like(glob))),
+    );
+    (!clauses.is_empty()).then(|| clauses.join(" AND "))
+}
+
+/// `REGEXP_MATCHES` condition on `column` for the query's symbol regex.
+/// `None` when the query has none.
+fn symbol_pattern_clause(column: &str, query: &SearchQuery) -> Option<String> {
+    query.symbol_pattern().map(|pattern| {
+        format!(
+            "REGEXP_MATCHES({column}, '{}')",
+            pattern.replace('\'', "''")
+        )
+    })
+}
+
 pub struct DuckdbVectorRepository {
     conn: Arc<Mutex<Connection>>,
     /// User-facing namespace name (e.g. `homeframework`). Used as the
@@ -288,8 +362,14 @@ impl DuckdbVectorRepository {
                 node_type TEXT NOT NULL,
                 symbol_name TEXT,
                 parent_symbol TEXT,
-                repository_id TEXT NOT NULL
+                repository_id TEXT NOT NULL,
+                part INTEGER,
+                is_async BOOLEAN NOT NULL DEFAULT false,
+                doc_comment TEXT
             );
+            ALTER TABLE "{schema}".chunks ADD COLUMN IF NOT EXISTS part INTEGER;
+            ALTER TABLE "{schema}".chunks ADD COLUMN IF NOT EXISTS is_async BOOLEAN DEFAULT false;
+            ALTER TABLE "{schema}".chunks ADD COLUMN IF NOT EXISTS doc_comment TEXT;
             CREATE TABLE IF NOT EXISTS "{schema}".embeddings (
                 chunk_id TEXT PRIMARY KEY,
                 vector FLOAT[{dims}] NOT NULL,
@@ -342,8 +422,14 @@ impl DuckdbVectorRepository {
                 store TEXT DEFAULT 'duckdb',
                 namespace TEXT,
                 git_remote TEXT,
-                languages TEXT
+                languages TEXT,
+                index_filters TEXT,
+                indexed_commit TEXT,
+                roots TEXT
             );
+            ALTER TABLE repositories ADD COLUMN IF NOT EXISTS index_filters TEXT;
+            ALTER TABLE repositories ADD COLUMN IF NOT EXISTS indexed_commit TEXT;
+            ALTER TABLE repositories ADD COLUMN IF NOT EXISTS roots TEXT;
             CREATE TABLE IF NOT EXISTS namespace_config (
                 namespace TEXT PRIMARY KEY,
                 schema_token TEXT NOT NULL,
@@ -431,6 +517,138 @@ impl DuckdbVectorRepository {
         Ok(())
     }
 
+    /// Every namespace in the database at `path`: each schema holding a
+    /// `chunks` table, with its stored embedding configuration, row counts and
+    /// repositories. Opens the file read-only.
+    pub fn list_namespaces(path: &Path) -> Result<Vec<NamespaceSummary>, DomainError> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let config = Config::default()
+            .access_mode(AccessMode::ReadOnly)
+            .map_err(|e| {
+                DomainError::storage(format!("Failed to configure read-only access: {}", e))
+            })?;
+        let conn = Connection::open_with_flags(path, config).map_err(|e| {
+            DomainError::storage(format!("Failed to open DuckDB (read-only): {}", e))
+        })?;
+        // The embeddings table carries an HNSW index, readable only with vss.
+        conn.execute_batch("LOAD vss; SET hnsw_enable_experimental_persistence = true;")
+            .map_err(|e| DomainError::storage(format!("Failed to load extensions: {}", e)))?;
+
+        let storage =
+            |e: duckdb::Error| DomainError::storage(format!("Failed to list namespaces: {}", e));
+        let schemas: Vec<(String, Option<String>, Option<String>, Option<i64>)> = {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT s.schema_name, c.namespace, c.embedding_model, c.dimensions \
+                     FROM information_schema.schemata s \
+                     JOIN information_schema.tables t \
+                       ON t.table_schema = s.schema_name AND t.table_name = 'chunks' \
+                     LEFT JOIN namespace_config c ON c.schema_token = s.schema_name \
+                     ORDER BY COALESCE(c.namespace, s.schema_name)",
+                )
+                .map_err(storage)?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })
+                .map_err(storage)?;
+            rows.collect::<Result<_, _>>().map_err(storage)?
+        };
+
+        let mut summaries = Vec::with_capacity(schemas.len());
+        for (schema, namespace, embedding_model, dimensions) in schemas {
+            let (chunk_count, text_bytes): (i64, i64) = conn
+                .query_row(
+                    &format!(
+                        r#"SELECT COUNT(*), COALESCE(SUM(octet_length(content)), 0)
+                           FROM "{schema}".chunks"#
+                    ),
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .map_err(storage)?;
+            let embedding_count: i64 = conn
+                .query_row(
+                    &format!(r#"SELECT COUNT(*) FROM "{schema}".embeddings"#),
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap_or(0);
+            let namespace = namespace.unwrap_or(schema);
+            let repositories = {
+                let mut stmt = conn
+                    .prepare("SELECT name FROM repositories WHERE namespace = ? ORDER BY name")
+                    .map_err(storage)?;
+                let rows = stmt
+                    .query_map(params![namespace], |row| row.get::<_, String>(0))
+                    .map_err(storage)?;
+                rows.collect::<Result<Vec<_>, _>>().map_err(storage)?
+            };
+            let dimensions = dimensions.map(|d| d as usize);
+            let vector_bytes = embedding_count as u64 * dimensions.unwrap_or(0) as u64 * 4;
+            summaries.push(NamespaceSummary {
+                namespace,
+                embedding_model,
+                dimensions,
+                chunk_count: chunk_count as u64,
+                embedding_count: embedding_count as u64,
+                estimated_bytes: text_bytes as u64 + vector_bytes,
+                repositories,
+            });
+        }
+        Ok(summaries)
+    }
+
+    /// Drop `namespace`: its schema (chunks and embeddings), its full-text
+    /// index schema and its `namespace_config` row. Repository rows and the
+    /// repository-scoped global tables (call graph, file hashes, ...) belong
+    /// to their own adapters and must be cleared by the caller.
+    pub async fn drop_namespace(
+        conn: Arc<Mutex<Connection>>,
+        namespace: &str,
+    ) -> Result<(), DomainError> {
+        let mut conn = conn.lock().await;
+        // The HNSW index and FTS macros can only be dropped with their
+        // extensions loaded.
+        Self::init_extensions_and_global_tables(&conn)?;
+
+        let schema: String = conn
+            .query_row(
+                "SELECT schema_token FROM namespace_config WHERE namespace = ?",
+                params![namespace],
+                |row| row.get(0),
+            )
+            .map_err(|_| DomainError::not_found(format!("Namespace '{}' not found", namespace)))?;
+
+        let drop_failed = |e: duckdb::Error| {
+            DomainError::storage(format!("Failed to drop namespace '{}': {}", namespace, e))
+        };
+        let tx = conn
+            .transaction()
+            .map_err(|e| DomainError::storage(format!("Failed to begin transaction: {}", e)))?;
+        tx.execute_batch(&format!(
+            r#"
+            DROP SCHEMA IF EXISTS "{fts}" CASCADE;
+            DROP SCHEMA IF EXISTS "{schema}" CASCADE;
+            "#,
+            fts = Self::fts_schema_name(&schema),
+            schema = schema
+        ))
+        .map_err(drop_failed)?;
+        tx.execute(
+            "DELETE FROM namespace_config WHERE namespace = ?",
+            params![namespace],
+        )
+        .map_err(drop_failed)?;
+        tx.commit()
+            .map_err(|e| DomainError::storage(format!("Failed to commit: {}", e)))?;
+
+        debug!("Dropped namespace {namespace} (schema={schema})");
+        Ok(())
+    }
+
     /// Read the stored `namespace_config` row, validate it against `cfg`, and
     /// return the namespace's schema token and effective dimensions.
     ///
@@ -517,7 +735,7 @@ impl DuckdbVectorRepository {
                         stored_target,
                         cfg.embedding_model,
                         cfg.embedding_target,
-                        cfg.embedding_model,
+                        stored_model,
                     )));
                 }
                 debug!(
@@ -593,6 +811,145 @@ impl DuckdbVectorRepository {
         }
     }
 
+    /// Whether the FTS index covers exactly the current chunks.  The index is
+    /// a snapshot taken by `create_fts_index`, so a run interrupted between
+    /// writing chunks and the rebuild in [`flush`](VectorRepository::flush)
+    /// leaves it describing an older set of rows.
+    fn fts_index_state(conn: &Connection, schema: &str) -> Result<IndexState, DomainError> {
+        if !Self::fts_index_exists(conn, schema) {
+            return Ok(IndexState::Missing);
+        }
+        let fts = Self::fts_schema_name(schema);
+        let in_step: bool = conn
+            .query_row(
+                &format!(
+                    "SELECT (SELECT COUNT(*) FROM \"{fts}\".docs) = \
+                            (SELECT COUNT(*) FROM \"{schema}\".chunks) \
+                        AND NOT EXISTS ( \
+                            SELECT 1 FROM \"{schema}\".chunks c \
+                            WHERE NOT EXISTS ( \
+                                SELECT 1 FROM \"{fts}\".docs d WHERE d.name = c.id))"
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| DomainError::storage(format!("Failed to check FTS index: {e}")))?;
+        Ok(if in_step {
+            IndexState::Ok
+        } else {
+            IndexState::Stale
+        })
+    }
+
+    /// The semantic and BM25 legs of [`VectorRepository::search`], fused
+    /// when both run.
+    async fn search_legs(
+        &self,
+        query_embedding: Option<&[f32]>,
+        query: &SearchQuery,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        if let Some(embedding) = query_embedding {
+            if embedding.len() != self.dimensions {
+                return Err(DomainError::invalid_input(format!(
+                    "Query embedding has {} dimensions but namespace '{}' is locked to \
+                     {}-dimensional vectors from the model it was indexed with",
+                    embedding.len(),
+                    self.namespace,
+                    self.dimensions
+                )));
+            }
+        }
+
+        let conn = self.conn.lock().await;
+
+        // `None` requests a text-only search (no embeddings indexed); the
+        // semantic leg is skipped entirely.
+        let semantic = match query_embedding {
+            None => Vec::new(),
+            Some(embedding) => {
+                let array_lit = self.vector_to_array_literal(embedding)?;
+                Self::run_semantic(&conn, &self.schema, &array_lit, query, query.limit())?
+                    .into_iter()
+                    .map(SearchResult::with_semantic_leg)
+                    .collect()
+            }
+        };
+
+        if !query.is_text_search() && query_embedding.is_some() {
+            return Ok(semantic);
+        }
+
+        // Rebuild the FTS index if the chunk data has changed since last search.
+        // This is a lazy rebuild strategy: we pay the rebuild cost once per "dirty"
+        // window (i.e., after any sequence of inserts/deletes) rather than after
+        // every individual write.
+        if self.fts_dirty.load(Ordering::Acquire) {
+            if self.read_only {
+                // DDL is forbidden in read-only mode; the FTS index was not built
+                // during a prior write session. Degrade silently to semantic-only.
+                debug!(
+                    "FTS index unavailable in read-only mode for namespace '{}'; \
+                     run 'codesearch index' to build it. Falling back to semantic-only.",
+                    self.namespace
+                );
+                return Ok(semantic);
+            }
+            match Self::rebuild_fts_index(&conn, &self.schema) {
+                Ok(()) => {
+                    self.fts_dirty.store(false, Ordering::Release);
+                    debug!("FTS index rebuilt for namespace '{}'", self.namespace);
+                }
+                Err(e) => {
+                    // FTS extension unavailable or another unexpected failure.
+                    warn!(
+                        "Failed to rebuild FTS index (falling back to semantic-only): {}",
+                        e
+                    );
+                    return Ok(semantic);
+                }
+            }
+        }
+
+        // With no semantic candidates the BM25 leg is the only source of
+        // results, so it must honour the full requested limit even when
+        // `--text-candidates` asks for fewer.
+        let text_fetch_limit = if semantic.is_empty() {
+            query.limit().max(query.text_candidates())
+        } else {
+            query.text_candidates()
+        };
+        let text: Vec<SearchResult> =
+            match Self::run_text(&conn, &self.schema, query, text_fetch_limit) {
+                Ok(results) => results
+                    .into_iter()
+                    .map(|r| r.with_text_leg(query.query()))
+                    .collect(),
+                Err(e) => {
+                    // If BM25 query fails (e.g. FTS schema missing in read-only DB),
+                    // degrade gracefully to semantic-only results.
+                    warn!(
+                        "BM25 text search failed (falling back to semantic-only): {}",
+                        e
+                    );
+                    return Ok(semantic);
+                }
+            };
+
+        let semantic_len = semantic.len();
+        let text_len = text.len();
+        let mut fused = rrf_fuse_hybrid(semantic, text, query);
+        info!(
+            "Hybrid search: {} semantic + {} BM25 candidates → {} after fusion",
+            semantic_len,
+            text_len,
+            fused.len()
+        );
+        if let Some(min) = query.min_score() {
+            fused.retain(|r| r.score() >= min);
+        }
+        Ok(fused)
+    }
+
     /// Rebuilds the FTS index from scratch for the given schema token.
     ///
     /// Indexes the real `"<schema>".chunks` table directly. The schema is a
@@ -617,9 +974,11 @@ impl DuckdbVectorRepository {
     fn vector_to_array_literal(&self, vector: &[f32]) -> Result<String, DomainError> {
         if vector.len() != self.dimensions {
             return Err(DomainError::invalid_input(format!(
-                "Expected embedding dimension {}, got {}",
-                self.dimensions,
-                vector.len()
+                "Embedding has {} dimensions but namespace '{}' is locked to \
+                 {}-dimensional vectors from the model it was created with",
+                vector.len(),
+                self.namespace,
+                self.dimensions
             )));
         }
         let mut s = String::with_capacity(vector.len() * 8);
@@ -654,14 +1013,19 @@ impl DuckdbVectorRepository {
         if let Some(node_types) = query.node_types() {
             clauses.push(format!("c.node_type IN ({})", quote_list(node_types)));
         }
+        if query.is_async_only() {
+            clauses.push("c.is_async".to_string());
+        }
         if let Some(repo_ids) = query.repository_ids() {
             clauses.push(format!("c.repository_id IN ({})", quote_list(repo_ids)));
         }
+        clauses.extend(path_glob_clause("c.file_path", query));
+        clauses.extend(symbol_pattern_clause("c.symbol_name", query));
         clauses
     }
 
     fn row_to_chunk(row: &Row) -> Result<CodeChunk, duckdb::Error> {
-        Ok(CodeChunk::reconstitute(
+        let chunk = CodeChunk::reconstitute(
             row.get::<_, String>(0)?,
             row.get::<_, String>(1)?,
             row.get::<_, String>(2)?,
@@ -672,7 +1036,15 @@ impl DuckdbVectorRepository {
             row.get::<_, Option<String>>(7)?,
             row.get::<_, Option<String>>(8)?,
             row.get::<_, String>(9)?,
-        ))
+        );
+        let mut chunk = chunk.with_async(row.get::<_, Option<bool>>(11)?.unwrap_or(false));
+        if let Some(part) = row.get::<_, Option<u32>>(10)? {
+            chunk = chunk.with_part(part);
+        }
+        if let Some(doc) = row.get::<_, Option<String>>(12)? {
+            chunk = chunk.with_doc_comment(doc);
+        }
+        Ok(chunk)
     }
 
     /// Two-stage semantic search that keeps the first stage in the exact shape
@@ -695,10 +1067,13 @@ impl DuckdbVectorRepository {
         if limit == 0 {
             return Ok(vec![]);
         }
+        // Keyset pagination needs the exact (score, id) order and the rows
+        // past the cursor, neither of which the HNSW candidate pass gives.
+        if query.is_paginated() {
+            return Self::run_semantic_full_scan(conn, namespace, array_lit, query, limit);
+        }
 
-        let has_filters = query.languages().is_some()
-            || query.node_types().is_some()
-            || query.repository_ids().is_some();
+        let has_filters = query.has_filters();
         let fetch = if has_filters {
             limit * HNSW_FILTER_OVERFETCH_MULTIPLIER + HNSW_FILTER_OVERFETCH_FLOOR
         } else {
@@ -789,7 +1164,7 @@ impl DuckdbVectorRepository {
         let mut sql = format!(
             "SELECT \
                 c.id, c.file_path, c.content, c.start_line, c.end_line, c.language, c.node_type, \
-                c.symbol_name, c.parent_symbol, c.repository_id \
+                c.symbol_name, c.parent_symbol, c.repository_id, c.part, c.is_async, c.doc_comment \
              FROM \"{schema}\".chunks c \
              WHERE c.id IN ({id_list})",
             schema = namespace,
@@ -837,7 +1212,9 @@ impl DuckdbVectorRepository {
     }
 
     /// Exhaustive fallback: the original join + sort over every embedding.
-    /// Only used when the filtered HNSW candidate pass cannot fill `limit`.
+    /// Used when the filtered HNSW candidate pass cannot fill `limit`, and for
+    /// paginated queries, whose cursor becomes the keyset condition
+    /// `score < cursor_score OR (score = cursor_score AND id > cursor_id)`.
     fn run_semantic_full_scan(
         conn: &Connection,
         namespace: &str,
@@ -845,30 +1222,47 @@ impl DuckdbVectorRepository {
         query: &SearchQuery,
         limit: usize,
     ) -> Result<Vec<SearchResult>, DomainError> {
+        // Scores are compared as FLOAT so a cursor's f32 score matches the
+        // row it was taken from exactly.
+        let score_expr =
+            format!("CAST(1.0 - array_cosine_distance(e.vector, {array_lit}) AS FLOAT)");
         let mut sql = format!(
             "SELECT \
                 c.id, c.file_path, c.content, c.start_line, c.end_line, c.language, c.node_type, \
-                c.symbol_name, c.parent_symbol, c.repository_id, \
-                1.0 - array_cosine_distance(e.vector, {array_lit}) AS score \
+                c.symbol_name, c.parent_symbol, c.repository_id, c.part, c.is_async, c.doc_comment, \
+                {score_expr} AS score \
              FROM \"{schema}\".embeddings e \
              JOIN \"{schema}\".chunks c ON c.id = e.chunk_id",
-            array_lit = array_lit,
+            score_expr = score_expr,
             schema = namespace,
         );
 
-        let where_clauses = Self::filter_clauses(query);
+        let cursor = query.cursor()?;
+        let mut where_clauses = Self::filter_clauses(query);
+        if cursor.is_some() {
+            where_clauses.push(format!(
+                "({score_expr} < ? OR ({score_expr} = ? AND c.id > ?))"
+            ));
+        }
         if !where_clauses.is_empty() {
             sql.push_str(" WHERE ");
             sql.push_str(&where_clauses.join(" AND "));
         }
-        sql.push_str(" ORDER BY score DESC LIMIT ?");
+        sql.push_str(" ORDER BY score DESC, c.id LIMIT ?");
 
         let mut stmt = conn.prepare(&sql).map_err(|e| {
             DomainError::storage(format!("Failed to prepare semantic search: {}", e))
         })?;
-        let mut rows = stmt
-            .query(params![limit as i64])
-            .map_err(|e| DomainError::storage(format!("Failed to run semantic search: {}", e)))?;
+        let mut rows = match &cursor {
+            Some(cursor) => stmt.query(params![
+                cursor.score(),
+                cursor.score(),
+                cursor.chunk_id(),
+                limit as i64
+            ]),
+            None => stmt.query(params![limit as i64]),
+        }
+        .map_err(|e| DomainError::storage(format!("Failed to run semantic search: {}", e)))?;
 
         let mut results = Vec::new();
         while let Some(row) = rows
@@ -876,7 +1270,7 @@ impl DuckdbVectorRepository {
             .map_err(|e| DomainError::storage(format!("Failed to read semantic row: {}", e)))?
         {
             let score: f32 = row
-                .get(10)
+                .get(13)
                 .map_err(|e| DomainError::storage(format!("Failed to read score: {}", e)))?;
             // In hybrid mode the full candidate pool feeds rrf_fuse; apply
             // min_score after fusion instead of dropping candidates here.
@@ -920,11 +1314,11 @@ impl DuckdbVectorRepository {
         // filters before sorting and limiting.
         let mut sql = format!(
             "SELECT sq.id, sq.file_path, sq.content, sq.start_line, sq.end_line, \
-             sq.language, sq.node_type, sq.symbol_name, sq.parent_symbol, sq.repository_id, \
+             sq.language, sq.node_type, sq.symbol_name, sq.parent_symbol, sq.repository_id, sq.part, sq.is_async, sq.doc_comment, \
              CAST(sq.score AS FLOAT) AS score \
              FROM ( \
                  SELECT c.id, c.file_path, c.content, c.start_line, c.end_line, \
-                        c.language, c.node_type, c.symbol_name, c.parent_symbol, c.repository_id, \
+                        c.language, c.node_type, c.symbol_name, c.parent_symbol, c.repository_id, c.part, c.is_async, c.doc_comment, \
                         \"{fts}\".match_bm25(c.id, ?) AS score \
                  FROM \"{ns}\".chunks c \
              ) sq \
@@ -950,6 +1344,9 @@ impl DuckdbVectorRepository {
                 .join(", ");
             extra.push(format!("sq.node_type IN ({})", quoted));
         }
+        if query.is_async_only() {
+            extra.push("sq.is_async".to_string());
+        }
         if let Some(repo_ids) = query.repository_ids() {
             let quoted = repo_ids
                 .iter()
@@ -958,6 +1355,8 @@ impl DuckdbVectorRepository {
                 .join(", ");
             extra.push(format!("sq.repository_id IN ({})", quoted));
         }
+        extra.extend(path_glob_clause("sq.file_path", query));
+        extra.extend(symbol_pattern_clause("sq.symbol_name", query));
         if !extra.is_empty() {
             sql.push_str(&format!(" AND ({})", extra.join(" AND ")));
         }
@@ -976,7 +1375,7 @@ impl DuckdbVectorRepository {
             .map_err(|e| DomainError::storage(format!("Failed to read BM25 row: {e}")))?
         {
             let score: f32 = row
-                .get(10)
+                .get(13)
                 .map_err(|e| DomainError::storage(format!("Failed to read BM25 score: {e}")))?;
             let chunk = Self::row_to_chunk(row).map_err(|e| {
                 DomainError::storage(format!("Failed to parse BM25 chunk row: {e}"))
@@ -1015,8 +1414,8 @@ impl VectorRepository for DuckdbVectorRepository {
                 .prepare(
                     &format!(
                         "INSERT OR REPLACE INTO \"{}\".chunks \
-                        (id, file_path, content, start_line, end_line, language, node_type, symbol_name, parent_symbol, repository_id) \
-                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                        (id, file_path, content, start_line, end_line, language, node_type, symbol_name, parent_symbol, repository_id, part, is_async, doc_comment) \
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
                         self.schema
                     ),
                 )
@@ -1034,6 +1433,9 @@ impl VectorRepository for DuckdbVectorRepository {
                     chunk.symbol_name(),
                     chunk.parent_symbol(),
                     chunk.repository_id(),
+                    chunk.part(),
+                    chunk.is_async(),
+                    chunk.doc_comment(),
                 ])
                 .map_err(|e| {
                     DomainError::storage(format!("Failed to insert chunk {}: {}", chunk.id(), e))
@@ -1114,6 +1516,47 @@ impl VectorRepository for DuckdbVectorRepository {
         Ok(())
     }
 
+    async fn bulk_delete(&self, chunk_ids: &[&str]) -> Result<u64, DomainError> {
+        if chunk_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.conn.lock().await;
+        let tx = conn
+            .transaction()
+            .map_err(|e| DomainError::storage(format!("Failed to begin transaction: {}", e)))?;
+
+        let mut total = 0u64;
+        for batch in chunk_ids.chunks(DELETE_ID_BATCH) {
+            let placeholders = vec!["?"; batch.len()].join(", ");
+            tx.execute(
+                &format!(
+                    "DELETE FROM \"{}\".embeddings WHERE chunk_id IN ({})",
+                    self.schema, placeholders
+                ),
+                params_from_iter(batch.iter().copied()),
+            )
+            .map_err(|e| DomainError::storage(format!("Failed to delete embeddings: {}", e)))?;
+            total += tx
+                .execute(
+                    &format!(
+                        "DELETE FROM \"{}\".chunks WHERE id IN ({})",
+                        self.schema, placeholders
+                    ),
+                    params_from_iter(batch.iter().copied()),
+                )
+                .map_err(|e| DomainError::storage(format!("Failed to delete chunks: {}", e)))?
+                as u64;
+        }
+
+        tx.commit()
+            .map_err(|e| DomainError::storage(format!("Failed to commit: {}", e)))?;
+        self.fts_dirty.store(true, Ordering::Release);
+
+        debug!("Bulk-deleted {} of {} chunks", total, chunk_ids.len());
+        Ok(total)
+    }
+
     async fn delete_by_repository(&self, repository_id: &str) -> Result<(), DomainError> {
         let mut conn = self.conn.lock().await;
         let tx = conn
@@ -1244,102 +1687,29 @@ impl VectorRepository for DuckdbVectorRepository {
         Ok(total)
     }
 
+    #[tracing::instrument(
+        name = "duckdb.search",
+        skip_all,
+        fields(
+            schema = %self.schema,
+            semantic = query_embedding.is_some(),
+            text_search = query.is_text_search(),
+            limit = query.limit(),
+            result_count = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        )
+    )]
     async fn search(
         &self,
         query_embedding: Option<&[f32]>,
         query: &SearchQuery,
     ) -> Result<Vec<SearchResult>, DomainError> {
-        if let Some(embedding) = query_embedding {
-            if embedding.len() != self.dimensions {
-                return Err(DomainError::invalid_input(format!(
-                    "Expected query embedding dimension {}, got {}",
-                    self.dimensions,
-                    embedding.len()
-                )));
-            }
-        }
-
-        let conn = self.conn.lock().await;
-
-        // `None` requests a text-only search (no embeddings indexed); the
-        // semantic leg is skipped entirely.
-        let semantic = match query_embedding {
-            None => Vec::new(),
-            Some(embedding) => {
-                let array_lit = self.vector_to_array_literal(embedding)?;
-                Self::run_semantic(&conn, &self.schema, &array_lit, query, query.limit())?
-            }
-        };
-
-        if !query.is_text_search() && query_embedding.is_some() {
-            return Ok(semantic);
-        }
-
-        // Rebuild the FTS index if the chunk data has changed since last search.
-        // This is a lazy rebuild strategy: we pay the rebuild cost once per "dirty"
-        // window (i.e., after any sequence of inserts/deletes) rather than after
-        // every individual write.
-        if self.fts_dirty.load(Ordering::Acquire) {
-            if self.read_only {
-                // DDL is forbidden in read-only mode; the FTS index was not built
-                // during a prior write session. Degrade silently to semantic-only.
-                debug!(
-                    "FTS index unavailable in read-only mode for namespace '{}'; \
-                     run 'codesearch index' to build it. Falling back to semantic-only.",
-                    self.namespace
-                );
-                return Ok(semantic);
-            }
-            match Self::rebuild_fts_index(&conn, &self.schema) {
-                Ok(()) => {
-                    self.fts_dirty.store(false, Ordering::Release);
-                    debug!("FTS index rebuilt for namespace '{}'", self.namespace);
-                }
-                Err(e) => {
-                    // FTS extension unavailable or another unexpected failure.
-                    warn!(
-                        "Failed to rebuild FTS index (falling back to semantic-only): {}",
-                        e
-                    );
-                    return Ok(semantic);
-                }
-            }
-        }
-
-        // With no semantic candidates the BM25 leg is the only source of
-        // results, so it must honour the full requested limit instead of the
-        // small hybrid headroom.
-        let text_fetch_limit = if semantic.is_empty() {
-            query.limit().max(BM25_FETCH_LIMIT)
-        } else {
-            BM25_FETCH_LIMIT
-        };
-        let text = match Self::run_text(&conn, &self.schema, query, text_fetch_limit) {
-            Ok(results) => results,
-            Err(e) => {
-                // If BM25 query fails (e.g. FTS schema missing in read-only DB),
-                // degrade gracefully to semantic-only results.
-                warn!(
-                    "BM25 text search failed (falling back to semantic-only): {}",
-                    e
-                );
-                return Ok(semantic);
-            }
-        };
-
-        let semantic_len = semantic.len();
-        let text_len = text.len();
-        let mut fused = rrf_fuse(vec![semantic, text], query.limit());
-        info!(
-            "Hybrid search: {} semantic + {} BM25 candidates → {} after fusion",
-            semantic_len,
-            text_len,
-            fused.len()
-        );
-        if let Some(min) = query.min_score() {
-            fused.retain(|r| r.score() >= min);
-        }
-        Ok(fused)
+        let started = Instant::now();
+        let results = self.search_legs(query_embedding, query).await?;
+        let span = tracing::Span::current();
+        span.record("result_count", results.len());
+        span.record("latency_ms", started.elapsed().as_millis() as u64);
+        Ok(results)
     }
 
     async fn flush(&self) -> Result<(), DomainError> {
@@ -1369,6 +1739,23 @@ impl VectorRepository for DuckdbVectorRepository {
         Ok(())
     }
 
+    /// Fold the WAL into the database file and release the blocks freed by
+    /// deletions. DuckDB never does this on its own, so the file keeps its
+    /// size after repositories are deleted until this runs. Affects the
+    /// whole database file, not just this namespace.
+    async fn compact(&self) -> Result<(), DomainError> {
+        if self.read_only {
+            return Err(DomainError::invalid_input(
+                "Cannot compact a read-only database connection",
+            ));
+        }
+        let conn = self.conn.lock().await;
+        conn.execute_batch("CHECKPOINT; VACUUM;")
+            .map_err(|e| DomainError::storage(format!("Failed to compact database: {}", e)))?;
+        info!("Compacted DuckDB database");
+        Ok(())
+    }
+
     async fn has_embeddings(&self) -> Result<bool, DomainError> {
         // Vectors are only ever added mid-process, so a `true` answer is
         // stable and skips the probe on every subsequent search.
@@ -1392,6 +1779,104 @@ impl VectorRepository for DuckdbVectorRepository {
         Ok(exists)
     }
 
+    async fn fetch_embeddings(&self, ids: &[&str]) -> Result<Vec<Embedding>, DomainError> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let id_list = ids
+            .iter()
+            .map(|id| format!("'{}'", id.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        // Read the fixed-size array back as its text form (`[0.1, 0.2, …]`)
+        // rather than depending on the driver's ARRAY value mapping.
+        let conn = self.conn.lock().await;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT chunk_id, CAST(vector AS VARCHAR), model \
+                 FROM \"{}\".embeddings WHERE chunk_id IN ({})",
+                self.schema, id_list
+            ))
+            .map_err(|e| DomainError::storage(format!("Failed to prepare embedding fetch: {e}")))?;
+        let mut rows = stmt
+            .query([])
+            .map_err(|e| DomainError::storage(format!("Failed to run embedding fetch: {e}")))?;
+
+        let mut embeddings = Vec::with_capacity(ids.len());
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| DomainError::storage(format!("Failed to read embedding row: {e}")))?
+        {
+            let read = |i| {
+                row.get::<_, String>(i)
+                    .map_err(|e| DomainError::storage(format!("Failed to read embedding row: {e}")))
+            };
+            let (chunk_id, text, model) = (read(0)?, read(1)?, read(2)?);
+            let vector = text
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .map(|v| v.trim().parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    DomainError::storage(format!("Failed to parse embedding for {chunk_id}: {e}"))
+                })?;
+            embeddings.push(Embedding::new(chunk_id, vector, model));
+        }
+        Ok(embeddings)
+    }
+
+    async fn integrity(&self) -> Result<Option<StorageIntegrity>, DomainError> {
+        let conn = self.conn.lock().await;
+        let schema = &self.schema;
+        let count = |sql: String| {
+            conn.query_row(&sql, [], |row| row.get::<_, i64>(0))
+                .map(|n| n as u64)
+                .map_err(|e| DomainError::storage(format!("Failed to run integrity check: {e}")))
+        };
+
+        let chunk_count = count(format!("SELECT COUNT(*) FROM \"{schema}\".chunks"))?;
+        let embedding_count = count(format!("SELECT COUNT(*) FROM \"{schema}\".embeddings"))?;
+        let chunks_without_embedding = count(format!(
+            "SELECT COUNT(*) FROM \"{schema}\".chunks c \
+             WHERE NOT EXISTS (SELECT 1 FROM \"{schema}\".embeddings e WHERE e.chunk_id = c.id)"
+        ))?;
+        let orphan_embeddings = count(format!(
+            "SELECT COUNT(*) FROM \"{schema}\".embeddings e \
+             WHERE NOT EXISTS (SELECT 1 FROM \"{schema}\".chunks c WHERE c.id = e.chunk_id)"
+        ))?;
+        let vector_index = match count(format!(
+            "SELECT COUNT(*) FROM duckdb_indexes() \
+             WHERE schema_name = '{schema}' AND index_name = 'embedding_hnsw_idx'"
+        ))? {
+            0 => IndexState::Missing,
+            _ => IndexState::Ok,
+        };
+        let text_index = Self::fts_index_state(&conn, schema)?;
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT repository_id, COUNT(*) FROM \"{schema}\".chunks GROUP BY repository_id"
+            ))
+            .map_err(|e| DomainError::storage(format!("Failed to prepare chunk tally: {e}")))?;
+        let chunks_per_repository = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })
+            .and_then(|rows| rows.collect::<Result<HashMap<_, _>, _>>())
+            .map_err(|e| DomainError::storage(format!("Failed to tally chunks: {e}")))?;
+
+        Ok(Some(StorageIntegrity {
+            chunk_count,
+            embedding_count,
+            chunks_without_embedding,
+            orphan_embeddings,
+            text_index,
+            vector_index,
+            chunks_per_repository,
+        }))
+    }
+
     async fn count(&self) -> Result<u64, DomainError> {
         let conn = self.conn.lock().await;
         let count: i64 = conn
@@ -1415,7 +1900,7 @@ impl VectorRepository for DuckdbVectorRepository {
             (
                 format!(
                     "SELECT id, file_path, content, start_line, end_line, language, node_type, \
-                     symbol_name, parent_symbol, repository_id \
+                     symbol_name, parent_symbol, repository_id, part, is_async, doc_comment \
                      FROM \"{}\".chunks WHERE file_path = ? ORDER BY start_line",
                     self.schema
                 ),
@@ -1425,7 +1910,7 @@ impl VectorRepository for DuckdbVectorRepository {
             (
                 format!(
                     "SELECT id, file_path, content, start_line, end_line, language, node_type, \
-                     symbol_name, parent_symbol, repository_id \
+                     symbol_name, parent_symbol, repository_id, part, is_async, doc_comment \
                      FROM \"{}\".chunks WHERE file_path = ? AND repository_id = ? \
                      ORDER BY start_line",
                     self.schema
@@ -1469,7 +1954,8 @@ impl VectorRepository for DuckdbVectorRepository {
         // When a class hint is available (e.g. "GenericUtils" from "GenericUtils#getIp"),
         // rank chunks whose file_path contains that hint higher so that ambiguous short
         // names (same method name in multiple classes) resolve to the right definition.
-        // Fall back to the smallest chunk (tightest scope) as the tiebreaker.
+        // Fall back to the smallest chunk (tightest scope) as the tiebreaker;
+        // a symbol split into parts is represented by its first part.
         let file_rank_expr = if class_hint.is_some() {
             "CASE WHEN file_path LIKE ? THEN 0 ELSE 1 END"
         } else {
@@ -1480,10 +1966,10 @@ impl VectorRepository for DuckdbVectorRepository {
             (
                 format!(
                     "SELECT id, file_path, content, start_line, end_line, language, node_type, \
-                     symbol_name, parent_symbol, repository_id \
+                     symbol_name, parent_symbol, repository_id, part, is_async, doc_comment \
                      FROM \"{}\".chunks \
                      WHERE symbol_name = ? \
-                     ORDER BY {file_rank_expr}, (end_line - start_line) ASC \
+                     ORDER BY {file_rank_expr}, COALESCE(part, 1), (end_line - start_line) ASC \
                      LIMIT 1",
                     self.schema
                 ),
@@ -1493,10 +1979,10 @@ impl VectorRepository for DuckdbVectorRepository {
             (
                 format!(
                     "SELECT id, file_path, content, start_line, end_line, language, node_type, \
-                     symbol_name, parent_symbol, repository_id \
+                     symbol_name, parent_symbol, repository_id, part, is_async, doc_comment \
                      FROM \"{}\".chunks \
                      WHERE symbol_name = ? AND repository_id = ? \
-                     ORDER BY {file_rank_expr}, (end_line - start_line) ASC \
+                     ORDER BY {file_rank_expr}, COALESCE(part, 1), (end_line - start_line) ASC \
                      LIMIT 1",
                     self.schema
                 ),
@@ -1534,6 +2020,55 @@ impl VectorRepository for DuckdbVectorRepository {
         Ok(chunk)
     }
 
+    async fn find_by_symbol(&self, query: &SearchQuery) -> Result<Vec<SearchResult>, DomainError> {
+        let name = query.query();
+        if name.is_empty() {
+            return Ok(vec![]);
+        }
+        let conn = self.conn.lock().await;
+
+        let mut where_clauses = Self::filter_clauses(query);
+        where_clauses.push("(c.symbol_name = ? OR c.symbol_name LIKE ? ESCAPE '\\')".to_string());
+        // Shorter names are closer prefix matches; a split symbol lists its
+        // parts in order.
+        let sql = format!(
+            "SELECT \
+                c.id, c.file_path, c.content, c.start_line, c.end_line, c.language, c.node_type, \
+                c.symbol_name, c.parent_symbol, c.repository_id, c.part, c.is_async, c.doc_comment, \
+                CAST(CASE WHEN c.symbol_name = ? THEN 1.0 ELSE 0.5 END AS FLOAT) AS score \
+             FROM \"{}\".chunks c \
+             WHERE {} \
+             ORDER BY score DESC, length(c.symbol_name), c.file_path, c.start_line, \
+                COALESCE(c.part, 1) \
+             LIMIT ?",
+            self.schema,
+            where_clauses.join(" AND ")
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DomainError::storage(format!("Failed to prepare symbol search: {e}")))?;
+        let prefix = glob_to_like(&format!("{name}*"));
+        let mut rows = stmt
+            .query(params![name, name, prefix, query.limit() as i64])
+            .map_err(|e| DomainError::storage(format!("Failed to run symbol search: {e}")))?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| DomainError::storage(format!("Failed to read symbol search row: {e}")))?
+        {
+            let score: f32 = row
+                .get(13)
+                .map_err(|e| DomainError::storage(format!("Failed to read score: {e}")))?;
+            let chunk = Self::row_to_chunk(row).map_err(|e| {
+                DomainError::storage(format!("Failed to parse symbol search chunk: {e}"))
+            })?;
+            results.push(SearchResult::new(chunk, score));
+        }
+        Ok(results)
+    }
+
     async fn find_chunks_by_symbols(
         &self,
         repository_id: &str,
@@ -1553,7 +2088,7 @@ impl VectorRepository for DuckdbVectorRepository {
 
         let mut sql = format!(
             "SELECT id, file_path, content, start_line, end_line, language, node_type, \
-             symbol_name, parent_symbol, repository_id \
+             symbol_name, parent_symbol, repository_id, part, is_async, doc_comment \
              FROM \"{}\".chunks WHERE symbol_name IN ({})",
             self.schema, symbol_list
         );
@@ -1621,4 +2156,126 @@ impl VectorRepository for DuckdbVectorRepository {
 
         Ok(result)
     }
+
+    async fn list_files(&self, repository_id: &str) -> Result<Vec<FileEntry>, DomainError> {
+        let conn = self.conn.lock().await;
+
+        let sql = format!(
+            "SELECT file_path, COUNT(*), language \
+             FROM \"{}\".chunks \
+             WHERE repository_id = ? \
+             GROUP BY file_path, language \
+             ORDER BY file_path, language",
+            self.schema
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DomainError::storage(format!("Failed to prepare file listing: {e}")))?;
+
+        let mut rows = stmt
+            .query(params![repository_id])
+            .map_err(|e| DomainError::storage(format!("Failed to run file listing: {e}")))?;
+
+        let mut files = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| DomainError::storage(format!("Failed to read file listing row: {e}")))?
+        {
+            let file_path: String = row
+                .get(0)
+                .map_err(|e| DomainError::storage(format!("Failed to read file_path: {e}")))?;
+            let chunk_count: i64 = row
+                .get(1)
+                .map_err(|e| DomainError::storage(format!("Failed to read chunk count: {e}")))?;
+            let language: String = row
+                .get(2)
+                .map_err(|e| DomainError::storage(format!("Failed to read language: {e}")))?;
+            files.push(FileEntry {
+                file_path,
+                chunk_count: chunk_count as u64,
+                language: Language::parse(&language),
+            });
+        }
+
+        Ok(files)
+    }
+
+    async fn stats_by_repository(
+        &self,
+    ) -> Result<HashMap<String, RepositoryChunkStats>, DomainError> {
+        let conn = self.conn.lock().await;
+
+        let sql = format!(
+            "SELECT repository_id, node_type, language, COUNT(*) \
+             FROM \"{}\".chunks \
+             GROUP BY repository_id, node_type, language",
+            self.schema
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DomainError::storage(format!("Failed to prepare chunk stats: {e}")))?;
+
+        let mut rows = stmt
+            .query([])
+            .map_err(|e| DomainError::storage(format!("Failed to run chunk stats: {e}")))?;
+
+        let mut stats: HashMap<String, RepositoryChunkStats> = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| DomainError::storage(format!("Failed to read chunk stats row: {e}")))?
+        {
+            let repository_id: String = row
+                .get(0)
+                .map_err(|e| DomainError::storage(format!("Failed to read repository_id: {e}")))?;
+            let node_type: String = row
+                .get(1)
+                .map_err(|e| DomainError::storage(format!("Failed to read node_type: {e}")))?;
+            let language: String = row
+                .get(2)
+                .map_err(|e| DomainError::storage(format!("Failed to read language: {e}")))?;
+            let count: i64 = row
+                .get(3)
+                .map_err(|e| DomainError::storage(format!("Failed to read chunk count: {e}")))?;
+            let entry = stats.entry(repository_id).or_default();
+            *entry.by_node_type.entry(node_type).or_default() += count as u64;
+            *entry.by_language.entry(language).or_default() += count as u64;
+        }
+
+        Ok(stats)
+    }
+
+    async fn count_by_language(
+        &self,
+        repository_id: Option<&str>,
+    ) -> Result<HashMap<Language, u64>, DomainError> {
+        let conn = self.conn.lock().await;
+
+        let sql = format!(
+            "SELECT language, COUNT(*) FROM \"{}\".chunks \
+             WHERE ?::VARCHAR IS NULL OR repository_id = ? \
+             GROUP BY language",
+            self.schema
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DomainError::storage(format!("Failed to prepare language counts: {e}")))?;
+
+        let rows = stmt
+            .query_map(params![repository_id, repository_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| {
+                DomainError::storage(format!("Failed to count chunks by language: {e}"))
+            })?;
+
+        let mut counts: HashMap<Language, u64> = HashMap::new();
+        for (language, count) in rows {
+            *counts.entry(Language::parse(&language)).or_default() += count;
+        }
+        Ok(counts)
+    }
 }
@@ -0,0 +1,303 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::application::{ChangedLines, GitChange, GitDiff};
+use crate::domain::DomainError;
+
+/// [`GitDiff`] backed by the `git` binary on `PATH`.
+///
+/// Paths are reported relative to the indexed directory (`--relative`), so a
+/// subdirectory of a larger work tree is diffed on its own.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GitCli;
+
+impl GitCli {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn run(repo_path: &Path, args: &[&str]) -> Result<Vec<u8>, DomainError> {
+        let output = tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| DomainError::internal(format!("Failed to run git: {e}")))?;
+        if !output.status.success() {
+            return Err(DomainError::internal(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(output.stdout)
+    }
+}
+
+#[async_trait]
+impl GitDiff for GitCli {
+    async fn head(&self, repo_path: &Path) -> Option<String> {
+        let stdout = Self::run(repo_path, &["rev-parse", "--verify", "HEAD"])
+            .await
+            .ok()?;
+        let sha = String::from_utf8_lossy(&stdout).trim().to_string();
+        (!sha.is_empty()).then_some(sha)
+    }
+
+    async fn changes_since(
+        &self,
+        repo_path: &Path,
+        since: &str,
+    ) -> Result<Vec<GitChange>, DomainError> {
+        let diff = Self::run(
+            repo_path,
+            &[
+                "diff",
+                "--name-status",
+                "-z",
+                "-M",
+                "--relative",
+                since,
+                "--",
+            ],
+        )
+        .await?;
+        let mut changes = parse_name_status(&diff)?;
+
+        // `git diff` only covers tracked files; new files not yet added are
+        // listed separately.
+        let untracked = Self::run(
+            repo_path,
+            &["ls-files", "--others", "--exclude-standard", "-z"],
+        )
+        .await?;
+        changes.extend(
+            split_nul(&untracked)
+                .into_iter()
+                .map(|path| GitChange::Added(path.to_string())),
+        );
+        debug!("git reports {} changes since {}", changes.len(), since);
+        Ok(changes)
+    }
+
+    async fn changed_lines(
+        &self,
+        repo_path: &Path,
+        since: &str,
+    ) -> Result<Vec<ChangedLines>, DomainError> {
+        let diff = Self::run(
+            repo_path,
+            &[
+                "-c",
+                "core.quotePath=false",
+                "diff",
+                "-U0",
+                "--no-color",
+                "--no-ext-diff",
+                "-M",
+                "--relative",
+                since,
+                "--",
+            ],
+        )
+        .await?;
+        let mut files = parse_unified_zero(&String::from_utf8_lossy(&diff));
+
+        // Untracked files are new throughout.
+        let untracked = Self::run(
+            repo_path,
+            &["ls-files", "--others", "--exclude-standard", "-z"],
+        )
+        .await?;
+        files.extend(split_nul(&untracked).into_iter().map(|path| ChangedLines {
+            file_path: path.to_string(),
+            ranges: vec![(1, u32::MAX)],
+        }));
+        debug!(
+            "git reports {} files with changed lines since {}",
+            files.len(),
+            since
+        );
+        Ok(files)
+    }
+}
+
+fn split_nul(bytes: &[u8]) -> Vec<&str> {
+    bytes
+        .split(|b| *b == 0)
+        .filter(|field| !field.is_empty())
+        .filter_map(|field| std::str::from_utf8(field).ok())
+        .collect()
+}
+
+/// Parse `git diff --name-status -z` output: a status field followed by one
+/// path, or two (old, new) for renames and copies.
+fn parse_name_status(bytes: &[u8]) -> Result<Vec<GitChange>, DomainError> {
+    let fields = split_nul(bytes);
+    let mut fields = fields.into_iter();
+    let mut changes = Vec::new();
+    while let Some(status) = fields.next() {
+        let mut path = || {
+            fields
+                .next()
+                .map(str::to_string)
+                .ok_or_else(|| DomainError::parse(format!("git diff: missing path after {status}")))
+        };
+        let change = match status.chars().next() {
+            Some('A') => GitChange::Added(path()?),
+            Some('M') | Some('T') => GitChange::Modified(path()?),
+            Some('D') => GitChange::Deleted(path()?),
+            Some('R') => {
+                let from = path()?;
+                let to = path()?;
+                GitChange::Renamed { from, to }
+            }
+            // A copy leaves its source in place.
+            Some('C') => {
+                path()?;
+                GitChange::Added(path()?)
+            }
+            _ => {
+                return Err(DomainError::parse(format!(
+                    "git diff: unexpected status '{status}'"
+                )))
+            }
+        };
+        changes.push(change);
+    }
+    Ok(changes)
+}
+
+/// Parse `git diff -U0` output into the changed line ranges of each file.
+///
+/// File headers (`--- a/…`, `+++ b/…`) are only read between a `diff --git`
+/// line and the first hunk, so removed lines that start with `--` are not
+/// mistaken for them. A deleted file reports its old path and old lines; a
+/// pure deletion inside a file marks the line it followed.
+fn parse_unified_zero(diff: &str) -> Vec<ChangedLines> {
+    let mut files: Vec<ChangedLines> = Vec::new();
+    let mut in_header = false;
+    let mut old_path: Option<String> = None;
+    let mut deleted = false;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            in_header = true;
+            old_path = None;
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            in_header = false;
+            let side = if deleted { '-' } else { '+' };
+            let range = hunk
+                .split_whitespace()
+                .find_map(|field| field.strip_prefix(side))
+                .and_then(hunk_range);
+            if let (Some(file), Some(range)) = (files.last_mut(), range) {
+                file.ranges.push(range);
+            }
+        } else if in_header {
+            if let Some(path) = line.strip_prefix("--- ") {
+                old_path = header_path(path, "a/");
+            } else if let Some(path) = line.strip_prefix("+++ ") {
+                let new_path = header_path(path, "b/");
+                deleted = new_path.is_none();
+                if let Some(file_path) = new_path.or_else(|| old_path.take()) {
+                    files.push(ChangedLines {
+                        file_path,
+                        ranges: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+    files.retain(|file| !file.ranges.is_empty());
+    files
+}
+
+/// The path of a `---`/`+++` header line, `None` for `/dev/null`.
+fn header_path(field: &str, prefix: &str) -> Option<String> {
+    let field = field.trim_end();
+    let field = field
+        .strip_prefix('"')
+        .and_then(|f| f.strip_suffix('"'))
+        .unwrap_or(field);
+    field.strip_prefix(prefix).map(str::to_string)
+}
+
+/// `start[,count]` of a hunk header as an inclusive line range.
+fn hunk_range(field: &str) -> Option<(u32, u32)> {
+    let (start, count) = match field.split_once(',') {
+        Some((start, count)) => (start.parse::<u32>().ok()?, count.parse::<u32>().ok()?),
+        None => (field.parse::<u32>().ok()?, 1),
+    };
+    Some(match count {
+        0 => (start.max(1), start.max(1)),
+        n => (start, start + n - 1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_name_status_with_renames_and_copies() {
+        let out =
+            b"M\0src/a.rs\0A\0src/new.rs\0D\0old.rs\0R087\0src/b.rs\0src/c.rs\0C100\0x.rs\0y.rs\0";
+        assert_eq!(
+            parse_name_status(out).unwrap(),
+            vec![
+                GitChange::Modified("src/a.rs".to_string()),
+                GitChange::Added("src/new.rs".to_string()),
+                GitChange::Deleted("old.rs".to_string()),
+                GitChange::Renamed {
+                    from: "src/b.rs".to_string(),
+                    to: "src/c.rs".to_string(),
+                },
+                GitChange::Added("y.rs".to_string()),
+            ]
+        );
+        assert!(parse_name_status(b"").unwrap().is_empty());
+        assert!(parse_name_status(b"R100\0only-one\0").is_err());
+    }
+
+    #[test]
+    fn parses_unified_zero_hunks() {
+        let out = "\
+diff --git a/src/a.rs b/src/a.rs
+index 1111111..2222222 100644
+--- a/src/a.rs
++++ b/src/a.rs
+@@ -3 +3,2 @@ fn main() {
+-    old();
++    new();
++    more();
+@@ -10,2 +11,0 @@ fn helper() {
+---- a removed SQL comment
+--- another
+diff --git a/old.rs b/old.rs
+deleted file mode 100644
+--- a/old.rs
++++ /dev/null
+@@ -1,4 +0,0 @@
+-fn gone() {}
+diff --git a/bin.dat b/bin.dat
+Binary files a/bin.dat and b/bin.dat differ
+";
+        assert_eq!(
+            parse_unified_zero(out),
+            vec![
+                ChangedLines {
+                    file_path: "src/a.rs".to_string(),
+                    ranges: vec![(3, 4), (11, 11)],
+                },
+                ChangedLines {
+                    file_path: "old.rs".to_string(),
+                    ranges: vec![(1, 4)],
+                },
+            ]
+        );
+        assert!(parse_unified_zero("").is_empty());
+    }
+}
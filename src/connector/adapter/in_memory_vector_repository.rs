@@ -5,9 +5,10 @@ use async_trait::async_trait;
 use tokio::sync::Mutex;
 use tracing::debug;
 
-use crate::application::{rrf_fuse, VectorRepository};
+use crate::application::{rrf_fuse_hybrid, FileEntry, RepositoryChunkStats, VectorRepository};
 use crate::domain::{
-    cosine_similarity, CodeChunk, DomainError, Embedding, SearchQuery, SearchResult,
+    cosine_similarity, CodeChunk, DomainError, Embedding, Language, SearchCursor, SearchLeg,
+    SearchQuery, SearchResult,
 };
 
 pub struct InMemoryVectorRepository {
@@ -63,6 +64,19 @@ impl VectorRepository for InMemoryVectorRepository {
         Ok(())
     }
 
+    async fn bulk_delete(&self, chunk_ids: &[&str]) -> Result<u64, DomainError> {
+        let mut chunk_store = self.chunks.lock().await;
+        let mut embedding_store = self.embeddings.lock().await;
+        let mut deleted = 0u64;
+        for chunk_id in chunk_ids {
+            if chunk_store.remove(*chunk_id).is_some() {
+                deleted += 1;
+            }
+            embedding_store.remove(*chunk_id);
+        }
+        Ok(deleted)
+    }
+
     async fn delete_by_repository(&self, repository_id: &str) -> Result<(), DomainError> {
         let mut chunk_store = self.chunks.lock().await;
         let mut embedding_store = self.embeddings.lock().await;
@@ -119,9 +133,13 @@ impl VectorRepository for InMemoryVectorRepository {
 
         // `None` requests a text-only search (no embeddings indexed); the
         // semantic leg is skipped entirely.
+        let cursor = query.cursor()?;
         let semantic = match query_embedding {
             None => Vec::new(),
-            Some(embedding) => self.search_semantic(embedding, query, fetch_limit).await,
+            Some(embedding) => {
+                self.search_semantic(embedding, query, fetch_limit, cursor.as_ref())
+                    .await
+            }
         };
 
         if !query.is_text_search() && query_embedding.is_some() {
@@ -129,9 +147,11 @@ impl VectorRepository for InMemoryVectorRepository {
         }
 
         let terms: Vec<&str> = query.query().split_whitespace().collect();
-        let text = self.search_text(&terms, query, fetch_limit).await;
+        let text = self
+            .search_text(&terms, query, query.text_candidates())
+            .await;
 
-        let mut fused = rrf_fuse(vec![semantic, text], query.limit());
+        let mut fused = rrf_fuse_hybrid(semantic, text, query);
         if let Some(min) = query.min_score() {
             fused.retain(|r| r.score() >= min);
         }
@@ -159,6 +179,14 @@ impl VectorRepository for InMemoryVectorRepository {
             .collect())
     }
 
+    async fn fetch_embeddings(&self, ids: &[&str]) -> Result<Vec<Embedding>, DomainError> {
+        let embedding_store = self.embeddings.lock().await;
+        Ok(ids
+            .iter()
+            .filter_map(|id| embedding_store.get(*id).cloned())
+            .collect())
+    }
+
     async fn count(&self) -> Result<u64, DomainError> {
         let chunks = self.chunks.lock().await;
         Ok(chunks.len() as u64)
@@ -209,6 +237,41 @@ impl VectorRepository for InMemoryVectorRepository {
         Ok(matches.first().map(|c| (*c).clone()))
     }
 
+    async fn find_by_symbol(&self, query: &SearchQuery) -> Result<Vec<SearchResult>, DomainError> {
+        let name = query.query();
+        if name.is_empty() {
+            return Ok(vec![]);
+        }
+        let chunks = self.chunks.lock().await;
+        let mut matches: Vec<(f32, &CodeChunk)> = chunks
+            .values()
+            .filter(|c| query.matches(c))
+            .filter_map(|c| match c.symbol_name()? {
+                symbol if symbol == name => Some((1.0, c)),
+                symbol if symbol.starts_with(name) => Some((0.5, c)),
+                _ => None,
+            })
+            .collect();
+        // Same order as the DuckDB adapter.
+        matches.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| {
+                    a.symbol_name()
+                        .map(str::len)
+                        .cmp(&b.symbol_name().map(str::len))
+                })
+                .then_with(|| a.file_path().cmp(b.file_path()))
+                .then_with(|| a.start_line().cmp(&b.start_line()))
+                .then_with(|| a.part().unwrap_or(1).cmp(&b.part().unwrap_or(1)))
+        });
+        Ok(matches
+            .into_iter()
+            .take(query.limit())
+            .map(|(score, c)| SearchResult::new(c.clone(), score))
+            .collect())
+    }
+
     async fn get_symbol_to_file_map(
         &self,
         repository_id: &str,
@@ -224,6 +287,65 @@ impl VectorRepository for InMemoryVectorRepository {
             .collect();
         Ok(result)
     }
+
+    async fn list_files(&self, repository_id: &str) -> Result<Vec<FileEntry>, DomainError> {
+        let chunks = self.chunks.lock().await;
+        let mut counts: HashMap<(&str, Language), u64> = HashMap::new();
+        for chunk in chunks.values() {
+            if chunk.repository_id() == repository_id {
+                *counts
+                    .entry((chunk.file_path(), chunk.language()))
+                    .or_default() += 1;
+            }
+        }
+        let mut files: Vec<FileEntry> = counts
+            .into_iter()
+            .map(|((file_path, language), chunk_count)| FileEntry {
+                file_path: file_path.to_string(),
+                chunk_count,
+                language,
+            })
+            .collect();
+        files.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then_with(|| a.language.as_str().cmp(b.language.as_str()))
+        });
+        Ok(files)
+    }
+
+    async fn stats_by_repository(
+        &self,
+    ) -> Result<HashMap<String, RepositoryChunkStats>, DomainError> {
+        let chunks = self.chunks.lock().await;
+        let mut stats: HashMap<String, RepositoryChunkStats> = HashMap::new();
+        for chunk in chunks.values() {
+            let entry = stats.entry(chunk.repository_id().to_string()).or_default();
+            *entry
+                .by_node_type
+                .entry(chunk.node_type().as_str().to_string())
+                .or_default() += 1;
+            *entry
+                .by_language
+                .entry(chunk.language().as_str().to_string())
+                .or_default() += 1;
+        }
+        Ok(stats)
+    }
+
+    async fn count_by_language(
+        &self,
+        repository_id: Option<&str>,
+    ) -> Result<HashMap<Language, u64>, DomainError> {
+        let chunks = self.chunks.lock().await;
+        let mut counts: HashMap<Language, u64> = HashMap::new();
+        for chunk in chunks.values() {
+            if repository_id.is_none_or(|id| chunk.repository_id() == id) {
+                *counts.entry(chunk.language()).or_default() += 1;
+            }
+        }
+        Ok(counts)
+    }
 }
 
 impl InMemoryVectorRepository {
@@ -232,6 +354,7 @@ impl InMemoryVectorRepository {
         query_embedding: &[f32],
         query: &SearchQuery,
         limit: usize,
+        cursor: Option<&SearchCursor>,
     ) -> Vec<SearchResult> {
         let scored_ids: Vec<(String, f32)> = {
             let embeddings = self.embeddings.lock().await;
@@ -241,8 +364,14 @@ impl InMemoryVectorRepository {
                     let score = cosine_similarity(query_embedding, embedding.vector());
                     (embedding.chunk_id().to_string(), score)
                 })
+                .filter(|(id, score)| cursor.is_none_or(|c| c.is_before(*score, id)))
                 .collect();
-            scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            // Ties break on chunk id, the order keyset pagination relies on.
+            scored.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.0.cmp(&b.0))
+            });
             scored
         };
 
@@ -269,7 +398,7 @@ impl InMemoryVectorRepository {
             if !query.matches(&chunk) {
                 continue;
             }
-            results.push(SearchResult::new(chunk, score));
+            results.push(SearchResult::new(chunk, score).with_semantic_leg());
         }
 
         results
@@ -297,6 +426,7 @@ impl InMemoryVectorRepository {
                     .map(|s| s.to_lowercase())
                     .unwrap_or_default();
 
+                let mut matched = Vec::new();
                 let score: f32 = terms
                     .iter()
                     .map(|t| {
@@ -311,6 +441,9 @@ impl InMemoryVectorRepository {
                         } else {
                             0.0
                         };
+                        if c + s > 0.0 && !matched.contains(&t) {
+                            matched.push(t);
+                        }
                         c + s
                     })
                     .sum::<f32>()
@@ -319,7 +452,11 @@ impl InMemoryVectorRepository {
                 if score == 0.0 || !query.matches(chunk) {
                     return None;
                 }
-                Some(SearchResult::new(chunk.clone(), score))
+                let leg = SearchLeg::Text {
+                    bm25_score: score,
+                    terms: matched,
+                };
+                Some(SearchResult::new(chunk.clone(), score).with_search_leg(leg))
             })
             .collect();
 
@@ -518,4 +655,82 @@ mod tests {
 
         assert!(results.len() <= 1, "limit should cap fused results");
     }
+
+    #[tokio::test]
+    async fn node_type_filter_applies_to_both_legs() {
+        // Every seeded chunk is a function, so a class-only query matches nothing
+        // in either leg while a function-only query keeps the usual results.
+        let repo = seeded_repo().await;
+        let query_embedding = unit_vec(4, 0);
+
+        let classes = SearchQuery::new("alpha")
+            .with_limit(5)
+            .with_text_search(true)
+            .with_node_types(vec!["class".to_string()]);
+        let results = repo.search(Some(&query_embedding), &classes).await.unwrap();
+        assert!(results.is_empty(), "no chunk is a class");
+
+        let functions = SearchQuery::new("alpha")
+            .with_limit(5)
+            .with_text_search(true)
+            .with_node_types(vec!["function".to_string()]);
+        let results = repo
+            .search(Some(&query_embedding), &functions)
+            .await
+            .unwrap();
+        assert_eq!(results[0].chunk().id(), "chunk-alpha");
+    }
+
+    #[tokio::test]
+    async fn async_only_filter_applies_to_both_legs() {
+        let repo = seeded_repo().await;
+        let gamma = make_chunk(
+            "chunk-gamma",
+            "async fn alpha_gamma() {}",
+            Some("alpha_gamma"),
+        )
+        .with_async(true);
+        let gamma_emb = Embedding::new(
+            "chunk-gamma".to_string(),
+            unit_vec(4, 2),
+            "test".to_string(),
+        );
+        repo.save_batch(&[gamma], &[gamma_emb]).await.unwrap();
+        let query_embedding = unit_vec(4, 0);
+
+        let query = SearchQuery::new("alpha")
+            .with_limit(5)
+            .with_text_search(true)
+            .with_async_only(true);
+        let results = repo.search(Some(&query_embedding), &query).await.unwrap();
+        let ids: Vec<&str> = results.iter().map(|r| r.chunk().id()).collect();
+        assert_eq!(ids, vec!["chunk-gamma"]);
+    }
+
+    #[tokio::test]
+    async fn file_pattern_filter_applies_to_both_legs() {
+        // Every seeded chunk lives in `file.rs`.
+        let repo = seeded_repo().await;
+        let query_embedding = unit_vec(4, 0);
+
+        let elsewhere = SearchQuery::new("alpha")
+            .with_limit(5)
+            .with_text_search(true)
+            .with_path_glob("src/**");
+        let results = repo
+            .search(Some(&query_embedding), &elsewhere)
+            .await
+            .unwrap();
+        assert!(results.is_empty(), "no chunk lives under src/");
+
+        let rust_files = SearchQuery::new("alpha")
+            .with_limit(5)
+            .with_text_search(true)
+            .with_path_glob("*.rs");
+        let results = repo
+            .search(Some(&query_embedding), &rust_files)
+            .await
+            .unwrap();
+        assert_eq!(results[0].chunk().id(), "chunk-alpha");
+    }
 }
@@ -0,0 +1,337 @@
+//! On-disk encoding of [`IndexArchive`]s: a zstd-compressed tarball holding,
+//! per repository, a JSON manifest, the repository row, and one JSON Lines
+//! file per store.
+//!
+//! ```text
+//! <repository id>/manifest.json        ArchiveManifest
+//! <repository id>/repository.json      Repository
+//! <repository id>/chunks.jsonl         CodeChunk per line
+//! <repository id>/embeddings.jsonl     Embedding per line
+//! <repository id>/file_hashes.jsonl    FileHash per line
+//! <repository id>/references.jsonl     SymbolReference per line
+//! ```
+//!
+//! Format version 1 archives hold a single repository with its entries at
+//! the top level; they are still read.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::application::{ArchiveManifest, IndexArchive};
+use crate::domain::{CodeChunk, DomainError, Embedding, FileHash, Repository, SymbolReference};
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const REPOSITORY_ENTRY: &str = "repository.json";
+const CHUNKS_ENTRY: &str = "chunks.jsonl";
+const EMBEDDINGS_ENTRY: &str = "embeddings.jsonl";
+const FILE_HASHES_ENTRY: &str = "file_hashes.jsonl";
+const REFERENCES_ENTRY: &str = "references.jsonl";
+
+/// zstd level used for archives; favours speed, embeddings compress poorly anyway.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Write `archive` to `path` as a `.tar.zst`, replacing any existing file.
+pub fn write_index_archive(archive: &IndexArchive, path: &Path) -> Result<(), DomainError> {
+    write_index_archives(std::slice::from_ref(archive), path)
+}
+
+/// Write several repositories' archives (e.g. a whole namespace) into one
+/// `.tar.zst` at `path`, replacing any existing file.
+pub fn write_index_archives(archives: &[IndexArchive], path: &Path) -> Result<(), DomainError> {
+    let file = File::create(path)
+        .map_err(|e| DomainError::storage(format!("Failed to create {}: {}", path.display(), e)))?;
+    let encoder = zstd::stream::Encoder::new(file, ZSTD_LEVEL)
+        .map_err(|e| DomainError::storage(format!("Failed to start zstd stream: {}", e)))?;
+    let mut tar = tar::Builder::new(encoder);
+
+    for archive in archives {
+        let dir = archive.repository.id();
+        let mut append = |entry: &str, data: Vec<u8>| {
+            append_entry(&mut tar, &format!("{}/{}", dir, entry), &data)
+        };
+        append(MANIFEST_ENTRY, to_json(&archive.manifest)?)?;
+        append(REPOSITORY_ENTRY, to_json(&archive.repository)?)?;
+        append(CHUNKS_ENTRY, to_jsonl(&archive.chunks)?)?;
+        append(EMBEDDINGS_ENTRY, to_jsonl(&archive.embeddings)?)?;
+        append(FILE_HASHES_ENTRY, to_jsonl(&archive.file_hashes)?)?;
+        append(REFERENCES_ENTRY, to_jsonl(&archive.references)?)?;
+    }
+
+    let encoder = tar
+        .into_inner()
+        .map_err(|e| DomainError::storage(format!("Failed to finish archive: {}", e)))?;
+    encoder
+        .finish()
+        .and_then(|mut file| file.flush())
+        .map_err(|e| DomainError::storage(format!("Failed to finish archive: {}", e)))
+}
+
+/// Read a single-repository archive written by [`write_index_archive`].
+pub fn read_index_archive(path: &Path) -> Result<IndexArchive, DomainError> {
+    let mut archives = read_index_archives(path)?;
+    if archives.len() != 1 {
+        return Err(DomainError::invalid_input(format!(
+            "{} holds {} repositories, expected one",
+            path.display(),
+            archives.len()
+        )));
+    }
+    Ok(archives.remove(0))
+}
+
+/// The parts of one repository's archive read so far.
+#[derive(Default)]
+struct PartialArchive {
+    manifest: Option<ArchiveManifest>,
+    repository: Option<Repository>,
+    chunks: Vec<CodeChunk>,
+    embeddings: Vec<Embedding>,
+    file_hashes: Vec<FileHash>,
+    references: Vec<SymbolReference>,
+}
+
+/// Read every repository in an archive written by [`write_index_archives`]
+/// (or [`write_index_archive`]), in archive order.
+pub fn read_index_archives(path: &Path) -> Result<Vec<IndexArchive>, DomainError> {
+    let file = File::open(path)
+        .map_err(|e| DomainError::storage(format!("Failed to open {}: {}", path.display(), e)))?;
+    let decoder = zstd::stream::Decoder::new(file)
+        .map_err(|e| DomainError::parse(format!("Not a zstd archive: {}", e)))?;
+    let mut tar = tar::Archive::new(decoder);
+
+    // Keyed by the entry's directory ("" for version 1 archives).
+    let mut parts: BTreeMap<String, PartialArchive> = BTreeMap::new();
+    let mut order: Vec<String> = Vec::new();
+
+    let entries = tar
+        .entries()
+        .map_err(|e| DomainError::parse(format!("Failed to read archive: {}", e)))?;
+    for entry in entries {
+        let entry =
+            entry.map_err(|e| DomainError::parse(format!("Failed to read archive: {}", e)))?;
+        let name = entry
+            .path()
+            .map_err(|e| DomainError::parse(format!("Bad archive entry name: {}", e)))?
+            .to_string_lossy()
+            .to_string();
+        let (dir, file) = name.rsplit_once('/').unwrap_or(("", name.as_str()));
+        if !parts.contains_key(dir) {
+            order.push(dir.to_string());
+        }
+        let part = parts.entry(dir.to_string()).or_default();
+        match file {
+            MANIFEST_ENTRY => part.manifest = Some(from_json(entry, &name)?),
+            REPOSITORY_ENTRY => part.repository = Some(from_json(entry, &name)?),
+            CHUNKS_ENTRY => part.chunks = from_jsonl(entry, &name)?,
+            EMBEDDINGS_ENTRY => part.embeddings = from_jsonl(entry, &name)?,
+            FILE_HASHES_ENTRY => part.file_hashes = from_jsonl(entry, &name)?,
+            REFERENCES_ENTRY => part.references = from_jsonl(entry, &name)?,
+            // Unknown entries are skipped so newer exporters can add extras.
+            _ => {}
+        }
+    }
+
+    let mut archives = Vec::new();
+    for dir in order {
+        let Some(part) = parts.remove(&dir) else {
+            continue;
+        };
+        // Directories holding only unknown entries are not repositories.
+        if part.manifest.is_none() && part.repository.is_none() {
+            continue;
+        }
+        let missing = |entry: &str| {
+            DomainError::parse(format!(
+                "Archive has no {}",
+                Path::new(&dir).join(entry).display()
+            ))
+        };
+        archives.push(IndexArchive {
+            manifest: part.manifest.ok_or_else(|| missing(MANIFEST_ENTRY))?,
+            repository: part.repository.ok_or_else(|| missing(REPOSITORY_ENTRY))?,
+            chunks: part.chunks,
+            embeddings: part.embeddings,
+            file_hashes: part.file_hashes,
+            references: part.references,
+        });
+    }
+    if archives.is_empty() {
+        return Err(DomainError::parse(format!(
+            "Archive has no {}",
+            MANIFEST_ENTRY
+        )));
+    }
+    Ok(archives)
+}
+
+fn append_entry<W: Write>(
+    tar: &mut tar::Builder<W>,
+    name: &str,
+    data: &[u8],
+) -> Result<(), DomainError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(&mut header, name, data)
+        .map_err(|e| DomainError::storage(format!("Failed to write {}: {}", name, e)))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, DomainError> {
+    serde_json::to_vec(value)
+        .map_err(|e| DomainError::storage(format!("Failed to serialise archive entry: {}", e)))
+}
+
+fn to_jsonl<T: Serialize>(values: &[T]) -> Result<Vec<u8>, DomainError> {
+    let mut out = Vec::new();
+    for value in values {
+        serde_json::to_writer(&mut out, value).map_err(|e| {
+            DomainError::storage(format!("Failed to serialise archive entry: {}", e))
+        })?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+fn from_json<T: DeserializeOwned>(reader: impl Read, name: &str) -> Result<T, DomainError> {
+    serde_json::from_reader(reader)
+        .map_err(|e| DomainError::parse(format!("Malformed {}: {}", name, e)))
+}
+
+fn from_jsonl<T: DeserializeOwned>(reader: impl Read, name: &str) -> Result<Vec<T>, DomainError> {
+    let mut values = Vec::new();
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line =
+            line.map_err(|e| DomainError::parse(format!("Failed to read {}: {}", name, e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        values.push(serde_json::from_str(&line).map_err(|e| {
+            DomainError::parse(format!("Malformed {} line {}: {}", name, index + 1, e))
+        })?);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::INDEX_ARCHIVE_FORMAT_VERSION;
+    use crate::domain::{Language, NodeType};
+
+    fn sample_archive() -> IndexArchive {
+        archive_of(Repository::new("demo".to_string(), "/src/demo".to_string()))
+    }
+
+    fn archive_of(repository: Repository) -> IndexArchive {
+        let chunk = CodeChunk::new(
+            "src/lib.rs".to_string(),
+            "fn demo() {}".to_string(),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            repository.id().to_string(),
+        );
+        let embedding = Embedding::new(chunk.id().to_string(), vec![0.5, 0.25], "m".to_string());
+        IndexArchive {
+            manifest: ArchiveManifest {
+                format_version: INDEX_ARCHIVE_FORMAT_VERSION,
+                repository_id: repository.id().to_string(),
+                embedding_model: "m".to_string(),
+                dimensions: 2,
+                chunk_count: 1,
+                embedding_count: 1,
+                exported_at: 0,
+            },
+            file_hashes: vec![FileHash::new(
+                "src/lib.rs".to_string(),
+                "abc".to_string(),
+                repository.id().to_string(),
+            )],
+            repository,
+            chunks: vec![chunk],
+            embeddings: vec![embedding],
+            references: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn archive_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.tar.zst");
+        let archive = sample_archive();
+
+        write_index_archive(&archive, &path).unwrap();
+        let read = read_index_archive(&path).unwrap();
+
+        assert_eq!(read.manifest, archive.manifest);
+        assert_eq!(read.repository.id(), archive.repository.id());
+        assert_eq!(read.chunks.len(), 1);
+        assert_eq!(read.chunks[0].id(), archive.chunks[0].id());
+        assert_eq!(read.embeddings[0].vector(), &[0.5, 0.25]);
+        assert_eq!(read.file_hashes[0].content_hash(), "abc");
+        assert!(read.references.is_empty());
+    }
+
+    #[test]
+    fn several_repositories_share_one_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("namespace.tar.zst");
+        let first = sample_archive();
+        let second = archive_of(Repository::new(
+            "other".to_string(),
+            "/src/other".to_string(),
+        ));
+
+        write_index_archives(&[first.clone(), second.clone()], &path).unwrap();
+        let read = read_index_archives(&path).unwrap();
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].repository.id(), first.repository.id());
+        assert_eq!(read[1].repository.id(), second.repository.id());
+        assert_eq!(read[1].chunks[0].repository_id(), second.repository.id());
+        assert!(read_index_archive(&path).is_err());
+    }
+
+    #[test]
+    fn reads_version_one_archives_with_top_level_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("v1.tar.zst");
+        let archive = sample_archive();
+
+        let encoder = zstd::stream::Encoder::new(File::create(&path).unwrap(), ZSTD_LEVEL).unwrap();
+        let mut tar = tar::Builder::new(encoder);
+        append_entry(
+            &mut tar,
+            MANIFEST_ENTRY,
+            &to_json(&archive.manifest).unwrap(),
+        )
+        .unwrap();
+        append_entry(
+            &mut tar,
+            REPOSITORY_ENTRY,
+            &to_json(&archive.repository).unwrap(),
+        )
+        .unwrap();
+        append_entry(&mut tar, CHUNKS_ENTRY, &to_jsonl(&archive.chunks).unwrap()).unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+
+        let read = read_index_archive(&path).unwrap();
+        assert_eq!(read.repository.id(), archive.repository.id());
+        assert_eq!(read.chunks.len(), 1);
+    }
+
+    #[test]
+    fn reading_a_non_archive_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bogus.tar.zst");
+        std::fs::write(&path, b"not an archive").unwrap();
+        assert!(read_index_archive(&path).is_err());
+    }
+}
@@ -151,11 +151,7 @@ impl LmStudioEmbedding {
 #[async_trait]
 impl EmbeddingService for LmStudioEmbedding {
     async fn embed_chunk(&self, chunk: &CodeChunk) -> Result<Embedding, DomainError> {
-        let text = format!(
-            "{} {}",
-            chunk.qualified_name().as_deref().unwrap_or(""),
-            chunk.content()
-        );
+        let text = chunk.embedding_text();
         let vectors = self.embed_texts(vec![text]).await?;
         Ok(Embedding::new(
             chunk.id().to_string(),
@@ -174,13 +170,7 @@ impl EmbeddingService for LmStudioEmbedding {
         for batch in chunks.chunks(BATCH_SIZE) {
             let texts: Vec<String> = batch
                 .iter()
-                .map(|c| {
-                    format!(
-                        "{} {}",
-                        c.qualified_name().as_deref().unwrap_or(""),
-                        c.content()
-                    )
-                })
+                .map(CodeChunk::embedding_text)
                 .collect();
 
             let vectors = self.embed_texts(texts).await?;
@@ -4,6 +4,7 @@
 //! - `GET  /api/repositories/:id`  — one repository + its cluster architecture overview
 //! - `DELETE /api/repositories/:id`— delete a repository by ID or path
 //! - `GET  /api/stats`             — index-wide statistics
+//! - `POST /api/vacuum`            — reclaim space left by deletions
 
 use axum::extract::{Path, State};
 use axum::Json;
@@ -100,6 +101,19 @@ pub async fn stats(State(state): State<AppState>) -> ApiResult<Json<Value>> {
         "total_files": total_files,
         "total_chunks": total_chunks,
         "data_dir": state.container.data_dir(),
+        "data_file_size": state.container.data_file_size(),
         "namespace": state.container.namespace(),
     })))
 }
+
+/// `POST /api/vacuum` — compact the database file (mirrors the CLI `vacuum`)
+/// and report its size before and after, in bytes.
+pub async fn vacuum(State(state): State<AppState>) -> ApiResult<Json<Value>> {
+    let size_before = state.container.data_file_size();
+    state.container.vector_repository().compact().await?;
+    let size_after = state.container.data_file_size();
+    Ok(Json(json!({
+        "size_before": size_before,
+        "size_after": size_after,
+    })))
+}
@@ -1,13 +1,14 @@
 //! Search endpoint — `POST /api/search`.
 //!
 //! Body maps onto the hybrid search use case (the same one the CLI `search`
-//! command drives). Returns a JSON array of structured results.
+//! command drives). Returns a JSON array of structured results; with
+//! `paginate` (or a `cursor`), also a `next_cursor` for the following page.
 
 use axum::extract::State;
 use axum::Json;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::{SearchQuery, SearchResult};
+use crate::domain::{SearchCursor, SearchQuery, SearchResult};
 
 use super::super::error::ApiResult;
 use super::super::server::AppState;
@@ -32,9 +33,19 @@ pub struct SearchRequest {
     /// Optional repository filter (names or UUIDs).
     #[serde(default)]
     pub repositories: Option<Vec<String>>,
+    /// Optional node-type filter (e.g. `["function", "class"]`).
+    #[serde(default)]
+    pub node_types: Option<Vec<String>>,
     /// Whether to include the keyword (BM25) leg. Defaults to `true`.
     #[serde(default = "default_text_search")]
     pub text_search: bool,
+    /// Page through results. Paginated searches rank by semantic similarity
+    /// alone, so every page continues the same order.
+    #[serde(default)]
+    pub paginate: bool,
+    /// `next_cursor` of the previous page; implies `paginate`.
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 fn default_limit() -> usize {
@@ -92,12 +103,26 @@ pub async fn search(
     if let Some(repositories) = req.repositories {
         query = query.with_repositories(repositories);
     }
+    if let Some(node_types) = req.node_types {
+        query = query.with_node_types(node_types);
+    }
+
+    let paginate = req.paginate || req.cursor.is_some();
+    if paginate {
+        query = query.with_cursor(req.cursor);
+    }
 
     let results = state.container.search_use_case().execute(query).await?;
     let hits: Vec<SearchHit> = results.iter().map(SearchHit::from_result).collect();
+    // A short page is the last one.
+    let next_cursor = results
+        .last()
+        .filter(|_| paginate && results.len() >= req.limit.max(1))
+        .map(|last| SearchCursor::after(last).encode());
 
     Ok(Json(serde_json::json!({
         "count": hits.len(),
         "results": hits,
+        "next_cursor": next_cursor,
     })))
 }
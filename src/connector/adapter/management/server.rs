@@ -103,6 +103,7 @@ pub fn routes(state: AppState) -> Router {
             get(handlers::repositories::get).delete(handlers::repositories::delete),
         )
         .route("/api/stats", get(handlers::repositories::stats))
+        .route("/api/vacuum", post(handlers::repositories::vacuum))
         // Search.
         .route("/api/search", post(handlers::search::search))
         // Call-graph queries.
@@ -213,6 +214,7 @@ async fn index(State(_state): State<AppState>) -> Json<Value> {
             { "method": "GET", "path": "/api/repositories/{id}", "description": "one repository + architecture overview" },
             { "method": "DELETE", "path": "/api/repositories/{id}", "description": "delete a repository by ID or path" },
             { "method": "GET", "path": "/api/stats", "description": "index-wide statistics" },
+            { "method": "POST", "path": "/api/vacuum", "description": "reclaim database space left by deletions" },
             { "method": "POST", "path": "/api/search", "description": "hybrid semantic + keyword code search" },
             { "method": "POST", "path": "/api/impact", "description": "blast radius of changing a symbol" },
             { "method": "GET", "path": "/api/context/{symbol}", "description": "callers + callees of a symbol" },
@@ -17,6 +17,9 @@
 //! | `done`     | explain, index        | operation-specific summary object (see handlers)        |
 //! | `error`    | explain, index        | `{ "message": "<human-readable error>" }`               |
 //!
+//! Index `progress` events with stage `parsing` are sent once per file and
+//! also carry `files_done`, `files_total`, `current_file` and `chunks_so_far`.
+//!
 //! A terminal `done` **or** `error` event is always the last frame of a stream,
 //! after which the server closes the connection. Clients should treat either as
 //! end-of-stream. If the client disconnects early, the underlying work is
@@ -36,7 +39,7 @@ use serde::Deserialize;
 use serde_json::json;
 use tokio::sync::mpsc;
 
-use crate::application::ChatClient;
+use crate::application::{ChatClient, IndexProgress};
 use crate::cli::LlmTarget;
 use crate::connector::adapter::{
     AnthropicClient, CodesearchConfig, CopilotChatClient, OpenAiChatClient,
@@ -464,13 +467,31 @@ async fn run_index_stream(
         json!({ "stage": "started", "message": format!("indexing {}", req.path) }),
     ));
 
-    let (store, namespace): (VectorStore, Option<String>) = if container.memory_storage() {
-        (VectorStore::InMemory, None)
-    } else {
-        (VectorStore::DuckDb, Some(container.namespace().to_string()))
+    let store = container.vector_store();
+    let namespace = match store {
+        VectorStore::InMemory => None,
+        _ => Some(container.namespace().to_string()),
     };
 
-    let use_case = container.index_use_case();
+    let progress_tx = event_tx.clone();
+    let use_case = container.index_use_case().with_progress_callback(Arc::new(
+        move |progress: IndexProgress| {
+            let _ = progress_tx.send(sse_event(
+                "progress",
+                json!({
+                    "stage": "parsing",
+                    "message": format!(
+                        "[{}/{}] {}",
+                        progress.files_done, progress.files_total, progress.current_file
+                    ),
+                    "files_done": progress.files_done,
+                    "files_total": progress.files_total,
+                    "current_file": progress.current_file,
+                    "chunks_so_far": progress.chunks_so_far,
+                }),
+            ));
+        },
+    ));
     let path = req.path.clone();
     let name = req.name.clone();
     let force = req.force;
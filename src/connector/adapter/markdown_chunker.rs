@@ -0,0 +1,236 @@
+//! Chunking for Markdown and plain-text documentation, which has no
+//! tree-sitter grammar: one chunk per heading section, with sections (or
+//! heading-less files) longer than the chunk limit cut into fixed-size
+//! overlapping windows.
+
+use std::path::Path;
+
+use super::treesitter_parser::split_lines;
+use crate::domain::{CodeChunk, Language, NodeType};
+
+/// A run of lines introduced by a heading (or the text before the first one).
+struct Section<'a> {
+    /// 0-based index of the first line.
+    start: usize,
+    /// 0-based index one past the last line.
+    end: usize,
+    title: Option<&'a str>,
+    parent: Option<&'a str>,
+}
+
+/// Split a documentation file into [`NodeType::Block`] chunks, one per
+/// heading section. The heading text becomes the symbol name and the
+/// enclosing higher-level heading the parent symbol. Plain-text files are
+/// cut into windows only: a line starting with `#` there is as likely a
+/// comment or a numbered item as a heading.
+pub(crate) fn chunk_markdown(
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+    max_lines: usize,
+) -> Vec<CodeChunk> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut chunks = Vec::new();
+
+    let sections = if is_plain_text(file_path) {
+        vec![Section {
+            start: 0,
+            end: lines.len(),
+            title: None,
+            parent: None,
+        }]
+    } else {
+        sections(&lines)
+    };
+    for section in sections {
+        // Blank lines before the next heading belong to no one.
+        let mut end = section.end;
+        while end > section.start + 1 && lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        let text = lines[section.start..end].concat();
+        if text.trim().len() < 10 {
+            continue;
+        }
+
+        let parts = split_lines(&text, section.start as u32 + 1, end as u32, max_lines);
+        let is_split = parts.len() > 1;
+        for (index, (start_line, end_line, part_content)) in parts.into_iter().enumerate() {
+            let mut chunk = CodeChunk::new(
+                file_path.to_string(),
+                part_content,
+                start_line,
+                end_line,
+                Language::Markdown,
+                NodeType::Block,
+                repository_id.to_string(),
+            );
+            if let Some(title) = section.title {
+                chunk = chunk.with_symbol_name(title.to_string());
+            }
+            if let Some(parent) = section.parent {
+                chunk = chunk.with_parent_symbol(parent.to_string());
+            }
+            if is_split {
+                chunk = chunk.with_part(index as u32 + 1);
+            }
+            chunks.push(chunk);
+        }
+    }
+    chunks
+}
+
+/// Whether `file_path` names a `.txt` file, whose `#` lines are not headings.
+fn is_plain_text(file_path: &str) -> bool {
+    Path::new(file_path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"))
+}
+
+/// Cut `lines` at every ATX heading outside a fenced code block.
+fn sections<'a>(lines: &[&'a str]) -> Vec<Section<'a>> {
+    let mut sections = vec![Section {
+        start: 0,
+        end: lines.len(),
+        title: None,
+        parent: None,
+    }];
+    // Open headings, outermost first, as `(level, title)`.
+    let mut open: Vec<(usize, &str)> = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+        let Some((level, title)) = heading(line) else {
+            continue;
+        };
+
+        while open
+            .last()
+            .is_some_and(|(open_level, _)| *open_level >= level)
+        {
+            open.pop();
+        }
+        let parent = open.last().map(|(_, title)| *title);
+        open.push((level, title));
+
+        if let Some(previous) = sections.last_mut() {
+            previous.end = index;
+        }
+        sections.push(Section {
+            start: index,
+            end: lines.len(),
+            title: (!title.is_empty()).then_some(title),
+            parent: parent.filter(|p| !p.is_empty()),
+        });
+    }
+    sections
+}
+
+/// `(level, title)` of an ATX heading line such as `## Install ##`.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let level = rest.len() - rest.trim_start_matches('#').len();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &rest[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t', '\r', '\n']) {
+        return None;
+    }
+    let title = rest.trim();
+    let title = title.trim_end_matches('#').trim_end();
+    Some((level, title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_follow_headings_outside_code_fences() {
+        let content = "Project overview text.\n\
+                       \n\
+                       # Guide\n\
+                       Read this first.\n\
+                       \n\
+                       ## Install\n\
+                       ```sh\n\
+                       # not a heading\n\
+                       cargo install codesearch\n\
+                       ```\n\
+                       \n\
+                       ## Usage ##\n\
+                       Run codesearch search.\n";
+        let chunks = chunk_markdown(content, "README.md", "repo", 150);
+
+        let summary: Vec<_> = chunks
+            .iter()
+            .map(|c| {
+                (
+                    c.symbol_name(),
+                    c.parent_symbol(),
+                    c.start_line(),
+                    c.end_line(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (None, None, 1, 1),
+                (Some("Guide"), None, 3, 4),
+                (Some("Install"), Some("Guide"), 6, 10),
+                (Some("Usage"), Some("Guide"), 12, 13),
+            ]
+        );
+        assert!(chunks
+            .iter()
+            .all(|c| c.language() == Language::Markdown && c.node_type() == NodeType::Block));
+    }
+
+    #[test]
+    fn test_text_without_headings_falls_back_to_windows() {
+        let content: String = (1..=250).map(|i| format!("line {i}\n")).collect();
+        let chunks = chunk_markdown(&content, "NOTES.txt", "repo", 100);
+
+        let ranges: Vec<_> = chunks
+            .iter()
+            .map(|c| (c.start_line(), c.end_line(), c.part()))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![(1, 100, Some(1)), (91, 190, Some(2)), (181, 250, Some(3))]
+        );
+        assert!(chunks.iter().all(|c| c.symbol_name().is_none()));
+    }
+
+    #[test]
+    fn test_plain_text_is_not_split_at_hash_lines() {
+        let content = "Release checklist\n\
+                       # bump the version first\n\
+                       run the tests\n\
+                       ## then tag\n\
+                       push the tag\n";
+        let chunks = chunk_markdown(content, "CHECKLIST.txt", "repo", 150);
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!((chunks[0].start_line(), chunks[0].end_line()), (1, 5));
+        assert_eq!(chunks[0].symbol_name(), None);
+        assert_eq!(chunks[0].language(), Language::Markdown);
+    }
+}
@@ -58,6 +58,11 @@ pub struct SearchToolInput {
     /// Filter results by repository IDs
     pub repositories: Option<Vec<String>>,
 
+    /// Filter results by node types (e.g., ["function"] for only functions). Valid values:
+    /// function, constructor, property, class, struct, enum, trait, impl, module, constant,
+    /// typedef, interface, block
+    pub node_types: Option<Vec<String>>,
+
     /// Enable keyword (BM25) search fused with semantic search via Reciprocal Rank Fusion.
     /// Defaults to true; set to false to use only semantic (vector) search.
     #[serde(default = "default_text_search")]
@@ -484,12 +489,18 @@ impl CodesearchMcpServer {
         if let Some(repos) = input.repositories {
             query = query.with_repositories(repos);
         }
+        if let Some(types) = input.node_types {
+            query = query.with_node_types(types);
+        }
 
         let use_case = self.container.search_use_case();
-        let results = use_case
-            .execute(query)
-            .await
-            .map_err(|e| McpError::internal_error(format!("Search failed: {}", e), None))?;
+        let results = use_case.execute(query).await.map_err(|e| {
+            if e.is_invalid_input() {
+                McpError::invalid_params(e.to_string(), None)
+            } else {
+                McpError::internal_error(format!("Search failed: {}", e), None)
+            }
+        })?;
 
         let outputs: Vec<SearchResultOutput> = results
             .iter()
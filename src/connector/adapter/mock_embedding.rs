@@ -1,36 +1,45 @@
 use async_trait::async_trait;
 use rand::Rng;
 use rand::SeedableRng;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use rand_chacha::ChaCha8Rng;
 use tracing::debug;
 
 use crate::application::EmbeddingService;
 use crate::domain::{CodeChunk, DomainError, Embedding, EmbeddingConfig};
 
+/// Seed used by [`MockEmbedding::new`] and [`MockEmbedding::with_dimensions`].
+pub const DEFAULT_MOCK_SEED: u64 = 42;
+
 pub struct MockEmbedding {
     config: EmbeddingConfig,
+    seed: u64,
 }
 
 impl MockEmbedding {
     pub fn new() -> Self {
+        Self::with_seed(DEFAULT_MOCK_SEED)
+    }
+
+    /// Mock whose vectors are derived from `seed` and the embedded text only,
+    /// so the same seed yields the same vectors on every run and platform.
+    /// ChaCha8's output is fixed by its specification, unlike `SmallRng`'s,
+    /// which may change between `rand` releases and differs by pointer width.
+    pub fn with_seed(seed: u64) -> Self {
         Self {
             config: EmbeddingConfig::new("mock-embedding".to_string(), 384, 512),
+            seed,
         }
     }
 
     pub fn with_dimensions(dimensions: usize) -> Self {
         Self {
             config: EmbeddingConfig::new("mock-embedding".to_string(), dimensions, 512),
+            seed: DEFAULT_MOCK_SEED,
         }
     }
 
     fn generate_embedding(&self, text: &str) -> Vec<f32> {
-        let mut hasher = DefaultHasher::new();
-        text.hash(&mut hasher);
-        let seed = hasher.finish();
-
-        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed ^ stable_hash(text));
         let mut vector: Vec<f32> = (0..self.config.dimensions())
             .map(|_| rng.gen_range(-1.0..1.0))
             .collect();
@@ -53,12 +62,24 @@ impl MockEmbedding {
         }
 
         text.push_str(&format!("[{}] ", chunk.node_type()));
+        if let Some(doc) = chunk.embedding_doc() {
+            text.push_str(doc);
+            text.push('\n');
+        }
         text.push_str(chunk.content());
 
         text
     }
 }
 
+/// FNV-1a over the text bytes. Unlike `DefaultHasher`, its output is fixed
+/// across Rust releases, which keeps the mock vectors reproducible.
+fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 impl Default for MockEmbedding {
     fn default() -> Self {
         Self::new()
@@ -126,6 +147,31 @@ mod tests {
         assert_eq!(embedding1, embedding2);
     }
 
+    #[tokio::test]
+    async fn test_mock_embedding_seed() {
+        let a = MockEmbedding::with_seed(7)
+            .embed_query("test")
+            .await
+            .unwrap();
+        let b = MockEmbedding::with_seed(7)
+            .embed_query("test")
+            .await
+            .unwrap();
+        let c = MockEmbedding::with_seed(8)
+            .embed_query("test")
+            .await
+            .unwrap();
+        let default = MockEmbedding::new().embed_query("test").await.unwrap();
+        let seeded = MockEmbedding::with_seed(DEFAULT_MOCK_SEED)
+            .embed_query("test")
+            .await
+            .unwrap();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(default, seeded);
+    }
+
     #[tokio::test]
     async fn test_mock_embedding_dimensions() {
         let service = MockEmbedding::with_dimensions(128);
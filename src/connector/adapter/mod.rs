@@ -20,22 +20,28 @@ mod copilot_chat_client;
 mod duckdb_analysis_repository;
 mod duckdb_call_graph_repository;
 mod duckdb_channel_endpoint_repository;
+mod duckdb_embedding_cache;
 mod duckdb_file_hash_repository;
 mod duckdb_memory_repository;
 mod duckdb_metadata_repository;
 mod duckdb_vector_repository;
+mod git_cli;
 mod in_memory_vector_repository;
+mod index_archive_file;
 mod llm_query_expander;
 pub mod management;
+mod markdown_chunker;
 pub mod mcp;
 mod mock_embedding;
 mod mock_reranking;
 mod no_embedding;
+mod ollama_embedding;
 mod openai_chat_client;
 mod openai_embedding;
 mod openai_reranking;
 mod ort_embedding;
 mod ort_reranking;
+mod pgvector_repository;
 mod resource_fetch;
 pub mod scip;
 mod session_discovery;
@@ -51,20 +57,25 @@ pub use copilot_chat_client::*;
 pub use duckdb_analysis_repository::*;
 pub use duckdb_call_graph_repository::*;
 pub use duckdb_channel_endpoint_repository::*;
+pub use duckdb_embedding_cache::*;
 pub use duckdb_file_hash_repository::*;
 pub use duckdb_memory_repository::*;
 pub use duckdb_metadata_repository::*;
 pub use duckdb_vector_repository::*;
+pub use git_cli::*;
 pub use in_memory_vector_repository::*;
+pub use index_archive_file::*;
 pub use llm_query_expander::*;
 pub use mock_embedding::*;
 pub use mock_reranking::*;
 pub use no_embedding::*;
+pub use ollama_embedding::*;
 pub use openai_chat_client::*;
 pub use openai_embedding::*;
 pub use openai_reranking::*;
 pub use ort_embedding::*;
 pub use ort_reranking::*;
+pub use pgvector_repository::*;
 pub use resource_fetch::*;
 pub use session_discovery::{
     discover_all_sessions, discover_all_sessions_streaming, load_transcript, LocalSessionDiscovery,
@@ -0,0 +1,319 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+use tracing::debug;
+
+use crate::application::EmbeddingService;
+use crate::domain::{CodeChunk, DomainError, Embedding, EmbeddingConfig};
+
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+const EMBEDDINGS_PATH: &str = "/api/embeddings";
+const DEFAULT_CONCURRENCY: usize = 4;
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    embedding: Vec<f32>,
+}
+
+/// HTTP embedding adapter targeting a local [Ollama](https://ollama.com)
+/// server's `/api/embeddings` endpoint (e.g. `nomic-embed-text`).
+///
+/// Ollama embeds one prompt per request, so batches are fanned out as
+/// concurrent requests — at most `concurrency` in flight at once.
+///
+/// **Configuration**:
+/// - Base URL: `OLLAMA_BASE_URL` env var (default `http://localhost:11434`).
+/// - Model name and dimensions: supplied at construction time from `--embedding-model`
+///   and `--embedding-dimensions`; they are stored in `namespace_config` and
+///   validated on every subsequent open.
+/// - Concurrency: the global `--embedding-concurrency` flag.
+pub struct OllamaEmbedding {
+    client: reqwest::Client,
+    url: String,
+    concurrency: usize,
+    config: EmbeddingConfig,
+    /// Width of the first vector the server returned; every later response
+    /// must match it.
+    detected_dimensions: OnceLock<usize>,
+}
+
+impl OllamaEmbedding {
+    /// Build against `OLLAMA_BASE_URL` (or the default local server).
+    pub fn new(model: impl Into<String>, dimensions: usize) -> Self {
+        let base =
+            std::env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Self::with_endpoint(&base, model, dimensions)
+    }
+
+    /// Build against an explicit server, e.g. `http://gpu-box:11434`.
+    pub fn with_endpoint(base_url: &str, model: impl Into<String>, dimensions: usize) -> Self {
+        let url = format!("{}{}", base_url.trim_end_matches('/'), EMBEDDINGS_PATH);
+        let model = model.into();
+
+        debug!(
+            "OllamaEmbedding: endpoint={}, model={}, dims={}",
+            url, model, dimensions
+        );
+
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(60))
+                .build()
+                .expect("reqwest::Client build failed"),
+            url,
+            concurrency: DEFAULT_CONCURRENCY,
+            config: EmbeddingConfig::new(model, dimensions, 512),
+            detected_dimensions: OnceLock::new(),
+        }
+    }
+
+    /// Maximum number of requests in flight while embedding a batch.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    async fn embed_one(
+        client: &reqwest::Client,
+        url: &str,
+        model: &str,
+        text: &str,
+    ) -> Result<Vec<f32>, DomainError> {
+        let response = client
+            .post(url)
+            .json(&EmbeddingRequest {
+                model,
+                prompt: text,
+            })
+            .send()
+            .await
+            .map_err(|e| DomainError::internal(format!("Ollama embedding request failed: {e}")))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(DomainError::internal(format!(
+                "Ollama embedding API returned {status}: {body}"
+            )));
+        }
+
+        let mut vec = response
+            .json::<EmbeddingResponse>()
+            .await
+            .map_err(|e| {
+                DomainError::internal(format!("Failed to parse Ollama embedding response: {e}"))
+            })?
+            .embedding;
+
+        // L2-normalise so cosine similarity equals dot product.
+        let norm: f32 = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for v in &mut vec {
+                *v /= norm;
+            }
+        }
+        Ok(vec)
+    }
+
+    /// Check a returned vector against the first width seen (caching it on
+    /// the first call) and against the namespace's configured dimensions.
+    fn check_dimensions(&self, vec: &[f32]) -> Result<(), DomainError> {
+        let detected = *self.detected_dimensions.get_or_init(|| {
+            debug!("OllamaEmbedding: detected {} dimensions", vec.len());
+            vec.len()
+        });
+        if vec.len() != detected {
+            return Err(DomainError::invalid_input(format!(
+                "Ollama model '{}' returned a {}-dimensional vector after earlier \
+                 {}-dimensional ones; was the model swapped on the server?",
+                self.config.model_name(),
+                vec.len(),
+                detected
+            )));
+        }
+        if detected != self.config.dimensions() {
+            return Err(DomainError::invalid_input(format!(
+                "Ollama model '{}' returns {}-dimensional vectors, but the namespace \
+                 expects {}. Create the namespace with --embedding-dimensions {}.",
+                self.config.model_name(),
+                detected,
+                self.config.dimensions(),
+                detected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Embed `texts` with up to `concurrency` requests in flight, returning
+    /// vectors in input order.
+    async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, DomainError> {
+        let n = texts.len();
+        let mut vectors: Vec<Vec<f32>> = vec![Vec::new(); n];
+        let mut set = JoinSet::new();
+        let mut pending = texts.into_iter().enumerate();
+
+        loop {
+            while set.len() < self.concurrency {
+                let Some((i, text)) = pending.next() else {
+                    break;
+                };
+                let client = self.client.clone();
+                let url = self.url.clone();
+                let model = self.config.model_name().to_string();
+                set.spawn(async move { (i, Self::embed_one(&client, &url, &model, &text).await) });
+            }
+            let Some(joined) = set.join_next().await else {
+                break;
+            };
+            let (i, result) = joined
+                .map_err(|e| DomainError::internal(format!("Embedding task panicked: {e}")))?;
+            let vec = result?;
+            self.check_dimensions(&vec)?;
+            vectors[i] = vec;
+        }
+
+        debug!("OllamaEmbedding: {} embedding(s)", n);
+        Ok(vectors)
+    }
+}
+
+#[async_trait]
+impl EmbeddingService for OllamaEmbedding {
+    async fn embed_chunk(&self, chunk: &CodeChunk) -> Result<Embedding, DomainError> {
+        let mut embeddings = self.embed_chunks(std::slice::from_ref(chunk)).await?;
+        embeddings
+            .pop()
+            .ok_or_else(|| DomainError::internal("OllamaEmbedding: empty response for chunk"))
+    }
+
+    async fn embed_chunks(&self, chunks: &[CodeChunk]) -> Result<Vec<Embedding>, DomainError> {
+        let texts: Vec<String> = chunks.iter().map(CodeChunk::embedding_text).collect();
+
+        let vectors = self.embed_texts(texts).await?;
+        Ok(chunks
+            .iter()
+            .zip(vectors)
+            .map(|(chunk, vector)| {
+                Embedding::new(
+                    chunk.id().to_string(),
+                    vector,
+                    self.config.model_name().to_string(),
+                )
+            })
+            .collect())
+    }
+
+    async fn embed_query(&self, query: &str) -> Result<Vec<f32>, DomainError> {
+        self.embed_texts(vec![query.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| DomainError::internal("OllamaEmbedding: empty response for query"))
+    }
+
+    fn config(&self) -> &EmbeddingConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{http::StatusCode, routing::post, Json, Router};
+    use serde_json::{json, Value};
+
+    /// Serve `app` on an ephemeral port, returning its base URL.
+    async fn spawn_server(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+        format!("http://{addr}")
+    }
+
+    /// A mock Ollama answering prompt `text-i` with `[1, i, 0]`, later
+    /// prompts faster than earlier ones so responses arrive out of order.
+    fn indexed_embeddings() -> Router {
+        Router::new().route(
+            EMBEDDINGS_PATH,
+            post(|Json(request): Json<Value>| async move {
+                let prompt = request["prompt"].as_str().unwrap_or_default();
+                let i: u64 = prompt
+                    .trim_start_matches("text-")
+                    .parse()
+                    .unwrap_or_default();
+                tokio::time::sleep(Duration::from_millis(10 * (8 - i.min(8)))).await;
+                Json(json!({ "embedding": [1.0, i as f32, 0.0] }))
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn dimensions_are_detected_from_first_response() {
+        let base = spawn_server(indexed_embeddings()).await;
+        let embedding = OllamaEmbedding::with_endpoint(&base, "nomic-embed-text", 3);
+
+        let vector = embedding.embed_query("text-0").await.unwrap();
+
+        assert_eq!(vector.len(), 3);
+        assert_eq!(embedding.detected_dimensions.get(), Some(&3));
+    }
+
+    #[tokio::test]
+    async fn dimension_mismatch_with_namespace_is_rejected() {
+        let base = spawn_server(indexed_embeddings()).await;
+        let embedding = OllamaEmbedding::with_endpoint(&base, "nomic-embed-text", 768);
+
+        let err = embedding.embed_query("text-0").await.unwrap_err();
+
+        assert!(
+            err.to_string().contains("--embedding-dimensions 3"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn batched_vectors_keep_input_order() {
+        let base = spawn_server(indexed_embeddings()).await;
+        let embedding =
+            OllamaEmbedding::with_endpoint(&base, "nomic-embed-text", 3).with_concurrency(4);
+        let texts: Vec<String> = (0..8).map(|i| format!("text-{i}")).collect();
+
+        let vectors = embedding.embed_texts(texts).await.unwrap();
+
+        assert_eq!(vectors.len(), 8);
+        for (i, vector) in vectors.iter().enumerate() {
+            // Normalised [1, i, 0]: the second component over the first is i.
+            let ratio = vector[1] / vector[0];
+            assert!((ratio - i as f32).abs() < 1e-4, "vector {i} is {vector:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn non_success_status_is_an_error() {
+        let app = Router::new().route(
+            EMBEDDINGS_PATH,
+            post(|| async { (StatusCode::NOT_FOUND, "model \"missing\" not found") }),
+        );
+        let base = spawn_server(app).await;
+        let embedding = OllamaEmbedding::with_endpoint(&base, "missing", 3);
+
+        let err = embedding.embed_query("text-0").await.unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("404"), "unexpected error: {message}");
+        assert!(message.contains("not found"), "unexpected error: {message}");
+    }
+}
@@ -9,7 +9,22 @@ use crate::domain::{CodeChunk, DomainError, Embedding, EmbeddingConfig};
 
 const DEFAULT_BASE_URL: &str = "http://localhost:1234";
 const EMBEDDINGS_PATH: &str = "/v1/embeddings";
-const BATCH_SIZE: usize = 32;
+const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// Estimated-token budget per `/v1/embeddings` request.  A chunk larger than
+/// the budget is still sent, alone in its own request.
+const MAX_BATCH_TOKENS: usize = 2048;
+
+/// Characters per token for the batch estimate.  Source code tokenizes denser
+/// than prose, so this sits below the usual `chars / 4` rule.
+const CODE_CHARS_PER_TOKEN: f64 = 3.3;
+
+/// Maximum number of retries when the server answers `429 Too Many Requests`.
+const RATE_LIMIT_RETRIES: u32 = 5;
+
+/// Initial backoff for rate-limit retries.  Doubles on each attempt unless the
+/// server sends a `Retry-After` header: 500 ms → 1 s → 2 s → 4 s → 8 s.
+const RATE_LIMIT_RETRY_INITIAL_MS: u64 = 500;
 
 #[derive(Serialize)]
 struct EmbeddingRequest<'a> {
@@ -33,12 +48,19 @@ struct EmbeddingData {
 ///
 /// **Configuration**:
 /// - Base URL: `OPENAI_BASE_URL` env var (default `http://localhost:1234`).
+/// - API key: `OPENAI_API_KEY` env var, sent as a bearer token when non-empty
+///   (hosted endpoints need it; local servers usually don't).
+/// - Batch size: `OPENAI_EMBEDDING_BATCH_SIZE` env var (default 32) — the most
+///   chunks sent per request; a request also stops growing once its estimated
+///   size reaches [`MAX_BATCH_TOKENS`].
 /// - Model name and dimensions: supplied at construction time from `--embedding-model`
 ///   and `--embedding-dimensions` CLI flags; they are stored in `namespace_config`
 ///   and validated on every subsequent open.
 pub struct OpenAiEmbedding {
     client: reqwest::Client,
     url: String,
+    api_key: Option<String>,
+    batch_size: usize,
     config: EmbeddingConfig,
 }
 
@@ -52,12 +74,31 @@ impl OpenAiEmbedding {
     pub fn new(model: impl Into<String>, dimensions: usize) -> Self {
         let base =
             std::env::var("OPENAI_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
-        let url = format!("{}{}", base.trim_end_matches('/'), EMBEDDINGS_PATH);
+        let api_key = std::env::var("OPENAI_API_KEY").ok();
+        Self::with_endpoint(&base, api_key.as_deref(), model, dimensions)
+    }
+
+    /// Build against an explicit endpoint instead of the `OPENAI_*`
+    /// environment, e.g. `https://api.openai.com` with a key, or a local
+    /// Ollama / LM Studio server with none.  An empty key counts as none.
+    pub fn with_endpoint(
+        base_url: &str,
+        api_key: Option<&str>,
+        model: impl Into<String>,
+        dimensions: usize,
+    ) -> Self {
+        let url = format!("{}{}", base_url.trim_end_matches('/'), EMBEDDINGS_PATH);
+        let api_key = api_key.filter(|k| !k.is_empty()).map(str::to_string);
+        let batch_size = std::env::var("OPENAI_EMBEDDING_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n: &usize| n > 0)
+            .unwrap_or(DEFAULT_BATCH_SIZE);
         let model = model.into();
 
         debug!(
-            "OpenAiEmbedding: endpoint={}, model={}, dims={}",
-            url, model, dimensions
+            "OpenAiEmbedding: endpoint={}, model={}, dims={}, batch_size={}",
+            url, model, dimensions, batch_size
         );
 
         Self {
@@ -66,10 +107,56 @@ impl OpenAiEmbedding {
                 .build()
                 .expect("reqwest::Client build failed"),
             url,
+            api_key,
+            batch_size,
             config: EmbeddingConfig::new(model, dimensions, 512),
         }
     }
 
+    /// POST the request, retrying with exponential backoff while the server
+    /// rate-limits us.  A `Retry-After` header (in seconds) overrides the
+    /// computed delay.  Any other status is returned to the caller untouched.
+    async fn send_with_retry(
+        &self,
+        request: &EmbeddingRequest<'_>,
+    ) -> Result<reqwest::Response, DomainError> {
+        let mut delay_ms = RATE_LIMIT_RETRY_INITIAL_MS;
+        for attempt in 0..=RATE_LIMIT_RETRIES {
+            let mut builder = self.client.post(&self.url).json(request);
+            if let Some(key) = &self.api_key {
+                builder = builder.bearer_auth(key);
+            }
+            let response = builder.send().await.map_err(|e| {
+                DomainError::internal(format!("OpenAI embedding request failed: {e}"))
+            })?;
+
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS
+                || attempt == RATE_LIMIT_RETRIES
+            {
+                return Ok(response);
+            }
+
+            let wait_ms = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(|secs| secs * 1000)
+                .unwrap_or(delay_ms);
+            if attempt == 0 {
+                warn!("OpenAiEmbedding: rate limited by the server; backing off…");
+            }
+            debug!(
+                "OpenAiEmbedding: 429 on attempt {}, retrying in {} ms",
+                attempt + 1,
+                wait_ms
+            );
+            tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+            delay_ms *= 2;
+        }
+        unreachable!()
+    }
+
     async fn embed_texts(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, DomainError> {
         if texts.is_empty() {
             return Ok(vec![]);
@@ -81,13 +168,7 @@ impl OpenAiEmbedding {
             input: texts,
         };
 
-        let response = self
-            .client
-            .post(&self.url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| DomainError::internal(format!("OpenAI embedding request failed: {e}")))?;
+        let response = self.send_with_retry(&request).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -107,20 +188,25 @@ impl OpenAiEmbedding {
 
         let expected = self.config.dimensions();
 
+        // A width mismatch would be rejected by the FLOAT[N] column anyway;
+        // fail here with the dimension the model actually returned so the
+        // user knows what to pass to --embedding-dimensions.
+        if let Some(d) = data.iter().find(|d| d.embedding.len() != expected) {
+            return Err(DomainError::embedding(format!(
+                "OpenAI embedding model '{}' returned {}-dimensional vectors, but the \
+                 namespace expects {}. Create the namespace with \
+                 --embedding-dimensions {}.",
+                self.config.model_name(),
+                d.embedding.len(),
+                expected,
+                d.embedding.len()
+            )));
+        }
+
         let embeddings = data
             .into_iter()
             .map(|d| {
                 let mut vec = d.embedding;
-                if vec.len() != expected {
-                    warn!(
-                        "OpenAiEmbedding: model '{}' returned {} dimensions, expected {}. \
-                         Check that the model matches --embedding-model and \
-                         --embedding-dimensions.",
-                        self.config.model_name(),
-                        vec.len(),
-                        expected
-                    );
-                }
                 // L2-normalise so cosine similarity equals dot product.
                 let norm: f32 = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
                 if norm > 0.0 {
@@ -145,11 +231,7 @@ impl OpenAiEmbedding {
 #[async_trait]
 impl EmbeddingService for OpenAiEmbedding {
     async fn embed_chunk(&self, chunk: &CodeChunk) -> Result<Embedding, DomainError> {
-        let text = format!(
-            "{} {}",
-            chunk.qualified_name().as_deref().unwrap_or(""),
-            chunk.content()
-        );
+        let text = chunk.embedding_text();
         let vectors = self.embed_texts(vec![text]).await?;
         Ok(Embedding::new(
             chunk.id().to_string(),
@@ -165,19 +247,12 @@ impl EmbeddingService for OpenAiEmbedding {
 
         let mut all_embeddings = Vec::with_capacity(chunks.len());
 
-        for batch in chunks.chunks(BATCH_SIZE) {
-            let texts: Vec<String> = batch
-                .iter()
-                .map(|c| {
-                    format!(
-                        "{} {}",
-                        c.qualified_name().as_deref().unwrap_or(""),
-                        c.content()
-                    )
-                })
-                .collect();
+        let texts: Vec<String> = chunks.iter().map(CodeChunk::embedding_text).collect();
+        let lengths: Vec<usize> = texts.iter().map(String::len).collect();
 
-            let vectors = self.embed_texts(texts).await?;
+        for range in batch_ranges(&lengths, self.batch_size, MAX_BATCH_TOKENS) {
+            let batch = &chunks[range.clone()];
+            let vectors = self.embed_texts(texts[range].to_vec()).await?;
 
             for (chunk, vector) in batch.iter().zip(vectors) {
                 all_embeddings.push(Embedding::new(
@@ -203,3 +278,53 @@ impl EmbeddingService for OpenAiEmbedding {
         &self.config
     }
 }
+
+/// Split texts (given by their lengths in bytes) into consecutive request
+/// ranges holding at most `max_items` texts and about `max_tokens` estimated
+/// tokens each.  Every range holds at least one text.
+fn batch_ranges(
+    lengths: &[usize],
+    max_items: usize,
+    max_tokens: usize,
+) -> Vec<std::ops::Range<usize>> {
+    let estimate = |len: usize| (len as f64 / CODE_CHARS_PER_TOKEN).ceil() as usize;
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+    for (i, &len) in lengths.iter().enumerate() {
+        let cost = estimate(len);
+        let full = i - start >= max_items || tokens + cost > max_tokens;
+        if i > start && full {
+            ranges.push(start..i);
+            start = i;
+            tokens = 0;
+        }
+        tokens += cost;
+    }
+    if start < lengths.len() {
+        ranges.push(start..lengths.len());
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_ranges_respect_item_and_token_limits() {
+        // 330 bytes ≈ 100 tokens each.
+        assert_eq!(batch_ranges(&[330; 5], 2, 10_000), vec![0..2, 2..4, 4..5]);
+        assert_eq!(batch_ranges(&[330; 5], 32, 250), vec![0..2, 2..4, 4..5]);
+    }
+
+    #[test]
+    fn oversized_text_gets_its_own_batch() {
+        assert_eq!(
+            batch_ranges(&[33, 33_000, 33], 32, 2048),
+            vec![0..1, 1..2, 2..3]
+        );
+        assert!(batch_ranges(&[], 32, 2048).is_empty());
+    }
+}
@@ -14,7 +14,6 @@ use crate::domain::{CodeChunk, DomainError, Embedding, EmbeddingConfig};
 
 use crate::connector::adapter::DEFAULT_ONNX_EMBEDDING_MODEL as DEFAULT_MODEL_ID;
 
-const DEFAULT_DIMENSIONS: usize = 384;
 const DEFAULT_MAX_SEQ_LENGTH: usize = 512;
 /// Number of chunks processed per ONNX inference call.
 ///
@@ -92,14 +91,35 @@ impl OrtEmbedding {
             .iter()
             .any(|i| i.name() == "token_type_ids");
 
-        let config = EmbeddingConfig::new(
-            model_name.to_string(),
-            DEFAULT_DIMENSIONS,
+        // The output width is a property of the model (384 for MiniLM, 768
+        // for mpnet, …), so measure it once with a tiny probe rather than
+        // assuming the default model's size.
+        let session = Mutex::new(session);
+        let dimensions = embed_texts_impl(
+            &session,
+            &tokenizer,
             DEFAULT_MAX_SEQ_LENGTH,
+            needs_token_type_ids,
+            &["dimension probe".to_string()],
+        )?
+        .first()
+        .map(Vec::len)
+        .filter(|&d| d > 0)
+        .ok_or_else(|| {
+            DomainError::embedding(format!(
+                "ONNX model '{model_name}' produced an empty embedding"
+            ))
+        })?;
+        debug!(
+            "ONNX model '{}' outputs {} dimensions",
+            model_name, dimensions
         );
 
+        let config =
+            EmbeddingConfig::new(model_name.to_string(), dimensions, DEFAULT_MAX_SEQ_LENGTH);
+
         Ok(Self {
-            session: Arc::new(Mutex::new(session)),
+            session: Arc::new(session),
             tokenizer: Arc::new(tokenizer),
             config,
             needs_token_type_ids,
@@ -324,11 +344,7 @@ fn embed_texts_impl(
 #[async_trait]
 impl EmbeddingService for OrtEmbedding {
     async fn embed_chunk(&self, chunk: &CodeChunk) -> Result<Embedding, DomainError> {
-        let text = format!(
-            "{} {}",
-            chunk.qualified_name().as_deref().unwrap_or(""),
-            chunk.content()
-        );
+        let text = chunk.embedding_text();
         let session = Arc::clone(&self.session);
         let tokenizer = Arc::clone(&self.tokenizer);
         let max_seq = self.config.max_sequence_length();
@@ -360,16 +376,7 @@ impl EmbeddingService for OrtEmbedding {
         // short and long chunks wastes compute on padding tokens.  Process
         // batches in ascending text-length order (byte length is a good token
         // proxy) and scatter results back into the original chunk order.
-        let mut texts: Vec<String> = chunks
-            .iter()
-            .map(|c| {
-                format!(
-                    "{} {}",
-                    c.qualified_name().as_deref().unwrap_or(""),
-                    c.content()
-                )
-            })
-            .collect();
+        let mut texts: Vec<String> = chunks.iter().map(CodeChunk::embedding_text).collect();
         let mut order: Vec<usize> = (0..texts.len()).collect();
         order.sort_by_key(|&i| texts[i].len());
 
@@ -459,7 +466,9 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(embedding.len(), DEFAULT_DIMENSIONS);
+        // all-MiniLM-L6-v2
+        assert_eq!(embedding.len(), 384);
+        assert_eq!(service.dimensions(), 384);
 
         let norm: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
         assert!(
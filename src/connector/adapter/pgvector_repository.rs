@@ -0,0 +1,918 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Client, NoTls, Row};
+use tracing::{debug, info, warn};
+
+use super::duckdb_vector_repository::{glob_to_like, path_glob_clause};
+use crate::application::{
+    rrf_fuse_hybrid, FileEntry, IndexState, RepositoryChunkStats, StorageIntegrity,
+    VectorRepository,
+};
+use crate::domain::{CodeChunk, DomainError, Embedding, Language, SearchQuery, SearchResult};
+
+/// Chunk columns in the order [`PgVectorRepository::row_to_chunk`] reads them.
+const CHUNK_COLUMNS: &str = "c.id, c.file_path, c.content, c.start_line, c.end_line, \
+     c.language, c.node_type, c.symbol_name, c.parent_symbol, c.repository_id, c.part, \
+     c.is_async, c.doc_comment";
+
+/// Text-search configuration for the `tsv` column. `simple` lower-cases
+/// tokens without stemming, the Postgres counterpart of the DuckDB FTS
+/// index's `stemmer='none'`: code identifiers are matched as written.
+const TS_CONFIG: &str = "simple";
+
+type Params<'a> = Vec<&'a (dyn ToSql + Sync)>;
+
+/// Vector store backed by Postgres with the pgvector extension, selected with
+/// the global `--postgres-url` flag.
+///
+/// Each namespace lives in a Postgres schema of the same name holding one
+/// `chunks` table: the DuckDB chunk columns plus the embedding (a
+/// `vector(<dims>)` column under an HNSW index) and a generated `tsvector`
+/// column under a GIN index, which serves the keyword leg of hybrid search
+/// in place of DuckDB's FTS extension.
+pub struct PgVectorRepository {
+    client: Mutex<Client>,
+    /// User-facing namespace name, used in logs and errors.
+    namespace: String,
+    /// The namespace as a quoted SQL identifier, interpolated into all
+    /// schema-qualified SQL.
+    schema: String,
+    /// Dimensionality of the embedding vectors for this namespace.
+    dimensions: usize,
+    /// Memoized "store holds at least one embedding" fact, as in the DuckDB
+    /// adapter: a `true` result is cached, `false` is re-probed.
+    has_vectors: AtomicBool,
+}
+
+impl PgVectorRepository {
+    /// Connect to `url` and open (or create) the schema for `namespace`.
+    ///
+    /// An existing schema must have been created with the same `dimensions`;
+    /// a mismatch is a hard error, since the `vector` column cannot hold
+    /// embeddings of another size.
+    pub async fn connect(
+        url: &str,
+        namespace: &str,
+        dimensions: usize,
+    ) -> Result<Self, DomainError> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls)
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to connect to Postgres: {e}")))?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                warn!("Postgres connection closed with an error: {e}");
+            }
+        });
+
+        let schema = format!("\"{}\"", namespace.replace('"', "\"\""));
+        let schema_sql = format!(
+            r#"
+            CREATE EXTENSION IF NOT EXISTS vector;
+            CREATE SCHEMA IF NOT EXISTS {schema};
+            CREATE TABLE IF NOT EXISTS {schema}.chunks (
+                id TEXT PRIMARY KEY,
+                file_path TEXT NOT NULL,
+                content TEXT NOT NULL,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                language TEXT NOT NULL,
+                node_type TEXT NOT NULL,
+                symbol_name TEXT,
+                parent_symbol TEXT,
+                repository_id TEXT NOT NULL,
+                part INTEGER,
+                is_async BOOLEAN NOT NULL DEFAULT false,
+                doc_comment TEXT,
+                embedding vector({dimensions}),
+                embedding_model TEXT,
+                tsv tsvector GENERATED ALWAYS AS (
+                    to_tsvector('{TS_CONFIG}', coalesce(symbol_name, '') || ' ' || content)
+                ) STORED
+            );
+            ALTER TABLE {schema}.chunks ADD COLUMN IF NOT EXISTS is_async BOOLEAN NOT NULL DEFAULT false;
+            ALTER TABLE {schema}.chunks ADD COLUMN IF NOT EXISTS doc_comment TEXT;
+            CREATE INDEX IF NOT EXISTS chunks_repository_file_idx
+                ON {schema}.chunks (repository_id, file_path);
+            CREATE INDEX IF NOT EXISTS chunks_symbol_name_idx
+                ON {schema}.chunks (symbol_name);
+            CREATE INDEX IF NOT EXISTS chunks_embedding_hnsw_idx
+                ON {schema}.chunks USING hnsw (embedding vector_cosine_ops);
+            CREATE INDEX IF NOT EXISTS chunks_tsv_idx
+                ON {schema}.chunks USING gin (tsv);
+            "#,
+        );
+        client.batch_execute(&schema_sql).await.map_err(|e| {
+            DomainError::storage(format!("Failed to initialize Postgres schema: {e}"))
+        })?;
+
+        // For a `vector(n)` column the type modifier is `n`.
+        let row = client
+            .query_one(
+                "SELECT a.atttypmod FROM pg_attribute a \
+                 JOIN pg_class t ON t.oid = a.attrelid \
+                 JOIN pg_namespace n ON n.oid = t.relnamespace \
+                 WHERE n.nspname = $1 AND t.relname = 'chunks' AND a.attname = 'embedding'",
+                &[&namespace],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to read vector dimensions: {e}")))?;
+        let stored: i32 = row.get(0);
+        if stored as usize != dimensions {
+            return Err(DomainError::invalid_input(format!(
+                "Postgres schema for namespace '{namespace}' stores {stored}-dimensional \
+                 vectors, but the embedding model produces {dimensions}. Use a different \
+                 namespace or drop the schema to re-index."
+            )));
+        }
+
+        debug!("Postgres schema initialised (namespace={namespace}, dims={dimensions})");
+        Ok(Self {
+            client: Mutex::new(client),
+            namespace: namespace.to_string(),
+            schema,
+            dimensions,
+            has_vectors: AtomicBool::new(false),
+        })
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// pgvector's text form (`[0.1,0.2,…]`), bound as a text parameter and
+    /// cast with `::text::vector` so no pgvector client type is needed.
+    fn vector_literal(&self, vector: &[f32]) -> Result<String, DomainError> {
+        if vector.len() != self.dimensions {
+            return Err(DomainError::invalid_input(format!(
+                "Embedding has {} dimensions but namespace '{}' is locked to \
+                 {}-dimensional vectors from the model it was created with",
+                vector.len(),
+                self.namespace,
+                self.dimensions
+            )));
+        }
+        let values: Vec<String> = vector.iter().map(|v| v.to_string()).collect();
+        Ok(format!("[{}]", values.join(",")))
+    }
+
+    /// SQL `c.<column> IN (...)` clauses for the optional query filters,
+    /// shared by both search legs and the symbol lookup.
+    fn filter_clauses(query: &SearchQuery) -> Vec<String> {
+        let quote_list = |values: &[String]| {
+            values
+                .iter()
+                .map(|v| format!("'{}'", v.replace('\'', "''")))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let mut clauses = Vec::new();
+        if let Some(languages) = query.languages() {
+            clauses.push(format!("c.language IN ({})", quote_list(languages)));
+        }
+        if let Some(node_types) = query.node_types() {
+            clauses.push(format!("c.node_type IN ({})", quote_list(node_types)));
+        }
+        if query.is_async_only() {
+            clauses.push("c.is_async".to_string());
+        }
+        if let Some(repo_ids) = query.repository_ids() {
+            clauses.push(format!("c.repository_id IN ({})", quote_list(repo_ids)));
+        }
+        clauses.extend(path_glob_clause("c.file_path", query));
+        if let Some(pattern) = query.symbol_pattern() {
+            clauses.push(format!("c.symbol_name ~ '{}'", pattern.replace('\'', "''")));
+        }
+        clauses
+    }
+
+    fn row_to_chunk(row: &Row) -> CodeChunk {
+        let chunk = CodeChunk::reconstitute(
+            row.get::<_, String>(0),
+            row.get::<_, String>(1),
+            row.get::<_, String>(2),
+            u32::try_from(row.get::<_, i32>(3)).unwrap_or(0),
+            u32::try_from(row.get::<_, i32>(4)).unwrap_or(0),
+            Language::parse(&row.get::<_, String>(5)),
+            crate::domain::NodeType::parse(&row.get::<_, String>(6)),
+            row.get::<_, Option<String>>(7),
+            row.get::<_, Option<String>>(8),
+            row.get::<_, String>(9),
+        )
+        .with_async(row.get::<_, bool>(11));
+        let chunk = match row.get::<_, Option<i32>>(10) {
+            Some(part) => chunk.with_part(part as u32),
+            None => chunk,
+        };
+        match row.get::<_, Option<String>>(12) {
+            Some(doc) => chunk.with_doc_comment(doc),
+            None => chunk,
+        }
+    }
+
+    /// Nearest neighbours by cosine similarity. Unpaginated queries order by
+    /// the bare distance so the HNSW index serves them; a paginated query
+    /// needs the chunk-id tiebreak and a keyset condition, and scans instead.
+    async fn run_semantic(
+        client: &Client,
+        schema: &str,
+        vector: &str,
+        query: &SearchQuery,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        let cursor = query.cursor()?;
+        let limit = query.limit() as i64;
+        let score_expr = "(1 - (c.embedding <=> $1::text::vector))::real";
+
+        let mut params: Params = vec![&vector, &limit];
+        let mut where_clauses = Self::filter_clauses(query);
+        where_clauses.push("c.embedding IS NOT NULL".to_string());
+        let (cursor_score, cursor_id) = match &cursor {
+            Some(cursor) => (cursor.score(), cursor.chunk_id().to_string()),
+            None => (0.0, String::new()),
+        };
+        let order = if query.is_paginated() {
+            if cursor.is_some() {
+                where_clauses.push(format!(
+                    "({score_expr} < $3 OR ({score_expr} = $3 AND c.id > $4))"
+                ));
+                params.push(&cursor_score);
+                params.push(&cursor_id);
+            }
+            "score DESC, c.id"
+        } else {
+            "c.embedding <=> $1::text::vector"
+        };
+
+        let sql = format!(
+            "SELECT {CHUNK_COLUMNS}, {score_expr} AS score \
+             FROM {schema}.chunks c \
+             WHERE {} \
+             ORDER BY {order} \
+             LIMIT $2",
+            where_clauses.join(" AND ")
+        );
+        let rows = client
+            .query(&sql, &params)
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run semantic search: {e}")))?;
+
+        let mut results = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let score: f32 = row.get(13);
+            // In hybrid mode the full candidate pool feeds rrf_fuse; apply
+            // min_score after fusion instead of dropping candidates here.
+            if !query.is_text_search() && query.min_score().is_some_and(|min| score < min) {
+                continue;
+            }
+            results.push(SearchResult::new(Self::row_to_chunk(row), score).with_semantic_leg());
+        }
+        Ok(results)
+    }
+
+    /// Keyword search over the `tsv` column, ranked with `ts_rank`.
+    ///
+    /// The query's tokens are OR-ed together rather than AND-ed (as
+    /// `plainto_tsquery` would), so a chunk matching any term is a candidate
+    /// and ranking decides, as with BM25 in the DuckDB adapter.
+    async fn run_text(
+        client: &Client,
+        schema: &str,
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        let query_str = query.query().trim().to_string();
+        if query_str.is_empty() {
+            return Ok(vec![]);
+        }
+        let limit = limit as i64;
+
+        let mut where_clauses = Self::filter_clauses(query);
+        where_clauses.push("c.tsv @@ q.query".to_string());
+        let sql = format!(
+            "SELECT {CHUNK_COLUMNS}, ts_rank(c.tsv, q.query)::real AS score \
+             FROM {schema}.chunks c, \
+                  (SELECT to_tsquery('{TS_CONFIG}', array_to_string(ARRAY( \
+                      SELECT quote_literal(lexeme) \
+                      FROM unnest(tsvector_to_array(to_tsvector('{TS_CONFIG}', $1))) lexeme \
+                  ), ' | ')) AS query) q \
+             WHERE {} \
+             ORDER BY score DESC, c.id \
+             LIMIT $2",
+            where_clauses.join(" AND ")
+        );
+        let rows = client
+            .query(&sql, &[&query_str, &limit])
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run text search: {e}")))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                SearchResult::new(Self::row_to_chunk(row), row.get(13)).with_text_leg(&query_str)
+            })
+            .collect())
+    }
+
+    async fn query_chunks(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+        what: &str,
+    ) -> Result<Vec<CodeChunk>, DomainError> {
+        let client = self.client.lock().await;
+        let rows = client
+            .query(sql, params)
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run {what}: {e}")))?;
+        Ok(rows.iter().map(Self::row_to_chunk).collect())
+    }
+}
+
+#[async_trait]
+impl VectorRepository for PgVectorRepository {
+    async fn save_batch(
+        &self,
+        chunks: &[CodeChunk],
+        embeddings: &[Embedding],
+    ) -> Result<(), DomainError> {
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        // An empty embeddings slice is a chunks-only save (--no-embeddings
+        // mode); any other length mismatch is a caller bug.
+        if !embeddings.is_empty() && chunks.len() != embeddings.len() {
+            return Err(DomainError::invalid_input(
+                "Chunk and embedding count mismatch".to_string(),
+            ));
+        }
+        let mut vectors: HashMap<&str, (String, &str)> = HashMap::new();
+        for embedding in embeddings {
+            vectors.insert(
+                embedding.chunk_id(),
+                (self.vector_literal(embedding.vector())?, embedding.model()),
+            );
+        }
+
+        let mut client = self.client.lock().await;
+        let tx = client
+            .transaction()
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to begin transaction: {e}")))?;
+        // A chunks-only save keeps whatever embedding the row already had.
+        let stmt = tx
+            .prepare(&format!(
+                "INSERT INTO {}.chunks \
+                 (id, file_path, content, start_line, end_line, language, node_type, \
+                  symbol_name, parent_symbol, repository_id, part, is_async, doc_comment, \
+                  embedding, embedding_model) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14::text::vector, $15) \
+                 ON CONFLICT (id) DO UPDATE SET \
+                    file_path = EXCLUDED.file_path, content = EXCLUDED.content, \
+                    start_line = EXCLUDED.start_line, end_line = EXCLUDED.end_line, \
+                    language = EXCLUDED.language, node_type = EXCLUDED.node_type, \
+                    symbol_name = EXCLUDED.symbol_name, \
+                    parent_symbol = EXCLUDED.parent_symbol, \
+                    repository_id = EXCLUDED.repository_id, part = EXCLUDED.part, \
+                    is_async = EXCLUDED.is_async, doc_comment = EXCLUDED.doc_comment, \
+                    embedding = COALESCE(EXCLUDED.embedding, chunks.embedding), \
+                    embedding_model = COALESCE(EXCLUDED.embedding_model, chunks.embedding_model)",
+                self.schema
+            ))
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to prepare chunk insert: {e}")))?;
+
+        for chunk in chunks {
+            let (vector, model) = match vectors.get(chunk.id()) {
+                Some((vector, model)) => (Some(vector.as_str()), Some(*model)),
+                None => (None, None),
+            };
+            tx.execute(
+                &stmt,
+                &[
+                    &chunk.id(),
+                    &chunk.file_path(),
+                    &chunk.content(),
+                    &(chunk.start_line() as i32),
+                    &(chunk.end_line() as i32),
+                    &chunk.language().as_str(),
+                    &chunk.node_type().as_str(),
+                    &chunk.symbol_name(),
+                    &chunk.parent_symbol(),
+                    &chunk.repository_id(),
+                    &chunk.part().map(|p| p as i32),
+                    &chunk.is_async(),
+                    &chunk.doc_comment(),
+                    &vector,
+                    &model,
+                ],
+            )
+            .await
+            .map_err(|e| {
+                DomainError::storage(format!("Failed to insert chunk {}: {e}", chunk.id()))
+            })?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to commit: {e}")))?;
+        if !embeddings.is_empty() {
+            self.has_vectors.store(true, Ordering::Release);
+        }
+
+        debug!(
+            "Saved {} chunks and {} embeddings to Postgres",
+            chunks.len(),
+            embeddings.len()
+        );
+        Ok(())
+    }
+
+    async fn delete(&self, chunk_id: &str) -> Result<(), DomainError> {
+        let client = self.client.lock().await;
+        client
+            .execute(
+                &format!("DELETE FROM {}.chunks WHERE id = $1", self.schema),
+                &[&chunk_id],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to delete chunk: {e}")))?;
+        Ok(())
+    }
+
+    async fn bulk_delete(&self, chunk_ids: &[&str]) -> Result<u64, DomainError> {
+        if chunk_ids.is_empty() {
+            return Ok(0);
+        }
+        let client = self.client.lock().await;
+        let deleted = client
+            .execute(
+                &format!("DELETE FROM {}.chunks WHERE id = ANY($1)", self.schema),
+                &[&chunk_ids],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to delete chunks: {e}")))?;
+
+        debug!("Bulk-deleted {} of {} chunks", deleted, chunk_ids.len());
+        Ok(deleted)
+    }
+
+    async fn delete_by_repository(&self, repository_id: &str) -> Result<(), DomainError> {
+        let client = self.client.lock().await;
+        client
+            .execute(
+                &format!(
+                    "DELETE FROM {}.chunks WHERE repository_id = $1",
+                    self.schema
+                ),
+                &[&repository_id],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to delete chunks: {e}")))?;
+        Ok(())
+    }
+
+    async fn delete_by_file_path(
+        &self,
+        repository_id: &str,
+        file_path: &str,
+    ) -> Result<u64, DomainError> {
+        self.delete_by_file_paths(repository_id, &[file_path]).await
+    }
+
+    async fn delete_by_file_paths(
+        &self,
+        repository_id: &str,
+        file_paths: &[&str],
+    ) -> Result<u64, DomainError> {
+        if file_paths.is_empty() {
+            return Ok(0);
+        }
+        let client = self.client.lock().await;
+        let deleted = client
+            .execute(
+                &format!(
+                    "DELETE FROM {}.chunks WHERE repository_id = $1 AND file_path = ANY($2)",
+                    self.schema
+                ),
+                &[&repository_id, &file_paths],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to delete chunks: {e}")))?;
+
+        debug!(
+            "Deleted {} chunks for {} files in repository {}",
+            deleted,
+            file_paths.len(),
+            repository_id
+        );
+        Ok(deleted)
+    }
+
+    #[tracing::instrument(
+        name = "postgres.search",
+        skip_all,
+        fields(
+            namespace = %self.namespace,
+            semantic = query_embedding.is_some(),
+            text_search = query.is_text_search(),
+            limit = query.limit(),
+            result_count = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        )
+    )]
+    async fn search(
+        &self,
+        query_embedding: Option<&[f32]>,
+        query: &SearchQuery,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        let started = Instant::now();
+        let vector = query_embedding
+            .map(|embedding| self.vector_literal(embedding))
+            .transpose()?;
+
+        let client = self.client.lock().await;
+        // `None` requests a text-only search (no embeddings indexed); the
+        // semantic leg is skipped entirely.
+        let semantic = match &vector {
+            None => Vec::new(),
+            Some(vector) => Self::run_semantic(&client, &self.schema, vector, query).await?,
+        };
+
+        let results = if !query.is_text_search() && vector.is_some() {
+            semantic
+        } else {
+            // With no semantic candidates the keyword leg is the only source
+            // of results, so it must honour the full requested limit.
+            let text_fetch_limit = if semantic.is_empty() {
+                query.limit().max(query.text_candidates())
+            } else {
+                query.text_candidates()
+            };
+            match Self::run_text(&client, &self.schema, query, text_fetch_limit).await {
+                Ok(text) => {
+                    let (semantic_len, text_len) = (semantic.len(), text.len());
+                    let mut fused = rrf_fuse_hybrid(semantic, text, query);
+                    info!(
+                        "Hybrid search: {} semantic + {} full-text candidates → {} after fusion",
+                        semantic_len,
+                        text_len,
+                        fused.len()
+                    );
+                    if let Some(min) = query.min_score() {
+                        fused.retain(|r| r.score() >= min);
+                    }
+                    fused
+                }
+                Err(e) => {
+                    warn!(
+                        "Full-text search failed (falling back to semantic-only): {}",
+                        e
+                    );
+                    semantic
+                }
+            }
+        };
+
+        let span = tracing::Span::current();
+        span.record("result_count", results.len());
+        span.record("latency_ms", started.elapsed().as_millis() as u64);
+        Ok(results)
+    }
+
+    async fn has_embeddings(&self) -> Result<bool, DomainError> {
+        if self.has_vectors.load(Ordering::Acquire) {
+            return Ok(true);
+        }
+        let client = self.client.lock().await;
+        let exists: bool = client
+            .query_one(
+                &format!(
+                    "SELECT EXISTS(SELECT 1 FROM {}.chunks WHERE embedding IS NOT NULL)",
+                    self.schema
+                ),
+                &[],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to probe embeddings: {e}")))?
+            .get(0);
+        if exists {
+            self.has_vectors.store(true, Ordering::Release);
+        }
+        Ok(exists)
+    }
+
+    async fn fetch_embeddings(&self, ids: &[&str]) -> Result<Vec<Embedding>, DomainError> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let client = self.client.lock().await;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT id, embedding::text, embedding_model FROM {}.chunks \
+                     WHERE id = ANY($1) AND embedding IS NOT NULL",
+                    self.schema
+                ),
+                &[&ids],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run embedding fetch: {e}")))?;
+
+        let mut embeddings = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let chunk_id: String = row.get(0);
+            let text: String = row.get(1);
+            let vector = text
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .map(|v| v.trim().parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    DomainError::storage(format!("Failed to parse embedding for {chunk_id}: {e}"))
+                })?;
+            let model: Option<String> = row.get(2);
+            embeddings.push(Embedding::new(chunk_id, vector, model.unwrap_or_default()));
+        }
+        Ok(embeddings)
+    }
+
+    async fn count(&self) -> Result<u64, DomainError> {
+        let client = self.client.lock().await;
+        let count: i64 = client
+            .query_one(&format!("SELECT COUNT(*) FROM {}.chunks", self.schema), &[])
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to count chunks: {e}")))?
+            .get(0);
+        Ok(count as u64)
+    }
+
+    async fn find_chunks_by_file(
+        &self,
+        repository_id: &str,
+        file_path: &str,
+    ) -> Result<Vec<CodeChunk>, DomainError> {
+        // An empty repository id searches every repository.
+        let sql = format!(
+            "SELECT {CHUNK_COLUMNS} FROM {}.chunks c \
+             WHERE c.file_path = $1 AND ($2 = '' OR c.repository_id = $2) \
+             ORDER BY c.start_line",
+            self.schema
+        );
+        self.query_chunks(&sql, &[&file_path, &repository_id], "file lookup")
+            .await
+    }
+
+    async fn find_chunk_by_symbol(
+        &self,
+        repository_id: &str,
+        symbol: &str,
+        class_hint: Option<&str>,
+    ) -> Result<Option<CodeChunk>, DomainError> {
+        // Chunks in a file named after the class hint first, then the
+        // tightest scope; a symbol split into parts is represented by its
+        // first part.
+        let file_hint_pattern = class_hint.map(|h| format!("%{}%", h));
+        let sql = format!(
+            "SELECT {CHUNK_COLUMNS} FROM {}.chunks c \
+             WHERE c.symbol_name = $1 AND ($2 = '' OR c.repository_id = $2) \
+             ORDER BY CASE WHEN c.file_path LIKE $3 THEN 0 ELSE 1 END, \
+                COALESCE(c.part, 1), (c.end_line - c.start_line) \
+             LIMIT 1",
+            self.schema
+        );
+        Ok(self
+            .query_chunks(
+                &sql,
+                &[&symbol, &repository_id, &file_hint_pattern],
+                "symbol lookup",
+            )
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    async fn find_chunks_by_symbols(
+        &self,
+        repository_id: &str,
+        symbols: &[&str],
+    ) -> Result<Vec<CodeChunk>, DomainError> {
+        if symbols.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = format!(
+            "SELECT {CHUNK_COLUMNS} FROM {}.chunks c \
+             WHERE c.symbol_name = ANY($1) AND ($2 = '' OR c.repository_id = $2)",
+            self.schema
+        );
+        self.query_chunks(&sql, &[&symbols, &repository_id], "batch symbol lookup")
+            .await
+    }
+
+    async fn find_by_symbol(&self, query: &SearchQuery) -> Result<Vec<SearchResult>, DomainError> {
+        let name = query.query();
+        if name.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut where_clauses = Self::filter_clauses(query);
+        where_clauses.push("(c.symbol_name = $1 OR c.symbol_name LIKE $2 ESCAPE '\\')".to_string());
+        // Shorter names are closer prefix matches; a split symbol lists its
+        // parts in order.
+        let sql = format!(
+            "SELECT {CHUNK_COLUMNS}, \
+                (CASE WHEN c.symbol_name = $1 THEN 1.0 ELSE 0.5 END)::real AS score \
+             FROM {}.chunks c \
+             WHERE {} \
+             ORDER BY score DESC, length(c.symbol_name), c.file_path, c.start_line, \
+                COALESCE(c.part, 1) \
+             LIMIT $3",
+            self.schema,
+            where_clauses.join(" AND ")
+        );
+        let prefix = glob_to_like(&format!("{name}*"));
+        let limit = query.limit() as i64;
+
+        let client = self.client.lock().await;
+        let rows = client
+            .query(&sql, &[&name, &prefix, &limit])
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run symbol search: {e}")))?;
+        Ok(rows
+            .iter()
+            .map(|row| SearchResult::new(Self::row_to_chunk(row), row.get(13)))
+            .collect())
+    }
+
+    async fn get_symbol_to_file_map(
+        &self,
+        repository_id: &str,
+    ) -> Result<Vec<(String, String)>, DomainError> {
+        let client = self.client.lock().await;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT symbol_name, file_path FROM {}.chunks \
+                     WHERE repository_id = $1 AND symbol_name IS NOT NULL",
+                    self.schema
+                ),
+                &[&repository_id],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run symbol map query: {e}")))?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    async fn list_files(&self, repository_id: &str) -> Result<Vec<FileEntry>, DomainError> {
+        let client = self.client.lock().await;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT file_path, COUNT(*), language FROM {}.chunks \
+                     WHERE repository_id = $1 \
+                     GROUP BY file_path, language \
+                     ORDER BY file_path, language",
+                    self.schema
+                ),
+                &[&repository_id],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run file listing: {e}")))?;
+        Ok(rows
+            .iter()
+            .map(|row| FileEntry {
+                file_path: row.get(0),
+                chunk_count: row.get::<_, i64>(1) as u64,
+                language: Language::parse(&row.get::<_, String>(2)),
+            })
+            .collect())
+    }
+
+    async fn stats_by_repository(
+        &self,
+    ) -> Result<HashMap<String, RepositoryChunkStats>, DomainError> {
+        let client = self.client.lock().await;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT repository_id, node_type, language, COUNT(*) FROM {}.chunks \
+                     GROUP BY repository_id, node_type, language",
+                    self.schema
+                ),
+                &[],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run chunk stats: {e}")))?;
+        let mut stats: HashMap<String, RepositoryChunkStats> = HashMap::new();
+        for row in &rows {
+            let count = row.get::<_, i64>(3) as u64;
+            let entry = stats.entry(row.get(0)).or_default();
+            *entry.by_node_type.entry(row.get(1)).or_default() += count;
+            *entry.by_language.entry(row.get(2)).or_default() += count;
+        }
+        Ok(stats)
+    }
+
+    async fn count_by_language(
+        &self,
+        repository_id: Option<&str>,
+    ) -> Result<HashMap<Language, u64>, DomainError> {
+        let client = self.client.lock().await;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT language, COUNT(*) FROM {}.chunks \
+                     WHERE $1::TEXT IS NULL OR repository_id = $1 \
+                     GROUP BY language",
+                    self.schema
+                ),
+                &[&repository_id],
+            )
+            .await
+            .map_err(|e| {
+                DomainError::storage(format!("Failed to count chunks by language: {e}"))
+            })?;
+        let mut counts: HashMap<Language, u64> = HashMap::new();
+        for row in &rows {
+            *counts
+                .entry(Language::parse(&row.get::<_, String>(0)))
+                .or_default() += row.get::<_, i64>(1) as u64;
+        }
+        Ok(counts)
+    }
+
+    async fn integrity(&self) -> Result<Option<StorageIntegrity>, DomainError> {
+        let client = self.client.lock().await;
+        let schema = &self.schema;
+        let row = client
+            .query_one(
+                &format!(
+                    "SELECT COUNT(*), COUNT(embedding), \
+                        (SELECT COUNT(*) FROM pg_indexes \
+                         WHERE schemaname = $1 AND indexname = 'chunks_tsv_idx'), \
+                        (SELECT COUNT(*) FROM pg_indexes \
+                         WHERE schemaname = $1 AND indexname = 'chunks_embedding_hnsw_idx') \
+                     FROM {schema}.chunks"
+                ),
+                &[&self.namespace],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to run integrity check: {e}")))?;
+        let chunk_count = row.get::<_, i64>(0) as u64;
+        let embedding_count = row.get::<_, i64>(1) as u64;
+        let index_state = |i| match row.get::<_, i64>(i) {
+            0 => IndexState::Missing,
+            _ => IndexState::Ok,
+        };
+
+        let chunks_per_repository = client
+            .query(
+                &format!(
+                    "SELECT repository_id, COUNT(*) FROM {schema}.chunks GROUP BY repository_id"
+                ),
+                &[],
+            )
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to tally chunks: {e}")))?
+            .iter()
+            .map(|row| (row.get(0), row.get::<_, i64>(1) as u64))
+            .collect();
+
+        // Embeddings live on the chunk row, so none can be orphaned; the
+        // generated `tsv` column can never be stale.
+        Ok(Some(StorageIntegrity {
+            chunk_count,
+            embedding_count,
+            chunks_without_embedding: chunk_count - embedding_count,
+            orphan_embeddings: 0,
+            text_index: index_state(2),
+            vector_index: index_state(3),
+            chunks_per_repository,
+        }))
+    }
+
+    /// Refresh the planner statistics after a batch of writes. The indexes
+    /// themselves are maintained by Postgres on every write.
+    async fn flush(&self) -> Result<(), DomainError> {
+        let client = self.client.lock().await;
+        // Like the DuckDB FTS rebuild, a failure here must not abort an
+        // indexing run whose data is already committed.
+        match client
+            .batch_execute(&format!("ANALYZE {}.chunks", self.schema))
+            .await
+        {
+            Ok(()) => info!(
+                "Analyzed Postgres chunks for namespace '{}'",
+                self.namespace
+            ),
+            Err(e) => warn!(
+                "Failed to analyze Postgres chunks for namespace '{}': {}",
+                self.namespace, e
+            ),
+        }
+        Ok(())
+    }
+}
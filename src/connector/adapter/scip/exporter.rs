@@ -0,0 +1,210 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use protobuf::{Message as ProtobufMessage, MessageField};
+use scip::types::symbol_information::Kind as SymbolKind;
+use scip::types::{
+    Document, Index, Metadata, Occurrence, ProtocolVersion, SymbolInformation, SymbolRole,
+    TextEncoding, ToolInfo,
+};
+
+use crate::domain::{Language, ReferenceKind, SymbolReference};
+
+/// Scheme of every symbol the exporter writes (`codesearch . <package> . <descriptor>`).
+const SYMBOL_SCHEME: &str = "codesearch";
+
+/// Writes references from the call graph as a SCIP [`Index`], so the graph
+/// codesearch builds with tree-sitter can feed Sourcegraph-compatible tools.
+///
+/// Every reference becomes an occurrence in the document of its caller file,
+/// and every caller a definition occurrence on the line of its first
+/// reference — the call graph does not keep definition sites, and SCIP
+/// consumers (including [`ScipImporter`](super::ScipImporter)) find a
+/// reference's enclosing function as the closest definition above it.
+///
+/// [`ReferenceKind`]s map onto SCIP symbol kinds, roles and descriptor
+/// suffixes:
+///
+/// | Reference kind | Descriptor | Kind / role |
+/// |---|---|---|
+/// | `call` | `name().` | `Function` |
+/// | `method_call`, `override` | `Type#name().` | `Method` |
+/// | `instantiation` | `Type().` | `Constructor` |
+/// | `type_reference`, `implementation`, `inheritance`, `generic_argument` | `Type#` | `Type` |
+/// | `import` | `name/` | `Import` role |
+/// | `variable_reference`, `field_access` | `name.` | `Variable` / `Field`, `ReadAccess` role |
+/// | `macro_invocation` | `name!` | `Macro` |
+///
+/// SCIP has no kind for implementation, inheritance, overrides or generic
+/// arguments, so importing the index reads those back as type references
+/// (overrides as method calls), field accesses as variable references and
+/// macro invocations as unknown.
+pub struct ScipExporter;
+
+impl ScipExporter {
+    /// Build the index for `references`; `project_root` is the repository's
+    /// absolute path.
+    pub fn build(references: &[SymbolReference], project_root: &str) -> Index {
+        let mut by_file: BTreeMap<&str, Vec<&SymbolReference>> = BTreeMap::new();
+        for reference in references {
+            by_file
+                .entry(reference.caller_file_path())
+                .or_default()
+                .push(reference);
+        }
+
+        Index {
+            metadata: MessageField::some(Metadata {
+                version: ProtocolVersion::UnspecifiedProtocolVersion.into(),
+                tool_info: MessageField::some(ToolInfo {
+                    name: "codesearch".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    ..Default::default()
+                }),
+                project_root: format!("file://{}", project_root),
+                text_document_encoding: TextEncoding::UTF8.into(),
+                ..Default::default()
+            }),
+            documents: by_file
+                .into_iter()
+                .map(|(path, refs)| build_document(path, &refs))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Build the index for `references` and write it to `out`.
+    pub async fn write(
+        references: &[SymbolReference],
+        project_root: &str,
+        out: &Path,
+    ) -> Result<()> {
+        let bytes = Self::build(references, project_root)
+            .write_to_bytes()
+            .context("failed to encode SCIP index")?;
+        tokio::fs::write(out, bytes)
+            .await
+            .with_context(|| format!("failed to write SCIP index to {:?}", out))
+    }
+}
+
+/// One document: a definition occurrence per caller, then the references in
+/// source order, with a symbol entry for each symbol they name.
+fn build_document(path: &str, references: &[&SymbolReference]) -> Document {
+    let language = references
+        .first()
+        .map(|r| r.language())
+        .unwrap_or(Language::Unknown);
+
+    let mut symbols: Vec<SymbolInformation> = Vec::new();
+    let mut declared: HashSet<String> = HashSet::new();
+    // One entry per symbol: the first kind a symbol is used with wins.
+    let mut declare = |symbol: &str, kind: SymbolKind| {
+        if declared.insert(symbol.to_string()) {
+            symbols.push(SymbolInformation {
+                symbol: symbol.to_string(),
+                kind: kind.into(),
+                ..Default::default()
+            });
+        }
+    };
+
+    // The first reference of each caller stands in for its definition line.
+    let mut caller_lines: BTreeMap<&str, u32> = BTreeMap::new();
+    for reference in references {
+        if let Some(caller) = reference.caller_symbol() {
+            let line = caller_lines.entry(caller).or_insert(u32::MAX);
+            *line = (*line).min(reference.reference_line());
+        }
+    }
+
+    let mut occurrences: Vec<Occurrence> = Vec::new();
+    for (caller, line) in caller_lines {
+        let name = descriptor_name(caller, language);
+        let kind = if name.contains('#') {
+            SymbolKind::Method
+        } else {
+            SymbolKind::Function
+        };
+        let symbol = scip_symbol(None, &format!("{name}()."));
+        declare(&symbol, kind);
+        occurrences.push(Occurrence {
+            range: vec![line.saturating_sub(1) as i32, 0, 0],
+            symbol,
+            symbol_roles: SymbolRole::Definition as i32,
+            ..Default::default()
+        });
+    }
+
+    let mut sorted: Vec<&SymbolReference> = references.to_vec();
+    sorted.sort_by_key(|r| (r.reference_line(), r.reference_column()));
+    for reference in sorted {
+        let (suffix, kind, role) = scip_shape(reference.reference_kind());
+        let name = descriptor_name(reference.callee_symbol(), language);
+        let symbol = scip_symbol(reference.callee_package(), &format!("{name}{suffix}"));
+        declare(&symbol, kind);
+
+        let line = reference.reference_line().saturating_sub(1) as i32;
+        let column = reference.reference_column().saturating_sub(1) as i32;
+        let width = reference
+            .callee_symbol()
+            .rsplit([':', '#', '.', '\\', '/'])
+            .next()
+            .map_or(0, str::len) as i32;
+        occurrences.push(Occurrence {
+            range: vec![line, column, column + width],
+            symbol,
+            symbol_roles: role,
+            ..Default::default()
+        });
+    }
+
+    Document {
+        language: language.as_str().to_string(),
+        relative_path: path.to_string(),
+        occurrences,
+        symbols,
+        ..Default::default()
+    }
+}
+
+/// `codesearch . <package> . <descriptor>`, with `.` standing for no package.
+fn scip_symbol(package: Option<&str>, descriptor: &str) -> String {
+    format!(
+        "{} . {} . {}",
+        SYMBOL_SCHEME,
+        package.unwrap_or("."),
+        descriptor
+    )
+}
+
+/// A stored symbol name in SCIP descriptor syntax: Rust/C++ `::` becomes the
+/// `#` type separator and PHP's `\` namespaces become `/`, the inverse of
+/// what the importer does to rust-analyzer and scip-php symbols.
+fn descriptor_name(symbol: &str, language: Language) -> String {
+    match language {
+        Language::Php => symbol.replace('\\', "/"),
+        _ => symbol.replace("::", "#"),
+    }
+}
+
+/// Descriptor suffix, symbol kind and occurrence role for a reference kind.
+fn scip_shape(kind: ReferenceKind) -> (&'static str, SymbolKind, i32) {
+    match kind {
+        ReferenceKind::Call => ("().", SymbolKind::Function, 0),
+        ReferenceKind::MethodCall | ReferenceKind::Override => ("().", SymbolKind::Method, 0),
+        ReferenceKind::Instantiation => ("().", SymbolKind::Constructor, 0),
+        ReferenceKind::TypeReference
+        | ReferenceKind::Implementation
+        | ReferenceKind::Inheritance
+        | ReferenceKind::GenericArgument => ("#", SymbolKind::Type, 0),
+        ReferenceKind::Import => ("/", SymbolKind::Namespace, SymbolRole::Import as i32),
+        ReferenceKind::VariableReference => {
+            (".", SymbolKind::Variable, SymbolRole::ReadAccess as i32)
+        }
+        ReferenceKind::FieldAccess => (".", SymbolKind::Field, SymbolRole::ReadAccess as i32),
+        ReferenceKind::MacroInvocation => ("!", SymbolKind::Macro, 0),
+        ReferenceKind::Unknown => (".", SymbolKind::UnspecifiedKind, 0),
+    }
+}
@@ -58,7 +58,13 @@ impl ScipImporter {
                 let language = scip_language_to_domain(&doc.language, &doc.relative_path);
                 if !matches!(
                     language,
-                    Language::JavaScript | Language::TypeScript | Language::Php
+                    Language::JavaScript
+                        | Language::TypeScript
+                        | Language::Php
+                        | Language::Java
+                        | Language::CSharp
+                        | Language::Scala
+                        | Language::Rust
                 ) {
                     continue;
                 }
@@ -104,7 +110,13 @@ fn build_definition_file_map(index: &scip::types::Index) -> HashMap<String, Stri
         let language = scip_language_to_domain(&doc.language, &doc.relative_path);
         if !matches!(
             language,
-            Language::JavaScript | Language::TypeScript | Language::Php
+            Language::JavaScript
+                | Language::TypeScript
+                | Language::Php
+                | Language::Java
+                | Language::CSharp
+                | Language::Scala
+                | Language::Rust
         ) {
             continue;
         }
@@ -169,10 +181,19 @@ fn process_document(
                 )
                 && !occ.range.is_empty()
         })
-        .map(|occ| ScopeDef {
-            line: occ.range[0] as u32,
-            symbol: normalize_symbol(&occ.symbol, language),
-            enclosing_scope: extract_enclosing_scope(&occ.symbol),
+        .map(|occ| {
+            let symbol = normalize_symbol(&occ.symbol, language);
+            // rust-analyzer's scope is the `impl` type, which normalisation
+            // has already moved in front of the `::`.
+            let enclosing_scope = match language {
+                Language::Rust => symbol.rsplit_once("::").map(|(scope, _)| scope.to_string()),
+                _ => extract_enclosing_scope(&occ.symbol),
+            };
+            ScopeDef {
+                line: occ.range[0] as u32,
+                symbol,
+                enclosing_scope,
+            }
         })
         .collect();
     scope_defs.sort_by_key(|s| s.line);
@@ -308,6 +329,9 @@ fn find_enclosing_scope(scope_defs: &[ScopeDef], line: u32) -> Option<ScopeDef>
 /// scip-php composer pkg dev Acme/Autoloader#myMethod().
 ///   → Acme\Autoloader#myMethod
 ///
+/// rust-analyzer cargo geometry 0.1.0 shapes/impl#[Circle][Shape]area().
+///   → Circle::area
+///
 /// local 42
 ///   → (empty — local symbols are filtered out by the caller)
 /// ```
@@ -360,6 +384,10 @@ fn normalize_symbol(symbol: &str, language: Language) -> String {
     // Remove backtick escaping used for identifiers with special characters.
     let unescaped = cleaned.replace('`', "");
 
+    if language == Language::Rust {
+        return normalize_rust_descriptor(&unescaped);
+    }
+
     if language == Language::Php {
         // PHP SCIP symbols use `/` as the namespace descriptor suffix, but PHP
         // developers expect `\` as the namespace separator. Convert back so
@@ -377,6 +405,33 @@ fn normalize_symbol(symbol: &str, language: Language) -> String {
     }
 }
 
+/// Turn a rust-analyzer descriptor into the `Type::member` names the
+/// tree-sitter chunker gives Rust items.
+///
+/// rust-analyzer prefixes every item with its module path
+/// (`shapes/Circle#radius`) and files methods under their `impl` block:
+/// `impl#[Circle]new` for an inherent impl, `impl#[Circle][Shape]area` for a
+/// trait impl. The module path and the trait are dropped, so both methods come
+/// out as `Circle::new` / `Circle::area`, as does a call through the trait.
+fn normalize_rust_descriptor(descriptor: &str) -> String {
+    if let Some(pos) = descriptor.rfind("impl#[") {
+        let rest = &descriptor[pos + "impl#[".len()..];
+        if let Some((self_type, after)) = rest.split_once(']') {
+            let member = match after.strip_prefix('[') {
+                Some(with_trait) => with_trait.split_once(']').map_or("", |(_, m)| m),
+                None => after,
+            };
+            return if member.is_empty() {
+                self_type.to_string()
+            } else {
+                format!("{}::{}", self_type, member)
+            };
+        }
+    }
+    let item = descriptor.rsplit('/').next().unwrap_or(descriptor);
+    item.replace('#', "::")
+}
+
 /// Strip file-path namespace prefix from a normalised SCIP descriptor.
 ///
 /// scip-typescript encodes the source file as a chain of namespace descriptors,
@@ -580,6 +635,10 @@ fn scip_language_to_domain(lang: &str, path: &str) -> Language {
         "rust" => Language::Rust,
         "python" => Language::Python,
         "go" => Language::Go,
+        "java" => Language::Java,
+        "scala" => Language::Scala,
+        "c#" | "csharp" => Language::CSharp,
+        "ruby" => Language::Ruby,
         "" => Language::from_path(Path::new(path)),
         _ => Language::Unknown,
     }
@@ -643,6 +702,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_symbol_php_method_through_promoted_property() {
+        // `$this->service->someMethod()` where `$service` is a promoted
+        // constructor parameter: scip-php resolves the receiver's type, so the
+        // callee arrives qualified by the class rather than as a bare name.
+        let sym = "scip-php composer pkg dev App/Services/UserService#someMethod().";
+        assert_eq!(
+            normalize_symbol(sym, Language::Php),
+            "App\\Services\\UserService#someMethod"
+        );
+    }
+
     #[test]
     fn test_normalize_symbol_php_global_function() {
         // A global PHP function (no namespace) stays without backslashes.
@@ -650,6 +721,22 @@ mod tests {
         assert_eq!(normalize_symbol(sym, Language::Php), "trim");
     }
 
+    #[test]
+    fn test_normalize_symbol_rust() {
+        let rust = |descriptor: &str| {
+            normalize_symbol(
+                &format!("rust-analyzer cargo geometry 0.1.0 {descriptor}"),
+                Language::Rust,
+            )
+        };
+        assert_eq!(rust("total_area()."), "total_area");
+        assert_eq!(rust("shapes/Circle#"), "Circle");
+        assert_eq!(rust("shapes/Circle#radius."), "Circle::radius");
+        assert_eq!(rust("shapes/Shape#area()."), "Shape::area");
+        assert_eq!(rust("shapes/impl#[Circle]new()."), "Circle::new");
+        assert_eq!(rust("shapes/impl#[Circle][Shape]area()."), "Circle::area");
+    }
+
     #[test]
     fn test_normalize_symbol_local() {
         assert_eq!(normalize_symbol("local 42", Language::JavaScript), "");
@@ -840,6 +927,13 @@ mod tests {
             Language::JavaScript
         );
         assert_eq!(scip_language_to_domain("PHP", "foo.php"), Language::Php);
+        assert_eq!(scip_language_to_domain("Java", "Foo.java"), Language::Java);
+        assert_eq!(
+            scip_language_to_domain("Scala", "Foo.scala"),
+            Language::Scala
+        );
+        assert_eq!(scip_language_to_domain("C#", "Foo.cs"), Language::CSharp);
+        assert_eq!(scip_language_to_domain("rust", "lib.rs"), Language::Rust);
         assert_eq!(
             scip_language_to_domain("Haskell", "foo.hs"),
             Language::Unknown
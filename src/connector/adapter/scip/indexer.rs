@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::time::Duration;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use tracing::{debug, info, warn};
 
 /// TypeScript project files scip-typescript will honour if present. When any of
@@ -112,16 +115,173 @@ impl TempTsconfig {
     }
 }
 
+/// Error returned when an indexer is still running after its timeout. The
+/// child process has been killed by the time this is returned.
+#[derive(Debug, thiserror::Error)]
+#[error("{binary} did not finish within {timeout:?} and was killed")]
+pub struct IndexerTimedOut {
+    pub binary: String,
+    pub timeout: Duration,
+}
+
+/// Run `command` to completion, killing it once `timeout` elapses.
+async fn output_with_timeout(
+    mut command: tokio::process::Command,
+    timeout: Option<Duration>,
+) -> Result<std::io::Result<Output>> {
+    let Some(timeout) = timeout else {
+        return Ok(command.output().await);
+    };
+    let binary = command.as_std().get_program().to_string_lossy().to_string();
+    // Dropping the `output()` future on timeout drops the child, which
+    // `kill_on_drop` turns into a kill instead of a detached process.
+    command.kill_on_drop(true);
+    match tokio::time::timeout(timeout, command.output()).await {
+        Ok(result) => Ok(result),
+        Err(_) => Err(IndexerTimedOut { binary, timeout }.into()),
+    }
+}
+
+/// Placeholder in a configured command line for the index file to write.
+const OUTPUT_PLACEHOLDER: &str = "{output}";
+
+/// Overrides for one SCIP indexer, collected from `--scip-indexer` settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScipIndexerConfig {
+    /// Program and arguments replacing the built-in command line. Every
+    /// [`OUTPUT_PLACEHOLDER`] is replaced with the index path.
+    pub command: Option<Vec<String>>,
+    /// Directory the command runs in, relative to the repository root.
+    pub working_dir: Option<PathBuf>,
+    /// Kill the indexer after this long instead of the global timeout.
+    pub timeout: Option<Duration>,
+    /// Glob over repository files that enables a custom indexer.
+    pub detect: Option<String>,
+}
+
+/// Per-indexer overrides and custom indexers, keyed by indexer name.
+///
+/// Each setting is `NAME=COMMAND` or `NAME.SETTING=VALUE`, with `SETTING` one
+/// of `cwd`, `timeout` (seconds) or `detect`. `typescript`, `php` and `rust`
+/// adjust the built-in indexers; any other name registers a custom indexer,
+/// which needs a command and a `detect` glob and runs whenever a file of the
+/// repository matches it:
+///
+/// ```text
+/// --scip-indexer 'rust.timeout=600'
+/// --scip-indexer 'java=scip-java index --output {output}'
+/// --scip-indexer 'java.detect=**/*.java'
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScipIndexerOverrides {
+    indexers: BTreeMap<String, ScipIndexerConfig>,
+}
+
+impl ScipIndexerOverrides {
+    /// Build the overrides from `--scip-indexer` settings, later settings
+    /// replacing earlier ones for the same key.
+    pub fn parse(settings: &[String]) -> Result<Self> {
+        let mut overrides = Self::default();
+        for setting in settings {
+            overrides.set(setting)?;
+        }
+        for (name, config) in &overrides.indexers {
+            let builtin = IndexerKind::from_config_name(name).is_some();
+            if builtin && config.detect.is_some() {
+                bail!(
+                    "scip indexer '{name}' is built in; 'detect' applies to custom indexers only"
+                );
+            }
+            if !builtin && (config.command.is_none() || config.detect.is_none()) {
+                bail!("custom scip indexer '{name}' needs both '{name}=COMMAND' and '{name}.detect=GLOB'");
+            }
+            if let Some(command) = &config.command {
+                if !command.iter().any(|arg| arg.contains(OUTPUT_PLACEHOLDER)) {
+                    bail!("scip indexer '{name}': the command must write to {OUTPUT_PLACEHOLDER}");
+                }
+            }
+        }
+        Ok(overrides)
+    }
+
+    fn set(&mut self, setting: &str) -> Result<()> {
+        let (key, value) = setting.split_once('=').ok_or_else(|| {
+            anyhow!("invalid scip indexer setting '{setting}': expected NAME[.SETTING]=VALUE")
+        })?;
+        let (name, field) = match key.split_once('.') {
+            Some((name, field)) => (name, Some(field)),
+            None => (key, None),
+        };
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            bail!("invalid scip indexer name '{name}' in '{setting}'");
+        }
+        let config = self.indexers.entry(name.to_string()).or_default();
+        match field {
+            None => {
+                let command: Vec<String> = value.split_whitespace().map(String::from).collect();
+                if command.is_empty() {
+                    bail!("scip indexer '{name}' has an empty command");
+                }
+                config.command = Some(command);
+            }
+            Some("cwd") => config.working_dir = Some(PathBuf::from(value)),
+            Some("timeout") => {
+                let secs: u64 = value.parse().map_err(|_| {
+                    anyhow!("invalid timeout '{value}' for scip indexer '{name}' (expected seconds)")
+                })?;
+                config.timeout = Some(Duration::from_secs(secs));
+            }
+            Some("detect") => {
+                glob::Pattern::new(value)
+                    .map_err(|e| anyhow!("invalid detect glob '{value}' for scip indexer '{name}': {e}"))?;
+                config.detect = Some(value.to_string());
+            }
+            Some(other) => bail!(
+                "unknown scip indexer setting '{other}' in '{setting}' (expected cwd, timeout or detect)"
+            ),
+        }
+        Ok(())
+    }
+
+    fn for_kind(&self, kind: IndexerKind) -> Option<&ScipIndexerConfig> {
+        self.indexers.get(kind.config_name())
+    }
+
+    /// Indexers registered under a name that is not built in.
+    fn custom(&self) -> impl Iterator<Item = (&str, &ScipIndexerConfig)> {
+        self.indexers
+            .iter()
+            .filter(|(name, _)| IndexerKind::from_config_name(name).is_none())
+            .map(|(name, config)| (name.as_str(), config))
+    }
+}
+
+/// An index file produced by one indexer run.
+#[derive(Debug, Clone)]
+pub struct IndexerOutput {
+    /// Indexer that wrote the file (`scip-php`, a custom indexer's name, …).
+    pub name: String,
+    pub index_path: PathBuf,
+    /// Whatever the indexer wrote to standard error, even on success.
+    pub stderr: String,
+}
+
 /// Shells out to a SCIP indexer binary and returns the path to the generated
 /// `index.scip` file.
 ///
 /// Supported indexers:
 /// - `scip-typescript` — handles JavaScript and TypeScript
 /// - `scip-php`        — handles PHP
+/// - `rust-analyzer`   — handles Rust (`rust-analyzer scip`)
 ///
-/// Both indexers are **required** when the repository contains the matching
+/// The first two are **required** when the repository contains the matching
 /// language files.  If the binary is not on `PATH`, indexing fails with a
 /// clear install hint rather than silently degrading to tree-sitter.
+/// rust-analyzer is optional: without it Rust keeps tree-sitter references.
 pub struct ScipIndexer;
 
 /// Which SCIP indexer to invoke.
@@ -131,14 +291,41 @@ pub enum IndexerKind {
     TypeScript,
     /// `scip-php` — covers `.php`
     Php,
+    /// `rust-analyzer scip` — covers a Cargo project's `.rs` files
+    Rust,
 }
 
 impl IndexerKind {
+    /// Name used for this indexer in `--scip-indexer` settings.
+    fn config_name(&self) -> &'static str {
+        match self {
+            IndexerKind::TypeScript => "typescript",
+            IndexerKind::Php => "php",
+            IndexerKind::Rust => "rust",
+        }
+    }
+
+    fn from_config_name(name: &str) -> Option<Self> {
+        [IndexerKind::TypeScript, IndexerKind::Php, IndexerKind::Rust]
+            .into_iter()
+            .find(|kind| kind.config_name() == name)
+    }
+
+    /// Index file written at the repository root.
+    fn output_file(&self) -> &'static str {
+        match self {
+            IndexerKind::TypeScript => "index-typescript.scip",
+            IndexerKind::Php => "index-php.scip",
+            IndexerKind::Rust => "index-rust.scip",
+        }
+    }
+
     /// The binary name looked up on `PATH`.
     fn binary(&self) -> &'static str {
         match self {
             IndexerKind::TypeScript => "scip-typescript",
             IndexerKind::Php => "scip-php",
+            IndexerKind::Rust => "rust-analyzer",
         }
     }
 
@@ -151,6 +338,12 @@ impl IndexerKind {
                 output_path.to_string_lossy().to_string(),
             ],
             IndexerKind::Php => vec!["-o".to_string(), output_path.to_string_lossy().to_string()],
+            IndexerKind::Rust => vec![
+                "scip".to_string(),
+                ".".to_string(),
+                "--output".to_string(),
+                output_path.to_string_lossy().to_string(),
+            ],
         }
     }
 
@@ -158,6 +351,7 @@ impl IndexerKind {
         match self {
             IndexerKind::TypeScript => "scip-typescript",
             IndexerKind::Php => "scip-php",
+            IndexerKind::Rust => "rust-analyzer scip",
         }
     }
 
@@ -168,67 +362,127 @@ impl IndexerKind {
                 "Install it with: npm install -g @sourcegraph/scip-typescript"
             }
             IndexerKind::Php => "Install it from: https://github.com/ArtemisMucaj/scip-php",
+            IndexerKind::Rust => "Install it with: rustup component add rust-analyzer",
         }
     }
 }
 
 impl ScipIndexer {
-    /// Run `kind` against `repo_path` and return the path to the generated
-    /// index file.
+    /// Run `kind` against `repo_path`, adjusted by `config` when given, and
+    /// return the generated index file.
     ///
     /// Returns `Err` in every failure case:
     /// - binary not on `PATH` → actionable install hint
     /// - non-zero exit code   → stderr forwarded to the user
     /// - index file missing after a successful exit → bug report hint
-    pub async fn run(repo_path: &Path, kind: IndexerKind) -> Result<PathBuf> {
-        if !Self::binary_available(kind).await {
-            return Err(anyhow!(
-                "'{}' was not found on PATH.\n  {}",
-                kind.binary(),
-                kind.install_hint(),
-            ));
-        }
-
-        let output_path = repo_path.join(match kind {
-            IndexerKind::TypeScript => "index-typescript.scip",
-            IndexerKind::Php => "index-php.scip",
-        });
+    /// - still running after `timeout` → killed, [`IndexerTimedOut`]
+    pub async fn run(
+        repo_path: &Path,
+        kind: IndexerKind,
+        config: Option<&ScipIndexerConfig>,
+        timeout: Option<Duration>,
+    ) -> Result<IndexerOutput> {
+        let config = config.cloned().unwrap_or_default();
+        let output_path = repo_path.join(kind.output_file());
+        let command = match &config.command {
+            Some(command) => substitute_output(command, &output_path),
+            None => {
+                if !Self::binary_available(kind).await {
+                    return Err(anyhow!(
+                        "'{}' was not found on PATH.\n  {}",
+                        kind.binary(),
+                        kind.install_hint(),
+                    ));
+                }
+                std::iter::once(kind.binary().to_string())
+                    .chain(kind.args(&output_path))
+                    .collect()
+            }
+        };
 
         // scip-typescript needs a tsconfig/jsconfig with `allowJs` to index a
         // vanilla JavaScript repo. Synthesise a throwaway one when the repo
-        // ships none, and remove it once indexing finishes. PHP needs no such
-        // config.
+        // ships none, and remove it once indexing finishes. PHP and Rust need
+        // no such config.
         let tsconfig_guard = match kind {
             IndexerKind::TypeScript => TempTsconfig::create_if_absent(repo_path).await?,
-            IndexerKind::Php => None,
+            IndexerKind::Php | IndexerKind::Rust => None,
         };
 
-        info!("Running {} in {:?}", kind.display_name(), repo_path);
-
-        let result = tokio::process::Command::new(kind.binary())
-            .args(kind.args(&output_path))
-            .current_dir(repo_path)
-            .output()
-            .await;
+        let result = Self::execute(
+            kind.display_name(),
+            &command,
+            repo_path,
+            &config,
+            output_path,
+            timeout,
+        )
+        .await;
 
         // Clean up the synthesised tsconfig before returning down any path.
         if let Some(guard) = tsconfig_guard {
             guard.cleanup().await;
         }
+        result
+    }
+
+    /// Run the custom indexer registered as `name`.
+    pub async fn run_custom(
+        repo_path: &Path,
+        name: &str,
+        config: &ScipIndexerConfig,
+        timeout: Option<Duration>,
+    ) -> Result<IndexerOutput> {
+        let output_path = repo_path.join(format!("index-{name}.scip"));
+        let command = config
+            .command
+            .as_deref()
+            .map(|command| substitute_output(command, &output_path))
+            .ok_or_else(|| anyhow!("custom scip indexer '{name}' has no command"))?;
+        Self::execute(name, &command, repo_path, config, output_path, timeout).await
+    }
 
-        match result {
+    /// Spawn `command` (program first), wait for it within the configured or
+    /// global timeout and check that it wrote `output_path`.
+    async fn execute(
+        name: &str,
+        command: &[String],
+        repo_path: &Path,
+        config: &ScipIndexerConfig,
+        output_path: PathBuf,
+        timeout: Option<Duration>,
+    ) -> Result<IndexerOutput> {
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| anyhow!("{} has an empty command line", name))?;
+        let working_dir = match &config.working_dir {
+            Some(dir) => repo_path.join(dir),
+            None => repo_path.to_path_buf(),
+        };
+
+        info!(
+            "Running {}: `{}` in {:?}",
+            name,
+            command.join(" "),
+            working_dir
+        );
+
+        let mut process = tokio::process::Command::new(program);
+        process.args(args).current_dir(&working_dir);
+
+        match output_with_timeout(process, config.timeout.or(timeout)).await? {
             Ok(output) if output.status.success() => {
                 if output_path.exists() {
-                    info!(
-                        "{} succeeded, index at {:?}",
-                        kind.display_name(),
-                        output_path
-                    );
-                    Ok(output_path)
+                    info!("{} succeeded, index at {:?}", name, output_path);
+                    Ok(IndexerOutput {
+                        name: name.to_string(),
+                        index_path: output_path,
+                        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                    })
                 } else {
                     Err(anyhow!(
                         "{} exited successfully but {:?} was not created",
-                        kind.display_name(),
+                        name,
                         output_path
                     ))
                 }
@@ -244,12 +498,12 @@ impl ScipIndexer {
                 };
                 Err(anyhow!(
                     "{} failed (exit {:?}):\n{}",
-                    kind.display_name(),
+                    name,
                     output.status.code(),
                     output_msg
                 ))
             }
-            Err(e) => Err(anyhow!("failed to spawn {}: {}", kind.binary(), e)),
+            Err(e) => Err(anyhow!("failed to spawn {}: {}", program, e)),
         }
     }
 
@@ -278,16 +532,51 @@ impl ScipIndexer {
     }
 }
 
+/// Replace [`OUTPUT_PLACEHOLDER`] in each argument of a configured command.
+fn substitute_output(command: &[String], output_path: &Path) -> Vec<String> {
+    let output = output_path.to_string_lossy();
+    command
+        .iter()
+        .map(|arg| arg.replace(OUTPUT_PLACEHOLDER, &output))
+        .collect()
+}
+
+/// Whether any non-ignored file under `repo_path` matches `detect`.
+async fn repository_matches(repo_path: &Path, detect: &str) -> bool {
+    let Ok(pattern) = glob::Pattern::new(detect) else {
+        return false;
+    };
+    let root = repo_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        ignore::Walk::new(&root)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .any(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(&root)
+                    .is_ok_and(|relative| pattern.matches_path(relative))
+            })
+    })
+    .await
+    .unwrap_or(false)
+}
+
 /// Run all required SCIP indexers for a repository.
 ///
 /// If a pre-existing `index.scip` is found in the repo root it is used as-is
 /// and no indexer binary is invoked.  Otherwise the appropriate indexer(s) are
-/// run and an error is returned if any of them are missing or fail.
+/// run and an error is returned if any of them are missing, fail, or outlive
+/// `timeout`. A `Cargo.toml` at the root adds rust-analyzer when it is
+/// installed, and each custom indexer in `overrides` runs when a repository
+/// file matches its `detect` glob.
 pub async fn run_applicable_indexers(
     repo_path: &Path,
     has_js_ts: bool,
     has_php: bool,
-) -> Result<Vec<(IndexerKind, PathBuf)>> {
+    overrides: &ScipIndexerOverrides,
+    timeout: Option<Duration>,
+) -> Result<Vec<IndexerOutput>> {
     // Pre-existing index takes precedence; the importer determines languages
     // per document so a single file covers both JS/TS and PHP.
     if let Some(existing) = ScipIndexer::find_existing(repo_path) {
@@ -295,19 +584,51 @@ pub async fn run_applicable_indexers(
             "Using pre-existing index.scip at {:?} (skipping indexer invocation)",
             existing
         );
-        return Ok(vec![(IndexerKind::TypeScript, existing)]);
+        return Ok(vec![IndexerOutput {
+            name: "index.scip".to_string(),
+            index_path: existing,
+            stderr: String::new(),
+        }]);
     }
 
     let mut results = Vec::new();
 
     if has_js_ts {
-        let path = ScipIndexer::run(repo_path, IndexerKind::TypeScript).await?;
-        results.push((IndexerKind::TypeScript, path));
+        let kind = IndexerKind::TypeScript;
+        results.push(ScipIndexer::run(repo_path, kind, overrides.for_kind(kind), timeout).await?);
     }
 
     if has_php {
-        let path = ScipIndexer::run(repo_path, IndexerKind::Php).await?;
-        results.push((IndexerKind::Php, path));
+        let kind = IndexerKind::Php;
+        results.push(ScipIndexer::run(repo_path, kind, overrides.for_kind(kind), timeout).await?);
+    }
+
+    if repo_path.join("Cargo.toml").is_file() {
+        let kind = IndexerKind::Rust;
+        let config = overrides.for_kind(kind);
+        // A configured command is the user opting in, so it runs (and fails
+        // loudly) even when rust-analyzer itself is not installed.
+        if config.is_some_and(|c| c.command.is_some()) || ScipIndexer::binary_available(kind).await
+        {
+            results.push(ScipIndexer::run(repo_path, kind, config, timeout).await?);
+        } else {
+            debug!(
+                "Cargo.toml found but {} is unavailable; Rust keeps tree-sitter references. {}",
+                kind.binary(),
+                kind.install_hint()
+            );
+        }
+    }
+
+    for (name, config) in overrides.custom() {
+        let Some(detect) = config.detect.as_deref() else {
+            continue;
+        };
+        if repository_matches(repo_path, detect).await {
+            results.push(ScipIndexer::run_custom(repo_path, name, config, timeout).await?);
+        } else {
+            debug!("No file matches {detect}; skipping scip indexer '{name}'");
+        }
     }
 
     Ok(results)
@@ -385,4 +706,96 @@ mod tests {
         let preserved = tokio::fs::read_to_string(&path).await.unwrap();
         assert!(preserved.contains("strict"));
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn hanging_command_is_killed_after_timeout() {
+        let mut command = tokio::process::Command::new("sleep");
+        command.arg("1000");
+        let started = std::time::Instant::now();
+
+        let err = output_with_timeout(command, Some(Duration::from_millis(100)))
+            .await
+            .expect_err("a command sleeping past its timeout must fail");
+
+        assert!(err.downcast_ref::<IndexerTimedOut>().is_some());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn parses_overrides_and_custom_indexers() {
+        let overrides = ScipIndexerOverrides::parse(&[
+            "rust.timeout=600".to_string(),
+            "rust.cwd=crates/app".to_string(),
+            "java=scip-java index --output {output}".to_string(),
+            "java.detect=**/*.java".to_string(),
+        ])
+        .unwrap();
+
+        let rust = overrides.for_kind(IndexerKind::Rust).unwrap();
+        assert_eq!(rust.timeout, Some(Duration::from_secs(600)));
+        assert_eq!(rust.working_dir, Some(PathBuf::from("crates/app")));
+        assert_eq!(rust.command, None);
+
+        let custom: Vec<_> = overrides.custom().collect();
+        assert_eq!(custom.len(), 1);
+        let (name, java) = custom[0];
+        assert_eq!(name, "java");
+        assert_eq!(java.detect.as_deref(), Some("**/*.java"));
+        assert_eq!(
+            substitute_output(
+                java.command.as_ref().unwrap(),
+                Path::new("/r/index-java.scip")
+            ),
+            ["scip-java", "index", "--output", "/r/index-java.scip"]
+        );
+    }
+
+    #[test]
+    fn rejects_incomplete_or_malformed_settings() {
+        let parse = |settings: &[&str]| {
+            ScipIndexerOverrides::parse(&settings.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+        };
+        // A custom indexer needs a detect glob, and every command an output.
+        assert!(parse(&["java=scip-java index --output {output}"]).is_err());
+        assert!(parse(&["php=scip-php", "php.timeout=5"]).is_err());
+        assert!(parse(&["rust.detect=*.rs"]).is_err());
+        assert!(parse(&["rust.timeout=soon"]).is_err());
+        assert!(parse(&["rust.memory=2G"]).is_err());
+        assert!(parse(&["rust"]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn custom_indexer_runs_when_detect_matches_and_keeps_stderr() {
+        let dir = tempdir().unwrap();
+        tokio::fs::create_dir_all(dir.path().join("src"))
+            .await
+            .unwrap();
+        tokio::fs::write(dir.path().join("src/Main.java"), "class Main {}")
+            .await
+            .unwrap();
+        let script = dir.path().join("indexer.sh");
+        tokio::fs::write(&script, "echo warned >&2\ntouch \"$1\"\n")
+            .await
+            .unwrap();
+        let overrides = ScipIndexerOverrides::parse(&[
+            format!("java=sh {} {{output}}", script.display()),
+            "java.detect=*.java".to_string(),
+            "kotlin=false {output}".to_string(),
+            "kotlin.detect=*.kt".to_string(),
+        ])
+        .unwrap();
+
+        let outputs = run_applicable_indexers(dir.path(), false, false, &overrides, None)
+            .await
+            .unwrap();
+
+        // Only the indexer whose glob matched ran.
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].name, "java");
+        assert_eq!(outputs[0].index_path, dir.path().join("index-java.scip"));
+        assert!(outputs[0].index_path.exists());
+        assert_eq!(outputs[0].stderr.trim(), "warned");
+    }
 }
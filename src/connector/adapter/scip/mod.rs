@@ -1,7 +1,12 @@
+mod exporter;
 mod importer;
 mod indexer;
 mod phase_runner;
 
+pub use exporter::ScipExporter;
 pub use importer::ScipImporter;
-pub use indexer::{run_applicable_indexers, IndexerKind, ScipIndexer};
+pub use indexer::{
+    run_applicable_indexers, IndexerKind, IndexerOutput, IndexerTimedOut, ScipIndexer,
+    ScipIndexerConfig, ScipIndexerOverrides,
+};
 pub use phase_runner::ScipRunner;
@@ -1,21 +1,52 @@
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
 
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::application::use_cases::Scip;
 use crate::domain::{DomainError, SymbolReference};
 
-use super::{run_applicable_indexers, ScipImporter};
+use super::{run_applicable_indexers, IndexerTimedOut, ScipImporter, ScipIndexerOverrides};
 
 /// Concrete implementation of [`Scip`] that shells out to
-/// `scip-typescript` and/or `scip-php`, parses the resulting index files,
+/// `scip-typescript`, `scip-php`, `rust-analyzer` and any custom indexers
+/// (`--scip-indexer`), parses the resulting index files,
 /// and returns symbol references keyed by relative file path.
 ///
 /// When neither indexer is installed the method returns `Ok(empty)` so the
 /// caller uses tree-sitter as before.  When an indexer **is** installed but
-/// fails, `Err` is returned — the failure is never silently swallowed.
-pub struct ScipRunner;
+/// fails, `Err` is returned — the failure is never silently swallowed. The
+/// one exception is an indexer outliving [`ScipRunner::with_timeout`]: it is
+/// killed and the phase yields nothing, so tree-sitter references are used.
+#[derive(Default)]
+pub struct ScipRunner {
+    timeout: Option<Duration>,
+    overrides: ScipIndexerOverrides,
+    /// Standard error of each indexer the last run invoked.
+    stderr: Mutex<Vec<(String, String)>>,
+}
+
+impl ScipRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Kill an indexer still running after `timeout` instead of waiting on it
+    /// forever (e.g. when it loops on a circular symlink).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Adjust the built-in indexers and register custom ones
+    /// (`--scip-indexer`).
+    pub fn with_overrides(mut self, overrides: ScipIndexerOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+}
 
 #[async_trait::async_trait]
 impl Scip for ScipRunner {
@@ -26,9 +57,28 @@ impl Scip for ScipRunner {
         has_js_ts: bool,
         has_php: bool,
     ) -> Result<HashMap<String, Vec<SymbolReference>>, DomainError> {
-        let index_files = run_applicable_indexers(repo_path, has_js_ts, has_php)
-            .await
-            .map_err(|e| DomainError::internal(format!("SCIP indexer failed: {:#}", e)))?;
+        self.stderr.lock().unwrap().clear();
+        let index_files = match run_applicable_indexers(
+            repo_path,
+            has_js_ts,
+            has_php,
+            &self.overrides,
+            self.timeout,
+        )
+        .await
+        {
+            Ok(files) => files,
+            Err(e) if e.downcast_ref::<IndexerTimedOut>().is_some() => {
+                warn!("{:#}; falling back to tree-sitter references", e);
+                return Ok(HashMap::new());
+            }
+            Err(e) => {
+                return Err(DomainError::internal(format!(
+                    "SCIP indexer failed: {:#}",
+                    e
+                )))
+            }
+        };
 
         if index_files.is_empty() {
             return Ok(HashMap::new());
@@ -36,7 +86,15 @@ impl Scip for ScipRunner {
 
         let mut combined: HashMap<String, Vec<SymbolReference>> = HashMap::new();
 
-        for (_kind, scip_path) in index_files {
+        self.stderr.lock().unwrap().extend(
+            index_files
+                .iter()
+                .filter(|output| !output.stderr.trim().is_empty())
+                .map(|output| (output.name.clone(), output.stderr.clone())),
+        );
+
+        for output in index_files {
+            let scip_path = output.index_path;
             match ScipImporter::import(&scip_path, repo_id).await {
                 Ok(by_file) => {
                     let file_count = by_file.len();
@@ -65,4 +123,8 @@ impl Scip for ScipRunner {
 
         Ok(combined)
     }
+
+    fn indexer_stderr(&self) -> Vec<(String, String)> {
+        self.stderr.lock().unwrap().clone()
+    }
 }
@@ -1002,10 +1002,8 @@ fn object_pattern_key_for_local(pattern: Node<'_>, source: &str, local: &str) ->
     for child in pattern.children(&mut cursor) {
         match child.kind() {
             // Shorthand: `{ topics }` — key and binding are the same identifier.
-            "shorthand_property_identifier_pattern" => {
-                if &source[child.byte_range()] == local {
-                    return Some(local.to_string());
-                }
+            "shorthand_property_identifier_pattern" if &source[child.byte_range()] == local => {
+                return Some(local.to_string());
             }
             // Renamed: `{ topics: t }` — pair_pattern with key `topics`, value `t`.
             "pair_pattern" => {
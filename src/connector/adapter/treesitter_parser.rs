@@ -1,13 +1,26 @@
+use std::collections::{HashMap, HashSet};
+
 use async_trait::async_trait;
 use streaming_iterator::StreamingIterator;
 use tracing::debug;
-use tree_sitter::{Parser, Query, QueryCursor};
+use tree_sitter::{Node, Parser, Query, QueryCursor};
 
+use super::markdown_chunker::chunk_markdown;
 use crate::application::ParserService;
-use crate::domain::{CodeChunk, DomainError, Language, NodeType};
+use crate::domain::{CodeChunk, DomainError, Language, NodeType, ReferenceKind, SymbolReference};
+
+/// Interpreters whose first non-flag argument is the script they run
+/// (`bash deploy.sh`).
+const SHELL_INTERPRETERS: &[&str] = &["bash", "sh", "dash", "ksh", "zsh"];
+
+/// Nodes longer than this many lines are split into overlapping parts; a
+/// single 800-line function makes for a poor embedding and is truncated by
+/// rerankers anyway.
+pub const DEFAULT_MAX_CHUNK_LINES: usize = 150;
 
 pub struct TreeSitterParser {
     supported_languages: Vec<Language>,
+    max_chunk_lines: usize,
 }
 
 impl TreeSitterParser {
@@ -21,13 +34,30 @@ impl TreeSitterParser {
                 Language::Go,
                 Language::HCL,
                 Language::Php,
+                Language::C,
                 Language::Cpp,
                 Language::Swift,
                 Language::Kotlin,
+                Language::Java,
+                Language::CSharp,
+                Language::Elixir,
+                Language::Lua,
+                Language::Ruby,
+                Language::Bash,
+                Language::Scala,
+                Language::Markdown,
             ],
+            max_chunk_lines: DEFAULT_MAX_CHUNK_LINES,
         }
     }
 
+    /// Split nodes longer than `lines` into overlapping parts (0 disables
+    /// splitting).
+    pub fn with_max_chunk_lines(mut self, lines: usize) -> Self {
+        self.max_chunk_lines = lines;
+        self
+    }
+
     fn get_ts_language(&self, language: Language) -> Option<tree_sitter::Language> {
         match language {
             Language::Rust => Some(tree_sitter_rust::LANGUAGE.into()),
@@ -37,10 +67,18 @@ impl TreeSitterParser {
             Language::Go => Some(tree_sitter_go::LANGUAGE.into()),
             Language::HCL => Some(tree_sitter_hcl::LANGUAGE.into()),
             Language::Php => Some(tree_sitter_php::LANGUAGE_PHP.into()),
+            Language::C => Some(tree_sitter_c::LANGUAGE.into()),
             Language::Cpp => Some(tree_sitter_cpp::LANGUAGE.into()),
             Language::Swift => Some(tree_sitter_swift::LANGUAGE.into()),
             Language::Kotlin => Some(tree_sitter_kotlin_ng::LANGUAGE.into()),
-            Language::Unknown => None,
+            Language::Java => Some(tree_sitter_java::LANGUAGE.into()),
+            Language::CSharp => Some(tree_sitter_c_sharp::LANGUAGE.into()),
+            Language::Elixir => Some(tree_sitter_elixir::LANGUAGE.into()),
+            Language::Lua => Some(tree_sitter_lua::LANGUAGE.into()),
+            Language::Ruby => Some(tree_sitter_ruby::LANGUAGE.into()),
+            Language::Bash => Some(tree_sitter_bash::LANGUAGE.into()),
+            Language::Scala => Some(tree_sitter_scala::LANGUAGE.into()),
+            Language::Markdown | Language::Unknown => None,
         }
     }
 
@@ -73,7 +111,14 @@ impl TreeSitterParser {
                   name: (identifier) @class.name
                   body: (class_body
                     (method_definition
-                      name: (property_identifier) @name) @function))
+                      name: (property_identifier) @name
+                      (#not-eq? @name "constructor")) @function))
+                (class_declaration
+                  name: (identifier) @class.name
+                  body: (class_body
+                    (method_definition
+                      name: (property_identifier) @name
+                      (#eq? @name "constructor")) @constructor))
                 (arrow_function) @function
                 "#
             }
@@ -85,7 +130,14 @@ impl TreeSitterParser {
                   name: (type_identifier) @class.name
                   body: (class_body
                     (method_definition
-                      name: (property_identifier) @name) @function))
+                      name: (property_identifier) @name
+                      (#not-eq? @name "constructor")) @function))
+                (class_declaration
+                  name: (type_identifier) @class.name
+                  body: (class_body
+                    (method_definition
+                      name: (property_identifier) @name
+                      (#eq? @name "constructor")) @constructor))
                 (arrow_function) @function
                 (interface_declaration name: (type_identifier) @name) @interface
                 (type_alias_declaration name: (type_identifier) @name) @typedef
@@ -114,7 +166,14 @@ impl TreeSitterParser {
                   name: (name) @class.name
                   body: (declaration_list
                     (method_declaration
-                      name: (name) @name) @function))
+                      name: (name) @name
+                      (#not-eq? @name "__construct")) @function))
+                (class_declaration
+                  name: (name) @class.name
+                  body: (declaration_list
+                    (method_declaration
+                      name: (name) @name
+                      (#eq? @name "__construct")) @constructor))
                 (interface_declaration name: (name) @name) @interface
                 (interface_declaration
                   name: (name) @class.name
@@ -126,11 +185,38 @@ impl TreeSitterParser {
                   name: (name) @class.name
                   body: (declaration_list
                     (method_declaration
-                      name: (name) @name) @function))
+                      name: (name) @name
+                      (#not-eq? @name "__construct")) @function))
+                (trait_declaration
+                  name: (name) @class.name
+                  body: (declaration_list
+                    (method_declaration
+                      name: (name) @name
+                      (#eq? @name "__construct")) @constructor))
                 (namespace_definition name: (namespace_name) @name) @module
                 (enum_declaration name: (name) @name) @enum
                 "#
             }
+            Language::C => {
+                r#"
+                ; Functions, including ones returning a pointer
+                (function_definition
+                  declarator: (function_declarator declarator: (identifier) @name)) @function
+                (function_definition
+                  declarator: (pointer_declarator
+                    declarator: (function_declarator declarator: (identifier) @name))) @function
+
+                ; Type definitions (forward declarations have no body)
+                (struct_specifier name: (type_identifier) @name body: (_)) @struct
+                (union_specifier name: (type_identifier) @name body: (_)) @struct
+                (enum_specifier name: (type_identifier) @name body: (_)) @enum
+                (type_definition declarator: (type_identifier) @name) @typedef
+
+                ; Macros
+                (preproc_function_def name: (identifier) @name) @function
+                (preproc_def name: (identifier) @name) @constant
+                "#
+            }
             Language::Cpp => {
                 r#"
                 ; Classes and structs
@@ -255,13 +341,306 @@ impl TreeSitterParser {
                 (type_alias (identifier) @name) @typedef
                 "#
             }
-            Language::Unknown => "",
+            Language::Java => {
+                r#"
+                ; Types
+                (class_declaration name: (identifier) @name) @class
+                (record_declaration name: (identifier) @name) @class
+                (interface_declaration name: (identifier) @name) @interface
+                (enum_declaration name: (identifier) @name) @enum
+
+                ; Methods and constructors, qualified by their enclosing type
+                (class_declaration
+                  name: (identifier) @class.name
+                  body: (class_body
+                    (method_declaration name: (identifier) @name) @function))
+                (class_declaration
+                  name: (identifier) @class.name
+                  body: (class_body
+                    (constructor_declaration name: (identifier) @name) @constructor))
+                (record_declaration
+                  name: (identifier) @class.name
+                  body: (class_body
+                    (method_declaration name: (identifier) @name) @function))
+                (interface_declaration
+                  name: (identifier) @class.name
+                  body: (interface_body
+                    (method_declaration name: (identifier) @name) @function))
+                (enum_declaration
+                  name: (identifier) @class.name
+                  body: (enum_body
+                    (enum_body_declarations
+                      (method_declaration name: (identifier) @name) @function)))
+                (enum_declaration
+                  name: (identifier) @class.name
+                  body: (enum_body
+                    (enum_body_declarations
+                      (constructor_declaration name: (identifier) @name) @constructor)))
+                "#
+            }
+            Language::CSharp => {
+                r#"
+                ; Namespaces and types
+                (namespace_declaration name: (_) @name) @module
+                (file_scoped_namespace_declaration name: (_) @name) @module
+                (class_declaration name: (identifier) @name) @class
+                (record_declaration name: (identifier) @name) @class
+                (struct_declaration name: (identifier) @name) @struct
+                (interface_declaration name: (identifier) @name) @interface
+                (enum_declaration name: (identifier) @name) @enum
+
+                ; Members, qualified by their enclosing type
+                (class_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (method_declaration name: (identifier) @name) @function))
+                (class_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (constructor_declaration name: (identifier) @name) @constructor))
+                (class_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (property_declaration name: (identifier) @name) @property))
+                (record_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (method_declaration name: (identifier) @name) @function))
+                (record_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (constructor_declaration name: (identifier) @name) @constructor))
+                (record_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (property_declaration name: (identifier) @name) @property))
+                (struct_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (method_declaration name: (identifier) @name) @function))
+                (struct_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (constructor_declaration name: (identifier) @name) @constructor))
+                (struct_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (property_declaration name: (identifier) @name) @property))
+                (interface_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (method_declaration name: (identifier) @name) @function))
+                (interface_declaration
+                  name: (identifier) @class.name
+                  body: (declaration_list
+                    (property_declaration name: (identifier) @name) @property))
+                "#
+            }
+            Language::Elixir => {
+                // Elixir definitions are all macro calls, so the defining keyword
+                // is captured as `@_kind` and filtered with `#any-of?`.
+                r#"
+                ; Modules, protocols and protocol implementations
+                (call
+                  target: (identifier) @_kind
+                  (arguments (alias) @name)
+                  (#eq? @_kind "defmodule")) @module
+                (call
+                  target: (identifier) @_kind
+                  (arguments (alias) @name)
+                  (#eq? @_kind "defprotocol")) @trait
+                (call
+                  target: (identifier) @_kind
+                  (arguments (alias) @name)
+                  (#eq? @_kind "defimpl")) @impl
+
+                ; Structs take the name of their enclosing module
+                (call
+                  target: (identifier) @_module
+                  (arguments (alias) @name)
+                  (do_block
+                    (call target: (identifier) @_kind) @struct)
+                  (#eq? @_module "defmodule")
+                  (#eq? @_kind "defstruct"))
+
+                ; Functions and macros, qualified by their enclosing module
+                (call
+                  target: (identifier) @_module
+                  (arguments (alias) @class.name)
+                  (do_block
+                    (call
+                      target: (identifier) @_kind
+                      (arguments
+                        [
+                          (identifier) @name
+                          (call target: (identifier) @name)
+                          (binary_operator
+                            left: (call target: (identifier) @name)
+                            operator: "when")
+                        ])) @function)
+                  (#any-of? @_module "defmodule" "defprotocol" "defimpl")
+                  (#any-of? @_kind "def" "defp" "defmacro" "defmacrop"))
+                "#
+            }
+            Language::Lua => {
+                r#"
+                ; Global and local functions (`local function f()` shares the node)
+                (function_declaration name: (identifier) @name) @function
+
+                ; `function M.f()` and `function Class:method()`, qualified by their table
+                (function_declaration
+                  name: (dot_index_expression
+                    table: (identifier) @class.name
+                    field: (identifier) @name)) @function
+                (function_declaration
+                  name: (method_index_expression
+                    table: (identifier) @class.name
+                    method: (identifier) @name)) @function
+
+                ; Tables used as modules / pseudo-classes: `local Account = {}`
+                (variable_declaration
+                  (assignment_statement
+                    (variable_list (identifier) @name)
+                    (expression_list (table_constructor)))) @class
+                (chunk
+                  (assignment_statement
+                    (variable_list (identifier) @name)
+                    (expression_list (table_constructor))) @class)
+                "#
+            }
+            Language::Ruby => {
+                r#"
+                ; Classes and modules (`class Foo::Bar` keeps the scoped name)
+                (class name: (_) @name) @class
+                (module name: (_) @name) @module
+
+                ; Top-level methods and `def self.foo` outside a class
+                (program (method name: (_) @name) @function)
+                (program (singleton_method name: (_) @name) @function)
+
+                ; Instance and singleton methods, qualified by their class or module
+                (class
+                  name: (_) @class.name
+                  (body_statement
+                    [
+                      (method name: (_) @name)
+                      (singleton_method name: (_) @name)
+                    ] @function))
+                (module
+                  name: (_) @class.name
+                  (body_statement
+                    [
+                      (method name: (_) @name)
+                      (singleton_method name: (_) @name)
+                    ] @function))
+                "#
+            }
+            Language::Bash => {
+                r#"
+                ; Both `function deploy { ... }` and `deploy() { ... }`
+                (function_definition name: (word) @name) @function
+                "#
+            }
+            Language::Scala => {
+                r#"
+                ; Top-level functions (Scala 3 / worksheets)
+                (compilation_unit (function_definition name: (identifier) @name) @function)
+
+                ; Types (`case class` is a class_definition too)
+                (class_definition name: (identifier) @name) @class
+                (object_definition name: (identifier) @name) @struct
+                (trait_definition name: (identifier) @name) @trait
+
+                ; Type aliases
+                (type_definition name: (type_identifier) @name) @typedef
+
+                ; Methods, qualified by their enclosing class, object or trait
+                (class_definition
+                  name: (identifier) @class.name
+                  body: (template_body
+                    (function_definition name: (identifier) @name) @function))
+                (object_definition
+                  name: (identifier) @class.name
+                  body: (template_body
+                    (function_definition name: (identifier) @name) @function))
+                (trait_definition
+                  name: (identifier) @class.name
+                  body: (template_body
+                    [
+                      (function_definition name: (identifier) @name)
+                      (function_declaration name: (identifier) @name)
+                    ] @function))
+                "#
+            }
+            Language::Markdown | Language::Unknown => "",
         }
     }
 
+    /// Reference queries for languages without a SCIP indexer, plus the
+    /// TypeScript decorators SCIP does not report as calls. Captures are
+    /// named after the reference kind: `@call`, `@import`, `@type`,
+    /// `@decorator`.
+    fn get_reference_patterns(&self, language: Language) -> &'static str {
+        match language {
+            Language::TypeScript => {
+                r#"
+                ; `@Injectable`, `@Component({...})`, `@Inject('TOKEN')`,
+                ; `@core.Input()`
+                (decorator (identifier) @decorator)
+                (decorator (member_expression property: (property_identifier) @decorator))
+                (decorator (call_expression function: (identifier) @decorator))
+                (decorator
+                  (call_expression
+                    function: (member_expression property: (property_identifier) @decorator)))
+                "#
+            }
+            Language::Python => {
+                r#"
+                ; `class Child(Base, mixins.Logged):`; keyword arguments such
+                ; as `metaclass=ABCMeta` are not base classes.
+                (class_definition
+                  superclasses: (argument_list (identifier) @inheritance))
+                (class_definition
+                  superclasses: (argument_list (attribute attribute: (identifier) @inheritance)))
+                "#
+            }
+            Language::C => {
+                r#"
+                (call_expression function: (identifier) @call)
+                (preproc_include path: (_) @import)
+                (type_identifier) @type
+                "#
+            }
+            _ => "",
+        }
+    }
+
+    /// Parse `content` with the grammar for `language`.
+    fn parse_tree(
+        &self,
+        content: &str,
+        language: Language,
+    ) -> Result<(tree_sitter::Tree, tree_sitter::Language), DomainError> {
+        let ts_language = self
+            .get_ts_language(language)
+            .ok_or_else(|| DomainError::parse(format!("Unsupported language: {:?}", language)))?;
+
+        let mut parser = Parser::new();
+        parser
+            .set_language(&ts_language)
+            .map_err(|e| DomainError::parse(format!("Failed to set language: {}", e)))?;
+
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| DomainError::parse("Failed to parse file"))?;
+        Ok((tree, ts_language))
+    }
+
     fn capture_to_node_type(capture_name: &str) -> NodeType {
         match capture_name {
             "function" => NodeType::Function,
+            "constructor" => NodeType::Constructor,
+            "property" => NodeType::Property,
             "class" => NodeType::Class,
             "struct" => NodeType::Struct,
             "enum" => NodeType::Enum,
@@ -291,18 +670,16 @@ impl ParserService for TreeSitterParser {
         language: Language,
         repository_id: &str,
     ) -> Result<Vec<CodeChunk>, DomainError> {
-        let ts_language = self
-            .get_ts_language(language)
-            .ok_or_else(|| DomainError::parse(format!("Unsupported language: {:?}", language)))?;
-
-        let mut parser = Parser::new();
-        parser
-            .set_language(&ts_language)
-            .map_err(|e| DomainError::parse(format!("Failed to set language: {}", e)))?;
+        if language == Language::Markdown {
+            return Ok(chunk_markdown(
+                content,
+                file_path,
+                repository_id,
+                self.max_chunk_lines,
+            ));
+        }
 
-        let tree = parser
-            .parse(content, None)
-            .ok_or_else(|| DomainError::parse("Failed to parse file"))?;
+        let (tree, ts_language) = self.parse_tree(content, language)?;
 
         let query_source = self.get_query_patterns(language);
         if query_source.is_empty() {
@@ -336,6 +713,9 @@ impl ParserService for TreeSitterParser {
                     symbol_name = Some(content[capture.node.byte_range()].to_string());
                 } else if capture_name.ends_with(".name") {
                     parent_symbol = Some(content[capture.node.byte_range()].to_string());
+                } else if capture_name.starts_with('_') {
+                    // Helper captures used only by predicates (e.g. `@_kind`).
+                    continue;
                 } else {
                     main_node = Some(capture.node);
                     node_type = Self::capture_to_node_type(capture_name);
@@ -343,33 +723,60 @@ impl ParserService for TreeSitterParser {
             }
 
             if let Some(node) = main_node {
-                let start_line = node.start_position().row as u32 + 1;
-                let end_line = node.end_position().row as u32 + 1;
-                let node_content = content[node.byte_range()].to_string();
-
-                if node_content.trim().len() < 10 {
+                if content[node.byte_range()].trim().len() < 10 {
                     continue;
                 }
 
-                let mut chunk = CodeChunk::new(
-                    file_path.to_string(),
-                    node_content,
-                    start_line,
-                    end_line,
-                    language,
-                    node_type,
-                    repository_id.to_string(),
-                );
-
-                if let Some(name) = symbol_name {
-                    chunk = chunk.with_symbol_name(name);
+                // Decorators and conditionals can sit between a class and its
+                // `__init__`, so constructors are recognised from the tree.
+                if language == Language::Python && symbol_name.as_deref() == Some("__init__") {
+                    if let Some(class) = python_enclosing_class(node, content) {
+                        node_type = NodeType::Constructor;
+                        parent_symbol = Some(class);
+                    }
                 }
 
-                if let Some(parent) = parent_symbol {
-                    chunk = chunk.with_parent_symbol(parent);
-                }
+                // Doc comments above the definition are part of its chunk.
+                let comments = leading_comments(node, content);
+                let first = comments.first().copied().unwrap_or(node);
+                let doc = doc_comment(node, &comments, content);
+                let start_line = first.start_position().row as u32 + 1;
+                let end_line = node.end_position().row as u32 + 1;
+                let node_content = content[first.start_byte()..node.end_byte()].to_string();
+
+                let parts = split_lines(&node_content, start_line, end_line, self.max_chunk_lines);
+                let is_split = parts.len() > 1;
+                let is_async = is_async_definition(node, language, content);
+                for (index, (part_start, part_end, part_content)) in parts.into_iter().enumerate() {
+                    let mut chunk = CodeChunk::new(
+                        file_path.to_string(),
+                        part_content,
+                        part_start,
+                        part_end,
+                        language,
+                        node_type,
+                        repository_id.to_string(),
+                    )
+                    .with_async(is_async);
+
+                    if let Some(doc) = &doc {
+                        chunk = chunk.with_doc_comment(doc.clone());
+                    }
 
-                chunks.push(chunk);
+                    if let Some(name) = &symbol_name {
+                        chunk = chunk.with_symbol_name(name.clone());
+                    }
+
+                    if let Some(parent) = &parent_symbol {
+                        chunk = chunk.with_parent_symbol(parent.clone());
+                    }
+
+                    if is_split {
+                        chunk = chunk.with_part(index as u32 + 1);
+                    }
+
+                    chunks.push(chunk);
+                }
             }
         }
 
@@ -383,250 +790,5120 @@ impl ParserService for TreeSitterParser {
         Ok(chunks)
     }
 
+    async fn extract_references(
+        &self,
+        content: &str,
+        file_path: &str,
+        language: Language,
+        repository_id: &str,
+    ) -> Result<Vec<SymbolReference>, DomainError> {
+        let references = match language {
+            // No SCIP indexer exists for shell, so script-to-script calls are
+            // taken straight from the syntax tree.
+            Language::Bash => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                script_references(&tree, content, file_path, repository_id)
+            }
+            // Scala needs receiver- and scope-aware names (`this.f()`,
+            // `Companion.f()`), which a flat query cannot provide.
+            Language::Scala => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                scala_references(&tree, content, file_path, repository_id)
+            }
+            // Go method calls are qualified by the receiver's type.
+            Language::Go => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                go_references(&tree, content, file_path, repository_id)
+            }
+            // Kotlin and Swift calls on `this`/`self` and on known classes
+            // are qualified the same way; Swift also records the protocols a
+            // type conforms to, and Kotlin the methods a class overrides.
+            Language::Kotlin | Language::Swift => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                let mut references =
+                    member_call_references(&tree, content, file_path, language, repository_id);
+                if language == Language::Swift {
+                    references.extend(swift_conformance_references(
+                        &tree,
+                        content,
+                        file_path,
+                        repository_id,
+                    ));
+                } else {
+                    references.extend(override_references(
+                        &tree,
+                        content,
+                        file_path,
+                        language,
+                        repository_id,
+                    ));
+                }
+                references
+            }
+            // Java calls, `new` expressions, imports and `implements` clauses
+            // all come from the syntax tree.
+            Language::Java => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                let mut references = java_references(&tree, content, file_path, repository_id);
+                references.extend(implementation_references(
+                    &tree,
+                    content,
+                    file_path,
+                    language,
+                    repository_id,
+                ));
+                references
+            }
+            // Likewise for C#, including LINQ query syntax.
+            Language::CSharp => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                let mut references = csharp_references(&tree, content, file_path, repository_id);
+                references.extend(implementation_references(
+                    &tree,
+                    content,
+                    file_path,
+                    language,
+                    repository_id,
+                ));
+                references
+            }
+            // Every Elixir construct is a macro call; calls, imports and
+            // behaviours are told apart by walking the tree with the
+            // enclosing module and its aliases in hand.
+            Language::Elixir => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                elixir_references(&tree, content, file_path, repository_id)
+            }
+            // Lua classes are tables wired up through metatables; calls are
+            // qualified by table and `setmetatable` marks inheritance.
+            Language::Lua => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                lua_references(&tree, content, file_path, repository_id)
+            }
+            // Ruby calls are qualified by receiver and class, with the
+            // `Kernel` methods every class can call left out.
+            Language::Ruby => {
+                let (tree, _) = self.parse_tree(content, language)?;
+                ruby_references(&tree, content, file_path, repository_id)
+            }
+            _ => {
+                let query_source = self.get_reference_patterns(language);
+                // SCIP indexers report calls but not which trait or interface
+                // a method implements, nor which base method it overrides;
+                // those edges come from the syntax tree.
+                let implements = matches!(
+                    language,
+                    Language::Rust | Language::TypeScript | Language::Php
+                );
+                let overrides = matches!(language, Language::TypeScript | Language::Python);
+                if query_source.is_empty() && !implements && !overrides {
+                    return Ok(Vec::new());
+                }
+                let (tree, ts_language) = self.parse_tree(content, language)?;
+                let mut references = Vec::new();
+                if !query_source.is_empty() {
+                    let query = Query::new(&ts_language, query_source).map_err(|e| {
+                        DomainError::parse(format!("Failed to create reference query: {}", e))
+                    })?;
+                    references = query_references(
+                        &query,
+                        &tree,
+                        content,
+                        file_path,
+                        language,
+                        repository_id,
+                    );
+                }
+                if implements {
+                    references.extend(implementation_references(
+                        &tree,
+                        content,
+                        file_path,
+                        language,
+                        repository_id,
+                    ));
+                }
+                if overrides {
+                    references.extend(override_references(
+                        &tree,
+                        content,
+                        file_path,
+                        language,
+                        repository_id,
+                    ));
+                }
+                references
+            }
+        };
+
+        debug!(
+            "Extracted {} references from {} ({:?})",
+            references.len(),
+            file_path,
+            language
+        );
+        Ok(references)
+    }
+
     fn supported_languages(&self) -> Vec<Language> {
         self.supported_languages.clone()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Wrapper nodes that start on the same line as the definition they hold
+/// (`export function ...`, a decorated Python `def`); comments documenting
+/// the definition sit above the wrapper.
+const DEFINITION_WRAPPERS: &[&str] = &["export_statement", "decorated_definition"];
 
-    #[tokio::test]
-    async fn test_parse_rust_function() {
-        let parser = TreeSitterParser::new();
-        let content = r#"
-fn hello_world() {
-    println!("Hello, world!");
+/// Whether a function definition is a coroutine: Kotlin `suspend fun` or
+/// Rust `async fn`. The keyword sits in the declaration's modifier list.
+fn is_async_definition(node: Node, language: Language, content: &str) -> bool {
+    match (language, node.kind()) {
+        (Language::Kotlin, "function_declaration") => {
+            has_modifier(node, "modifiers", "suspend", content)
+        }
+        (Language::Rust, "function_item") => {
+            has_modifier(node, "function_modifiers", "async", content)
+        }
+        _ => false,
+    }
 }
 
-fn add(a: i32, b: i32) -> i32 {
-    a + b
+/// Whether `keyword` appears in the `modifiers` child of a declaration.
+fn has_modifier(node: Node, modifiers: &str, keyword: &str, content: &str) -> bool {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == modifiers
+            && content[child.byte_range()]
+                .split_whitespace()
+                .any(|w| w == keyword)
+        {
+            return true;
+        }
+    }
+    false
 }
-"#;
 
-        let chunks = parser
-            .parse_file(content, "test.rs", Language::Rust, "test-repo")
-            .await
-            .unwrap();
+/// The comments directly above a definition, top to bottom. Comments must be
+/// contiguous (no blank line) and start their own line; Rust attributes
+/// between the comments and the item are stepped over. Shebangs and inner doc
+/// comments (`//!`) describe the file, not the item.
+fn leading_comments<'t>(node: Node<'t>, content: &str) -> Vec<Node<'t>> {
+    let mut anchor = node;
+    while let Some(parent) = anchor.parent() {
+        if !DEFINITION_WRAPPERS.contains(&parent.kind()) {
+            break;
+        }
+        anchor = parent;
+    }
 
-        assert!(!chunks.is_empty());
+    let mut comments = Vec::new();
+    let mut top_row = anchor.start_position().row;
+    let mut sibling = anchor.prev_sibling();
+    while let Some(prev) = sibling {
+        let is_comment = prev.kind().contains("comment");
+        if !is_comment && prev.kind() != "attribute_item" {
+            break;
+        }
+        let text = &content[prev.byte_range()];
+        if ["#!", "//!", "/*!"].iter().any(|p| text.starts_with(p))
+            || last_row(prev) + 1 < top_row
+            || !starts_line(prev, content)
+        {
+            break;
+        }
+        if is_comment {
+            comments.push(prev);
+        }
+        top_row = prev.start_position().row;
+        sibling = prev.prev_sibling();
     }
+    comments.reverse();
+    comments
+}
 
-    #[tokio::test]
-    async fn test_parse_python_class() {
-        let parser = TreeSitterParser::new();
-        let content = r#"
-class Calculator:
-    def add(self, a, b):
-        return a + b
+/// Documentation of a definition without comment markers: a Python
+/// docstring when there is one, else its leading `comments`.
+fn doc_comment(node: Node, comments: &[Node], content: &str) -> Option<String> {
+    let lines: Vec<&str> = match docstring(node) {
+        Some(string) => {
+            let text = content[string.byte_range()].trim_start_matches(char::is_alphabetic);
+            ["\"\"\"", "'''", "\"", "'"]
+                .iter()
+                .find_map(|q| text.strip_prefix(q)?.strip_suffix(q))
+                .unwrap_or(text)
+                .lines()
+                .map(str::trim)
+                .collect()
+        }
+        None => comments
+            .iter()
+            .flat_map(|c| content[c.byte_range()].lines())
+            .map(strip_comment_markers)
+            .collect(),
+    };
+    let start = lines.iter().position(|l| !l.is_empty())?;
+    let end = lines.iter().rposition(|l| !l.is_empty())?;
+    Some(lines[start..=end].join("\n"))
+}
 
-    def subtract(self, a, b):
-        return a - b
-"#;
+/// The string literal opening a Python function or class body.
+fn docstring(node: Node) -> Option<Node> {
+    if !matches!(node.kind(), "function_definition" | "class_definition") {
+        return None;
+    }
+    let first = node.child_by_field_name("body")?.named_child(0)?;
+    let string = first.named_child(0)?;
+    (first.kind() == "expression_statement" && string.kind() == "string").then_some(string)
+}
 
-        let chunks = parser
-            .parse_file(content, "calc.py", Language::Python, "test-repo")
-            .await
-            .unwrap();
+/// One line of a comment without its `///`, `/**`, `*`, `#:`, `--` (…)
+/// markers.
+fn strip_comment_markers(line: &str) -> &str {
+    let line = line.trim();
+    let line = line.strip_suffix("*/").unwrap_or(line);
+    ["/**", "/*", "///", "//", "#:", "#", "---", "--", "*"]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .unwrap_or(line)
+        .trim()
+}
 
-        assert!(!chunks.is_empty());
+/// Name of the class a Python function is defined in, looking through
+/// decorators and compound statements but not through enclosing functions.
+fn python_enclosing_class(function: Node, content: &str) -> Option<String> {
+    let mut current = function.parent();
+    while let Some(node) = current {
+        match node.kind() {
+            "class_definition" => {
+                let name = node.child_by_field_name("name")?;
+                return Some(content[name.byte_range()].to_string());
+            }
+            "function_definition" | "lambda" | "module" => return None,
+            _ => current = node.parent(),
+        }
+    }
+    None
+}
+
+/// Row of the last character of `node`; some grammars end line comments at
+/// column 0 of the following row.
+fn last_row(node: Node) -> usize {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
+/// `true` when only whitespace precedes `node` on its first line.
+fn starts_line(node: Node, content: &str) -> bool {
+    let before = &content[..node.start_byte()];
+    before[before.rfind('\n').map_or(0, |i| i + 1)..]
+        .trim()
+        .is_empty()
+}
+
+/// Split a node's source into `(start_line, end_line, content)` windows of
+/// at most `max_lines` whole lines, each overlapping the previous one by a
+/// tenth of the window so that code straddling a boundary stays intact in
+/// at least one part. Nodes within the limit come back as a single window.
+pub(super) fn split_lines(
+    content: &str,
+    start_line: u32,
+    end_line: u32,
+    max_lines: usize,
+) -> Vec<(u32, u32, String)> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    if max_lines == 0 || lines.len() <= max_lines {
+        return vec![(start_line, end_line, content.to_string())];
+    }
+
+    let step = max_lines - max_lines / 10;
+    let mut parts = Vec::new();
+    let mut offset = 0;
+    loop {
+        let end = (offset + max_lines).min(lines.len());
+        parts.push((
+            start_line + offset as u32,
+            start_line + end as u32 - 1,
+            lines[offset..end].concat(),
+        ));
+        if end == lines.len() {
+            return parts;
+        }
+        offset += step;
+    }
+}
+
+/// Script-to-script calls in a shell script. Edges are attributed to the
+/// script itself so `impact deploy.sh` can keep walking up through the
+/// scripts that call its callers.
+fn script_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let caller = script_name(file_path).to_string();
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "command" {
+            if let Some(callee) = invoked_script(node, content) {
+                let position = node.start_position();
+                references.push(
+                    SymbolReference::new(
+                        Some(caller.clone()),
+                        callee,
+                        file_path.to_string(),
+                        file_path.to_string(),
+                        position.row as u32 + 1,
+                        position.column as u32 + 1,
+                        ReferenceKind::Call,
+                        Language::Bash,
+                        repository_id.to_string(),
+                    )
+                    .with_caller_node_type("script"),
+                );
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// Calls, imports, `extends`/`with` parents and implicit conversions in a
+/// Scala file. Calls and conversions are attributed to the enclosing method
+/// (`Type.method`), parents to the class, object or trait that declares them.
+fn scala_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let reference = |caller: Option<String>, callee: String, node: Node, kind| {
+        let position = node.start_position();
+        SymbolReference::new(
+            caller,
+            callee,
+            file_path.to_string(),
+            file_path.to_string(),
+            position.row as u32 + 1,
+            position.column as u32 + 1,
+            kind,
+            Language::Scala,
+            repository_id.to_string(),
+        )
+    };
+
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "call_expression" => {
+                let enclosing = scala_enclosing_type(node, content);
+                let callee = node.child_by_field_name("function").and_then(|f| {
+                    qualify_callee_name(f, content, enclosing.as_deref(), &HashMap::new())
+                });
+                if let Some(callee) = callee {
+                    references.push(reference(
+                        scala_enclosing_method(node, content),
+                        callee,
+                        node,
+                        ReferenceKind::Call,
+                    ));
+                }
+            }
+            "import_declaration" => {
+                let caller = scala_enclosing_method(node, content);
+                let text = content[node.byte_range()].trim_start_matches("import");
+                for path in split_imports(text) {
+                    references.push(reference(caller.clone(), path, node, ReferenceKind::Import));
+                }
+            }
+            "extends_clause" => {
+                let owner = node
+                    .parent()
+                    .and_then(|p| p.child_by_field_name("name"))
+                    .map(|n| content[n.byte_range()].to_string());
+                let mut cursor = node.walk();
+                for parent in node.named_children(&mut cursor) {
+                    if parent.kind() == "arguments" {
+                        continue;
+                    }
+                    references.push(reference(
+                        owner.clone(),
+                        scala_type_name(parent, content),
+                        parent,
+                        ReferenceKind::Inheritance,
+                    ));
+                }
+            }
+            // `implicit def toRich(x: Int): RichInt` silently converts to
+            // `RichInt`; record the target type so the conversion shows up
+            // in its impact.
+            "function_definition" if is_implicit(node, content) => {
+                if let Some(target) = node.child_by_field_name("return_type") {
+                    references.push(reference(
+                        scala_enclosing_method(target, content),
+                        scala_type_name(target, content),
+                        target,
+                        ReferenceKind::TypeReference,
+                    ));
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// Call-graph name of the function a Scala call targets:
+///
+/// - `helper()` → `helper`
+/// - `this.helper()` → `Enclosing.helper` (inside class `Enclosing`)
+/// - `Companion.create()` / `pkg.Companion.create()` → `Companion.create`;
+///   an upper-case receiver is taken to be an object
+/// - `value.method()` → `method`; the receiver's type is unknown
+/// - `convert[Int](x)` → `convert`
+///
+/// and of a Kotlin or Swift call, whose receivers are looked up in
+/// `receivers` (receiver name → class) instead of guessed from their case:
+///
+/// - `this.publish()` / `self.publish()` → `Enclosing.publish`
+/// - `EventBus.create()` → `EventBus.create` for a class declared in the file
+/// - `bus.publish()` → `EventBus.publish` for a Kotlin constructor property
+///   `bus: EventBus`
+/// - `other.publish()` → `publish`
+///
+/// and of a Java `method_invocation`, given as the call itself:
+///
+/// - `this.area()` → `Enclosing#area`
+/// - `Circle.fromDiameter(2)` → `Circle#fromDiameter`
+/// - `registry.register(c)` / `register(c)` → `register`
+///
+/// and of a C# invocation's `function`, which follows the same rules:
+///
+/// - `this.Register(s)` → `Enclosing#Register`
+/// - `Circle.FromDiameter(2)` → `Circle#FromDiameter`
+/// - `_shapes.Where(…)` / `Helper<int>(3)` → `Where` / `Helper`
+///
+/// and of a Go call, where `receivers` holds the enclosing method's receiver:
+///
+/// - `h.Handle()` in `func (h *Handler) …` → `Handler.Handle`
+/// - `other.Handle()` / `fmt.Println()` → `Handle` / `Println`
+///
+/// Calls whose target is itself an expression (`f(a)(b)`, `(g)(x)`) yield
+/// `None`.
+fn qualify_callee_name(
+    function: Node,
+    content: &str,
+    enclosing: Option<&str>,
+    receivers: &HashMap<String, String>,
+) -> Option<String> {
+    match function.kind() {
+        "identifier" | "operator_identifier" | "simple_identifier" => {
+            Some(content[function.byte_range()].to_string())
+        }
+        "generic_function" => qualify_callee_name(
+            function.child_by_field_name("function")?,
+            content,
+            enclosing,
+            receivers,
+        ),
+        "selector_expression" => {
+            let field = &content[function.child_by_field_name("field")?.byte_range()];
+            let operand = &content[function.child_by_field_name("operand")?.byte_range()];
+            Some(match receivers.get(operand) {
+                Some(owner) => format!("{}.{}", owner, field),
+                None => field.to_string(),
+            })
+        }
+        "navigation_expression" => {
+            let member = navigation_member(function, content)?;
+            let target = function.named_child(0)?;
+            let receiver = match target.kind() {
+                "navigation_expression" => navigation_member(target, content)?,
+                _ => &content[target.byte_range()],
+            };
+            let owner = match receiver {
+                "this" | "self" => enclosing,
+                _ => receivers.get(receiver).map(String::as_str),
+            };
+            Some(match owner {
+                Some(owner) => format!("{}.{}", owner, member),
+                None => member.to_string(),
+            })
+        }
+        "method_invocation" => {
+            let name = &content[function.child_by_field_name("name")?.byte_range()];
+            Some(qualify_class_member(
+                function.child_by_field_name("object"),
+                name,
+                content,
+                enclosing,
+            ))
+        }
+        "member_access_expression" => {
+            let mut name = function.child_by_field_name("name")?;
+            if name.kind() == "generic_name" {
+                name = name.named_child(0)?;
+            }
+            Some(qualify_class_member(
+                function.child_by_field_name("expression"),
+                &content[name.byte_range()],
+                content,
+                enclosing,
+            ))
+        }
+        "generic_name" => Some(content[function.named_child(0)?.byte_range()].to_string()),
+        "field_expression" => {
+            let field = &content[function.child_by_field_name("field")?.byte_range()];
+            let value = function.child_by_field_name("value")?;
+            let receiver = match value.kind() {
+                "field_expression" => &content[value.child_by_field_name("field")?.byte_range()],
+                _ => &content[value.byte_range()],
+            };
+            if receiver == "this" {
+                return Some(match enclosing {
+                    Some(owner) => format!("{}.{}", owner, field),
+                    None => field.to_string(),
+                });
+            }
+            if receiver.starts_with(|c: char| c.is_ascii_uppercase())
+                && receiver.chars().all(|c| c.is_alphanumeric() || c == '_')
+            {
+                return Some(format!("{}.{}", receiver, field));
+            }
+            Some(field.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Calls in a Go file, attributed to `Type.Method` for methods and the bare
+/// name for functions, with calls on the method's receiver qualified the
+/// same way (see [`qualify_callee_name`]).
+fn go_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let mut references = Vec::new();
+    let mut stack = vec![(tree.root_node(), None::<String>, HashMap::new())];
+    while let Some((node, caller, receivers)) = stack.pop() {
+        let (caller, receivers) = match node.kind() {
+            "function_declaration" => (
+                node.child_by_field_name("name")
+                    .map(|n| content[n.byte_range()].to_string()),
+                HashMap::new(),
+            ),
+            "method_declaration" => {
+                let receivers = go_receiver_types(node, content);
+                let name = node
+                    .child_by_field_name("name")
+                    .map(|n| &content[n.byte_range()]);
+                let owner = receivers.values().next();
+                let caller = match (owner, name) {
+                    (Some(owner), Some(name)) => Some(format!("{}.{}", owner, name)),
+                    (None, name) => name.map(str::to_string),
+                    (_, None) => None,
+                };
+                (caller, receivers)
+            }
+            _ => (caller, receivers),
+        };
+        if node.kind() == "call_expression" {
+            let callee = node
+                .child_by_field_name("function")
+                .and_then(|f| qualify_callee_name(f, content, None, &receivers));
+            if let Some(callee) = callee {
+                let position = node.start_position();
+                references.push(SymbolReference::new(
+                    caller.clone(),
+                    callee,
+                    file_path.to_string(),
+                    file_path.to_string(),
+                    position.row as u32 + 1,
+                    position.column as u32 + 1,
+                    ReferenceKind::Call,
+                    Language::Go,
+                    repository_id.to_string(),
+                ));
+            }
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            stack.push((child, caller.clone(), receivers.clone()));
+        }
+    }
+    references
+}
+
+/// Receiver name → type name of a Go method: `func (h *Handler) …` →
+/// `h` → `Handler`, with pointers and type parameters (`*Cache[K, V]`)
+/// stripped. An unnamed receiver (`func (Handler) …`) still names the type,
+/// keyed by the empty string.
+fn go_receiver_types(method: Node, content: &str) -> HashMap<String, String> {
+    let mut types = HashMap::new();
+    let Some(receiver) = method.child_by_field_name("receiver") else {
+        return types;
+    };
+    let mut cursor = receiver.walk();
+    for parameter in receiver.named_children(&mut cursor) {
+        let Some(ty) = parameter.child_by_field_name("type") else {
+            continue;
+        };
+        let ty = content[ty.byte_range()].trim_start_matches('*');
+        let ty = ty.split('[').next().unwrap_or(ty).trim();
+        let name = parameter
+            .child_by_field_name("name")
+            .map_or("", |n| &content[n.byte_range()]);
+        types.insert(name.to_string(), ty.to_string());
+    }
+    types
+}
+
+/// Calls, `new` expressions, imports and `extends` clauses in a Java file.
+/// Calls and instantiations are attributed to the enclosing member
+/// (`Type#method`, or the type itself in a field initializer) and qualified
+/// by [`qualify_callee_name`]; `implements` clauses are left to
+/// [`implementation_references`].
+fn java_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let reference = |caller: Option<String>, callee: String, node: Node, kind| {
+        let position = node.start_position();
+        SymbolReference::new(
+            caller,
+            callee,
+            file_path.to_string(),
+            file_path.to_string(),
+            position.row as u32 + 1,
+            position.column as u32 + 1,
+            kind,
+            Language::Java,
+            repository_id.to_string(),
+        )
+    };
+
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "method_invocation" => {
+                let enclosing = enclosing_class_name(node, content);
+                if let Some(callee) =
+                    qualify_callee_name(node, content, enclosing.as_deref(), &HashMap::new())
+                {
+                    references.push(reference(
+                        enclosing_class_member(node, content),
+                        callee,
+                        node,
+                        ReferenceKind::Call,
+                    ));
+                }
+            }
+            "object_creation_expression" => {
+                if let Some(ty) = node.child_by_field_name("type") {
+                    references.push(reference(
+                        enclosing_class_member(node, content),
+                        base_type_name(&content[ty.byte_range()], ".").to_string(),
+                        node,
+                        ReferenceKind::Instantiation,
+                    ));
+                }
+            }
+            // `import java.util.List;`, `import static org.junit.Assert.*;`
+            "import_declaration" => {
+                let path: String = content[node.byte_range()]
+                    .trim_start_matches("import")
+                    .trim_end_matches(';')
+                    .trim()
+                    .trim_start_matches("static ")
+                    .split_whitespace()
+                    .collect();
+                references.push(reference(None, path, node, ReferenceKind::Import));
+            }
+            // `class B extends A`, `interface B extends A, C`
+            "superclass" | "extends_interfaces" => {
+                let owner = node
+                    .parent()
+                    .and_then(|p| p.child_by_field_name("name"))
+                    .map(|n| content[n.byte_range()].to_string());
+                for parent in java_type_list(node) {
+                    references.push(reference(
+                        owner.clone(),
+                        base_type_name(&content[parent.byte_range()], ".").to_string(),
+                        parent,
+                        ReferenceKind::Inheritance,
+                    ));
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// The types named by a Java `superclass`, `super_interfaces` or
+/// `extends_interfaces` clause.
+fn java_type_list(clause: Node) -> Vec<Node> {
+    let mut cursor = clause.walk();
+    let children: Vec<Node> = clause.named_children(&mut cursor).collect();
+    children
+        .into_iter()
+        .flat_map(|child| {
+            if child.kind() == "type_list" {
+                let mut cursor = child.walk();
+                child.named_children(&mut cursor).collect()
+            } else {
+                vec![child]
+            }
+        })
+        .collect()
+}
+
+/// Calls, `new` expressions, `using` directives, base classes and LINQ
+/// query clauses in a C# file, attributed like Java's (see
+/// [`java_references`]). Query syntax
+/// is recorded as calls to the methods it compiles to: `where` → `Where`,
+/// `orderby x descending` → `OrderByDescending`.
+fn csharp_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let reference = |caller: Option<String>, callee: String, node: Node, kind| {
+        let position = node.start_position();
+        SymbolReference::new(
+            caller,
+            callee,
+            file_path.to_string(),
+            file_path.to_string(),
+            position.row as u32 + 1,
+            position.column as u32 + 1,
+            kind,
+            Language::CSharp,
+            repository_id.to_string(),
+        )
+    };
+
+    let interfaces = csharp_interface_names(tree.root_node(), content);
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "invocation_expression" => {
+                let enclosing = enclosing_class_name(node, content);
+                let callee = node.child_by_field_name("function").and_then(|f| {
+                    qualify_callee_name(f, content, enclosing.as_deref(), &HashMap::new())
+                });
+                if let Some(callee) = callee {
+                    references.push(reference(
+                        enclosing_class_member(node, content),
+                        callee,
+                        node,
+                        ReferenceKind::Call,
+                    ));
+                }
+            }
+            "object_creation_expression" => {
+                if let Some(ty) = node.child_by_field_name("type") {
+                    references.push(reference(
+                        enclosing_class_member(node, content),
+                        base_type_name(&content[ty.byte_range()], ".").to_string(),
+                        node,
+                        ReferenceKind::Instantiation,
+                    ));
+                }
+            }
+            // `using System.Linq;`, `using static System.Math;`,
+            // `using Geo = Example.Geometry;` (recorded as its target)
+            "using_directive" => {
+                let alias = node.child_by_field_name("name").map(|n| n.id());
+                let mut cursor = node.walk();
+                let target = node
+                    .named_children(&mut cursor)
+                    .find(|c| Some(c.id()) != alias && c.kind() != "comment");
+                if let Some(target) = target {
+                    references.push(reference(
+                        None,
+                        content[target.byte_range()].to_string(),
+                        node,
+                        ReferenceKind::Import,
+                    ));
+                }
+            }
+            // `class ScaledCircle : Circle, IComparable<T>`: the base class
+            // is inherited from; interfaces are implemented, which
+            // `implementation_references` records. An interface's bases are
+            // all inherited.
+            "base_list" => {
+                let Some(owner) = node.parent() else {
+                    continue;
+                };
+                let owner_name = owner
+                    .child_by_field_name("name")
+                    .map(|n| content[n.byte_range()].to_string());
+                for (name, base) in csharp_bases(node, content) {
+                    if owner.kind() == "interface_declaration"
+                        || !csharp_is_interface(&name, &interfaces)
+                    {
+                        references.push(reference(
+                            owner_name.clone(),
+                            name,
+                            base,
+                            ReferenceKind::Inheritance,
+                        ));
+                    }
+                }
+            }
+            "where_clause" | "select_clause" | "order_by_clause" | "group_clause"
+            | "join_clause" | "let_clause" => {
+                let method = match node.kind() {
+                    "where_clause" => "Where",
+                    "select_clause" | "let_clause" => "Select",
+                    "group_clause" => "GroupBy",
+                    "join_clause" => "Join",
+                    _ if content[node.byte_range()]
+                        .split_whitespace()
+                        .last()
+                        .is_some_and(|w| w == "descending") =>
+                    {
+                        "OrderByDescending"
+                    }
+                    _ => "OrderBy",
+                };
+                references.push(reference(
+                    enclosing_class_member(node, content),
+                    method.to_string(),
+                    node,
+                    ReferenceKind::Call,
+                ));
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// The types in a C# `base_list`, with generics and namespaces stripped:
+/// `: Base, IComparable<T>, Acme.IShape` → `Base`, `IComparable`, `IShape`.
+fn csharp_bases<'t>(base_list: Node<'t>, content: &str) -> Vec<(String, Node<'t>)> {
+    let mut cursor = base_list.walk();
+    base_list
+        .named_children(&mut cursor)
+        .filter_map(|base| {
+            // A record's `: Base(X)` passes its primary constructor's
+            // parameters on.
+            let ty = match base.kind() {
+                "primary_constructor_base_type" => base.named_child(0)?,
+                "identifier" | "generic_name" | "qualified_name" => base,
+                _ => return None,
+            };
+            let name = base_type_name(&content[ty.byte_range()], ".");
+            Some((name.to_string(), base))
+        })
+        .collect()
+}
+
+/// Names of the interfaces declared in a C# file.
+fn csharp_interface_names(root: Node, content: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "interface_declaration" {
+            if let Some(name) = node.child_by_field_name("name") {
+                names.insert(content[name.byte_range()].to_string());
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    names
+}
+
+/// Whether a C# base type is an interface: one declared in the file, or
+/// one following the .NET `IName` convention (`IDisposable`, `IComparable`),
+/// since a base list does not say which entry is the class.
+fn csharp_is_interface(name: &str, declared: &HashSet<String>) -> bool {
+    let mut chars = name.chars();
+    declared.contains(name)
+        || (chars.next() == Some('I') && chars.next().is_some_and(|c| c.is_ascii_uppercase()))
+}
+
+/// Java and C# type declarations, whose members are named `Type#member`.
+fn is_class_like_declaration(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "class_declaration"
+            | "interface_declaration"
+            | "enum_declaration"
+            | "record_declaration"
+            | "struct_declaration"
+    )
+}
+
+/// Name of the innermost Java or C# type declaration containing `node`.
+fn enclosing_class_name(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if is_class_like_declaration(candidate) {
+            return candidate
+                .child_by_field_name("name")
+                .map(|n| content[n.byte_range()].to_string());
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Caller of a Java or C# reference: the enclosing method, constructor or
+/// property as `Type#member`, or the type itself outside any member (field
+/// initializers, base lists).
+fn enclosing_class_member(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if matches!(
+            candidate.kind(),
+            "method_declaration" | "constructor_declaration" | "property_declaration"
+        ) {
+            let name = &content[candidate.child_by_field_name("name")?.byte_range()];
+            return Some(match enclosing_class_name(candidate, content) {
+                Some(owner) => format!("{}#{}", owner, name),
+                None => name.to_string(),
+            });
+        }
+        if is_class_like_declaration(candidate) {
+            return candidate
+                .child_by_field_name("name")
+                .map(|n| content[n.byte_range()].to_string());
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// `Type#member` for a Java or C# call on `this` (the enclosing type) or on
+/// a receiver whose last segment is upper-case and therefore taken to be a
+/// class (`Circle.fromDiameter()`, `java.util.Objects.hash()`); the bare
+/// member for any other receiver, whose type is unknown here.
+fn qualify_class_member(
+    receiver: Option<Node>,
+    member: &str,
+    content: &str,
+    enclosing: Option<&str>,
+) -> String {
+    let owner = receiver.and_then(|receiver| match receiver.kind() {
+        "this" => enclosing,
+        _ => {
+            let text = &content[receiver.byte_range()];
+            let last = text.rsplit('.').next().unwrap_or(text);
+            (last.starts_with(|c: char| c.is_ascii_uppercase())
+                && last.chars().all(|c| c.is_alphanumeric() || c == '_'))
+            .then_some(last)
+        }
+    });
+    match owner {
+        Some(owner) => format!("{}#{}", owner, member),
+        None => member.to_string(),
+    }
+}
+
+/// Kernel macros and special forms that look like local calls in an Elixir
+/// syntax tree but are not functions of the module.
+const ELIXIR_SPECIAL_FORMS: &[&str] = &[
+    "case",
+    "cond",
+    "defexception",
+    "defoverridable",
+    "defstruct",
+    "fn",
+    "for",
+    "if",
+    "quote",
+    "raise",
+    "receive",
+    "reraise",
+    "super",
+    "throw",
+    "try",
+    "unless",
+    "unquote",
+    "unquote_splicing",
+    "with",
+];
+
+/// Module, function and name tables in effect at a point of an Elixir file.
+#[derive(Clone, Default)]
+struct ElixirScope {
+    /// The enclosing `defmodule`/`defprotocol`/`defimpl`, as written.
+    module: Option<String>,
+    /// Call-graph name of the enclosing `def` (`Shapes.Circle.area`).
+    function: Option<String>,
+    /// `alias` short name → full module name.
+    aliases: HashMap<String, String>,
+    /// Function name → module, for `import Mod, only: [f: 1]`.
+    imports: HashMap<String, String>,
+    /// Functions defined in the enclosing module.
+    functions: HashSet<String>,
+}
+
+impl ElixirScope {
+    /// The caller of a reference: the enclosing function, or the module for
+    /// module-level code.
+    fn caller(&self) -> Option<String> {
+        self.function.clone().or_else(|| self.module.clone())
+    }
+
+    /// `Circle.area` with `alias Shapes.Circle` in effect → `Shapes.Circle`.
+    fn expand(&self, module: &str) -> String {
+        let (head, rest) = match module.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (module, None),
+        };
+        match (self.aliases.get(head), rest) {
+            (Some(full), Some(rest)) => format!("{}.{}", full, rest),
+            (Some(full), None) => full.clone(),
+            (None, _) => module.to_string(),
+        }
+    }
+}
+
+/// Calls, pipelines, captures, `import`/`alias`/`use`/`require` edges and
+/// behaviour and protocol implementations in an Elixir file. Calls are
+/// attributed to the enclosing `def` (`Module.function`) and qualified by
+/// module: `Map.update!` through the file's aliases, local calls to
+/// functions of the same module as `Module.function`, imported functions by
+/// their `only:` list, and anything else by its bare name.
+fn elixir_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let mut references = Vec::new();
+    let mut scope = ElixirScope::default();
+    elixir_walk(
+        tree.root_node(),
+        content,
+        &mut scope,
+        &mut |caller, callee, node: Node, kind| {
+            let position = node.start_position();
+            references.push(SymbolReference::new(
+                caller,
+                callee,
+                file_path.to_string(),
+                file_path.to_string(),
+                position.row as u32 + 1,
+                position.column as u32 + 1,
+                kind,
+                Language::Elixir,
+                repository_id.to_string(),
+            ));
+        },
+    );
+    references
+}
+
+/// Visits `node` for [`elixir_references`]. `alias` and `import` update
+/// `scope` in place, so they apply to the rest of the enclosing module.
+fn elixir_walk(
+    node: Node,
+    content: &str,
+    scope: &mut ElixirScope,
+    emit: &mut dyn FnMut(Option<String>, String, Node, ReferenceKind),
+) {
+    let text = |n: Node| content[n.byte_range()].to_string();
+    match node.kind() {
+        "call" => {
+            let Some(target) = node.child_by_field_name("target") else {
+                return;
+            };
+            let arguments = node
+                .named_children(&mut node.walk())
+                .find(|c| c.kind() == "arguments");
+            let args: Vec<Node> = arguments
+                .map(|a| a.named_children(&mut a.walk()).collect())
+                .unwrap_or_default();
+            if target.kind() == "dot" {
+                let callee = match (
+                    target.child_by_field_name("left"),
+                    target.child_by_field_name("right"),
+                ) {
+                    (Some(left), Some(right)) if right.kind() == "identifier" => {
+                        match left.kind() {
+                            "alias" => {
+                                Some(format!("{}.{}", scope.expand(&text(left)), text(right)))
+                            }
+                            "atom" => Some(format!("{}.{}", text(left), text(right))),
+                            "identifier" if text(left) == "__MODULE__" => scope
+                                .module
+                                .as_ref()
+                                .map(|m| format!("{}.{}", m, text(right))),
+                            // `map.key` is field access; `var.fun()` is
+                            // dynamic dispatch the tree cannot resolve.
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if let Some(callee) = callee {
+                    emit(scope.caller(), callee, node, ReferenceKind::Call);
+                }
+                elixir_walk_children(node, content, scope, emit, Some(target));
+                return;
+            }
+            if target.kind() != "identifier" {
+                elixir_walk_children(node, content, scope, emit, None);
+                return;
+            }
+            let keyword = text(target);
+            match keyword.as_str() {
+                "defmodule" | "defprotocol" | "defimpl" => {
+                    let Some(name) = args.first().filter(|a| a.kind() == "alias") else {
+                        return;
+                    };
+                    let module = text(*name);
+                    if keyword == "defimpl" {
+                        // `defimpl Proto, for: Type` outside `Type` names it;
+                        // inside, the enclosing module implements it.
+                        let implementor = elixir_keyword(&args, "for", content)
+                            .map(|t| scope.expand(&t))
+                            .or_else(|| scope.module.clone());
+                        if let Some(implementor) = implementor {
+                            emit(
+                                Some(implementor),
+                                scope.expand(&module),
+                                *name,
+                                ReferenceKind::Implementation,
+                            );
+                        }
+                    }
+                    let mut inner = ElixirScope {
+                        module: Some(module),
+                        function: None,
+                        aliases: scope.aliases.clone(),
+                        imports: scope.imports.clone(),
+                        functions: HashSet::new(),
+                    };
+                    if let Some(body) = elixir_do_block(node) {
+                        inner.functions = elixir_module_functions(body, content);
+                        elixir_walk_children(body, content, &mut inner, emit, None);
+                    }
+                }
+                "def" | "defp" | "defmacro" | "defmacrop" | "defguard" | "defguardp" => {
+                    let name = args
+                        .first()
+                        .and_then(|head| elixir_def_name(*head, content));
+                    let mut inner = scope.clone();
+                    inner.function = name.map(|name| match &scope.module {
+                        Some(module) => format!("{}.{}", module, name),
+                        None => name,
+                    });
+                    // The head and its guard are patterns, not calls; only
+                    // the `do:` keyword or block holds the body.
+                    for arg in args.iter().skip(1) {
+                        elixir_walk(*arg, content, &mut inner, emit);
+                    }
+                    if let Some(body) = elixir_do_block(node) {
+                        elixir_walk(body, content, &mut inner, emit);
+                    }
+                }
+                "alias" => {
+                    for (full, short) in elixir_aliases(&args, content) {
+                        let full = scope.expand(&full);
+                        emit(scope.caller(), full.clone(), node, ReferenceKind::Import);
+                        scope.aliases.insert(short, full);
+                    }
+                }
+                "import" | "require" | "use" => {
+                    let Some(module) = args.first().filter(|a| a.kind() == "alias") else {
+                        return;
+                    };
+                    let module = scope.expand(&text(*module));
+                    if keyword == "import" {
+                        for function in elixir_import_only(&args, content) {
+                            scope.imports.insert(function, module.clone());
+                        }
+                    }
+                    emit(scope.caller(), module, node, ReferenceKind::Import);
+                }
+                _ if ELIXIR_SPECIAL_FORMS.contains(&keyword.as_str()) => {
+                    elixir_walk_children(node, content, scope, emit, Some(target));
+                }
+                _ => {
+                    emit(
+                        scope.caller(),
+                        elixir_local_callee(&keyword, scope),
+                        node,
+                        ReferenceKind::Call,
+                    );
+                    elixir_walk_children(node, content, scope, emit, Some(target));
+                }
+            }
+        }
+        "unary_operator" => {
+            let operator = node.child_by_field_name("operator").map(text);
+            let Some(operand) = node.child_by_field_name("operand") else {
+                return;
+            };
+            match operator.as_deref() {
+                // Module attributes are data, except `@behaviour`.
+                Some("@") => {
+                    let is_behaviour = operand
+                        .child_by_field_name("target")
+                        .is_some_and(|t| text(t) == "behaviour");
+                    let behaviour = operand
+                        .named_children(&mut operand.walk())
+                        .find(|c| c.kind() == "arguments")
+                        .and_then(|a| a.named_child(0))
+                        .filter(|a| a.kind() == "alias");
+                    if let (true, Some(behaviour), Some(module)) =
+                        (is_behaviour, behaviour, scope.module.clone())
+                    {
+                        emit(
+                            Some(module),
+                            scope.expand(&text(behaviour)),
+                            behaviour,
+                            ReferenceKind::Implementation,
+                        );
+                    }
+                }
+                // `&validate/1` captures a local function; `&Mod.f/1` is a
+                // `call` on the left of `/` and is visited as one.
+                Some("&") => {
+                    let local = (operand.kind() == "binary_operator")
+                        .then(|| operand.child_by_field_name("left"))
+                        .flatten()
+                        .filter(|left| left.kind() == "identifier");
+                    match local {
+                        Some(left) => emit(
+                            scope.caller(),
+                            elixir_local_callee(&text(left), scope),
+                            left,
+                            ReferenceKind::Call,
+                        ),
+                        None => elixir_walk(operand, content, scope, emit),
+                    }
+                }
+                _ => elixir_walk(operand, content, scope, emit),
+            }
+        }
+        _ => elixir_walk_children(node, content, scope, emit, None),
+    }
+}
+
+/// Visits the children of `node` other than `skip` (a call's target).
+fn elixir_walk_children(
+    node: Node,
+    content: &str,
+    scope: &mut ElixirScope,
+    emit: &mut dyn FnMut(Option<String>, String, Node, ReferenceKind),
+    skip: Option<Node>,
+) {
+    let children: Vec<Node> = node.named_children(&mut node.walk()).collect();
+    for child in children {
+        if Some(child.id()) != skip.map(|s| s.id()) {
+            elixir_walk(child, content, scope, emit);
+        }
+    }
+}
+
+/// The callee of a local call: `Module.name` for a function defined in the
+/// enclosing module, `Imported.name` for one listed in an `import … only:`,
+/// the bare name otherwise (a `Kernel` function or a macro's import).
+fn elixir_local_callee(name: &str, scope: &ElixirScope) -> String {
+    match (&scope.module, scope.imports.get(name)) {
+        (Some(module), _) if scope.functions.contains(name) => format!("{}.{}", module, name),
+        (_, Some(imported)) => format!("{}.{}", imported, name),
+        _ => name.to_string(),
+    }
+}
+
+/// The `do … end` block of an Elixir call.
+fn elixir_do_block(call: Node) -> Option<Node> {
+    let mut cursor = call.walk();
+    let block = call
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "do_block");
+    block
+}
+
+/// The function a `def` head names: `area(shape)`, `new(r) when …`, `unit`.
+fn elixir_def_name(head: Node, content: &str) -> Option<String> {
+    let head = match head.kind() {
+        "binary_operator" => head.child_by_field_name("left")?,
+        _ => head,
+    };
+    let name = match head.kind() {
+        "call" => head.child_by_field_name("target")?,
+        "identifier" => head,
+        _ => return None,
+    };
+    (name.kind() == "identifier").then(|| content[name.byte_range()].to_string())
+}
+
+/// Names of the functions and macros defined directly in a module body.
+fn elixir_module_functions(body: Node, content: &str) -> HashSet<String> {
+    let mut cursor = body.walk();
+    body.named_children(&mut cursor)
+        .filter(|c| c.kind() == "call")
+        .filter(|c| {
+            c.child_by_field_name("target").is_some_and(|t| {
+                matches!(
+                    &content[t.byte_range()],
+                    "def" | "defp" | "defmacro" | "defmacrop" | "defguard" | "defguardp"
+                )
+            })
+        })
+        .filter_map(|c| {
+            let arguments = c
+                .named_children(&mut c.walk())
+                .find(|a| a.kind() == "arguments")?;
+            elixir_def_name(arguments.named_child(0)?, content)
+        })
+        .collect()
+}
+
+/// The alias value of keyword `key` among a call's arguments (`for: Type`).
+fn elixir_keyword(args: &[Node], key: &str, content: &str) -> Option<String> {
+    let keywords = args.iter().find(|a| a.kind() == "keywords")?;
+    let mut cursor = keywords.walk();
+    let found = keywords.named_children(&mut cursor).find_map(|pair| {
+        let name = &content[pair.child_by_field_name("key")?.byte_range()];
+        let value = pair.child_by_field_name("value")?;
+        (name.trim_end().trim_end_matches(':') == key && value.kind() == "alias")
+            .then(|| content[value.byte_range()].to_string())
+    });
+    found
+}
+
+/// `(full, short)` names bound by an `alias`: `alias Shapes.Circle` →
+/// `("Shapes.Circle", "Circle")`, `alias Shapes.{Circle, Square}` → one
+/// pair each, `alias Foo.Bar, as: Baz` → `("Foo.Bar", "Baz")`.
+fn elixir_aliases(args: &[Node], content: &str) -> Vec<(String, String)> {
+    let short = |full: &str| full.rsplit('.').next().unwrap_or(full).to_string();
+    let Some(first) = args.first() else {
+        return Vec::new();
+    };
+    match first.kind() {
+        "alias" => {
+            let full = content[first.byte_range()].to_string();
+            let name = elixir_keyword(args, "as", content).unwrap_or_else(|| short(&full));
+            vec![(full, name)]
+        }
+        "dot" => {
+            let (Some(prefix), Some(tuple)) = (
+                first.child_by_field_name("left"),
+                first.child_by_field_name("right"),
+            ) else {
+                return Vec::new();
+            };
+            let prefix = &content[prefix.byte_range()];
+            let mut cursor = tuple.walk();
+            let aliases = tuple
+                .named_children(&mut cursor)
+                .filter(|c| c.kind() == "alias")
+                .map(|c| {
+                    let full = format!("{}.{}", prefix, &content[c.byte_range()]);
+                    let name = short(&full);
+                    (full, name)
+                })
+                .collect();
+            aliases
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Function names in an `import Mod, only: [f: 1, g: 2]` list.
+fn elixir_import_only(args: &[Node], content: &str) -> Vec<String> {
+    let Some(keywords) = args.iter().find(|a| a.kind() == "keywords") else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    let mut stack = vec![*keywords];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "pair" {
+            let key = node
+                .child_by_field_name("key")
+                .map(|k| content[k.byte_range()].trim_end().trim_end_matches(':'));
+            if key == Some("only") {
+                if let Some(list) = node.child_by_field_name("value") {
+                    let mut cursor = list.walk();
+                    for entry in list.named_children(&mut cursor) {
+                        let mut entry_cursor = entry.walk();
+                        for pair in entry.named_children(&mut entry_cursor) {
+                            if let Some(key) = pair.child_by_field_name("key") {
+                                names.push(
+                                    content[key.byte_range()]
+                                        .trim_end()
+                                        .trim_end_matches(':')
+                                        .to_string(),
+                                );
+                            }
+                        }
+                    }
+                }
+                continue;
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    names
+}
+
+/// Calls, `require` imports and metatable links in a Lua file. Calls are
+/// attributed to the enclosing function (`Table.fn`, whether declared with
+/// `.` or `:`) and qualified by table:
+///
+/// - `self:deposit()` / `self.helper()` → `Enclosing.deposit` / `.helper`
+/// - `Account.new()` / `json.encode()` → `Account.new` / `json.encode`
+/// - `account:deposit()` → `Account.deposit` when `account` was created by
+///   `Account.new(…)` or `setmetatable(…, Account)` in the file, `deposit`
+///   otherwise
+///
+/// `local Sub = setmetatable({}, { __index = Base })` and
+/// `Sub.__index = Base` are [`ReferenceKind::Inheritance`] edges;
+/// `setmetatable(obj, Account)` elsewhere instantiates `Account`.
+fn lua_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let text = |node: Node| &content[node.byte_range()];
+    let reference = |caller: Option<String>, callee: String, node: Node, kind| {
+        let position = node.start_position();
+        SymbolReference::new(
+            caller,
+            callee,
+            file_path.to_string(),
+            file_path.to_string(),
+            position.row as u32 + 1,
+            position.column as u32 + 1,
+            kind,
+            Language::Lua,
+            repository_id.to_string(),
+        )
+    };
+
+    let instances = lua_instances(tree.root_node(), content);
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "function_call" => {
+                let caller = lua_enclosing_function(node, content);
+                let arguments: Vec<Node> = node
+                    .child_by_field_name("arguments")
+                    .map(|a| a.named_children(&mut a.walk()).collect())
+                    .unwrap_or_default();
+                let Some(name) = node.child_by_field_name("name") else {
+                    continue;
+                };
+                match (name.kind(), text(name)) {
+                    ("identifier", "require") => {
+                        let module = arguments
+                            .first()
+                            .filter(|a| a.kind() == "string")
+                            .map(|a| unquote(text(*a)).to_string());
+                        if let Some(module) = module {
+                            references.push(reference(caller, module, node, ReferenceKind::Import));
+                        }
+                    }
+                    ("identifier", "setmetatable") => {
+                        let metatable = arguments.get(1).and_then(|m| lua_metatable(*m, content));
+                        if let Some(metatable) = metatable {
+                            match lua_assigned_table(node, content) {
+                                Some(table) if table != metatable => references.push(reference(
+                                    Some(table),
+                                    metatable,
+                                    node,
+                                    ReferenceKind::Inheritance,
+                                )),
+                                _ => references.push(reference(
+                                    caller,
+                                    metatable,
+                                    node,
+                                    ReferenceKind::Instantiation,
+                                )),
+                            }
+                        }
+                    }
+                    ("identifier", callee) => {
+                        references.push(reference(
+                            caller,
+                            callee.to_string(),
+                            node,
+                            ReferenceKind::Call,
+                        ));
+                    }
+                    ("dot_index_expression" | "method_index_expression", _) => {
+                        let table = name.child_by_field_name("table");
+                        let member = name
+                            .child_by_field_name("field")
+                            .or_else(|| name.child_by_field_name("method"));
+                        if let (Some(table), Some(member)) = (table, member) {
+                            let owner = match (table.kind(), text(table)) {
+                                ("identifier", "self") => caller
+                                    .as_deref()
+                                    .and_then(|c| c.rsplit_once('.'))
+                                    .map(|(owner, _)| owner.to_string()),
+                                ("identifier", receiver)
+                                    if name.kind() == "method_index_expression" =>
+                                {
+                                    instances.get(receiver).cloned()
+                                }
+                                ("identifier", receiver) => Some(receiver.to_string()),
+                                _ => None,
+                            };
+                            let callee = match owner {
+                                Some(owner) => format!("{}.{}", owner, text(member)),
+                                None => text(member).to_string(),
+                            };
+                            references.push(reference(caller, callee, node, ReferenceKind::Call));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            // `Savings.__index = Account`; `Account.__index = Account` only
+            // makes the table its own metatable.
+            "assignment_statement" => {
+                let target = node
+                    .named_child(0)
+                    .filter(|v| v.kind() == "variable_list")
+                    .and_then(|v| v.named_child(0))
+                    .filter(|t| t.kind() == "dot_index_expression");
+                let value = node
+                    .named_child(1)
+                    .and_then(|e| e.named_child(0))
+                    .filter(|v| v.kind() == "identifier");
+                if let (Some(target), Some(value)) = (target, value) {
+                    let table = target.child_by_field_name("table");
+                    let field = target.child_by_field_name("field").map(text);
+                    if let (Some(table), Some("__index")) = (table, field) {
+                        if text(table) != text(value) {
+                            references.push(reference(
+                                Some(text(table).to_string()),
+                                text(value).to_string(),
+                                node,
+                                ReferenceKind::Inheritance,
+                            ));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// Call-graph name of the Lua function declaration containing `node`:
+/// `Account.deposit` for `function Account:deposit()`, the bare name for
+/// plain functions. Anonymous functions belong to their enclosing
+/// declaration.
+fn lua_enclosing_function(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if candidate.kind() == "function_declaration" {
+            let name = candidate.child_by_field_name("name")?;
+            return Some(content[name.byte_range()].replace(':', "."));
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// The table a metatable argument points lookups at: `Account` in
+/// `setmetatable(o, Account)` and `setmetatable(o, { __index = Account })`.
+fn lua_metatable(metatable: Node, content: &str) -> Option<String> {
+    match metatable.kind() {
+        "identifier" => Some(content[metatable.byte_range()].to_string()),
+        "table_constructor" => {
+            let mut cursor = metatable.walk();
+            let index = metatable.named_children(&mut cursor).find(|field| {
+                field
+                    .child_by_field_name("name")
+                    .is_some_and(|n| &content[n.byte_range()] == "__index")
+            })?;
+            let value = index
+                .child_by_field_name("value")
+                .filter(|v| v.kind() == "identifier")?;
+            Some(content[value.byte_range()].to_string())
+        }
+        _ => None,
+    }
+}
+
+/// The table variable a call's result is assigned to outside any function,
+/// `Savings` in `local Savings = setmetatable({}, …)`.
+fn lua_assigned_table(call: Node, content: &str) -> Option<String> {
+    let assignment = call.parent()?.parent()?;
+    if assignment.kind() != "assignment_statement"
+        || lua_enclosing_function(assignment, content).is_some()
+    {
+        return None;
+    }
+    let variable = assignment.named_child(0)?.named_child(0)?;
+    (variable.kind() == "identifier").then(|| content[variable.byte_range()].to_string())
+}
+
+/// Variables holding instances of a table, `account` → `Account` for
+/// `local account = Account.new(…)` or `= setmetatable(…, Account)`.
+/// `self` is left out: it always means the enclosing table.
+fn lua_instances(root: Node, content: &str) -> HashMap<String, String> {
+    let mut instances = HashMap::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "assignment_statement" {
+            let variable = node
+                .named_child(0)
+                .and_then(|v| v.named_child(0))
+                .filter(|v| v.kind() == "identifier");
+            let value = node
+                .named_child(1)
+                .and_then(|e| e.named_child(0))
+                .filter(|v| v.kind() == "function_call");
+            if let (Some(variable), Some(value)) = (variable, value) {
+                let name = value.child_by_field_name("name");
+                let table = match name.map(|n| (n.kind(), n)) {
+                    Some(("dot_index_expression", name))
+                        if name
+                            .child_by_field_name("field")
+                            .is_some_and(|f| &content[f.byte_range()] == "new") =>
+                    {
+                        name.child_by_field_name("table")
+                            .filter(|t| t.kind() == "identifier")
+                            .map(|t| content[t.byte_range()].to_string())
+                    }
+                    Some(("identifier", name)) if &content[name.byte_range()] == "setmetatable" => {
+                        value
+                            .child_by_field_name("arguments")
+                            .and_then(|a| a.named_child(1))
+                            .and_then(|m| lua_metatable(m, content))
+                    }
+                    _ => None,
+                };
+                let variable = &content[variable.byte_range()];
+                if let (Some(table), false) = (table, variable == "self") {
+                    instances.insert(variable.to_string(), table);
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    instances
+}
+
+/// `Kernel` and `Module` methods called without a receiver in Ruby
+/// (`puts`, `raise`, `attr_reader`); they would otherwise show up as calls
+/// to methods of the enclosing class.
+const RUBY_KERNEL_METHODS: &[&str] = &[
+    "attr_accessor",
+    "attr_reader",
+    "attr_writer",
+    "define_method",
+    "fail",
+    "format",
+    "lambda",
+    "loop",
+    "module_function",
+    "new",
+    "p",
+    "pp",
+    "print",
+    "printf",
+    "private",
+    "private_constant",
+    "proc",
+    "protected",
+    "public",
+    "puts",
+    "raise",
+    "sprintf",
+];
+
+/// Calls, constant references, `require` imports and mixins in a Ruby file.
+/// References are attributed to the enclosing method (`Class#method`, for
+/// `def self.x` too) or, in a class body, to the class. Calls are qualified
+/// by receiver:
+///
+/// - `Billing.currency` / `self.total` → `Billing#currency` / `Owner#total`
+/// - `describe` / `describe()` → `Owner#describe` when the enclosing class
+///   defines it, the bare name otherwise; `Kernel` methods are skipped
+/// - `invoice.add_line` → `add_line`, the receiver's class being unknown
+/// - `Invoice.new` / `new` in a class → instantiation of `Invoice` / the class
+///
+/// `include`/`extend`/`prepend` are [`ReferenceKind::Implementation`] edges
+/// from the class or module, `class A < B` an inheritance edge.
+fn ruby_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let text = |node: Node| &content[node.byte_range()];
+    let reference = |caller: Option<String>, callee: String, node: Node, kind| {
+        let position = node.start_position();
+        SymbolReference::new(
+            caller,
+            callee,
+            file_path.to_string(),
+            file_path.to_string(),
+            position.row as u32 + 1,
+            position.column as u32 + 1,
+            kind,
+            Language::Ruby,
+            repository_id.to_string(),
+        )
+    };
+
+    let mut methods = HashSet::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "method" | "singleton_method") {
+            if let Some(name) = ruby_method_name(node, content) {
+                methods.insert(name);
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    // A call on the implicit receiver targets the enclosing class's method
+    // when it has one.
+    let implicit = |name: &str, node: Node| match ruby_owner(node, content) {
+        Some(owner) if methods.contains(&format!("{}#{}", owner, name)) => {
+            format!("{}#{}", owner, name)
+        }
+        _ => name.to_string(),
+    };
+
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "call" => {
+                let caller = ruby_caller(node, content);
+                let Some(method) = node.child_by_field_name("method") else {
+                    continue;
+                };
+                let method = text(method);
+                let receiver = node.child_by_field_name("receiver");
+                let argument = node
+                    .child_by_field_name("arguments")
+                    .and_then(|a| a.named_child(0));
+                match (receiver.map(|r| (r.kind(), r)), method) {
+                    (None, "require" | "require_relative") => {
+                        let path = argument
+                            .filter(|a| a.kind() == "string")
+                            .map(|a| unquote(text(a)).to_string());
+                        if let Some(path) = path {
+                            references.push(reference(caller, path, node, ReferenceKind::Import));
+                        }
+                    }
+                    (None, _) if ruby_is_mixin(node, content) => {
+                        let mixins: Vec<Node> = node
+                            .child_by_field_name("arguments")
+                            .map(|a| a.named_children(&mut a.walk()).collect())
+                            .unwrap_or_default();
+                        for mixin in mixins {
+                            if matches!(mixin.kind(), "constant" | "scope_resolution") {
+                                references.push(reference(
+                                    ruby_owner(node, content),
+                                    base_type_name(text(mixin), "::").to_string(),
+                                    mixin,
+                                    ReferenceKind::Implementation,
+                                ));
+                            }
+                        }
+                    }
+                    (None, "new") => {
+                        if let Some(owner) = ruby_owner(node, content) {
+                            references.push(reference(
+                                caller,
+                                base_type_name(&owner, "::").to_string(),
+                                node,
+                                ReferenceKind::Instantiation,
+                            ));
+                        }
+                    }
+                    (None, name) if RUBY_KERNEL_METHODS.contains(&name) => {}
+                    (None, name) => {
+                        references.push(reference(
+                            caller,
+                            implicit(name, node),
+                            node,
+                            ReferenceKind::Call,
+                        ));
+                    }
+                    (Some(("constant" | "scope_resolution", receiver)), "new") => {
+                        references.push(reference(
+                            caller,
+                            base_type_name(text(receiver), "::").to_string(),
+                            node,
+                            ReferenceKind::Instantiation,
+                        ));
+                    }
+                    (Some(("constant" | "scope_resolution", receiver)), name) => {
+                        let owner = base_type_name(text(receiver), "::");
+                        references.push(reference(
+                            caller,
+                            format!("{}#{}", owner, name),
+                            node,
+                            ReferenceKind::Call,
+                        ));
+                    }
+                    (Some(("self", _)), name) => {
+                        let callee = match ruby_owner(node, content) {
+                            Some(owner) => format!("{}#{}", owner, name),
+                            None => name.to_string(),
+                        };
+                        references.push(reference(caller, callee, node, ReferenceKind::Call));
+                    }
+                    (Some(_), name) => {
+                        references.push(reference(
+                            caller,
+                            name.to_string(),
+                            node,
+                            ReferenceKind::Call,
+                        ));
+                    }
+                }
+            }
+            // A bare `describe` is a call only if the class defines it;
+            // otherwise it is a local variable.
+            "identifier" if ruby_is_statement(node) => {
+                let name = text(node);
+                if let Some(owner) = ruby_owner(node, content) {
+                    let qualified = format!("{}#{}", owner, name);
+                    if methods.contains(&qualified) {
+                        references.push(reference(
+                            ruby_caller(node, content),
+                            qualified,
+                            node,
+                            ReferenceKind::Call,
+                        ));
+                    }
+                }
+            }
+            "superclass" => {
+                if let Some(parent) = node.named_child(0) {
+                    references.push(reference(
+                        ruby_owner(parent, content),
+                        base_type_name(text(parent), "::").to_string(),
+                        parent,
+                        ReferenceKind::Inheritance,
+                    ));
+                }
+            }
+            "constant" | "scope_resolution" if ruby_is_type_reference(node, content) => {
+                references.push(reference(
+                    ruby_caller(node, content),
+                    base_type_name(text(node), "::").to_string(),
+                    node,
+                    ReferenceKind::TypeReference,
+                ));
+            }
+            _ => {}
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    references
+}
+
+/// Name of the innermost Ruby class or module containing `node`, as
+/// written (`Invoice`, `Billing::Refund`).
+fn ruby_owner(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if matches!(candidate.kind(), "class" | "module") {
+            return candidate
+                .child_by_field_name("name")
+                .map(|n| content[n.byte_range()].to_string());
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Call-graph name of a Ruby method definition: `Owner#name` inside a class
+/// or module, the bare name at the top level.
+fn ruby_method_name(method: Node, content: &str) -> Option<String> {
+    let name = &content[method.child_by_field_name("name")?.byte_range()];
+    Some(match ruby_owner(method, content) {
+        Some(owner) => format!("{}#{}", owner, name),
+        None => name.to_string(),
+    })
+}
+
+/// The caller of a Ruby reference: the enclosing method, else the
+/// enclosing class or module.
+fn ruby_caller(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if matches!(candidate.kind(), "method" | "singleton_method") {
+            return ruby_method_name(candidate, content);
+        }
+        if matches!(candidate.kind(), "class" | "module") {
+            return ruby_owner(node, content);
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Whether an identifier stands alone as a statement of a method body,
+/// where Ruby reads it as a method call if no local of that name exists.
+fn ruby_is_statement(node: Node) -> bool {
+    node.parent().is_some_and(|p| p.kind() == "body_statement")
+}
+
+/// Whether a constant is a use of a type rather than part of a definition,
+/// a receiver, a mixin or a superclass (each recorded on its own).
+fn ruby_is_type_reference(node: Node, content: &str) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    match parent.kind() {
+        "class" | "module" | "scope_resolution" | "superclass" => false,
+        "call" => parent
+            .child_by_field_name("receiver")
+            .is_none_or(|receiver| receiver.id() != node.id()),
+        "argument_list" => !parent
+            .parent()
+            .is_some_and(|call| ruby_is_mixin(call, content)),
+        _ => true,
+    }
+}
+
+/// Whether a call is `include`, `extend` or `prepend` on the implicit
+/// receiver.
+fn ruby_is_mixin(call: Node, content: &str) -> bool {
+    call.kind() == "call"
+        && call.child_by_field_name("receiver").is_none()
+        && call
+            .child_by_field_name("method")
+            .is_some_and(|m| matches!(&content[m.byte_range()], "include" | "extend" | "prepend"))
+}
+
+/// [`ReferenceKind::Implementation`] edges from a type to each trait or
+/// interface it implements (`impl ParserService for TreeSitterParser`,
+/// `class Foo implements Bar`), and from every method of the block to the
+/// method it implements (`TreeSitterParser::parse_file` →
+/// `ParserService::parse_file`), so impact analysis can follow calls made
+/// through the trait. Names match the SCIP importer's: `Type::method` for
+/// Rust, `Class#method` for TypeScript, Java and C#, and `Ns\Class#method`
+/// for PHP.
+///
+/// The members of an interface are not known here, so a class implementing
+/// several interfaces links each of its methods to all of them.
+fn implementation_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    language: Language,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let php_names = match language {
+        Language::Php => PhpNames::collect(tree.root_node(), content),
+        _ => PhpNames::default(),
+    };
+    let csharp_interfaces = match language {
+        Language::CSharp => csharp_interface_names(tree.root_node(), content),
+        _ => HashSet::new(),
+    };
+    let separator = if language == Language::Rust {
+        "::"
+    } else {
+        "#"
+    };
+    let reference = |caller: String, callee: String, node: Node| {
+        let position = node.start_position();
+        SymbolReference::new(
+            Some(caller),
+            callee,
+            file_path.to_string(),
+            file_path.to_string(),
+            position.row as u32 + 1,
+            position.column as u32 + 1,
+            ReferenceKind::Implementation,
+            language,
+            repository_id.to_string(),
+        )
+    };
+
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let block = match (language, node.kind()) {
+            (Language::Rust, "impl_item") => rust_implements(node, content),
+            (Language::TypeScript, "class_declaration" | "abstract_class_declaration") => {
+                typescript_implements(node, content)
+            }
+            (Language::Php, "class_declaration") => php_implements(node, content, &php_names),
+            (Language::Java, "class_declaration" | "enum_declaration" | "record_declaration") => {
+                java_implements(node, content)
+            }
+            (
+                Language::CSharp,
+                "class_declaration" | "struct_declaration" | "record_declaration",
+            ) => csharp_implements(node, content, &csharp_interfaces),
+            _ => None,
+        };
+        if let Some(block) = block {
+            for (interface, interface_node) in &block.interfaces {
+                references.push(reference(
+                    block.implementor.clone(),
+                    interface.clone(),
+                    *interface_node,
+                ));
+                for method in &block.methods {
+                    let name = &content[method.byte_range()];
+                    references.push(reference(
+                        format!("{}{}{}", block.implementor, separator, name),
+                        format!("{}{}{}", interface, separator, name),
+                        *method,
+                    ));
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// A type together with the traits or interfaces it implements (or the
+/// classes it overrides methods of) and the name nodes of the methods it
+/// defines for them.
+struct ImplementsBlock<'t> {
+    implementor: String,
+    interfaces: Vec<(String, Node<'t>)>,
+    methods: Vec<Node<'t>>,
+}
+
+/// `impl Trait for Type { fn method() }`; inherent impls yield `None`.
+fn rust_implements<'t>(node: Node<'t>, content: &str) -> Option<ImplementsBlock<'t>> {
+    let interface = node.child_by_field_name("trait")?;
+    let implementor = node.child_by_field_name("type")?;
+    Some(ImplementsBlock {
+        implementor: base_type_name(&content[implementor.byte_range()], "::").to_string(),
+        interfaces: vec![(
+            base_type_name(&content[interface.byte_range()], "::").to_string(),
+            interface,
+        )],
+        methods: member_names(node, "function_item"),
+    })
+}
+
+/// `class Foo extends Base implements Bar, Baz<T> { method() {} }`.
+fn typescript_implements<'t>(node: Node<'t>, content: &str) -> Option<ImplementsBlock<'t>> {
+    let name = node.child_by_field_name("name")?;
+    let mut interfaces = Vec::new();
+    let mut cursor = node.walk();
+    for heritage in node.named_children(&mut cursor) {
+        if heritage.kind() != "class_heritage" {
+            continue;
+        }
+        let mut heritage_cursor = heritage.walk();
+        for clause in heritage.named_children(&mut heritage_cursor) {
+            if clause.kind() != "implements_clause" {
+                continue;
+            }
+            let mut clause_cursor = clause.walk();
+            for interface in clause.named_children(&mut clause_cursor) {
+                let interface_name = base_type_name(&content[interface.byte_range()], ".");
+                interfaces.push((interface_name.to_string(), interface));
+            }
+        }
+    }
+    (!interfaces.is_empty()).then(|| ImplementsBlock {
+        implementor: content[name.byte_range()].to_string(),
+        interfaces,
+        methods: member_names(node, "method_definition"),
+    })
+}
+
+/// `class Foo implements Bar, \Vendor\Baz { function method() {} }`, with
+/// names resolved against the file's namespace and `use` imports.
+fn php_implements<'t>(
+    node: Node<'t>,
+    content: &str,
+    names: &PhpNames,
+) -> Option<ImplementsBlock<'t>> {
+    let name = node.child_by_field_name("name")?;
+    let mut interfaces = Vec::new();
+    let mut cursor = node.walk();
+    for clause in node.named_children(&mut cursor) {
+        if clause.kind() != "class_interface_clause" {
+            continue;
+        }
+        let mut clause_cursor = clause.walk();
+        for interface in clause.named_children(&mut clause_cursor) {
+            if matches!(interface.kind(), "name" | "qualified_name") {
+                interfaces.push((names.resolve(&content[interface.byte_range()]), interface));
+            }
+        }
+    }
+    (!interfaces.is_empty()).then(|| ImplementsBlock {
+        implementor: names.qualify(&content[name.byte_range()]),
+        interfaces,
+        methods: member_names(node, "method_declaration"),
+    })
+}
+
+/// `class Circle extends Base implements Shape, Comparable<Circle> { … }`,
+/// and the same `implements` clause on enums and records.
+fn java_implements<'t>(node: Node<'t>, content: &str) -> Option<ImplementsBlock<'t>> {
+    let name = node.child_by_field_name("name")?;
+    let clause = node.child_by_field_name("interfaces")?;
+    let interfaces = java_type_list(clause)
+        .into_iter()
+        .map(|interface| {
+            let interface_name = base_type_name(&content[interface.byte_range()], ".");
+            (interface_name.to_string(), interface)
+        })
+        .collect();
+    Some(ImplementsBlock {
+        implementor: content[name.byte_range()].to_string(),
+        interfaces,
+        methods: member_names(node, "method_declaration"),
+    })
+}
+
+/// `class Circle : Shape, IShape, IComparable<Circle> { … }`: the interfaces
+/// in the base list (see [`csharp_is_interface`]) and the methods and
+/// properties that may implement them.
+fn csharp_implements<'t>(
+    node: Node<'t>,
+    content: &str,
+    declared: &HashSet<String>,
+) -> Option<ImplementsBlock<'t>> {
+    let name = node.child_by_field_name("name")?;
+    let mut cursor = node.walk();
+    let base_list = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "base_list")?;
+    let interfaces: Vec<_> = csharp_bases(base_list, content)
+        .into_iter()
+        .filter(|(interface, _)| csharp_is_interface(interface, declared))
+        .collect();
+    let mut methods = member_names(node, "method_declaration");
+    methods.extend(member_names(node, "property_declaration"));
+    (!interfaces.is_empty()).then(|| ImplementsBlock {
+        implementor: content[name.byte_range()].to_string(),
+        interfaces,
+        methods,
+    })
+}
+
+/// Name nodes of the `kind` members declared directly in a type's body.
+fn member_names<'t>(node: Node<'t>, kind: &str) -> Vec<Node<'t>> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return Vec::new();
+    };
+    let mut cursor = body.walk();
+    body.named_children(&mut cursor)
+        .filter(|member| member.kind() == kind)
+        .filter_map(|member| member.child_by_field_name("name"))
+        .collect()
+}
+
+/// A type name without generic arguments or its module path:
+/// `fmt::Display` → `Display`, `Repository<User>` → `Repository`.
+fn base_type_name<'a>(name: &'a str, separator: &str) -> &'a str {
+    let name = name.split('<').next().unwrap_or(name).trim();
+    name.rsplit(separator).next().unwrap_or(name)
+}
+
+/// [`ReferenceKind::Override`] edges from a subclass method to the method of
+/// the same name on each direct superclass (`Circle#area` → `Shape#area`),
+/// so impact analysis can list the overrides of a changed base method and
+/// follow calls made through the base class. What counts as an override
+/// depends on what each language marks:
+///
+/// - TypeScript: every method but the constructor of a class that
+///   `extends` another, since the base class's members are not known here;
+/// - Python: methods decorated `@override` (PEP 698) or commented
+///   `# override`, linked to every base class;
+/// - Kotlin: functions with the `override` modifier, linked to every
+///   supertype and named `Type.method` like the rest of the Kotlin graph.
+fn override_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    language: Language,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let separator = if language == Language::Kotlin {
+        "."
+    } else {
+        "#"
+    };
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let block = match (language, node.kind()) {
+            (Language::TypeScript, "class_declaration" | "abstract_class_declaration") => {
+                typescript_overrides(node, content)
+            }
+            (Language::Python, "class_definition") => python_overrides(node, content),
+            (Language::Kotlin, "class_declaration" | "object_declaration") => {
+                kotlin_overrides(node, content)
+            }
+            _ => None,
+        };
+        if let Some(block) = block {
+            for (base, _) in &block.interfaces {
+                for method in &block.methods {
+                    let name = &content[method.byte_range()];
+                    let position = method.start_position();
+                    references.push(SymbolReference::new(
+                        Some(format!("{}{}{}", block.implementor, separator, name)),
+                        format!("{}{}{}", base, separator, name),
+                        file_path.to_string(),
+                        file_path.to_string(),
+                        position.row as u32 + 1,
+                        position.column as u32 + 1,
+                        ReferenceKind::Override,
+                        language,
+                        repository_id.to_string(),
+                    ));
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// `class Circle extends Shape<T> { area() {} }`.
+fn typescript_overrides<'t>(node: Node<'t>, content: &str) -> Option<ImplementsBlock<'t>> {
+    let name = node.child_by_field_name("name")?;
+    let mut bases = Vec::new();
+    let mut cursor = node.walk();
+    for heritage in node.named_children(&mut cursor) {
+        if heritage.kind() != "class_heritage" {
+            continue;
+        }
+        let mut heritage_cursor = heritage.walk();
+        for clause in heritage.named_children(&mut heritage_cursor) {
+            if clause.kind() != "extends_clause" {
+                continue;
+            }
+            if let Some(base) = clause.named_child(0) {
+                bases.push((
+                    base_type_name(&content[base.byte_range()], ".").to_string(),
+                    base,
+                ));
+            }
+        }
+    }
+    let methods: Vec<Node> = member_names(node, "method_definition")
+        .into_iter()
+        .filter(|method| &content[method.byte_range()] != "constructor")
+        .collect();
+    (!bases.is_empty()).then(|| ImplementsBlock {
+        implementor: content[name.byte_range()].to_string(),
+        interfaces: bases,
+        methods,
+    })
+}
+
+/// `class Circle(Shape):` with its `@override` / `# override` methods.
+fn python_overrides<'t>(node: Node<'t>, content: &str) -> Option<ImplementsBlock<'t>> {
+    let name = node.child_by_field_name("name")?;
+    let superclasses = node.child_by_field_name("superclasses")?;
+    let mut bases = Vec::new();
+    let mut cursor = superclasses.walk();
+    for base in superclasses.named_children(&mut cursor) {
+        let base_name = match base.kind() {
+            "identifier" => Some(base),
+            "attribute" => base.child_by_field_name("attribute"),
+            // `metaclass=ABCMeta` is not a base class.
+            _ => None,
+        };
+        if let Some(base_name) = base_name {
+            bases.push((content[base_name.byte_range()].to_string(), base));
+        }
+    }
+
+    let mut methods = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let (function, decorators) = match member.kind() {
+                "function_definition" => (Some(member), None),
+                "decorated_definition" => (member.child_by_field_name("definition"), Some(member)),
+                _ => (None, None),
+            };
+            let Some(function) = function.filter(|f| f.kind() == "function_definition") else {
+                continue;
+            };
+            if python_marked_override(function, decorators, content) {
+                methods.extend(function.child_by_field_name("name"));
+            }
+        }
+    }
+    (!bases.is_empty() && !methods.is_empty()).then(|| ImplementsBlock {
+        implementor: content[name.byte_range()].to_string(),
+        interfaces: bases,
+        methods,
+    })
+}
+
+/// `@override` (also `@typing.override`) among `decorated`'s decorators, or
+/// an `# override` comment above the method or on its `def` line.
+fn python_marked_override(function: Node, decorated: Option<Node>, content: &str) -> bool {
+    if let Some(decorated) = decorated {
+        let mut cursor = decorated.walk();
+        let marked = decorated
+            .named_children(&mut cursor)
+            .filter(|d| d.kind() == "decorator")
+            .any(|d| {
+                let name = content[d.byte_range()].trim_start_matches('@').trim();
+                name.rsplit('.').next() == Some("override")
+            });
+        if marked {
+            return true;
+        }
+    }
+
+    let is_marker = |comment: Node| {
+        strip_comment_markers(&content[comment.byte_range()])
+            .split(|c: char| c.is_whitespace() || c == ':')
+            .next()
+            .is_some_and(|word| word.eq_ignore_ascii_case("override"))
+    };
+    if leading_comments(function, content)
+        .into_iter()
+        .any(is_marker)
+    {
+        return true;
+    }
+    // A comment after the `def ...:` lands before or at the start of the body.
+    let def_row = function.start_position().row;
+    let mut cursor = function.walk();
+    let mut candidates: Vec<Node> = function.named_children(&mut cursor).collect();
+    if let Some(first) = function
+        .child_by_field_name("body")
+        .and_then(|body| body.named_child(0))
+    {
+        candidates.push(first);
+    }
+    candidates
+        .into_iter()
+        .any(|c| c.kind() == "comment" && c.start_position().row == def_row && is_marker(c))
+}
+
+/// `class Circle(r: Double) : Shape(), Drawable { override fun area() }`.
+fn kotlin_overrides<'t>(node: Node<'t>, content: &str) -> Option<ImplementsBlock<'t>> {
+    let name = declaration_name(node, content)?;
+    let mut bases = Vec::new();
+    let mut cursor = node.walk();
+    let mut stack: Vec<Node> = node
+        .children(&mut cursor)
+        .filter(|c| c.kind() != "class_body")
+        .collect();
+    while let Some(child) = stack.pop() {
+        if child.kind() == "delegation_specifier" {
+            // `Shape()`, `Drawable`, `Repo<User>`, `Log by logger`
+            let text = &content[child.byte_range()];
+            let base = text
+                .split(|c: char| c == '(' || c == '<' || c.is_whitespace())
+                .next()
+                .unwrap_or_default();
+            let base = base.rsplit('.').next().unwrap_or(base);
+            if !base.is_empty() {
+                bases.push((base.to_string(), child));
+            }
+            continue;
+        }
+        let mut cursor = child.walk();
+        stack.extend(child.children(&mut cursor));
+    }
+    bases.sort_by_key(|(_, node)| node.start_byte());
+
+    let mut methods = Vec::new();
+    let mut cursor = node.walk();
+    for body in node.children(&mut cursor) {
+        if body.kind() != "class_body" {
+            continue;
+        }
+        let mut body_cursor = body.walk();
+        for member in body.named_children(&mut body_cursor) {
+            if member.kind() == "function_declaration"
+                && has_modifier(member, "modifiers", "override", content)
+            {
+                methods.extend(declaration_name_node(member));
+            }
+        }
+    }
+    (!bases.is_empty() && !methods.is_empty()).then(|| ImplementsBlock {
+        implementor: name.to_string(),
+        interfaces: bases,
+        methods,
+    })
+}
+
+/// The member a Kotlin or Swift `navigation_expression` selects: `publish`
+/// in `bus.publish`.
+fn navigation_member<'a>(navigation: Node, content: &'a str) -> Option<&'a str> {
+    fn last<'t>(node: Node<'t>) -> Option<Node<'t>> {
+        let mut cursor = node.walk();
+        let mut last = None;
+        for child in node.named_children(&mut cursor) {
+            last = Some(child);
+        }
+        last
+    }
+    let mut member = last(navigation)?;
+    if member.kind() == "navigation_suffix" {
+        member = member
+            .child_by_field_name("suffix")
+            .or_else(|| last(member))?;
+    }
+    Some(&content[member.byte_range()])
+}
+
+/// Calls in a Kotlin or Swift file, attributed to `Type.method` callers and
+/// qualified by [`qualify_callee_name`]. Receivers resolve to the classes,
+/// objects and protocols declared in the file and, inside a Kotlin class, to
+/// the types of its constructor properties.
+fn member_call_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    language: Language,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let mut declared = HashMap::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if is_member_type_declaration(node) {
+            if let Some(name) = declaration_name(node, content) {
+                declared.insert(name.to_string(), name.to_string());
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+
+    let mut references = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "call_expression" {
+            let owner = enclosing_member_type(node);
+            let enclosing = owner.and_then(|o| declaration_name(o, content));
+            let mut receivers = declared.clone();
+            if let Some(owner) = owner {
+                receivers.extend(constructor_properties(owner, content));
+            }
+            let callee = node
+                .child_by_field_name("function")
+                .or_else(|| node.named_child(0))
+                .and_then(|f| qualify_callee_name(f, content, enclosing, &receivers));
+            if let Some(callee) = callee {
+                let position = node.start_position();
+                references.push(SymbolReference::new(
+                    enclosing_member_function(node, content),
+                    callee,
+                    file_path.to_string(),
+                    file_path.to_string(),
+                    position.row as u32 + 1,
+                    position.column as u32 + 1,
+                    ReferenceKind::Call,
+                    language,
+                    repository_id.to_string(),
+                ));
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    references
+}
+
+/// The types named after the `:` of Swift type declarations, attributed to
+/// the declaring type. Structs, enums, actors and extensions can only adopt
+/// protocols, so their parents are [`ReferenceKind::Implementation`]s; so are
+/// a class's, except the first when it is not a protocol declared in the
+/// file, which Swift requires to be the superclass
+/// ([`ReferenceKind::Inheritance`]). A protocol inherits its parents.
+fn swift_conformance_references(
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let mut declarations = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "class_declaration" | "protocol_declaration") {
+            declarations.push(node);
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    let protocols: HashSet<&str> = declarations
+        .iter()
+        .filter(|d| d.kind() == "protocol_declaration")
+        .filter_map(|d| declaration_name(*d, content))
+        .collect();
+
+    let mut references = Vec::new();
+    for declaration in declarations {
+        let owner = declaration_name(declaration, content).map(str::to_string);
+        let declared_as = match declaration.kind() {
+            "protocol_declaration" => "protocol",
+            _ => declaration
+                .child_by_field_name("declaration_kind")
+                .map_or("", |k| &content[k.byte_range()]),
+        };
+        let mut cursor = declaration.walk();
+        let parents = declaration
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "inheritance_specifier");
+        for (index, parent) in parents.enumerate() {
+            let name = parent
+                .child_by_field_name("inherits_from")
+                .unwrap_or(parent);
+            let name = content[name.byte_range()]
+                .split('<')
+                .next()
+                .unwrap_or_default();
+            let name = name.trim();
+            let kind = match declared_as {
+                "protocol" => ReferenceKind::Inheritance,
+                "class" if index == 0 && !protocols.contains(name) => ReferenceKind::Inheritance,
+                _ => ReferenceKind::Implementation,
+            };
+            let position = parent.start_position();
+            references.push(SymbolReference::new(
+                owner.clone(),
+                name.to_string(),
+                file_path.to_string(),
+                file_path.to_string(),
+                position.row as u32 + 1,
+                position.column as u32 + 1,
+                kind,
+                Language::Swift,
+                repository_id.to_string(),
+            ));
+        }
+    }
+    references
+}
+
+/// Kotlin classes, interfaces and objects; Swift classes, structs, enums,
+/// actors, extensions and protocols. A Kotlin `companion object` is not a
+/// type of its own: its members belong to the enclosing class.
+fn is_member_type_declaration(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "class_declaration" | "object_declaration" | "protocol_declaration"
+    )
+}
+
+/// The `name` field of a declaration, or its first identifier child where
+/// the grammar leaves it unnamed (Kotlin).
+fn declaration_name<'a>(node: Node, content: &'a str) -> Option<&'a str> {
+    declaration_name_node(node).map(|name| &content[name.byte_range()])
+}
+
+/// The node [`declaration_name`] reads.
+fn declaration_name_node(node: Node) -> Option<Node> {
+    if let Some(name) = node.child_by_field_name("name") {
+        return Some(name);
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if matches!(
+            child.kind(),
+            "identifier" | "simple_identifier" | "type_identifier"
+        ) {
+            return Some(child);
+        }
+    }
+    None
+}
+
+/// The innermost Kotlin or Swift type declaration containing `node`.
+fn enclosing_member_type(node: Node) -> Option<Node> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if is_member_type_declaration(candidate) {
+            return Some(candidate);
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Call-graph name of the Kotlin or Swift function containing `node`
+/// (`Type.method`, or the bare name for top-level functions).
+fn enclosing_member_function(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        let name = match candidate.kind() {
+            "function_declaration" => declaration_name(candidate, content),
+            "init_declaration" => Some("init"),
+            _ => None,
+        };
+        if let Some(name) = name {
+            return Some(
+                match enclosing_member_type(candidate).and_then(|t| declaration_name(t, content)) {
+                    Some(owner) => format!("{}.{}", owner, name),
+                    None => name.to_string(),
+                },
+            );
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// `(name, type)` of each parameter of a Kotlin class's primary constructor,
+/// `class OrderService(private val bus: EventBus)` → `("bus", "EventBus")`;
+/// the type is reduced to its base name (`Map<K, V>?` → `Map`).
+fn constructor_properties(class: Node, content: &str) -> Vec<(String, String)> {
+    let mut properties = Vec::new();
+    let mut cursor = class.walk();
+    let mut stack: Vec<Node> = class
+        .children(&mut cursor)
+        .filter(|c| c.kind() != "class_body")
+        .collect();
+    while let Some(node) = stack.pop() {
+        if node.kind() != "class_parameter" {
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+            continue;
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        let Some(colon) = children.iter().position(|c| c.kind() == ":") else {
+            continue;
+        };
+        let name = children[..colon]
+            .iter()
+            .rfind(|c| matches!(c.kind(), "identifier" | "simple_identifier"));
+        let ty = children[colon + 1..].iter().find(|c| c.is_named());
+        if let (Some(name), Some(ty)) = (name, ty) {
+            let ty = content[ty.byte_range()]
+                .split('<')
+                .next()
+                .unwrap_or_default();
+            let ty = ty.trim().trim_end_matches('?');
+            properties.push((
+                content[name.byte_range()].to_string(),
+                ty.rsplit('.').next().unwrap_or(ty).to_string(),
+            ));
+        }
+    }
+    properties
+}
+
+/// The namespace and `use` imports of a PHP file, to turn the names in its
+/// class declarations into the fully qualified names scip-php reports.
+#[derive(Default)]
+struct PhpNames {
+    namespace: Option<String>,
+    /// Import alias (the last segment unless renamed with `as`) → full name.
+    imports: HashMap<String, String>,
+}
+
+impl PhpNames {
+    fn collect(root: Node, content: &str) -> Self {
+        let mut names = Self::default();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            match node.kind() {
+                "namespace_definition" => {
+                    names.namespace = node
+                        .child_by_field_name("name")
+                        .map(|n| content[n.byte_range()].to_string());
+                }
+                "namespace_use_clause" => {
+                    let clause = content[node.byte_range()].trim();
+                    let (path, alias) = match clause.split_once(" as ") {
+                        Some((path, alias)) => (path.trim(), alias.trim()),
+                        None => (clause, clause.rsplit('\\').next().unwrap_or(clause)),
+                    };
+                    names
+                        .imports
+                        .insert(alias.to_string(), path.trim_start_matches('\\').to_string());
+                }
+                _ => {}
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        names
+    }
+
+    /// Full name of a class declared in this file.
+    fn qualify(&self, name: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}\\{}", namespace, name),
+            None => name.to_string(),
+        }
+    }
+
+    /// Full name of a class referenced in this file: `\A\B` is already
+    /// absolute, an imported first segment expands to its import, anything
+    /// else is relative to the file's namespace.
+    fn resolve(&self, name: &str) -> String {
+        if let Some(absolute) = name.strip_prefix('\\') {
+            return absolute.to_string();
+        }
+        let (first, rest) = match name.split_once('\\') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (name, None),
+        };
+        match (self.imports.get(first), rest) {
+            (Some(import), Some(rest)) => format!("{}\\{}", import, rest),
+            (Some(import), None) => import.clone(),
+            (None, _) => self.qualify(name),
+        }
+    }
+}
+
+/// Name of the innermost class, object or trait containing `node`.
+fn scala_enclosing_type(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if matches!(
+            candidate.kind(),
+            "class_definition" | "object_definition" | "trait_definition"
+        ) {
+            let name = candidate.child_by_field_name("name")?;
+            return Some(content[name.byte_range()].to_string());
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Call-graph name of the method containing `node` (`Type.method`, or the
+/// bare name for top-level functions); `None` outside any method.
+fn scala_enclosing_method(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if candidate.kind() == "function_definition" {
+            let name = &content[candidate.child_by_field_name("name")?.byte_range()];
+            return Some(match scala_enclosing_type(candidate, content) {
+                Some(owner) => format!("{}.{}", owner, name),
+                None => name.to_string(),
+            });
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Base name of a type: `Seq[Row]` → `Seq`, `spark.Job` stays qualified.
+fn scala_type_name(node: Node, content: &str) -> String {
+    match node.kind() {
+        "generic_type" => node
+            .child_by_field_name("type")
+            .map(|t| scala_type_name(t, content))
+            .unwrap_or_else(|| content[node.byte_range()].to_string()),
+        _ => content[node.byte_range()].to_string(),
+    }
+}
+
+/// `true` when a definition carries the `implicit` modifier.
+fn is_implicit(node: Node, content: &str) -> bool {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "modifiers"
+            && content[child.byte_range()]
+                .split_whitespace()
+                .any(|w| w == "implicit")
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// The paths of one `import` clause, whitespace removed:
+/// `a.B, c.{D, E}` → `a.B`, `c.{D,E}`. Commas inside selector braces do not
+/// separate paths.
+fn split_imports(text: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                paths.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ if c.is_whitespace() => continue,
+            _ => {}
+        }
+        current.push(c);
+    }
+    paths.push(current);
+    paths.retain(|p| !p.is_empty());
+    paths
+}
+
+/// Turn the captures of a [`get_reference_patterns`] query into references,
+/// attributed to the enclosing function (`None` at file scope); decorators
+/// are attributed to the declaration they decorate.
+///
+/// [`get_reference_patterns`]: TreeSitterParser::get_reference_patterns
+fn query_references(
+    query: &Query,
+    tree: &tree_sitter::Tree,
+    content: &str,
+    file_path: &str,
+    language: Language,
+    repository_id: &str,
+) -> Vec<SymbolReference> {
+    let capture_names = query.capture_names();
+    let mut cursor = QueryCursor::new();
+    let mut matches_iter = cursor.matches(query, tree.root_node(), content.as_bytes());
+
+    let mut references = Vec::new();
+    while let Some(query_match) = matches_iter.next() {
+        for capture in query_match.captures {
+            let kind = match capture_names[capture.index as usize] {
+                "call" => ReferenceKind::Call,
+                "import" => ReferenceKind::Import,
+                "type" => ReferenceKind::TypeReference,
+                "decorator" => ReferenceKind::MacroInvocation,
+                "inheritance" => ReferenceKind::Inheritance,
+                _ => continue,
+            };
+            let node = capture.node;
+            if kind == ReferenceKind::TypeReference && is_type_declaration_name(node) {
+                continue;
+            }
+            // `#include <stdio.h>` / `#include "list.h"` → `stdio.h` / `list.h`
+            let callee = content[node.byte_range()]
+                .trim_matches(|c| matches!(c, '"' | '<' | '>'))
+                .to_string();
+            let caller = match kind {
+                ReferenceKind::MacroInvocation => decorated_symbol_name(node, content),
+                ReferenceKind::Inheritance => inheriting_class_name(node, content),
+                _ => enclosing_function_name(node, content),
+            };
+            let position = node.start_position();
+            references.push(SymbolReference::new(
+                caller,
+                callee,
+                file_path.to_string(),
+                file_path.to_string(),
+                position.row as u32 + 1,
+                position.column as u32 + 1,
+                kind,
+                language,
+                repository_id.to_string(),
+            ));
+        }
+    }
+    references
+}
+
+/// `true` when a `type_identifier` names the type being declared (the
+/// `foo` in `struct foo { ... }` or `typedef int foo;`) rather than using it.
+fn is_type_declaration_name(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    match parent.kind() {
+        "type_definition" => parent.child_by_field_name("type") != Some(node),
+        "struct_specifier" | "union_specifier" | "enum_specifier" => {
+            parent.child_by_field_name("body").is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Name of the function whose body contains `node`, unwrapping pointer and
+/// function declarators down to the identifier.
+fn enclosing_function_name(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if candidate.kind() == "function_definition" {
+            let mut declarator = candidate.child_by_field_name("declarator");
+            while let Some(d) = declarator {
+                if d.kind() == "identifier" {
+                    return Some(content[d.byte_range()].to_string());
+                }
+                declarator = d.child_by_field_name("declarator");
+            }
+            return None;
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Name of the Python class whose base list contains `node`.
+fn inheriting_class_name(node: Node, content: &str) -> Option<String> {
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        if candidate.kind() == "class_definition" {
+            return candidate
+                .child_by_field_name("name")
+                .map(|name| content[name.byte_range()].to_string());
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+/// Call-graph name of the declaration a TypeScript decorator applies to:
+/// `Foo` for a class, `Foo#bar` for a method or property, and the method for
+/// a decorated constructor parameter (`Foo#constructor` for
+/// `constructor(@Inject('TOKEN') x)`).
+fn decorated_symbol_name(node: Node, content: &str) -> Option<String> {
+    let mut child = node;
+    let mut current = node.parent();
+    while let Some(candidate) = current {
+        let name = |n: Node| {
+            n.child_by_field_name("name")
+                .map(|name| content[name.byte_range()].to_string())
+        };
+        match candidate.kind() {
+            // `@Component(...) export class Foo {}`
+            "export_statement" => {
+                return candidate.child_by_field_name("declaration").and_then(name)
+            }
+            "class_declaration" | "abstract_class_declaration" => return name(candidate),
+            "method_definition" | "public_field_definition" => {
+                let member = name(candidate)?;
+                let class = candidate
+                    .parent()
+                    .filter(|body| body.kind() == "class_body")
+                    .and_then(|body| body.parent())
+                    .and_then(name);
+                return Some(match class {
+                    Some(class) => format!("{}#{}", class, member),
+                    None => member,
+                });
+            }
+            // Method decorators are siblings before the method in the class
+            // body: `@HostListener('click') onClick() {}`.
+            "class_body" if child.kind() == "decorator" => {
+                let mut member = child.next_named_sibling();
+                while let Some(decorator) = member.filter(|m| m.kind() == "decorator") {
+                    member = decorator.next_named_sibling();
+                }
+                current = member;
+                child = candidate;
+            }
+            _ => {
+                child = candidate;
+                current = candidate.parent();
+            }
+        }
+    }
+    None
+}
+
+/// Last path component of a script path: `./bin/deploy.sh` → `deploy.sh`.
+fn script_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The script a shell `command` node runs or sources, by file name:
+/// `./deploy.sh`, `bin/release`, `source lib.sh`, `. lib.sh` and
+/// `bash deploy.sh` all qualify.  Plain commands (`echo`, `git`) and
+/// targets whose file name is computed at runtime are ignored.
+fn invoked_script(command: Node, content: &str) -> Option<String> {
+    let name = unquote(&content[command.child_by_field_name("name")?.byte_range()]);
+
+    let target = if matches!(name, "source" | ".") || SHELL_INTERPRETERS.contains(&name) {
+        let mut cursor = command.walk();
+        let arguments: Vec<&str> = command
+            .children_by_field_name("argument", &mut cursor)
+            .map(|arg| unquote(&content[arg.byte_range()]))
+            .collect();
+        // `bash -c '...'` runs an inline command, not a script.
+        if arguments.contains(&"-c") {
+            return None;
+        }
+        arguments.into_iter().find(|arg| !arg.starts_with('-'))?
+    } else if name.contains('/') || name.ends_with(".sh") || name.ends_with(".bash") {
+        name
+    } else {
+        return None;
+    };
+
+    // `"$DIR/lib.sh"` still names `lib.sh`; only the file name must be literal.
+    let file_name = script_name(target);
+    if file_name.is_empty() || file_name.contains(['$', '`']) {
+        return None;
+    }
+    Some(file_name.to_string())
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| c == '"' || c == '\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_rust_function() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+fn hello_world() {
+    println!("Hello, world!");
+}
+
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+"#;
+
+        let chunks = parser
+            .parse_file(content, "test.rs", Language::Rust, "test-repo")
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parse_python_class() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+class Calculator:
+    def add(self, a, b):
+        return a + b
+
+    def subtract(self, a, b):
+        return a - b
+"#;
+
+        let chunks = parser
+            .parse_file(content, "calc.py", Language::Python, "test-repo")
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parse_php_class() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+<?php
+class Calculator {
+    public function add($a, $b) {
+        return $a + $b;
+    }
+
+    public function subtract($a, $b) {
+        return $a - $b;
+    }
+}
+"#;
+
+        let chunks = parser
+            .parse_file(content, "calc.php", Language::Php, "test-repo")
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_constructors_get_their_own_node_type() {
+        let parser = TreeSitterParser::new();
+        let python = r#"
+class Account:
+    def __init__(self, owner):
+        self.owner = owner
+
+    def close(self):
+        self.owner = None
+"#;
+        let typescript = r#"
+class Account {
+    constructor(private owner: string) {
+        this.owner = owner;
+    }
+
+    close(): void {
+        this.owner = "";
+    }
+}
+"#;
+
+        for (content, path, language) in [
+            (python, "account.py", Language::Python),
+            (typescript, "account.ts", Language::TypeScript),
+        ] {
+            let chunks = parser
+                .parse_file(content, path, language, "test-repo")
+                .await
+                .unwrap();
+            let kinds: Vec<(NodeType, Option<&str>)> = chunks
+                .iter()
+                .filter(|c| c.node_type() != NodeType::Class)
+                .map(|c| (c.node_type(), c.symbol_name()))
+                .collect();
+            let constructor = if language == Language::Python {
+                "__init__"
+            } else {
+                "constructor"
+            };
+            assert_eq!(kinds.len(), 2, "{path}: {kinds:?}");
+            assert!(kinds.contains(&(NodeType::Constructor, Some(constructor))));
+            assert!(kinds.contains(&(NodeType::Function, Some("close"))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decorated_and_nested_python_constructors() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+import sys
+
+class Account:
+    @typechecked
+    def __init__(self, owner):
+        self.owner = owner
+
+class Ledger:
+    if sys.version_info >= (3, 8):
+        def __init__(self, entries):
+            self.entries = entries
+    else:
+        def __init__(self, entries):
+            self.entries = list(entries)
+
+def build():
+    def __init__(self):
+        self.ready = True
+    return __init__
+"#;
+        let chunks = parser
+            .parse_file(content, "ledger.py", Language::Python, "test-repo")
+            .await
+            .unwrap();
+        let inits: Vec<(NodeType, Option<&str>)> = chunks
+            .iter()
+            .filter(|c| c.symbol_name() == Some("__init__"))
+            .map(|c| (c.node_type(), c.parent_symbol()))
+            .collect();
+
+        assert_eq!(
+            inits,
+            vec![
+                (NodeType::Constructor, Some("Account")),
+                (NodeType::Constructor, Some("Ledger")),
+                (NodeType::Constructor, Some("Ledger")),
+                (NodeType::Function, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_php_class_with_promoted_constructor() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+<?php
+class UserController {
+    public function __construct(private readonly UserService $service) {
+    }
+
+    public function show(int $id) {
+        return $this->service->someMethod($id);
+    }
+}
+"#;
+
+        let chunks = parser
+            .parse_file(content, "UserController.php", Language::Php, "test-repo")
+            .await
+            .unwrap();
+
+        let members: Vec<(NodeType, Option<&str>, Option<&str>)> = chunks
+            .iter()
+            .map(|c| (c.node_type(), c.parent_symbol(), c.symbol_name()))
+            .collect();
+        assert!(members.contains(&(
+            NodeType::Constructor,
+            Some("UserController"),
+            Some("__construct")
+        )));
+        assert!(members.contains(&(NodeType::Function, Some("UserController"), Some("show"))));
+    }
+
+    #[tokio::test]
+    async fn test_parse_cpp_method_outside_class() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_cpp.cpp")
+            .expect("failed to read sample_cpp.cpp");
+
+        let chunks = parser
+            .parse_file(&content, "sample_cpp.cpp", Language::Cpp, "test-repo")
+            .await
+            .unwrap();
+
+        let has_method = chunks
+            .iter()
+            .any(|chunk| chunk.symbol_name() == Some("calculate_area"));
+
+        assert!(has_method, "expected calculate_area to be indexed");
+    }
+
+    #[tokio::test]
+    async fn test_parse_cpp_does_not_duplicate_out_of_class_method() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_cpp.cpp")
+            .expect("failed to read sample_cpp.cpp");
+
+        let chunks = parser
+            .parse_file(&content, "sample_cpp.cpp", Language::Cpp, "test-repo")
+            .await
+            .unwrap();
+
+        let area_count = chunks
+            .iter()
+            .filter(|chunk| chunk.symbol_name() == Some("calculate_area"))
+            .count();
+        assert_eq!(
+            area_count, 1,
+            "expected calculate_area to appear exactly once"
+        );
+
+        let unnamed_area_count = chunks
+            .iter()
+            .filter(|chunk| {
+                chunk.node_type() == NodeType::Function
+                    && chunk.symbol_name().is_none()
+                    && chunk.content().contains("calculate_area")
+            })
+            .count();
+        assert_eq!(
+            unnamed_area_count, 0,
+            "expected no unnamed function chunk containing calculate_area"
+        );
+
+        let main_count = chunks
+            .iter()
+            .filter(|chunk| chunk.symbol_name() == Some("main"))
+            .count();
+        assert_eq!(main_count, 1, "expected main to appear exactly once");
+    }
+    #[tokio::test]
+    async fn test_parse_swift_class_and_struct() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_swift.swift")
+            .expect("failed to read sample_swift.swift");
+
+        let chunks = parser
+            .parse_file(&content, "sample_swift.swift", Language::Swift, "test-repo")
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty(), "Should extract chunks from Swift file");
+
+        let has_circle = chunks.iter().any(|c| c.symbol_name() == Some("Circle"));
+        assert!(has_circle, "Should find Circle class");
+
+        let has_rect = chunks.iter().any(|c| c.symbol_name() == Some("Rectangle"));
+        assert!(has_rect, "Should find Rectangle struct");
+
+        let has_shape = chunks.iter().any(|c| c.symbol_name() == Some("Shape"));
+        assert!(has_shape, "Should find Shape protocol");
+    }
+
+    #[tokio::test]
+    async fn test_parse_swift_functions_and_enums() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_swift.swift")
+            .expect("failed to read sample_swift.swift");
+
+        let chunks = parser
+            .parse_file(&content, "sample_swift.swift", Language::Swift, "test-repo")
+            .await
+            .unwrap();
+
+        let has_fn = chunks
+            .iter()
+            .any(|c| c.symbol_name() == Some("printShapeInfo"));
+        assert!(has_fn, "Should find printShapeInfo function");
+
+        let has_enum = chunks.iter().any(|c| c.symbol_name() == Some("Result"));
+        assert!(has_enum, "Should find Result enum");
+    }
+
+    #[tokio::test]
+    async fn test_parse_kotlin_classes_and_functions() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_kotlin.kt")
+            .expect("failed to read sample_kotlin.kt");
+
+        let chunks = parser
+            .parse_file(&content, "sample_kotlin.kt", Language::Kotlin, "test-repo")
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty(), "Should extract chunks from Kotlin file");
+
+        let has_circle = chunks.iter().any(|c| c.symbol_name() == Some("Circle"));
+        assert!(has_circle, "Should find Circle class");
+
+        let has_rect = chunks.iter().any(|c| c.symbol_name() == Some("Rectangle"));
+        assert!(has_rect, "Should find Rectangle data class");
+
+        let has_shape = chunks.iter().any(|c| c.symbol_name() == Some("Shape"));
+        assert!(has_shape, "Should find Shape interface");
+    }
+
+    #[tokio::test]
+    async fn test_parse_kotlin_suspend_functions_are_async() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+class UserRepository(private val api: Api) {
+    suspend fun fetchUser(id: String): User {
+        return api.get(id)
+    }
+
+    fun cachedUser(id: String): User? {
+        return cache[id]
+    }
+}
+
+@Throws(IOException::class)
+private suspend fun refreshAll(repo: UserRepository) {
+    repo.fetchUser("all")
+}
+"#;
+
+        let chunks = parser
+            .parse_file(content, "users.kt", Language::Kotlin, "test-repo")
+            .await
+            .unwrap();
+
+        let is_async = |name: &str| {
+            chunks
+                .iter()
+                .find(|c| c.symbol_name() == Some(name))
+                .unwrap_or_else(|| panic!("missing chunk {name}"))
+                .is_async()
+        };
+        assert!(is_async("fetchUser"));
+        assert!(is_async("refreshAll"));
+        assert!(!is_async("cachedUser"));
+        assert!(!is_async("UserRepository"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_rust_async_fn_is_async() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+pub async fn load_config(path: &str) -> Config {
+    read(path).await
+}
+
+pub fn parse_config(text: &str) -> Config {
+    toml::from_str(text).unwrap()
+}
+"#;
+
+        let chunks = parser
+            .parse_file(content, "config.rs", Language::Rust, "test-repo")
+            .await
+            .unwrap();
+
+        let chunk = |name: &str| {
+            chunks
+                .iter()
+                .find(|c| c.symbol_name() == Some(name))
+                .unwrap()
+        };
+        assert!(chunk("load_config").is_async());
+        assert!(!chunk("parse_config").is_async());
+    }
+
+    #[tokio::test]
+    async fn test_parse_kotlin_object_and_enum() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_kotlin.kt")
+            .expect("failed to read sample_kotlin.kt");
+
+        let chunks = parser
+            .parse_file(&content, "sample_kotlin.kt", Language::Kotlin, "test-repo")
+            .await
+            .unwrap();
+
+        let has_math_utils = chunks.iter().any(|c| c.symbol_name() == Some("MathUtils"));
+        assert!(has_math_utils, "Should find MathUtils singleton object");
+
+        let has_color = chunks.iter().any(|c| c.symbol_name() == Some("Color"));
+        assert!(has_color, "Should find Color enum class");
+
+        let has_print_fn = chunks
+            .iter()
+            .any(|c| c.symbol_name() == Some("printShapeInfo"));
+        assert!(
+            has_print_fn,
+            "Should find printShapeInfo top-level function"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_kotlin_type_alias() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_kotlin.kt")
+            .expect("failed to read sample_kotlin.kt");
+
+        let chunks = parser
+            .parse_file(&content, "sample_kotlin.kt", Language::Kotlin, "test-repo")
+            .await
+            .unwrap();
+
+        let typedef_chunks: Vec<_> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::TypeDef)
+            .collect();
+        assert!(
+            !typedef_chunks.is_empty(),
+            "Should find at least one type alias"
+        );
+
+        let has_shape_list = chunks.iter().any(|c| c.symbol_name() == Some("ShapeList"));
+        assert!(has_shape_list, "Should find ShapeList type alias");
+    }
+
+    #[tokio::test]
+    async fn test_parse_java_types() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_java.java")
+            .expect("failed to read sample_java.java");
+
+        let chunks = parser
+            .parse_file(&content, "sample_java.java", Language::Java, "test-repo")
+            .await
+            .unwrap();
+
+        let find = |name: &str, node_type: NodeType| {
+            chunks
+                .iter()
+                .any(|c| c.symbol_name() == Some(name) && c.node_type() == node_type)
+        };
+        assert!(find("Circle", NodeType::Class), "Should find Circle class");
+        assert!(
+            find("Shape", NodeType::Interface),
+            "Should find Shape interface"
+        );
+        assert!(find("Color", NodeType::Enum), "Should find Color enum");
+        assert!(
+            find("Rectangle", NodeType::Class),
+            "Should find Rectangle record"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_java_methods_are_qualified() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_java.java")
+            .expect("failed to read sample_java.java");
+
+        let chunks = parser
+            .parse_file(&content, "sample_java.java", Language::Java, "test-repo")
+            .await
+            .unwrap();
+
+        let names: Vec<String> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::Function)
+            .filter_map(|c| c.call_graph_name())
+            .collect();
+        for expected in [
+            "Circle#area",
+            "Circle#fromDiameter",
+            "Color#toHex",
+            "Rectangle#area",
+            "ShapeRegistry#register",
+        ] {
+            assert!(
+                names.iter().any(|n| n == expected),
+                "expected {expected} in {names:?}"
+            );
+        }
+        let constructors: Vec<String> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::Constructor)
+            .filter_map(|c| c.call_graph_name())
+            .collect();
+        assert!(constructors.contains(&"Circle#Circle".to_string()));
+        assert!(!names.contains(&"Circle#Circle".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_extract_java_references() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_java.java")
+            .expect("failed to read sample_java.java");
+
+        let references = parser
+            .extract_references(&content, "sample_java.java", Language::Java, "test-repo")
+            .await
+            .unwrap();
+
+        let has = |caller: Option<&str>, callee: &str, kind: ReferenceKind| {
+            references.iter().any(|r| {
+                r.caller_symbol() == caller
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == kind
+            })
+        };
+        assert!(
+            has(
+                Some("ScaledCircle#scaledArea"),
+                "Circle#fromDiameter",
+                ReferenceKind::Call
+            ),
+            "static calls keep their class: {references:?}"
+        );
+        assert!(
+            has(
+                Some("ScaledCircle#scaledArea"),
+                "ScaledCircle#area",
+                ReferenceKind::Call
+            ),
+            "this.area() is qualified by the enclosing class"
+        );
+        assert!(has(
+            Some("ScaledCircle#scaledArea"),
+            "register",
+            ReferenceKind::Call
+        ));
+        assert!(has(
+            Some("ScaledCircle#compareTo"),
+            "scaledArea",
+            ReferenceKind::Call
+        ));
+        assert!(has(
+            Some("Circle#fromDiameter"),
+            "Circle",
+            ReferenceKind::Instantiation
+        ));
+        assert!(
+            has(
+                Some("ShapeRegistry"),
+                "ArrayList",
+                ReferenceKind::Instantiation
+            ),
+            "field initializers are attributed to the class"
+        );
+        assert!(has(None, "java.util.List", ReferenceKind::Import));
+        assert!(has(
+            Some("ScaledCircle"),
+            "Circle",
+            ReferenceKind::Inheritance
+        ));
+        assert!(has(Some("Solid"), "Shape", ReferenceKind::Inheritance));
+        assert!(has(Some("Circle"), "Shape", ReferenceKind::Implementation));
+        assert!(has(
+            Some("Circle#area"),
+            "Shape#area",
+            ReferenceKind::Implementation
+        ));
+        assert!(has(
+            Some("Rectangle#area"),
+            "Shape#area",
+            ReferenceKind::Implementation
+        ));
+        assert!(has(
+            Some("ScaledCircle"),
+            "Comparable",
+            ReferenceKind::Implementation
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parse_csharp_types() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_csharp.cs")
+            .expect("failed to read sample_csharp.cs");
+
+        let chunks = parser
+            .parse_file(&content, "sample_csharp.cs", Language::CSharp, "test-repo")
+            .await
+            .unwrap();
+
+        let find = |name: &str, node_type: NodeType| {
+            chunks
+                .iter()
+                .any(|c| c.symbol_name() == Some(name) && c.node_type() == node_type)
+        };
+        assert!(
+            find("Example.Shapes", NodeType::Module),
+            "Should find Example.Shapes namespace"
+        );
+        assert!(find("Circle", NodeType::Class), "Should find Circle class");
+        assert!(
+            find("IShape", NodeType::Interface),
+            "Should find IShape interface"
+        );
+        assert!(find("Point", NodeType::Struct), "Should find Point struct");
+        assert!(
+            find("Rectangle", NodeType::Class),
+            "Should find Rectangle record"
+        );
+        assert!(find("Color", NodeType::Enum), "Should find Color enum");
+    }
+
+    #[tokio::test]
+    async fn test_parse_csharp_members_are_qualified() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_csharp.cs")
+            .expect("failed to read sample_csharp.cs");
+
+        let chunks = parser
+            .parse_file(&content, "sample_csharp.cs", Language::CSharp, "test-repo")
+            .await
+            .unwrap();
+
+        let names: Vec<String> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::Function)
+            .filter_map(|c| c.call_graph_name())
+            .collect();
+        for expected in [
+            "Circle#Area",
+            "Circle#FromDiameter",
+            "Point#DistanceTo",
+            "Rectangle#Area",
+            "ShapeRegistry#TotalArea",
+            "IShape#Area",
+        ] {
+            assert!(
+                names.iter().any(|n| n == expected),
+                "expected {expected} in {names:?}"
+            );
+        }
+        let of_type = |node_type: NodeType| -> Vec<String> {
+            chunks
+                .iter()
+                .filter(|c| c.node_type() == node_type)
+                .filter_map(|c| c.call_graph_name())
+                .collect()
+        };
+        assert!(of_type(NodeType::Constructor).contains(&"Circle#Circle".to_string()));
+        let properties = of_type(NodeType::Property);
+        assert!(properties.contains(&"Circle#Name".to_string()));
+        assert!(properties.contains(&"IShape#Name".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_extract_csharp_references() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_csharp.cs")
+            .expect("failed to read sample_csharp.cs");
+
+        let references = parser
+            .extract_references(&content, "sample_csharp.cs", Language::CSharp, "test-repo")
+            .await
+            .unwrap();
+
+        let has = |caller: Option<&str>, callee: &str, kind: ReferenceKind| {
+            references.iter().any(|r| {
+                r.caller_symbol() == caller
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == kind
+            })
+        };
+        assert!(
+            has(Some("Point#DistanceTo"), "Math#Sqrt", ReferenceKind::Call),
+            "static calls keep their class: {references:?}"
+        );
+        assert!(has(
+            Some("ShapeRegistry#Register"),
+            "Add",
+            ReferenceKind::Call
+        ));
+        for method in ["Where", "Select", "Sum", "Area"] {
+            assert!(
+                has(Some("ShapeRegistry#TotalArea"), method, ReferenceKind::Call),
+                "LINQ method chain should call {method}"
+            );
+        }
+        for method in ["Where", "OrderByDescending", "Select", "Area"] {
+            assert!(
+                has(
+                    Some("ShapeRegistry#LargeNames"),
+                    method,
+                    ReferenceKind::Call
+                ),
+                "LINQ query syntax should call {method}"
+            );
+        }
+        assert!(has(
+            Some("Circle#FromDiameter"),
+            "Circle",
+            ReferenceKind::Instantiation
+        ));
+        assert!(
+            has(Some("ShapeRegistry"), "List", ReferenceKind::Instantiation),
+            "field initializers are attributed to the class"
+        );
+        assert!(has(None, "System.Linq", ReferenceKind::Import));
+        assert!(has(None, "System.Math", ReferenceKind::Import));
+    }
+
+    #[tokio::test]
+    async fn test_csharp_base_lists_and_this_calls() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_csharp.cs")
+            .expect("failed to read sample_csharp.cs");
+
+        let references = parser
+            .extract_references(&content, "sample_csharp.cs", Language::CSharp, "test-repo")
+            .await
+            .unwrap();
+
+        let has = |caller: Option<&str>, callee: &str, kind: ReferenceKind| {
+            has_reference(&references, caller, callee, kind)
+        };
+        assert!(
+            has(
+                Some("ScaledCircle#ScaledArea"),
+                "ScaledCircle#Area",
+                ReferenceKind::Call
+            ),
+            "this.Area() is qualified by the enclosing class: {references:?}"
+        );
+        assert!(has(
+            Some("ScaledCircle#CompareTo"),
+            "ScaledArea",
+            ReferenceKind::Call
+        ));
+        assert!(has(
+            Some("ScaledCircle"),
+            "Circle",
+            ReferenceKind::Inheritance
+        ));
+        assert!(
+            !has(
+                Some("ScaledCircle"),
+                "Circle",
+                ReferenceKind::Implementation
+            ),
+            "the base class is not an interface"
+        );
+        assert!(has(
+            Some("ScaledCircle"),
+            "IComparable",
+            ReferenceKind::Implementation
+        ));
+        assert!(has(
+            Some("ScaledCircle#CompareTo"),
+            "IComparable#CompareTo",
+            ReferenceKind::Implementation
+        ));
+        assert!(has(Some("Circle"), "IShape", ReferenceKind::Implementation));
+        assert!(has(
+            Some("Circle#Area"),
+            "IShape#Area",
+            ReferenceKind::Implementation
+        ));
+        assert!(
+            has(
+                Some("Circle#Name"),
+                "IShape#Name",
+                ReferenceKind::Implementation
+            ),
+            "properties implement interface properties"
+        );
+        assert!(has(Some("ISolid"), "IShape", ReferenceKind::Inheritance));
+    }
+
+    #[tokio::test]
+    async fn test_parse_elixir_modules() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_elixir.ex")
+            .expect("failed to read sample_elixir.ex");
+
+        let chunks = parser
+            .parse_file(&content, "sample_elixir.ex", Language::Elixir, "test-repo")
+            .await
+            .unwrap();
+
+        let find = |name: &str, node_type: NodeType| {
+            chunks
+                .iter()
+                .any(|c| c.symbol_name() == Some(name) && c.node_type() == node_type)
+        };
+        assert!(
+            find("Shapes.Circle", NodeType::Module),
+            "Should find Shapes.Circle module"
+        );
+        assert!(
+            find("Shapes.Circle", NodeType::Struct),
+            "Should find Shapes.Circle struct"
+        );
+        assert!(
+            find("Shapes.Shape", NodeType::Trait),
+            "Should find Shapes.Shape protocol"
+        );
+        assert!(
+            find("Shapes.Shape", NodeType::Impl),
+            "Should find Shapes.Shape implementation"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_elixir_functions_are_qualified() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_elixir.ex")
+            .expect("failed to read sample_elixir.ex");
+
+        let chunks = parser
+            .parse_file(&content, "sample_elixir.ex", Language::Elixir, "test-repo")
+            .await
+            .unwrap();
+
+        let names: Vec<String> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::Function)
+            .filter_map(|c| c.call_graph_name())
+            .collect();
+        for expected in [
+            "Shapes.Circle.new",
+            "Shapes.Circle.area",
+            "Shapes.Circle.scale",
+            "Shapes.Circle.validate",
+            "Shapes.Circle.unit",
+            "Shapes.Registry.total_area",
+            "Shapes.Registry.debug",
+        ] {
+            assert!(
+                names.iter().any(|n| n == expected),
+                "expected {expected} in {names:?}"
+            );
+        }
+    }
+
+    async fn elixir_fixture_references() -> Vec<SymbolReference> {
+        let content = std::fs::read_to_string("tests/fixtures/sample_elixir.ex")
+            .expect("failed to read sample_elixir.ex");
+        TreeSitterParser::new()
+            .extract_references(&content, "sample_elixir.ex", Language::Elixir, "test-repo")
+            .await
+            .unwrap()
+    }
+
+    fn has_reference(
+        references: &[SymbolReference],
+        caller: Option<&str>,
+        callee: &str,
+        kind: ReferenceKind,
+    ) -> bool {
+        references.iter().any(|r| {
+            r.caller_symbol() == caller && r.callee_symbol() == callee && r.reference_kind() == kind
+        })
+    }
+
+    #[tokio::test]
+    async fn test_elixir_pipeline_stages_are_calls() {
+        let references = elixir_fixture_references().await;
+        let call =
+            |caller, callee| has_reference(&references, Some(caller), callee, ReferenceKind::Call);
+        assert!(
+            call("Shapes.Circle.scale", "Shapes.Circle.validate"),
+            "local pipeline stage is qualified by its module: {references:?}"
+        );
+        assert!(call("Shapes.Circle.scale", "Map.update!"));
+        assert!(
+            call("Shapes.Registry.total_area", "Enum.map"),
+            "imported functions are qualified by their module"
+        );
+        assert!(call("Shapes.Registry.total_area", "Enum.sum"));
+    }
+
+    #[tokio::test]
+    async fn test_elixir_remote_calls_are_qualified() {
+        let references = elixir_fixture_references().await;
+        let call =
+            |caller, callee| has_reference(&references, Some(caller), callee, ReferenceKind::Call);
+        assert!(call("Shapes.Shape.area", "Shapes.Circle.area"));
+        assert!(call("Shapes.Circle.area", ":math.pi"));
+        assert!(
+            call("Shapes.Registry.total_area", "Shapes.Shape.area"),
+            "captures (&Mod.fun/1) are calls"
+        );
+        assert!(
+            call("Shapes.Registry.unit_area", "Shapes.Circle.unit"),
+            "aliases expand to the full module"
+        );
+        assert!(call("Shapes.Registry.unit_area", "Shapes.Shape.area"));
+        assert!(call("Shapes.Registry.debug", "IO.inspect"));
+    }
+
+    #[tokio::test]
+    async fn test_elixir_import_alias_and_use_edges() {
+        let references = elixir_fixture_references().await;
+        let import = |caller, callee| {
+            has_reference(&references, Some(caller), callee, ReferenceKind::Import)
+        };
+        assert!(import("Shapes.Circle", "Shapes.Shape"));
+        assert!(import("Shapes.Registry", "GenServer"), "use");
+        assert!(import("Shapes.Registry", "Enum"), "import");
+        assert!(
+            import("Shapes.Registry", "Shapes.Circle"),
+            "multi-alias expands each module"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_elixir_behaviours_and_protocol_impls() {
+        let references = elixir_fixture_references().await;
+        assert!(has_reference(
+            &references,
+            Some("Shapes.Registry"),
+            "GenServer",
+            ReferenceKind::Implementation
+        ));
+        assert!(has_reference(
+            &references,
+            Some("Shapes.Circle"),
+            "Shapes.Shape",
+            ReferenceKind::Implementation
+        ));
+        assert!(
+            !references
+                .iter()
+                .any(|r| matches!(r.callee_symbol(), "moduledoc" | "doc" | "behaviour")),
+            "module attributes are not calls"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_elixir_calls_attributed_to_enclosing_def() {
+        let references = elixir_fixture_references().await;
+        assert!(has_reference(
+            &references,
+            Some("Shapes.Circle.unit"),
+            "Shapes.Circle.new",
+            ReferenceKind::Call
+        ));
+        assert!(
+            !references.iter().any(|r| r.callee_symbol() == "is_number"),
+            "guards are not calls"
+        );
+        assert!(
+            !has_reference(
+                &references,
+                Some("Shapes.Circle.area"),
+                "Shapes.Circle.area",
+                ReferenceKind::Call
+            ),
+            "def heads are not calls"
+        );
+        assert!(
+            !references.iter().any(|r| matches!(
+                r.callee_symbol(),
+                "def" | "defp" | "defmacro" | "quote" | "unquote" | "defstruct"
+            )),
+            "definitions and special forms are not calls"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_lua_functions_and_tables() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_lua.lua")
+            .expect("failed to read sample_lua.lua");
+
+        let chunks = parser
+            .parse_file(&content, "sample_lua.lua", Language::Lua, "test-repo")
+            .await
+            .unwrap();
+
+        let find = |name: &str, node_type: NodeType| {
+            chunks
+                .iter()
+                .any(|c| c.symbol_name() == Some(name) && c.node_type() == node_type)
+        };
+        assert!(
+            find("Account", NodeType::Class),
+            "Should find Account table"
+        );
+        assert!(find("Config", NodeType::Class), "Should find Config table");
+        assert!(
+            find("clamp", NodeType::Function),
+            "Should find local function clamp"
+        );
+        assert!(
+            find("greet", NodeType::Function),
+            "Should find global function greet"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_lua_references() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_lua.lua")
+            .expect("failed to read sample_lua.lua");
+
+        let references = parser
+            .extract_references(&content, "sample_lua.lua", Language::Lua, "test-repo")
+            .await
+            .unwrap();
+
+        let has = |caller: Option<&str>, callee: &str, kind: ReferenceKind| {
+            has_reference(&references, caller, callee, kind)
+        };
+        let call = |caller, callee| has(caller, callee, ReferenceKind::Call);
+        assert!(
+            call(Some("SavingsAccount.accrue"), "SavingsAccount.deposit"),
+            "self:method() is qualified by the enclosing table: {references:?}"
+        );
+        assert!(call(
+            Some("SavingsAccount.accrue"),
+            "SavingsAccount.balance"
+        ));
+        assert!(call(Some("SavingsAccount.new"), "Account.new"));
+        assert!(call(Some("greet"), "json.encode"));
+        assert!(call(Some("greet"), "print"));
+        assert!(call(Some("Account.deposit"), "clamp"));
+        assert!(
+            call(None, "SavingsAccount.accrue"),
+            "obj:method() resolves instances created in the file"
+        );
+        assert!(has(None, "json", ReferenceKind::Import));
+        assert!(has(None, "util", ReferenceKind::Import));
+        assert!(has(
+            Some("SavingsAccount"),
+            "Account",
+            ReferenceKind::Inheritance
+        ));
+        assert!(
+            !has(Some("Account"), "Account", ReferenceKind::Inheritance),
+            "a table that is its own metatable inherits nothing"
+        );
+        assert!(has(
+            Some("Account.new"),
+            "Account",
+            ReferenceKind::Instantiation
+        ));
+        assert!(has(
+            Some("SavingsAccount.new"),
+            "SavingsAccount",
+            ReferenceKind::Instantiation
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parse_lua_methods_are_qualified() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_lua.lua")
+            .expect("failed to read sample_lua.lua");
+
+        let chunks = parser
+            .parse_file(&content, "sample_lua.lua", Language::Lua, "test-repo")
+            .await
+            .unwrap();
+
+        let names: Vec<String> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::Function)
+            .filter_map(|c| c.call_graph_name())
+            .collect();
+        for expected in ["Account.new", "Account.deposit", "Account.balance"] {
+            assert!(
+                names.iter().any(|n| n == expected),
+                "expected {expected} in {names:?}"
+            );
+        }
+    }
+
+    async fn ruby_fixture_references() -> Vec<SymbolReference> {
+        let content = std::fs::read_to_string("tests/fixtures/sample_ruby.rb")
+            .expect("failed to read sample_ruby.rb");
+        TreeSitterParser::new()
+            .extract_references(&content, "sample_ruby.rb", Language::Ruby, "test-repo")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ruby_calls_are_qualified_by_receiver() {
+        let references = ruby_fixture_references().await;
+        let call =
+            |caller, callee| has_reference(&references, Some(caller), callee, ReferenceKind::Call);
+        assert!(
+            call("Invoice#to_json", "Billing#currency"),
+            "calls on a constant keep its class: {references:?}"
+        );
+        assert!(call("CreditNote#for", "Logging#info"));
+        assert!(call("CreditNote#for", "add_line"));
+        assert!(
+            call("CreditNote#for", "format_amount"),
+            "top-level methods are not qualified"
+        );
+        assert!(
+            call("CreditNote#summary", "CreditNote#describe"),
+            "bare calls to the class's own methods are qualified"
+        );
+        assert!(call("Billing::Refund#apply", "add_line"));
+    }
+
+    #[tokio::test]
+    async fn test_ruby_kernel_methods_are_skipped() {
+        let references = ruby_fixture_references().await;
+        for kernel in ["puts", "raise", "new", "format", "attr_reader", "include"] {
+            assert!(
+                !references
+                    .iter()
+                    .any(|r| r.reference_kind() == ReferenceKind::Call
+                        && r.callee_symbol() == kernel),
+                "{kernel} should not be a call: {references:?}"
+            );
+        }
+        assert!(
+            !references
+                .iter()
+                .any(|r| r.callee_symbol() == "note" || r.callee_symbol() == "CreditNote#note"),
+            "local variables are not calls"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ruby_instantiations_and_constants() {
+        let references = ruby_fixture_references().await;
+        let has = |caller, callee, kind| has_reference(&references, Some(caller), callee, kind);
+        assert!(has(
+            "CreditNote#for",
+            "Invoice",
+            ReferenceKind::Instantiation
+        ));
+        assert!(
+            has("Invoice#empty", "Invoice", ReferenceKind::Instantiation),
+            "a bare `new` instantiates the enclosing class"
+        );
+        assert!(has(
+            "Invoice#add_line",
+            "ArgumentError",
+            ReferenceKind::TypeReference
+        ));
+        assert!(
+            !has_reference(&references, None, "Billing", ReferenceKind::TypeReference),
+            "definition names are not references"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ruby_requires_are_imports() {
+        let references = ruby_fixture_references().await;
+        assert!(has_reference(
+            &references,
+            None,
+            "json",
+            ReferenceKind::Import
+        ));
+        assert!(has_reference(
+            &references,
+            None,
+            "support/logging",
+            ReferenceKind::Import
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ruby_mixins_and_superclasses() {
+        let references = ruby_fixture_references().await;
+        let has = |caller, callee, kind| has_reference(&references, Some(caller), callee, kind);
+        assert!(has("Invoice", "Comparable", ReferenceKind::Implementation));
+        assert!(has("Invoice", "Forwardable", ReferenceKind::Implementation));
+        assert!(has("CreditNote", "Invoice", ReferenceKind::Inheritance));
+        assert!(
+            !has("Invoice", "Comparable", ReferenceKind::TypeReference),
+            "mixins are not also type references"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_ruby_classes_and_modules() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_ruby.rb")
+            .expect("failed to read sample_ruby.rb");
+
+        let chunks = parser
+            .parse_file(&content, "sample_ruby.rb", Language::Ruby, "test-repo")
+            .await
+            .unwrap();
+
+        let find = |name: &str, node_type: NodeType| {
+            chunks
+                .iter()
+                .any(|c| c.symbol_name() == Some(name) && c.node_type() == node_type)
+        };
+        assert!(
+            find("Billing", NodeType::Module),
+            "Should find Billing module"
+        );
+        assert!(
+            find("Invoice", NodeType::Class),
+            "Should find Invoice class"
+        );
+        assert!(
+            find("Billing::Refund", NodeType::Class),
+            "Should find scoped Billing::Refund class"
+        );
+        assert!(
+            find("format_amount", NodeType::Function),
+            "Should find top-level method format_amount"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_ruby_methods_are_qualified() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_ruby.rb")
+            .expect("failed to read sample_ruby.rb");
+
+        let chunks = parser
+            .parse_file(&content, "sample_ruby.rb", Language::Ruby, "test-repo")
+            .await
+            .unwrap();
+
+        let names: Vec<String> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::Function)
+            .filter_map(|c| c.call_graph_name())
+            .collect();
+        for expected in [
+            "Billing#currency",
+            "Invoice#initialize",
+            "Invoice#empty",
+            "Invoice#add_line",
+            "Invoice#to_json",
+            "Billing::Refund#apply",
+        ] {
+            assert!(
+                names.iter().any(|n| n == expected),
+                "expected {expected} in {names:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_bash_functions_in_both_syntaxes() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_bash.sh")
+            .expect("failed to read sample_bash.sh");
+
+        let chunks = parser
+            .parse_file(&content, "deploy.sh", Language::Bash, "test-repo")
+            .await
+            .unwrap();
+
+        let functions: Vec<&str> = chunks
+            .iter()
+            .filter(|c| c.node_type() == NodeType::Function)
+            .filter_map(|c| c.symbol_name())
+            .collect();
+        for expected in ["build_image", "push_image", "main"] {
+            assert!(
+                functions.contains(&expected),
+                "expected {expected} in {functions:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_bash_script_references() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_bash.sh")
+            .expect("failed to read sample_bash.sh");
+
+        let references = parser
+            .extract_references(&content, "bin/deploy.sh", Language::Bash, "test-repo")
+            .await
+            .unwrap();
+
+        let mut callees: Vec<&str> = references.iter().map(|r| r.callee_symbol()).collect();
+        callees.sort();
+        assert_eq!(callees, ["common.sh", "migrate.sh", "notify.sh"]);
+        assert!(references.iter().all(|r| {
+            r.reference_kind() == ReferenceKind::Call && r.caller_symbol() == Some("deploy.sh")
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_parse_c_definitions() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_c.c")
+            .expect("failed to read sample_c.c");
+
+        let chunks = parser
+            .parse_file(&content, "sample_c.c", Language::C, "test-repo")
+            .await
+            .unwrap();
+
+        let find = |name: &str, node_type: NodeType| {
+            chunks
+                .iter()
+                .any(|c| c.symbol_name() == Some(name) && c.node_type() == node_type)
+        };
+        assert!(find("node", NodeType::Struct), "Should find struct node");
+        assert!(find("list_t", NodeType::TypeDef), "Should find list_t");
+        assert!(find("color", NodeType::Enum), "Should find enum color");
+        assert!(
+            find("node_new", NodeType::Function),
+            "Should find pointer-returning node_new"
+        );
+        assert!(find("list_push", NodeType::Function));
+        assert!(
+            find("list_empty", NodeType::Function),
+            "Should find function-like macro list_empty"
+        );
+        assert!(find("LIST_MAX", NodeType::Constant));
+    }
+
+    #[tokio::test]
+    async fn test_extract_c_references() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_c.c")
+            .expect("failed to read sample_c.c");
+
+        let references = parser
+            .extract_references(&content, "sample_c.c", Language::C, "test-repo")
+            .await
+            .unwrap();
+
+        let has = |caller: Option<&str>, callee: &str, kind: ReferenceKind| {
+            references.iter().any(|r| {
+                r.caller_symbol() == caller
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == kind
+            })
+        };
+        assert!(has(Some("list_push"), "node_new", ReferenceKind::Call));
+        assert!(has(Some("node_new"), "malloc", ReferenceKind::Call));
+        assert!(has(None, "stdlib.h", ReferenceKind::Import));
+        assert!(has(None, "list.h", ReferenceKind::Import));
+        assert!(has(
+            Some("list_push"),
+            "list_t",
+            ReferenceKind::TypeReference
+        ));
+        // Declaring a type is not a reference to it.
+        assert!(!references
+            .iter()
+            .any(|r| r.callee_symbol() == "list_t" && r.caller_symbol().is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_parse_scala_definitions() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_scala.scala")
+            .expect("failed to read sample_scala.scala");
+
+        let chunks = parser
+            .parse_file(&content, "CountJob.scala", Language::Scala, "test-repo")
+            .await
+            .unwrap();
+
+        let find = |name: &str, parent: Option<&str>, node_type: NodeType| {
+            chunks.iter().any(|c| {
+                c.symbol_name() == Some(name)
+                    && c.parent_symbol() == parent
+                    && c.node_type() == node_type
+            })
+        };
+        assert!(find("Job", None, NodeType::Trait));
+        assert!(find("BaseJob", None, NodeType::Class));
+        assert!(find("Event", None, NodeType::Class), "case class");
+        assert!(find("CountJob", None, NodeType::Class));
+        assert!(find("CountJob", None, NodeType::Struct), "companion object");
+        assert!(find("DailyCounts", None, NodeType::TypeDef));
+        assert!(find("run", Some("Job"), NodeType::Function));
+        assert!(find("log", Some("BaseJob"), NodeType::Function));
+        assert!(find("load", Some("CountJob"), NodeType::Function));
+        assert!(find("summarise", Some("CountJob"), NodeType::Function));
+    }
+
+    #[tokio::test]
+    async fn test_extract_scala_references() {
+        let parser = TreeSitterParser::new();
+        let content = std::fs::read_to_string("tests/fixtures/sample_scala.scala")
+            .expect("failed to read sample_scala.scala");
+
+        let references = parser
+            .extract_references(&content, "CountJob.scala", Language::Scala, "test-repo")
+            .await
+            .unwrap();
+
+        let has = |caller: Option<&str>, callee: &str, kind: ReferenceKind| {
+            references.iter().any(|r| {
+                r.caller_symbol() == caller
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == kind
+            })
+        };
+        assert!(has(Some("CountJob.run"), "load", ReferenceKind::Call));
+        assert!(
+            has(Some("CountJob.run"), "CountJob.log", ReferenceKind::Call),
+            "this.log() is qualified by the enclosing class"
+        );
+        assert!(
+            has(
+                Some("CountJob.run"),
+                "CountJob.summarise",
+                ReferenceKind::Call
+            ),
+            "companion calls keep their object"
+        );
+        assert!(has(
+            Some("CountJob.main"),
+            "SparkSession.builder",
+            ReferenceKind::Call
+        ));
+        assert!(has(Some("CountJob.load"), "parquet", ReferenceKind::Call));
+        assert!(has(
+            None,
+            "org.apache.spark.sql.{DataFrame,SparkSession}",
+            ReferenceKind::Import
+        ));
+        assert!(has(
+            None,
+            "scala.concurrent.duration._",
+            ReferenceKind::Import
+        ));
+        assert!(has(Some("BaseJob"), "Job", ReferenceKind::Inheritance));
+        assert!(has(
+            Some("BaseJob"),
+            "Serializable",
+            ReferenceKind::Inheritance
+        ));
+        assert!(has(Some("CountJob"), "BaseJob", ReferenceKind::Inheritance));
+        assert!(has(
+            Some("CountJob.toDaily"),
+            "DailyCounts",
+            ReferenceKind::TypeReference
+        ));
     }
 
     #[tokio::test]
-    async fn test_parse_php_class() {
+    async fn test_go_receiver_call_qualified_with_receiver_type() {
         let parser = TreeSitterParser::new();
         let content = r#"
-<?php
-class Calculator {
-    public function add($a, $b) {
-        return $a + $b;
-    }
+package server
 
-    public function subtract($a, $b) {
-        return $a - $b;
-    }
+type Handler struct {
+    store *Store
+}
+
+func (h *Handler) Handle() {
+    h.validate()
+    h.store.Save()
+    log.Println("handled")
+}
+
+func (h Handler) validate() {}
+
+func (h *Handler) ServeHTTP() {
+    h.Handle()
+}
+
+func Serve(h *Handler) {
+    h.Handle()
+    helper()
 }
 "#;
 
-        let chunks = parser
-            .parse_file(content, "calc.php", Language::Php, "test-repo")
+        let references = parser
+            .extract_references(content, "server.go", Language::Go, "test-repo")
             .await
             .unwrap();
 
-        assert!(!chunks.is_empty());
+        let has = |caller: &str, callee: &str| {
+            references.iter().any(|r| {
+                r.caller_symbol() == Some(caller)
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == ReferenceKind::Call
+            })
+        };
+        assert!(
+            has("Handler.ServeHTTP", "Handler.Handle"),
+            "h.Handle() is qualified by the receiver type: {references:?}"
+        );
+        assert!(has("Handler.Handle", "Handler.validate"));
+        assert!(has("Handler.Handle", "Save"));
+        assert!(has("Handler.Handle", "Println"));
+        assert!(
+            has("Serve", "Handle"),
+            "only the method's own receiver is qualified"
+        );
+        assert!(has("Serve", "helper"));
     }
 
     #[tokio::test]
-    async fn test_parse_cpp_method_outside_class() {
+    async fn test_kotlin_this_call_qualified_with_enclosing_class() {
         let parser = TreeSitterParser::new();
-        let content = std::fs::read_to_string("tests/fixtures/sample_cpp.cpp")
-            .expect("failed to read sample_cpp.cpp");
+        let content = r#"
+class EventBus {
+    fun publish(event: String) {}
 
-        let chunks = parser
-            .parse_file(&content, "sample_cpp.cpp", Language::Cpp, "test-repo")
+    fun broadcast(event: String) {
+        this.publish(event)
+    }
+}
+
+object Registry {
+    fun register(name: String) {}
+}
+
+class OrderService(private val bus: EventBus, val log: Logger) {
+    fun place(order: String, audit: AuditTrail) {
+        bus.publish(order)
+        this.bus.publish(order)
+        Registry.register(order)
+        log.info(order)
+        audit.record(order)
+    }
+}
+"#;
+
+        let references = parser
+            .extract_references(content, "Orders.kt", Language::Kotlin, "test-repo")
             .await
             .unwrap();
 
-        let has_method = chunks
-            .iter()
-            .any(|chunk| chunk.symbol_name() == Some("calculate_area"));
-
-        assert!(has_method, "expected calculate_area to be indexed");
+        let has = |caller: &str, callee: &str| {
+            references.iter().any(|r| {
+                r.caller_symbol() == Some(caller)
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == ReferenceKind::Call
+            })
+        };
+        assert!(
+            has("EventBus.broadcast", "EventBus.publish"),
+            "this.publish() is qualified by the enclosing class"
+        );
+        assert!(
+            has("OrderService.place", "EventBus.publish"),
+            "calls on a constructor property use its type"
+        );
+        assert!(has("OrderService.place", "Registry.register"));
+        assert!(
+            has("OrderService.place", "Logger.info"),
+            "property types need not be declared in the file"
+        );
+        assert!(
+            has("OrderService.place", "record"),
+            "receivers of unknown types stay unqualified"
+        );
     }
 
     #[tokio::test]
-    async fn test_parse_cpp_does_not_duplicate_out_of_class_method() {
+    async fn test_swift_self_call_qualified_with_enclosing_class() {
         let parser = TreeSitterParser::new();
-        let content = std::fs::read_to_string("tests/fixtures/sample_cpp.cpp")
-            .expect("failed to read sample_cpp.cpp");
+        let content = r#"
+class EventBus {
+    func publish(_ event: String) {}
 
-        let chunks = parser
-            .parse_file(&content, "sample_cpp.cpp", Language::Cpp, "test-repo")
+    func broadcast(_ event: String) {
+        self.publish(event)
+    }
+}
+
+enum Registry {
+    static func register(_ name: String) {}
+}
+
+func place(_ order: String) {
+    Registry.register(order)
+    order.append("!")
+}
+"#;
+
+        let references = parser
+            .extract_references(content, "Orders.swift", Language::Swift, "test-repo")
             .await
             .unwrap();
 
-        let area_count = chunks
-            .iter()
-            .filter(|chunk| chunk.symbol_name() == Some("calculate_area"))
-            .count();
-        assert_eq!(
-            area_count, 1,
-            "expected calculate_area to appear exactly once"
+        let has = |caller: &str, callee: &str| {
+            references.iter().any(|r| {
+                r.caller_symbol() == Some(caller)
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == ReferenceKind::Call
+            })
+        };
+        assert!(
+            has("EventBus.broadcast", "EventBus.publish"),
+            "self.publish() is qualified by the enclosing class"
         );
+        assert!(has("place", "Registry.register"));
+        assert!(has("place", "append"));
+    }
 
-        let unnamed_area_count = chunks
-            .iter()
-            .filter(|chunk| {
-                chunk.node_type() == NodeType::Function
-                    && chunk.symbol_name().is_none()
-                    && chunk.content().contains("calculate_area")
+    #[tokio::test]
+    async fn test_extract_swift_protocol_conformances() {
+        let parser = TreeSitterParser::new();
+        let content = r#"
+protocol Identifiable {}
+protocol Auditable: Identifiable {}
+
+class Entity {}
+
+class Order: Entity, Codable, Auditable {}
+
+struct Line: Hashable, Identifiable {}
+
+extension Order: CustomStringConvertible {}
+"#;
+
+        let references = parser
+            .extract_references(content, "Order.swift", Language::Swift, "test-repo")
+            .await
+            .unwrap();
+
+        let has = |owner: &str, parent: &str, kind: ReferenceKind| {
+            references.iter().any(|r| {
+                r.caller_symbol() == Some(owner)
+                    && r.callee_symbol() == parent
+                    && r.reference_kind() == kind
             })
-            .count();
-        assert_eq!(
-            unnamed_area_count, 0,
-            "expected no unnamed function chunk containing calculate_area"
-        );
+        };
+        assert!(has("Order", "Entity", ReferenceKind::Inheritance));
+        for (owner, protocol) in [
+            ("Order", "Codable"),
+            ("Order", "Auditable"),
+            ("Line", "Hashable"),
+            ("Line", "Identifiable"),
+            ("Order", "CustomStringConvertible"),
+        ] {
+            assert!(
+                has(owner, protocol, ReferenceKind::Implementation),
+                "{owner} conforms to {protocol}"
+            );
+        }
+        assert!(has("Auditable", "Identifiable", ReferenceKind::Inheritance));
+    }
 
-        let main_count = chunks
-            .iter()
-            .filter(|chunk| chunk.symbol_name() == Some("main"))
-            .count();
-        assert_eq!(main_count, 1, "expected main to appear exactly once");
+    #[test]
+    fn test_split_imports_keeps_selectors_together() {
+        assert_eq!(split_imports(" a.B, c.{D, E => F}"), ["a.B", "c.{D,E=>F}"]);
+        assert_eq!(split_imports(" scala.util._"), ["scala.util._"]);
     }
+
     #[tokio::test]
-    async fn test_parse_swift_class_and_struct() {
+    async fn test_extract_typescript_decorator_references() {
         let parser = TreeSitterParser::new();
-        let content = std::fs::read_to_string("tests/fixtures/sample_swift.swift")
-            .expect("failed to read sample_swift.swift");
+        let content = r#"
+@Module({ imports: [HttpModule], providers: [AppService] })
+export class AppModule {}
 
-        let chunks = parser
-            .parse_file(&content, "sample_swift.swift", Language::Swift, "test-repo")
+@Component({ selector: 'app-root' })
+class AppComponent {
+    @Input() title: string;
+
+    constructor(@Inject('TOKEN') private readonly token: string) {}
+
+    @HostListener('click')
+    onClick() {}
+}
+"#;
+
+        let references = parser
+            .extract_references(content, "app.ts", Language::TypeScript, "test-repo")
             .await
             .unwrap();
 
-        assert!(!chunks.is_empty(), "Should extract chunks from Swift file");
+        let has = |caller: &str, callee: &str| {
+            references.iter().any(|r| {
+                r.caller_symbol() == Some(caller)
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == ReferenceKind::MacroInvocation
+            })
+        };
+        assert!(has("AppModule", "Module"), "{references:?}");
+        assert!(has("AppComponent", "Component"));
+        assert!(has("AppComponent#title", "Input"));
+        assert!(has("AppComponent#constructor", "Inject"));
+        assert!(has("AppComponent#onClick", "HostListener"));
+        // Decorator arguments are not themselves decorators.
+        assert!(!references.iter().any(|r| r.callee_symbol() == "HttpModule"));
+    }
 
-        let has_circle = chunks.iter().any(|c| c.symbol_name() == Some("Circle"));
-        assert!(has_circle, "Should find Circle class");
+    #[tokio::test]
+    async fn test_extract_implementation_references() {
+        let parser = TreeSitterParser::new();
+        let implementations = |content: &'static str, file: &'static str, language| {
+            let parser = &parser;
+            async move {
+                parser
+                    .extract_references(content, file, language, "test-repo")
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .filter(|r| r.reference_kind() == ReferenceKind::Implementation)
+                    .map(|r| {
+                        (
+                            r.caller_symbol().unwrap_or_default().to_string(),
+                            r.callee_symbol().to_string(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+        let edge = |caller: &str, callee: &str| (caller.to_string(), callee.to_string());
 
-        let has_rect = chunks.iter().any(|c| c.symbol_name() == Some("Rectangle"));
-        assert!(has_rect, "Should find Rectangle struct");
+        let rust = implementations(
+            r#"
+impl TreeSitterParser {
+    fn new() -> Self { Self }
+}
 
-        let has_shape = chunks.iter().any(|c| c.symbol_name() == Some("Shape"));
-        assert!(has_shape, "Should find Shape protocol");
+impl crate::application::ParserService for TreeSitterParser {
+    fn parse_file(&self) {}
+    fn supported_languages(&self) {}
+}
+"#,
+            "parser.rs",
+            Language::Rust,
+        )
+        .await;
+        assert_eq!(rust.len(), 3, "inherent impls link nothing: {rust:?}");
+        assert!(rust.contains(&edge("TreeSitterParser", "ParserService")));
+        assert!(rust.contains(&edge(
+            "TreeSitterParser::parse_file",
+            "ParserService::parse_file"
+        )));
+        assert!(rust.contains(&edge(
+            "TreeSitterParser::supported_languages",
+            "ParserService::supported_languages"
+        )));
+
+        let typescript = implementations(
+            r#"
+export class UserRepository extends Base implements Repository<User>, Disposable {
+    find(id: string) {}
+}
+"#,
+            "users.ts",
+            Language::TypeScript,
+        )
+        .await;
+        assert!(typescript.contains(&edge("UserRepository", "Repository")));
+        assert!(typescript.contains(&edge("UserRepository#find", "Repository#find")));
+        assert!(typescript.contains(&edge("UserRepository#find", "Disposable#find")));
+        assert!(!typescript.iter().any(|(_, callee)| callee == "Base"));
+
+        let php = implementations(
+            r#"<?php
+namespace App\Repositories;
+
+use App\Contracts\Repository;
+
+class UserRepository implements Repository, \Countable
+{
+    public function find($id) {}
+}
+"#,
+            "UserRepository.php",
+            Language::Php,
+        )
+        .await;
+        assert!(php.contains(&edge(
+            "App\\Repositories\\UserRepository",
+            "App\\Contracts\\Repository"
+        )));
+        assert!(php.contains(&edge(
+            "App\\Repositories\\UserRepository#find",
+            "App\\Contracts\\Repository#find"
+        )));
+        assert!(php.contains(&edge(
+            "App\\Repositories\\UserRepository#find",
+            "Countable#find"
+        )));
     }
 
     #[tokio::test]
-    async fn test_parse_swift_functions_and_enums() {
+    async fn test_extract_override_references() {
         let parser = TreeSitterParser::new();
-        let content = std::fs::read_to_string("tests/fixtures/sample_swift.swift")
-            .expect("failed to read sample_swift.swift");
+        let overrides = |content: &'static str, file: &'static str, language| {
+            let parser = &parser;
+            async move {
+                parser
+                    .extract_references(content, file, language, "test-repo")
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .filter(|r| r.reference_kind() == ReferenceKind::Override)
+                    .map(|r| {
+                        (
+                            r.caller_symbol().unwrap_or_default().to_string(),
+                            r.callee_symbol().to_string(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+        let edge = |caller: &str, callee: &str| (caller.to_string(), callee.to_string());
 
-        let chunks = parser
-            .parse_file(&content, "sample_swift.swift", Language::Swift, "test-repo")
-            .await
-            .unwrap();
+        let typescript = overrides(
+            r#"
+export class Circle extends shapes.Shape<number> implements Drawable {
+    constructor(private r: number) { super(); }
+    area() { return 3.14 * this.r * this.r; }
+}
+"#,
+            "circle.ts",
+            Language::TypeScript,
+        )
+        .await;
+        assert_eq!(typescript, vec![edge("Circle#area", "Shape#area")]);
 
-        let has_fn = chunks
-            .iter()
-            .any(|c| c.symbol_name() == Some("printShapeInfo"));
-        assert!(has_fn, "Should find printShapeInfo function");
+        let python = overrides(
+            r#"
+class Circle(Shape, metaclass=ABCMeta):
+    @override
+    def area(self):
+        return 3.14
 
-        let has_enum = chunks.iter().any(|c| c.symbol_name() == Some("Result"));
-        assert!(has_enum, "Should find Result enum");
+    # override: scaled like the base
+    def scale(self, k):
+        pass
+
+    def radius(self):
+        return 1
+"#,
+            "circle.py",
+            Language::Python,
+        )
+        .await;
+        assert_eq!(python.len(), 2, "{python:?}");
+        assert!(python.contains(&edge("Circle#area", "Shape#area")));
+        assert!(python.contains(&edge("Circle#scale", "Shape#scale")));
+
+        let kotlin = overrides(
+            r#"
+class Circle(val r: Double) : Shape(), Drawable {
+    override fun area(): Double = 3.14 * r * r
+    fun radius(): Double = r
+}
+"#,
+            "Circle.kt",
+            Language::Kotlin,
+        )
+        .await;
+        assert_eq!(kotlin.len(), 2, "{kotlin:?}");
+        assert!(kotlin.contains(&edge("Circle.area", "Shape.area")));
+        assert!(kotlin.contains(&edge("Circle.area", "Drawable.area")));
     }
 
     #[tokio::test]
-    async fn test_parse_kotlin_classes_and_functions() {
+    async fn test_extract_python_inheritance_references() {
         let parser = TreeSitterParser::new();
-        let content = std::fs::read_to_string("tests/fixtures/sample_kotlin.kt")
-            .expect("failed to read sample_kotlin.kt");
+        let content = r#"
+class Base:
+    pass
 
-        let chunks = parser
-            .parse_file(&content, "sample_kotlin.kt", Language::Kotlin, "test-repo")
+class Child(Base):
+    pass
+
+class Model(Child, mixins.Logged, metaclass=ABCMeta):
+    pass
+"#;
+
+        let references = parser
+            .extract_references(content, "models.py", Language::Python, "test-repo")
             .await
             .unwrap();
 
-        assert!(!chunks.is_empty(), "Should extract chunks from Kotlin file");
+        let has = |caller: &str, callee: &str| {
+            references.iter().any(|r| {
+                r.caller_symbol() == Some(caller)
+                    && r.callee_symbol() == callee
+                    && r.reference_kind() == ReferenceKind::Inheritance
+            })
+        };
+        assert!(has("Child", "Base"), "{references:?}");
+        assert!(has("Model", "Child"));
+        assert!(has("Model", "Logged"));
+        assert!(!references.iter().any(|r| r.callee_symbol() == "ABCMeta"));
+        assert!(!references.iter().any(|r| r.caller_symbol() == Some("Base")));
+    }
 
-        let has_circle = chunks.iter().any(|c| c.symbol_name() == Some("Circle"));
-        assert!(has_circle, "Should find Circle class");
+    #[tokio::test]
+    async fn test_oversized_function_is_split_into_overlapping_parts() {
+        let parser = TreeSitterParser::new().with_max_chunk_lines(100);
+        let body: String = (0..248).map(|i| format!("    let v{i} = {i};\n")).collect();
+        let content = format!("fn huge() {{\n{body}}}\n");
 
-        let has_rect = chunks.iter().any(|c| c.symbol_name() == Some("Rectangle"));
-        assert!(has_rect, "Should find Rectangle data class");
+        let chunks = parser
+            .parse_file(&content, "huge.rs", Language::Rust, "test-repo")
+            .await
+            .unwrap();
 
-        let has_shape = chunks.iter().any(|c| c.symbol_name() == Some("Shape"));
-        assert!(has_shape, "Should find Shape interface");
+        // 250 lines, windows of 100 advancing by 90: 1-100, 91-190, 181-250.
+        let spans: Vec<(u32, u32, Option<u32>)> = chunks
+            .iter()
+            .map(|c| (c.start_line(), c.end_line(), c.part()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![(1, 100, Some(1)), (91, 190, Some(2)), (181, 250, Some(3))]
+        );
+        let source_lines: Vec<&str> = content.lines().collect();
+        for chunk in &chunks {
+            assert_eq!(chunk.symbol_name(), Some("huge"));
+            // Parts hold whole source lines, matching their reported span.
+            let expected =
+                &source_lines[chunk.start_line() as usize - 1..chunk.end_line() as usize];
+            assert_eq!(chunk.content().lines().collect::<Vec<_>>(), expected);
+        }
     }
 
     #[tokio::test]
-    async fn test_parse_kotlin_object_and_enum() {
-        let parser = TreeSitterParser::new();
-        let content = std::fs::read_to_string("tests/fixtures/sample_kotlin.kt")
-            .expect("failed to read sample_kotlin.kt");
+    async fn test_function_within_limit_is_not_split() {
+        let parser = TreeSitterParser::new().with_max_chunk_lines(3);
+        let content = "fn small() {\n    let x = 1;\n}\n";
 
         let chunks = parser
-            .parse_file(&content, "sample_kotlin.kt", Language::Kotlin, "test-repo")
+            .parse_file(content, "small.rs", Language::Rust, "test-repo")
             .await
             .unwrap();
 
-        let has_math_utils = chunks.iter().any(|c| c.symbol_name() == Some("MathUtils"));
-        assert!(has_math_utils, "Should find MathUtils singleton object");
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].part(), None);
+        assert_eq!((chunks[0].start_line(), chunks[0].end_line()), (1, 3));
+    }
 
-        let has_color = chunks.iter().any(|c| c.symbol_name() == Some("Color"));
-        assert!(has_color, "Should find Color enum class");
+    #[tokio::test]
+    async fn test_rust_doc_comment_is_attached_to_function() {
+        let parser = TreeSitterParser::new();
+        let content = r#"use std::fmt;
 
-        let has_print_fn = chunks
+/// Adds two numbers.
+/// Overflow wraps.
+#[inline]
+pub fn add(a: u8, b: u8) -> u8 {
+    a.wrapping_add(b)
+}
+
+// Unrelated note.
+
+fn sub(a: u8, b: u8) -> u8 {
+    a - b
+}
+"#;
+
+        let chunks = parser
+            .parse_file(content, "math.rs", Language::Rust, "test-repo")
+            .await
+            .unwrap();
+
+        let add = chunks
             .iter()
-            .any(|c| c.symbol_name() == Some("printShapeInfo"));
-        assert!(
-            has_print_fn,
-            "Should find printShapeInfo top-level function"
+            .find(|c| c.symbol_name() == Some("add"))
+            .unwrap();
+        assert!(add
+            .content()
+            .starts_with("/// Adds two numbers.\n/// Overflow wraps.\n#[inline]"));
+        assert_eq!((add.start_line(), add.end_line()), (3, 8));
+        assert_eq!(
+            add.doc_comment(),
+            Some("Adds two numbers.\nOverflow wraps.")
         );
+
+        // A blank line separates the comment from `sub`.
+        let sub = chunks
+            .iter()
+            .find(|c| c.symbol_name() == Some("sub"))
+            .unwrap();
+        assert!(sub.content().starts_with("fn sub"));
+        assert_eq!(sub.start_line(), 12);
+        assert_eq!(sub.doc_comment(), None);
     }
 
     #[tokio::test]
-    async fn test_parse_kotlin_type_alias() {
+    async fn test_python_comments_and_docstring_are_in_chunk() {
         let parser = TreeSitterParser::new();
-        let content = std::fs::read_to_string("tests/fixtures/sample_kotlin.kt")
-            .expect("failed to read sample_kotlin.kt");
+        let content = r#"# Normalises user input.
+def clean(value):
+    """Strip whitespace and lowercase."""
+    return value.strip().lower()
+"#;
 
         let chunks = parser
-            .parse_file(&content, "sample_kotlin.kt", Language::Kotlin, "test-repo")
+            .parse_file(content, "clean.py", Language::Python, "test-repo")
             .await
             .unwrap();
 
-        let typedef_chunks: Vec<_> = chunks
+        let clean = chunks
             .iter()
-            .filter(|c| c.node_type() == NodeType::TypeDef)
-            .collect();
-        assert!(
-            !typedef_chunks.is_empty(),
-            "Should find at least one type alias"
-        );
+            .find(|c| c.symbol_name() == Some("clean"))
+            .unwrap();
+        assert!(clean.content().starts_with("# Normalises user input."));
+        assert!(clean.content().contains("Strip whitespace and lowercase."));
+        assert_eq!(clean.start_line(), 1);
+        // The docstring wins over the comment above the `def`.
+        assert_eq!(clean.doc_comment(), Some("Strip whitespace and lowercase."));
+    }
 
-        let has_shape_list = chunks.iter().any(|c| c.symbol_name() == Some("ShapeList"));
-        assert!(has_shape_list, "Should find ShapeList type alias");
+    #[tokio::test]
+    async fn test_typescript_jsdoc_is_attached_to_exported_function() {
+        let parser = TreeSitterParser::new();
+        let content = r#"import { db } from "./db";
+
+/**
+ * Loads a user by id.
+ */
+export function loadUser(id: string) {
+    return db.users.find(id);
+}
+"#;
+
+        let chunks = parser
+            .parse_file(content, "users.ts", Language::TypeScript, "test-repo")
+            .await
+            .unwrap();
+
+        let load = chunks
+            .iter()
+            .find(|c| c.symbol_name() == Some("loadUser"))
+            .unwrap();
+        assert!(load.content().contains("Loads a user by id."));
+        assert!(load.content().contains("export function loadUser"));
+        assert_eq!(load.start_line(), 3);
+        assert_eq!(load.doc_comment(), Some("Loads a user by id."));
     }
 }
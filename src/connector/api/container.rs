@@ -7,27 +7,30 @@ use tracing::{debug, warn};
 
 use crate::application::{
     AnalysisRepository, CallGraphRepository, CallGraphUseCase, ChannelEndpointRepository,
-    ChannelLinkUseCase, ChatClient, FileHashRepository, ImportSessionUseCase, MemoryBrowseUseCase,
-    MemoryDreamUseCase, MemoryExtractionUseCase, MemoryRepository, MemorySearchUseCase,
-    MetadataRepository, QueryExpander, SummarizeMemoryUseCase,
+    ChannelLinkUseCase, ChatClient, EmbeddingCache, FileHashRepository, ImportSessionUseCase,
+    IndexArchiveUseCase, MemoryBrowseUseCase, MemoryDreamUseCase, MemoryExtractionUseCase,
+    MemoryRepository, MemorySearchUseCase, MetadataRepository, QueryExpander,
+    SummarizeMemoryUseCase,
 };
 use crate::cli::{EmbeddingTarget, LlmTarget, RerankingTarget};
-use crate::connector::adapter::scip::ScipRunner;
+use crate::connector::adapter::scip::{ScipIndexerOverrides, ScipRunner};
 use crate::connector::adapter::{
-    DuckdbAnalysisRepository, DuckdbMemoryRepository, NamespaceEmbeddingConfig, NoEmbedding,
+    DuckdbAnalysisRepository, DuckdbEmbeddingCache, DuckdbMemoryRepository, GitCli,
+    NamespaceEmbeddingConfig, NoEmbedding, PgVectorRepository, EMBEDDING_CACHE_DB_FILE,
     MEMORY_DB_FILE, NO_EMBEDDINGS_MODEL,
 };
 use crate::{
     AnthropicClient, AnthropicReranking, ClusterDetectionUseCase, CommunityNamingUseCase,
-    CopilotChatClient, CouplingDetectionUseCase, DeleteRepositoryUseCase,
+    CopilotChatClient, CouplingDetectionUseCase, DeleteRepositoryUseCase, DiffImpactUseCase,
     DuckdbCallGraphRepository, DuckdbChannelEndpointRepository, DuckdbFileHashRepository,
     DuckdbMetadataRepository, DuckdbVectorRepository, EmbeddingService, ExecutionFeaturesUseCase,
     ExplainUseCase, FileRelationshipUseCase, GraphExpansionUseCase, ImpactAnalysisUseCase,
-    InMemoryVectorRepository, IndexRepositoryUseCase, ListRepositoriesUseCase, LlmQueryExpander,
-    MockEmbedding, MockReranking, OpenAiChatClient, OpenAiEmbedding, OpenAiReranking, OrtEmbedding,
-    OrtReranking, RepositoryOverviewUseCase, RerankingService, Scip, SearchCodeUseCase,
+    InMemoryVectorRepository, IndexHealthUseCase, IndexRepositoryUseCase, ListRepositoriesUseCase,
+    LlmQueryExpander, MockEmbedding, MockReranking, OllamaEmbedding, OpenAiChatClient,
+    OpenAiEmbedding, OpenAiReranking, OrtEmbedding, OrtReranking, PruneRepositoryUseCase,
+    RepositoryOverviewUseCase, RerankingService, Scip, SearchCodeUseCase, SimilarCodeUseCase,
     SnippetLookupUseCase, SymbolClusterDetectionUseCase, SymbolContextUseCase,
-    TreeSitterChannelExtractor, TreeSitterParser, VectorRepository,
+    TreeSitterChannelExtractor, TreeSitterParser, VectorRepository, VectorStore,
 };
 
 pub struct ContainerConfig {
@@ -114,6 +117,19 @@ pub struct ContainerConfig {
     ///
     /// Default: 4.
     pub parse_concurrency: usize,
+    /// Store chunks and embeddings in Postgres with pgvector instead of
+    /// DuckDB (`--postgres-url`). The namespace maps to a Postgres schema;
+    /// repository metadata and the call graph stay in DuckDB.
+    pub postgres_url: Option<String>,
+    /// Report indexing progress as periodic lines instead of a redrawn bar,
+    /// so log lines reaching the console (`--verbose`, `--log-format json`)
+    /// stay readable.
+    pub plain_progress: bool,
+    /// `--verbose`: commands with an extended report print it (`stats`
+    /// adds per-namespace storage and language breakdowns).
+    pub verbose: bool,
+    /// Per-indexer SCIP overrides and custom indexers (`--scip-indexer`).
+    pub scip_indexers: ScipIndexerOverrides,
 }
 
 pub struct Container {
@@ -131,11 +147,17 @@ pub struct Container {
     /// `vector_repo` so cross-namespace read views can be built (`None` for
     /// in-memory storage).
     duckdb_vector: Option<Arc<DuckdbVectorRepository>>,
+    /// Embedding model and dimensions the namespace was opened with; recorded
+    /// in exported archives and checked on import.
+    embedding_cfg: NamespaceEmbeddingConfig,
     /// Lazily opened memory store, shared across calls. Caching matters for
     /// the long-running MCP server: DuckDB allows only one writer per file,
     /// so concurrent tool calls must reuse a single connection instead of
     /// each opening `memory.duckdb`.
     memory_repo: std::sync::Mutex<Option<Arc<dyn MemoryRepository>>>,
+    /// Lazily opened embedding cache (`embedding_cache.duckdb`), shared
+    /// across index runs for the same single-writer reason.
+    embedding_cache: std::sync::Mutex<Option<Arc<dyn EmbeddingCache>>>,
     /// The live active LLM backend. Seeded at boot from the persisted config
     /// (`config.json`'s `llm_target`, falling back to the `--llm-target` flag)
     /// and switchable at runtime via the management API, so a native app can
@@ -231,6 +253,11 @@ const READ_ONLY_LOCK_RETRIES: u32 = 5;
 /// 500 ms → 1 s → 2 s → 4 s → 8 s  (≈ 15.5 s total wait before giving up).
 const READ_ONLY_LOCK_RETRY_INITIAL_MS: u64 = 500;
 
+/// How long a SCIP indexer may run before it is killed and the index falls
+/// back to tree-sitter references. Generous, since large TypeScript projects
+/// legitimately take minutes.
+const SCIP_INDEXER_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
 /// Returns `true` when the error string looks like a DuckDB file-lock conflict
 /// produced by a concurrent writer process.
 pub(crate) fn is_lock_conflict(err: &str) -> bool {
@@ -281,9 +308,10 @@ impl Container {
                 Some(m) => m,
                 None => match config.embedding_target {
                     EmbeddingTarget::Onnx => ONNX_DEFAULT_MODEL.to_string(),
-                    EmbeddingTarget::Api => {
+                    EmbeddingTarget::Api | EmbeddingTarget::Ollama => {
                         return Err(anyhow::anyhow!(
-                            "--embedding-model is required when using --embedding-target=api"
+                            "--embedding-model is required when using --embedding-target=api \
+                             or --embedding-target=ollama"
                         ));
                     }
                 },
@@ -311,7 +339,22 @@ impl Container {
                     } else {
                         Some(effective_model.as_str())
                     };
-                    Arc::new(OrtEmbedding::new(model_arg)?)
+                    let service = OrtEmbedding::new(model_arg)?;
+                    // The namespace's FLOAT[N] column is sized from the stored
+                    // dimensions; a model of another width can never fill it.
+                    if service.dimensions() != config.embedding_dimensions {
+                        return Err(anyhow::anyhow!(
+                            "Embedding model '{}' outputs {}-dimensional vectors, but namespace \
+                             '{}' is configured for {}. Create a namespace with \
+                             --embedding-dimensions {} for this model.",
+                            effective_model,
+                            service.dimensions(),
+                            config.namespace,
+                            config.embedding_dimensions,
+                            service.dimensions()
+                        ));
+                    }
+                    Arc::new(service)
                 }
                 EmbeddingTarget::Api => {
                     debug!(
@@ -323,6 +366,16 @@ impl Container {
                         config.embedding_dimensions,
                     ))
                 }
+                EmbeddingTarget::Ollama => {
+                    debug!(
+                        "Using Ollama embedding service (model='{}', dims={}, concurrency={})",
+                        effective_model, config.embedding_dimensions, config.parse_concurrency
+                    );
+                    Arc::new(
+                        OllamaEmbedding::new(effective_model.clone(), config.embedding_dimensions)
+                            .with_concurrency(config.parse_concurrency),
+                    )
+                }
             }
         };
 
@@ -371,6 +424,7 @@ impl Container {
                 match config.embedding_target {
                     EmbeddingTarget::Onnx => "onnx".to_string(),
                     EmbeddingTarget::Api => "api".to_string(),
+                    EmbeddingTarget::Ollama => "ollama".to_string(),
                 }
             },
             embedding_model: effective_model,
@@ -387,7 +441,30 @@ impl Container {
             call_graph_repo,
             channel_endpoint_repo,
             analysis_repo,
-        ): StorageRepos = if config.memory_storage {
+        ): StorageRepos = if let Some(url) = &config.postgres_url {
+            debug!(
+                "Using Postgres vector storage namespace {}",
+                config.namespace
+            );
+            let vector = Arc::new(
+                PgVectorRepository::connect(url, &config.namespace, ns_cfg.dimensions).await?,
+            );
+            let (
+                repo_adapter,
+                file_hash_repo,
+                call_graph_repo,
+                channel_endpoint_repo,
+                analysis_repo,
+            ) = init_duckdb_metadata_repos(&db_path, config.read_only).await?;
+            (
+                vector,
+                repo_adapter,
+                file_hash_repo,
+                call_graph_repo,
+                channel_endpoint_repo,
+                analysis_repo,
+            )
+        } else if config.memory_storage {
             debug!("Using in-memory vector storage");
             let vector = Arc::new(InMemoryVectorRepository::new());
             let (
@@ -560,7 +637,8 @@ impl Container {
             }
         };
 
-        let call_graph_use_case = Arc::new(CallGraphUseCase::new(call_graph_repo));
+        let call_graph_use_case =
+            Arc::new(CallGraphUseCase::new(call_graph_repo).with_vector_repo(vector_repo.clone()));
 
         // Initialise the query expander when --expand-query is requested.
         // Falls back gracefully to the original query when the server is unreachable.
@@ -608,7 +686,9 @@ impl Container {
             channel_endpoint_repo,
             analysis_repo,
             duckdb_vector,
+            embedding_cfg: ns_cfg,
             memory_repo: std::sync::Mutex::new(None),
+            embedding_cache: std::sync::Mutex::new(None),
             // A backend chosen through the app (persisted in config.json) wins
             // over the flag's default, so the choice survives restarts. The flag
             // is the fallback when nothing was persisted.
@@ -623,11 +703,15 @@ impl Container {
     }
 
     pub fn index_use_case(&self) -> IndexRepositoryUseCase {
-        let scip: Arc<dyn Scip> = Arc::new(ScipRunner);
+        let scip: Arc<dyn Scip> = Arc::new(
+            ScipRunner::new()
+                .with_timeout(SCIP_INDEXER_TIMEOUT)
+                .with_overrides(self.config.scip_indexers.clone()),
+        );
         // One extractor instance serves both roles: per-file extraction and the
         // cross-file resolution pass (it owns the tree-sitter config resolver).
         let channel_extractor = Arc::new(TreeSitterChannelExtractor::new());
-        IndexRepositoryUseCase::new(
+        let use_case = IndexRepositoryUseCase::new(
             self.repo_adapter.clone(),
             self.vector_repo.clone(),
             self.file_hash_repo.clone(),
@@ -636,13 +720,25 @@ impl Container {
             self.embedding_service.clone(),
         )
         .with_scip(scip)
+        .with_git(Arc::new(GitCli::new()))
         .with_channel_extraction(
             channel_extractor.clone(),
             self.channel_endpoint_repo.clone(),
         )
         .with_channel_resolution(channel_extractor)
-        .with_analysis_repo(self.analysis_repo.clone())
-        .with_parse_concurrency(self.config.parse_concurrency)
+        .with_analysis_repo(self.analysis_repo.clone());
+        if !self.embedding_service.embeddings_enabled() {
+            return use_case;
+        }
+        // The cache only saves work; indexing proceeds without it when the
+        // file cannot be opened (e.g. another process holds it).
+        match self.embedding_cache() {
+            Ok(cache) => use_case.with_embedding_cache(cache),
+            Err(e) => {
+                warn!("Embedding cache unavailable, embedding every chunk: {}", e);
+                use_case
+            }
+        }
     }
 
     pub fn search_use_case(&self) -> SearchCodeUseCase {
@@ -664,6 +760,10 @@ impl Container {
         use_case
     }
 
+    pub fn similar_code_use_case(&self) -> SimilarCodeUseCase {
+        SimilarCodeUseCase::new(self.vector_repo.clone())
+    }
+
     pub fn list_use_case(&self) -> ListRepositoriesUseCase {
         ListRepositoriesUseCase::new(self.repo_adapter.clone())
     }
@@ -672,6 +772,11 @@ impl Container {
         self.repo_adapter.clone()
     }
 
+    /// The vector store backing the active namespace.
+    pub fn vector_repository(&self) -> Arc<dyn VectorRepository> {
+        self.vector_repo.clone()
+    }
+
     /// The channel-endpoint repository (Kafka/HTTP/MQTT endpoints).
     pub fn channel_endpoint_repository(
         &self,
@@ -744,6 +849,36 @@ impl Container {
         .with_analysis_repo(self.analysis_repo.clone())
     }
 
+    pub fn prune_use_case(&self) -> PruneRepositoryUseCase {
+        PruneRepositoryUseCase::new(
+            self.repo_adapter.clone(),
+            self.vector_repo.clone(),
+            self.file_hash_repo.clone(),
+            self.call_graph_use_case.clone(),
+        )
+        .with_channel_endpoints(self.channel_endpoint_repo.clone())
+        .with_analysis_repo(self.analysis_repo.clone())
+    }
+
+    pub fn index_archive_use_case(&self) -> IndexArchiveUseCase {
+        IndexArchiveUseCase::new(
+            self.repo_adapter.clone(),
+            self.vector_repo.clone(),
+            self.file_hash_repo.clone(),
+            self.call_graph_use_case.clone(),
+            self.embedding_cfg.embedding_model.clone(),
+            self.embedding_cfg.dimensions,
+        )
+    }
+
+    pub fn index_health_use_case(&self) -> IndexHealthUseCase {
+        IndexHealthUseCase::new(
+            self.vector_repo.clone(),
+            self.repo_adapter.clone(),
+            self.file_hash_repo.clone(),
+        )
+    }
+
     pub fn channel_link_use_case(&self) -> ChannelLinkUseCase {
         ChannelLinkUseCase::new(self.channel_endpoint_repo.clone())
     }
@@ -757,10 +892,45 @@ impl Container {
         ImpactAnalysisUseCase::new(self.call_graph_use_case.clone())
     }
 
+    /// Impact analysis of a git diff, reading chunks from `namespace`'s
+    /// store (the active one when `None` or unreachable).
+    pub async fn diff_impact_use_case(&self, namespace: Option<&str>) -> DiffImpactUseCase {
+        let vector_repo = match namespace {
+            Some(ns) => self.namespace_vector_repository(ns).await,
+            None => None,
+        };
+        DiffImpactUseCase::new(
+            self.impact_use_case(),
+            vector_repo.unwrap_or_else(|| self.vector_repo.clone()),
+            Arc::new(GitCli::new()),
+        )
+    }
+
     pub fn context_use_case(&self) -> SymbolContextUseCase {
         SymbolContextUseCase::new(self.call_graph_use_case.clone())
     }
 
+    /// The vector store of `namespace`: the boot store for the active
+    /// namespace, a read view over the shared DuckDB file for a sibling.
+    /// `None` when the namespace can't be reached from this container
+    /// (in-memory or Postgres storage, or a namespace never indexed).
+    pub async fn namespace_vector_repository(
+        &self,
+        namespace: &str,
+    ) -> Option<Arc<dyn VectorRepository>> {
+        if namespace == self.config.namespace {
+            return Some(self.vector_repo.clone());
+        }
+        let duckdb = self.duckdb_vector.as_ref()?;
+        match duckdb.namespace_view(namespace).await {
+            Ok(view) => Some(Arc::new(view)),
+            Err(e) => {
+                tracing::debug!("No vector store view for namespace '{namespace}': {e}");
+                None
+            }
+        }
+    }
+
     pub fn snippet_lookup_use_case(&self) -> SnippetLookupUseCase {
         SnippetLookupUseCase::new(self.vector_repo.clone())
     }
@@ -893,6 +1063,24 @@ impl Container {
         Ok(repo)
     }
 
+    /// Open the embedding cache — a dedicated DuckDB file
+    /// (`embedding_cache.duckdb`) shared by every namespace, created on first
+    /// use. Entries are keyed by model, so switching models never returns a
+    /// stale vector.
+    pub fn embedding_cache(&self) -> Result<Arc<dyn EmbeddingCache>> {
+        let mut cache = self
+            .embedding_cache
+            .lock()
+            .map_err(|_| anyhow::anyhow!("embedding cache lock poisoned"))?;
+        if let Some(repo) = cache.as_ref() {
+            return Ok(Arc::clone(repo));
+        }
+        let db_path = PathBuf::from(&self.config.data_dir).join(EMBEDDING_CACHE_DB_FILE);
+        let repo: Arc<dyn EmbeddingCache> = Arc::new(DuckdbEmbeddingCache::new(&db_path)?);
+        *cache = Some(Arc::clone(&repo));
+        Ok(repo)
+    }
+
     /// Session import + memory extraction + virtual-filesystem summarization,
     /// all driven by the given chat model.
     pub fn memory_import_use_case(
@@ -976,6 +1164,14 @@ impl Container {
         PathBuf::from(&self.config.data_dir).join("codesearch.duckdb")
     }
 
+    /// Size in bytes of the DuckDB database file, or `None` when it does not
+    /// exist (e.g. an in-memory store).
+    pub fn data_file_size(&self) -> Option<u64> {
+        std::fs::metadata(self.metadata_db_path())
+            .ok()
+            .map(|m| m.len())
+    }
+
     /// The live active LLM backend. Seeded from persisted config / the
     /// `--llm-target` flag at boot and updated by [`Container::set_llm_target`].
     /// Serve-mode handlers use it as the default when a request omits its own,
@@ -1006,4 +1202,23 @@ impl Container {
     pub fn memory_storage(&self) -> bool {
         self.config.memory_storage
     }
+
+    pub fn plain_progress(&self) -> bool {
+        self.config.plain_progress
+    }
+
+    pub fn verbose(&self) -> bool {
+        self.config.verbose
+    }
+
+    /// The store chunks are written to, recorded on each indexed repository.
+    pub fn vector_store(&self) -> VectorStore {
+        if self.config.postgres_url.is_some() {
+            VectorStore::Postgres
+        } else if self.config.memory_storage {
+            VectorStore::InMemory
+        } else {
+            VectorStore::DuckDb
+        }
+    }
 }
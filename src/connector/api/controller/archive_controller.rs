@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::application::IndexArchive;
+use crate::connector::adapter::{read_index_archives, write_index_archives};
+
+use super::super::Container;
+
+pub struct ArchiveController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> ArchiveController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Write the index of `repository` (ID or name), or of every repository
+    /// in the current namespace, to `out`.
+    pub async fn export(&self, repository: Option<String>, out: String) -> Result<String> {
+        let use_case = self.container.index_archive_use_case();
+        let archives: Vec<IndexArchive> = match repository {
+            Some(repository) => {
+                let repository_id = self
+                    .container
+                    .resolve_repository_id(Some(&repository))
+                    .await;
+                vec![use_case.export(&repository_id).await?]
+            }
+            None => {
+                use_case
+                    .export_namespace(self.container.namespace())
+                    .await?
+            }
+        };
+        if archives.is_empty() {
+            anyhow::bail!(
+                "No repositories indexed in namespace '{}'",
+                self.container.namespace()
+            );
+        }
+
+        let mut lines: Vec<String> = archives
+            .iter()
+            .map(|archive| {
+                format!(
+                    "Exported {} ({} chunks, {} embeddings, {} references)",
+                    archive.repository.name(),
+                    archive.chunks.len(),
+                    archive.embeddings.len(),
+                    archive.references.len()
+                )
+            })
+            .collect();
+        lines.push(format!("Wrote {}", out));
+        let out_path = PathBuf::from(&out);
+        tokio::task::spawn_blocking(move || write_index_archives(&archives, &out_path))
+            .await
+            .context("Archive writer task panicked")??;
+        Ok(lines.join("\n"))
+    }
+
+    /// Load the archive at `archive` into the current namespace. A
+    /// single-repository archive may be registered at the local checkout
+    /// `path`; otherwise each repository keeps its archived path.
+    pub async fn import(&self, archive: String, path: Option<String>) -> Result<String> {
+        let local_path = match path {
+            Some(path) => Some(
+                Path::new(&path)
+                    .canonicalize()
+                    .with_context(|| format!("Invalid path '{}'", path))?
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            None => None,
+        };
+        let archive_path = PathBuf::from(&archive);
+        let archives = tokio::task::spawn_blocking(move || read_index_archives(&archive_path))
+            .await
+            .context("Archive reader task panicked")??;
+        if local_path.is_some() && archives.len() > 1 {
+            anyhow::bail!(
+                "{} holds {} repositories; --path only applies to single-repository archives",
+                archive,
+                archives.len()
+            );
+        }
+
+        let use_case = self.container.index_archive_use_case();
+        let mut lines = Vec::with_capacity(archives.len());
+        for archive in archives {
+            let chunk_count = archive.chunks.len();
+            let path = local_path
+                .clone()
+                .unwrap_or_else(|| archive.repository.path().to_string());
+            let repository = use_case
+                .import(
+                    archive,
+                    &path,
+                    self.container.vector_store(),
+                    self.container.namespace(),
+                )
+                .await?;
+            lines.push(format!(
+                "Imported {} ({} chunks) at {}\nRepository ID: {}",
+                repository.name(),
+                chunk_count,
+                repository.path(),
+                repository.id()
+            ));
+        }
+        Ok(lines.join("\n"))
+    }
+}
@@ -0,0 +1,24 @@
+use anyhow::{Context, Result};
+
+use super::super::Container;
+
+pub struct CacheController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> CacheController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Drop every cached embedding, for all models.
+    pub async fn clear(&self) -> Result<String> {
+        let removed = self
+            .container
+            .embedding_cache()?
+            .clear()
+            .await
+            .context("Failed to clear the embedding cache")?;
+        Ok(format!("Cleared {} cached embeddings", removed))
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+
+use crate::tui::widgets::syntax::dedent;
+use crate::CodeChunk;
+
+use super::super::Container;
+
+/// Content lines shown per chunk.
+const PREVIEW_LINES: usize = 5;
+
+/// Preview lines longer than this are cut with `…`.
+const PREVIEW_WIDTH: usize = 100;
+
+pub struct ChunkController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> ChunkController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// List the chunks stored for `file` (relative to the repository root),
+    /// or only those covering `line`.
+    pub async fn chunk(
+        &self,
+        file: String,
+        line: Option<u32>,
+        repository: Option<String>,
+    ) -> Result<String> {
+        let file = file.trim_start_matches("./");
+        let use_case = self
+            .container
+            .snippet_lookup_for_repository(repository.as_deref())
+            .await;
+        let chunks = use_case
+            .chunks_for_file(repository.as_deref().unwrap_or(""), file, line)
+            .await?;
+
+        if chunks.is_empty() {
+            return Ok(match line {
+                Some(line) => format!("No stored chunk covers line {line} of {file}."),
+                None => format!("No chunks stored for {file}."),
+            });
+        }
+        Ok(format_chunks(file, &chunks))
+    }
+}
+
+fn format_chunks(file: &str, chunks: &[CodeChunk]) -> String {
+    let mut output = format!("{} chunk(s) stored for {}:\n\n", chunks.len(), file);
+    for chunk in chunks {
+        let part = chunk
+            .part()
+            .map(|p| format!(" (part {p})"))
+            .unwrap_or_default();
+        output.push_str(&format!(
+            "{}  {}-{}  {}  {}{}\n",
+            chunk.id(),
+            chunk.start_line(),
+            chunk.end_line(),
+            chunk.node_type(),
+            chunk.symbol_name().unwrap_or("-"),
+            part
+        ));
+        for line in dedent(chunk.content()).lines().take(PREVIEW_LINES) {
+            output.push_str(&format!("   | {}\n", truncate(line)));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn truncate(line: &str) -> String {
+    match line.char_indices().nth(PREVIEW_WIDTH) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
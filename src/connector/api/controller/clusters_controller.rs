@@ -70,6 +70,9 @@ impl<'a> ClustersController<'a> {
             OutputFormat::Vimgrep => {
                 anyhow::bail!("vimgrep output format is not supported for cluster list")
             }
+            OutputFormat::Sarif => {
+                anyhow::bail!("sarif output format is not supported for cluster list")
+            }
             OutputFormat::Text => {
                 if cg.clusters.is_empty() {
                     return Ok(format!(
@@ -146,6 +149,9 @@ impl<'a> ClustersController<'a> {
             (Some(_), OutputFormat::Vimgrep) => {
                 anyhow::bail!("vimgrep output format is not supported for cluster get")
             }
+            (_, OutputFormat::Sarif) => {
+                anyhow::bail!("sarif output format is not supported for cluster get")
+            }
             (Some(c), OutputFormat::Text) => format!(
                 "File `{}` belongs to cluster `{}` \
                  ({} files, {}, cohesion {:.2})\n",
@@ -53,6 +53,9 @@ impl<'a> CouplingsController<'a> {
             OutputFormat::Vimgrep => {
                 anyhow::bail!("vimgrep output format is not supported for couplings")
             }
+            OutputFormat::Sarif => {
+                anyhow::bail!("sarif output format is not supported for couplings")
+            }
             OutputFormat::Text => render_text(&report),
         })
     }
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+
+use crate::application::{CallCycle, CallGraphQuery};
+use crate::cli::OutputFormatTextJson;
+
+use super::super::Container;
+
+pub struct CyclesController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> CyclesController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Without `--repository`, every indexed repository is searched together
+    /// so cycles that cross repository boundaries show up too.
+    pub async fn cycles(
+        &self,
+        repository: Option<String>,
+        min_size: usize,
+        format: OutputFormatTextJson,
+    ) -> Result<String> {
+        let repository_ids: Vec<String> = match repository {
+            Some(repo_id) => vec![repo_id],
+            None => self
+                .container
+                .list_use_case()
+                .execute()
+                .await
+                .context("Failed to list repositories")?
+                .iter()
+                .map(|r| r.id().to_string())
+                .collect(),
+        };
+        let cycles = self
+            .container
+            .call_graph_use_case()
+            .find_call_cycles(&repository_ids, &CallGraphQuery::new(), min_size)
+            .await?;
+
+        Ok(match format {
+            OutputFormatTextJson::Json => serde_json::to_string_pretty(&cycles)?,
+            OutputFormatTextJson::Text => Self::format_text(&cycles),
+        })
+    }
+
+    /// One cycle per line, closed back on its first symbol. A component with
+    /// several interlocking loops is walked depth-first, so its chain lists
+    /// every member but not necessarily one continuous loop.
+    fn format_text(cycles: &[CallCycle]) -> String {
+        if cycles.is_empty() {
+            return "No circular call chains found.".to_string();
+        }
+        cycles
+            .iter()
+            .map(|c| {
+                let mut chain = c.cycle.clone();
+                chain.extend(c.cycle.first().cloned());
+                chain.join(" → ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
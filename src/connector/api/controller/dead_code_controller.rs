@@ -0,0 +1,142 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::application::{CallGraphQuery, ImpactNode, DEFAULT_ENTRY_POINTS};
+use crate::cli::OutputFormatTextJson;
+use crate::domain::Language;
+
+use super::super::Container;
+
+pub struct DeadCodeController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> DeadCodeController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Without `--repository`, every indexed repository is checked in turn.
+    /// `min_callers` above 1 also reports rarely-called definitions.
+    pub async fn dead_code(
+        &self,
+        repository: Option<String>,
+        language: Option<String>,
+        exclude_tests: bool,
+        min_callers: u64,
+        format: OutputFormatTextJson,
+    ) -> Result<String> {
+        let language = match language.as_deref() {
+            Some(name) => match Language::parse(name) {
+                Language::Unknown => anyhow::bail!("Unknown language '{name}'"),
+                language => Some(language),
+            },
+            None => None,
+        };
+        let repository_ids: Vec<String> = match repository {
+            Some(repo_id) => vec![repo_id],
+            None => self
+                .container
+                .list_use_case()
+                .execute()
+                .await
+                .context("Failed to list repositories")?
+                .iter()
+                .map(|r| r.id().to_string())
+                .collect(),
+        };
+
+        let use_case = self.container.call_graph_use_case();
+        let mut unreachable = Vec::new();
+        for repository_id in repository_ids {
+            let mut query = CallGraphQuery::new().with_repository(repository_id);
+            if let Some(language) = language {
+                query = query.with_language(language.as_str());
+            }
+            unreachable.extend(
+                use_case
+                    .find_rarely_called_symbols(DEFAULT_ENTRY_POINTS, &query, min_callers)
+                    .await?,
+            );
+        }
+        if exclude_tests {
+            unreachable.retain(|node| !is_test_path(&node.file_path));
+        }
+
+        Ok(match format {
+            OutputFormatTextJson::Json => serde_json::to_string_pretty(&unreachable)?,
+            OutputFormatTextJson::Text => Self::format_text(&unreachable, min_callers),
+        })
+    }
+
+    fn format_text(unreachable: &[ImpactNode], min_callers: u64) -> String {
+        if min_callers > 1 {
+            if unreachable.is_empty() {
+                return format!("No functions with fewer than {min_callers} callers found.");
+            }
+            let mut out: Vec<String> = unreachable
+                .iter()
+                .map(|n| {
+                    format!(
+                        "{}:{}  {} ({} callers)",
+                        n.file_path, n.line, n.symbol, n.call_count
+                    )
+                })
+                .collect();
+            out.push(format!(
+                "\n{} function(s) with fewer than {} callers",
+                unreachable.len(),
+                min_callers
+            ));
+            return out.join("\n");
+        }
+        if unreachable.is_empty() {
+            return "No uncalled functions found.".to_string();
+        }
+        let mut out: Vec<String> = unreachable
+            .iter()
+            .map(|n| format!("{}:{}  {}", n.file_path, n.line, n.symbol))
+            .collect();
+        out.push(format!("\n{} uncalled function(s)", unreachable.len()));
+        out.join("\n")
+    }
+}
+
+/// Whether `file_path` looks like test code: a `*_test.*` file or anything
+/// under a `tests/` or `spec/` directory.
+fn is_test_path(file_path: &str) -> bool {
+    let path = Path::new(file_path);
+    let in_test_dir = path
+        .parent()
+        .into_iter()
+        .flat_map(|p| p.components())
+        .any(|c| matches!(c.as_os_str().to_str(), Some("tests" | "spec")));
+    let test_file = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .is_some_and(|stem| stem.ends_with("_test"));
+    in_test_dir || test_file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_are_recognised() {
+        assert!(is_test_path("tests/integration.rs"));
+        assert!(is_test_path("crates/core/tests/parser.rs"));
+        assert!(is_test_path("spec/models/user_spec.rb"));
+        assert!(is_test_path("pkg/server/handler_test.go"));
+        assert!(is_test_path("lib/parser_test.py"));
+    }
+
+    #[test]
+    fn source_paths_are_not_tests() {
+        assert!(!is_test_path("src/lib.rs"));
+        assert!(!is_test_path("src/testsuite.rs"));
+        assert!(!is_test_path("src/latest/mod.rs"));
+        assert!(!is_test_path("src/contest_test_data.rs"));
+    }
+}
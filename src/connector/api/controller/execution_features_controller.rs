@@ -31,6 +31,9 @@ impl<'a> ExecutionFeaturesController<'a> {
         Ok(match format {
             OutputFormat::Json => serde_json::to_string_pretty(&features)?,
             OutputFormat::Vimgrep => Self::format_list_vimgrep(&features),
+            OutputFormat::Sarif => {
+                anyhow::bail!("sarif output format is not supported for features")
+            }
             OutputFormat::Text => Self::format_list_text(&features),
         })
     }
@@ -49,11 +52,17 @@ impl<'a> ExecutionFeaturesController<'a> {
             None => Ok(match format {
                 OutputFormat::Json => "null".to_string(),
                 OutputFormat::Vimgrep => String::new(),
+                OutputFormat::Sarif => {
+                    anyhow::bail!("sarif output format is not supported for features")
+                }
                 OutputFormat::Text => format!("No entry-point feature found for '{symbol}'."),
             }),
             Some(feature) => Ok(match format {
                 OutputFormat::Json => serde_json::to_string_pretty(&feature)?,
                 OutputFormat::Vimgrep => Self::format_feature_vimgrep(&feature),
+                OutputFormat::Sarif => {
+                    anyhow::bail!("sarif output format is not supported for features")
+                }
                 OutputFormat::Text => Self::format_feature_text(&feature),
             }),
         }
@@ -74,6 +83,9 @@ impl<'a> ExecutionFeaturesController<'a> {
         Ok(match format {
             OutputFormat::Json => serde_json::to_string_pretty(&features)?,
             OutputFormat::Vimgrep => Self::format_list_vimgrep(&features),
+            OutputFormat::Sarif => {
+                anyhow::bail!("sarif output format is not supported for features")
+            }
             OutputFormat::Text => {
                 if features.is_empty() {
                     format!(
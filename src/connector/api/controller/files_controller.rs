@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::application::FileEntry;
+use crate::cli::{FileSort, OutputFormatTextJson};
+use crate::domain::Language;
+
+use super::super::Container;
+
+pub struct FilesController<'a> {
+    container: &'a Container,
+}
+
+/// Files of one repository, for `--format json`.
+#[derive(Serialize)]
+struct RepositoryFiles {
+    repository_id: String,
+    files: Vec<FileEntry>,
+}
+
+impl<'a> FilesController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// List the files stored for `repository`, or for every indexed
+    /// repository, with their chunk counts.
+    pub async fn files(
+        &self,
+        repository: Option<String>,
+        language: Option<String>,
+        sort: FileSort,
+        format: OutputFormatTextJson,
+    ) -> Result<String> {
+        let language = match language.as_deref() {
+            Some(name) => match Language::parse(name) {
+                Language::Unknown if !name.eq_ignore_ascii_case("unknown") => {
+                    anyhow::bail!("Unknown language '{name}'")
+                }
+                language => Some(language),
+            },
+            None => None,
+        };
+        let repository_ids: Vec<String> = match repository {
+            Some(repo_id) => vec![repo_id],
+            None => self
+                .container
+                .list_use_case()
+                .execute()
+                .await
+                .context("Failed to list repositories")?
+                .iter()
+                .map(|r| r.id().to_string())
+                .collect(),
+        };
+
+        let mut listing = Vec::with_capacity(repository_ids.len());
+        for repository_id in repository_ids {
+            let mut files = self
+                .container
+                .snippet_lookup_for_repository(Some(&repository_id))
+                .await
+                .files_for_repository(&repository_id, language)
+                .await?;
+            if let FileSort::Chunks = sort {
+                // Stable, so equal counts stay in path order.
+                files.sort_by_key(|f| std::cmp::Reverse(f.chunk_count));
+            }
+            listing.push(RepositoryFiles {
+                repository_id,
+                files,
+            });
+        }
+
+        Ok(match format {
+            OutputFormatTextJson::Json => serde_json::to_string_pretty(&listing)?,
+            OutputFormatTextJson::Text => format_text(&listing),
+        })
+    }
+}
+
+fn format_text(listing: &[RepositoryFiles]) -> String {
+    if listing.iter().all(|r| r.files.is_empty()) {
+        return "No indexed files found.".to_string();
+    }
+    let mut output = String::new();
+    for repo in listing.iter().filter(|r| !r.files.is_empty()) {
+        let chunks: u64 = repo.files.iter().map(|f| f.chunk_count).sum();
+        output.push_str(&format!(
+            "{} ({} files, {} chunks)\n",
+            repo.repository_id,
+            repo.files.len(),
+            chunks
+        ));
+        for file in &repo.files {
+            output.push_str(&format!(
+                "  {:>6}  {:<10}  {}\n",
+                file.chunk_count,
+                file.language.as_str(),
+                file.file_path
+            ));
+        }
+        output.push('\n');
+    }
+    output
+}
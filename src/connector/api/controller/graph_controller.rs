@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+use crate::application::CallGraphQuery;
+use crate::cli::GraphOutputFormat;
+
+use super::super::Container;
+
+pub struct GraphController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> GraphController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    pub async fn graph(
+        &self,
+        symbol: String,
+        depth: usize,
+        repository: Option<String>,
+        format: GraphOutputFormat,
+    ) -> Result<String> {
+        let mut query = CallGraphQuery::new();
+        if let Some(repo_id) = repository {
+            query = query.with_repository(repo_id);
+        }
+
+        let graph = self
+            .container
+            .call_graph_use_case()
+            .export_graph(&symbol, depth, &query)
+            .await?;
+
+        Ok(match format {
+            GraphOutputFormat::Dot => graph.dot().to_string(),
+            GraphOutputFormat::Mermaid => graph.mermaid().to_string(),
+        })
+    }
+}
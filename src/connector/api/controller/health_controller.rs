@@ -0,0 +1,109 @@
+use anyhow::{bail, Result};
+
+use crate::application::{HealthIssue, IndexState};
+
+use super::super::Container;
+
+pub struct HealthController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> HealthController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Check the current namespace's index. Returns `OK` when healthy and an
+    /// error listing every issue (so the process exits 1) otherwise.
+    pub async fn health(&self) -> Result<String> {
+        let namespace = self.container.namespace();
+        let Some(issues) = self
+            .container
+            .index_health_use_case()
+            .execute(namespace)
+            .await?
+        else {
+            bail!("Health checks need persistent storage; in-memory indexes are not checked");
+        };
+        if issues.is_empty() {
+            return Ok("OK".to_string());
+        }
+
+        // Store-wide issues are fixed by reindexing every repository in the
+        // namespace; name them so the suggested commands can be run as-is.
+        let paths: Vec<String> = self
+            .container
+            .list_use_case()
+            .execute()
+            .await?
+            .into_iter()
+            .filter(|r| r.namespace() == Some(namespace))
+            .map(|r| r.path().to_string())
+            .collect();
+
+        bail!("{}", format_issues(namespace, &issues, &paths))
+    }
+}
+
+fn format_issues(namespace: &str, issues: &[HealthIssue], paths: &[String]) -> String {
+    let mut output = format!(
+        "{} issue(s) found in namespace '{}':\n",
+        issues.len(),
+        namespace
+    );
+    for issue in issues {
+        let (problem, fixes) = match issue {
+            HealthIssue::MissingEmbeddings { count } => (
+                format!("{count} chunk(s) have no embedding"),
+                fix_each(paths, "index --force"),
+            ),
+            HealthIssue::OrphanEmbeddings { count } => (
+                format!("{count} embedding(s) reference a missing chunk"),
+                fix_each(paths, "index --force"),
+            ),
+            HealthIssue::TextIndex {
+                state: IndexState::Stale,
+            } => (
+                "full-text index is stale".to_string(),
+                fix_each(paths, "index --force"),
+            ),
+            HealthIssue::TextIndex { .. } => (
+                "full-text index is missing".to_string(),
+                fix_each(paths, "update"),
+            ),
+            HealthIssue::VectorIndexMissing => (
+                "HNSW index on embeddings is missing".to_string(),
+                fix_each(paths, "update"),
+            ),
+            HealthIssue::CountMismatch {
+                repository,
+                path,
+                recorded_chunks,
+                actual_chunks,
+                recorded_files,
+                actual_files,
+            } => (
+                format!(
+                    "{repository}: metadata records {recorded_chunks} chunks / {recorded_files} \
+                     files, store has {actual_chunks} chunks / {actual_files} files"
+                ),
+                vec![format!("codesearch index --force {path}")],
+            ),
+        };
+        output.push_str(&format!("\n  - {problem}\n"));
+        for fix in fixes {
+            output.push_str(&format!("    fix: {fix}\n"));
+        }
+    }
+    output
+}
+
+fn fix_each(paths: &[String], command: &str) -> Vec<String> {
+    if paths.is_empty() {
+        return vec![format!("codesearch {command} <path>")];
+    }
+    paths
+        .iter()
+        .map(|path| format!("codesearch {command} {path}"))
+        .collect()
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+
+use crate::application::{CallGraphQuery, Hotspot, HotspotReport};
+use crate::cli::OutputFormatTextJson;
+
+use super::super::Container;
+
+pub struct HotspotsController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> HotspotsController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Without `--repository`, references from every indexed repository are
+    /// counted together.
+    pub async fn hotspots(
+        &self,
+        repository: Option<String>,
+        top: u32,
+        min_fanin: u64,
+        format: OutputFormatTextJson,
+    ) -> Result<String> {
+        let mut query = CallGraphQuery::new();
+        if let Some(repo_id) = repository {
+            query = query.with_repository(repo_id);
+        }
+        let report = self
+            .container
+            .call_graph_use_case()
+            .find_hotspots(&query, top, min_fanin)
+            .await?;
+
+        Ok(match format {
+            OutputFormatTextJson::Json => serde_json::to_string_pretty(&report)?,
+            OutputFormatTextJson::Text => Self::format_text(&report),
+        })
+    }
+
+    fn format_text(report: &HotspotReport) -> String {
+        if report.by_fan_in.is_empty() && report.by_fan_out.is_empty() {
+            return "No references in the call graph.".to_string();
+        }
+        let mut out = String::new();
+        out.push_str("Most referenced (fan-in)\n");
+        out.push_str(&Self::format_table(&report.by_fan_in));
+        out.push_str("\nMost referencing (fan-out)\n");
+        out.push_str(&Self::format_table(&report.by_fan_out));
+        out
+    }
+
+    fn format_table(hotspots: &[Hotspot]) -> String {
+        if hotspots.is_empty() {
+            return "  (none)\n".to_string();
+        }
+        let width = hotspots
+            .iter()
+            .map(|h| h.symbol.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Symbol".len());
+        let mut out = format!(
+            "  {:<width$}  {:>6}  {:>7}  Defined at\n",
+            "Symbol", "Fan-in", "Fan-out"
+        );
+        for h in hotspots {
+            let location = match (&h.file_path, h.line) {
+                (Some(file), Some(line)) => format!("{file}:{line}"),
+                _ => "-".to_string(),
+            };
+            out.push_str(&format!(
+                "  {:<width$}  {:>6}  {:>7}  {}\n",
+                h.symbol, h.fan_in, h.fan_out, location
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_output_lists_both_rankings() {
+        let hotspot = |symbol: &str, fan_in, fan_out, file: Option<&str>| Hotspot {
+            symbol: symbol.to_string(),
+            fan_in,
+            fan_out,
+            file_path: file.map(str::to_string),
+            line: file.map(|_| 12),
+        };
+        let report = HotspotReport {
+            by_fan_in: vec![hotspot("log", 40, 1, Some("src/log.rs"))],
+            by_fan_out: vec![hotspot("main", 0, 25, None)],
+        };
+
+        let text = HotspotsController::format_text(&report);
+
+        assert!(text.contains("  log         40        1  src/log.rs:12\n"));
+        assert!(text.contains("  main         0       25  -\n"));
+        assert!(text.find("fan-in").unwrap() < text.find("fan-out").unwrap());
+    }
+}
@@ -1,38 +1,248 @@
 use std::collections::HashMap;
+use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
-use crate::cli::OutputFormat;
+use crate::application::{DiffImpactReport, ANONYMOUS_SYMBOL, DISPATCH_REFERENCE_KIND};
+use crate::cli::ImpactOutputFormat;
+use crate::domain::ReferenceKind;
 use crate::{ImpactAnalysis, ImpactNode};
 
+/// Fill colours for DOT nodes, indexed by hop depth (0 = queried symbol).
+/// Depths beyond the palette reuse the last colour.
+const DOT_DEPTH_COLORS: &[&str] = &[
+    "#e74c3c", "#f39c12", "#f7dc6f", "#abebc6", "#aed6f1", "#d7dbdd",
+];
+
 use super::super::Container;
+use super::sarif::{sarif_log, SarifResult};
 
 pub struct ImpactController<'a> {
     container: &'a Container,
 }
 
+/// Returned by `impact --exit-code` when the blast radius is larger than
+/// the threshold. The report is carried along so it can still be printed
+/// before the process exits non-zero.
+#[derive(Debug)]
+pub struct ImpactThresholdExceeded {
+    pub output: String,
+    pub affected: usize,
+    pub threshold: usize,
+}
+
+impl std::fmt::Display for ImpactThresholdExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} symbols affected, more than the threshold of {}",
+            self.affected, self.threshold
+        )
+    }
+}
+
+impl std::error::Error for ImpactThresholdExceeded {}
+
 impl<'a> ImpactController<'a> {
     pub fn new(container: &'a Container) -> Self {
         Self { container }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn impact(
         &self,
         symbol: String,
         repository: Option<String>,
-        format: OutputFormat,
+        format: ImpactOutputFormat,
         is_regex: bool,
+        follow_impls: bool,
+        kinds: Vec<String>,
+        exclude_tests: bool,
+        gate: Option<usize>,
     ) -> Result<String> {
-        let use_case = self.container.impact_use_case();
+        let use_case = self
+            .container
+            .impact_use_case()
+            .with_follow_impls(follow_impls)
+            .with_kinds(parse_kinds(&kinds)?)
+            .with_exclude_tests(exclude_tests);
         let analysis = use_case
             .analyze(&symbol, repository.as_deref(), is_regex)
             .await?;
 
-        Ok(match format {
-            OutputFormat::Json => serde_json::to_string_pretty(&analysis)?,
-            OutputFormat::Vimgrep => Self::format_impact_vimgrep(&analysis),
-            OutputFormat::Text => self.format_impact(&analysis),
-        })
+        let output = match format {
+            ImpactOutputFormat::Json => serde_json::to_string_pretty(&analysis)?,
+            ImpactOutputFormat::Vimgrep => Self::format_impact_vimgrep(&analysis),
+            ImpactOutputFormat::Dot => Self::format_impact_dot(&analysis),
+            ImpactOutputFormat::Sarif => Self::format_impact_sarif(&analysis),
+            ImpactOutputFormat::Text => Self::format_impact(&analysis, self.container.verbose()),
+        };
+        check_gate(output, analysis.total_affected, gate)
+    }
+
+    /// Impact of every symbol changed since `base` in the repository
+    /// (auto-detected from the working directory when `None`), grouped by
+    /// changed symbol.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn impact_diff(
+        &self,
+        base: String,
+        repository: Option<String>,
+        format: ImpactOutputFormat,
+        follow_impls: bool,
+        kinds: Vec<String>,
+        exclude_tests: bool,
+        gate: Option<usize>,
+    ) -> Result<String> {
+        if matches!(format, ImpactOutputFormat::Dot) {
+            bail!("dot output format is not supported with --diff");
+        }
+        let kinds = parse_kinds(&kinds)?;
+        let repository_id = self
+            .container
+            .resolve_repository_id(repository.as_deref())
+            .await;
+        let repo = self
+            .container
+            .list_use_case()
+            .execute()
+            .await?
+            .into_iter()
+            .find(|r| r.id() == repository_id)
+            .context(
+                "No indexed repository to diff: run from inside an indexed repository or pass \
+                 --repository",
+            )?;
+
+        let report = self
+            .container
+            .diff_impact_use_case(repo.namespace())
+            .await
+            .with_follow_impls(follow_impls)
+            .with_kinds(kinds)
+            .with_exclude_tests(exclude_tests)
+            .analyze(Path::new(repo.path()), repo.id(), &base)
+            .await?;
+
+        let output = match format {
+            ImpactOutputFormat::Json => serde_json::to_string_pretty(&report)?,
+            ImpactOutputFormat::Vimgrep => report
+                .changed_symbols
+                .iter()
+                .map(|changed| Self::format_impact_vimgrep(&changed.impact))
+                .filter(|lines| !lines.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ImpactOutputFormat::Sarif => {
+                let results: Vec<SarifResult> = report
+                    .changed_symbols
+                    .iter()
+                    .flat_map(|changed| sarif_results(&changed.impact))
+                    .collect();
+                sarif_log(&results)
+            }
+            ImpactOutputFormat::Text => Self::format_diff_impact(&report, self.container.verbose()),
+            ImpactOutputFormat::Dot => unreachable!("rejected above"),
+        };
+        check_gate(output, report.total_affected, gate)
+    }
+
+    /// One section per changed symbol (its location, then the usual impact
+    /// tree), followed by the merged totals.
+    fn format_diff_impact(report: &DiffImpactReport, verbose: bool) -> String {
+        if report.changed_symbols.is_empty() {
+            return format!(
+                "No indexed symbols changed since '{}' ({} files with changes).",
+                report.base, report.changed_files
+            );
+        }
+
+        let mut out = String::new();
+        for changed in &report.changed_symbols {
+            out.push_str(&format!(
+                "{} ({}:{}-{}) — {} affected\n",
+                changed.symbol,
+                changed.file_path,
+                changed.start_line,
+                changed.end_line,
+                changed.impact.total_affected
+            ));
+            if changed.impact.total_affected > 0 {
+                out.push_str(&Self::format_impact(&changed.impact, verbose));
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "{} changed symbols since '{}', {} affected symbols in total",
+            report.changed_symbols.len(),
+            report.base,
+            report.total_affected
+        ));
+        out
+    }
+
+    /// Render the blast radius as a Graphviz digraph.
+    ///
+    /// Edges point from caller to callee, so the queried symbol sits at the
+    /// bottom of a `rankdir=BT` layout and each hop depth forms its own rank.
+    pub fn format_impact_dot(analysis: &ImpactAnalysis) -> String {
+        let mut out = String::from("digraph impact {\n");
+        out.push_str("    rankdir=BT;\n");
+        out.push_str("    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+        out.push_str("    edge [fontname=\"Helvetica\", fontsize=10];\n");
+
+        let mut ranks: Vec<Vec<String>> = vec![Vec::new()];
+        for root in &analysis.root_symbols {
+            let id = dot_escape(root);
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", fillcolor=\"{}\", penwidth=2];\n",
+                id,
+                id,
+                depth_color(0)
+            ));
+            ranks[0].push(id);
+        }
+
+        for node in analysis.by_depth.iter().flatten() {
+            let id = dot_escape(&node_id(&node.symbol, &node.file_path));
+            let label = dot_escape(&format!(
+                "{} ({}:{})",
+                node.symbol, node.file_path, node.line
+            ));
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", fillcolor=\"{}\"];\n",
+                id,
+                label,
+                depth_color(node.depth)
+            ));
+            while ranks.len() <= node.depth {
+                ranks.push(Vec::new());
+            }
+            ranks[node.depth].push(id);
+        }
+
+        for rank in ranks.iter().filter(|r| r.len() > 1) {
+            let ids: Vec<String> = rank.iter().map(|id| format!("\"{}\"", id)).collect();
+            out.push_str(&format!("    {{ rank=same; {}; }}\n", ids.join("; ")));
+        }
+
+        for edge in &analysis.edges {
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                dot_escape(&node_id(&edge.caller, &edge.file_path)),
+                dot_escape(&edge.callee),
+                dot_escape(&edge.reference_kind)
+            ));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render every affected symbol as a SARIF 2.1.0 result located at the
+    /// reference that pulls it into the blast radius.
+    pub fn format_impact_sarif(analysis: &ImpactAnalysis) -> String {
+        sarif_log(&sarif_results(analysis))
     }
 
     fn format_impact_vimgrep(analysis: &ImpactAnalysis) -> String {
@@ -50,7 +260,12 @@ impl<'a> ImpactController<'a> {
             .join("\n")
     }
 
-    fn format_impact(&self, analysis: &ImpactAnalysis) -> String {
+    /// Render the blast radius as a tree rooted at the queried symbol: each
+    /// affected symbol is indented by its hop depth under the symbol that
+    /// reached it, with the kind and location of that reference. Siblings
+    /// leading to the most impactful symbols come first. `verbose` adds the
+    /// repository id of every reference.
+    fn format_impact(analysis: &ImpactAnalysis, verbose: bool) -> String {
         if analysis.total_affected == 0 {
             return format!(
                 "No callers found for '{}'. Either the symbol is a root entry point or \
@@ -65,98 +280,319 @@ impl<'a> ImpactController<'a> {
             analysis.root_symbol
         );
 
-        let all_nodes: Vec<&ImpactNode> = analysis.by_depth.iter().flatten().collect();
-
-        // Build children_map: symbol → nodes that list it as via_symbol.
-        let mut children_map: HashMap<&str, Vec<&ImpactNode>> = HashMap::new();
-        for node in &all_nodes {
+        // Children of every node, keyed by the parent's (depth, symbol); the
+        // roots sit at depth 0.
+        let mut children: HashMap<(usize, &str), Vec<&ImpactNode>> = HashMap::new();
+        for node in analysis.by_depth.iter().flatten() {
             if let Some(via) = node.via_symbol.as_deref() {
-                children_map.entry(via).or_default().push(node);
+                children
+                    .entry((node.depth - 1, via))
+                    .or_default()
+                    .push(node);
             }
         }
 
-        // Leaf nodes (no one calls them) become roots in the inverted tree.
-        let leaf_nodes: Vec<&ImpactNode> = all_nodes
-            .iter()
-            .copied()
-            .filter(|n| !children_map.contains_key(n.symbol.as_str()))
-            .collect();
-
-        // Lookup by (depth, symbol) for unambiguous path tracing.
-        // or_insert keeps only the *first* ImpactNode seen for any (depth, symbol) pair.
-        // When the same symbol appears at the same depth via two different call paths, the
-        // duplicate is intentionally dropped so that each (depth, symbol) key maps to exactly
-        // one parent — giving the path-tracing loop a single, deterministic choice at every
-        // step.  The trade-off is that alternate routes to the same node are not rendered;
-        // this is acceptable here because the goal is to show one representative call chain
-        // from each leaf up to the queried symbol, not to enumerate every possible path.
-        let mut node_by_depth_symbol: HashMap<(usize, &str), &ImpactNode> = HashMap::new();
-        for node in &all_nodes {
-            node_by_depth_symbol
-                .entry((node.depth, node.symbol.as_str()))
-                .or_insert(node);
-        }
-
-        for (idx, &leaf) in leaf_nodes.iter().enumerate() {
-            // Trace from leaf back toward the root symbol.
-            let mut path: Vec<&ImpactNode> = vec![leaf];
-            let mut current = leaf;
-            while let Some(via) = current.via_symbol.as_deref() {
-                let parent_depth = current.depth.saturating_sub(1);
-                if let Some(&parent) = node_by_depth_symbol.get(&(parent_depth, via)) {
-                    path.push(parent);
-                    current = parent;
-                } else {
-                    break;
-                }
+        let mut first = true;
+        for root in &analysis.root_symbols {
+            if !children.contains_key(&(0, root.as_str())) {
+                continue;
             }
-
-            Self::render_reversed_path(&path, &analysis.root_symbol, &mut out);
-
-            if idx < leaf_nodes.len() - 1 {
+            if !first {
                 out.push('\n');
             }
+            first = false;
+            out.push_str(root);
+            out.push('\n');
+            Self::render_children(&children, (0, root), verbose, &mut out);
         }
 
         out
     }
 
-    fn alias_suffix(alias: &Option<String>) -> String {
-        alias
-            .as_ref()
-            .map(|a| format!(", as {}", a))
-            .unwrap_or_default()
+    fn render_children(
+        children: &HashMap<(usize, &str), Vec<&ImpactNode>>,
+        parent: (usize, &str),
+        verbose: bool,
+        out: &mut String,
+    ) {
+        let Some(nodes) = children.get(&parent) else {
+            return;
+        };
+        // The sort is stable, so equally impactful siblings keep BFS order.
+        let mut nodes = nodes.clone();
+        nodes.sort_by(|a, b| subtree_score(children, b).total_cmp(&subtree_score(children, a)));
+        for node in nodes {
+            out.push_str(&format!(
+                "{}└── {}{} ← {} {} at {}:{}",
+                "    ".repeat(node.depth - 1),
+                node.symbol,
+                Self::node_suffix(node),
+                hop_verb(&node.reference_kind),
+                parent.1,
+                node.file_path,
+                node.line,
+            ));
+            if verbose {
+                out.push_str(&format!(" [repo {}]", node.repository_id));
+            }
+            out.push('\n');
+            Self::render_children(children, (node.depth, &node.symbol), verbose, out);
+        }
     }
 
-    /// Render a single path (leaf → … → root) as an indented tree.
-    /// `path[0]` is the most-upstream caller (tree root); the queried symbol
-    /// is appended as the terminal leaf.
-    fn render_reversed_path(path: &[&ImpactNode], root_symbol: &str, out: &mut String) {
-        if path.is_empty() {
-            return;
+    /// ` (as alias, N calls)`, or nothing when neither is known.
+    fn node_suffix(node: &ImpactNode) -> String {
+        let mut parts = Vec::new();
+        if let Some(alias) = &node.import_alias {
+            parts.push(format!("as {}", alias));
+        }
+        match node.call_count {
+            0 => {}
+            1 => parts.push("1 call".to_string()),
+            n => parts.push(format!("{} calls", n)),
+        }
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", parts.join(", "))
         }
-        for (depth, node) in path.iter().enumerate() {
-            let alias_suffix = Self::alias_suffix(&node.import_alias);
-            if depth == 0 {
-                out.push_str(&format!(
-                    "{} [{}{}] {}:{}\n",
-                    node.symbol, node.reference_kind, alias_suffix, node.file_path, node.line,
-                ));
-            } else {
-                let indent = "    ".repeat(depth - 1);
-                out.push_str(&format!(
-                    "{}└── {} [{}{}] {}:{}\n",
-                    indent,
-                    node.symbol,
-                    node.reference_kind,
-                    alias_suffix,
-                    node.file_path,
-                    node.line,
-                ));
+    }
+}
+
+/// One SARIF result per affected symbol of `analysis`.
+fn sarif_results(analysis: &ImpactAnalysis) -> Vec<SarifResult<'_>> {
+    analysis
+        .by_depth
+        .iter()
+        .flatten()
+        .map(|node| SarifResult {
+            rule_id: "impact",
+            message: format!(
+                "{} is affected by changes to {} (depth {}, {})",
+                node.symbol, analysis.root_symbol, node.depth, node.reference_kind
+            ),
+            file_path: &node.file_path,
+            start_line: node.line,
+        })
+        .collect()
+}
+
+/// Parse `--kinds` values into reference kinds. `all` (alone or among
+/// others) yields an empty list, which traverses every kind.
+fn parse_kinds(kinds: &[String]) -> Result<Vec<ReferenceKind>> {
+    if kinds.iter().any(|kind| kind.trim() == "all") {
+        return Ok(Vec::new());
+    }
+    kinds
+        .iter()
+        .map(|kind| match ReferenceKind::parse(kind.trim()) {
+            ReferenceKind::Unknown if kind.trim() != "unknown" => {
+                bail!("unknown reference kind '{}' in --kinds", kind)
             }
+            parsed => Ok(parsed),
+        })
+        .collect()
+}
+
+/// Pass `output` through, or fail with [`ImpactThresholdExceeded`] when
+/// `--exit-code` is set and more than `threshold` symbols are affected.
+fn check_gate(output: String, affected: usize, gate: Option<usize>) -> Result<String> {
+    match gate {
+        Some(threshold) if affected > threshold => Err(ImpactThresholdExceeded {
+            output,
+            affected,
+            threshold,
         }
-        // Queried symbol is always the terminal leaf.
-        let indent = "    ".repeat(path.len() - 1);
-        out.push_str(&format!("{}└── {}\n", indent, root_symbol));
+        .into()),
+        _ => Ok(output),
+    }
+}
+
+/// Highest [`ImpactNode::impact_score`] of `node` and everything reached
+/// through it.
+fn subtree_score(children: &HashMap<(usize, &str), Vec<&ImpactNode>>, node: &ImpactNode) -> f64 {
+    children
+        .get(&(node.depth, node.symbol.as_str()))
+        .into_iter()
+        .flatten()
+        .map(|child| subtree_score(children, child))
+        .fold(node.impact_score(), f64::max)
+}
+
+/// How an affected symbol relates to the symbol that reached it, for the
+/// text tree (`handler ← calls authenticate at …`).
+fn hop_verb(reference_kind: &str) -> &'static str {
+    match reference_kind {
+        "call" | "method_call" => "calls",
+        "macro_invocation" => "invokes",
+        "type_reference" | "generic_argument" | "variable_reference" => "references",
+        "field_access" => "accesses",
+        "import" => "imports",
+        "instantiation" => "instantiates",
+        "implementation" => "implements",
+        "inheritance" => "extends",
+        "override" => "overrides",
+        DISPATCH_REFERENCE_KIND => "dispatches to",
+        _ => "uses",
+    }
+}
+
+/// Graph node identifier for a symbol. Anonymous callers are qualified by
+/// file so that module-level code in different files stays distinct.
+fn node_id(symbol: &str, file_path: &str) -> String {
+    if symbol == ANONYMOUS_SYMBOL {
+        format!("{}@{}", symbol, file_path)
+    } else {
+        symbol.to_string()
+    }
+}
+
+fn depth_color(depth: usize) -> &'static str {
+    DOT_DEPTH_COLORS[depth.min(DOT_DEPTH_COLORS.len() - 1)]
+}
+
+/// Escape a string for use inside a double-quoted DOT identifier.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImpactEdge;
+
+    fn node(symbol: &str, depth: usize, via: &str) -> ImpactNode {
+        ImpactNode {
+            symbol: symbol.to_string(),
+            depth,
+            file_path: "src/lib.rs".to_string(),
+            line: depth as u32 * 10,
+            reference_kind: "call".to_string(),
+            repository_id: "repo".to_string(),
+            import_alias: None,
+            via_symbol: Some(via.to_string()),
+            call_count: 0,
+            path: Vec::new(),
+        }
+    }
+
+    fn edge(caller: &str, callee: &str) -> ImpactEdge {
+        ImpactEdge {
+            caller: caller.to_string(),
+            callee: callee.to_string(),
+            reference_kind: "call".to_string(),
+            file_path: "src/lib.rs".to_string(),
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn test_dot_output_labels_nodes_and_edges() {
+        let analysis = ImpactAnalysis {
+            root_symbol: "target".to_string(),
+            root_symbols: vec!["target".to_string()],
+            total_affected: 2,
+            max_depth_reached: 2,
+            by_depth: vec![vec![node("a", 1, "target")], vec![node("b", 2, "a")]],
+            edges: vec![edge("a", "target"), edge("b", "a"), edge("target", "b")],
+        };
+
+        let dot = ImpactController::format_impact_dot(&analysis);
+
+        assert!(dot.starts_with("digraph impact {"));
+        assert!(dot.contains("\"a\" [label=\"a (src/lib.rs:10)\""));
+        assert!(dot.contains("\"b\" -> \"a\" [label=\"call\"]"));
+        // The cycle back into the root is emitted exactly once.
+        assert_eq!(dot.matches("\"target\" -> \"b\"").count(), 1);
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_sarif_output_has_one_result_per_affected_node() {
+        let analysis = ImpactAnalysis {
+            root_symbol: "target".to_string(),
+            root_symbols: vec!["target".to_string()],
+            total_affected: 2,
+            max_depth_reached: 2,
+            by_depth: vec![vec![node("a", 1, "target")], vec![node("b", 2, "a")]],
+            edges: vec![edge("a", "target"), edge("b", "a")],
+        };
+
+        let sarif = ImpactController::format_impact_sarif(&analysis);
+        let value: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+        let results = value["runs"][0]["results"].as_array().unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            20
+        );
+        assert!(results[1]["message"]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("b is affected by changes to target"));
+    }
+
+    #[test]
+    fn test_text_output_puts_most_called_chain_first() {
+        let mut hot = node("hot", 2, "cold");
+        hot.call_count = 50;
+        let analysis = ImpactAnalysis {
+            root_symbol: "target".to_string(),
+            root_symbols: vec!["target".to_string()],
+            total_affected: 3,
+            max_depth_reached: 2,
+            by_depth: vec![
+                vec![node("warm", 1, "target"), node("cold", 1, "target")],
+                vec![hot],
+            ],
+            edges: vec![
+                edge("warm", "target"),
+                edge("cold", "target"),
+                edge("hot", "cold"),
+            ],
+        };
+
+        let text = ImpactController::format_impact(&analysis, false);
+
+        // The chain through `hot` ranks `cold` above `warm`.
+        let cold_at = text
+            .find("└── cold ← calls target at src/lib.rs:10")
+            .unwrap();
+        let warm_at = text.find("└── warm ← calls target").unwrap();
+        assert!(cold_at < warm_at);
+        assert!(text.contains("\n    └── hot (50 calls) ← calls cold at src/lib.rs:20\n"));
+    }
+
+    #[test]
+    fn test_text_output_shows_repository_only_when_verbose() {
+        let analysis = ImpactAnalysis {
+            root_symbol: "target".to_string(),
+            root_symbols: vec!["target".to_string()],
+            total_affected: 1,
+            max_depth_reached: 1,
+            by_depth: vec![vec![node("a", 1, "target")]],
+            edges: vec![edge("a", "target")],
+        };
+
+        assert!(!ImpactController::format_impact(&analysis, false).contains("repo"));
+        assert!(ImpactController::format_impact(&analysis, true).contains("[repo repo]"));
+    }
+
+    #[test]
+    fn test_parse_kinds_accepts_known_kinds_and_all() {
+        let kinds =
+            |list: &[&str]| parse_kinds(&list.iter().map(|k| k.to_string()).collect::<Vec<_>>());
+        assert_eq!(
+            kinds(&["call", " method_call"]).unwrap(),
+            [ReferenceKind::Call, ReferenceKind::MethodCall]
+        );
+        assert!(kinds(&["call", "all"]).unwrap().is_empty());
+        assert!(kinds(&["calls"]).is_err());
+    }
+
+    #[test]
+    fn test_dot_escape_quotes_and_backslashes() {
+        assert_eq!(dot_escape(r#"a"b\c"#), r#"a\"b\\c"#);
     }
 }
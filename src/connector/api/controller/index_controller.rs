@@ -1,6 +1,19 @@
-use anyhow::Result;
+use std::collections::HashSet;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::{Repository, VectorStore};
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::application::{IndexProgress, IndexProgressCallback};
+use crate::cli::HeaderLanguage;
+use crate::connector::adapter::TreeSitterParser;
+use crate::{DomainError, IndexFilters, IndexRepositoryUseCase, Language, Repository, VectorStore};
 
 use super::super::Container;
 
@@ -13,25 +26,190 @@ impl<'a> IndexController<'a> {
         Self { container }
     }
 
-    pub async fn index(&self, path: String, name: Option<String>, force: bool) -> Result<String> {
-        let (vector_store, ns): (VectorStore, Option<String>) = if self.container.memory_storage() {
-            (VectorStore::InMemory, None)
-        } else {
-            (
-                VectorStore::DuckDb,
-                Some(self.container.namespace().to_string()),
-            )
+    #[allow(clippy::too_many_arguments)]
+    pub async fn index(
+        &self,
+        paths: Vec<String>,
+        name: Option<String>,
+        force: bool,
+        include: Vec<String>,
+        exclude: Vec<String>,
+        watch: bool,
+        header_language: HeaderLanguage,
+        max_chunk_lines: usize,
+        debounce_ms: u64,
+        no_docs: bool,
+        since: Option<Option<String>>,
+        jobs: Option<usize>,
+        max_file_size: u64,
+    ) -> Result<String> {
+        let vector_store = self.container.vector_store();
+        let ns = match vector_store {
+            VectorStore::InMemory => None,
+            _ => Some(self.container.namespace().to_string()),
         };
 
-        let use_case = self.container.index_use_case();
+        // No globs on the command line means "reuse whatever was stored".
+        let filters = IndexFilters::new(include, exclude);
+        let filters = (!filters.is_empty()).then_some(filters);
+
+        let parser = TreeSitterParser::new().with_max_chunk_lines(max_chunk_lines);
+        let mut use_case = self
+            .container
+            .index_use_case()
+            .with_parser_service(Arc::new(parser))
+            .with_progress_callback(progress_reporter(self.container.plain_progress()))
+            .with_docs(!no_docs)
+            .with_max_file_size(max_file_size)
+            .with_header_language(match header_language {
+                HeaderLanguage::C => Language::C,
+                HeaderLanguage::Cpp => Language::Cpp,
+            });
+        if let Some(rev) = since {
+            use_case = use_case.with_since(rev);
+        }
+        if let Some(jobs) = jobs {
+            use_case = use_case.with_parse_concurrency(jobs);
+        }
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
         let repo = use_case
-            .execute(&path, name.as_deref(), vector_store, ns, force)
+            .execute_with_roots(
+                &paths,
+                name.as_deref(),
+                vector_store,
+                ns.clone(),
+                force,
+                filters,
+            )
+            .await?;
+
+        if !watch {
+            return Ok(self.format_index_success(&repo, &use_case));
+        }
+
+        // Watch mode runs until Ctrl-C, so report the initial index now and
+        // return nothing for main.rs to print afterwards.
+        println!("{}", self.format_index_success(&repo, &use_case));
+        let debounce = Duration::from_millis(debounce_ms);
+        Self::watch(&use_case, &repo, vector_store, ns, debounce).await?;
+        Ok(String::new())
+    }
+
+    /// Re-index only what changed under `path` since the last run.
+    pub async fn update(&self, path: String, name: Option<String>, force: bool) -> Result<String> {
+        let vector_store = self.container.vector_store();
+        let ns = match vector_store {
+            VectorStore::InMemory => None,
+            _ => Some(self.container.namespace().to_string()),
+        };
+
+        let update = self
+            .container
+            .index_use_case()
+            .with_progress_callback(progress_reporter(self.container.plain_progress()))
+            .execute_incremental(&path, name.as_deref(), vector_store, ns, force)
             .await?;
 
-        Ok(self.format_index_success(&repo))
+        Ok(format!(
+            "Updated repository: {} ({} files, {} chunks)\n\
+             Files added: {}, Files changed: {}, Files removed: {}, Chunks delta: {:+}",
+            update.repository.name(),
+            update.repository.file_count(),
+            update.repository.chunk_count(),
+            update.files_added,
+            update.files_changed,
+            update.files_removed,
+            update.chunk_delta
+        ))
+    }
+
+    /// Re-index `repo` whenever files under it change.
+    ///
+    /// Events are coalesced until `debounce` passes without a new one, since
+    /// editors often write a file twice in quick succession (truncate + write,
+    /// or write + rename). A batch of edits re-indexes just the touched files
+    /// via [`IndexRepositoryUseCase::update_file`]. A batch with removals or
+    /// renames runs the regular incremental index instead, because a removed
+    /// directory does not name the indexed files it held.
+    async fn watch(
+        use_case: &IndexRepositoryUseCase,
+        repo: &Repository,
+        store: VectorStore,
+        namespace: Option<String>,
+        debounce: Duration,
+    ) -> Result<()> {
+        let root = repo.path();
+        let root_path = Path::new(root);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let _ = tx.send(event);
+        })
+        .context("failed to create file watcher")?;
+        watcher
+            .watch(root_path, RecursiveMode::Recursive)
+            .with_context(|| format!("failed to watch {}", root))?;
+        info!("Watching {} for changes (Ctrl-C to stop)", root);
+
+        loop {
+            let first = tokio::select! {
+                event = rx.recv() => match event {
+                    Some(event) => event,
+                    None => break,
+                },
+                _ = tokio::signal::ctrl_c() => break,
+            };
+
+            let mut changed: HashSet<PathBuf> = HashSet::new();
+            let mut structural = collect_changed_paths(first, root_path, &mut changed);
+            while let Ok(Some(event)) = tokio::time::timeout(debounce, rx.recv()).await {
+                structural |= collect_changed_paths(event, root_path, &mut changed);
+            }
+            if changed.is_empty() {
+                continue;
+            }
+
+            let start = Instant::now();
+            let result = if structural {
+                use_case
+                    .execute(root, None, store, namespace.clone(), false)
+                    .await
+            } else {
+                Self::update_files(use_case, repo.id(), &changed).await
+            };
+            match result {
+                Ok(repo) => info!(
+                    "Re-indexed {} after {} changed path(s) in {:.2}s ({} files, {} chunks)",
+                    repo.name(),
+                    changed.len(),
+                    start.elapsed().as_secs_f64(),
+                    repo.file_count(),
+                    repo.chunk_count()
+                ),
+                Err(e) => warn!("Re-indexing {} failed: {}", root, e),
+            }
+        }
+
+        info!("Stopped watching {}", root);
+        Ok(())
+    }
+
+    /// Re-index each changed file in turn, returning the repository as left
+    /// by the last update.
+    async fn update_files(
+        use_case: &IndexRepositoryUseCase,
+        repository_id: &str,
+        changed: &HashSet<PathBuf>,
+    ) -> Result<Repository, DomainError> {
+        let mut repo = None;
+        for path in changed {
+            repo = Some(use_case.update_file(repository_id, path).await?);
+        }
+        repo.ok_or_else(|| DomainError::internal("No files to update"))
     }
 
-    fn format_index_success(&self, repo: &Repository) -> String {
+    fn format_index_success(&self, repo: &Repository, use_case: &IndexRepositoryUseCase) -> String {
+        let cache = use_case.embedding_cache_stats();
+        let skipped = use_case.skipped_file_count();
         let mut output = format!(
             "Successfully indexed repository: {} ({} files, {} chunks)",
             repo.name(),
@@ -48,6 +226,153 @@ impl<'a> IndexController<'a> {
             output.push_str(&format!("\nLanguages: {}", langs.join(", ")));
         }
 
+        if skipped > 0 {
+            output.push_str(&format!(
+                "\nSkipped {} files (too large or binary; see --max-file-size)",
+                skipped
+            ));
+        }
+
+        if let Some(rate) = cache.hit_rate() {
+            output.push_str(&format!(
+                "\nEmbedding cache: {}/{} chunks reused ({:.0}%)",
+                cache.hits,
+                cache.hits + cache.misses,
+                rate * 100.0
+            ));
+        }
+
+        for (indexer, stderr) in use_case.scip_stderr() {
+            let lines: Vec<&str> = stderr.trim_end().lines().collect();
+            output.push_str(&format!("\n{} stderr:", indexer));
+            if lines.len() > SCIP_STDERR_LINES {
+                output.push_str(&format!(
+                    "\n  ({} earlier lines omitted)",
+                    lines.len() - SCIP_STDERR_LINES
+                ));
+            }
+            for line in &lines[lines.len().saturating_sub(SCIP_STDERR_LINES)..] {
+                output.push_str(&format!("\n  {}", line));
+            }
+        }
+
         output
     }
 }
+
+/// Trailing lines of each SCIP indexer's stderr shown in the index report.
+const SCIP_STDERR_LINES: usize = 20;
+
+/// Files between two progress lines when the bar cannot be drawn.
+const PROGRESS_LINE_EVERY: u64 = 100;
+
+/// Progress output for the CLI: a redrawn bar with files, chunks, embedding
+/// rate and ETA when stdout is a terminal, otherwise a one-line summary on
+/// stderr every [`PROGRESS_LINE_EVERY`] files so redirected output stays
+/// clean. `plain` forces the line mode, so log lines on the console are not
+/// overwritten by the bar.
+fn progress_reporter(plain: bool) -> IndexProgressCallback {
+    if std::io::stdout().is_terminal() && !plain {
+        let bar = ProgressBar::new(0);
+        bar.set_style(
+            ProgressStyle::with_template("  [{pos}/{len}] ETA {eta} {wide_msg:.dim}")
+                .expect("Invalid progress bar template"),
+        );
+        Arc::new(move |progress: IndexProgress| {
+            // Watch mode reuses the reporter for every re-index.
+            if progress.files_done == 1 {
+                bar.reset();
+            }
+            bar.set_length(progress.files_total);
+            bar.set_position(progress.files_done);
+            bar.set_message(format!(
+                "{} chunks, {:.0} emb/s  {}",
+                progress.chunks_so_far,
+                per_second(progress.embeddings_so_far, bar.elapsed()),
+                progress.current_file
+            ));
+            if progress.files_done >= progress.files_total {
+                bar.finish_and_clear();
+            }
+        })
+    } else {
+        let started = std::sync::Mutex::new(Instant::now());
+        Arc::new(move |progress: IndexProgress| {
+            let Ok(mut started) = started.lock() else {
+                return;
+            };
+            if progress.files_done == 1 {
+                *started = Instant::now();
+            }
+            let done = progress.files_done >= progress.files_total;
+            if !progress.files_done.is_multiple_of(PROGRESS_LINE_EVERY) && !done {
+                return;
+            }
+            let elapsed = started.elapsed();
+            let remaining = progress.files_total.saturating_sub(progress.files_done);
+            let eta = elapsed.mul_f64(remaining as f64 / progress.files_done.max(1) as f64);
+            eprintln!(
+                "Indexing: {}/{} files, {} chunks, {:.0} embeddings/s, ETA {}s",
+                progress.files_done,
+                progress.files_total,
+                progress.chunks_so_far,
+                per_second(progress.embeddings_so_far, elapsed),
+                eta.as_secs()
+            );
+        })
+    }
+}
+
+fn per_second(count: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        count as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// Add the paths from a watcher event that may affect the index to `changed`,
+/// returning `true` when the event removed or renamed something.
+///
+/// Paths under hidden directories (`.git`, editor swap dirs) are ignored.
+/// Creations and modifications only count for supported source files;
+/// removals and renames always count, since a removed directory carries no
+/// extension but may have held indexed files.
+fn collect_changed_paths(
+    event: notify::Result<Event>,
+    root: &Path,
+    changed: &mut HashSet<PathBuf>,
+) -> bool {
+    let event = match event {
+        Ok(event) => event,
+        Err(e) => {
+            warn!("File watcher error: {}", e);
+            return false;
+        }
+    };
+    let structural = matches!(
+        event.kind,
+        EventKind::Remove(_) | EventKind::Modify(notify::event::ModifyKind::Name(_))
+    );
+    if !structural && !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return false;
+    }
+    let mut structural_change = false;
+    for path in event.paths {
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let hidden = relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if structural || Language::from_path(&path) != Language::Unknown {
+            structural_change |= structural;
+            changed.insert(path);
+        }
+    }
+    structural_change
+}
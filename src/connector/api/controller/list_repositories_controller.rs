@@ -28,6 +28,14 @@ impl<'a> ListRepositoriesController<'a> {
         for repo in repos {
             output.push_str(&format!("  {} ({})\n", repo.name(), repo.id()));
             output.push_str(&format!("    Path: {}\n", repo.path()));
+            if !repo.roots().is_empty() {
+                let roots: Vec<String> = repo
+                    .root_paths()
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect();
+                output.push_str(&format!("    Roots: {}\n", roots.join(", ")));
+            }
             output.push_str(&format!(
                 "    Files: {}, Chunks: {}\n",
                 repo.file_count(),
@@ -41,15 +49,45 @@ impl<'a> ListRepositoriesController<'a> {
                     .collect();
                 output.push_str(&format!("    Languages: {}\n", langs.join(", ")));
             }
+            let filters = repo.index_filters();
+            if !filters.include.is_empty() {
+                output.push_str(&format!("    Include: {}\n", filters.include.join(", ")));
+            }
+            if !filters.exclude.is_empty() {
+                output.push_str(&format!("    Exclude: {}\n", filters.exclude.join(", ")));
+            }
             let ns_display = repo.namespace().unwrap_or("(none)");
             output.push_str(&format!(
                 "    Store: {}, Namespace: {}\n",
                 repo.store().as_str(),
                 ns_display
             ));
+            output.push_str(&format!(
+                "    Model: {}\n",
+                self.format_embedding_model(repo.namespace())
+            ));
             output.push('\n');
         }
 
         output
     }
+
+    /// The embedding model bound to `namespace` in `namespace_config`, the
+    /// same lookup `stats` uses.
+    fn format_embedding_model(&self, namespace: Option<&str>) -> String {
+        namespace
+            .and_then(|ns| {
+                let db_path =
+                    std::path::Path::new(self.container.data_dir()).join("codesearch.duckdb");
+                crate::namespace_embedding_config(&db_path, ns)
+            })
+            .filter(|cfg| cfg.embedding_model != crate::NO_EMBEDDINGS_MODEL)
+            .map(|cfg| {
+                format!(
+                    "{} ({}-dim, {})",
+                    cfg.embedding_model, cfg.dimensions, cfg.embedding_target
+                )
+            })
+            .unwrap_or_else(|| "no embeddings".to_string())
+    }
 }
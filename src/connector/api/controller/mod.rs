@@ -26,38 +26,67 @@ pub(crate) fn build_chat_client(llm: LlmTarget, data_dir: &str) -> Result<Arc<dy
     })
 }
 
+pub mod archive_controller;
+pub mod cache_controller;
 pub mod channels_controller;
+pub mod chunk_controller;
 pub mod clusters_controller;
 pub mod couplings_controller;
+pub mod cycles_controller;
+pub mod dead_code_controller;
 pub mod delete_controller;
 pub mod execution_features_controller;
 pub mod explain_controller;
+pub mod files_controller;
+pub mod graph_controller;
+pub mod health_controller;
+pub mod hotspots_controller;
 pub mod impact_controller;
 pub mod index_controller;
 pub mod list_repositories_controller;
 pub mod memory_controller;
 pub mod overview_controller;
+pub mod path_controller;
+pub mod prune_controller;
+pub mod rename_controller;
+mod sarif;
+pub mod scip_controller;
 pub mod search_controller;
 pub mod stats_controller;
 pub mod symbol_clusters_controller;
 pub mod symbol_context_controller;
 pub mod uses_controller;
+pub mod vacuum_controller;
 pub mod visualize_controller;
 
+pub use archive_controller::ArchiveController;
+pub use cache_controller::CacheController;
 pub use channels_controller::ChannelsController;
+pub use chunk_controller::ChunkController;
 pub use clusters_controller::ClustersController;
 pub use couplings_controller::CouplingsController;
+pub use cycles_controller::CyclesController;
+pub use dead_code_controller::DeadCodeController;
 pub use delete_controller::DeleteController;
 pub use execution_features_controller::ExecutionFeaturesController;
 pub use explain_controller::ExplainController;
-pub use impact_controller::ImpactController;
+pub use files_controller::FilesController;
+pub use graph_controller::GraphController;
+pub use health_controller::HealthController;
+pub use hotspots_controller::HotspotsController;
+pub use impact_controller::{ImpactController, ImpactThresholdExceeded};
 pub use index_controller::IndexController;
 pub use list_repositories_controller::ListRepositoriesController;
 pub use memory_controller::{run_import_picker_ui, MemoryController};
 pub use overview_controller::OverviewController;
+pub use path_controller::PathController;
+pub use prune_controller::PruneController;
+pub use rename_controller::RenameController;
+pub use scip_controller::ScipController;
 pub use search_controller::SearchController;
 pub use stats_controller::StatsController;
 pub use symbol_clusters_controller::SymbolClustersController;
 pub use symbol_context_controller::SymbolContextController;
 pub use uses_controller::UsesController;
+pub use vacuum_controller::VacuumController;
 pub use visualize_controller::VisualizeController;
@@ -0,0 +1,93 @@
+use anyhow::Result;
+
+use crate::application::{CallGraphQuery, CallPathReport};
+use crate::cli::OutputFormatTextJson;
+
+use super::super::Container;
+
+pub struct PathController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> PathController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    pub async fn path(
+        &self,
+        from: String,
+        to: String,
+        depth: usize,
+        max_paths: usize,
+        repository: Option<String>,
+        format: OutputFormatTextJson,
+    ) -> Result<String> {
+        let mut query = CallGraphQuery::new();
+        if let Some(repo_id) = repository {
+            query = query.with_repository(repo_id);
+        }
+
+        let report = self
+            .container
+            .call_graph_use_case()
+            .find_paths(&from, &to, depth, max_paths, &query)
+            .await?;
+
+        Ok(match format {
+            OutputFormatTextJson::Json => serde_json::to_string_pretty(&report)?,
+            OutputFormatTextJson::Text => Self::format_text(&report),
+        })
+    }
+
+    fn format_text(report: &CallPathReport) -> String {
+        if report.paths.is_empty() {
+            return format!(
+                "No call path from '{}' to '{}' within {} hops. Try a larger --depth, \
+                 or check that both symbols have been indexed.",
+                report.from, report.to, report.max_depth
+            );
+        }
+
+        let hops = report.paths[0].hops.len();
+        let mut out = format!(
+            "Call paths from '{}' to '{}' ({} shortest, {} hop{})\n\
+             ─────────────────────────────────────────\n",
+            report.from,
+            report.to,
+            report.paths.len(),
+            hops,
+            if hops == 1 { "" } else { "s" },
+        );
+
+        for (idx, path) in report.paths.iter().enumerate() {
+            if idx > 0 {
+                out.push('\n');
+            }
+            let Some(first) = path.hops.first() else {
+                continue;
+            };
+            out.push_str(&format!("{}\n\n", path.symbols().join(" → ")));
+            out.push_str(&format!("{}\n", first.caller));
+            for (depth, hop) in path.hops.iter().enumerate() {
+                out.push_str(&format!(
+                    "{}└── {} [{}] {}:{}\n",
+                    "    ".repeat(depth),
+                    hop.callee,
+                    hop.reference_kind,
+                    hop.file_path,
+                    hop.line,
+                ));
+            }
+        }
+
+        if report.truncated {
+            out.push_str(&format!(
+                "\n… more paths exist; showing the first {} (raise --max-paths to see more)\n",
+                report.paths.len()
+            ));
+        }
+
+        out
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+
+use crate::application::PruneReport;
+use crate::cli::OutputFormatTextJson;
+
+use super::super::Container;
+
+pub struct PruneController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> PruneController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Prune files that no longer exist on disk from `repository`, or from
+    /// every indexed repository.
+    pub async fn prune(
+        &self,
+        repository: Option<String>,
+        dry_run: bool,
+        format: OutputFormatTextJson,
+    ) -> Result<String> {
+        let repository_ids: Vec<String> = match repository {
+            Some(repo_id) => vec![repo_id],
+            None => self
+                .container
+                .list_use_case()
+                .execute()
+                .await
+                .context("Failed to list repositories")?
+                .iter()
+                .map(|r| r.id().to_string())
+                .collect(),
+        };
+
+        let use_case = self.container.prune_use_case();
+        let mut reports = Vec::with_capacity(repository_ids.len());
+        for repository_id in &repository_ids {
+            reports.push(use_case.execute(repository_id, dry_run).await?);
+        }
+
+        match format {
+            OutputFormatTextJson::Json => {
+                serde_json::to_string_pretty(&reports).context("Failed to serialize report")
+            }
+            OutputFormatTextJson::Text => Ok(render_text(&reports, dry_run)),
+        }
+    }
+}
+
+fn render_text(reports: &[PruneReport], dry_run: bool) -> String {
+    let mut lines = Vec::new();
+    for report in reports {
+        if report.stale_files.is_empty() {
+            lines.push(format!("{}: no stale files", report.repository_id));
+            continue;
+        }
+        if dry_run {
+            lines.push(format!(
+                "{}: {} stale files (dry run, nothing removed)",
+                report.repository_id,
+                report.stale_files.len()
+            ));
+        } else {
+            lines.push(format!(
+                "{}: removed {} stale files ({} chunks)",
+                report.repository_id,
+                report.stale_files.len(),
+                report.chunks_removed
+            ));
+        }
+        for path in &report.stale_files {
+            lines.push(format!("  {}", path));
+        }
+    }
+    if lines.is_empty() {
+        return "No repositories indexed.".to_string();
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+
+use crate::application::CallGraphQuery;
+
+use super::super::Container;
+
+pub struct RenameController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> RenameController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Rename `old` to `new` in the call graph, or with `dry_run` only list
+    /// the references that would change.
+    pub async fn rename(
+        &self,
+        old: String,
+        new: String,
+        repository: Option<String>,
+        dry_run: bool,
+    ) -> Result<String> {
+        let mut query = CallGraphQuery::new();
+        if let Some(repo_id) = repository {
+            query = query.with_repository(repo_id);
+        }
+        let call_graph = self.container.call_graph_use_case();
+
+        if !dry_run {
+            let renamed = call_graph.rename_symbol(&old, &new, &query).await?;
+            return Ok(format!(
+                "Renamed {} to {} in {} references",
+                old, new, renamed
+            ));
+        }
+
+        let references = call_graph.find_symbol_references(&old, &query).await?;
+        if references.is_empty() {
+            return Ok(format!("No references to {} found.", old));
+        }
+        let mut output = format!(
+            "Would rename {} to {} in {} references:\n",
+            old,
+            new,
+            references.len()
+        );
+        for reference in &references {
+            output.push_str(&format!(
+                "  {}:{}  {} -> {}\n",
+                reference.reference_file_path(),
+                reference.reference_line(),
+                reference.caller_symbol().unwrap_or("<module>"),
+                reference.callee_symbol()
+            ));
+        }
+        Ok(output.trim_end().to_string())
+    }
+}
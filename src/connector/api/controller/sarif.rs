@@ -0,0 +1,81 @@
+//! Minimal SARIF 2.1.0 rendering shared by the `search` and `impact`
+//! controllers, so results can be uploaded to code-scanning dashboards.
+
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// One SARIF `result`: a message anchored to a file and line.
+pub(crate) struct SarifResult<'a> {
+    pub rule_id: &'a str,
+    pub message: String,
+    pub file_path: &'a str,
+    pub start_line: u32,
+}
+
+/// Wrap `results` in a single-run SARIF log produced by `codesearch`.
+pub(crate) fn sarif_log(results: &[SarifResult<'_>]) -> String {
+    let results: Vec<Value> = results.iter().map(sarif_result).collect();
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codesearch",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                }
+            },
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_else(|e| {
+        eprintln!("Failed to serialize SARIF log: {e}");
+        "{}".to_string()
+    })
+}
+
+fn sarif_result(result: &SarifResult<'_>) -> Value {
+    json!({
+        "ruleId": result.rule_id,
+        "level": "note",
+        "message": { "text": result.message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": result.file_path },
+                // SARIF lines are 1-based; a 0 would make the log invalid.
+                "region": { "startLine": result.start_line.max(1) },
+            }
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sarif_log_has_one_run_and_located_results() {
+        let log = sarif_log(&[SarifResult {
+            rule_id: "search",
+            message: "parse_config (score: 0.900)".to_string(),
+            file_path: "src/config.rs",
+            start_line: 0,
+        }]);
+        let value: Value = serde_json::from_str(&log).unwrap();
+
+        assert_eq!(value["version"], "2.1.0");
+        assert_eq!(value["runs"].as_array().unwrap().len(), 1);
+        let run = &value["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "codesearch");
+        let location = &run["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/config.rs");
+        assert_eq!(location["region"]["startLine"], 1);
+        assert_eq!(
+            run["results"][0]["message"]["text"],
+            "parse_config (score: 0.900)"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::connector::adapter::scip::ScipExporter;
+
+use super::super::Container;
+
+pub struct ScipController<'a> {
+    container: &'a Container,
+}
+
+impl<'a> ScipController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Write the call graph of `repository` (ID or name) to `out` as a SCIP
+    /// index.
+    pub async fn export(&self, repository: String, out: String) -> Result<String> {
+        let repository_id = self
+            .container
+            .resolve_repository_id(Some(&repository))
+            .await;
+        let repo = self
+            .container
+            .metadata_repository()
+            .find_by_id(&repository_id)
+            .await?
+            .with_context(|| format!("Repository '{}' not found", repository))?;
+        let references = self
+            .container
+            .call_graph_use_case()
+            .find_by_repository(repo.id())
+            .await?;
+
+        ScipExporter::write(&references, repo.path(), &PathBuf::from(&out)).await?;
+
+        let files: HashSet<&str> = references.iter().map(|r| r.caller_file_path()).collect();
+        Ok(format!(
+            "Exported {} references from {} files of {}\nWrote {}",
+            references.len(),
+            files.len(),
+            repo.name(),
+            out
+        ))
+    }
+}
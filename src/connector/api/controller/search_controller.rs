@@ -1,11 +1,16 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
 use serde::Serialize;
+use tracing::warn;
 
 use crate::cli::OutputFormat;
 use crate::tui::widgets::syntax::dedent;
-use crate::{SearchQuery, SearchResult};
+use crate::{LegScore, ScoreExplanation, SearchLeg, SearchQuery, SearchResult};
 
 use super::super::Container;
+use super::sarif::{sarif_log, SarifResult};
 
 pub struct SearchController<'a> {
     container: &'a Container,
@@ -20,7 +25,54 @@ struct JsonSearchResult<'a> {
     language: String,
     node_type: &'a str,
     symbol_name: Option<&'a str>,
+    /// Set when the symbol was too long to index whole; see `--max-chunk-lines`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part: Option<u32>,
+    /// Kotlin `suspend fun` / Rust `async fn`.
+    is_async: bool,
+    /// The symbol's documentation, without comment markers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doc_comment: Option<&'a str>,
+    /// Lower-ranked duplicates collapsed into this result.
+    #[serde(skip_serializing_if = "is_zero")]
+    duplicates: usize,
     content: &'a str,
+    /// `--context` lines above the chunk, read from the current source file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context_before: Option<String>,
+    /// `--context` lines below the chunk, read from the current source file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context_after: Option<String>,
+    /// `--explain` score provenance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explain: Option<&'a ScoreExplanation>,
+    /// Query terms the keyword leg matched; empty for semantic-only hits.
+    matched_terms: &'a [String],
+    /// The retrieval legs that found this result.
+    search_legs: &'a [SearchLeg],
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// A result's surroundings read back from its source file for `--context`:
+/// the chunk's own lines as they are on disk plus N lines either side.
+struct SourceContext {
+    before: Vec<String>,
+    lines: Vec<String>,
+    after: Vec<String>,
+}
+
+/// `--context` lookups by chunk id. `None` marks a result whose file changed
+/// or disappeared since indexing; an absent id means no context was asked for.
+type SourceContexts = HashMap<String, Option<SourceContext>>;
+
+/// `--group-by-file` JSON shape: one object per file, its matches by score.
+#[derive(Serialize)]
+struct JsonFileGroup<'a> {
+    file_path: &'a str,
+    matches: Vec<JsonSearchResult<'a>>,
 }
 
 impl<'a> SearchController<'a> {
@@ -28,6 +80,7 @@ impl<'a> SearchController<'a> {
         Self { container }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn search(
         &self,
         query: String,
@@ -35,36 +88,227 @@ impl<'a> SearchController<'a> {
         min_score: Option<f32>,
         languages: Option<Vec<String>>,
         repositories: Option<Vec<String>>,
+        node_types: Option<Vec<String>>,
+        async_only: bool,
+        paths: Vec<String>,
+        exclude: Vec<String>,
+        symbol_regex: Option<String>,
+        diversity: f32,
         format: OutputFormat,
         text_search: bool,
+        rrf_k: f32,
+        semantic_weight: f32,
+        text_weight: f32,
+        text_candidates: Option<usize>,
+        dedup: bool,
+        group_by_file: bool,
+        context: usize,
+        explain: bool,
+        symbol: bool,
+        show_expansions: bool,
     ) -> Result<String> {
+        if rrf_k.is_nan() || rrf_k < 0.0 {
+            anyhow::bail!("--rrf-k must be zero or positive (got {rrf_k})");
+        }
+        if [semantic_weight, text_weight]
+            .iter()
+            .any(|w| w.is_nan() || *w < 0.0)
+            || semantic_weight + text_weight == 0.0
+        {
+            anyhow::bail!(
+                "--semantic-weight and --text-weight must be non-negative and not both zero"
+            );
+        }
+
         let mut search_query = SearchQuery::new(&query)
             .with_limit(num)
-            .with_text_search(text_search);
+            .with_text_search(text_search)
+            .with_rrf_k(rrf_k)
+            .with_leg_weights(semantic_weight, text_weight)
+            .with_explain(explain);
 
         if let Some(score) = min_score {
             search_query = search_query.with_min_score(score);
         }
-        if let Some(langs) = languages {
-            search_query = search_query.with_languages(langs);
+        if let Some(n) = text_candidates {
+            search_query = search_query.with_text_candidates(n);
+        }
+        for glob in &exclude {
+            glob::Pattern::new(glob)
+                .map_err(|e| anyhow::anyhow!("Invalid --exclude glob '{glob}': {e}"))?;
         }
-        if let Some(repos) = repositories {
-            search_query = search_query.with_repositories(repos);
+        let mut search_query =
+            with_filters(search_query, languages, repositories, node_types, paths)?
+                .with_exclude_paths(exclude)
+                .with_async_only(async_only);
+        if let Some(pattern) = symbol_regex {
+            search_query = search_query.with_symbol_pattern(&pattern)?;
         }
 
-        let use_case = self.container.search_use_case();
-        let results = use_case.execute(search_query).await?;
+        if !(0.0..=1.0).contains(&diversity) {
+            anyhow::bail!("--diversity must be between 0.0 and 1.0 (got {diversity})");
+        }
+
+        let use_case = self
+            .container
+            .search_use_case()
+            .with_diversity(diversity)
+            .with_dedup(dedup);
+        let (results, expansions) = if symbol {
+            (use_case.find_symbol(&search_query).await?, Vec::new())
+        } else {
+            use_case.execute_with_expansions(search_query).await?
+        };
+        let expansions_note = if show_expansions && !symbol {
+            format_expansions(&expansions)
+        } else {
+            String::new()
+        };
+        let contexts = match format {
+            OutputFormat::Text | OutputFormat::Json => {
+                self.load_contexts(&results, context).await?
+            }
+            OutputFormat::Vimgrep | OutputFormat::Sarif => SourceContexts::new(),
+        };
+
+        // Machine-readable formats keep stdout parseable; the variants go to
+        // stderr instead.
+        if !matches!(format, OutputFormat::Text) && !expansions_note.is_empty() {
+            eprint!("{expansions_note}");
+        }
 
         Ok(match format {
-            OutputFormat::Text => self.format_search_results(&results),
-            OutputFormat::Json => self.format_search_results_json(&results),
+            OutputFormat::Text => {
+                let mut output = expansions_note;
+                output.push_str(&if group_by_file {
+                    self.format_search_results_grouped(&results, &contexts)
+                } else {
+                    self.format_search_results(&results, &contexts)
+                });
+                if explain && !results.is_empty() {
+                    output.push_str(&format_explain_table(&results));
+                }
+                output
+            }
+            OutputFormat::Json if group_by_file => {
+                self.format_search_results_json_grouped(&results, &contexts)
+            }
+            OutputFormat::Json => self.format_search_results_json(&results, &contexts),
             OutputFormat::Vimgrep => self.format_search_results_vimgrep(&results),
+            OutputFormat::Sarif => self.format_search_results_sarif(&results),
+        })
+    }
+
+    /// Find chunks similar to the one covering `location` (`<file>:<line>`),
+    /// reusing its stored embedding as the query vector.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn similar(
+        &self,
+        location: String,
+        num: usize,
+        threshold: Option<f32>,
+        languages: Option<Vec<String>>,
+        repositories: Option<Vec<String>>,
+        node_types: Option<Vec<String>>,
+        paths: Vec<String>,
+        format: OutputFormat,
+    ) -> Result<String> {
+        let (file, line) = location
+            .rsplit_once(':')
+            .and_then(|(file, line)| Some((file, line.parse::<u32>().ok()?)))
+            .ok_or_else(|| anyhow::anyhow!("Expected <file>:<line>, got '{location}'"))?;
+        let file = file.trim_start_matches("./");
+
+        let mut query = SearchQuery::new("").with_limit(num);
+        if let Some(threshold) = threshold {
+            query = query.with_min_score(threshold);
+        }
+        let query = with_filters(query, languages, repositories, node_types, paths)?;
+
+        let similar = self
+            .container
+            .similar_code_use_case()
+            .execute(file, line, &query)
+            .await?;
+
+        Ok(match format {
+            OutputFormat::Text => {
+                let symbol = similar
+                    .source
+                    .symbol_name()
+                    .map(|name| format!(" ({name})"))
+                    .unwrap_or_default();
+                format!(
+                    "Similar to {}{}\n\n{}",
+                    similar.source.location(),
+                    symbol,
+                    self.format_search_results(&similar.results, &SourceContexts::new())
+                )
+            }
+            OutputFormat::Json => {
+                self.format_search_results_json(&similar.results, &SourceContexts::new())
+            }
+            OutputFormat::Vimgrep => self.format_search_results_vimgrep(&similar.results),
+            OutputFormat::Sarif => self.format_search_results_sarif(&similar.results),
         })
     }
 
+    /// Read `lines` lines above and below every result from its file under
+    /// the repository path recorded at index time. A file that was deleted,
+    /// or no longer holds the chunk where the index says, gets a warning and
+    /// keeps its stored content without context.
+    async fn load_contexts(
+        &self,
+        results: &[SearchResult],
+        lines: usize,
+    ) -> Result<SourceContexts> {
+        let mut contexts = SourceContexts::new();
+        if lines == 0 || results.is_empty() {
+            return Ok(contexts);
+        }
+
+        let roots: HashMap<String, String> = self
+            .container
+            .list_use_case()
+            .execute()
+            .await?
+            .into_iter()
+            .map(|r| (r.id().to_string(), r.path().to_string()))
+            .collect();
+        let mut files: HashMap<PathBuf, Option<Vec<String>>> = HashMap::new();
+
+        for result in results {
+            let chunk = result.chunk();
+            let context = match roots.get(chunk.repository_id()) {
+                Some(root) => {
+                    let path = Path::new(root).join(chunk.file_path());
+                    if !files.contains_key(&path) {
+                        let source = tokio::fs::read_to_string(&path)
+                            .await
+                            .ok()
+                            .map(|text| text.lines().map(str::to_string).collect());
+                        files.insert(path.clone(), source);
+                    }
+                    files[&path]
+                        .as_deref()
+                        .and_then(|source| surrounding_lines(source, result, lines))
+                }
+                None => None,
+            };
+            if context.is_none() {
+                warn!(
+                    "{} changed or was deleted since indexing; showing the stored chunk without context",
+                    chunk.location()
+                );
+            }
+            contexts.insert(chunk.id().to_string(), context);
+        }
+        Ok(contexts)
+    }
+
     // ── formatting helpers ────────────────────────────────────────────────────
 
-    fn format_search_results(&self, results: &[SearchResult]) -> String {
+    fn format_search_results(&self, results: &[SearchResult], contexts: &SourceContexts) -> String {
         if results.is_empty() {
             return "No results found.".to_string();
         }
@@ -73,49 +317,93 @@ impl<'a> SearchController<'a> {
 
         for (i, result) in results.iter().enumerate() {
             output.push_str(&format!(
-                "{}. {} (score: {:.3})\n",
+                "{}. {} (score: {:.3}){}{}\n",
                 i + 1,
                 result.chunk().location(),
-                result.score()
+                result.score(),
+                legs_note(result),
+                duplicates_note(result)
             ));
+            push_result_body(&mut output, result, "   ", contexts);
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Text output for `--group-by-file`: one header per file, in order of
+    /// its best hit, with that file's matches beneath it. Matches keep their
+    /// overall rank number.
+    fn format_search_results_grouped(
+        &self,
+        results: &[SearchResult],
+        contexts: &SourceContexts,
+    ) -> String {
+        if results.is_empty() {
+            return "No results found.".to_string();
+        }
 
-            if let Some(name) = result.chunk().symbol_name() {
+        let groups = group_by_file(results);
+        let mut output = format!(
+            "Found {} results in {} files:\n\n",
+            results.len(),
+            groups.len()
+        );
+
+        for (file_path, matches) in groups {
+            output.push_str(file_path);
+            output.push('\n');
+            for (rank, result) in matches {
                 output.push_str(&format!(
-                    "   Symbol: {} ({})\n",
-                    name,
-                    result.chunk().node_type()
+                    "  {}. lines {}-{} (score: {:.3}){}{}\n",
+                    rank,
+                    result.chunk().start_line(),
+                    result.chunk().end_line(),
+                    result.score(),
+                    legs_note(result),
+                    duplicates_note(result)
                 ));
+                push_result_body(&mut output, result, "     ", contexts);
             }
-
-            let preview: String = dedent(result.chunk().content())
-                .lines()
-                .take(10)
-                .map(|l| format!("   | {}", l))
-                .collect::<Vec<_>>()
-                .join("\n");
-            output.push_str(&preview);
-            output.push_str("\n\n");
+            output.push('\n');
         }
 
         output
     }
 
-    fn format_search_results_json(&self, results: &[SearchResult]) -> String {
-        let json_results: Vec<JsonSearchResult> = results
-            .iter()
-            .map(|r| JsonSearchResult {
-                file_path: r.chunk().file_path(),
-                start_line: r.chunk().start_line(),
-                end_line: r.chunk().end_line(),
-                score: r.score(),
-                language: r.chunk().language().to_string(),
-                node_type: r.chunk().node_type().as_str(),
-                symbol_name: r.chunk().symbol_name(),
-                content: r.chunk().content(),
+    fn format_search_results_json(
+        &self,
+        results: &[SearchResult],
+        contexts: &SourceContexts,
+    ) -> String {
+        let json_results: Vec<JsonSearchResult> =
+            results.iter().map(|r| json_result(r, contexts)).collect();
+
+        serde_json::to_string_pretty(&json_results).unwrap_or_else(|e| {
+            eprintln!("Failed to serialize search results: {e}");
+            "[]".to_string()
+        })
+    }
+
+    /// JSON output for `--group-by-file`. Opt-in so the flat array that
+    /// editor integrations parse stays the default shape.
+    fn format_search_results_json_grouped(
+        &self,
+        results: &[SearchResult],
+        contexts: &SourceContexts,
+    ) -> String {
+        let groups: Vec<JsonFileGroup> = group_by_file(results)
+            .into_iter()
+            .map(|(file_path, matches)| JsonFileGroup {
+                file_path,
+                matches: matches
+                    .into_iter()
+                    .map(|(_, r)| json_result(r, contexts))
+                    .collect(),
             })
             .collect();
 
-        serde_json::to_string_pretty(&json_results).unwrap_or_else(|e| {
+        serde_json::to_string_pretty(&groups).unwrap_or_else(|e| {
             eprintln!("Failed to serialize search results: {e}");
             "[]".to_string()
         })
@@ -144,4 +432,347 @@ impl<'a> SearchController<'a> {
             .collect::<Vec<_>>()
             .join("\n")
     }
+
+    /// Format results as a SARIF 2.1.0 log, one `result` per hit.
+    fn format_search_results_sarif(&self, results: &[SearchResult]) -> String {
+        let results: Vec<SarifResult> = results
+            .iter()
+            .map(|r| {
+                let symbol = r
+                    .chunk()
+                    .symbol_name()
+                    .unwrap_or(r.chunk().node_type().as_str());
+                SarifResult {
+                    rule_id: "search",
+                    message: format!("{} (score: {:.3})", symbol, r.score()),
+                    file_path: r.chunk().file_path(),
+                    start_line: r.chunk().start_line(),
+                }
+            })
+            .collect();
+        sarif_log(&results)
+    }
+}
+
+/// Apply the filter flags shared by `search` and `similar` to `query`.
+fn with_filters(
+    mut query: SearchQuery,
+    languages: Option<Vec<String>>,
+    repositories: Option<Vec<String>>,
+    node_types: Option<Vec<String>>,
+    paths: Vec<String>,
+) -> Result<SearchQuery> {
+    if let Some(langs) = languages {
+        query = query.with_languages(langs);
+    }
+    if let Some(repos) = repositories {
+        query = query.with_repositories(repos);
+    }
+    if let Some(types) = node_types {
+        query = query.with_node_types(types);
+    }
+    for glob in paths {
+        glob::Pattern::new(glob.strip_prefix('!').unwrap_or(&glob))
+            .map_err(|e| anyhow::anyhow!("Invalid --path glob '{glob}': {e}"))?;
+        query = query.with_path_glob(&glob);
+    }
+    Ok(query)
+}
+
+fn json_result<'r>(r: &'r SearchResult, contexts: &SourceContexts) -> JsonSearchResult<'r> {
+    let context = contexts.get(r.chunk().id()).and_then(Option::as_ref);
+    JsonSearchResult {
+        file_path: r.chunk().file_path(),
+        start_line: r.chunk().start_line(),
+        end_line: r.chunk().end_line(),
+        score: r.score(),
+        language: r.chunk().language().to_string(),
+        node_type: r.chunk().node_type().as_str(),
+        symbol_name: r.chunk().symbol_name(),
+        part: r.chunk().part(),
+        is_async: r.chunk().is_async(),
+        doc_comment: r.chunk().doc_comment(),
+        duplicates: r.duplicates(),
+        content: r.chunk().content(),
+        context_before: context.map(|c| c.before.join("\n")),
+        context_after: context.map(|c| c.after.join("\n")),
+        explain: r.explanation(),
+        matched_terms: r.matched_terms(),
+        search_legs: r.search_legs(),
+    }
+}
+
+/// `--show-expansions` header: the phrasings searched besides the query.
+fn format_expansions(expansions: &[String]) -> String {
+    if expansions.is_empty() {
+        return "Query expansion produced no variants; searched the original query only.\n\n"
+            .to_string();
+    }
+    let mut out = String::from("Expanded query variants:\n");
+    for (i, variant) in expansions.iter().enumerate() {
+        out.push_str(&format!("  {}. {}\n", i + 1, variant));
+    }
+    out.push('\n');
+    out
+}
+
+/// `--explain` table: one row per result, in output order, with each stage's
+/// contribution. `-` marks a stage that didn't run or didn't see the result.
+fn format_explain_table(results: &[SearchResult]) -> String {
+    let leg = |leg: Option<LegScore>| match leg {
+        Some(leg) => format!("#{:<3} {:.3}", leg.rank, leg.score),
+        None => "-".to_string(),
+    };
+    let score = |score: Option<f32>| match score {
+        Some(score) => format!("{score:.4}"),
+        None => "-".to_string(),
+    };
+
+    let mut output = format!(
+        "Score breakdown:\n  {:>3}  {:<14}{:<14}{:<9}{}\n",
+        "#", "semantic", "bm25", "rrf", "rerank"
+    );
+    for (i, result) in results.iter().enumerate() {
+        let explanation = result.explanation().cloned().unwrap_or_default();
+        output.push_str(&format!(
+            "  {:>3}  {:<14}{:<14}{:<9}{}\n",
+            i + 1,
+            leg(explanation.semantic),
+            leg(explanation.text),
+            score(explanation.rrf),
+            score(explanation.rerank),
+        ));
+    }
+    output
+}
+
+/// The chunk's lines in `source` with up to `n` lines either side, or `None`
+/// when the file no longer holds the chunk at its indexed line range.
+fn surrounding_lines(source: &[String], result: &SearchResult, n: usize) -> Option<SourceContext> {
+    let chunk = result.chunk();
+    let start = (chunk.start_line() as usize).checked_sub(1)?;
+    let end = chunk.end_line() as usize;
+    if start >= end || end > source.len() {
+        return None;
+    }
+
+    // Chunk content can start mid-line (tree-sitter ranges begin at the first
+    // token), so compare with whitespace collapsed.
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !normalize(&source[start..end].join("\n")).contains(&normalize(chunk.content())) {
+        return None;
+    }
+
+    Some(SourceContext {
+        before: source[start.saturating_sub(n)..start].to_vec(),
+        lines: source[start..end].to_vec(),
+        after: source[end..(end + n).min(source.len())].to_vec(),
+    })
+}
+
+/// How the result was found: ` [semantic]`, ` [keyword: authenticate, user]`
+/// or both, comma-separated. Empty when no leg was recorded.
+fn legs_note(result: &SearchResult) -> String {
+    let legs: Vec<String> = result
+        .search_legs()
+        .iter()
+        .map(|leg| match leg {
+            SearchLeg::Semantic { .. } => "semantic".to_string(),
+            SearchLeg::Text { terms, .. } if terms.is_empty() => "keyword".to_string(),
+            SearchLeg::Text { terms, .. } => format!("keyword: {}", terms.join(", ")),
+        })
+        .collect();
+    if legs.is_empty() {
+        return String::new();
+    }
+    format!(" [{}]", legs.join(", "))
+}
+
+fn duplicates_note(result: &SearchResult) -> String {
+    match result.duplicates() {
+        0 => String::new(),
+        1 => " (+1 duplicate)".to_string(),
+        n => format!(" (+{n} duplicates)"),
+    }
+}
+
+/// Symbol line and a 10-line preview of `result`, indented by `indent`.
+/// With `--context`, the preview is framed by the surrounding source lines,
+/// marked `:` instead of `|`.
+fn push_result_body(
+    output: &mut String,
+    result: &SearchResult,
+    indent: &str,
+    contexts: &SourceContexts,
+) {
+    if let Some(name) = result.chunk().symbol_name() {
+        let part = result
+            .chunk()
+            .part()
+            .map(|p| format!(", part {}", p))
+            .unwrap_or_default();
+        output.push_str(&format!(
+            "{}Symbol: {} ({}{})\n",
+            indent,
+            name,
+            result.chunk().node_type(),
+            part
+        ));
+    }
+
+    let preview: String = match contexts.get(result.chunk().id()) {
+        Some(Some(context)) => {
+            let lines: Vec<&str> = context.lines.iter().take(10).map(String::as_str).collect();
+            let block: Vec<&str> = context
+                .before
+                .iter()
+                .map(String::as_str)
+                .chain(lines.iter().copied())
+                .chain(context.after.iter().map(String::as_str))
+                .collect();
+            let chunk_lines = context.before.len()..context.before.len() + lines.len();
+            dedent(&block.join("\n"))
+                .lines()
+                .enumerate()
+                .map(|(i, l)| {
+                    let marker = if chunk_lines.contains(&i) { '|' } else { ':' };
+                    format!("{}{} {}", indent, marker, l)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        context => {
+            let mut preview = dedent(result.chunk().content())
+                .lines()
+                .take(10)
+                .map(|l| format!("{}| {}", indent, l))
+                .collect::<Vec<_>>()
+                .join("\n");
+            if let Some(None) = context {
+                preview.push_str(&format!(
+                    "\n{}(source changed since indexing; no context shown)",
+                    indent
+                ));
+            }
+            preview
+        }
+    };
+    output.push_str(&preview);
+    output.push('\n');
+}
+
+/// Cluster results by file (and repository, so equal paths in two indexed
+/// repositories stay apart). Files are ordered by their best match, matches
+/// within a file by score; each match carries its 1-based overall rank.
+fn group_by_file(results: &[SearchResult]) -> Vec<(&str, Vec<(usize, &SearchResult)>)> {
+    let mut groups: Vec<(&str, &str, Vec<(usize, &SearchResult)>)> = Vec::new();
+    for (i, result) in results.iter().enumerate() {
+        let chunk = result.chunk();
+        let key = (chunk.repository_id(), chunk.file_path());
+        match groups
+            .iter_mut()
+            .find(|(repo, path, _)| (*repo, *path) == key)
+        {
+            Some((_, _, matches)) => matches.push((i + 1, result)),
+            None => groups.push((key.0, key.1, vec![(i + 1, result)])),
+        }
+    }
+    for (_, _, matches) in &mut groups {
+        matches.sort_by(|a, b| b.1.score().total_cmp(&a.1.score()));
+    }
+    groups.sort_by(|a, b| b.2[0].1.score().total_cmp(&a.2[0].1.score()));
+    groups
+        .into_iter()
+        .map(|(_, path, matches)| (path, matches))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CodeChunk, Language, NodeType};
+
+    fn result(file: &str, line: u32, score: f32) -> SearchResult {
+        let chunk = CodeChunk::new(
+            file.to_string(),
+            format!("fn f{line}() {{}}"),
+            line,
+            line,
+            Language::Rust,
+            NodeType::Function,
+            "repo".to_string(),
+        );
+        SearchResult::new(chunk, score)
+    }
+
+    #[test]
+    fn group_by_file_orders_files_by_best_match() {
+        let results = vec![
+            result("a.rs", 1, 0.9),
+            result("b.rs", 1, 0.8),
+            result("a.rs", 10, 0.7),
+            result("c.rs", 1, 0.6),
+            result("b.rs", 10, 0.5),
+        ];
+
+        let groups = group_by_file(&results);
+        let shape: Vec<(&str, Vec<usize>)> = groups
+            .iter()
+            .map(|(path, matches)| (*path, matches.iter().map(|(rank, _)| *rank).collect()))
+            .collect();
+        assert_eq!(
+            shape,
+            vec![
+                ("a.rs", vec![1, 3]),
+                ("b.rs", vec![2, 5]),
+                ("c.rs", vec![4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn surrounding_lines_reads_context_and_detects_stale_files() {
+        let source: Vec<String> = (1..=10).map(|i| format!("line {i}")).collect();
+        let chunk = CodeChunk::new(
+            "a.rs".to_string(),
+            "line 5\nline 6".to_string(),
+            5,
+            6,
+            Language::Rust,
+            NodeType::Function,
+            "repo".to_string(),
+        );
+        let hit = SearchResult::new(chunk, 0.5);
+
+        let context = surrounding_lines(&source, &hit, 2).expect("chunk is unchanged");
+        assert_eq!(context.before, vec!["line 3", "line 4"]);
+        assert_eq!(context.lines, vec!["line 5", "line 6"]);
+        assert_eq!(context.after, vec!["line 7", "line 8"]);
+
+        let edge = surrounding_lines(&source[..6], &hit, 3).expect("chunk is unchanged");
+        assert!(edge.after.is_empty());
+
+        let mut edited = source.clone();
+        edited[4] = "line five".to_string();
+        assert!(surrounding_lines(&edited, &hit, 2).is_none());
+        assert!(surrounding_lines(&source[..5], &hit, 2).is_none());
+    }
+
+    #[test]
+    fn legs_note_names_the_legs_that_found_a_result() {
+        assert_eq!(legs_note(&result("a.rs", 1, 0.9)), "");
+        assert_eq!(
+            legs_note(&result("a.rs", 1, 0.9).with_semantic_leg()),
+            " [semantic]"
+        );
+        let keyword = result("a.rs", 1, 0.9).with_search_leg(SearchLeg::Text {
+            bm25_score: 2.0,
+            terms: vec!["authenticate".to_string(), "user".to_string()],
+        });
+        assert_eq!(legs_note(&keyword), " [keyword: authenticate, user]");
+        assert_eq!(
+            legs_note(&keyword.with_semantic_leg()),
+            " [keyword: authenticate, user, semantic]"
+        );
+    }
 }
@@ -1,7 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Serialize;
 
+use crate::application::{IndexState, RepositoryChunkStats};
+use crate::cli::OutputFormatTextJson;
 use crate::domain::ChannelEndpoint;
 
 use super::super::Container;
@@ -15,10 +18,18 @@ impl<'a> StatsController<'a> {
         Self { container }
     }
 
-    pub async fn stats(&self) -> Result<String> {
+    pub async fn stats(&self, format: OutputFormatTextJson) -> Result<String> {
         let use_case = self.container.list_use_case();
         let repos = use_case.execute().await?;
 
+        // One grouped scan of the chunk table covers every repository.
+        let mut chunk_stats = self
+            .container
+            .vector_repository()
+            .stats_by_repository()
+            .await
+            .unwrap_or_default();
+
         let call_graph_use_case = self.container.call_graph_use_case();
         let channel_repo = self.container.channel_endpoint_repository();
         let analysis_repo = self.container.analysis_repository();
@@ -95,6 +106,10 @@ impl<'a> StatsController<'a> {
                 languages: lang_map,
                 namespace: repo.namespace(),
                 embedding_info,
+                store: repo.store().as_str(),
+                updated_at: repo.updated_at(),
+                index_age_seconds: repo.seconds_since_update(),
+                chunk_breakdown: chunk_stats.remove(repo_id).unwrap_or_default(),
                 call_graph: cg_stats,
                 channel_count: endpoints.len() as u64,
                 channels_by_protocol: count_by_protocol(&endpoints),
@@ -104,7 +119,134 @@ impl<'a> StatsController<'a> {
             });
         }
 
-        Ok(self.format_output(&repos, &repo_details, &globals, &memory_stats))
+        let storage = if self.container.verbose() {
+            Some(self.storage_details(&repos).await)
+        } else {
+            None
+        };
+
+        match format {
+            OutputFormatTextJson::Json => {
+                let report = StatsReport {
+                    repositories: repos.len(),
+                    total_files: repos.iter().map(|r| r.file_count()).sum(),
+                    total_chunks: repos.iter().map(|r| r.chunk_count()).sum(),
+                    data_dir: self.container.data_dir(),
+                    data_file_size: self.container.data_file_size(),
+                    globals: &globals,
+                    memory: &memory_stats,
+                    details: &repo_details,
+                    storage: storage.as_ref(),
+                };
+                serde_json::to_string_pretty(&report).context("Failed to serialize stats")
+            }
+            OutputFormatTextJson::Text => {
+                let mut output = self.format_output(&repos, &repo_details, &globals, &memory_stats);
+                if let Some(storage) = &storage {
+                    output.push_str(&self.format_storage(&repo_details, storage));
+                }
+                Ok(output)
+            }
+        }
+    }
+
+    /// The `--verbose` extras: chunks per language summed over every
+    /// namespace's store, and the state of each namespace's FTS index.
+    async fn storage_details(&self, repos: &[crate::Repository]) -> StorageDetails {
+        let mut namespaces: BTreeSet<&str> = repos.iter().filter_map(|r| r.namespace()).collect();
+        namespaces.insert(self.container.namespace());
+
+        let mut details = StorageDetails::default();
+        for namespace in namespaces {
+            let Some(store) = self.container.namespace_vector_repository(namespace).await else {
+                continue;
+            };
+            for (language, count) in store.count_by_language(None).await.unwrap_or_default() {
+                *details
+                    .chunks_by_language
+                    .entry(language.as_str().to_string())
+                    .or_default() += count;
+            }
+            if let Ok(Some(integrity)) = store.integrity().await {
+                details
+                    .fts_index
+                    .insert(namespace.to_string(), fts_status(integrity.text_index));
+            }
+        }
+        details
+    }
+
+    fn format_storage(&self, repo_details: &[RepoDetail], storage: &StorageDetails) -> String {
+        let mut lines = Vec::new();
+
+        lines.push("Repositories".to_string());
+        lines.push("-".repeat(40));
+        let rows: Vec<[String; 6]> = repo_details
+            .iter()
+            .map(|d| {
+                [
+                    d.name.to_string(),
+                    d.store.to_string(),
+                    d.namespace.unwrap_or("(none)").to_string(),
+                    d.file_count.to_string(),
+                    d.chunk_count.to_string(),
+                    format!("{} ago", format_age(d.index_age_seconds)),
+                ]
+            })
+            .collect();
+        let header = [
+            "Name",
+            "Store",
+            "Namespace",
+            "Files",
+            "Chunks",
+            "Last Indexed",
+        ];
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let render = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            format!("  {}", padded.join("  ").trim_end())
+        };
+        lines.push(render(&header.map(str::to_string)));
+        for row in &rows {
+            lines.push(render(row));
+        }
+        lines.push(String::new());
+
+        if !storage.chunks_by_language.is_empty() {
+            lines.push("Chunks by Language (vector store)".to_string());
+            lines.push("-".repeat(40));
+            let mut counts: Vec<(&String, &u64)> = storage.chunks_by_language.iter().collect();
+            counts.sort_by_key(|(_, count)| std::cmp::Reverse(**count));
+            for (language, count) in counts {
+                lines.push(format!("  {}: {}", language, count));
+            }
+            lines.push(String::new());
+        }
+
+        lines.push("Storage".to_string());
+        lines.push("-".repeat(40));
+        if let Some(size) = self.container.data_file_size() {
+            lines.push(format!("  DuckDB file size: {}", format_megabytes(size)));
+        }
+        if !storage.fts_index.is_empty() {
+            lines.push("  FTS index:".to_string());
+            for (namespace, status) in &storage.fts_index {
+                lines.push(format!("    {}: {}", namespace, status));
+            }
+        }
+        lines.push(String::new());
+
+        lines.join("\n")
     }
 
     async fn fetch_memory_stats(&self) -> Result<crate::application::MemoryStats> {
@@ -114,10 +256,8 @@ impl<'a> StatsController<'a> {
 
     fn format_embedding_info(&self, namespace: Option<&str>) -> String {
         if let Some(ns) = namespace {
-            if let Some(cfg) = crate::namespace_embedding_config(
-                std::path::Path::new(self.container.data_dir()),
-                ns,
-            ) {
+            let db_path = std::path::Path::new(self.container.data_dir()).join("codesearch.duckdb");
+            if let Some(cfg) = crate::namespace_embedding_config(&db_path, ns) {
                 return format!(
                     "target={}, model={}, dims={}",
                     cfg.embedding_target, cfg.embedding_model, cfg.dimensions
@@ -152,6 +292,9 @@ impl<'a> StatsController<'a> {
         lines.push(format!("  Total Files:   {}", total_files));
         lines.push(format!("  Total Chunks:  {}", total_chunks));
         lines.push(format!("  Data Dir:      {}", self.container.data_dir()));
+        if let Some(size) = self.container.data_file_size() {
+            lines.push(format!("  Data file size: {}", format_megabytes(size)));
+        }
         lines.push(String::new());
 
         // Languages (global)
@@ -235,10 +378,28 @@ impl<'a> StatsController<'a> {
                     detail.namespace.unwrap_or("(none)")
                 ));
                 lines.push(format!("    Embedding:       {}", detail.embedding_info));
+                lines.push(format!("    Store:           {}", detail.store));
+                lines.push(format!(
+                    "    Last Indexed:    {} ago",
+                    format_age(detail.index_age_seconds)
+                ));
                 lines.push(format!("    Files:           {}", detail.file_count));
                 lines.push(format!("    Chunks:          {}", detail.chunk_count));
                 lines.push(String::new());
 
+                // Chunk breakdown from the vector store (per repo)
+                if !detail.chunk_breakdown.by_node_type.is_empty() {
+                    lines.push("    Chunks by node type:".to_string());
+                    for (node_type, count) in &detail.chunk_breakdown.by_node_type {
+                        lines.push(format!("      {}: {}", node_type, count));
+                    }
+                    lines.push("    Chunks by language:".to_string());
+                    for (lang, count) in &detail.chunk_breakdown.by_language {
+                        lines.push(format!("      {}: {}", lang, count));
+                    }
+                    lines.push(String::new());
+                }
+
                 // Languages (per repo)
                 if !detail.languages.is_empty() {
                     lines.push("    Languages:".to_string());
@@ -312,8 +473,34 @@ impl<'a> StatsController<'a> {
     }
 }
 
+/// The `--format json` shape of the stats command.
+#[derive(Serialize)]
+struct StatsReport<'a> {
+    repositories: usize,
+    total_files: u64,
+    total_chunks: u64,
+    data_dir: &'a str,
+    data_file_size: Option<u64>,
+    #[serde(flatten)]
+    globals: &'a GlobalStats,
+    memory: &'a crate::application::MemoryStats,
+    #[serde(rename = "per_repository")]
+    details: &'a [RepoDetail<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<&'a StorageDetails>,
+}
+
+/// Store-level breakdowns printed by `stats --verbose`.
+#[derive(Default, Serialize)]
+struct StorageDetails {
+    /// Stored chunks per language, summed over every namespace.
+    chunks_by_language: BTreeMap<String, u64>,
+    /// Full-text index state per namespace: `present`, `stale` or `absent`.
+    fts_index: BTreeMap<String, &'static str>,
+}
+
 /// Aggregated totals across all repositories, shown in the global summary.
-#[derive(Default)]
+#[derive(Default, Serialize)]
 struct GlobalStats {
     /// Per-language (file_count, chunk_count) summed over every repo.
     languages: HashMap<String, (u64, u64)>,
@@ -326,6 +513,7 @@ struct GlobalStats {
     features: u64,
 }
 
+#[derive(Serialize)]
 struct RepoDetail<'a> {
     name: &'a str,
     path: &'a str,
@@ -335,6 +523,13 @@ struct RepoDetail<'a> {
     languages: HashMap<String, (u64, u64)>,
     namespace: Option<&'a str>,
     embedding_info: String,
+    /// Vector storage backend the repository was indexed into.
+    store: &'static str,
+    /// Unix timestamp of the last (re)index.
+    updated_at: i64,
+    index_age_seconds: i64,
+    /// Chunk counts by node type and language, from the vector store.
+    chunk_breakdown: RepositoryChunkStats,
     call_graph: crate::application::CallGraphStats,
     channel_count: u64,
     channels_by_protocol: Vec<(String, u64)>,
@@ -343,6 +538,29 @@ struct RepoDetail<'a> {
     feature_count: u64,
 }
 
+/// Render a duration in seconds as its largest whole unit (`42s`, `3h`, `5d`).
+fn format_age(seconds: i64) -> String {
+    match seconds {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3_600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3_600),
+        s => format!("{}d", s / 86_400),
+    }
+}
+
+fn fts_status(state: IndexState) -> &'static str {
+    match state {
+        IndexState::Ok => "present",
+        IndexState::Stale => "stale",
+        IndexState::Missing => "absent",
+    }
+}
+
+/// Render a byte count in megabytes (`12.3 MB`).
+pub(crate) fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
 fn count_by_protocol(endpoints: &[ChannelEndpoint]) -> Vec<(String, u64)> {
     let mut counts: HashMap<String, u64> = HashMap::new();
     for ep in endpoints {
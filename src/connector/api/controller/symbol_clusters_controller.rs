@@ -68,6 +68,9 @@ impl<'a> SymbolClustersController<'a> {
             OutputFormat::Vimgrep => {
                 anyhow::bail!("vimgrep output format is not supported for symbol-clusters list")
             }
+            OutputFormat::Sarif => {
+                anyhow::bail!("sarif output format is not supported for symbol-clusters list")
+            }
             OutputFormat::Text => {
                 if graph.communities.is_empty() {
                     return Ok(format!(
@@ -136,6 +139,9 @@ impl<'a> SymbolClustersController<'a> {
             OutputFormat::Vimgrep => {
                 anyhow::bail!("vimgrep output format is not supported for symbol-clusters get")
             }
+            OutputFormat::Sarif => {
+                anyhow::bail!("sarif output format is not supported for symbol-clusters get")
+            }
             OutputFormat::Text => match result {
                 None => format!(
                     "Symbol `{}` was not found in any community for repository `{}`.",
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 
 use anyhow::Result;
 
-use crate::cli::OutputFormat;
+use crate::cli::ContextOutputFormat;
 use crate::{ContextNode, SymbolContext};
 
 use super::super::Container;
@@ -21,21 +21,107 @@ impl<'a> SymbolContextController<'a> {
         &self,
         symbol: String,
         repository: Option<String>,
-        format: OutputFormat,
+        limit: Option<usize>,
+        format: ContextOutputFormat,
         is_regex: bool,
     ) -> Result<String> {
         let use_case = self.container.context_use_case();
-        let ctx = use_case
+        let mut ctx = use_case
             .get_context(&symbol, repository.as_deref(), is_regex)
             .await?;
+        if let Some(limit) = limit {
+            Self::apply_limit(&mut ctx, limit);
+        }
 
         Ok(match format {
-            OutputFormat::Json => serde_json::to_string_pretty(&ctx)?,
-            OutputFormat::Vimgrep => Self::format_vimgrep(&ctx),
-            OutputFormat::Text => Self::format_text(&ctx),
+            ContextOutputFormat::Json => serde_json::to_string_pretty(&ctx)?,
+            ContextOutputFormat::Vimgrep => Self::format_vimgrep(&ctx),
+            ContextOutputFormat::Mermaid => Self::format_mermaid(&ctx),
+            ContextOutputFormat::Text => Self::format_text(&ctx),
         })
     }
 
+    /// Keep at most `limit` callers and `limit` callees, nearest hops first.
+    ///
+    /// The totals are left untouched so that they still report how many
+    /// symbols the BFS found before truncation.
+    fn apply_limit(ctx: &mut SymbolContext, limit: usize) {
+        fn truncate(by_depth: &mut Vec<Vec<ContextNode>>, limit: usize) {
+            let mut remaining = limit;
+            for level in by_depth.iter_mut() {
+                level.truncate(remaining);
+                remaining -= level.len();
+            }
+            by_depth.retain(|level| !level.is_empty());
+        }
+        truncate(&mut ctx.callers_by_depth, limit);
+        truncate(&mut ctx.callees_by_depth, limit);
+    }
+
+    /// Render the context as a Mermaid `graph TD` flowchart.
+    ///
+    /// The queried symbol sits in the middle: caller edges point into it and
+    /// callee edges point out of it. Node ids are generated, so symbol names
+    /// only ever appear inside quoted, entity-escaped labels.
+    pub fn format_mermaid(ctx: &SymbolContext) -> String {
+        let mut out = String::from("graph TD\n");
+        let mut ids: HashMap<&str, String> = HashMap::new();
+        out.push_str(&format!("    n0[\"{}\"]\n", mermaid_escape(&ctx.symbol)));
+        ids.insert(ctx.symbol.as_str(), "n0".to_string());
+        for root in &ctx.root_symbols {
+            ids.insert(root.as_str(), "n0".to_string());
+        }
+        let mut next_id = 1;
+
+        for node in ctx
+            .callers_by_depth
+            .iter()
+            .chain(ctx.callees_by_depth.iter())
+            .flatten()
+        {
+            if !ids.contains_key(node.symbol.as_str()) {
+                let id = format!("n{}", next_id);
+                next_id += 1;
+                out.push_str(&format!(
+                    "    {}[\"{}\"]\n",
+                    id,
+                    mermaid_escape(&node.symbol)
+                ));
+                ids.insert(node.symbol.as_str(), id);
+            }
+        }
+
+        let mut seen_edges: HashSet<(String, String, String)> = HashSet::new();
+        let mut push_edge = |from: &str, to: &str, kind: &str, out: &mut String| {
+            if seen_edges.insert((from.to_string(), to.to_string(), kind.to_string())) {
+                out.push_str(&format!(
+                    "    {} -->|{}| {}\n",
+                    from,
+                    mermaid_escape(kind),
+                    to
+                ));
+            }
+        };
+        // Callers point at the symbol they reach (their BFS parent).
+        for node in ctx.callers_by_depth.iter().flatten() {
+            let target = node.via_symbol.as_deref().unwrap_or(&ctx.symbol);
+            if let (Some(from), Some(to)) = (ids.get(node.symbol.as_str()), ids.get(target)) {
+                push_edge(from, to, &node.reference_kind, &mut out);
+            }
+        }
+        // Callees are pointed at by their BFS parent.
+        for node in ctx.callees_by_depth.iter().flatten() {
+            let source = node.via_symbol.as_deref().unwrap_or(&ctx.symbol);
+            if let (Some(from), Some(to)) = (ids.get(source), ids.get(node.symbol.as_str())) {
+                push_edge(from, to, &node.reference_kind, &mut out);
+            }
+        }
+
+        out.push_str("    classDef root stroke-width:3px;\n");
+        out.push_str("    class n0 root;\n");
+        out
+    }
+
     fn format_vimgrep(ctx: &SymbolContext) -> String {
         let callers = ctx.callers_by_depth.iter().flatten().map(|n| {
             format!(
@@ -248,3 +334,76 @@ impl<'a> SymbolContextController<'a> {
         }
     }
 }
+
+/// Escape a symbol for use inside a double-quoted Mermaid label.
+///
+/// `#` starts Mermaid entity codes, so it is escaped first; quotes and angle
+/// brackets (generics) are replaced with their entity equivalents.
+fn mermaid_escape(s: &str) -> String {
+    s.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(symbol: &str, depth: usize, via: &str) -> ContextNode {
+        ContextNode {
+            symbol: symbol.to_string(),
+            depth,
+            file_path: "src/lib.rs".to_string(),
+            line: depth as u32 * 10,
+            reference_kind: "call".to_string(),
+            repository_id: "repo".to_string(),
+            import_alias: None,
+            via_symbol: Some(via.to_string()),
+        }
+    }
+
+    fn context() -> SymbolContext {
+        SymbolContext {
+            symbol: "Auth#login".to_string(),
+            root_symbols: vec!["Auth#login".to_string()],
+            callers_by_depth: vec![
+                vec![node("handler::post", 1, "Auth#login")],
+                vec![node("main", 2, "handler::post")],
+            ],
+            total_callers: 2,
+            max_caller_depth: 2,
+            callees_by_depth: vec![vec![
+                node("db.query", 1, "Auth#login"),
+                node("Token<\"jwt\">", 1, "Auth#login"),
+            ]],
+            total_callees: 2,
+            max_callee_depth: 1,
+        }
+    }
+
+    #[test]
+    fn test_mermaid_output_points_callers_in_and_callees_out() {
+        let mermaid = SymbolContextController::format_mermaid(&context());
+
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("n0[\"Auth#35;login\"]"));
+        assert!(mermaid.contains("n1[\"handler::post\"]"));
+        assert!(mermaid.contains("n1 -->|call| n0"));
+        assert!(mermaid.contains("n2 -->|call| n1"));
+        assert!(mermaid.contains("n0 -->|call| n3"));
+        assert!(mermaid.contains("n4[\"Token#lt;#quot;jwt#quot;#gt;\"]"));
+        assert!(mermaid.contains("class n0 root;"));
+    }
+
+    #[test]
+    fn test_limit_caps_each_direction() {
+        let mut ctx = context();
+        SymbolContextController::apply_limit(&mut ctx, 1);
+
+        assert_eq!(ctx.callers_by_depth.len(), 1);
+        assert_eq!(ctx.callers_by_depth[0][0].symbol, "handler::post");
+        assert_eq!(ctx.callees_by_depth.iter().flatten().count(), 1);
+        assert_eq!(ctx.total_callers, 2);
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::cli::OutputFormatTextJson;
+
+use super::super::Container;
+use super::stats_controller::format_megabytes;
+
+pub struct VacuumController<'a> {
+    container: &'a Container,
+}
+
+/// Size of the database file around a vacuum, in bytes.
+#[derive(Serialize)]
+struct VacuumReport {
+    size_before: u64,
+    size_after: u64,
+    reclaimed: u64,
+}
+
+impl<'a> VacuumController<'a> {
+    pub fn new(container: &'a Container) -> Self {
+        Self { container }
+    }
+
+    /// Compact the database file and report how much space was reclaimed.
+    pub async fn vacuum(&self, format: OutputFormatTextJson) -> Result<String> {
+        let size_before = self.container.data_file_size().unwrap_or(0);
+        self.container
+            .vector_repository()
+            .compact()
+            .await
+            .context("Failed to vacuum the database")?;
+        let size_after = self.container.data_file_size().unwrap_or(0);
+        let report = VacuumReport {
+            size_before,
+            size_after,
+            reclaimed: size_before.saturating_sub(size_after),
+        };
+
+        match format {
+            OutputFormatTextJson::Json => {
+                serde_json::to_string_pretty(&report).context("Failed to serialize report")
+            }
+            OutputFormatTextJson::Text => Ok(format!(
+                "Data file size: {} -> {} (reclaimed {})",
+                format_megabytes(report.size_before),
+                format_megabytes(report.size_after),
+                format_megabytes(report.reclaimed)
+            )),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Prometheus exposition for the MCP HTTP server's `/metrics` route.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use tracing::warn;
+
+use crate::application::metrics::DB_CHUNK_COUNT;
+
+use super::Container;
+
+/// Histogram buckets (seconds) for the search, embedding and rerank
+/// latencies: a cached ONNX query embedding takes milliseconds, a remote
+/// reranker over a large pool can take tens of seconds.
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// Install the process-wide Prometheus recorder. Call once, before serving.
+pub fn install_prometheus_recorder() -> Result<PrometheusHandle> {
+    PrometheusBuilder::new()
+        .set_buckets(DURATION_BUCKETS)
+        .context("Invalid Prometheus histogram buckets")?
+        .install_recorder()
+        .context("Failed to install Prometheus recorder")
+}
+
+/// Refresh the per-namespace chunk gauge from repository metadata and render
+/// every metric in the Prometheus text format.
+pub async fn render_metrics(container: &Container, handle: &PrometheusHandle) -> String {
+    match container.list_use_case().execute().await {
+        Ok(repos) => {
+            let mut per_namespace: BTreeMap<String, u64> = BTreeMap::new();
+            for repo in &repos {
+                let namespace = repo.namespace().unwrap_or(container.namespace());
+                *per_namespace.entry(namespace.to_string()).or_default() += repo.chunk_count();
+            }
+            for (namespace, chunks) in per_namespace {
+                metrics::gauge!(DB_CHUNK_COUNT, "namespace" => namespace).set(chunks as f64);
+            }
+        }
+        Err(e) => warn!("Failed to refresh {DB_CHUNK_COUNT}: {e}"),
+    }
+    handle.render()
+}
+
+/// Router serving [`render_metrics`] at `/metrics`, merged into the MCP HTTP
+/// server's routes.
+pub fn metrics_router(container: Arc<Container>, handle: PrometheusHandle) -> axum::Router {
+    axum::Router::new().route(
+        "/metrics",
+        axum::routing::get(move || async move { render_metrics(&container, &handle).await }),
+    )
+}
@@ -1,16 +1,20 @@
 pub mod container;
 pub mod controller;
 pub mod copilot_command;
+pub mod metrics;
 pub mod openai_command;
 pub mod repo_resolver;
 pub mod router;
+pub mod telemetry;
 
 pub use container::{Container, ContainerConfig};
-pub use controller::{run_import_picker_ui, MemoryController};
+pub use controller::{run_import_picker_ui, ImpactThresholdExceeded, MemoryController};
 pub use copilot_command::run as run_copilot_command;
+pub use metrics::{install_prometheus_recorder, metrics_router, render_metrics};
 pub use openai_command::run as run_openai_command;
 pub use repo_resolver::{
     namespace_embedding_config, resolve as resolve_repo_context, resolve_memory_project,
     ResolvedContext,
 };
 pub use router::Router;
+pub use telemetry::{otlp_layer, otlp_tracer_provider, OtlpGuard};
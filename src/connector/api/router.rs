@@ -1,28 +1,47 @@
 use anyhow::Result;
 
-use crate::cli::{ClustersSubcommand, MemorySubcommand, SymbolClustersSubcommand};
+use crate::cli::{
+    CacheSubcommand, ClustersSubcommand, MemorySubcommand, ScipSubcommand, SymbolClustersSubcommand,
+};
 use crate::{Commands, FeaturesSubcommand};
 
 use super::container::Container;
 use super::controller::{
-    ChannelsController, ClustersController, CouplingsController, DeleteController,
-    ExecutionFeaturesController, ExplainController, ImpactController, IndexController,
-    ListRepositoriesController, MemoryController, OverviewController, SearchController,
-    StatsController, SymbolClustersController, SymbolContextController, UsesController,
+    ArchiveController, CacheController, ChannelsController, ChunkController, ClustersController,
+    CouplingsController, CyclesController, DeadCodeController, DeleteController,
+    ExecutionFeaturesController, ExplainController, FilesController, GraphController,
+    HealthController, HotspotsController, ImpactController, IndexController,
+    ListRepositoriesController, MemoryController, OverviewController, PathController,
+    PruneController, RenameController, ScipController, SearchController, StatsController,
+    SymbolClustersController, SymbolContextController, UsesController, VacuumController,
     VisualizeController,
 };
 
 pub struct Router<'a> {
+    archive_controller: ArchiveController<'a>,
+    cache_controller: CacheController<'a>,
+    scip_controller: ScipController<'a>,
     channels_controller: ChannelsController<'a>,
+    chunk_controller: ChunkController<'a>,
     search_controller: SearchController<'a>,
     impact_controller: ImpactController<'a>,
     explain_controller: ExplainController<'a>,
     symbol_context_controller: SymbolContextController<'a>,
+    path_controller: PathController<'a>,
+    cycles_controller: CyclesController<'a>,
+    hotspots_controller: HotspotsController<'a>,
+    files_controller: FilesController<'a>,
+    graph_controller: GraphController<'a>,
     stats_controller: StatsController<'a>,
+    health_controller: HealthController<'a>,
     index_controller: IndexController<'a>,
     list_repositories_controller: ListRepositoriesController<'a>,
     memory_controller: MemoryController<'a>,
+    dead_code_controller: DeadCodeController<'a>,
     delete_controller: DeleteController<'a>,
+    prune_controller: PruneController<'a>,
+    rename_controller: RenameController<'a>,
+    vacuum_controller: VacuumController<'a>,
     uses_controller: UsesController<'a>,
     execution_features_controller: ExecutionFeaturesController<'a>,
     clusters_controller: ClustersController<'a>,
@@ -35,16 +54,30 @@ pub struct Router<'a> {
 impl<'a> Router<'a> {
     pub fn new(container: &'a Container) -> Self {
         Self {
+            archive_controller: ArchiveController::new(container),
             channels_controller: ChannelsController::new(container),
+            chunk_controller: ChunkController::new(container),
             search_controller: SearchController::new(container),
             impact_controller: ImpactController::new(container),
             explain_controller: ExplainController::new(container),
             symbol_context_controller: SymbolContextController::new(container),
+            path_controller: PathController::new(container),
+            cycles_controller: CyclesController::new(container),
+            hotspots_controller: HotspotsController::new(container),
+            files_controller: FilesController::new(container),
+            graph_controller: GraphController::new(container),
             stats_controller: StatsController::new(container),
+            health_controller: HealthController::new(container),
             index_controller: IndexController::new(container),
             list_repositories_controller: ListRepositoriesController::new(container),
             memory_controller: MemoryController::new(container),
+            dead_code_controller: DeadCodeController::new(container),
             delete_controller: DeleteController::new(container),
+            prune_controller: PruneController::new(container),
+            rename_controller: RenameController::new(container),
+            vacuum_controller: VacuumController::new(container),
+            cache_controller: CacheController::new(container),
+            scip_controller: ScipController::new(container),
             uses_controller: UsesController::new(container),
             execution_features_controller: ExecutionFeaturesController::new(container),
             clusters_controller: ClustersController::new(container),
@@ -57,8 +90,41 @@ impl<'a> Router<'a> {
 
     pub async fn route(&self, command: Commands) -> Result<String> {
         match command {
-            Commands::Index { path, name, force } => {
-                self.index_controller.index(path, name, force).await
+            Commands::Index {
+                paths,
+                name,
+                force,
+                include,
+                exclude,
+                watch,
+                header_language,
+                max_chunk_lines,
+                debounce_ms,
+                no_docs,
+                since,
+                jobs,
+                max_file_size,
+            } => {
+                self.index_controller
+                    .index(
+                        paths,
+                        name,
+                        force,
+                        include,
+                        exclude,
+                        watch,
+                        header_language,
+                        max_chunk_lines,
+                        debounce_ms,
+                        no_docs,
+                        since,
+                        jobs,
+                        max_file_size,
+                    )
+                    .await
+            }
+            Commands::Update { path, name, force } => {
+                self.index_controller.update(path, name, force).await
             }
             Commands::Search {
                 query,
@@ -66,8 +132,24 @@ impl<'a> Router<'a> {
                 min_score,
                 language,
                 repository,
+                node_type,
+                async_only,
+                path,
+                exclude,
+                symbol_regex,
+                diversity,
                 format,
                 text_search,
+                rrf_k,
+                semantic_weight,
+                text_weight,
+                text_candidates,
+                no_dedup,
+                group_by_file,
+                context,
+                explain,
+                symbol,
+                show_expansions,
             } => {
                 self.search_controller
                     .search(
@@ -76,32 +158,200 @@ impl<'a> Router<'a> {
                         min_score,
                         language,
                         repository,
+                        node_type,
+                        async_only,
+                        path,
+                        exclude,
+                        symbol_regex,
+                        diversity,
                         format,
                         text_search,
+                        rrf_k,
+                        semantic_weight,
+                        text_weight,
+                        text_candidates,
+                        !no_dedup,
+                        group_by_file,
+                        context,
+                        explain,
+                        symbol,
+                        show_expansions,
                     )
                     .await
             }
+            Commands::Similar {
+                location,
+                num,
+                threshold,
+                language,
+                repository,
+                node_type,
+                path,
+                format,
+            } => {
+                self.search_controller
+                    .similar(
+                        location, num, threshold, language, repository, node_type, path, format,
+                    )
+                    .await
+            }
+            Commands::Chunk {
+                file,
+                line,
+                repository,
+            } => self.chunk_controller.chunk(file, line, repository).await,
+            Commands::Files {
+                repository,
+                language,
+                sort,
+                format,
+            } => {
+                self.files_controller
+                    .files(repository, language, sort, format)
+                    .await
+            }
             Commands::List => self.list_repositories_controller.list().await,
             Commands::Delete { id_or_path } => self.delete_controller.delete(id_or_path).await,
-            Commands::Stats => self.stats_controller.stats().await,
+            Commands::Prune {
+                repository,
+                dry_run,
+                format,
+            } => {
+                self.prune_controller
+                    .prune(repository, dry_run, format)
+                    .await
+            }
+            Commands::Export { repository, out } => {
+                self.archive_controller.export(repository, out).await
+            }
+            Commands::Import { archive, path } => {
+                self.archive_controller.import(archive, path).await
+            }
+            Commands::Vacuum { format } => self.vacuum_controller.vacuum(format).await,
+            Commands::Rename {
+                old_symbol,
+                new_symbol,
+                repository,
+                dry_run,
+            } => {
+                self.rename_controller
+                    .rename(old_symbol, new_symbol, repository, dry_run)
+                    .await
+            }
+            Commands::Cache { subcommand } => match subcommand {
+                CacheSubcommand::Clear => self.cache_controller.clear().await,
+            },
+            Commands::Scip { subcommand } => match subcommand {
+                ScipSubcommand::Export { repository, out } => {
+                    self.scip_controller.export(repository, out).await
+                }
+            },
+            Commands::Stats { format } => self.stats_controller.stats(format).await,
+            Commands::Health => self.health_controller.health().await,
             Commands::Impact {
                 symbol,
                 repository,
                 format,
                 regex,
+                follow_impls,
+                kinds,
+                exclude_tests,
+                diff,
+                exit_code,
+                threshold,
             } => {
-                self.impact_controller
-                    .impact(symbol, repository, format, regex)
-                    .await
+                let gate = exit_code.then_some(threshold);
+                match (diff, symbol) {
+                    (Some(base), _) => {
+                        self.impact_controller
+                            .impact_diff(
+                                base,
+                                repository,
+                                format,
+                                follow_impls,
+                                kinds,
+                                exclude_tests,
+                                gate,
+                            )
+                            .await
+                    }
+                    (None, symbol) => {
+                        self.impact_controller
+                            .impact(
+                                symbol.unwrap_or_default(),
+                                repository,
+                                format,
+                                regex,
+                                follow_impls,
+                                kinds,
+                                exclude_tests,
+                                gate,
+                            )
+                            .await
+                    }
+                }
             }
             Commands::Context {
                 symbol,
                 repository,
+                limit,
                 format,
                 regex,
             } => {
                 self.symbol_context_controller
-                    .context(symbol, repository, format, regex)
+                    .context(symbol, repository, limit, format, regex)
+                    .await
+            }
+            Commands::Path {
+                from,
+                to,
+                depth,
+                max_paths,
+                repository,
+                format,
+            } => {
+                self.path_controller
+                    .path(from, to, depth, max_paths, repository, format)
+                    .await
+            }
+            Commands::Graph {
+                symbol,
+                depth,
+                repository,
+                format,
+            } => {
+                self.graph_controller
+                    .graph(symbol, depth, repository, format)
+                    .await
+            }
+            Commands::Cycles {
+                repository,
+                min_size,
+                format,
+            } => {
+                self.cycles_controller
+                    .cycles(repository, min_size, format)
+                    .await
+            }
+            Commands::Hotspots {
+                repository,
+                top,
+                min_fanin,
+                format,
+            } => {
+                self.hotspots_controller
+                    .hotspots(repository, top, min_fanin, format)
+                    .await
+            }
+            Commands::DeadCode {
+                repository,
+                language,
+                exclude_tests,
+                min_callers,
+                format,
+            } => {
+                self.dead_code_controller
+                    .dead_code(repository, language, exclude_tests, min_callers, format)
                     .await
             }
             Commands::Explain {
@@ -283,6 +533,9 @@ impl<'a> Router<'a> {
             Commands::Create { .. } => Err(anyhow::anyhow!(
                 "create command is handled separately in main"
             )),
+            Commands::Namespace { .. } => Err(anyhow::anyhow!(
+                "namespace command is handled separately in main"
+            )),
             Commands::Mcp { .. } => {
                 Err(anyhow::anyhow!("MCP command is handled separately in main"))
             }
@@ -0,0 +1,98 @@
+//! OpenTelemetry span export for `--otlp-endpoint`.
+
+use anyhow::Result;
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use tracing_opentelemetry::OpenTelemetryLayer;
+
+/// Path the OTLP/HTTP trace exporter posts to, appended to `--otlp-endpoint`
+/// when the user gave only the collector's base URL.
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+/// Flushes and shuts down the OTLP exporter when dropped at the end of
+/// `main`, so the last batch of spans is not lost on exit.
+pub struct OtlpGuard(pub TracerProvider);
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.0.shutdown() {
+            eprintln!("Failed to flush OpenTelemetry spans: {e}");
+        }
+    }
+}
+
+/// The traces URL for a collector endpoint: `http://localhost:4318` →
+/// `http://localhost:4318/v1/traces`; a URL already ending in the traces
+/// path is kept as is.
+fn otlp_traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with(OTLP_TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}{OTLP_TRACES_PATH}")
+    }
+}
+
+/// Build a tracer provider exporting spans over OTLP/HTTP to `endpoint`
+/// (e.g. `http://localhost:4318` for a local Jaeger or Tempo collector), or
+/// `None` when no endpoint is configured and export is off.
+pub fn otlp_tracer_provider(endpoint: Option<&str>) -> Result<Option<TracerProvider>> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    let Some(endpoint) = endpoint else {
+        return Ok(None);
+    };
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(otlp_traces_url(endpoint))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create OTLP exporter: {e}"))?;
+    Ok(Some(
+        TracerProvider::builder()
+            .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+            .with_resource(opentelemetry_sdk::Resource::new(vec![
+                opentelemetry::KeyValue::new("service.name", "codesearch"),
+                opentelemetry::KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            ]))
+            .build(),
+    ))
+}
+
+/// The `tracing` layer feeding spans to `provider`; `None` (no layer at all)
+/// when export is off.
+pub fn otlp_layer<S>(provider: Option<&TracerProvider>) -> Option<OpenTelemetryLayer<S, Tracer>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::trace::TracerProvider as _;
+
+    provider
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("codesearch")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_is_off_without_an_endpoint() {
+        let provider = otlp_tracer_provider(None).unwrap();
+        assert!(provider.is_none());
+        assert!(otlp_layer::<tracing_subscriber::Registry>(provider.as_ref()).is_none());
+    }
+
+    #[test]
+    fn traces_path_is_appended_once() {
+        assert_eq!(
+            otlp_traces_url("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            otlp_traces_url("http://localhost:4318/"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            otlp_traces_url("https://collector.example/v1/traces"),
+            "https://collector.example/v1/traces"
+        );
+    }
+}
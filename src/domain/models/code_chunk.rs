@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::Language;
+use super::{compute_file_hash, Language};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeChunk {
@@ -15,6 +15,18 @@ pub struct CodeChunk {
     symbol_name: Option<String>,
     parent_symbol: Option<String>,
     repository_id: String,
+    /// 1-based position among the pieces an oversized node was split into;
+    /// `None` for nodes indexed whole.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part: Option<u32>,
+    /// A coroutine (Kotlin `suspend fun`, Rust `async fn`), callable only
+    /// from another coroutine or an async scope.
+    #[serde(default)]
+    is_async: bool,
+    /// The documentation written for the symbol (the comment block above
+    /// it, a Python docstring), without comment markers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    doc_comment: Option<String>,
 }
 
 impl CodeChunk {
@@ -38,6 +50,9 @@ impl CodeChunk {
             symbol_name: None,
             parent_symbol: None,
             repository_id,
+            part: None,
+            is_async: false,
+            doc_comment: None,
         }
     }
 
@@ -66,6 +81,9 @@ impl CodeChunk {
             symbol_name,
             parent_symbol,
             repository_id,
+            part: None,
+            is_async: false,
+            doc_comment: None,
         }
     }
 
@@ -79,6 +97,21 @@ impl CodeChunk {
         self
     }
 
+    pub fn with_part(mut self, part: u32) -> Self {
+        self.part = Some(part);
+        self
+    }
+
+    pub fn with_async(mut self, is_async: bool) -> Self {
+        self.is_async = is_async;
+        self
+    }
+
+    pub fn with_doc_comment(mut self, doc_comment: impl Into<String>) -> Self {
+        self.doc_comment = Some(doc_comment.into());
+        self
+    }
+
     pub fn id(&self) -> &str {
         &self.id
     }
@@ -119,6 +152,18 @@ impl CodeChunk {
         &self.repository_id
     }
 
+    pub fn part(&self) -> Option<u32> {
+        self.part
+    }
+
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+
+    pub fn doc_comment(&self) -> Option<&str> {
+        self.doc_comment.as_deref()
+    }
+
     pub fn location(&self) -> String {
         format!("{}:{}-{}", self.file_path, self.start_line, self.end_line)
     }
@@ -163,12 +208,42 @@ impl CodeChunk {
         }
     }
 
+    /// Text an embedder turns into this chunk's vector: the qualified name,
+    /// then the doc comment and the content.
+    pub fn embedding_text(&self) -> String {
+        let name = self.qualified_name().unwrap_or_default();
+        match self.embedding_doc() {
+            Some(doc) => format!("{} {}\n{}", name, doc, self.content),
+            None => format!("{} {}", name, self.content),
+        }
+    }
+
+    /// The doc comment, unless the content already carries it: leading
+    /// comments and docstrings are part of a definition's (first) chunk, so
+    /// only the later parts of a split definition need it repeated.
+    pub fn embedding_doc(&self) -> Option<&str> {
+        self.doc_comment
+            .as_deref()
+            .filter(|doc| !doc.lines().all(|line| self.content.contains(line.trim())))
+    }
+
+    /// SHA-256 of what an embedder sees for this chunk (qualified name, doc
+    /// comment and content). Chunks with equal keys get equal vectors from
+    /// one model.
+    pub fn embedding_key(&self) -> String {
+        let name = self.qualified_name().unwrap_or_default();
+        compute_file_hash(&match self.embedding_doc() {
+            Some(doc) => format!("{}\n{}\n{}", name, doc, self.content),
+            None => format!("{}\n{}", name, self.content),
+        })
+    }
+
     /// Returns the symbol name in the format used by the call graph (SCIP-derived).
     ///
     /// SCIP uses language-specific separators between class and member:
-    /// - TypeScript / JavaScript / PHP: `ClassName#method`
+    /// - TypeScript / JavaScript / PHP / Java / C# / Ruby: `ClassName#method`
     /// - C++ / Rust: `ClassName::method`
-    /// - Python / Go / Kotlin: `ClassName.method`
+    /// - Python / Go / Kotlin / Scala / Elixir / Lua: `ClassName.method`
     ///
     /// Falls back to `symbol_name` when no parent is present.  The suffix
     /// resolver in `resolve_symbols` performs word-boundary matching, so a
@@ -179,8 +254,18 @@ impl CodeChunk {
         match (&self.parent_symbol, &self.symbol_name) {
             (Some(parent), Some(name)) => {
                 let sep = match self.language {
-                    Language::TypeScript | Language::JavaScript | Language::Php => "#",
-                    Language::Python | Language::Go | Language::Kotlin => ".",
+                    Language::TypeScript
+                    | Language::JavaScript
+                    | Language::Php
+                    | Language::Java
+                    | Language::CSharp
+                    | Language::Ruby => "#",
+                    Language::Python
+                    | Language::Go
+                    | Language::Kotlin
+                    | Language::Scala
+                    | Language::Elixir
+                    | Language::Lua => ".",
                     _ => "::",
                 };
                 Some(format!("{}{}{}", parent, sep, name))
@@ -196,6 +281,11 @@ impl CodeChunk {
 #[serde(rename_all = "snake_case")]
 pub enum NodeType {
     Function,
+    /// Constructors and initialisers (`constructor`, `__init__`,
+    /// `__construct`, Java/C# constructor declarations).
+    Constructor,
+    /// Properties declared with accessors (C# `{ get; set; }`).
+    Property,
     Class,
     Struct,
     Enum,
@@ -212,6 +302,8 @@ impl NodeType {
     pub fn as_str(&self) -> &'static str {
         match self {
             NodeType::Function => "function",
+            NodeType::Constructor => "constructor",
+            NodeType::Property => "property",
             NodeType::Class => "class",
             NodeType::Struct => "struct",
             NodeType::Enum => "enum",
@@ -226,8 +318,16 @@ impl NodeType {
     }
 
     pub fn parse(s: &str) -> Self {
-        match s {
+        Self::try_parse(s).unwrap_or(NodeType::Block)
+    }
+
+    /// Strict counterpart of [`parse`](Self::parse) for user input: `None`
+    /// for anything that is not one of [`all`](Self::all)'s names.
+    pub fn try_parse(s: &str) -> Option<Self> {
+        Some(match s {
             "function" => NodeType::Function,
+            "constructor" => NodeType::Constructor,
+            "property" => NodeType::Property,
             "class" => NodeType::Class,
             "struct" => NodeType::Struct,
             "enum" => NodeType::Enum,
@@ -237,8 +337,27 @@ impl NodeType {
             "constant" => NodeType::Constant,
             "typedef" => NodeType::TypeDef,
             "interface" => NodeType::Interface,
-            _ => NodeType::Block,
-        }
+            "block" => NodeType::Block,
+            _ => return None,
+        })
+    }
+
+    pub fn all() -> Vec<NodeType> {
+        vec![
+            NodeType::Function,
+            NodeType::Constructor,
+            NodeType::Property,
+            NodeType::Class,
+            NodeType::Struct,
+            NodeType::Enum,
+            NodeType::Trait,
+            NodeType::Impl,
+            NodeType::Module,
+            NodeType::Constant,
+            NodeType::TypeDef,
+            NodeType::Interface,
+            NodeType::Block,
+        ]
     }
 }
 
@@ -272,6 +391,15 @@ mod tests {
         assert!(!chunk.is_type_definition());
     }
 
+    #[test]
+    fn test_node_type_try_parse_round_trips_every_variant() {
+        for node_type in NodeType::all() {
+            assert_eq!(NodeType::try_parse(node_type.as_str()), Some(node_type));
+        }
+        assert_eq!(NodeType::try_parse("fn"), None);
+        assert_eq!(NodeType::parse("fn"), NodeType::Block);
+    }
+
     #[test]
     fn test_qualified_name() {
         let chunk = CodeChunk::new(
@@ -289,6 +417,41 @@ mod tests {
         assert_eq!(chunk.qualified_name(), Some("MyStruct::method".to_string()));
     }
 
+    #[test]
+    fn test_embedding_text_repeats_doc_only_where_content_lacks_it() {
+        let chunk = |content: &str| {
+            CodeChunk::new(
+                "src/lib.rs".to_string(),
+                content.to_string(),
+                1,
+                3,
+                Language::Rust,
+                NodeType::Function,
+                "repo".to_string(),
+            )
+            .with_symbol_name("add")
+            .with_doc_comment("Adds two numbers.")
+        };
+
+        let whole = chunk("/// Adds two numbers.\nfn add() {}");
+        assert_eq!(
+            whole.embedding_text(),
+            "add /// Adds two numbers.\nfn add() {}"
+        );
+
+        let later_part = chunk("    a + b\n}");
+        assert_eq!(
+            later_part.embedding_text(),
+            "add Adds two numbers.\n    a + b\n}"
+        );
+        assert_ne!(
+            later_part.embedding_key(),
+            chunk("    a + b\n}")
+                .with_doc_comment("Subtracts.")
+                .embedding_key()
+        );
+    }
+
     #[test]
     fn test_location_format() {
         let chunk = CodeChunk::new(
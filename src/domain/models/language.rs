@@ -11,9 +11,20 @@ pub enum Language {
     Go,
     HCL,
     Php,
+    C,
     Cpp,
     Swift,
     Kotlin,
+    Java,
+    CSharp,
+    Elixir,
+    Lua,
+    Ruby,
+    Bash,
+    Scala,
+    /// Markdown and plain-text documentation, chunked by heading (plain
+    /// text by fixed-size window) rather than parsed with tree-sitter.
+    Markdown,
     Unknown,
 }
 
@@ -27,20 +38,62 @@ impl Language {
             "go" => Language::Go,
             "hcl" | "tf" => Language::HCL,
             "php" => Language::Php,
-            "cpp" | "cc" | "cxx" | "c" | "h" | "hpp" => Language::Cpp,
+            "c" | "h" => Language::C,
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Language::Cpp,
             "swift" => Language::Swift,
             "kt" | "kts" => Language::Kotlin,
+            "java" => Language::Java,
+            "cs" | "csx" => Language::CSharp,
+            "ex" | "exs" => Language::Elixir,
+            "lua" | "luau" => Language::Lua,
+            "rb" | "rake" | "gemspec" => Language::Ruby,
+            "sh" | "bash" => Language::Bash,
+            "scala" | "sc" => Language::Scala,
+            "md" | "mdx" | "markdown" | "txt" => Language::Markdown,
             _ => Language::Unknown,
         }
     }
 
     pub fn from_path(path: &Path) -> Self {
+        // Extension-less files recognised by their conventional name.
+        if let Some("Gemfile" | "Rakefile") = path.file_name().and_then(|n| n.to_str()) {
+            return Language::Ruby;
+        }
         path.extension()
             .and_then(|ext| ext.to_str())
             .map(Self::from_extension)
             .unwrap_or(Language::Unknown)
     }
 
+    /// Detect a script language from its first line (`#!/bin/bash`,
+    /// `#!/usr/bin/env sh`, ...).  Returns `Unknown` when there is no
+    /// shebang or the interpreter is not a supported one.
+    pub fn from_shebang(first_line: &str) -> Self {
+        let Some(command) = first_line.strip_prefix("#!") else {
+            return Language::Unknown;
+        };
+        let mut words = command.split_whitespace();
+        let mut interpreter = words.next().unwrap_or("");
+        if interpreter.rsplit('/').next() == Some("env") {
+            // Skip `env` flags such as `-S`.
+            interpreter = words.find(|w| !w.starts_with('-')).unwrap_or("");
+        }
+        match interpreter.rsplit('/').next().unwrap_or("") {
+            "bash" | "sh" | "dash" | "ksh" => Language::Bash,
+            _ => Language::Unknown,
+        }
+    }
+
+    /// [`from_path`](Self::from_path), falling back to the shebang in
+    /// `content` for extension-less files such as `bin/deploy`.
+    pub fn from_path_and_content(path: &Path, content: &str) -> Self {
+        let language = Self::from_path(path);
+        if language != Language::Unknown || path.extension().is_some() {
+            return language;
+        }
+        Self::from_shebang(content.lines().next().unwrap_or(""))
+    }
+
     pub fn parse(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "rust" => Language::Rust,
@@ -50,9 +103,18 @@ impl Language {
             "go" => Language::Go,
             "hcl" => Language::HCL,
             "php" => Language::Php,
+            "c" => Language::C,
             "cpp" | "c++" => Language::Cpp,
             "swift" => Language::Swift,
             "kotlin" => Language::Kotlin,
+            "java" => Language::Java,
+            "csharp" | "c#" => Language::CSharp,
+            "elixir" => Language::Elixir,
+            "lua" | "luau" => Language::Lua,
+            "ruby" => Language::Ruby,
+            "bash" | "sh" | "shell" => Language::Bash,
+            "scala" => Language::Scala,
+            "markdown" | "md" => Language::Markdown,
             _ => Language::Unknown,
         }
     }
@@ -66,9 +128,18 @@ impl Language {
             Language::Go => "go",
             Language::HCL => "hcl",
             Language::Php => "php",
+            Language::C => "c",
             Language::Cpp => "cpp",
             Language::Swift => "swift",
             Language::Kotlin => "kotlin",
+            Language::Java => "java",
+            Language::CSharp => "csharp",
+            Language::Elixir => "elixir",
+            Language::Lua => "lua",
+            Language::Ruby => "ruby",
+            Language::Bash => "bash",
+            Language::Scala => "scala",
+            Language::Markdown => "markdown",
             Language::Unknown => "unknown",
         }
     }
@@ -86,9 +157,18 @@ impl Language {
             Language::Go => &["go"],
             Language::HCL => &["hcl", "tf"],
             Language::Php => &["php"],
-            Language::Cpp => &["cpp", "cc", "cxx", "c", "h", "hpp"],
+            Language::C => &["c", "h"],
+            Language::Cpp => &["cpp", "cc", "cxx", "hpp", "hh", "hxx"],
             Language::Swift => &["swift"],
             Language::Kotlin => &["kt", "kts"],
+            Language::Java => &["java"],
+            Language::CSharp => &["cs", "csx"],
+            Language::Elixir => &["ex", "exs"],
+            Language::Lua => &["lua", "luau"],
+            Language::Ruby => &["rb", "rake", "gemspec"],
+            Language::Bash => &["sh", "bash"],
+            Language::Scala => &["scala", "sc"],
+            Language::Markdown => &["md", "mdx", "markdown", "txt"],
             Language::Unknown => &[],
         }
     }
@@ -102,9 +182,13 @@ impl Language {
                 | Language::Go
                 | Language::HCL
                 | Language::Php
+                | Language::C
                 | Language::Cpp
                 | Language::Swift
                 | Language::Kotlin
+                | Language::Java
+                | Language::CSharp
+                | Language::Scala
         )
     }
 
@@ -115,9 +199,13 @@ impl Language {
                 | Language::TypeScript
                 | Language::Go
                 | Language::Php
+                | Language::C
                 | Language::Cpp
                 | Language::Swift
                 | Language::Kotlin
+                | Language::Java
+                | Language::CSharp
+                | Language::Scala
         )
     }
 
@@ -130,9 +218,18 @@ impl Language {
             Language::Go,
             Language::HCL,
             Language::Php,
+            Language::C,
             Language::Cpp,
             Language::Swift,
             Language::Kotlin,
+            Language::Java,
+            Language::CSharp,
+            Language::Elixir,
+            Language::Lua,
+            Language::Ruby,
+            Language::Bash,
+            Language::Scala,
+            Language::Markdown,
         ]
     }
 }
@@ -156,9 +253,26 @@ mod tests {
         assert_eq!(Language::from_extension("go"), Language::Go);
         assert_eq!(Language::from_extension("hcl"), Language::HCL);
         assert_eq!(Language::from_extension("php"), Language::Php);
+        assert_eq!(Language::from_extension("c"), Language::C);
+        assert_eq!(Language::from_extension("h"), Language::C);
+        assert_eq!(Language::from_extension("cpp"), Language::Cpp);
+        assert_eq!(Language::from_extension("hpp"), Language::Cpp);
         assert_eq!(Language::from_extension("kt"), Language::Kotlin);
         assert_eq!(Language::from_extension("kts"), Language::Kotlin);
-        assert_eq!(Language::from_extension("txt"), Language::Unknown);
+        assert_eq!(Language::from_extension("java"), Language::Java);
+        assert_eq!(Language::from_extension("cs"), Language::CSharp);
+        assert_eq!(Language::from_extension("csx"), Language::CSharp);
+        assert_eq!(Language::from_extension("ex"), Language::Elixir);
+        assert_eq!(Language::from_extension("exs"), Language::Elixir);
+        assert_eq!(Language::from_extension("lua"), Language::Lua);
+        assert_eq!(Language::from_extension("luau"), Language::Lua);
+        assert_eq!(Language::from_extension("rb"), Language::Ruby);
+        assert_eq!(Language::from_extension("sh"), Language::Bash);
+        assert_eq!(Language::from_extension("md"), Language::Markdown);
+        assert_eq!(Language::from_extension("txt"), Language::Markdown);
+        assert_eq!(Language::from_extension("bash"), Language::Bash);
+        assert_eq!(Language::from_extension("scala"), Language::Scala);
+        assert_eq!(Language::from_extension("sc"), Language::Scala);
     }
 
     #[test]
@@ -171,12 +285,51 @@ mod tests {
             Language::from_path(Path::new("script.py")),
             Language::Python
         );
+        assert_eq!(Language::from_path(Path::new("Gemfile")), Language::Ruby);
+        assert_eq!(
+            Language::from_path(Path::new("tasks/Rakefile")),
+            Language::Ruby
+        );
+    }
+
+    #[test]
+    fn test_language_from_shebang() {
+        assert_eq!(Language::from_shebang("#!/bin/bash"), Language::Bash);
+        assert_eq!(Language::from_shebang("#!/bin/sh -e"), Language::Bash);
+        assert_eq!(
+            Language::from_shebang("#!/usr/bin/env bash"),
+            Language::Bash
+        );
+        assert_eq!(
+            Language::from_shebang("#!/usr/bin/env python3"),
+            Language::Unknown
+        );
+        assert_eq!(Language::from_shebang("echo hi"), Language::Unknown);
+    }
+
+    #[test]
+    fn test_language_from_path_and_content() {
+        let script = "#!/usr/bin/env bash\necho deploying\n";
+        assert_eq!(
+            Language::from_path_and_content(Path::new("bin/deploy"), script),
+            Language::Bash
+        );
+        // Extensions win over the shebang.
+        assert_eq!(
+            Language::from_path_and_content(Path::new("notes.txt"), script),
+            Language::Markdown
+        );
+        assert_eq!(
+            Language::from_path_and_content(Path::new("bin/tool"), "plain text"),
+            Language::Unknown
+        );
     }
 
     #[test]
     fn test_language_from_str() {
         assert_eq!(Language::parse("rust"), Language::Rust);
         assert_eq!(Language::parse("PYTHON"), Language::Python);
+        assert_eq!(Language::parse("c#"), Language::CSharp);
         assert_eq!(Language::parse("unknown_lang"), Language::Unknown);
     }
 
@@ -203,7 +356,15 @@ mod tests {
         assert!(supported.contains(&Language::Python));
         assert!(supported.contains(&Language::HCL));
         assert!(supported.contains(&Language::Php));
+        assert!(supported.contains(&Language::C));
         assert!(supported.contains(&Language::Kotlin));
+        assert!(supported.contains(&Language::Java));
+        assert!(supported.contains(&Language::CSharp));
+        assert!(supported.contains(&Language::Elixir));
+        assert!(supported.contains(&Language::Lua));
+        assert!(supported.contains(&Language::Bash));
+        assert!(supported.contains(&Language::Scala));
+        assert!(supported.contains(&Language::Markdown));
         assert!(!supported.contains(&Language::Unknown));
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use tracing::warn;
@@ -11,6 +12,7 @@ pub enum VectorStore {
     #[default]
     DuckDb,
     InMemory,
+    Postgres,
 }
 
 impl VectorStore {
@@ -18,6 +20,7 @@ impl VectorStore {
         match self {
             VectorStore::DuckDb => "duckdb",
             VectorStore::InMemory => "memory",
+            VectorStore::Postgres => "postgres",
         }
     }
 }
@@ -29,6 +32,7 @@ impl std::str::FromStr for VectorStore {
         Ok(match s.to_lowercase().as_str() {
             "duckdb" => VectorStore::DuckDb,
             "memory" | "inmemory" | "in_memory" => VectorStore::InMemory,
+            "postgres" | "pgvector" => VectorStore::Postgres,
             unknown => {
                 warn!(
                     "Unknown vector store type '{}', defaulting to DuckDB",
@@ -56,6 +60,29 @@ impl LanguageStats {
     }
 }
 
+/// User-supplied include/exclude globs applied to the indexing file walk,
+/// on top of `.gitignore` filtering. Persisted on the repository so that
+/// re-indexing walks the same set of files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IndexFilters {
+    /// When non-empty, only files matching at least one of these globs are indexed.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Files matching any of these globs are skipped, even if included.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl IndexFilters {
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Self {
+        Self { include, exclude }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
     id: String,
@@ -65,9 +92,9 @@ pub struct Repository {
     updated_at: i64,
     chunk_count: u64,
     file_count: u64,
-    /// Vector store backend (duckdb, chromadb, memory).
+    /// Vector store backend (duckdb, memory, postgres).
     store: VectorStore,
-    /// Namespace for vector storage (DuckDB schema or ChromaDB collection).
+    /// Namespace for vector storage (DuckDB or Postgres schema).
     namespace: Option<String>,
     /// Normalised git remote (e.g. `github.com/owner/repo`), when the indexed
     /// path is a git repository. Used as a stable, portable key to resolve which
@@ -77,6 +104,18 @@ pub struct Repository {
     /// Language statistics (language name -> stats).
     #[serde(default)]
     languages: HashMap<String, LanguageStats>,
+    /// Include/exclude globs used when walking the repository.
+    #[serde(default)]
+    index_filters: IndexFilters,
+    /// Git commit the index was last brought up to date with; the default
+    /// base for the next `index --since`.
+    #[serde(default)]
+    indexed_commit: Option<String>,
+    /// Directories indexed under `path`, relative to it, when the repository
+    /// spans several sibling roots (`path` is then their common parent).
+    /// Empty means the whole of `path`.
+    #[serde(default)]
+    roots: Vec<String>,
 }
 
 impl Repository {
@@ -94,6 +133,9 @@ impl Repository {
             namespace: None,
             git_remote: None,
             languages: HashMap::new(),
+            index_filters: IndexFilters::default(),
+            indexed_commit: None,
+            roots: Vec::new(),
         }
     }
 
@@ -117,6 +159,9 @@ impl Repository {
             namespace,
             git_remote,
             languages: HashMap::new(),
+            index_filters: IndexFilters::default(),
+            indexed_commit: None,
+            roots: Vec::new(),
         }
     }
 
@@ -134,6 +179,7 @@ impl Repository {
         namespace: Option<String>,
         git_remote: Option<String>,
         languages: HashMap<String, LanguageStats>,
+        index_filters: IndexFilters,
     ) -> Self {
         Self {
             id,
@@ -147,6 +193,9 @@ impl Repository {
             namespace,
             git_remote,
             languages,
+            index_filters,
+            indexed_commit: None,
+            roots: Vec::new(),
         }
     }
 
@@ -194,6 +243,40 @@ impl Repository {
         self.git_remote = git_remote;
     }
 
+    pub fn index_filters(&self) -> &IndexFilters {
+        &self.index_filters
+    }
+
+    pub fn set_index_filters(&mut self, index_filters: IndexFilters) {
+        self.index_filters = index_filters;
+    }
+
+    pub fn indexed_commit(&self) -> Option<&str> {
+        self.indexed_commit.as_deref()
+    }
+
+    pub fn set_indexed_commit(&mut self, indexed_commit: Option<String>) {
+        self.indexed_commit = indexed_commit;
+    }
+
+    pub fn roots(&self) -> &[String] {
+        &self.roots
+    }
+
+    pub fn set_roots(&mut self, roots: Vec<String>) {
+        self.roots = roots;
+    }
+
+    /// Absolute directories walked when indexing: each root under `path`, or
+    /// `path` itself for a single-root repository.
+    pub fn root_paths(&self) -> Vec<PathBuf> {
+        let base = Path::new(&self.path);
+        if self.roots.is_empty() {
+            return vec![base.to_path_buf()];
+        }
+        self.roots.iter().map(|root| base.join(root)).collect()
+    }
+
     pub fn languages(&self) -> &HashMap<String, LanguageStats> {
         &self.languages
     }
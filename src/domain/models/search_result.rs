@@ -1,12 +1,110 @@
 use serde::{Deserialize, Serialize};
 
 use super::CodeChunk;
+use crate::domain::DomainError;
+
+/// A result's position and raw score in one retrieval leg.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LegScore {
+    /// 1-based rank within the leg.
+    pub rank: usize,
+    pub score: f32,
+}
+
+/// How a result's score came about, recorded for `search --explain`: its
+/// place in each retrieval leg, the fused RRF score and the reranker score.
+/// Stages that did not run (or did not see the result) are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    /// Semantic (vector) leg; the score is the cosine similarity.
+    pub semantic: Option<LegScore>,
+    /// Keyword leg; the score is the BM25 score.
+    pub text: Option<LegScore>,
+    /// Score after fusing the legs with RRF.
+    pub rrf: Option<f32>,
+    /// Cross-encoder score, when reranking ran.
+    pub rerank: Option<f32>,
+}
+
+/// A retrieval leg that found a result, with what it matched on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "leg", rename_all = "snake_case")]
+pub enum SearchLeg {
+    /// Vector similarity; the score is the cosine similarity.
+    Semantic { score: f32 },
+    /// Keyword match; `terms` are the query terms the chunk contains.
+    Text { bm25_score: f32, terms: Vec<String> },
+}
+
+impl SearchLeg {
+    /// A keyword-leg hit on `chunk` for `query`, with the query terms found
+    /// in the chunk's content or symbol name.
+    pub fn text(bm25_score: f32, query: &str, chunk: &CodeChunk) -> Self {
+        let mut haystack: Vec<String> = terms(chunk.content()).collect();
+        haystack.extend(chunk.symbol_name().into_iter().flat_map(terms));
+        let mut matched: Vec<String> = Vec::new();
+        for term in terms(query) {
+            if haystack.contains(&term) && !matched.contains(&term) {
+                matched.push(term);
+            }
+        }
+        SearchLeg::Text {
+            bm25_score,
+            terms: matched,
+        }
+    }
+
+    /// Fold another hit in the same leg into this one (the same chunk found
+    /// again for another query variant): the best score, every term.
+    fn merge(&mut self, other: &SearchLeg) {
+        match (self, other) {
+            (SearchLeg::Semantic { score }, SearchLeg::Semantic { score: other }) => {
+                *score = score.max(*other);
+            }
+            (
+                SearchLeg::Text { bm25_score, terms },
+                SearchLeg::Text {
+                    bm25_score: other_score,
+                    terms: other_terms,
+                },
+            ) => {
+                *bm25_score = bm25_score.max(*other_score);
+                for term in other_terms {
+                    if !terms.contains(term) {
+                        terms.push(term.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Lower-cased alphanumeric runs of `text`, the way the keyword index
+/// tokenizes: `authenticate_user` yields `authenticate` and `user`.
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     chunk: CodeChunk,
     score: f32,
     highlights: Option<Vec<String>>,
+    /// Lower-ranked copies of this chunk folded into it by search dedup.
+    #[serde(default)]
+    duplicates: usize,
+    /// Score provenance, only recorded when the query asks for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    explanation: Option<ScoreExplanation>,
+    /// Query terms matched by the keyword leg; empty for semantic-only hits.
+    #[serde(default)]
+    matched_terms: Vec<String>,
+    /// The retrieval legs that found this result, at most one of each kind.
+    #[serde(default)]
+    search_legs: Vec<SearchLeg>,
 }
 
 impl SearchResult {
@@ -15,9 +113,63 @@ impl SearchResult {
             chunk,
             score,
             highlights: None,
+            duplicates: 0,
+            explanation: None,
+            matched_terms: Vec::new(),
+            search_legs: Vec::new(),
         }
     }
 
+    /// Record that `leg` found this result. A leg of a kind already recorded
+    /// is merged into it; keyword terms are added to `matched_terms`.
+    pub fn with_search_leg(mut self, leg: SearchLeg) -> Self {
+        if let SearchLeg::Text { terms, .. } = &leg {
+            for term in terms {
+                if !self.matched_terms.contains(term) {
+                    self.matched_terms.push(term.clone());
+                }
+            }
+        }
+        let same_kind = self
+            .search_legs
+            .iter_mut()
+            .find(|l| std::mem::discriminant(&**l) == std::mem::discriminant(&leg));
+        match same_kind {
+            Some(existing) => existing.merge(&leg),
+            None => self.search_legs.push(leg),
+        }
+        self
+    }
+
+    /// [`with_search_leg`](Self::with_search_leg) for each of `legs`.
+    pub fn with_search_legs(self, legs: impl IntoIterator<Item = SearchLeg>) -> Self {
+        legs.into_iter()
+            .fold(self, |result, leg| result.with_search_leg(leg))
+    }
+
+    /// Tag this result as a semantic-leg hit, with its current score.
+    pub fn with_semantic_leg(self) -> Self {
+        let score = self.score;
+        self.with_search_leg(SearchLeg::Semantic { score })
+    }
+
+    /// Tag this result as a keyword-leg hit for `query`, with its current
+    /// score as the BM25 score.
+    pub fn with_text_leg(self, query: &str) -> Self {
+        let leg = SearchLeg::text(self.score, query, &self.chunk);
+        self.with_search_leg(leg)
+    }
+
+    pub fn with_explanation(mut self, explanation: ScoreExplanation) -> Self {
+        self.explanation = Some(explanation);
+        self
+    }
+
+    pub fn with_duplicates(mut self, duplicates: usize) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
     pub fn with_highlights(mut self, highlights: Vec<String>) -> Self {
         self.highlights = Some(highlights);
         self
@@ -35,6 +187,22 @@ impl SearchResult {
         self.highlights.as_deref()
     }
 
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    pub fn explanation(&self) -> Option<&ScoreExplanation> {
+        self.explanation.as_ref()
+    }
+
+    pub fn matched_terms(&self) -> &[String] {
+        &self.matched_terms
+    }
+
+    pub fn search_legs(&self) -> &[SearchLeg] {
+        &self.search_legs
+    }
+
     pub fn is_relevant(&self, threshold: f32) -> bool {
         self.score >= threshold
     }
@@ -48,6 +216,158 @@ impl SearchResult {
     }
 }
 
+/// Position just after a result, for keyset pagination over results ordered
+/// by descending score, then ascending chunk id.
+///
+/// Encoded as `<score bits in hex>:<chunk id>`, so the score survives the
+/// round trip exactly and ties are broken the same way on every page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchCursor {
+    score: f32,
+    chunk_id: String,
+}
+
+impl SearchCursor {
+    pub fn after(result: &SearchResult) -> Self {
+        Self {
+            score: result.score(),
+            chunk_id: result.chunk().id().to_string(),
+        }
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::invalid_input(format!("Invalid search cursor '{cursor}'"));
+        let (bits, chunk_id) = cursor.split_once(':').ok_or_else(invalid)?;
+        let bits = u32::from_str_radix(bits, 16).map_err(|_| invalid())?;
+        Ok(Self {
+            score: f32::from_bits(bits),
+            chunk_id: chunk_id.to_string(),
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{:08x}:{}", self.score.to_bits(), self.chunk_id)
+    }
+
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    pub fn chunk_id(&self) -> &str {
+        &self.chunk_id
+    }
+
+    /// Whether a result with `score` and `chunk_id` belongs after the cursor.
+    pub fn is_before(&self, score: f32, chunk_id: &str) -> bool {
+        score < self.score || (score == self.score && chunk_id > self.chunk_id.as_str())
+    }
+}
+
+/// Smallest keyword (BM25) candidate pool fetched for a hybrid search.
+pub const MIN_TEXT_CANDIDATES: usize = 30;
+
+/// Keyword candidates fetched per requested result, so the keyword leg can
+/// still contribute exact matches when many results are asked for.
+pub const TEXT_CANDIDATES_PER_RESULT: usize = 3;
+
+/// A symbol-name regex, compiled once and checked on construction and on
+/// deserialisation.
+///
+/// The pattern is evaluated in process by the `regex` crate, by DuckDB (RE2)
+/// and by PostgreSQL (POSIX), so only syntax the three read the same way is
+/// accepted: literals, `.`, bracket expressions (including `[[:alpha:]]`),
+/// `^`/`$`, `|`, groups and `(?:…)`, the quantifiers `* + ? {n,m}` and the
+/// escapes `\d \D \w \W \s \S` or a backslash before punctuation. Flags
+/// such as `(?i)`, named groups, `\b`, `\A`/`\z` and `\p{…}` are rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SymbolPattern {
+    source: String,
+    regex: regex::Regex,
+}
+
+impl SymbolPattern {
+    pub fn new(pattern: &str) -> Result<Self, DomainError> {
+        let invalid = |reason: String| {
+            DomainError::invalid_input(format!("Invalid symbol regex '{pattern}': {reason}"))
+        };
+        if let Some(construct) = non_portable_construct(pattern) {
+            return Err(invalid(format!("'{construct}' is not supported")));
+        }
+        let regex = regex::Regex::new(pattern).map_err(|e| invalid(e.to_string()))?;
+        Ok(Self {
+            source: pattern.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_match(&self, symbol: &str) -> bool {
+        self.regex.is_match(symbol)
+    }
+}
+
+impl TryFrom<String> for SymbolPattern {
+    type Error = DomainError;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        Self::new(&pattern)
+    }
+}
+
+impl From<SymbolPattern> for String {
+    fn from(pattern: SymbolPattern) -> Self {
+        pattern.source
+    }
+}
+
+/// First construct in `pattern` outside the portable subset documented on
+/// [`SymbolPattern`], if any.
+fn non_portable_construct(pattern: &str) -> Option<String> {
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped = chars.next()?;
+                if escaped.is_alphanumeric() && !"dDwWsS".contains(escaped) {
+                    return Some(format!("\\{escaped}"));
+                }
+            }
+            '[' if !in_class => {
+                in_class = true;
+                // A `]` right after `[` or `[^` is a literal member.
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                }
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                }
+            }
+            '[' if chars.peek() == Some(&':') => {
+                // POSIX class such as `[:alpha:]` inside a bracket expression.
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                }
+            }
+            ']' if in_class => in_class = false,
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                chars.next();
+                if chars.peek() != Some(&':') {
+                    return Some("(?".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchQuery {
     query: String,
@@ -56,7 +376,41 @@ pub struct SearchQuery {
     languages: Option<Vec<String>>,
     repository_ids: Option<Vec<String>>,
     node_types: Option<Vec<String>>,
+    /// Only return coroutine chunks (see [`CodeChunk::is_async`]).
+    #[serde(default)]
+    async_only: bool,
+    /// Globs over the chunk's file path, e.g. `src/auth/**`; a leading `!`
+    /// excludes matching paths instead.
+    path_globs: Vec<String>,
+    /// Regex the chunk's symbol name must match.
+    #[serde(default)]
+    symbol_pattern: Option<SymbolPattern>,
     text_search: bool,
+    /// RRF smoothing constant for hybrid fusion; `None` uses the default.
+    #[serde(default)]
+    rrf_k: Option<f32>,
+    /// Weight of the semantic leg in hybrid fusion.
+    #[serde(default = "unit_weight")]
+    semantic_weight: f32,
+    /// Weight of the keyword (BM25) leg in hybrid fusion.
+    #[serde(default = "unit_weight")]
+    text_weight: f32,
+    /// Keyword candidates to fetch; `None` scales with the limit.
+    #[serde(default)]
+    text_candidates: Option<usize>,
+    /// Record per-stage score provenance on every result.
+    #[serde(default)]
+    explain: bool,
+    /// Pagination was requested (see [`SearchQuery::with_cursor`]).
+    #[serde(default)]
+    paginated: bool,
+    /// Encoded [`SearchCursor`] the page starts after; `None` is the first page.
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
+fn unit_weight() -> f32 {
+    1.0
 }
 
 impl SearchQuery {
@@ -68,7 +422,17 @@ impl SearchQuery {
             languages: None,
             repository_ids: None,
             node_types: None,
+            async_only: false,
+            path_globs: Vec::new(),
+            symbol_pattern: None,
             text_search: false,
+            rrf_k: None,
+            semantic_weight: 1.0,
+            text_weight: 1.0,
+            text_candidates: None,
+            explain: false,
+            paginated: false,
+            cursor: None,
         }
     }
 
@@ -93,16 +457,83 @@ impl SearchQuery {
         self
     }
 
+    /// Restrict to these node types. Names are trimmed and lowercased to
+    /// match the stored form (`NodeType::as_str`).
     pub fn with_node_types(mut self, types: Vec<String>) -> Self {
-        self.node_types = Some(types);
+        self.node_types = Some(types.iter().map(|t| t.trim().to_lowercase()).collect());
+        self
+    }
+
+    pub fn with_async_only(mut self, async_only: bool) -> Self {
+        self.async_only = async_only;
+        self
+    }
+
+    /// Add a file-path glob (`*` and `?` as in `glob::Pattern`; `*` also
+    /// crosses `/`). Results must match at least one plain glob, if any, and
+    /// none of the `!`-prefixed ones.
+    pub fn with_path_glob(mut self, glob: &str) -> Self {
+        self.path_globs.push(glob.to_string());
         self
     }
 
+    /// Drop results whose file path matches any of `patterns`, e.g.
+    /// `vendor/**`; shorthand for `!`-prefixed [`Self::with_path_glob`]s.
+    pub fn with_exclude_paths(mut self, patterns: Vec<String>) -> Self {
+        self.path_globs
+            .extend(patterns.into_iter().map(|glob| format!("!{glob}")));
+        self
+    }
+
+    /// Only return chunks whose symbol name matches `regex` (unanchored, so
+    /// `^handle_` for a prefix); chunks without a symbol name never match.
+    /// See [`SymbolPattern`] for the supported syntax.
+    pub fn with_symbol_pattern(mut self, regex: &str) -> Result<Self, DomainError> {
+        self.symbol_pattern = Some(SymbolPattern::new(regex)?);
+        Ok(self)
+    }
+
     pub fn with_text_search(mut self, enabled: bool) -> Self {
         self.text_search = enabled;
         self
     }
 
+    pub fn with_rrf_k(mut self, k: f32) -> Self {
+        self.rrf_k = Some(k);
+        self
+    }
+
+    /// Weight the semantic and keyword legs of hybrid fusion: each hit scores
+    /// `weight / (k + rank)` per leg it appears in.
+    pub fn with_leg_weights(mut self, semantic: f32, text: f32) -> Self {
+        self.semantic_weight = semantic;
+        self.text_weight = text;
+        self
+    }
+
+    /// Fetch exactly `n` keyword (BM25) candidates instead of the default
+    /// pool of `max(limit × 3, 30)`.
+    pub fn with_text_candidates(mut self, n: usize) -> Self {
+        self.text_candidates = Some(n.max(1));
+        self
+    }
+
+    /// Record on each result how its score came about (see
+    /// [`ScoreExplanation`]).
+    pub fn with_explain(mut self, enabled: bool) -> Self {
+        self.explain = enabled;
+        self
+    }
+
+    /// Page through results: `None` asks for the first page, a cursor from
+    /// a previous page for the results after it. Paginated searches rank by
+    /// semantic similarity alone so every page continues the same order.
+    pub fn with_cursor(mut self, cursor: Option<String>) -> Self {
+        self.paginated = true;
+        self.cursor = cursor;
+        self
+    }
+
     pub fn query(&self) -> &str {
         &self.query
     }
@@ -127,12 +558,73 @@ impl SearchQuery {
         self.node_types.as_deref()
     }
 
+    pub fn is_async_only(&self) -> bool {
+        self.async_only
+    }
+
+    pub fn path_globs(&self) -> &[String] {
+        &self.path_globs
+    }
+
+    /// The path globs split into `(included, excluded)`, with the `!` of
+    /// excluded ones stripped.
+    pub fn split_path_globs(&self) -> (Vec<&str>, Vec<&str>) {
+        let mut included = Vec::new();
+        let mut excluded = Vec::new();
+        for glob in &self.path_globs {
+            match glob.strip_prefix('!') {
+                Some(glob) => excluded.push(glob),
+                None => included.push(glob.as_str()),
+            }
+        }
+        (included, excluded)
+    }
+
+    pub fn symbol_pattern(&self) -> Option<&str> {
+        self.symbol_pattern.as_ref().map(SymbolPattern::as_str)
+    }
+
     pub fn is_text_search(&self) -> bool {
         self.text_search
     }
 
+    pub fn rrf_k(&self) -> Option<f32> {
+        self.rrf_k
+    }
+
+    pub fn semantic_weight(&self) -> f32 {
+        self.semantic_weight
+    }
+
+    pub fn text_weight(&self) -> f32 {
+        self.text_weight
+    }
+
+    /// Number of keyword (BM25) candidates to fetch before fusion.
+    pub fn text_candidates(&self) -> usize {
+        self.text_candidates
+            .unwrap_or_else(|| (self.limit * TEXT_CANDIDATES_PER_RESULT).max(MIN_TEXT_CANDIDATES))
+    }
+
+    pub fn explain(&self) -> bool {
+        self.explain
+    }
+
+    pub fn is_paginated(&self) -> bool {
+        self.paginated
+    }
+
+    /// The decoded cursor this page starts after, if any.
+    pub fn cursor(&self) -> Result<Option<SearchCursor>, DomainError> {
+        self.cursor.as_deref().map(SearchCursor::decode).transpose()
+    }
+
     pub fn has_filters(&self) -> bool {
-        self.languages.is_some() || self.repository_ids.is_some() || self.node_types.is_some()
+        self.languages.is_some()
+            || self.repository_ids.is_some()
+            || self.node_types.is_some()
+            || !self.path_globs.is_empty()
+            || self.symbol_pattern.is_some()
     }
 
     /// `true` when `chunk` passes every optional column filter on this query
@@ -146,10 +638,26 @@ impl SearchQuery {
                 .node_types
                 .as_ref()
                 .is_none_or(|types| types.iter().any(|t| t == chunk.node_type().as_str()))
+            && (!self.async_only || chunk.is_async())
             && self
                 .repository_ids
                 .as_ref()
                 .is_none_or(|ids| ids.iter().any(|r| r == chunk.repository_id()))
+            && self.matches_path(chunk.file_path())
+            && self.matches_symbol(chunk.symbol_name())
+    }
+
+    fn matches_symbol(&self, symbol: Option<&str>) -> bool {
+        self.symbol_pattern
+            .as_ref()
+            .is_none_or(|pattern| symbol.is_some_and(|name| pattern.is_match(name)))
+    }
+
+    fn matches_path(&self, path: &str) -> bool {
+        let matches = |glob: &str| glob::Pattern::new(glob).is_ok_and(|p| p.matches(path));
+        let (included, excluded) = self.split_path_globs();
+        (included.is_empty() || included.into_iter().any(matches))
+            && !excluded.into_iter().any(matches)
     }
 
     pub fn filters_by_language(&self, language: &str) -> bool {
@@ -180,6 +688,12 @@ impl SearchQuery {
         if let Some(ref types) = self.node_types {
             parts.push(format!("types={:?}", types));
         }
+        if !self.path_globs.is_empty() {
+            parts.push(format!("paths={:?}", self.path_globs));
+        }
+        if let Some(ref pattern) = self.symbol_pattern {
+            parts.push(format!("symbol_regex={:?}", pattern.as_str()));
+        }
         parts.push(format!("text_search={}", self.text_search));
 
         parts.join(", ")
@@ -226,6 +740,118 @@ mod tests {
         assert!(query.has_filters());
     }
 
+    #[test]
+    fn test_path_glob_matches_chunk_path() {
+        let chunk = sample_chunk();
+
+        assert!(SearchQuery::new("q").with_path_glob("*.rs").matches(&chunk));
+        assert!(SearchQuery::new("q")
+            .with_path_glob("te?t.rs")
+            .matches(&chunk));
+        assert!(!SearchQuery::new("q")
+            .with_path_glob("src/**")
+            .matches(&chunk));
+        assert!(SearchQuery::new("q").with_path_glob("*.rs").has_filters());
+    }
+
+    #[test]
+    fn test_path_globs_combine_includes_and_excludes() {
+        let chunk = |path: &str| {
+            CodeChunk::new(
+                path.to_string(),
+                "fn test() {}".to_string(),
+                1,
+                1,
+                Language::Rust,
+                NodeType::Function,
+                "repo".to_string(),
+            )
+        };
+        let query = SearchQuery::new("q")
+            .with_path_glob("src/**")
+            .with_path_glob("lib/**")
+            .with_path_glob("!**/tests/**");
+
+        assert!(query.matches(&chunk("src/search.rs")));
+        assert!(query.matches(&chunk("lib/util.rs")));
+        assert!(!query.matches(&chunk("src/tests/search.rs")));
+        assert!(!query.matches(&chunk("bin/main.rs")));
+
+        let exclude_only = SearchQuery::new("q").with_path_glob("!**/tests/**");
+        assert!(exclude_only.matches(&chunk("bin/main.rs")));
+        assert!(!exclude_only.matches(&chunk("crate/tests/it.rs")));
+
+        let excluded = SearchQuery::new("q")
+            .with_exclude_paths(vec!["vendor/**".to_string(), "*.pb.go".to_string()]);
+        assert!(excluded.matches(&chunk("cmd/main.go")));
+        assert!(!excluded.matches(&chunk("vendor/github.com/x/y.go")));
+        assert!(!excluded.matches(&chunk("api/service.pb.go")));
+    }
+
+    #[test]
+    fn test_symbol_pattern_matches_symbol_name() {
+        let chunk = |symbol: Option<&str>| {
+            let chunk = sample_chunk();
+            match symbol {
+                Some(name) => chunk.with_symbol_name(name),
+                None => chunk,
+            }
+        };
+        let query = SearchQuery::new("q")
+            .with_symbol_pattern("^handle_(get|post)$")
+            .unwrap();
+
+        assert!(query.has_filters());
+        assert!(query.matches(&chunk(Some("handle_get"))));
+        assert!(!query.matches(&chunk(Some("handle_delete"))));
+        assert!(!query.matches(&chunk(None)));
+        assert!(SearchQuery::new("q").matches(&chunk(None)));
+
+        let err = SearchQuery::new("q")
+            .with_symbol_pattern("handle_(")
+            .unwrap_err();
+        assert!(matches!(err, DomainError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_symbol_pattern_rejects_engine_specific_syntax() {
+        for pattern in [
+            "(?i)handle",
+            "(?P<verb>get)",
+            r"\bhandle",
+            r"handle\z",
+            r"\p{Lu}\w+",
+        ] {
+            let err = SymbolPattern::new(pattern).unwrap_err();
+            assert!(matches!(err, DomainError::InvalidInput(_)), "{pattern}");
+        }
+        for pattern in [
+            r"^(?:get|set)_\w+$",
+            r"Repository\.find",
+            "[(?]",
+            "[]a]+",
+            "[[:upper:]][a-z]{2,}",
+        ] {
+            assert!(SymbolPattern::new(pattern).is_ok(), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn test_deserialized_query_validates_symbol_pattern() {
+        let json = |pattern: &str| {
+            let mut value = serde_json::to_value(SearchQuery::new("q")).unwrap();
+            value["symbol_pattern"] = serde_json::Value::from(pattern);
+            value
+        };
+
+        let query: SearchQuery = serde_json::from_value(json("^handle_")).unwrap();
+        assert_eq!(query.symbol_pattern(), Some("^handle_"));
+        assert!(query.matches(&sample_chunk().with_symbol_name("handle_get")));
+
+        assert!(serde_json::from_value::<SearchQuery>(json("handle_(")).is_err());
+        assert!(serde_json::from_value::<SearchQuery>(json("(?i)handle")).is_err());
+    }
+
     #[test]
     fn test_query_filters() {
         let query =
@@ -236,6 +862,19 @@ mod tests {
         assert!(!query.filters_by_language("go"));
     }
 
+    #[test]
+    fn test_text_candidates_scale_with_limit_unless_set() {
+        assert_eq!(SearchQuery::new("q").with_limit(5).text_candidates(), 30);
+        assert_eq!(SearchQuery::new("q").with_limit(25).text_candidates(), 75);
+        assert_eq!(
+            SearchQuery::new("q")
+                .with_limit(25)
+                .with_text_candidates(12)
+                .text_candidates(),
+            12
+        );
+    }
+
     #[test]
     fn test_text_search_defaults_to_false() {
         let query = SearchQuery::new("find functions");
@@ -275,4 +914,61 @@ mod tests {
         let query2 = SearchQuery::new("q");
         assert!(query2.summary().contains("text_search=false"));
     }
+
+    #[test]
+    fn test_search_cursor_round_trips_and_orders_ties_by_id() {
+        let result = SearchResult::new(sample_chunk(), 0.1 + 0.2);
+        let cursor = SearchCursor::decode(&SearchCursor::after(&result).encode()).unwrap();
+        assert_eq!(cursor.score(), result.score());
+        assert_eq!(cursor.chunk_id(), result.chunk().id());
+
+        assert!(cursor.is_before(0.2, "a"));
+        assert!(!cursor.is_before(0.9, "z"));
+        let later_id = format!("{}~", cursor.chunk_id());
+        assert!(cursor.is_before(cursor.score(), &later_id));
+        assert!(!cursor.is_before(cursor.score(), cursor.chunk_id()));
+
+        assert!(SearchCursor::decode("not-a-cursor").is_err());
+        let query = SearchQuery::new("q").with_cursor(Some("zz:id".to_string()));
+        assert!(query.is_paginated());
+        assert!(query.cursor().is_err());
+    }
+
+    #[test]
+    fn test_text_leg_records_matched_terms_and_legs_merge() {
+        let chunk = CodeChunk::new(
+            "auth.rs".to_string(),
+            "fn authenticate_user(name: &str) -> bool { true }".to_string(),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            "repo".to_string(),
+        );
+        let result = SearchResult::new(chunk, 4.2)
+            .with_text_leg("Authenticate the user")
+            .with_semantic_leg();
+        assert_eq!(result.matched_terms(), ["authenticate", "user"]);
+        assert_eq!(
+            result.search_legs(),
+            [
+                SearchLeg::Text {
+                    bm25_score: 4.2,
+                    terms: vec!["authenticate".to_string(), "user".to_string()],
+                },
+                SearchLeg::Semantic { score: 4.2 },
+            ]
+        );
+
+        // A second keyword hit merges into the first leg.
+        let merged = result.with_search_leg(SearchLeg::Text {
+            bm25_score: 1.0,
+            terms: vec!["name".to_string()],
+        });
+        assert_eq!(merged.search_legs().len(), 2);
+        assert_eq!(merged.matched_terms(), ["authenticate", "user", "name"]);
+
+        let plain = SearchResult::new(sample_chunk(), 0.9).with_semantic_leg();
+        assert!(plain.matched_terms().is_empty());
+    }
 }
@@ -255,6 +255,8 @@ pub enum ReferenceKind {
     Implementation,
     /// Inheritance: `class Foo extends Bar`
     Inheritance,
+    /// Method override: `Circle#area` overriding `Shape#area`
+    Override,
     /// Generic/template reference: `Vec<Foo>`
     GenericArgument,
     /// Unknown or unclassified reference
@@ -274,6 +276,7 @@ impl ReferenceKind {
             ReferenceKind::Instantiation => "instantiation",
             ReferenceKind::Implementation => "implementation",
             ReferenceKind::Inheritance => "inheritance",
+            ReferenceKind::Override => "override",
             ReferenceKind::GenericArgument => "generic_argument",
             ReferenceKind::Unknown => "unknown",
         }
@@ -291,6 +294,7 @@ impl ReferenceKind {
             "instantiation" => ReferenceKind::Instantiation,
             "implementation" => ReferenceKind::Implementation,
             "inheritance" => ReferenceKind::Inheritance,
+            "override" => ReferenceKind::Override,
             "generic_argument" => ReferenceKind::GenericArgument,
             _ => ReferenceKind::Unknown,
         }
@@ -356,6 +360,7 @@ mod tests {
             ReferenceKind::TypeReference,
             ReferenceKind::Import,
             ReferenceKind::MacroInvocation,
+            ReferenceKind::Override,
         ];
 
         for kind in kinds {
@@ -5,31 +5,39 @@ pub mod domain;
 pub mod tui;
 
 pub use application::{
-    AnalysisRepository, CallGraphQuery, CallGraphRepository, CallGraphStats, CallGraphUseCase,
-    ChannelEndpointRepository, ChannelExtractor, ChannelLinkOptions, ChannelLinkReport,
-    ChannelLinkUseCase, ChannelOverview, ChannelResolver, ChatClient, ClusterDetectionUseCase,
-    CommunityNamingUseCase, ContextNode, CouplingDetectionUseCase, DeleteRepositoryUseCase,
-    DreamReport, EmbeddingService, ExecutionFeaturesUseCase, ExplainResult, ExplainUseCase,
-    ExtractionReport, FileHashRepository, FileRelationshipUseCase, GraphExpansionUseCase,
-    HarvestReport, ImpactAnalysis, ImpactAnalysisUseCase, ImpactNode, ImportOutcome,
-    ImportSessionUseCase, IndexRepositoryUseCase, LanguageShare, ListRepositoriesUseCase,
-    MemoryBrowseUseCase, MemoryDreamUseCase, MemoryExtractionUseCase, MemoryLevel,
-    MemoryRepository, MemoryRow, MemorySearchUseCase, MetadataRepository, ModuleDependency,
-    ModuleOverview, OverviewOptions, OverviewReport, OverviewStats, ParserService, QueryExpander,
+    AnalysisRepository, ArchiveManifest, CallCycle, CallGraphQuery, CallGraphRepository,
+    CallGraphStats, CallGraphUseCase, CallPath, CallPathHop, CallPathReport, ChangedLines,
+    ChangedSymbolImpact, ChannelEndpointRepository, ChannelExtractor, ChannelLinkOptions,
+    ChannelLinkReport, ChannelLinkUseCase, ChannelOverview, ChannelResolver, ChatClient,
+    ClusterDetectionUseCase, CommunityNamingUseCase, ContextNode, CouplingDetectionUseCase,
+    DeleteRepositoryUseCase, DiffImpactReport, DiffImpactUseCase, DreamReport, EmbeddingCache,
+    EmbeddingCacheStats, EmbeddingService, ExecutionFeaturesUseCase, ExplainResult, ExplainUseCase,
+    ExtractionReport, FileEntry, FileHashRepository, FileRelationshipUseCase, GitChange, GitDiff,
+    GraphExpansionUseCase, GraphExport, HarvestReport, HealthIssue, Hotspot, HotspotReport,
+    ImpactAnalysis, ImpactAnalysisUseCase, ImpactEdge, ImpactNode, ImportOutcome,
+    ImportSessionUseCase, IndexArchive, IndexArchiveUseCase, IndexHealthUseCase, IndexProgress,
+    IndexProgressCallback, IndexRepositoryUseCase, IndexState, IndexUpdate, LanguageShare,
+    ListRepositoriesUseCase, MemoryBrowseUseCase, MemoryDreamUseCase, MemoryExtractionUseCase,
+    MemoryLevel, MemoryRepository, MemoryRow, MemorySearchUseCase, MetadataRepository,
+    ModuleDependency, ModuleOverview, OverviewOptions, OverviewReport, OverviewStats,
+    ParserService, PruneReport, PruneRepositoryUseCase, QueryExpander, RepositoryChunkStats,
     RepositoryOverviewUseCase, RerankingService, ResolveChannelsUseCase, ResolvedConfigValue,
-    RowTarget, Scip, SearchCodeUseCase, SessionDiscovery, SkippedSection, SnippetLookupUseCase,
-    SummarizeMemoryUseCase, SymbolClusterDetectionUseCase, SymbolContext, SymbolContextUseCase,
-    VectorRepository, MEMORY_ROOT_URI, RESOURCES_ROOT_URI, SESSIONS_ROOT_URI,
+    RowTarget, Scip, SearchCodeUseCase, SessionDiscovery, SimilarCode, SimilarCodeUseCase,
+    SkippedSection, SnippetLookupUseCase, StorageIntegrity, SummarizeMemoryUseCase,
+    SymbolClusterDetectionUseCase, SymbolContext, SymbolContextUseCase, SymbolFanCount,
+    VectorRepository, INDEX_ARCHIVE_FORMAT_VERSION, MEMORY_ROOT_URI, RESOURCES_ROOT_URI,
+    SESSIONS_ROOT_URI,
 };
 
 pub use application::resource_slug;
 
-pub use application::{aggregate, render, VizFormat, DEFAULT_NODE_LIMIT};
+pub use application::{aggregate, render, VizFormat, DEFAULT_ENTRY_POINTS, DEFAULT_NODE_LIMIT};
 
 pub use cli::{
-    ClustersSubcommand, Commands, CopilotSubcommand, EmbeddingTarget, FeaturesSubcommand,
-    LlmTarget, MemorySubcommand, OpenaiSubcommand, OutputFormat, RerankingTarget,
-    SymbolClustersSubcommand, TuiMode,
+    CacheSubcommand, ClustersSubcommand, Commands, CopilotSubcommand, EmbeddingTarget,
+    FeaturesSubcommand, LlmTarget, LogFormat, MemorySubcommand, NamespaceSubcommand,
+    OpenaiSubcommand, OutputFormat, RerankingTarget, ScipSubcommand, SymbolClustersSubcommand,
+    TuiMode,
 };
 
 pub use connector::adapter::{
@@ -42,13 +50,16 @@ pub use connector::adapter::management::{
 };
 
 pub use connector::{
-    parse_transcript, parse_transcript_file, AnthropicClient, AnthropicReranking, CodesearchConfig,
-    CopilotChatClient, DuckdbAnalysisRepository, DuckdbCallGraphRepository,
-    DuckdbChannelEndpointRepository, DuckdbFileHashRepository, DuckdbMemoryRepository,
-    DuckdbMetadataRepository, DuckdbVectorRepository, InMemoryVectorRepository, LlmQueryExpander,
-    MockEmbedding, MockReranking, NamespaceEmbeddingConfig, NoEmbedding, OpenAiChatClient,
-    OpenAiEmbedding, OpenAiReranking, OrtEmbedding, OrtReranking, TreeSitterChannelExtractor,
-    TreeSitterParser, DEFAULT_ONNX_EMBEDDING_MODEL, MEMORY_DB_FILE, NO_EMBEDDINGS_MODEL,
+    parse_transcript, parse_transcript_file, read_index_archive, read_index_archives,
+    write_index_archive, write_index_archives, AnthropicClient, AnthropicReranking,
+    CodesearchConfig, CopilotChatClient, DuckdbAnalysisRepository, DuckdbCallGraphRepository,
+    DuckdbChannelEndpointRepository, DuckdbEmbeddingCache, DuckdbFileHashRepository,
+    DuckdbMemoryRepository, DuckdbMetadataRepository, DuckdbVectorRepository, GitCli,
+    InMemoryVectorRepository, LlmQueryExpander, MockEmbedding, MockReranking,
+    NamespaceEmbeddingConfig, NamespaceSummary, NoEmbedding, OllamaEmbedding, OpenAiChatClient,
+    OpenAiEmbedding, OpenAiReranking, OrtEmbedding, OrtReranking, PgVectorRepository,
+    TreeSitterChannelExtractor, TreeSitterParser, DEFAULT_MOCK_SEED, DEFAULT_ONNX_EMBEDDING_MODEL,
+    EMBEDDING_CACHE_DB_FILE, MEMORY_DB_FILE, NO_EMBEDDINGS_MODEL,
 };
 
 pub use domain::{
@@ -56,16 +67,18 @@ pub use domain::{
     ChannelRole, Cluster, ClusterGraph, CodeChunk, CommunityCoupling, CouplingElement,
     CouplingElementKind, CouplingReport, DiscoveredSession, DomainError, DreamRun, Embedding,
     EmbeddingConfig, EndpointSource, ExecutionFeature, FeatureNode, FileHash, ImportedSession,
-    IndexingStatus, Language, MemoryItem, MemoryKind, MemoryNode, MemoryOperation, NodeKind,
-    NodeType, Protocol, ReferenceKind, Repository, SearchQuery, SearchResult, SessionLocator,
-    SessionMessage, SessionSource, SessionTranscript, SymbolCommunity, SymbolCommunityGraph,
-    SymbolReference, VectorStore, NAMESPACE_SCOPE_ID,
+    IndexFilters, IndexingStatus, Language, LegScore, MemoryItem, MemoryKind, MemoryNode,
+    MemoryOperation, NodeKind, NodeType, Protocol, ReferenceKind, Repository, ScoreExplanation,
+    SearchCursor, SearchLeg, SearchQuery, SearchResult, SessionLocator, SessionMessage,
+    SessionSource, SessionTranscript, SymbolCommunity, SymbolCommunityGraph, SymbolReference,
+    VectorStore, NAMESPACE_SCOPE_ID,
 };
 
 pub use domain::{CommunityMeta, GraphEdge, GraphLevel, GraphNode, GraphView};
 
 pub use connector::api::{
-    namespace_embedding_config, resolve_memory_project, resolve_repo_context, run_copilot_command,
-    run_import_picker_ui, run_openai_command, Container, ContainerConfig, MemoryController,
-    ResolvedContext, Router,
+    install_prometheus_recorder, metrics_router, namespace_embedding_config, otlp_layer,
+    otlp_tracer_provider, render_metrics, resolve_memory_project, resolve_repo_context,
+    run_copilot_command, run_import_picker_ui, run_openai_command, Container, ContainerConfig,
+    ImpactThresholdExceeded, MemoryController, OtlpGuard, ResolvedContext, Router,
 };
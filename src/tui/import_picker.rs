@@ -330,11 +330,9 @@ fn run_loop(
             // ── List view ──────────────────────────────────────────────────
             (View::List, KeyCode::Esc | KeyCode::Char('q')) => return Ok(()),
             // Tab opens the highlighted session's chat as a full-screen view.
-            (View::List, KeyCode::Tab) => {
-                if !state.sessions.is_empty() {
-                    state.view = View::Transcript;
-                    state.transcript_scroll = 0;
-                }
+            (View::List, KeyCode::Tab) if !state.sessions.is_empty() => {
+                state.view = View::Transcript;
+                state.transcript_scroll = 0;
             }
             (View::List, KeyCode::Up | KeyCode::Char('k')) => move_selection(&mut state, -1),
             (View::List, KeyCode::Down | KeyCode::Char('j')) => move_selection(&mut state, 1),
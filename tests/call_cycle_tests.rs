@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use codesearch::{
+    CallCycle, CallGraphQuery, CallGraphRepository, CallGraphUseCase, DuckdbCallGraphRepository,
+    DuckdbMetadataRepository, Language, ReferenceKind, SymbolReference,
+};
+
+async fn make_call_graph_use_case() -> Arc<CallGraphUseCase> {
+    let metadata_repository =
+        Arc::new(DuckdbMetadataRepository::in_memory().expect("Failed to create DuckDB"));
+    let shared_conn = metadata_repository.shared_connection();
+    let call_graph_repo: Arc<dyn CallGraphRepository> = Arc::new(
+        DuckdbCallGraphRepository::with_connection(shared_conn)
+            .await
+            .expect("Failed to create call graph repo"),
+    );
+    Arc::new(CallGraphUseCase::new(call_graph_repo))
+}
+
+fn call(caller: &str, callee: &str, repo: &str) -> SymbolReference {
+    SymbolReference::new(
+        Some(caller.to_string()),
+        callee.to_string(),
+        "src/lib.ts".to_string(),
+        "src/lib.ts".to_string(),
+        1,
+        0,
+        ReferenceKind::Call,
+        Language::TypeScript,
+        repo.to_string(),
+    )
+}
+
+/// ```text
+/// a ─▶ b ─▶ c ─▶ a        (three-symbol cycle)
+/// c ─▶ d ─▶ e ─▶ d        (separate two-symbol cycle reachable from it)
+/// f ─▶ f                  (self-recursion, not reported by find_cycles)
+/// g ─▶ a                  (enters a cycle without being part of one)
+/// ```
+async fn seed_cycles(cg: &Arc<CallGraphUseCase>) {
+    let refs = vec![
+        call("a", "b", "repo1"),
+        call("b", "c", "repo1"),
+        call("c", "a", "repo1"),
+        call("c", "d", "repo1"),
+        call("d", "e", "repo1"),
+        call("e", "d", "repo1"),
+        call("f", "f", "repo1"),
+        call("g", "a", "repo1"),
+    ];
+    cg.save_references(&refs)
+        .await
+        .expect("Failed to seed references");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_cycles_returns_sorted_components_largest_first() {
+    let cg = make_call_graph_use_case().await;
+    seed_cycles(&cg).await;
+
+    let cycles = cg.find_cycles("repo1").await.expect("find_cycles failed");
+
+    assert_eq!(cycles, vec![vec!["a", "b", "c"], vec!["d", "e"]]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_cycles_is_scoped_to_the_repository() {
+    let cg = make_call_graph_use_case().await;
+    seed_cycles(&cg).await;
+
+    let cycles = cg.find_cycles("repo2").await.expect("find_cycles failed");
+
+    assert!(cycles.is_empty(), "unexpected cycles: {cycles:?}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_cycles_across_repositories_joins_cross_repo_edges() {
+    let cg = make_call_graph_use_case().await;
+    cg.save_references(&[
+        call("client", "server", "repo1"),
+        call("server", "client", "repo2"),
+    ])
+    .await
+    .expect("Failed to seed references");
+
+    assert!(cg.find_cycles("repo1").await.unwrap().is_empty());
+    let cycles = cg
+        .find_cycles_in_repositories(&["repo1".to_string(), "repo2".to_string()])
+        .await
+        .expect("find_cycles_in_repositories failed");
+    assert_eq!(cycles, vec![vec!["client", "server"]]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_strongly_connected_components_include_self_loops() {
+    let cg = make_call_graph_use_case().await;
+    seed_cycles(&cg).await;
+
+    let components = cg
+        .find_strongly_connected_components(&CallGraphQuery::new().with_repository("repo1"))
+        .await
+        .expect("find_strongly_connected_components failed");
+
+    assert_eq!(
+        components,
+        vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_strongly_connected_components_without_repository_span_the_namespace() {
+    let cg = make_call_graph_use_case().await;
+    seed_cycles(&cg).await;
+    cg.save_references(&[
+        call("client", "server", "repo2"),
+        call("server", "client", "repo3"),
+    ])
+    .await
+    .expect("Failed to seed references");
+
+    let components = cg
+        .find_strongly_connected_components(&CallGraphQuery::new())
+        .await
+        .expect("find_strongly_connected_components failed");
+
+    assert_eq!(
+        components,
+        vec![
+            vec!["a", "b", "c"],
+            vec!["client", "server"],
+            vec!["d", "e"],
+            vec!["f"]
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_cycles_follow_call_order_and_list_files() {
+    let cg = make_call_graph_use_case().await;
+    let in_file = |caller: &str, callee: &str, file: &str| {
+        SymbolReference::new(
+            Some(caller.to_string()),
+            callee.to_string(),
+            file.to_string(),
+            file.to_string(),
+            1,
+            0,
+            ReferenceKind::Call,
+            Language::TypeScript,
+            "repo1".to_string(),
+        )
+    };
+    cg.save_references(&[
+        in_file("AuthService", "TokenStore", "auth.ts"),
+        in_file("TokenStore", "Cache", "token.ts"),
+        in_file("Cache", "AuthService", "cache.ts"),
+        in_file("Cache", "log", "cache.ts"),
+        in_file("walk", "walk", "tree.ts"),
+    ])
+    .await
+    .expect("Failed to seed references");
+    let repos = vec!["repo1".to_string()];
+
+    let cycles = cg
+        .find_call_cycles(&repos, &CallGraphQuery::new(), 2)
+        .await
+        .expect("find_call_cycles failed");
+    assert_eq!(
+        cycles,
+        vec![CallCycle {
+            cycle: vec![
+                "AuthService".to_string(),
+                "TokenStore".to_string(),
+                "Cache".to_string()
+            ],
+            files: vec![
+                "auth.ts".to_string(),
+                "cache.ts".to_string(),
+                "token.ts".to_string()
+            ],
+        }]
+    );
+
+    let with_self_loops = cg
+        .find_call_cycles(&repos, &CallGraphQuery::new(), 1)
+        .await
+        .expect("find_call_cycles failed");
+    assert_eq!(with_self_loops.len(), 2);
+    assert_eq!(with_self_loops[1].cycle, vec!["walk"]);
+    assert_eq!(with_self_loops[1].files, vec!["tree.ts"]);
+
+    let larger = cg
+        .find_call_cycles(&repos, &CallGraphQuery::new(), 4)
+        .await
+        .expect("find_call_cycles failed");
+    assert!(larger.is_empty(), "unexpected cycles: {larger:?}");
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use codesearch::{
+    CallGraphQuery, CallGraphRepository, CallGraphUseCase, DuckdbCallGraphRepository,
+    DuckdbMetadataRepository, Language, ReferenceKind, SymbolReference,
+};
+
+async fn make_call_graph_use_case() -> Arc<CallGraphUseCase> {
+    let metadata_repository =
+        Arc::new(DuckdbMetadataRepository::in_memory().expect("Failed to create DuckDB"));
+    let shared_conn = metadata_repository.shared_connection();
+    let call_graph_repo: Arc<dyn CallGraphRepository> = Arc::new(
+        DuckdbCallGraphRepository::with_connection(shared_conn)
+            .await
+            .expect("Failed to create call graph repo"),
+    );
+    Arc::new(CallGraphUseCase::new(call_graph_repo))
+}
+
+fn call(caller: &str, callee: &str, file: &str, line: u32) -> SymbolReference {
+    SymbolReference::new(
+        Some(caller.to_string()),
+        callee.to_string(),
+        file.to_string(),
+        file.to_string(),
+        line,
+        0,
+        ReferenceKind::Call,
+        Language::Rust,
+        "repo1".to_string(),
+    )
+}
+
+/// Seed a diamond with a cycle and a longer detour:
+///
+/// ```text
+/// handle ─▶ auth ─▶ validate
+///   │                  ▲
+///   └──▶ session ──────┘
+/// auth ─▶ handle          (cycle back to the start)
+/// handle ─▶ log ─▶ audit ─▶ validate   (longer route, never shortest)
+/// ```
+async fn seed_diamond(cg: &Arc<CallGraphUseCase>) {
+    let refs = vec![
+        call("handle", "auth", "src/handle.rs", 10),
+        call("handle", "session", "src/handle.rs", 11),
+        call("handle", "log", "src/handle.rs", 12),
+        call("auth", "validate", "src/auth.rs", 20),
+        call("auth", "handle", "src/auth.rs", 21),
+        call("session", "validate", "src/session.rs", 30),
+        call("log", "audit", "src/log.rs", 40),
+        call("audit", "validate", "src/audit.rs", 50),
+    ];
+    cg.save_references(&refs)
+        .await
+        .expect("Failed to seed references");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_paths_returns_all_shortest_paths() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    let report = cg
+        .find_paths("handle", "validate", 6, 10, &CallGraphQuery::new())
+        .await
+        .expect("find_paths failed");
+
+    assert_eq!(report.paths.len(), 2, "expected the two 2-hop routes");
+    assert!(!report.truncated);
+    for path in &report.paths {
+        assert_eq!(path.hops.len(), 2);
+        assert_eq!(path.hops[0].caller, "handle");
+        assert_eq!(path.hops[1].callee, "validate");
+    }
+    let middles: Vec<&str> = report
+        .paths
+        .iter()
+        .map(|p| p.hops[0].callee.as_str())
+        .collect();
+    assert!(middles.contains(&"auth"));
+    assert!(middles.contains(&"session"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_paths_records_hop_locations() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    let report = cg
+        .find_paths("auth", "validate", 6, 10, &CallGraphQuery::new())
+        .await
+        .expect("find_paths failed");
+
+    assert_eq!(report.paths.len(), 1);
+    let hop = &report.paths[0].hops[0];
+    assert_eq!(hop.file_path, "src/auth.rs");
+    assert_eq!(hop.line, 20);
+    assert_eq!(hop.reference_kind, "call");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_paths_caps_path_count() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    let report = cg
+        .find_paths("handle", "validate", 6, 1, &CallGraphQuery::new())
+        .await
+        .expect("find_paths failed");
+
+    assert_eq!(report.paths.len(), 1);
+    assert!(report.truncated, "a second shortest path exists");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_paths_accepts_unbounded_path_count() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    let report = cg
+        .find_paths("handle", "validate", 6, usize::MAX, &CallGraphQuery::new())
+        .await
+        .expect("find_paths failed");
+
+    assert_eq!(report.paths.len(), 2);
+    assert!(!report.truncated);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_paths_respects_depth_limit() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    let report = cg
+        .find_paths("log", "validate", 1, 10, &CallGraphQuery::new())
+        .await
+        .expect("find_paths failed");
+    assert!(report.paths.is_empty(), "validate is 2 hops from log");
+
+    let report = cg
+        .find_paths("log", "validate", 2, 10, &CallGraphQuery::new())
+        .await
+        .expect("find_paths failed");
+    assert_eq!(report.paths.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_paths_terminates_on_cycles_without_a_route() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    // `handle` ↔ `auth` form a cycle; nothing reaches `nowhere`.
+    let report = cg
+        .find_paths("auth", "nowhere", 50, 10, &CallGraphQuery::new())
+        .await
+        .expect("find_paths failed");
+    assert!(report.paths.is_empty());
+    assert!(!report.truncated);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shortest_path_returns_symbol_chain() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    let path = cg
+        .shortest_path("log", "validate", &CallGraphQuery::new())
+        .await
+        .expect("shortest_path failed");
+    assert_eq!(
+        path,
+        Some(vec![
+            "log".to_string(),
+            "audit".to_string(),
+            "validate".to_string()
+        ])
+    );
+
+    let path = cg
+        .shortest_path("validate", "handle", &CallGraphQuery::new())
+        .await
+        .expect("shortest_path failed");
+    assert_eq!(path, None, "validate calls nothing");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_export_graph_collects_callers_up_to_depth() {
+    let cg = make_call_graph_use_case().await;
+    seed_diamond(&cg).await;
+
+    let graph = cg
+        .export_graph("validate", 1, &CallGraphQuery::new())
+        .await
+        .expect("export_graph failed");
+    let mut direct: Vec<&str> = graph.nodes.iter().map(|n| n.symbol.as_str()).collect();
+    direct.sort();
+    assert_eq!(direct, ["audit", "auth", "session"]);
+    assert_eq!(graph.edges.len(), 3);
+
+    let graph = cg
+        .export_graph("validate", 3, &CallGraphQuery::new())
+        .await
+        .expect("export_graph failed");
+    let handle_nodes = graph.nodes.iter().filter(|n| n.symbol == "handle").count();
+    assert_eq!(handle_nodes, 1, "each caller appears once");
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.caller == "auth" && e.callee == "handle"),
+        "the auth → handle back edge is kept"
+    );
+    assert!(graph.dot().to_string().contains("\"handle\" -> \"auth\""));
+}
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use codesearch::{
+    CallGraphQuery, CallGraphRepository, CallGraphUseCase, CodeChunk, DuckdbCallGraphRepository,
+    DuckdbMetadataRepository, InMemoryVectorRepository, Language, NodeType, ReferenceKind,
+    SymbolReference, VectorRepository, DEFAULT_ENTRY_POINTS,
+};
+
+const REPO: &str = "repo1";
+
+fn function(path: &str, name: &str, line: u32) -> CodeChunk {
+    CodeChunk::new(
+        path.to_string(),
+        format!("fn {name}() {{}}"),
+        line,
+        line + 2,
+        Language::Rust,
+        NodeType::Function,
+        REPO.to_string(),
+    )
+    .with_symbol_name(name)
+}
+
+fn call(caller: &str, callee: &str) -> SymbolReference {
+    SymbolReference::new(
+        Some(caller.to_string()),
+        callee.to_string(),
+        "src/lib.rs".to_string(),
+        "src/lib.rs".to_string(),
+        1,
+        0,
+        ReferenceKind::Call,
+        Language::Rust,
+        REPO.to_string(),
+    )
+}
+
+/// ```text
+/// main ─▶ used              (used is live, main is an entry point)
+/// recurse ─▶ recurse        (only calls itself)
+/// orphan                    (never referenced)
+/// Parser::parse             (method nobody calls)
+/// ```
+async fn seeded_use_case() -> CallGraphUseCase {
+    let metadata_repository =
+        Arc::new(DuckdbMetadataRepository::in_memory().expect("Failed to create DuckDB"));
+    let call_graph_repo: Arc<dyn CallGraphRepository> = Arc::new(
+        DuckdbCallGraphRepository::with_connection(metadata_repository.shared_connection())
+            .await
+            .expect("Failed to create call graph repo"),
+    );
+    let vector_repo = Arc::new(InMemoryVectorRepository::new());
+    vector_repo
+        .save_batch(
+            &[
+                function("src/lib.rs", "main", 1),
+                function("src/lib.rs", "used", 5),
+                function("src/lib.rs", "recurse", 9),
+                function("src/lib.rs", "orphan", 13),
+                function("src/parser.rs", "parse", 1).with_parent_symbol("Parser"),
+                function("tests/helpers.rs", "fixture", 1),
+            ],
+            &[],
+        )
+        .await
+        .expect("Failed to seed chunks");
+
+    let use_case = CallGraphUseCase::new(call_graph_repo).with_vector_repo(vector_repo);
+    use_case
+        .save_references(&[call("main", "used"), call("recurse", "recurse")])
+        .await
+        .expect("Failed to seed references");
+    use_case
+}
+
+fn symbols(nodes: &[codesearch::ImpactNode]) -> Vec<&str> {
+    nodes.iter().map(|n| n.symbol.as_str()).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_unreachable_symbols_reports_uncalled_definitions() {
+    let use_case = seeded_use_case().await;
+    let query = CallGraphQuery::new().with_repository(REPO);
+
+    let unreachable = use_case
+        .find_unreachable_symbols(DEFAULT_ENTRY_POINTS, &query)
+        .await
+        .expect("find_unreachable_symbols");
+
+    assert_eq!(
+        symbols(&unreachable),
+        vec!["recurse", "orphan", "Parser::parse", "fixture"]
+    );
+    assert_eq!(unreachable[1].file_path, "src/lib.rs");
+    assert_eq!(unreachable[1].line, 13);
+    assert_eq!(unreachable[1].reference_kind, "function");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_unreachable_symbols_honours_roots_and_language() {
+    let use_case = seeded_use_case().await;
+
+    let rooted = use_case
+        .find_unreachable_symbols(
+            &["main", "orphan"],
+            &CallGraphQuery::new().with_repository(REPO),
+        )
+        .await
+        .expect("find_unreachable_symbols");
+    assert!(!symbols(&rooted).contains(&"orphan"));
+
+    let python_only = use_case
+        .find_unreachable_symbols(
+            DEFAULT_ENTRY_POINTS,
+            &CallGraphQuery::new()
+                .with_repository(REPO)
+                .with_language("python"),
+        )
+        .await
+        .expect("find_unreachable_symbols");
+    assert!(python_only.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_rarely_called_symbols_includes_single_caller_definitions() {
+    let use_case = seeded_use_case().await;
+    let query = CallGraphQuery::new().with_repository(REPO);
+
+    let rare = use_case
+        .find_rarely_called_symbols(DEFAULT_ENTRY_POINTS, &query, 2)
+        .await
+        .expect("find_rarely_called_symbols");
+    assert_eq!(
+        symbols(&rare),
+        vec!["used", "recurse", "orphan", "Parser::parse", "fixture"]
+    );
+    assert_eq!(rare[0].call_count, 1);
+    assert_eq!(rare[1].call_count, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_rarely_called_symbols_rejects_zero() {
+    let use_case = seeded_use_case().await;
+    let query = CallGraphQuery::new().with_repository(REPO);
+
+    assert!(use_case
+        .find_rarely_called_symbols(DEFAULT_ENTRY_POINTS, &query, 0)
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_self_calls_are_taken_off_once() {
+    let use_case = seeded_use_case().await;
+    // `Parser::parse` calls itself under both of its names and is called
+    // once from `main`; it is reachable whichever name the self-calls use.
+    use_case
+        .save_references(&[
+            call("main", "Parser::parse"),
+            call("Parser::parse", "Parser::parse"),
+            call("Parser::parse", "parse"),
+        ])
+        .await
+        .expect("Failed to seed references");
+    let query = CallGraphQuery::new().with_repository(REPO);
+
+    let rare = use_case
+        .find_rarely_called_symbols(DEFAULT_ENTRY_POINTS, &query, 2)
+        .await
+        .expect("find_rarely_called_symbols");
+    let parse = rare
+        .iter()
+        .find(|n| n.symbol == "Parser::parse")
+        .expect("Parser::parse has a single caller");
+    assert_eq!(parse.call_count, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_unreachable_symbols_requires_a_repository() {
+    let use_case = seeded_use_case().await;
+    assert!(use_case
+        .find_unreachable_symbols(DEFAULT_ENTRY_POINTS, &CallGraphQuery::new())
+        .await
+        .is_err());
+}
@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
 use codesearch::{
-    CodeChunk, DuckdbVectorRepository, Embedding, Language, NamespaceEmbeddingConfig, NodeType,
-    SearchQuery, VectorRepository,
+    CodeChunk, DuckdbMetadataRepository, DuckdbVectorRepository, Embedding, FileEntry,
+    InMemoryVectorRepository, IndexState, Language, MetadataRepository, NamespaceEmbeddingConfig,
+    NodeType, Repository, SearchCursor, SearchQuery, VectorRepository, VectorStore,
 };
 use tempfile::tempdir;
 
@@ -84,6 +85,176 @@ async fn duckdb_vector_repository_can_save_and_search() {
     assert!(results[0].score() > 0.99, "expected near-identical score");
 }
 
+#[tokio::test]
+async fn duckdb_vector_repository_cursor_pages_through_ties() {
+    let Some(repo) = try_in_memory() else { return };
+
+    // One exact match and three chunks tied at score 0, so the page boundary
+    // falls inside the tie and only the chunk-id tiebreak keeps pages apart.
+    let chunks: Vec<CodeChunk> = (0..4)
+        .map(|i| {
+            CodeChunk::new(
+                format!("src/f{i}.rs"),
+                format!("fn f{i}() {{}}"),
+                1,
+                1,
+                Language::Rust,
+                NodeType::Function,
+                "repo-page".to_string(),
+            )
+        })
+        .collect();
+    let embeddings: Vec<Embedding> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            Embedding::new(
+                c.id().to_string(),
+                unit_vector(384, if i == 0 { 0 } else { 1 }),
+                "mock".to_string(),
+            )
+        })
+        .collect();
+    repo.save_batch(&chunks, &embeddings)
+        .await
+        .expect("save_batch");
+
+    let query_vec = unit_vector(384, 0);
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    loop {
+        let query = SearchQuery::new("f").with_limit(2).with_cursor(cursor);
+        let page = repo.search(Some(&query_vec), &query).await.expect("search");
+        seen.extend(page.iter().map(|r| r.chunk().id().to_string()));
+        match page.last() {
+            Some(last) if page.len() == 2 => cursor = Some(SearchCursor::after(last).encode()),
+            _ => break,
+        }
+    }
+
+    assert_eq!(seen.len(), 4, "pages overlapped or skipped: {seen:?}");
+    assert_eq!(seen[0], chunks[0].id());
+    let mut tied: Vec<&str> = chunks[1..].iter().map(|c| c.id()).collect();
+    tied.sort();
+    assert_eq!(&seen[1..], tied.as_slice());
+}
+
+#[tokio::test]
+async fn duckdb_vector_repository_file_pattern_restricts_results() {
+    let Some(repo) = try_in_memory() else { return };
+
+    let chunk = |path: &str, name: &str| {
+        CodeChunk::new(
+            path.to_string(),
+            format!("pub fn {name}() -> bool {{ true }}"),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            "repo-1".to_string(),
+        )
+        .with_symbol_name(name)
+    };
+    let chunks = vec![
+        chunk("src/auth/login.rs", "login"),
+        chunk("src/auth_helpers.rs", "helper"),
+        chunk("src/db/pool.rs", "pool"),
+    ];
+    let embeddings: Vec<_> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, c)| Embedding::new(c.id().to_string(), unit_vector(384, i), "mock".to_string()))
+        .collect();
+    repo.save_batch(&chunks, &embeddings)
+        .await
+        .expect("save_batch");
+
+    let paths_for = |pattern: &'static str| {
+        let repo = repo.clone();
+        async move {
+            let query = SearchQuery::new("anything")
+                .with_limit(10)
+                .with_path_glob(pattern);
+            repo.search(Some(&unit_vector(384, 2)), &query)
+                .await
+                .expect("search")
+                .iter()
+                .map(|r| r.chunk().file_path().to_string())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(paths_for("src/auth/**").await, vec!["src/auth/login.rs"]);
+    // `_` is literal in a glob, so it must not act as LIKE's single-char
+    // wildcard and pull in `src/auth/login.rs`.
+    assert_eq!(paths_for("src/auth_*").await, vec!["src/auth_helpers.rs"]);
+    // A `!` glob excludes instead.
+    let mut rest = paths_for("!src/auth/**").await;
+    rest.sort();
+    assert_eq!(rest, vec!["src/auth_helpers.rs", "src/db/pool.rs"]);
+}
+
+#[tokio::test]
+async fn duckdb_vector_repository_find_by_symbol_ranks_exact_before_prefix() {
+    let Some(repo) = try_in_memory() else { return };
+
+    let chunk = |path: &str, name: &str, language: Language| {
+        CodeChunk::new(
+            path.to_string(),
+            format!("fn {name}() -> bool {{ true }}"),
+            1,
+            1,
+            language,
+            NodeType::Function,
+            "repo-1".to_string(),
+        )
+        .with_symbol_name(name)
+    };
+    let chunks = vec![
+        chunk("src/config_loader.rs", "parse_config_file", Language::Rust),
+        chunk("src/config.rs", "parse_config", Language::Rust),
+        chunk("lib/config.py", "parse_config", Language::Python),
+        chunk("src/other.rs", "parse_configs_v2", Language::Rust),
+        chunk("src/unrelated.rs", "reparse_config", Language::Rust),
+    ];
+    repo.save_batch(&chunks, &[]).await.expect("save_batch");
+
+    let found = |query: SearchQuery| {
+        let repo = repo.clone();
+        async move {
+            repo.find_by_symbol(&query)
+                .await
+                .expect("find_by_symbol")
+                .iter()
+                .map(|r| (r.chunk().file_path().to_string(), r.score()))
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(
+        found(SearchQuery::new("parse_config").with_limit(10)).await,
+        vec![
+            ("lib/config.py".to_string(), 1.0),
+            ("src/config.rs".to_string(), 1.0),
+            ("src/other.rs".to_string(), 0.5),
+            ("src/config_loader.rs".to_string(), 0.5),
+        ]
+    );
+    assert_eq!(
+        found(
+            SearchQuery::new("parse_config")
+                .with_limit(10)
+                .with_languages(vec!["python".to_string()])
+        )
+        .await,
+        vec![("lib/config.py".to_string(), 1.0)]
+    );
+    // `_` is literal, not LIKE's single-character wildcard.
+    assert!(found(SearchQuery::new("parse_c_nfig").with_limit(10))
+        .await
+        .is_empty());
+}
+
 #[tokio::test]
 async fn duckdb_vector_repository_delete_by_repository_removes_all() {
     let Some(repo) = try_in_memory() else { return };
@@ -130,6 +301,131 @@ async fn duckdb_vector_repository_delete_by_repository_removes_all() {
     assert_eq!(repo.count().await.expect("count"), 0);
 }
 
+#[tokio::test]
+async fn duckdb_vector_repository_list_files_counts_chunks_per_file() {
+    let Some(repo) = try_in_memory() else { return };
+
+    let chunk = |file: &str, start: u32, language: Language, repository: &str| {
+        CodeChunk::new(
+            file.to_string(),
+            format!("chunk at {start}"),
+            start,
+            start,
+            language,
+            NodeType::Function,
+            repository.to_string(),
+        )
+    };
+    repo.save_batch(
+        &[
+            chunk("src/b.py", 1, Language::Python, "repo-files"),
+            chunk("src/a.rs", 1, Language::Rust, "repo-files"),
+            chunk("src/a.rs", 5, Language::Rust, "repo-files"),
+            chunk("src/other.rs", 1, Language::Rust, "repo-other"),
+        ],
+        &[],
+    )
+    .await
+    .expect("save_batch");
+
+    let files = repo.list_files("repo-files").await.expect("list_files");
+    assert_eq!(
+        files,
+        vec![
+            FileEntry {
+                file_path: "src/a.rs".to_string(),
+                chunk_count: 2,
+                language: Language::Rust,
+            },
+            FileEntry {
+                file_path: "src/b.py".to_string(),
+                chunk_count: 1,
+                language: Language::Python,
+            },
+        ]
+    );
+    assert!(repo.list_files("missing").await.unwrap().is_empty());
+}
+
+fn stats_fixture_chunks() -> Vec<CodeChunk> {
+    let chunk = |file: &str, start: u32, language, node_type, repository: &str| {
+        CodeChunk::new(
+            file.to_string(),
+            format!("chunk at {start}"),
+            start,
+            start,
+            language,
+            node_type,
+            repository.to_string(),
+        )
+    };
+    vec![
+        chunk("a.rs", 1, Language::Rust, NodeType::Function, "repo-a"),
+        chunk("a.rs", 5, Language::Rust, NodeType::Struct, "repo-a"),
+        chunk("b.py", 1, Language::Python, NodeType::Function, "repo-a"),
+        chunk("c.go", 1, Language::Go, NodeType::Function, "repo-b"),
+    ]
+}
+
+async fn assert_stats_by_repository(repo: &dyn VectorRepository) {
+    repo.save_batch(&stats_fixture_chunks(), &[])
+        .await
+        .expect("save_batch");
+
+    let stats = repo
+        .stats_by_repository()
+        .await
+        .expect("stats_by_repository");
+    assert_eq!(stats.len(), 2);
+
+    let a = &stats["repo-a"];
+    assert_eq!(a.by_node_type.get("function"), Some(&2));
+    assert_eq!(a.by_node_type.get("struct"), Some(&1));
+    assert_eq!(a.by_language.get("rust"), Some(&2));
+    assert_eq!(a.by_language.get("python"), Some(&1));
+
+    let b = &stats["repo-b"];
+    assert_eq!(b.by_node_type.get("function"), Some(&1));
+    assert_eq!(b.by_language.get("go"), Some(&1));
+}
+
+#[tokio::test]
+async fn duckdb_vector_repository_stats_by_repository_groups_chunks() {
+    let Some(repo) = try_in_memory() else { return };
+    assert_stats_by_repository(repo.as_ref()).await;
+}
+
+#[tokio::test]
+async fn in_memory_vector_repository_stats_by_repository_groups_chunks() {
+    assert_stats_by_repository(&InMemoryVectorRepository::new()).await;
+}
+
+async fn assert_bulk_delete(repo: &dyn VectorRepository) {
+    let chunks = stats_fixture_chunks();
+    repo.save_batch(&chunks, &[]).await.expect("save_batch");
+
+    // More ids than fit in one IN list, with the real ones at either end.
+    let unknown: Vec<String> = (0..1200).map(|i| format!("missing-{i}")).collect();
+    let mut ids = vec![chunks[0].id()];
+    ids.extend(unknown.iter().map(String::as_str));
+    ids.push(chunks[3].id());
+
+    assert_eq!(repo.bulk_delete(&ids).await.expect("bulk_delete"), 2);
+    assert_eq!(repo.count().await.expect("count"), 2);
+    assert_eq!(repo.bulk_delete(&[]).await.expect("bulk_delete"), 0);
+}
+
+#[tokio::test]
+async fn duckdb_vector_repository_bulk_delete_removes_listed_chunks() {
+    let Some(repo) = try_in_memory() else { return };
+    assert_bulk_delete(repo.as_ref()).await;
+}
+
+#[tokio::test]
+async fn in_memory_vector_repository_bulk_delete_removes_listed_chunks() {
+    assert_bulk_delete(&InMemoryVectorRepository::new()).await;
+}
+
 #[tokio::test]
 async fn duckdb_vector_repository_bm25_text_search_finds_matching_chunks() {
     // Verify that the DuckDB FTS-backed BM25 path finds chunks whose content
@@ -370,6 +666,119 @@ async fn duckdb_vector_repository_schema_namespaces_tables() {
     assert!(results_b.is_empty(), "expected no results from schema_b");
 }
 
+/// Dropping one namespace removes its schema and config but leaves a sibling
+/// namespace in the same database intact.
+#[tokio::test]
+async fn duckdb_vector_repository_drop_namespace_spares_siblings() {
+    let dir = tempdir().expect("tempdir");
+    let db_path = dir.path().join("codesearch.duckdb");
+    let cfg = default_cfg();
+
+    // Create `junk` first, on its own connection.
+    if try_with_namespace(&db_path, "junk", &cfg).is_none() {
+        return;
+    }
+    let Some(keep) = try_with_namespace(&db_path, "keep", &cfg) else {
+        return;
+    };
+    let chunk = CodeChunk::new(
+        "src/lib.rs".to_string(),
+        "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+        1,
+        1,
+        Language::Rust,
+        NodeType::Function,
+        "repo-keep".to_string(),
+    );
+    keep.save_batch(&[chunk], &[]).await.expect("save keep");
+
+    DuckdbVectorRepository::drop_namespace(keep.shared_connection(), "junk")
+        .await
+        .expect("drop_namespace");
+
+    assert!(keep.namespace_view("junk").await.is_err());
+    assert!(keep.namespace_view("keep").await.is_ok());
+    assert_eq!(keep.count().await.expect("count keep"), 1);
+
+    let err = DuckdbVectorRepository::drop_namespace(keep.shared_connection(), "junk")
+        .await
+        .expect_err("dropping twice must fail");
+    assert!(err.to_string().contains("not found"), "{err}");
+}
+
+/// `list_namespaces` reports each namespace's config, counts and repositories.
+#[tokio::test]
+async fn duckdb_vector_repository_list_namespaces_summarises_each_namespace() {
+    let dir = tempdir().expect("tempdir");
+    let db_path = dir.path().join("codesearch.duckdb");
+    let no_embeddings = NamespaceEmbeddingConfig {
+        embedding_target: "none".to_string(),
+        embedding_model: "none".to_string(),
+        dimensions: 384,
+    };
+    if let Err(e) = DuckdbVectorRepository::create_namespace(&db_path, "empty", &no_embeddings) {
+        eprintln!("SKIP: create_namespace unavailable ({e}). Skipping test.");
+        return;
+    }
+
+    {
+        let Some(team) = try_with_namespace(&db_path, "team", &default_cfg()) else {
+            return;
+        };
+        let chunk = CodeChunk::new(
+            "src/lib.rs".to_string(),
+            "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_string(),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            "repo-1".to_string(),
+        );
+        let embedding = Embedding::new(
+            chunk.id().to_string(),
+            unit_vector(384, 0),
+            "mock".to_string(),
+        );
+        team.save_batch(&[chunk], &[embedding])
+            .await
+            .expect("save_batch");
+        let metadata =
+            DuckdbMetadataRepository::with_connection(team.shared_connection()).expect("metadata");
+        metadata
+            .save(&Repository::new_with_storage(
+                "api".to_string(),
+                "/src/api".to_string(),
+                VectorStore::DuckDb,
+                Some("team".to_string()),
+                None,
+            ))
+            .await
+            .expect("save repository");
+    }
+
+    let namespaces = DuckdbVectorRepository::list_namespaces(&db_path).expect("list");
+    let names: Vec<&str> = namespaces.iter().map(|n| n.namespace.as_str()).collect();
+    assert_eq!(names, ["empty", "team"]);
+
+    let empty = &namespaces[0];
+    assert_eq!(empty.embedding_model.as_deref(), Some("none"));
+    assert_eq!(empty.chunk_count, 0);
+    assert!(empty.repositories.is_empty());
+
+    let team = &namespaces[1];
+    assert_eq!(team.dimensions, Some(384));
+    assert_eq!(team.chunk_count, 1);
+    assert_eq!(team.embedding_count, 1);
+    assert!(team.estimated_bytes >= 384 * 4);
+    assert_eq!(team.repositories, ["api"]);
+
+    let metadata = DuckdbMetadataRepository::new(&db_path).expect("open metadata");
+    assert_eq!(
+        metadata.list_namespaces().await.expect("list names"),
+        ["empty", "team"]
+    );
+}
+
 /// `create_namespace` persists the configuration, later reads resolve it, and
 /// creating the same namespace twice is rejected.
 #[test]
@@ -412,6 +821,63 @@ fn test_create_namespace_round_trip_and_duplicate() {
     assert!(try_with_namespace(&db_path, "team-a", &cfg).is_some());
 }
 
+/// A 768-dim namespace stores 768-wide vectors and rejects any other width,
+/// both on save and on query, with an invalid-input error naming the
+/// namespace.
+#[tokio::test]
+async fn test_namespace_dimensions_are_enforced() {
+    let dir = tempdir().expect("tempdir");
+    let db_path = dir.path().join("codesearch.duckdb");
+    let cfg = NamespaceEmbeddingConfig {
+        embedding_target: "api".to_string(),
+        embedding_model: "nomic-embed-text".to_string(),
+        dimensions: 768,
+    };
+    let Some(repo) = try_with_namespace(&db_path, "wide", &cfg) else {
+        return;
+    };
+    assert_eq!(repo.dimensions(), 768);
+
+    let chunk = CodeChunk::new(
+        "src/lib.rs".to_string(),
+        "pub fn wide() -> usize { 768 }".to_string(),
+        1,
+        1,
+        Language::Rust,
+        NodeType::Function,
+        "repo-1".to_string(),
+    );
+    let fits = Embedding::new(
+        chunk.id().to_string(),
+        unit_vector(768, 0),
+        cfg.embedding_model.clone(),
+    );
+    repo.save_batch(std::slice::from_ref(&chunk), &[fits])
+        .await
+        .expect("768-dim vectors fit a 768-dim namespace");
+
+    let too_narrow = Embedding::new(
+        chunk.id().to_string(),
+        unit_vector(384, 0),
+        cfg.embedding_model.clone(),
+    );
+    let err = repo
+        .save_batch(std::slice::from_ref(&chunk), &[too_narrow])
+        .await
+        .expect_err("384-dim vectors must not fit a 768-dim namespace");
+    assert!(err.is_invalid_input(), "unexpected error kind: {err}");
+    assert!(
+        err.to_string().contains("'wide'"),
+        "unexpected error: {err}"
+    );
+
+    let err = repo
+        .search(Some(&unit_vector(384, 0)), &SearchQuery::new("wide"))
+        .await
+        .expect_err("a 384-dim query must not search a 768-dim namespace");
+    assert!(err.is_invalid_input(), "unexpected error kind: {err}");
+}
+
 /// A namespace created with the no-embeddings sentinel is resolvable and
 /// opens with any embedding config (nothing to validate against).
 #[test]
@@ -438,3 +904,259 @@ fn test_create_no_embeddings_namespace() {
     // the sentinel skips embedding-space validation.
     assert!(try_with_namespace(&db_path, "fast", &default_cfg()).is_some());
 }
+
+#[tokio::test]
+async fn duckdb_vector_repository_integrity_reports_gaps_and_stale_fts() {
+    let Some(repo) = try_in_memory() else { return };
+
+    let chunk = |path: &str, name: &str| {
+        CodeChunk::new(
+            path.to_string(),
+            format!("fn {name}() -> u32 {{ 42 }}"),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            "repo-1".to_string(),
+        )
+        .with_symbol_name(name)
+    };
+    let embedded = chunk("src/a.rs", "alpha");
+    let bare = chunk("src/b.rs", "beta");
+    let embedding = Embedding::new(
+        embedded.id().to_string(),
+        unit_vector(384, 0),
+        "mock".to_string(),
+    );
+    repo.save_batch(&[embedded, bare], &[embedding])
+        .await
+        .expect("save_batch");
+    repo.flush().await.expect("flush");
+
+    let integrity = repo.integrity().await.expect("integrity").expect("duckdb");
+    assert_eq!(integrity.chunk_count, 2);
+    assert_eq!(integrity.embedding_count, 1);
+    assert_eq!(integrity.chunks_without_embedding, 1);
+    assert_eq!(integrity.orphan_embeddings, 0);
+    assert_eq!(integrity.text_index, IndexState::Ok);
+    assert_eq!(integrity.vector_index, IndexState::Ok);
+    assert_eq!(integrity.chunks_per_repository.get("repo-1"), Some(&2));
+
+    // Chunks written without the flush that rebuilds FTS leave it stale.
+    repo.save_batch(&[chunk("src/c.rs", "gamma")], &[])
+        .await
+        .expect("save_batch");
+    let integrity = repo.integrity().await.expect("integrity").expect("duckdb");
+    assert_eq!(integrity.text_index, IndexState::Stale);
+}
+
+#[tokio::test]
+async fn duckdb_vector_repository_bm25_pool_scales_with_limit() {
+    let Some(repo) = try_in_memory() else { return };
+
+    let chunk = |i: usize, content: String| {
+        CodeChunk::new(
+            format!("src/file_{i}.rs"),
+            content,
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            "repo-pool".to_string(),
+        )
+    };
+    // Fifteen keyword hits with distinct term frequencies (so BM25 ranks them
+    // 1..=15) and no embeddings, plus five embedded chunks that only the
+    // semantic leg can find.
+    let keyword: Vec<_> = (1..=15)
+        .map(|i| {
+            let body = "frobnicate(); ".repeat(16 - i);
+            chunk(i, format!("fn caller_{i}() {{ {body}}}"))
+        })
+        .collect();
+    let semantic: Vec<_> = (0..5)
+        .map(|i| chunk(100 + i, format!("fn unrelated_{i}() {{ compute(); }}")))
+        .collect();
+    let embeddings: Vec<_> = semantic
+        .iter()
+        .enumerate()
+        .map(|(i, c)| Embedding::new(c.id().to_string(), unit_vector(384, i), "mock".to_string()))
+        .collect();
+    let chunks: Vec<_> = keyword.iter().chain(&semantic).cloned().collect();
+    repo.save_batch(&chunks, &embeddings)
+        .await
+        .expect("save_batch");
+    repo.flush().await.expect("flush");
+
+    let query = SearchQuery::new("frobnicate")
+        .with_limit(25)
+        .with_text_search(true);
+    let results = repo
+        .search(Some(&unit_vector(384, 0)), &query)
+        .await
+        .expect("hybrid search");
+    let keyword_hits = results
+        .iter()
+        .filter(|r| r.chunk().content().contains("frobnicate"))
+        .count();
+    // A fixed pool of 10 BM25 candidates lost ranks 11..=15.
+    assert_eq!(
+        keyword_hits, 15,
+        "rank-15 keyword match must survive fusion"
+    );
+
+    let capped = repo
+        .search(Some(&unit_vector(384, 0)), &query.with_text_candidates(5))
+        .await
+        .expect("hybrid search");
+    assert_eq!(
+        capped
+            .iter()
+            .filter(|r| r.chunk().content().contains("frobnicate"))
+            .count(),
+        5
+    );
+}
+
+/// Backend parity: the same corpus and hybrid query must rank the same chunks
+/// near the top on DuckDB and on the in-memory store, whose keyword legs score
+/// differently (BM25 vs. substring hits) but are fused the same way.
+#[tokio::test]
+async fn duckdb_and_in_memory_hybrid_search_agree_on_top_results() {
+    let Some(duckdb) = try_in_memory() else {
+        return;
+    };
+    let memory = Arc::new(InMemoryVectorRepository::new());
+
+    let corpus = [
+        (
+            "src/config.rs",
+            "parse_config_file",
+            "fn parse_config_file(path: &Path) -> Config { parse the config file at path }",
+        ),
+        (
+            "src/config.rs",
+            "load_config",
+            "fn load_config() -> Config { parse config from the environment }",
+        ),
+        (
+            "src/io.rs",
+            "read_file",
+            "fn read_file(path: &Path) -> String { read the whole file }",
+        ),
+        (
+            "src/net.rs",
+            "open_socket",
+            "fn open_socket(addr: &str) -> Socket { connect to the address }",
+        ),
+        (
+            "src/math.rs",
+            "add",
+            "fn add(a: i32, b: i32) -> i32 { a + b }",
+        ),
+        (
+            "src/auth.rs",
+            "hash_password",
+            "fn hash_password(pw: &str) -> String { argon2 hash }",
+        ),
+    ];
+    let chunks: Vec<CodeChunk> = corpus
+        .iter()
+        .map(|(path, symbol, content)| {
+            CodeChunk::new(
+                path.to_string(),
+                content.to_string(),
+                1,
+                1,
+                Language::Rust,
+                NodeType::Function,
+                "parity".to_string(),
+            )
+            .with_symbol_name(*symbol)
+        })
+        .collect();
+    // The first three chunks lean towards the query vector, most to least.
+    let embeddings: Vec<Embedding> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let mut v = unit_vector(384, i + 1);
+            if i < 3 {
+                v[0] = 3.0 - i as f32;
+            }
+            let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            Embedding::new(
+                c.id().to_string(),
+                v.iter().map(|x| x / norm).collect(),
+                "mock".to_string(),
+            )
+        })
+        .collect();
+
+    for repo in [
+        duckdb.clone() as Arc<dyn VectorRepository>,
+        memory.clone() as Arc<dyn VectorRepository>,
+    ] {
+        repo.save_batch(&chunks, &embeddings)
+            .await
+            .expect("save_batch");
+        repo.flush().await.expect("flush");
+    }
+
+    let query_vec = unit_vector(384, 0);
+    let query = SearchQuery::new("parse config file")
+        .with_limit(3)
+        .with_text_search(true);
+    let top3 = |results: Vec<codesearch::SearchResult>| -> Vec<String> {
+        results.iter().map(|r| r.chunk().id().to_string()).collect()
+    };
+    let from_duckdb = top3(duckdb.search(Some(&query_vec), &query).await.unwrap());
+    let from_memory = top3(memory.search(Some(&query_vec), &query).await.unwrap());
+
+    assert_eq!(from_duckdb.len(), 3);
+    assert_eq!(from_memory.len(), 3);
+    assert_eq!(from_duckdb[0], chunks[0].id(), "duckdb: {from_duckdb:?}");
+    assert_eq!(from_memory[0], chunks[0].id(), "memory: {from_memory:?}");
+    let shared = from_duckdb
+        .iter()
+        .filter(|id| from_memory.contains(id))
+        .count();
+    assert!(
+        shared >= 2,
+        "top-3 overlap too small: duckdb {from_duckdb:?}, memory {from_memory:?}"
+    );
+}
+
+#[tokio::test]
+async fn duckdb_vector_repository_compact_keeps_remaining_rows() {
+    let dir = tempdir().unwrap();
+    let db = dir.path().join("codesearch.duckdb");
+    let Some(repo) = try_with_namespace(&db, "main", &default_cfg()) else {
+        return;
+    };
+
+    let chunk = |repository_id: &str, i: usize| {
+        CodeChunk::new(
+            format!("src/f{i}.rs"),
+            format!("pub fn f{i}() {{}}"),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            repository_id.to_string(),
+        )
+    };
+    for (repository_id, n) in [("gone", 50), ("kept", 3)] {
+        let chunks: Vec<CodeChunk> = (0..n).map(|i| chunk(repository_id, i)).collect();
+        let embeddings: Vec<Embedding> = chunks
+            .iter()
+            .map(|c| Embedding::new(c.id().to_string(), unit_vector(384, 0), "m".to_string()))
+            .collect();
+        repo.save_batch(&chunks, &embeddings).await.unwrap();
+    }
+    repo.delete_by_repository("gone").await.unwrap();
+
+    repo.compact().await.expect("compact");
+    assert_eq!(repo.count().await.unwrap(), 3);
+    assert_eq!(repo.list_files("kept").await.unwrap().len(), 3);
+}
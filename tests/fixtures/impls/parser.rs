@@ -0,0 +1,35 @@
+pub trait ParserService {
+    fn parse_file(&self, content: &str) -> Vec<String>;
+}
+
+pub struct TreeSitterParser;
+
+impl TreeSitterParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ParserService for TreeSitterParser {
+    fn parse_file(&self, content: &str) -> Vec<String> {
+        content.lines().map(str::to_string).collect()
+    }
+}
+
+pub struct MarkdownParser;
+
+impl ParserService for MarkdownParser {
+    fn parse_file(&self, content: &str) -> Vec<String> {
+        vec![content.to_string()]
+    }
+}
+
+pub struct IndexUseCase {
+    parser: Box<dyn ParserService>,
+}
+
+impl IndexUseCase {
+    pub fn run(&self, content: &str) -> usize {
+        self.parser.parse_file(content).len()
+    }
+}
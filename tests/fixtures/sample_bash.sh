@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+DIR="$(cd "$(dirname "$0")" && pwd)"
+source "$DIR/lib/common.sh"
+
+function build_image {
+    local tag="$1"
+    docker build -t "app:${tag}" .
+}
+
+push_image() {
+    local tag="$1"
+    docker push "app:${tag}"
+    ./notify.sh "pushed ${tag}"
+}
+
+main() {
+    build_image "$1"
+    push_image "$1"
+    bash scripts/migrate.sh --env prod
+    bash -c 'echo done'
+}
+
+main "$@"
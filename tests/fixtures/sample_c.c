@@ -0,0 +1,38 @@
+#include <stdlib.h>
+#include "list.h"
+
+#define LIST_MAX 1024
+#define list_empty(l) ((l)->len == 0)
+
+struct node {
+    int value;
+    struct node *next;
+};
+
+typedef struct {
+    struct node *head;
+    size_t len;
+} list_t;
+
+enum color { RED, GREEN, BLUE };
+
+static const struct node sentinel = { .value = -1, .next = NULL };
+
+static struct node *node_new(int value)
+{
+    struct node *n = malloc(sizeof(*n));
+    n->value = value;
+    n->next = NULL;
+    return n;
+}
+
+int list_push(list_t *list, int value)
+{
+    if (list->len >= LIST_MAX)
+        return -1;
+    struct node *n = node_new(value);
+    n->next = list->head;
+    list->head = n;
+    list->len++;
+    return 0;
+}
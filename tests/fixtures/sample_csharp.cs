@@ -0,0 +1,114 @@
+using System;
+using System.Collections.Generic;
+using System.Linq;
+using static System.Math;
+
+namespace Example.Shapes
+{
+    // Interface with a method and a property
+    public interface IShape
+    {
+        double Area();
+        string Name { get; }
+    }
+
+    // Class with a constructor, property and methods
+    public class Circle : IShape
+    {
+        private readonly double _radius;
+
+        public Circle(double radius)
+        {
+            _radius = radius;
+        }
+
+        public string Name => "circle";
+
+        public double Area()
+        {
+            return Math.PI * _radius * _radius;
+        }
+
+        public static Circle FromDiameter(double diameter)
+        {
+            return new Circle(diameter / 2);
+        }
+    }
+
+    // Subclass with a base class and an interface
+    public class ScaledCircle : Circle, IComparable<ScaledCircle>
+    {
+        private readonly double _scale;
+
+        public ScaledCircle(double radius, double scale) : base(radius)
+        {
+            _scale = scale;
+        }
+
+        public double ScaledArea()
+        {
+            return this.Area() * _scale;
+        }
+
+        public int CompareTo(ScaledCircle other)
+        {
+            return ScaledArea().CompareTo(other.ScaledArea());
+        }
+    }
+
+    // Interface extending another
+    public interface ISolid : IShape
+    {
+        double Volume();
+    }
+
+    // Struct with a method
+    public struct Point
+    {
+        public double X;
+        public double Y;
+
+        public double DistanceTo(Point other)
+        {
+            return Math.Sqrt(Math.Pow(X - other.X, 2) + Math.Pow(Y - other.Y, 2));
+        }
+    }
+
+    // Record with a method
+    public record Rectangle(double Width, double Height)
+    {
+        public double Area() => Width * Height;
+    }
+
+    // Enum
+    public enum Color
+    {
+        Red,
+        Green,
+        Blue
+    }
+
+    // Class using LINQ over a generic collection
+    public class ShapeRegistry
+    {
+        private readonly List<IShape> _shapes = new List<IShape>();
+
+        public void Register(IShape shape)
+        {
+            _shapes.Add(shape);
+        }
+
+        public double TotalArea()
+        {
+            return _shapes.Where(s => s.Area() > 0).Select(s => s.Area()).Sum();
+        }
+
+        public IEnumerable<string> LargeNames()
+        {
+            return from shape in _shapes
+                   where shape.Area() > 10
+                   orderby shape.Name descending
+                   select shape.Name;
+        }
+    }
+}
@@ -0,0 +1,60 @@
+defprotocol Shapes.Shape do
+  @doc "Returns the area of the shape"
+  def area(shape)
+end
+
+defmodule Shapes.Circle do
+  @moduledoc "A circle defined by its radius."
+
+  defstruct radius: 0.0
+
+  alias Shapes.Shape
+
+  # Plain function with arguments
+  def new(radius) when is_number(radius) do
+    %__MODULE__{radius: radius}
+  end
+
+  def area(%__MODULE__{radius: r}) do
+    :math.pi() * r * r
+  end
+
+  # Pipeline through local and remote calls
+  def scale(circle, factor) do
+    circle
+    |> validate()
+    |> Map.update!(:radius, &(&1 * factor))
+  end
+
+  # Private helper
+  defp validate(%__MODULE__{} = circle), do: circle
+
+  # Zero-arity function without parentheses
+  def unit, do: new(1.0)
+end
+
+defimpl Shapes.Shape, for: Shapes.Circle do
+  def area(circle), do: Shapes.Circle.area(circle)
+end
+
+defmodule Shapes.Registry do
+  @behaviour GenServer
+
+  use GenServer
+  import Enum, only: [sum: 1, map: 2]
+  alias Shapes.{Circle, Shape}
+
+  def total_area(shapes) do
+    shapes
+    |> map(&Shapes.Shape.area/1)
+    |> sum()
+  end
+
+  def unit_area, do: Circle.unit() |> Shape.area()
+
+  defmacro debug(expr) do
+    quote do
+      IO.inspect(unquote(expr))
+    end
+  end
+end
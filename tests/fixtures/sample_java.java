@@ -0,0 +1,82 @@
+package com.example.shapes;
+
+import java.util.ArrayList;
+import java.util.List;
+
+// Interface with an abstract method
+interface Shape {
+    double area();
+}
+
+// Class with a constructor and methods
+public class Circle implements Shape {
+    private final double radius;
+
+    public Circle(double radius) {
+        this.radius = radius;
+    }
+
+    @Override
+    public double area() {
+        return Math.PI * radius * radius;
+    }
+
+    public static Circle fromDiameter(double diameter) {
+        return new Circle(diameter / 2);
+    }
+}
+
+// Enum with a constructor and a method
+enum Color {
+    RED("#ff0000"), GREEN("#00ff00");
+
+    private final String hex;
+
+    Color(String hex) {
+        this.hex = hex;
+    }
+
+    public String toHex() {
+        return this.hex;
+    }
+}
+
+// Record with a derived accessor
+record Rectangle(double width, double height) implements Shape {
+    public double area() {
+        return width * height;
+    }
+}
+
+class ShapeRegistry {
+    private final List<Shape> shapes = new ArrayList<>();
+
+    public void register(Shape shape) {
+        shapes.add(shape);
+    }
+}
+
+// Subclass calling through `this`, a static factory and a collaborator
+class ScaledCircle extends Circle implements Comparable<ScaledCircle> {
+    private final ShapeRegistry registry = new ShapeRegistry();
+    private final double scale;
+
+    ScaledCircle(double radius, double scale) {
+        super(radius);
+        this.scale = scale;
+    }
+
+    public double scaledArea() {
+        Circle unit = Circle.fromDiameter(2);
+        registry.register(unit);
+        return this.area() * scale;
+    }
+
+    public int compareTo(ScaledCircle other) {
+        return Double.compare(scaledArea(), other.scaledArea());
+    }
+}
+
+interface Solid extends Shape {
+    double volume();
+}
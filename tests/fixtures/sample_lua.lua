@@ -0,0 +1,52 @@
+local json = require("json")
+
+-- Table used as a pseudo-class
+local Account = {}
+Account.__index = Account
+
+-- Constructor using a metatable
+function Account.new(owner, balance)
+  local self = setmetatable({}, Account)
+  self.owner = owner
+  self.balance_ = balance or 0
+  return self
+end
+
+-- Methods called with `account:deposit(10)`
+function Account:deposit(amount)
+  self.balance_ = self.balance_ + clamp(amount, 0, math.huge)
+end
+
+function Account:balance()
+  return self.balance_
+end
+
+-- Local helper function
+local function clamp(value, low, high)
+  return math.max(low, math.min(value, high))
+end
+
+-- Global table and function
+Config = { currency = "EUR", precision = 2 }
+
+function greet(name)
+  print("Hello, " .. name .. "! " .. json.encode(Config))
+end
+
+-- Subclass inheriting through its metatable
+local SavingsAccount = setmetatable({}, { __index = Account })
+SavingsAccount.__index = SavingsAccount
+
+function SavingsAccount.new(owner, balance, rate)
+  local self = Account.new(owner, balance)
+  self.rate = rate
+  return setmetatable(self, SavingsAccount)
+end
+
+function SavingsAccount:accrue()
+  self:deposit(self:balance() * self.rate)
+end
+
+local savings = SavingsAccount.new("ada", 100, 0.02)
+savings:accrue()
+local util = require "util"
@@ -0,0 +1,64 @@
+require "json"
+require_relative "support/logging"
+
+# Top-level helper
+def format_amount(cents)
+  format("%.2f", cents / 100.0)
+end
+
+module Billing
+  # Module function defined on the module itself
+  def self.currency
+    "EUR"
+  end
+
+  class Invoice
+    include Comparable
+    extend Forwardable
+
+    attr_reader :total
+
+    def initialize(total)
+      @total = total
+    end
+
+    def self.empty
+      new(0)
+    end
+
+    def add_line(amount)
+      raise ArgumentError, "negative amount" if amount.negative?
+
+      @total += amount
+      self
+    end
+
+    def to_json(*args)
+      { total: total, currency: Billing.currency }.to_json(*args)
+    end
+  end
+end
+
+class Billing::Refund
+  def apply(invoice)
+    invoice.add_line(-invoice.total)
+  end
+end
+
+class CreditNote < Billing::Invoice
+  def self.for(invoice)
+    note = Billing::Invoice.new(invoice.total)
+    note.add_line(format_amount(invoice.total).to_i)
+    Logging.info("credit note issued")
+    note
+  end
+
+  def summary
+    puts to_json
+    describe
+  end
+
+  def describe
+    "credit note for #{total}"
+  end
+end
@@ -0,0 +1,49 @@
+package com.example.jobs
+
+import org.apache.spark.sql.{DataFrame, SparkSession}
+import scala.concurrent.duration._
+
+/** Row counts per day. */
+type DailyCounts = Map[String, Long]
+
+trait Job {
+  def name: String
+
+  def run(spark: SparkSession): Unit
+}
+
+abstract class BaseJob(val name: String) extends Job with Serializable {
+  def log(message: String): Unit = {
+    println(s"[$name] $message")
+  }
+}
+
+case class Event(id: String, day: String, value: Long)
+
+class CountJob(input: String) extends BaseJob("count") {
+  def run(spark: SparkSession): Unit = {
+    val events = load(spark)
+    this.log("loaded")
+    val counts = CountJob.summarise(events)
+    counts.show()
+  }
+
+  def load(spark: SparkSession): DataFrame = {
+    spark.read.parquet(input)
+  }
+}
+
+object CountJob {
+  implicit def toDaily(frame: DataFrame): DailyCounts = {
+    Map.empty
+  }
+
+  def summarise(events: DataFrame): DataFrame = {
+    events.groupBy("day").count()
+  }
+
+  def main(args: Array[String]): Unit = {
+    val spark = SparkSession.builder().getOrCreate()
+    new CountJob(args(0)).run(spark)
+  }
+}
@@ -0,0 +1,6 @@
+pub mod shapes;
+use shapes::Circle;
+pub fn total_area(circles: &[Circle]) -> f64 {
+    circles.iter().map(|c| c.area()).sum()
+}
+pub fn unit() -> Circle { Circle::new(1.0) }
@@ -0,0 +1,4 @@
+pub trait Shape { fn area(&self) -> f64; }
+pub struct Circle { radius: f64 }
+impl Circle { pub fn new(radius: f64) -> Self { Circle { radius } } }
+impl Shape for Circle { fn area(&self) -> f64 { 3.14 * self.radius * self.radius } }
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use codesearch::{
+    CallGraphQuery, CallGraphRepository, CallGraphUseCase, CodeChunk, DuckdbCallGraphRepository,
+    DuckdbMetadataRepository, InMemoryVectorRepository, Language, NodeType, ReferenceKind,
+    SymbolReference, VectorRepository,
+};
+
+const REPO: &str = "repo1";
+
+fn call(caller: &str, callee: &str) -> SymbolReference {
+    SymbolReference::new(
+        Some(caller.to_string()),
+        callee.to_string(),
+        "src/lib.rs".to_string(),
+        "src/lib.rs".to_string(),
+        1,
+        0,
+        ReferenceKind::Call,
+        Language::Rust,
+        REPO.to_string(),
+    )
+}
+
+/// ```text
+/// main ─▶ parse, render, log
+/// parse ─▶ log
+/// render ─▶ log
+/// ```
+/// Only `log` and `main` have chunks.
+async fn seeded_use_case() -> CallGraphUseCase {
+    let metadata_repository =
+        Arc::new(DuckdbMetadataRepository::in_memory().expect("Failed to create DuckDB"));
+    let call_graph_repo: Arc<dyn CallGraphRepository> = Arc::new(
+        DuckdbCallGraphRepository::with_connection(metadata_repository.shared_connection())
+            .await
+            .expect("Failed to create call graph repo"),
+    );
+    let function = |path: &str, name: &str, line| {
+        CodeChunk::new(
+            path.to_string(),
+            format!("fn {name}() {{}}"),
+            line,
+            line + 2,
+            Language::Rust,
+            NodeType::Function,
+            REPO.to_string(),
+        )
+        .with_symbol_name(name)
+    };
+    let vector_repo = Arc::new(InMemoryVectorRepository::new());
+    vector_repo
+        .save_batch(
+            &[
+                function("src/log.rs", "log", 3),
+                function("src/main.rs", "main", 1),
+            ],
+            &[],
+        )
+        .await
+        .expect("Failed to seed chunks");
+
+    let use_case = CallGraphUseCase::new(call_graph_repo).with_vector_repo(vector_repo);
+    use_case
+        .save_references(&[
+            call("main", "parse"),
+            call("main", "render"),
+            call("main", "log"),
+            call("parse", "log"),
+            call("render", "log"),
+        ])
+        .await
+        .expect("Failed to seed references");
+    use_case
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_hotspots_ranks_fan_in_and_fan_out_with_definitions() {
+    let use_case = seeded_use_case().await;
+    let query = CallGraphQuery::new().with_repository(REPO);
+
+    let report = use_case
+        .find_hotspots(&query, 2, 1)
+        .await
+        .expect("find_hotspots");
+
+    let log = &report.by_fan_in[0];
+    assert_eq!(
+        (log.symbol.as_str(), log.fan_in, log.fan_out),
+        ("log", 3, 0)
+    );
+    assert_eq!(log.file_path.as_deref(), Some("src/log.rs"));
+    assert_eq!(log.line, Some(3));
+    assert_eq!(report.by_fan_in.len(), 2);
+    assert_eq!(report.by_fan_in[1].file_path, None);
+
+    let main = &report.by_fan_out[0];
+    assert_eq!(
+        (main.symbol.as_str(), main.fan_in, main.fan_out),
+        ("main", 0, 3)
+    );
+    assert_eq!(main.file_path.as_deref(), Some("src/main.rs"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_hotspots_min_fan_in_trims_the_fan_in_ranking() {
+    let use_case = seeded_use_case().await;
+    let query = CallGraphQuery::new().with_repository(REPO);
+
+    let report = use_case
+        .find_hotspots(&query, 10, 2)
+        .await
+        .expect("find_hotspots");
+
+    let fan_in: Vec<&str> = report.by_fan_in.iter().map(|h| h.symbol.as_str()).collect();
+    assert_eq!(fan_in, ["log"]);
+    assert_eq!(report.by_fan_out.len(), 3);
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use codesearch::{
+    CallGraphRepository, CallGraphUseCase, DuckdbCallGraphRepository, DuckdbMetadataRepository,
+    ImpactAnalysis, ImpactAnalysisUseCase, Language, ParserService, ReferenceKind, SymbolReference,
+    TreeSitterParser,
+};
+
+const FIXTURE: &str = "tests/fixtures/impls/parser.rs";
+
+/// Call graph of the fixture: the `implementation` edges the parser records
+/// for its `impl ParserService for …` blocks, plus the call through the trait
+/// object that rust-analyzer's SCIP index reports as a call to the trait
+/// method.
+async fn fixture_call_graph() -> Arc<CallGraphUseCase> {
+    let metadata_repository =
+        Arc::new(DuckdbMetadataRepository::in_memory().expect("Failed to create DuckDB"));
+    let call_graph_repo: Arc<dyn CallGraphRepository> = Arc::new(
+        DuckdbCallGraphRepository::with_connection(metadata_repository.shared_connection())
+            .await
+            .expect("Failed to create call graph repo"),
+    );
+    let call_graph = Arc::new(CallGraphUseCase::new(call_graph_repo));
+
+    let content = std::fs::read_to_string(FIXTURE).expect("failed to read fixture");
+    let mut references = TreeSitterParser::new()
+        .extract_references(&content, "src/parser.rs", Language::Rust, "repo1")
+        .await
+        .expect("failed to extract references");
+    references.push(SymbolReference::new(
+        Some("IndexUseCase::run".to_string()),
+        "ParserService::parse_file".to_string(),
+        "src/parser.rs".to_string(),
+        "src/parser.rs".to_string(),
+        33,
+        21,
+        ReferenceKind::Call,
+        Language::Rust,
+        "repo1".to_string(),
+    ));
+    call_graph
+        .save_references(&references)
+        .await
+        .expect("Failed to seed references");
+    call_graph
+}
+
+fn affected(analysis: &ImpactAnalysis) -> Vec<(&str, &str)> {
+    analysis
+        .by_depth
+        .iter()
+        .flatten()
+        .map(|n| (n.symbol.as_str(), n.reference_kind.as_str()))
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_changing_an_implementation_reaches_callers_through_the_trait() {
+    let call_graph = fixture_call_graph().await;
+
+    let analysis = ImpactAnalysisUseCase::new(call_graph.clone())
+        .analyze("TreeSitterParser::parse_file", None, false)
+        .await
+        .expect("impact failed");
+
+    let nodes = affected(&analysis);
+    assert!(
+        nodes.contains(&("ParserService::parse_file", "dispatch")),
+        "{nodes:?}"
+    );
+    assert!(nodes.contains(&("IndexUseCase::run", "call")), "{nodes:?}");
+    assert!(
+        !nodes
+            .iter()
+            .any(|(s, _)| *s == "MarkdownParser::parse_file"),
+        "sibling implementations are not affected: {nodes:?}"
+    );
+    assert!(analysis
+        .edges
+        .iter()
+        .any(|e| e.caller == "ParserService::parse_file"
+            && e.callee == "TreeSitterParser::parse_file"
+            && e.reference_kind == "dispatch"));
+
+    let analysis = ImpactAnalysisUseCase::new(call_graph)
+        .with_follow_impls(false)
+        .analyze("TreeSitterParser::parse_file", None, false)
+        .await
+        .expect("impact failed");
+    assert_eq!(analysis.total_affected, 0, "{:?}", affected(&analysis));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_changing_a_trait_method_reaches_its_implementations_and_callers() {
+    let call_graph = fixture_call_graph().await;
+
+    let analysis = ImpactAnalysisUseCase::new(call_graph.clone())
+        .analyze("ParserService::parse_file", None, false)
+        .await
+        .expect("impact failed");
+
+    let nodes = affected(&analysis);
+    assert!(nodes.contains(&("IndexUseCase::run", "call")), "{nodes:?}");
+    assert!(
+        nodes.contains(&("TreeSitterParser::parse_file", "implementation")),
+        "{nodes:?}"
+    );
+    assert!(
+        nodes.contains(&("MarkdownParser::parse_file", "implementation")),
+        "{nodes:?}"
+    );
+
+    let analysis = ImpactAnalysisUseCase::new(call_graph)
+        .with_follow_impls(false)
+        .analyze("ParserService::parse_file", None, false)
+        .await
+        .expect("impact failed");
+    assert_eq!(affected(&analysis), [("IndexUseCase::run", "call")]);
+}
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use codesearch::{
+    read_index_archive, read_index_archives, write_index_archive, write_index_archives,
+    CallGraphRepository, CallGraphUseCase, CodeChunk, DuckdbCallGraphRepository,
+    DuckdbFileHashRepository, DuckdbMetadataRepository, Embedding, FileHash, FileHashRepository,
+    InMemoryVectorRepository, IndexArchiveUseCase, Language, MetadataRepository, NodeType,
+    ReferenceKind, Repository, SymbolReference, VectorRepository, VectorStore,
+};
+use tempfile::tempdir;
+
+struct Store {
+    metadata: Arc<DuckdbMetadataRepository>,
+    vectors: Arc<InMemoryVectorRepository>,
+    file_hashes: Arc<dyn FileHashRepository>,
+    call_graph: Arc<CallGraphUseCase>,
+}
+
+impl Store {
+    async fn new() -> Self {
+        let metadata =
+            Arc::new(DuckdbMetadataRepository::in_memory().expect("Failed to create DuckDB"));
+        let shared_conn = metadata.shared_connection();
+        let file_hashes: Arc<dyn FileHashRepository> = Arc::new(
+            DuckdbFileHashRepository::with_connection(Arc::clone(&shared_conn))
+                .await
+                .expect("Failed to create file hash repo"),
+        );
+        let call_graph_repo: Arc<dyn CallGraphRepository> = Arc::new(
+            DuckdbCallGraphRepository::with_connection(shared_conn)
+                .await
+                .expect("Failed to create call graph repo"),
+        );
+        Self {
+            metadata,
+            vectors: Arc::new(InMemoryVectorRepository::new()),
+            file_hashes,
+            call_graph: Arc::new(CallGraphUseCase::new(call_graph_repo)),
+        }
+    }
+
+    fn archive_use_case(&self, model: &str, dimensions: usize) -> IndexArchiveUseCase {
+        IndexArchiveUseCase::new(
+            self.metadata.clone(),
+            self.vectors.clone(),
+            self.file_hashes.clone(),
+            self.call_graph.clone(),
+            model,
+            dimensions,
+        )
+    }
+}
+
+/// Seed `store` with one indexed repository: two chunks (one without an
+/// embedding), a file hash and a call-graph reference.
+async fn seed(store: &Store) -> Repository {
+    let repository = Repository::new("demo".to_string(), "/build/demo".to_string());
+    let id = repository.id().to_string();
+    let chunk = |path: &str, content: &str| {
+        CodeChunk::new(
+            path.to_string(),
+            content.to_string(),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            id.clone(),
+        )
+    };
+    let add = chunk("src/lib.rs", "fn add() { sub() }").with_symbol_name("add");
+    let sub = chunk("src/sub.rs", "fn sub() {}").with_symbol_name("sub");
+    store
+        .vectors
+        .save_batch(
+            std::slice::from_ref(&add),
+            &[Embedding::new(
+                add.id().to_string(),
+                vec![1.0, 0.0, 0.0],
+                "mock".to_string(),
+            )],
+        )
+        .await
+        .unwrap();
+    store
+        .vectors
+        .save_batch(std::slice::from_ref(&sub), &[])
+        .await
+        .unwrap();
+    store
+        .file_hashes
+        .save_batch(&[FileHash::new(
+            "src/lib.rs".to_string(),
+            "hash".to_string(),
+            id.clone(),
+        )])
+        .await
+        .unwrap();
+    store
+        .call_graph
+        .save_references(&[SymbolReference::new(
+            Some("add".to_string()),
+            "sub".to_string(),
+            "src/lib.rs".to_string(),
+            "src/lib.rs".to_string(),
+            1,
+            12,
+            ReferenceKind::Call,
+            Language::Rust,
+            id.clone(),
+        )])
+        .await
+        .unwrap();
+    store.metadata.save(&repository).await.unwrap();
+    repository
+}
+
+#[tokio::test]
+async fn export_then_import_restores_the_index_at_a_new_path() {
+    let source = Store::new().await;
+    let repository = seed(&source).await;
+    let id = repository.id();
+
+    let dir = tempdir().unwrap();
+    let archive_path = dir.path().join("index.tar.zst");
+    let archive = source
+        .archive_use_case("mock", 3)
+        .export(id)
+        .await
+        .expect("export");
+    assert_eq!(archive.manifest.chunk_count, 2);
+    assert_eq!(archive.manifest.embedding_count, 1);
+    assert_eq!(archive.manifest.embedding_model, "mock");
+    write_index_archive(&archive, &archive_path).expect("write archive");
+
+    let target = Store::new().await;
+    let checkout = dir.path().join("checkout");
+    std::fs::create_dir_all(&checkout).unwrap();
+    let checkout = checkout.to_string_lossy().to_string();
+    let imported = target
+        .archive_use_case("mock", 3)
+        .import(
+            read_index_archive(&archive_path).expect("read archive"),
+            &checkout,
+            VectorStore::DuckDb,
+            "team",
+        )
+        .await
+        .expect("import");
+
+    assert_eq!(imported.id(), id);
+    assert_eq!(imported.path(), checkout);
+    assert_eq!(imported.namespace(), Some("team"));
+    let stored = target.metadata.find_by_path(&checkout).await.unwrap();
+    assert_eq!(stored.map(|r| r.id().to_string()), Some(id.to_string()));
+
+    assert_eq!(target.vectors.count().await.unwrap(), 2);
+    let lib = target
+        .vectors
+        .find_chunks_by_file(id, "src/lib.rs")
+        .await
+        .unwrap();
+    let embeddings = target
+        .vectors
+        .fetch_embeddings(&[lib[0].id()])
+        .await
+        .unwrap();
+    assert_eq!(embeddings[0].vector(), &[1.0, 0.0, 0.0]);
+    assert_eq!(
+        target
+            .file_hashes
+            .find_by_repository(id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        target
+            .call_graph
+            .find_by_repository(id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn import_refuses_a_different_embedding_space() {
+    let source = Store::new().await;
+    let repository = seed(&source).await;
+    let archive = source
+        .archive_use_case("mock", 3)
+        .export(repository.id())
+        .await
+        .expect("export");
+
+    let target = Store::new().await;
+    let err = target
+        .archive_use_case("other-model", 3)
+        .import(archive, "/tmp/demo", VectorStore::DuckDb, "team")
+        .await
+        .expect_err("model mismatch must be refused");
+    assert!(err.to_string().contains("other-model"), "{err}");
+    assert_eq!(target.vectors.count().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn namespace_export_round_trips_every_repository() {
+    let source = Store::new().await;
+    seed(&source).await;
+    let team = |name: &str| {
+        Repository::new_with_storage(
+            name.to_string(),
+            format!("/build/{name}"),
+            VectorStore::DuckDb,
+            Some("team".to_string()),
+            None,
+        )
+    };
+    let (api, web) = (team("api"), team("web"));
+    source.metadata.save(&api).await.unwrap();
+    source.metadata.save(&web).await.unwrap();
+
+    let archives = source
+        .archive_use_case("mock", 3)
+        .export_namespace("team")
+        .await
+        .expect("export");
+    let mut names: Vec<&str> = archives.iter().map(|a| a.repository.name()).collect();
+    names.sort();
+    assert_eq!(names, vec!["api", "web"], "other namespaces are left out");
+
+    let dir = tempdir().unwrap();
+    let archive_path = dir.path().join("team.tar.zst");
+    write_index_archives(&archives, &archive_path).expect("write archive");
+
+    // Without a checkout the archived paths are kept.
+    let target = Store::new().await;
+    let use_case = target.archive_use_case("mock", 3);
+    for archive in read_index_archives(&archive_path).expect("read archive") {
+        let path = archive.repository.path().to_string();
+        use_case
+            .import(archive, &path, VectorStore::DuckDb, "team")
+            .await
+            .expect("import");
+    }
+    let imported = target.metadata.find_by_path("/build/web").await.unwrap();
+    assert_eq!(
+        imported.map(|r| r.id().to_string()),
+        Some(web.id().to_string())
+    );
+    assert_eq!(target.metadata.list().await.unwrap().len(), 2);
+}
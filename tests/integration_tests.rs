@@ -3,8 +3,9 @@ use std::sync::Arc;
 use codesearch::{
     CallGraphQuery, CallGraphRepository, CallGraphUseCase, CodeChunk, DuckdbCallGraphRepository,
     DuckdbFileHashRepository, DuckdbMetadataRepository, FileHashRepository,
-    InMemoryVectorRepository, IndexRepositoryUseCase, Language, ListRepositoriesUseCase,
-    MockEmbedding, NodeType, ParserService, ReferenceKind, SearchCodeUseCase, SearchQuery,
+    InMemoryVectorRepository, IndexFilters, IndexProgress, IndexRepositoryUseCase, Language,
+    ListRepositoriesUseCase, MockEmbedding, NodeType, ParserService, PruneRepositoryUseCase,
+    ReferenceKind, SearchCodeUseCase, SearchQuery, SimilarCodeUseCase, SnippetLookupUseCase,
     SymbolReference, TreeSitterParser, VectorStore,
 };
 use tempfile::tempdir;
@@ -160,7 +161,7 @@ async fn test_language_detection() {
     );
     assert_eq!(
         Language::from_path(Path::new("readme.md")),
-        Language::Unknown
+        Language::Markdown
     );
 }
 
@@ -316,6 +317,94 @@ pub fn subtract(a: i32, b: i32) -> i32 {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_similar_uses_stored_embedding_and_skips_source() {
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let src_dir = temp_dir.path().join("src");
+    std::fs::create_dir_all(&src_dir).expect("Failed to create src directory");
+    std::fs::write(
+        src_dir.join("lib.rs"),
+        r#"
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub fn subtract(a: i32, b: i32) -> i32 {
+    a - b
+}
+"#,
+    )
+    .expect("Failed to write test file");
+
+    let embedding_service = Arc::new(MockEmbedding::new());
+    IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        embedding_service,
+    )
+    .execute(
+        temp_dir.path().to_str().unwrap(),
+        Some("test-repo"),
+        VectorStore::InMemory,
+        None,
+        false,
+    )
+    .await
+    .expect("Indexing failed");
+
+    let use_case = SimilarCodeUseCase::new(env.vector_repo.clone());
+    let similar = use_case
+        .execute("src/lib.rs", 3, &SearchQuery::new("").with_limit(5))
+        .await
+        .expect("Similarity search failed");
+
+    assert_eq!(similar.source.symbol_name(), Some("add"));
+    assert!(
+        !similar.results.is_empty(),
+        "Should find the other function"
+    );
+    assert!(
+        similar
+            .results
+            .iter()
+            .all(|r| r.chunk().id() != similar.source.id()),
+        "The source chunk must not be returned"
+    );
+
+    let err = use_case
+        .execute("src/lib.rs", 100, &SearchQuery::new("").with_limit(5))
+        .await
+        .expect_err("no chunk covers line 100");
+    assert!(err.is_not_found());
+}
+
+#[tokio::test]
+async fn test_search_rejects_unknown_node_type() {
+    let env = setup_test_env().await;
+    let embedding_service = Arc::new(MockEmbedding::new());
+    let search_use_case = SearchCodeUseCase::new(env.vector_repo.clone(), embedding_service);
+
+    let query = SearchQuery::new("parse config")
+        .with_node_types(vec!["Function".to_string(), "fn".to_string()]);
+    let err = search_use_case
+        .execute(query)
+        .await
+        .expect_err("unknown node type must be rejected");
+
+    assert!(err.is_invalid_input());
+    assert!(err.to_string().contains("'fn'"), "got: {err}");
+    assert!(
+        err.to_string()
+            .contains("function, constructor, property, class"),
+        "got: {err}"
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_hybrid_search_returns_results() {
     let env = setup_test_env().await;
@@ -563,6 +652,793 @@ async fn test_hybrid_search_with_text_search_disabled_returns_semantic_only() {
 /// `addContext` (not the full SCIP path). The reference kind is
 /// `Call` (inferred from the `().` descriptor suffix when SymbolKind is
 /// Unspecified).
+#[tokio::test(flavor = "multi_thread")]
+async fn test_index_filters_are_applied_and_persisted() {
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    for dir in ["src/generated", "vendor"] {
+        std::fs::create_dir_all(root.join(dir)).expect("Failed to create directory");
+    }
+    let function = "pub fn add(a: i32, b: i32) -> i32 { a + b }";
+    for file in ["src/lib.rs", "src/generated/out.rs", "vendor/dep.rs"] {
+        std::fs::write(root.join(file), function).expect("Failed to write test file");
+    }
+
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    );
+    let filters = IndexFilters::new(
+        vec!["src/**".to_string()],
+        vec!["src/generated/**".to_string()],
+    );
+    let repository = index_use_case
+        .execute_with_filters(
+            root.to_str().unwrap(),
+            Some("filtered-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+            Some(filters.clone()),
+        )
+        .await
+        .expect("Indexing failed");
+
+    assert_eq!(
+        repository.file_count(),
+        1,
+        "Only src/lib.rs should be indexed"
+    );
+    assert_eq!(repository.index_filters(), &filters);
+
+    // Re-indexing without explicit filters reuses the stored ones, so a new
+    // excluded file is still skipped.
+    std::fs::write(root.join("src/generated/more.rs"), function)
+        .expect("Failed to write test file");
+    let repository = index_use_case
+        .execute(
+            root.to_str().unwrap(),
+            Some("filtered-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Re-indexing failed");
+
+    assert_eq!(repository.file_count(), 1);
+    assert_eq!(repository.index_filters(), &filters);
+}
+
+/// `update_file` re-indexes a single changed file and drops the chunks of a
+/// removed one, keeping the repository counts in step.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_file_reindexes_changed_and_removed_files() {
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::write(root.join("a.rs"), "pub fn alpha(a: i32) -> i32 { a + 1 }")
+        .expect("Failed to write test file");
+    std::fs::write(root.join("b.rs"), "pub fn beta(b: i32) -> i32 { b * 2 }")
+        .expect("Failed to write test file");
+
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    );
+    let repository = index_use_case
+        .execute(
+            root.to_str().unwrap(),
+            Some("watched-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Indexing failed");
+    assert_eq!(repository.file_count(), 2);
+
+    std::fs::write(
+        root.join("a.rs"),
+        "pub fn alpha(a: i32) -> i32 { a + 1 }\n\npub fn gamma(c: i32) -> i32 { c - 3 }",
+    )
+    .expect("Failed to write test file");
+    let repository = index_use_case
+        .update_file(repository.id(), &root.join("a.rs"))
+        .await
+        .expect("Updating a.rs failed");
+    assert_eq!(repository.file_count(), 2);
+    assert_eq!(repository.chunk_count(), 3);
+
+    std::fs::remove_file(root.join("b.rs")).expect("Failed to remove test file");
+    let repository = index_use_case
+        .update_file(repository.id(), &root.join("b.rs"))
+        .await
+        .expect("Updating b.rs failed");
+    assert_eq!(repository.file_count(), 1);
+    assert_eq!(repository.chunk_count(), 2);
+
+    let hashes = env
+        .file_hash_repo
+        .find_by_repository(repository.id())
+        .await
+        .unwrap();
+    let paths: Vec<&str> = hashes.iter().map(|h| h.file_path()).collect();
+    assert_eq!(paths, vec!["a.rs"]);
+}
+
+/// `execute_incremental` reports added, changed and removed files against the
+/// hashes stored by the previous run.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_execute_incremental_reports_file_changes() {
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    std::fs::write(root.join("a.rs"), "pub fn alpha(a: i32) -> i32 { a + 1 }")
+        .expect("Failed to write test file");
+    std::fs::write(root.join("b.rs"), "pub fn beta(b: i32) -> i32 { b * 2 }")
+        .expect("Failed to write test file");
+
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    );
+    let path = root.to_str().unwrap();
+    let first = index_use_case
+        .execute_incremental(
+            path,
+            Some("updated-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Indexing failed");
+    assert_eq!(first.files_added, 2);
+    assert_eq!(first.chunk_delta, 2);
+
+    std::fs::write(
+        root.join("a.rs"),
+        "pub fn alpha(a: i32) -> i32 { a + 1 }\n\npub fn gamma(c: i32) -> i32 { c - 3 }",
+    )
+    .expect("Failed to write test file");
+    std::fs::remove_file(root.join("b.rs")).expect("Failed to remove test file");
+    std::fs::write(root.join("c.rs"), "pub fn delta(d: i32) -> i32 { d / 4 }")
+        .expect("Failed to write test file");
+
+    let update = index_use_case
+        .execute_incremental(
+            path,
+            Some("updated-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Update failed");
+    assert_eq!(update.files_added, 1);
+    assert_eq!(update.files_changed, 1);
+    assert_eq!(update.files_removed, 1);
+    assert_eq!(update.chunk_delta, 1);
+    assert_eq!(update.repository.file_count(), 2);
+
+    let unchanged = index_use_case
+        .execute_incremental(
+            path,
+            Some("updated-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("No-op update failed");
+    assert_eq!(
+        (
+            unchanged.files_added,
+            unchanged.files_changed,
+            unchanged.files_removed,
+            unchanged.chunk_delta
+        ),
+        (0, 0, 0, 0)
+    );
+}
+
+/// Chunks of a file that vanished without a file-hash record are found by
+/// `prune` (kept on a dry run) and also dropped by the next incremental run.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prune_removes_chunks_of_missing_files() {
+    use codesearch::VectorRepository;
+
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    std::fs::write(root.join("a.rs"), "pub fn alpha(a: i32) -> i32 { a + 1 }")
+        .expect("Failed to write test file");
+
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    );
+    let path = root.to_str().unwrap();
+    let repository = index_use_case
+        .execute(path, Some("prune-repo"), VectorStore::InMemory, None, false)
+        .await
+        .expect("Indexing failed");
+
+    let orphan = |file: &str| {
+        CodeChunk::new(
+            file.to_string(),
+            "pub fn orphan() {}".to_string(),
+            1,
+            1,
+            Language::Rust,
+            NodeType::Function,
+            repository.id().to_string(),
+        )
+    };
+    env.vector_repo
+        .save_batch(&[orphan("gone.rs")], &[])
+        .await
+        .expect("save_batch");
+
+    let prune = PruneRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+    );
+    let dry_run = prune
+        .execute(repository.id(), true)
+        .await
+        .expect("Dry run failed");
+    assert_eq!(dry_run.stale_files, vec!["gone.rs".to_string()]);
+    assert_eq!(dry_run.chunks_removed, 0);
+    assert_eq!(
+        env.vector_repo
+            .list_files(repository.id())
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let report = prune
+        .execute(repository.id(), false)
+        .await
+        .expect("Prune failed");
+    assert_eq!(report.stale_files, vec!["gone.rs".to_string()]);
+    assert_eq!(report.chunks_removed, 1);
+    let files = env.vector_repo.list_files(repository.id()).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].file_path, "a.rs");
+
+    env.vector_repo
+        .save_batch(&[orphan("also_gone.rs")], &[])
+        .await
+        .expect("save_batch");
+    index_use_case
+        .execute(path, Some("prune-repo"), VectorStore::InMemory, None, false)
+        .await
+        .expect("Incremental indexing failed");
+    let files = env.vector_repo.list_files(repository.id()).await.unwrap();
+    assert_eq!(files.len(), 1, "incremental indexing prunes stale files");
+}
+
+/// Oversized and binary files are skipped and counted, whatever their extension.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_oversized_and_binary_files_are_skipped() {
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    std::fs::write(root.join("small.rs"), "pub fn small() {}").unwrap();
+    std::fs::write(root.join("bundle.js"), "var x = 1;\n".repeat(100)).unwrap();
+    std::fs::write(root.join("blob.rs"), b"pub fn \0\x01\x02").unwrap();
+
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    )
+    .with_max_file_size(512);
+    let repository = index_use_case
+        .execute(
+            root.to_str().unwrap(),
+            Some("guard-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Indexing failed");
+
+    assert_eq!(repository.file_count(), 1);
+    assert_eq!(index_use_case.skipped_file_count(), 2);
+}
+
+#[tokio::test]
+async fn test_multiple_roots_index_as_one_repository() {
+    use codesearch::MetadataRepository;
+
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let base = temp_dir.path();
+    for dir in ["api", "web", "unrelated"] {
+        std::fs::create_dir(base.join(dir)).unwrap();
+        std::fs::write(
+            base.join(dir).join("lib.rs"),
+            format!("pub fn {dir}_entry() {{}}\n"),
+        )
+        .unwrap();
+    }
+    let api = base.join("api");
+    let web = base.join("web");
+
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    );
+    let paths = [api.to_str().unwrap(), web.to_str().unwrap()];
+    let repository = index_use_case
+        .execute_with_roots(
+            &paths,
+            Some("combined"),
+            VectorStore::InMemory,
+            None,
+            false,
+            None,
+        )
+        .await
+        .expect("Indexing failed");
+
+    assert_eq!(repository.file_count(), 2);
+    assert_eq!(repository.roots(), ["api", "web"]);
+    let stored = env
+        .metadata_repository
+        .find_by_id(repository.id())
+        .await
+        .unwrap()
+        .expect("repository saved");
+    assert_eq!(stored.roots(), ["api", "web"]);
+
+    // Re-indexing picks up changes under either root and still ignores
+    // siblings outside them.
+    std::fs::write(web.join("extra.rs"), "pub fn extra() {}\n").unwrap();
+    std::fs::write(base.join("unrelated").join("more.rs"), "pub fn more() {}\n").unwrap();
+    let repository = index_use_case
+        .execute_with_roots(
+            &paths,
+            Some("combined"),
+            VectorStore::InMemory,
+            None,
+            false,
+            None,
+        )
+        .await
+        .expect("Re-indexing failed");
+    assert_eq!(repository.file_count(), 3);
+}
+
+/// Parsing files in parallel yields the same index as parsing them one at a
+/// time.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_parallel_parsing_matches_sequential_index() {
+    use codesearch::VectorRepository;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    for i in 0..24 {
+        std::fs::write(
+            root.join(format!("mod_{i}.rs")),
+            format!("pub fn f{i}(a: i32) -> i32 {{ a + {i} }}\n\npub struct S{i};\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            root.join(format!("mod_{i}.py")),
+            format!("def g{i}(x):\n    return x * {i}\n"),
+        )
+        .unwrap();
+    }
+    // Unparseable input is skipped per file in both modes.
+    std::fs::write(root.join("broken.rs"), [0xff, 0xfe, 0x00]).unwrap();
+
+    let mut results = Vec::new();
+    for jobs in [1, 8] {
+        let env = setup_test_env().await;
+        let repository = IndexRepositoryUseCase::new(
+            env.metadata_repository.clone(),
+            env.vector_repo.clone(),
+            env.file_hash_repo.clone(),
+            env.call_graph_use_case.clone(),
+            env.parser.clone(),
+            Arc::new(MockEmbedding::new()),
+        )
+        .with_parse_concurrency(jobs)
+        .execute(
+            root.to_str().unwrap(),
+            None,
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Indexing failed");
+        let mut files: Vec<(String, u64)> = env
+            .vector_repo
+            .list_files(repository.id())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.file_path, f.chunk_count))
+            .collect();
+        files.sort();
+        results.push((repository.file_count(), repository.chunk_count(), files));
+    }
+
+    assert_eq!(results[0].2.len(), 48);
+    assert_eq!(results[0], results[1]);
+}
+
+/// Git stand-in: reports a fixed change list and remembers the revision it
+/// was asked to diff against.
+struct FakeGit {
+    head: std::sync::Mutex<String>,
+    changes: Vec<codesearch::GitChange>,
+    lines: Vec<codesearch::ChangedLines>,
+    since: std::sync::Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl codesearch::GitDiff for FakeGit {
+    async fn head(&self, _repo_path: &std::path::Path) -> Option<String> {
+        Some(self.head.lock().unwrap().clone())
+    }
+
+    async fn changes_since(
+        &self,
+        _repo_path: &std::path::Path,
+        since: &str,
+    ) -> Result<Vec<codesearch::GitChange>, codesearch::DomainError> {
+        *self.since.lock().unwrap() = Some(since.to_string());
+        Ok(self.changes.clone())
+    }
+
+    async fn changed_lines(
+        &self,
+        _repo_path: &std::path::Path,
+        since: &str,
+    ) -> Result<Vec<codesearch::ChangedLines>, codesearch::DomainError> {
+        *self.since.lock().unwrap() = Some(since.to_string());
+        Ok(self.lines.clone())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_since_indexes_only_git_changes() {
+    use codesearch::{GitChange, MetadataRepository, VectorRepository};
+
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    std::fs::write(root.join("a.rs"), "pub fn alpha() {}").unwrap();
+    std::fs::write(root.join("b.rs"), "pub fn beta() {}").unwrap();
+
+    let git = Arc::new(FakeGit {
+        head: std::sync::Mutex::new("c1".to_string()),
+        changes: vec![
+            GitChange::Modified("a.rs".to_string()),
+            GitChange::Renamed {
+                from: "b.rs".to_string(),
+                to: "c.rs".to_string(),
+            },
+        ],
+        lines: Vec::new(),
+        since: std::sync::Mutex::new(None),
+    });
+    let index_use_case = || {
+        IndexRepositoryUseCase::new(
+            env.metadata_repository.clone(),
+            env.vector_repo.clone(),
+            env.file_hash_repo.clone(),
+            env.call_graph_use_case.clone(),
+            env.parser.clone(),
+            Arc::new(MockEmbedding::new()),
+        )
+        .with_git(git.clone())
+    };
+    let path = root.to_str().unwrap();
+    let repository = index_use_case()
+        .execute(path, Some("since-repo"), VectorStore::InMemory, None, false)
+        .await
+        .expect("Indexing failed");
+    assert_eq!(repository.indexed_commit(), Some("c1"));
+
+    std::fs::write(root.join("a.rs"), "pub fn alpha_v2() {}").unwrap();
+    std::fs::rename(root.join("b.rs"), root.join("c.rs")).unwrap();
+    // Not reported by git, so a diff-driven run leaves it alone.
+    std::fs::write(root.join("d.rs"), "pub fn delta() {}").unwrap();
+    *git.head.lock().unwrap() = "c2".to_string();
+
+    let repository = index_use_case()
+        .with_since(None)
+        .execute(path, Some("since-repo"), VectorStore::InMemory, None, false)
+        .await
+        .expect("Incremental indexing failed");
+    assert_eq!(git.since.lock().unwrap().as_deref(), Some("c1"));
+    assert_eq!(repository.indexed_commit(), Some("c2"));
+
+    let mut files: Vec<String> = env
+        .vector_repo
+        .list_files(repository.id())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|f| f.file_path)
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["a.rs".to_string(), "c.rs".to_string()]);
+
+    let stored = env
+        .metadata_repository
+        .find_by_id(repository.id())
+        .await
+        .unwrap()
+        .expect("repository");
+    assert_eq!(stored.indexed_commit(), Some("c2"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_diff_impact_analyses_symbols_of_changed_lines() {
+    use codesearch::{ChangedLines, DiffImpactUseCase, ImpactAnalysisUseCase};
+
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    // Rust calls come from SCIP, so the call graph here is parsed from Go.
+    std::fs::write(
+        temp_dir.path().join("lib.go"),
+        "package lib\n\n\
+         func helper() int {\n\treturn 1\n}\n\n\
+         func caller() int {\n\treturn helper() + 1\n}\n\n\
+         func top() int {\n\treturn caller()\n}\n\n\
+         func unrelated() int {\n\treturn 2\n}\n",
+    )
+    .unwrap();
+    let repository = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    )
+    .execute(
+        temp_dir.path().to_str().unwrap(),
+        Some("diff-repo"),
+        VectorStore::InMemory,
+        None,
+        false,
+    )
+    .await
+    .expect("Indexing failed");
+
+    // The body of `helper` changed; `unrelated` did not.
+    let git = Arc::new(FakeGit {
+        head: std::sync::Mutex::new("c1".to_string()),
+        changes: Vec::new(),
+        lines: vec![ChangedLines {
+            file_path: "lib.go".to_string(),
+            ranges: vec![(4, 4)],
+        }],
+        since: std::sync::Mutex::new(None),
+    });
+    let report = DiffImpactUseCase::new(
+        ImpactAnalysisUseCase::new(env.call_graph_use_case.clone()),
+        env.vector_repo.clone(),
+        git.clone(),
+    )
+    .analyze(temp_dir.path(), repository.id(), "origin/main")
+    .await
+    .expect("Diff impact failed");
+
+    assert_eq!(git.since.lock().unwrap().as_deref(), Some("origin/main"));
+    let changed: Vec<&str> = report
+        .changed_symbols
+        .iter()
+        .map(|c| c.symbol.as_str())
+        .collect();
+    assert_eq!(changed, vec!["helper"]);
+    let affected: Vec<&str> = report.changed_symbols[0]
+        .impact
+        .by_depth
+        .iter()
+        .flatten()
+        .map(|n| n.symbol.as_str())
+        .collect();
+    assert!(affected.contains(&"caller"), "{affected:?}");
+    assert!(affected.contains(&"top"), "{affected:?}");
+    assert_eq!(report.total_affected, affected.len());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_embedding_cache_reuses_vectors_on_reindex() {
+    use codesearch::DuckdbEmbeddingCache;
+
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    std::fs::write(temp_dir.path().join("a.rs"), "pub fn alpha() {}").unwrap();
+    std::fs::write(temp_dir.path().join("b.rs"), "pub fn beta() {}").unwrap();
+
+    let cache = Arc::new(DuckdbEmbeddingCache::in_memory().unwrap());
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    )
+    .with_embedding_cache(cache);
+    let path = temp_dir.path().to_str().unwrap();
+
+    index_use_case
+        .execute(path, Some("cache-repo"), VectorStore::InMemory, None, false)
+        .await
+        .expect("Indexing failed");
+    let first = index_use_case.embedding_cache_stats();
+    assert_eq!(first.hits, 0);
+    assert!(first.misses > 0);
+
+    // A forced re-index parses everything again, but no chunk changed.
+    index_use_case
+        .execute(path, Some("cache-repo"), VectorStore::InMemory, None, true)
+        .await
+        .expect("Re-indexing failed");
+    let second = index_use_case.embedding_cache_stats();
+    assert_eq!(second.hits, first.misses);
+    assert_eq!(second.misses, 0);
+    assert_eq!(second.hit_rate(), Some(1.0));
+}
+
+/// `chunks_for_file` lists a file's stored chunks in line order, or just the
+/// ones covering a line.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_chunks_for_file_lists_stored_chunks() {
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    std::fs::write(
+        root.join("lib.rs"),
+        "pub fn alpha(a: i32) -> i32 {\n    a + 1\n}\n\npub fn beta(b: i32) -> i32 {\n    b * 2\n}\n",
+    )
+    .expect("Failed to write test file");
+
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    );
+    let repository = index_use_case
+        .execute(
+            root.to_str().unwrap(),
+            Some("chunked-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Indexing failed");
+
+    let lookup = SnippetLookupUseCase::new(env.vector_repo.clone());
+    let all = lookup
+        .chunks_for_file(repository.id(), "lib.rs", None)
+        .await
+        .unwrap();
+    let symbols: Vec<_> = all.iter().map(|c| c.symbol_name().unwrap()).collect();
+    assert_eq!(symbols, vec!["alpha", "beta"]);
+
+    let at_line = lookup
+        .chunks_for_file(repository.id(), "lib.rs", Some(6))
+        .await
+        .unwrap();
+    assert_eq!(at_line.len(), 1);
+    assert_eq!(at_line[0].symbol_name(), Some("beta"));
+
+    let between = lookup
+        .chunks_for_file(repository.id(), "lib.rs", Some(4))
+        .await
+        .unwrap();
+    assert!(between.is_empty());
+}
+
+/// The progress callback hears about every file, ending at the total.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_progress_callback_reports_every_file() {
+    let env = setup_test_env().await;
+
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    for file in ["a.rs", "b.rs", "c.rs"] {
+        std::fs::write(
+            root.join(file),
+            "pub fn add(a: i32, b: i32) -> i32 { a + b }",
+        )
+        .expect("Failed to write test file");
+    }
+
+    let updates: Arc<std::sync::Mutex<Vec<IndexProgress>>> = Arc::default();
+    let sink = Arc::clone(&updates);
+    let index_use_case = IndexRepositoryUseCase::new(
+        env.metadata_repository.clone(),
+        env.vector_repo.clone(),
+        env.file_hash_repo.clone(),
+        env.call_graph_use_case.clone(),
+        env.parser.clone(),
+        Arc::new(MockEmbedding::new()),
+    )
+    .with_progress_callback(Arc::new(move |progress| {
+        sink.lock().unwrap().push(progress);
+    }));
+    index_use_case
+        .execute(
+            root.to_str().unwrap(),
+            Some("progress-repo"),
+            VectorStore::InMemory,
+            None,
+            false,
+        )
+        .await
+        .expect("Indexing failed");
+
+    let updates = updates.lock().unwrap();
+    assert_eq!(updates.len(), 3);
+    let last = updates.last().unwrap();
+    assert_eq!((last.files_done, last.files_total), (3, 3));
+    assert_eq!(last.chunks_so_far, 3);
+    // Embedding trails parsing, so it can only lag the chunk count.
+    assert!(updates
+        .iter()
+        .all(|p| p.embeddings_so_far <= p.chunks_so_far));
+    let mut files: Vec<&str> = updates.iter().map(|p| p.current_file.as_str()).collect();
+    files.sort();
+    assert_eq!(files, vec!["a.rs", "b.rs", "c.rs"]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_commonjs_require_captured_as_import_in_call_graph() {
     let env = setup_test_env().await;
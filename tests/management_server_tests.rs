@@ -37,6 +37,10 @@ async fn test_container() -> (Arc<Container>, TempDir) {
         embedding_model: None,
         embedding_dimensions: 384,
         parse_concurrency: 1,
+        postgres_url: None,
+        plain_progress: false,
+        verbose: false,
+        scip_indexers: Default::default(),
     };
     let container = Arc::new(
         Container::new(config)
@@ -408,6 +412,25 @@ async fn stats_endpoint_reports_totals() {
     server.abort();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn vacuum_endpoint_reports_file_sizes() {
+    let (container, _dir) = test_container().await;
+    let (base_url, server) = spawn_management_server_with_container(container).await;
+
+    let resp = reqwest::Client::new()
+        .post(format!("{base_url}/api/vacuum"))
+        .send()
+        .await
+        .expect("request to /api/vacuum failed");
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await.expect("response body was not JSON");
+    let fields = body.as_object().expect("object body");
+    assert!(fields.contains_key("size_before") && fields.contains_key("size_after"));
+
+    server.abort();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn search_endpoint_returns_results() {
     let (container, _dir) = test_container().await;
@@ -0,0 +1,97 @@
+//! Integration test for the MCP HTTP server's `/metrics` route.
+//!
+//! Installs the process-wide Prometheus recorder (hence one test per file),
+//! runs an index and a search against an in-memory container, then scrapes
+//! `/metrics` over loopback and checks the recorded series are exposed.
+
+use std::sync::Arc;
+
+use codesearch::{
+    install_prometheus_recorder, metrics_router, Container, ContainerConfig, EmbeddingTarget,
+    LlmTarget, RerankingTarget, SearchQuery, VectorStore,
+};
+use tempfile::tempdir;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn metrics_endpoint_exposes_search_and_index_series() {
+    let handle = install_prometheus_recorder().expect("failed to install recorder");
+
+    let data_dir = tempdir().expect("failed to create temp dir");
+    let container = Arc::new(
+        Container::new(ContainerConfig {
+            data_dir: data_dir.path().to_string_lossy().to_string(),
+            mock_embeddings: true,
+            namespace: "metrics".to_string(),
+            memory_storage: true,
+            no_rerank: true,
+            no_embeddings: false,
+            read_only: false,
+            expand_query: false,
+            embedding_target: EmbeddingTarget::Onnx,
+            reranking_target: RerankingTarget::Onnx,
+            llm_target: LlmTarget::Anthropic,
+            embedding_model: None,
+            embedding_dimensions: 384,
+            parse_concurrency: 1,
+            postgres_url: None,
+            plain_progress: false,
+            verbose: false,
+            scip_indexers: Default::default(),
+        })
+        .await
+        .expect("failed to build in-memory container"),
+    );
+
+    let repo = tempdir().expect("failed to create fixture dir");
+    std::fs::write(
+        repo.path().join("lib.rs"),
+        "/// Add two integers together.\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )
+    .expect("failed to write fixture file");
+    container
+        .index_use_case()
+        .execute(
+            repo.path().to_str().unwrap(),
+            Some("fixture-repo"),
+            VectorStore::InMemory,
+            Some("metrics".to_string()),
+            false,
+        )
+        .await
+        .expect("failed to index fixture");
+    container
+        .search_use_case()
+        .execute(SearchQuery::new("add two integers"))
+        .await
+        .expect("search failed");
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("failed to bind ephemeral port");
+    let addr = listener.local_addr().expect("failed to read local addr");
+    let server = tokio::spawn(async move {
+        axum::serve(listener, metrics_router(container, handle))
+            .await
+            .ok();
+    });
+
+    let response = reqwest::get(format!("http://{addr}/metrics"))
+        .await
+        .expect("request failed");
+    assert!(response.status().is_success());
+    let body = response.text().await.expect("failed to read body");
+
+    for series in [
+        "codesearch_search_requests_total 1",
+        "codesearch_index_requests_total 1",
+        "codesearch_chunks_indexed_total",
+        "codesearch_search_duration_seconds_bucket",
+        "codesearch_search_duration_seconds_count 1",
+        "codesearch_embedding_duration_seconds_bucket",
+        "codesearch_db_chunk_count{namespace=\"metrics\"}",
+    ] {
+        assert!(body.contains(series), "missing {series} in:\n{body}");
+    }
+
+    server.abort();
+}
@@ -0,0 +1,282 @@
+//! Integration tests for `PgVectorRepository`. They need a Postgres server
+//! with the pgvector extension and run only when `CODESEARCH_TEST_POSTGRES_URL`
+//! points at one, e.g.
+//!
+//! ```text
+//! docker run -d -p 5432:5432 -e POSTGRES_PASSWORD=pg pgvector/pgvector:pg16
+//! CODESEARCH_TEST_POSTGRES_URL=postgres://postgres:pg@localhost/postgres cargo test
+//! ```
+
+use codesearch::{
+    CodeChunk, Embedding, FileEntry, IndexState, Language, NodeType, PgVectorRepository,
+    SearchCursor, SearchQuery, VectorRepository,
+};
+
+const POSTGRES_URL_ENV: &str = "CODESEARCH_TEST_POSTGRES_URL";
+
+fn unit_vector(dim: usize, hot_index: usize) -> Vec<f32> {
+    let mut v = vec![0.0; dim];
+    v[hot_index] = 1.0;
+    v
+}
+
+/// Connect to a fresh schema named `namespace`, dropping whatever an earlier
+/// run left behind. Returns None (and prints a skip message) when no test
+/// database is configured.
+async fn try_connect(namespace: &str) -> Option<PgVectorRepository> {
+    let Ok(url) = std::env::var(POSTGRES_URL_ENV) else {
+        eprintln!("SKIP: {POSTGRES_URL_ENV} not set. Skipping Postgres test.");
+        return None;
+    };
+    let (client, connection) = tokio_postgres::connect(&url, tokio_postgres::NoTls)
+        .await
+        .expect("connect to test Postgres");
+    tokio::spawn(connection);
+    client
+        .batch_execute(&format!("DROP SCHEMA IF EXISTS \"{namespace}\" CASCADE"))
+        .await
+        .expect("drop test schema");
+    Some(
+        PgVectorRepository::connect(&url, namespace, 384)
+            .await
+            .expect("open Postgres repository"),
+    )
+}
+
+fn chunk(path: &str, content: &str, repository: &str) -> CodeChunk {
+    CodeChunk::new(
+        path.to_string(),
+        content.to_string(),
+        1,
+        3,
+        Language::Rust,
+        NodeType::Function,
+        repository.to_string(),
+    )
+}
+
+#[tokio::test]
+async fn pgvector_repository_can_save_search_and_delete() {
+    let Some(repo) = try_connect("cs_test_save").await else {
+        return;
+    };
+
+    let add = chunk(
+        "src/lib.rs",
+        "pub fn add(a: i32, b: i32) -> i32 { a + b }",
+        "repo-1",
+    )
+    .with_symbol_name("add");
+    let sub = chunk(
+        "src/sub.rs",
+        "pub fn sub(a: i32, b: i32) -> i32 { a - b }",
+        "repo-1",
+    )
+    .with_symbol_name("sub");
+    let embeddings = vec![
+        Embedding::new(
+            add.id().to_string(),
+            unit_vector(384, 0),
+            "mock".to_string(),
+        ),
+        Embedding::new(
+            sub.id().to_string(),
+            unit_vector(384, 1),
+            "mock".to_string(),
+        ),
+    ];
+    repo.save_batch(&[add.clone(), sub.clone()], &embeddings)
+        .await
+        .expect("save_batch");
+    repo.flush().await.expect("flush");
+
+    assert_eq!(repo.count().await.expect("count"), 2);
+    assert!(repo.has_embeddings().await.expect("has_embeddings"));
+
+    let query = SearchQuery::new("add numbers").with_limit(1);
+    let results = repo
+        .search(Some(&unit_vector(384, 0)), &query)
+        .await
+        .expect("search");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].chunk().id(), add.id());
+    assert!((results[0].score() - 1.0).abs() < 1e-5);
+
+    let fetched = repo
+        .fetch_embeddings(&[add.id(), "missing"])
+        .await
+        .expect("fetch_embeddings");
+    assert_eq!(fetched.len(), 1);
+    assert_eq!(fetched[0].vector(), unit_vector(384, 0).as_slice());
+
+    let deleted = repo
+        .delete_by_file_path("repo-1", "src/lib.rs")
+        .await
+        .expect("delete_by_file_path");
+    assert_eq!(deleted, 1);
+    repo.delete_by_repository("repo-1")
+        .await
+        .expect("delete_by_repository");
+    assert_eq!(repo.count().await.expect("count"), 0);
+}
+
+#[tokio::test]
+async fn pgvector_repository_text_leg_finds_keyword_matches() {
+    let Some(repo) = try_connect("cs_test_text").await else {
+        return;
+    };
+
+    let auth = chunk(
+        "src/auth.rs",
+        "pub fn authenticate_user(username: &str) -> bool { username == \"admin\" }",
+        "repo-text",
+    )
+    .with_symbol_name("authenticate_user");
+    let math = chunk(
+        "src/math.rs",
+        "pub fn add(a: i32, b: i32) -> i32 { a + b }",
+        "repo-text",
+    );
+    repo.save_batch(&[auth.clone(), math], &[])
+        .await
+        .expect("save_batch");
+
+    // No embeddings, so the full-text leg alone must find the chunk.
+    let query = SearchQuery::new("authenticate user")
+        .with_limit(5)
+        .with_text_search(true);
+    let results = repo.search(None, &query).await.expect("text search");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].chunk().id(), auth.id());
+    assert!(!repo.has_embeddings().await.expect("has_embeddings"));
+}
+
+#[tokio::test]
+async fn pgvector_repository_cursor_pages_through_ties() {
+    let Some(repo) = try_connect("cs_test_cursor").await else {
+        return;
+    };
+
+    let chunks: Vec<CodeChunk> = (0..4)
+        .map(|i| {
+            chunk(
+                &format!("src/f{i}.rs"),
+                &format!("fn f{i}() {{}}"),
+                "repo-page",
+            )
+        })
+        .collect();
+    let embeddings: Vec<Embedding> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            Embedding::new(
+                c.id().to_string(),
+                unit_vector(384, if i == 0 { 0 } else { 1 }),
+                "mock".to_string(),
+            )
+        })
+        .collect();
+    repo.save_batch(&chunks, &embeddings)
+        .await
+        .expect("save_batch");
+
+    let query_vec = unit_vector(384, 0);
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    loop {
+        let query = SearchQuery::new("f").with_limit(2).with_cursor(cursor);
+        let page = repo.search(Some(&query_vec), &query).await.expect("search");
+        seen.extend(page.iter().map(|r| r.chunk().id().to_string()));
+        match page.last() {
+            Some(last) if page.len() == 2 => cursor = Some(SearchCursor::after(last).encode()),
+            _ => break,
+        }
+    }
+
+    assert_eq!(seen.len(), 4, "pages overlapped or skipped: {seen:?}");
+    assert_eq!(seen[0], chunks[0].id());
+    let mut tied: Vec<&str> = chunks[1..].iter().map(|c| c.id()).collect();
+    tied.sort();
+    assert_eq!(&seen[1..], tied.as_slice());
+}
+
+#[tokio::test]
+async fn pgvector_repository_lookups_and_integrity() {
+    let Some(repo) = try_connect("cs_test_lookup").await else {
+        return;
+    };
+
+    let alpha = chunk("src/a.rs", "fn alpha() {}", "repo-lookup").with_symbol_name("alpha");
+    let alphabet =
+        chunk("src/b.rs", "fn alphabet() {}", "repo-lookup").with_symbol_name("alphabet");
+    let embedding = Embedding::new(
+        alpha.id().to_string(),
+        unit_vector(384, 0),
+        "mock".to_string(),
+    );
+    repo.save_batch(&[alpha.clone(), alphabet.clone()], &[embedding])
+        .await
+        .expect("save_batch");
+    // A chunks-only re-save keeps the stored embedding.
+    repo.save_batch(std::slice::from_ref(&alpha), &[])
+        .await
+        .expect("save_batch");
+
+    let hits = repo
+        .find_by_symbol(&SearchQuery::new("alpha"))
+        .await
+        .expect("find_by_symbol");
+    let ids: Vec<&str> = hits.iter().map(|r| r.chunk().id()).collect();
+    assert_eq!(ids, vec![alpha.id(), alphabet.id()]);
+    assert_eq!(hits[0].score(), 1.0);
+
+    let found = repo
+        .find_chunk_by_symbol("repo-lookup", "alphabet", None)
+        .await
+        .expect("find_chunk_by_symbol");
+    assert_eq!(
+        found.map(|c| c.id().to_string()),
+        Some(alphabet.id().to_string())
+    );
+    assert_eq!(
+        repo.find_chunks_by_file("", "src/a.rs")
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        repo.list_files("repo-lookup").await.expect("list_files")[0],
+        FileEntry {
+            file_path: "src/a.rs".to_string(),
+            chunk_count: 1,
+            language: Language::Rust,
+        }
+    );
+
+    let integrity = repo
+        .integrity()
+        .await
+        .expect("integrity")
+        .expect("postgres");
+    assert_eq!(integrity.chunk_count, 2);
+    assert_eq!(integrity.embedding_count, 1);
+    assert_eq!(integrity.chunks_without_embedding, 1);
+    assert_eq!(integrity.text_index, IndexState::Ok);
+    assert_eq!(integrity.vector_index, IndexState::Ok);
+    assert_eq!(integrity.chunks_per_repository.get("repo-lookup"), Some(&2));
+}
+
+#[tokio::test]
+async fn pgvector_repository_rejects_dimension_change() {
+    let Some(_repo) = try_connect("cs_test_dims").await else {
+        return;
+    };
+    let url = std::env::var(POSTGRES_URL_ENV).unwrap();
+    let err = PgVectorRepository::connect(&url, "cs_test_dims", 768)
+        .await
+        .err()
+        .expect("dimension mismatch must fail");
+    assert!(err.to_string().contains("384-dimensional"), "{err}");
+}
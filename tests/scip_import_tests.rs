@@ -0,0 +1,169 @@
+use std::path::Path;
+
+use codesearch::connector::adapter::scip::{ScipExporter, ScipImporter};
+use codesearch::{Language, ReferenceKind, SymbolReference};
+
+/// `tests/fixtures/scip/rust/index.scip` is a `rust-analyzer scip` index of the
+/// two source files next to it.
+const RUST_FIXTURE: &str = "tests/fixtures/scip/rust/index.scip";
+
+fn has_edge(refs: &[SymbolReference], caller: &str, callee: &str) -> bool {
+    refs.iter()
+        .any(|r| r.caller_symbol() == Some(caller) && r.callee_symbol() == callee)
+}
+
+#[tokio::test]
+async fn test_rust_analyzer_index_uses_tree_sitter_style_names() {
+    let by_file = ScipImporter::import(Path::new(RUST_FIXTURE), "geometry")
+        .await
+        .expect("fixture should import");
+
+    let lib = &by_file["src/lib.rs"];
+    // A call through the `Shape` trait lands on the implementing type.
+    assert!(has_edge(lib, "total_area", "Circle::area"));
+    assert!(has_edge(lib, "unit", "Circle::new"));
+    let new_call = lib
+        .iter()
+        .find(|r| r.callee_symbol() == "Circle::new")
+        .unwrap();
+    assert_eq!(new_call.reference_file_path(), "src/shapes.rs");
+    assert!(lib
+        .iter()
+        .any(|r| r.callee_symbol() == "Circle" && r.reference_kind() == ReferenceKind::Import));
+
+    let shapes = &by_file["src/shapes.rs"];
+    assert!(has_edge(shapes, "Circle::area", "Circle::radius"));
+    let area_read = shapes
+        .iter()
+        .find(|r| r.caller_symbol() == Some("Circle::area"))
+        .unwrap();
+    assert_eq!(area_read.enclosing_scope(), Some("Circle"));
+}
+
+fn reference(
+    file: &str,
+    caller: Option<&str>,
+    callee: &str,
+    line: u32,
+    column: u32,
+    kind: ReferenceKind,
+    language: Language,
+) -> SymbolReference {
+    SymbolReference::new(
+        caller.map(String::from),
+        callee.to_string(),
+        file.to_string(),
+        file.to_string(),
+        line,
+        column,
+        kind,
+        language,
+        "repo".to_string(),
+    )
+}
+
+/// The fields a SCIP index can carry, for comparing references.
+fn edge(
+    r: &SymbolReference,
+) -> (
+    Option<String>,
+    String,
+    String,
+    String,
+    u32,
+    u32,
+    ReferenceKind,
+    Option<String>,
+) {
+    (
+        r.caller_symbol().map(String::from),
+        r.callee_symbol().to_string(),
+        r.caller_file_path().to_string(),
+        r.reference_file_path().to_string(),
+        r.reference_line(),
+        r.reference_column(),
+        r.reference_kind(),
+        r.callee_package().map(String::from),
+    )
+}
+
+#[tokio::test]
+async fn test_exported_call_graph_round_trips_through_the_importer() {
+    let rust = |caller, callee, line, column, kind| {
+        reference(
+            "src/lib.rs",
+            caller,
+            callee,
+            line,
+            column,
+            kind,
+            Language::Rust,
+        )
+    };
+    let java = |caller, callee, line, column, kind| {
+        reference(
+            "src/App.java",
+            caller,
+            callee,
+            line,
+            column,
+            kind,
+            Language::Java,
+        )
+    };
+    let references = vec![
+        rust(None, "shapes::Circle", 1, 12, ReferenceKind::Import),
+        rust(
+            Some("total_area"),
+            "Vec",
+            3,
+            25,
+            ReferenceKind::TypeReference,
+        ),
+        rust(
+            Some("total_area"),
+            "Circle::area",
+            4,
+            22,
+            ReferenceKind::MethodCall,
+        ),
+        rust(Some("unit"), "Circle::new", 8, 5, ReferenceKind::Call),
+        java(
+            Some("App#main"),
+            "Greeter",
+            3,
+            25,
+            ReferenceKind::Instantiation,
+        ),
+        java(
+            Some("App#main"),
+            "Greeter#greet",
+            4,
+            9,
+            ReferenceKind::MethodCall,
+        )
+        .with_callee_package("greeter-lib"),
+        java(
+            Some("App#main"),
+            "count",
+            5,
+            9,
+            ReferenceKind::VariableReference,
+        ),
+    ];
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("index.scip");
+    ScipExporter::write(&references, "/src/app", &out)
+        .await
+        .expect("export should succeed");
+    let by_file = ScipImporter::import(&out, "repo")
+        .await
+        .expect("exported index should import");
+
+    let mut imported: Vec<_> = by_file.values().flatten().map(edge).collect();
+    let mut expected: Vec<_> = references.iter().map(edge).collect();
+    imported.sort_by_key(|e| (e.2.clone(), e.4, e.5));
+    expected.sort_by_key(|e| (e.2.clone(), e.4, e.5));
+    assert_eq!(imported, expected);
+}